@@ -0,0 +1,75 @@
+//!
+//! Cost of the runtime byte order ([`RuntimeEndian`]) compared to the static
+//! ones on a symbol table scan, run with `cargo bench --bench endianness`.
+//!
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use elfprobe_core::elf::{Elf64Symbol, Symbol};
+use elfprobe_core::endian::{BigEndian, Endianness, LittleEndian, RuntimeEndian};
+
+const SYMBOLS: usize = 100_000;
+const ROUNDS: u32 = 200;
+
+/// Builds a symbol table with distinct values and sizes.
+fn table<E: Endianness>() -> Vec<Elf64Symbol<E>> {
+  (0..SYMBOLS as u64)
+    .map(|index| {
+      let mut symbol = Elf64Symbol::<E>::default();
+      symbol.st_value.set(0x40_1000 + index * 16);
+      symbol.st_size.set(index % 64);
+      symbol.st_shndx.set(index as u16);
+      symbol
+    })
+    .collect()
+}
+
+/// Scans the table as tools do (addresses, sizes and section indexes).
+fn scan<E: Endianness>(symbols: &[Elf64Symbol<E>]) -> u64 {
+  symbols
+    .iter()
+    .map(|symbol| symbol.st_value() ^ symbol.st_size() ^ u64::from(symbol.st_shndx()))
+    .fold(0, u64::wrapping_add)
+}
+
+/// Returns the best time of a scan over the rounds.
+fn measure<E: Endianness>() -> Duration {
+  let symbols = table::<E>();
+  let expected = scan(&symbols);
+
+  (0..ROUNDS)
+    .map(|_| {
+      let start = Instant::now();
+      assert_eq!(scan(black_box(&symbols)), expected);
+      start.elapsed()
+    })
+    .min()
+    .unwrap_or_default()
+}
+
+fn main() {
+  let results = [
+    ("LittleEndian", measure::<LittleEndian>()),
+    ("BigEndian", measure::<BigEndian>()),
+    (
+      "RuntimeEndian (LE)",
+      RuntimeEndian::with(true, measure::<RuntimeEndian>),
+    ),
+    (
+      "RuntimeEndian (BE)",
+      RuntimeEndian::with(false, measure::<RuntimeEndian>),
+    ),
+  ];
+
+  let baseline = results[0].1.as_secs_f64();
+  for (name, duration) in results {
+    println!(
+      "{:<20} {:>10.2?} per scan, {:>6.2} ns per symbol, x{:.2}",
+      name,
+      duration,
+      duration.as_nanos() as f64 / SYMBOLS as f64,
+      duration.as_secs_f64() / baseline,
+    );
+  }
+}
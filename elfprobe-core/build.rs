@@ -0,0 +1,34 @@
+//!
+//! Records what `--version` shows that cargo does not give to the crate:
+//! the target triple and `git describe` of the checkout, left out when the
+//! sources are not a git repository (crate tarball) or git is missing.
+//!
+
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+  println!(
+    "cargo:rustc-env=ELFPROBE_TARGET={}",
+    env::var("TARGET").unwrap_or_default()
+  );
+
+  let describe = Command::new("git")
+    .args(["describe", "--always", "--dirty", "--tags"])
+    .output()
+    .ok()
+    .filter(|output| output.status.success())
+    .and_then(|output| String::from_utf8(output.stdout).ok());
+  if let Some(describe) = describe {
+    println!("cargo:rustc-env=ELFPROBE_GIT_DESCRIBE={}", describe.trim());
+  }
+
+  // A path that does not exist would rerun the script on every build.
+  for path in ["../.git/HEAD", "../.git/index", "../.git/refs"] {
+    if Path::new(path).exists() {
+      println!("cargo:rerun-if-changed={}", path);
+    }
+  }
+  println!("cargo:rerun-if-changed=build.rs");
+}
@@ -0,0 +1,72 @@
+//!
+//! Analysis of untrusted files as a service would run it: hard ceilings on
+//! the size, the tables, the nesting and the time spent (see [`Limits`]),
+//! the diagnostics of the file are printed with their stable code. Run with
+//! `cargo run --example service -- <file>`.
+//!
+
+use std::time::Duration;
+use std::{env, fs, process};
+
+use elfprobe_core::elf::{parse_elf_with, ElfFile, ElfObject, ElfType, Limits, ParseOptions, SHT_SYMTAB};
+use elfprobe_core::error::ElfError;
+use elfprobe_core::reader::Reader;
+
+/// Uploads up to 64 MiB, 64 Ki entries per table, no archive in an archive, 2 seconds.
+const OPTIONS: ParseOptions = ParseOptions {
+  limits: Limits {
+    max_bytes: Some(64 << 20),
+    max_entries: Some(1 << 16),
+    max_depth: Some(1),
+    time_budget: Some(Duration::from_secs(2)),
+  },
+  ..ParseOptions::LENIENT
+};
+
+/// Returns the number of sections, segments and symbols then the diagnostics.
+fn analyze<'data, R: Reader<'data>, T: ElfType>(object: &ElfObject<'data, R, T>) -> Result<String, ElfError> {
+  let sections = object.section_headers()?.len();
+  object.check_time_budget()?;
+  let segments = object.program_headers()?.len();
+  object.check_time_budget()?;
+  let symbols = object
+    .symbol_table(SHT_SYMTAB)?
+    .map_or(0, |table| table.symbols.len());
+
+  let mut report = format!(
+    "Sections: {}\nSegments: {}\nSymbols: {}\n",
+    sections, segments, symbols
+  );
+  for diagnostic in object.findings() {
+    report += &format!(
+      "{} [{}]: {}\n",
+      diagnostic.severity, diagnostic.code, diagnostic.message
+    );
+  }
+  Ok(report)
+}
+
+fn main() {
+  let Some(path) = env::args_os().nth(1) else {
+    eprintln!("Usage: service <file>");
+    process::exit(2);
+  };
+  let bytes = fs::read(&path).unwrap_or_else(|error| {
+    eprintln!("{}: {}", path.to_string_lossy(), error);
+    process::exit(1);
+  });
+
+  let report = parse_elf_with(bytes.as_slice(), OPTIONS).and_then(|elf| match elf {
+    ElfFile::Elf32Be(object) => analyze(&object),
+    ElfFile::Elf64Be(object) => analyze(&object),
+    ElfFile::Elf32Le(object) => analyze(&object),
+    ElfFile::Elf64Le(object) => analyze(&object),
+  });
+  match report {
+    Ok(report) => print!("{}", report),
+    Err(error) => {
+      eprintln!("{}: {}", path.to_string_lossy(), error);
+      process::exit(1);
+    }
+  }
+}
@@ -0,0 +1,47 @@
+//!
+//! Summary of an ELF file parsed from bytes, without files or memory mapping
+//! so that it builds for `wasm32-unknown-unknown` (call [`parse_summary()`]
+//! from the host, with the bytes it read). Run with
+//! `cargo run --example summary -- <file>`.
+//!
+
+use std::{env, fs, process};
+
+use elfprobe_core::prelude::*;
+
+///
+/// Returns the header (one field per line) and the number of sections,
+/// segments and symbols of the ELF file, or the error.
+///
+pub fn parse_summary(bytes: &[u8]) -> String {
+  let view = match parse_elf_view(bytes) {
+    Ok(view) => view,
+    Err(error) => return format!("Invalid ELF file: {}\n", error),
+  };
+
+  let mut summary = view.header_text();
+  if !summary.ends_with('\n') {
+    summary.push('\n');
+  }
+  summary += &format!(
+    "Sections: {}\nSegments: {}\nSymbols: {}\n",
+    view.sections().count(),
+    view.segments().count(),
+    view.symbols().count()
+  );
+  summary
+}
+
+fn main() {
+  let Some(path) = env::args_os().nth(1) else {
+    eprintln!("Usage: summary <file>");
+    process::exit(2);
+  };
+  match fs::read(&path) {
+    Ok(bytes) => print!("{}", parse_summary(&bytes)),
+    Err(error) => {
+      eprintln!("{}: {}", path.to_string_lossy(), error);
+      process::exit(1);
+    }
+  }
+}
@@ -0,0 +1,402 @@
+use std::borrow::Cow;
+use std::ffi::OsStr;
+use std::mem::size_of;
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+
+use crate::error::ElfError;
+use crate::reader::Reader;
+use elfprobe_macro::Pod;
+
+// https://man7.org/linux/man-pages/man5/ar.5.html (BSD)
+// https://sourceware.org/binutils/docs/binutils/ar.html
+// https://github.com/bminor/binutils-gdb/blob/master/include/aout/ar.h
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+/// Magic string at the start of an archive.
+pub const ARMAG: &[u8; 8] = b"!<arch>\n";
+
+/// Magic string at the start of a GNU thin archive.
+pub const ARMAG_THIN: &[u8; 8] = b"!<thin>\n";
+
+/// Magic string at the end of every member header.
+pub const ARFMAG: &[u8; 2] = b"`\n";
+
+/// Prefix of BSD long names (`#1/<length>`), the name precedes the data.
+const AR_BSD_NAME: &[u8] = b"#1/";
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+///
+/// Member header, every field is ASCII padded with spaces (decimal numbers
+/// but for the octal `ar_mode`). The member data follows the header and is
+/// padded to an even offset.
+///
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Pod)]
+pub struct ArHeader {
+  pub ar_name: [u8; 16],
+  pub ar_date: [u8; 12],
+  pub ar_uid: [u8; 6],
+  pub ar_gid: [u8; 6],
+  pub ar_mode: [u8; 8],
+  pub ar_size: [u8; 10],
+  pub ar_fmag: [u8; 2],
+}
+
+impl ArHeader {
+  /// Returns the name field without its padding.
+  pub fn name(&self) -> &[u8] {
+    trim_end(&self.ar_name, b' ')
+  }
+
+  /// Returns the size of the member data, `None` if it is not a number.
+  pub fn size(&self) -> Option<usize> {
+    decimal(&self.ar_size)
+  }
+}
+
+/// A member of an [`Archive`], see [`Archive::members()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ArchiveMember<'data> {
+  /// Offset of the member header in the archive.
+  pub offset: usize,
+  /// File name, long GNU and BSD names resolved.
+  pub name: &'data [u8],
+  /// File data, always empty in a thin archive (see [`Self::path()`]).
+  pub data: &'data [u8],
+}
+
+impl<'data> ArchiveMember<'data> {
+  /// Returns the printable name, see [`printable()`](crate::elf::printable()).
+  pub fn name(&self) -> Cow<'data, str> {
+    crate::elf::printable(self.name)
+  }
+
+  ///
+  /// Returns the path of the file referenced by a thin archive member, the
+  /// name is relative to the directory of the archive (unless absolute).
+  ///
+  pub fn path(&self, directory: &Path) -> PathBuf {
+    directory.join(OsStr::from_bytes(self.name))
+  }
+}
+
+///
+/// A static library (`ar` archive), a sequence of members each made of an
+/// [`ArHeader`] and the file data. Special members are not files:
+///
+/// - GNU: `/` (or `/SYM64/`) is the symbol index and `//` holds the names
+///   longer than 15 bytes, referenced as `/<offset>`.
+/// - BSD: long names (or names with spaces) are stored at the start of the
+///   data as `#1/<length>`, the symbol index is the `__.SYMDEF` member.
+///
+/// GNU thin archives (`!<thin>\n`) only embed the special members, the
+/// other ones are references to files (see [`ArchiveMember::path()`]).
+///
+#[derive(Debug, Copy, Clone)]
+pub struct Archive<'data> {
+  data: &'data [u8],
+  thin: bool,
+  /// GNU long names table (`//`), empty if there is none.
+  names: &'data [u8],
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data> Archive<'data> {
+  ///
+  /// Returns whether the data starts with the magic of an archive
+  /// (`!<arch>\n`) or of a thin archive (`!<thin>\n`).
+  ///
+  pub fn is_archive(data: &[u8]) -> bool {
+    data.starts_with(ARMAG) || data.starts_with(ARMAG_THIN)
+  }
+
+  /// Returns whether members are references to files.
+  #[inline]
+  pub fn is_thin(&self) -> bool {
+    self.thin
+  }
+
+  /// Checks the magic and locates the GNU long names table.
+  pub fn parse(data: &'data [u8]) -> Result<Self, ElfError> {
+    if !Self::is_archive(data) {
+      return Err(ElfError::InvalidArchive {
+        offset: 0,
+        reason: "bad magic (expected !<arch> or !<thin>)",
+      });
+    }
+
+    let thin = data.starts_with(ARMAG_THIN);
+    let mut archive = Self {
+      data,
+      thin,
+      names: &[],
+    };
+    for entry in archive.entries() {
+      let (header, _, bytes) = entry?;
+      if header.name() == b"//" {
+        archive.names = bytes;
+        break;
+      }
+    }
+
+    Ok(archive)
+  }
+
+  ///
+  /// Returns an iterator over the file members (special members skipped),
+  /// the iteration stops after the first malformed header.
+  ///
+  pub fn members(&self) -> impl Iterator<Item = Result<ArchiveMember<'data>, ElfError>> + '_ {
+    self.entries().filter_map(move |entry| {
+      let (header, offset, data) = match entry {
+        Ok(entry) => entry,
+        Err(error) => return Some(Err(error)),
+      };
+
+      let error = |reason| ElfError::InvalidArchive { offset, reason };
+      let name = header.name();
+      let member = |name, data| Some(Ok(ArchiveMember { offset, name, data }));
+
+      match name {
+        _ if is_special(name) => None,
+
+        _ if name.starts_with(AR_BSD_NAME) => {
+          let Some(length) = decimal(&name[AR_BSD_NAME.len()..]).filter(|&length| length <= data.len())
+          else {
+            return Some(Err(error("invalid BSD name length")));
+          };
+
+          let (name, data) = data.split_at(length);
+          member(trim_end(name, 0), data)
+        }
+
+        [b'/', digits @ ..] => {
+          let Some(name) = decimal(digits).and_then(|offset| self.long_name(offset)) else {
+            return Some(Err(error("invalid GNU name offset")));
+          };
+
+          member(name, data)
+        }
+
+        _ => member(name.strip_suffix(b"/").unwrap_or(name), data),
+      }
+    })
+  }
+
+  /// Returns the GNU long name at the given offset (terminated by `/\n`).
+  fn long_name(&self, offset: usize) -> Option<&'data [u8]> {
+    let bytes = self.names.get(offset..)?;
+    let end = bytes.iter().position(|&byte| byte == b'\n')?;
+    let name = &bytes[..end];
+    Some(name.strip_suffix(b"/").unwrap_or(name))
+  }
+
+  /// Returns every member as is: header, offset of the header and data.
+  fn entries(&self) -> impl Iterator<Item = Result<(&'data ArHeader, usize, &'data [u8]), ElfError>> {
+    let (data, thin) = (self.data, self.thin);
+    let mut offset = Some(ARMAG.len());
+
+    std::iter::from_fn(move || {
+      let current = offset.take().filter(|&offset| offset < data.len())?;
+      let error = |reason| {
+        Some(Err(ElfError::InvalidArchive {
+          offset: current,
+          reason,
+        }))
+      };
+
+      let Ok(header) = data.read_pod::<ArHeader>(current) else {
+        return error("truncated member header");
+      };
+
+      if &header.ar_fmag != ARFMAG {
+        return error("bad member header magic");
+      }
+
+      // The size of a thin member is the one of the referenced file.
+      let start = current + size_of::<ArHeader>();
+      let size = header
+        .size()
+        .map(|size| match thin && !is_special(header.name()) {
+          true => 0,
+          false => size,
+        });
+
+      let Some(bytes) = size.and_then(|size| data.read_bytes(size, start)) else {
+        return error("member data out of the archive");
+      };
+
+      // Members are 2-byte aligned.
+      let end = start + bytes.len();
+      offset = Some(end + end % 2);
+      Some(Ok((header, current, bytes)))
+    })
+  }
+}
+
+/// Returns whether the member is the symbol index or the long names table.
+fn is_special(name: &[u8]) -> bool {
+  matches!(name, b"/" | b"/SYM64/" | b"//")
+}
+
+/// Returns the bytes without the given trailing padding.
+fn trim_end(bytes: &[u8], padding: u8) -> &[u8] {
+  let end = bytes
+    .iter()
+    .rposition(|&byte| byte != padding)
+    .map_or(0, |end| end + 1);
+  &bytes[..end]
+}
+
+/// Parses a space-padded decimal ASCII number.
+fn decimal(bytes: &[u8]) -> Option<usize> {
+  std::str::from_utf8(trim_end(bytes, b' ')).ok()?.parse().ok()
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Returns a member (header, data and padding).
+  fn member(name: &str, data: &[u8]) -> Vec<u8> {
+    let header = format!(
+      "{:<16}{:<12}{:<6}{:<6}{:<8}{:<10}`\n",
+      name,
+      0,
+      0,
+      0,
+      644,
+      data.len()
+    );
+    let mut bytes = [header.as_bytes(), data].concat();
+    if bytes.len() % 2 == 1 {
+      bytes.push(b'\n');
+    }
+    bytes
+  }
+
+  fn names(archive: &Archive) -> Vec<String> {
+    archive
+      .members()
+      .map(|member| member.unwrap().name().into_owned())
+      .collect()
+  }
+
+  #[test]
+  fn header_memory_size() {
+    assert_eq!(size_of::<ArHeader>(), 60);
+  }
+
+  #[test]
+  fn gnu() {
+    let bytes = [
+      &ARMAG[..],
+      &member("/", b"\0\0\0\0"),
+      &member("//", b"a_very_long_file_name.o/\n"),
+      &member("short.o/", b"\x7fELF!"),
+      &member("/0", b"data"),
+    ]
+    .concat();
+
+    let archive = Archive::parse(&bytes).unwrap();
+    assert_eq!(names(&archive), ["short.o", "a_very_long_file_name.o"]);
+
+    let member = archive.members().next().unwrap().unwrap();
+    assert_eq!(member.data, b"\x7fELF!", "Without padding");
+    assert_eq!(member.offset, 8 + 64 + 60 + 26);
+  }
+
+  #[test]
+  fn bsd() {
+    let bytes = [
+      &ARMAG[..],
+      &member("#1/20", b"__.SYMDEF SORTED\0\0\0\0"),
+      &member("#1/28", b"a_very_long_file_name.o\0\0\0\0\0ELF"),
+      &member("short.o", b""),
+    ]
+    .concat();
+
+    let archive = Archive::parse(&bytes).unwrap();
+    assert_eq!(
+      names(&archive),
+      ["__.SYMDEF SORTED", "a_very_long_file_name.o", "short.o"]
+    );
+    assert_eq!(archive.members().nth(1).unwrap().unwrap().data, b"ELF");
+  }
+
+  #[test]
+  fn malformed() {
+    assert!(Archive::parse(b"!<arch\n").is_err());
+    assert_eq!(Archive::parse(ARMAG).unwrap().members().count(), 0);
+
+    let mut bytes = [&ARMAG[..], &member("a.o/", b"ab"), &member("/9", b"")].concat();
+    let archive = Archive::parse(&bytes).unwrap();
+    let members = archive.members().collect::<Vec<_>>();
+    assert_eq!(
+      members[1],
+      Err(ElfError::InvalidArchive {
+        offset: 70,
+        reason: "invalid GNU name offset"
+      })
+    );
+
+    bytes[8 + 48..8 + 51].copy_from_slice(b"999"); // ar_size of a.o
+    let archive = Archive::parse(&bytes);
+    assert_eq!(
+      archive.unwrap_err(),
+      ElfError::InvalidArchive {
+        offset: 8,
+        reason: "member data out of the archive"
+      }
+    );
+  }
+
+  #[test]
+  fn thin() {
+    let mut bytes = [
+      &ARMAG_THIN[..],
+      &member("/", b"\0\0\0\0"),
+      &member("//", b"objects/a_very_long_file_name.o/\n/usr/lib/b.o/\n"),
+    ]
+    .concat();
+
+    // Headers only, sizes are the ones of the referenced files.
+    let header = |name: &str, size: usize| member(name, &vec![0; size])[..60].to_vec();
+    bytes.extend(header("/0", 1234));
+    bytes.extend(header("/33", 77));
+
+    let archive = Archive::parse(&bytes).unwrap();
+    assert!(archive.is_thin());
+    assert_eq!(
+      names(&archive),
+      ["objects/a_very_long_file_name.o", "/usr/lib/b.o"]
+    );
+
+    let paths = archive
+      .members()
+      .map(|member| member.unwrap().path(Path::new("/build/lib")))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      paths,
+      [
+        Path::new("/build/lib/objects/a_very_long_file_name.o"),
+        Path::new("/usr/lib/b.o")
+      ]
+    );
+    assert!(archive.members().all(|member| member.unwrap().data.is_empty()));
+  }
+}
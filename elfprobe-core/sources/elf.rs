@@ -5,15 +5,118 @@
 // https://rust-lang.github.io/rfcs/1210-impl-specialization.html#the-default-keyword
 // https://users.rust-lang.org/t/whats-default-fn/105388/6
 
-use std::fmt::Debug;
-use std::fmt::Display;
-use std::marker::PhantomData;
+use ::core::fmt::Debug;
+use ::core::fmt::Display;
+use ::core::marker::PhantomData;
+// The caches are shared by the threads with `std`, `core` has no lock.
+#[cfg(not(feature = "std"))]
+use ::core::cell::OnceCell as OnceLock;
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
 
+use crate::alloc_prelude::*;
 use crate::endian::{BigEndian, Endianness, LittleEndian};
-use crate::error::BytesError;
+use crate::error::{BytesError, ElfError};
 use crate::pod::Pod;
 use crate::primitive::{I16, I32, I64, U16, U32, U64};
-use elfprobe_macro::Pod;
+use crate::utils;
+use elfprobe_macro::{FieldLayout, Pod};
+
+mod abi;
+mod annotate;
+mod attributes;
+mod builder;
+mod comment;
+mod compression;
+mod core;
+mod coverage;
+mod cross_check;
+mod debug;
+mod diagnostics;
+mod diff;
+mod digests;
+mod dynamic;
+mod eh_frame;
+mod entry;
+mod flags;
+mod go;
+mod init;
+mod layout;
+mod mips;
+mod modinfo;
+mod notes;
+mod offsets;
+#[cfg(all(feature = "std", unix))]
+mod owned;
+mod patch;
+mod properties;
+mod relocation_types;
+mod relocations;
+mod rust;
+mod script;
+mod sections;
+mod security;
+mod segments;
+mod strings;
+mod symbols;
+mod tls;
+mod validation;
+mod view;
+
+pub use abi::e_type::ET_CORE;
+pub use abi::{object_type, p_flags, p_type, section_type, segment_type, sh_flags, sh_type};
+pub use annotate::{Annotated, AnnotatedField};
+pub use attributes::{
+  Attribute, AttributeSubsection, AttributeValue, RiscvIsa, SHT_ARM_ATTRIBUTES, SHT_RISCV_ATTRIBUTES,
+};
+pub use builder::{BuilderSection, ElfBuilder};
+pub use compression::{
+  CompressionHeader, Elf32CompressionHeader, Elf64CompressionHeader, ElfCompressionHeader,
+};
+pub use core::{CoreNote, FileTable, MappedFile, PrPsInfo, PrStatus, Register};
+pub use coverage::{CoverageGap, CoveredSection, SymbolCoverage, COVERAGE_GAPS};
+pub use cross_check::{CrossCheck, Divergence, DivergenceKind};
+#[cfg(feature = "xz")]
+pub use debug::DEBUGDATA_LIMIT;
+pub use debug::{debug_totals, DebugCompression, DebugSection, DebugTotal};
+pub use diagnostics::{Diagnostic, DiagnosticCode, Diagnostics, Limit, Limits, ParseOptions};
+pub use diff::{Change, ContentChange, ElfDiff, Ignored, CONTENT_CHANGES};
+pub use digests::{Digests, SectionDigest};
+pub use dynamic::{d_tag, df, df_1, dynamic_tag, flag_names, ElfDynamic};
+pub use eh_frame::{parse_eh_frame_hdr, read_encoded, EhFrameEntry, EhFrameHdr};
+pub use entry::EntryPoint;
+pub use flags::MachineFlags;
+pub use go::{GoBuildInfo, GoModule, GO_BUILDINFO_MAGIC};
+pub use init::{InitArray, InitEntry, SHT_FINI_ARRAY, SHT_INIT_ARRAY, SHT_PREINIT_ARRAY};
+pub use layout::{Gap, GapContents, Layout, Region};
+pub use mips::{ElfMipsAbiFlags, MipsAbi, SHT_MIPS_ABIFLAGS};
+pub use modinfo::ModInfo;
+pub use notes::{note_type_name, nt_core, nt_freebsd, nt_gnu, nt_linux, ElfNote, ElfNoteHeader};
+pub use offsets::{HeaderOffsets, ELF32_HEADER, ELF64_HEADER};
+pub use offsets::{EI_ABIVERSION, EI_CLASS, EI_DATA, EI_MAG0, EI_NIDENT, EI_OSABI, EI_PAD, EI_VERSION};
+#[cfg(all(feature = "std", unix))]
+pub use owned::OwnedElfFile;
+pub use properties::{parse_properties, GnuProperty, NT_GNU_PROPERTY_TYPE_0};
+pub use relocation_types::{r_386, r_aarch64, r_arm, r_riscv, r_x86_64, relocation_name};
+pub use relocations::{
+  plt_relocation_name, r_info, relative_type, relocation_kind, ElfRel, ElfRela, PltRelocation, Relocation,
+  RelocationKind, RelocationStats,
+};
+pub use script::{is_linker_script, linker_script_input};
+pub use sections::{ElfSection, SectionRef};
+pub use security::{
+  Linkage, RelroLevel, SecurityReport, Status, TextRelocation, TextRelocations, TEXTREL_SAMPLES,
+};
+pub use segments::{Elf32ProgramHeader, Elf64ProgramHeader, ProgramHeader, ZeroFill, PN_XNUM};
+pub use strings::{escape_controls, printable, StringTable};
+pub use symbols::{
+  st_bind, st_type, st_visibility, Elf32Symbol, Elf64Symbol, SourceFiles, StripStatus, Symbol, SymbolFilter,
+  SymbolInfo, SymbolListing, SymbolOrder, SymbolRef, SymbolSelection, SymbolTable, SHT_DYNSYM, SHT_SYMTAB,
+  STT_TLS,
+};
+pub use tls::{TlsLayout, TlsSection, TlsSegment, TlsSymbol};
+pub use validation::{Severity, ValidationIssue, ELFMAG};
+pub use view::{parse_elf_view, ElfView, HeaderEntry, SectionEntry, SegmentEntry, SymbolEntry};
 
 // ╔═╗┬  ┬┌─┐┌─┐┌─┐┌─┐
 // ╠═╣│  │├─┤└─┐├┤ └─┐
@@ -50,22 +153,51 @@ pub trait ElfType: Type {
   type Endian: self::Endianness;
 
   /// Unsigned program address
-  type Addr: Type;
+  type Addr: Type + Into<u64>;
 
   /// Unsigned medium integer
-  type Half: Type;
+  type Half: Type + Into<u64>;
 
   /// Unsigned file offset
-  type Off: Type;
+  type Off: Type + Into<u64>;
 
   /// Signed large integer
-  type Sword: Type;
+  type Sword: Type + Into<i64>;
 
   /// Unsigned small integer
-  type Uchar: Type;
+  type Uchar: Type + Into<u64>;
 
   /// Unsigned large integer
-  type Word: Type;
+  type Word: Type + Into<u64>;
+
+  /// Unsigned extra large integer (same as `Word` in elf32)
+  type Xword: Type + Into<u64>;
+
+  /// Signed extra large integer (same as `Sword` in elf32)
+  type Sxword: Type + Into<i64>;
+
+  /// Program header (fields are not in the same order in elf32 and elf64)
+  type ProgramHeader: ProgramHeader;
+  type CompressionHeader: CompressionHeader;
+
+  /// Symbol (fields are not in the same order in elf32 and elf64)
+  type Symbol: Symbol;
+
+  /// File class (`ei_class`) of this type.
+  const CLASS: u8;
+
+  /// Returns the data encoding (`ei_data`) of this type.
+  fn data() -> u8 {
+    match Self::Endian::is_little_endian() {
+      true => abi::ei_data::ELFDATA2LSB,
+      false => abi::ei_data::ELFDATA2MSB,
+    }
+  }
+
+  /// Returns the address display, as wide as an address of this type.
+  fn address(value: u64) -> utils::Addr {
+    utils::Addr(value, ::core::mem::size_of::<Self::Addr>() * 2)
+  }
 }
 
 #[derive(Debug, Default, Copy, Clone, Pod)]
@@ -78,6 +210,12 @@ impl<E: self::Endianness> ElfType for ElfType32<E> {
   type Sword = Elf32_Sword<E>;
   type Uchar = u8; // Unsigned C char
   type Word = Elf32_Word<E>;
+  type Xword = Elf32_Word<E>;
+  type Sxword = Elf32_Sword<E>;
+  type ProgramHeader = Elf32ProgramHeader<E>;
+  type CompressionHeader = Elf32CompressionHeader<E>;
+  type Symbol = Elf32Symbol<E>;
+  const CLASS: u8 = abi::ei_class::ELFCLASS32;
 }
 
 #[derive(Debug, Default, Copy, Clone, Pod)]
@@ -90,6 +228,12 @@ impl<E: self::Endianness> ElfType for ElfType64<E> {
   type Sword = Elf64_Sword<E>;
   type Uchar = u8; // Unsigned C char
   type Word = Elf64_Word<E>;
+  type Xword = Elf64_Xword<E>;
+  type Sxword = Elf64_Sxword<E>;
+  type ProgramHeader = Elf64ProgramHeader<E>;
+  type CompressionHeader = Elf64CompressionHeader<E>;
+  type Symbol = Elf64Symbol<E>;
+  const CLASS: u8 = abi::ei_class::ELFCLASS64;
 }
 
 // ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
@@ -97,7 +241,8 @@ impl<E: self::Endianness> ElfType for ElfType64<E> {
 // ╚═╝ ┴ ┴└─└─┘└─┘ ┴
 
 #[repr(C)]
-#[derive(Debug, Default, Copy, Clone, Pod)]
+#[derive(Debug, Default, Copy, Clone, Pod, FieldLayout)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ElfIdentification<ElfType: self::ElfType> {
   pub ei_mag0: ElfType::Uchar,
   pub ei_mag1: ElfType::Uchar,
@@ -114,7 +259,7 @@ pub struct ElfIdentification<ElfType: self::ElfType> {
 #[test]
 fn test_elf_identification_memory_size() {
   use crate::endian::{BigEndian, LittleEndian};
-  use std::mem::size_of;
+  use ::core::mem::size_of;
 
   type ElfIdentification32<Endianness> = ElfIdentification<ElfType32<Endianness>>;
   type ElfIdentification64<Endianness> = ElfIdentification<ElfType64<Endianness>>;
@@ -126,7 +271,13 @@ fn test_elf_identification_memory_size() {
 }
 
 #[repr(C)]
-#[derive(Debug, Default, Copy, Clone, Pod)]
+#[derive(Debug, Default, Copy, Clone, Pod, FieldLayout)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(
+  feature = "serde",
+  serde(bound = "ElfType::Uchar: serde::Serialize, ElfType::Half: serde::Serialize, \
+    ElfType::Word: serde::Serialize, ElfType::Addr: serde::Serialize, ElfType::Off: serde::Serialize")
+)]
 pub struct ElfHeader<ElfType: self::ElfType> {
   pub e_ident: ElfIdentification<ElfType>,
   pub e_type: ElfType::Half,
@@ -144,6 +295,200 @@ pub struct ElfHeader<ElfType: self::ElfType> {
   pub e_shstrndx: ElfType::Half,
 }
 
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+///
+/// Returns the meaning of a constant, or the constant itself when unknown.
+/// The machine-readable form is the name and the value (`ET_DYN(0x3)`).
+///
+fn describe<Type>(constant: utils::Constant<Type>, machine: bool) -> String
+where
+  utils::Constant<Type>: Display,
+  Type: Copy,
+{
+  match machine {
+    true => format!("{:#}", constant),
+    false => constant
+      .meaning()
+      .map_or_else(|| constant.to_string(), String::from),
+  }
+}
+
+///
+/// Displays an ELF header, position-independent executables are told apart
+/// from shared objects (see [`ElfObject::display_header()`]).
+///
+/// The alternate form (`{:#}`) displays the constants in their
+/// machine-readable form (`ET_DYN(0x3)`) instead of their meaning, for
+/// templated and JSON output.
+///
+pub struct HeaderDisplay<'header, ElfType: self::ElfType> {
+  header: &'header ElfHeader<ElfType>,
+  is_pie: bool,
+  /// First section header, resolves the extended counts and index.
+  initial_section: Option<&'header ElfSection<ElfType>>,
+}
+
+impl<ElfType: self::ElfType> Display for ElfHeader<ElfType> {
+  fn fmt(&self, formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+    let display = HeaderDisplay {
+      header: self,
+      is_pie: false,
+      initial_section: None,
+    };
+
+    display.fmt(formatter)
+  }
+}
+
+impl<ElfType: self::ElfType> Display for HeaderDisplay<'_, ElfType> {
+  fn fmt(&self, formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+    use crate::utils::{display_table, FileOffset};
+
+    let header = self.header;
+    let ident = &header.e_ident;
+    let magic = [
+      ident.ei_mag0,
+      ident.ei_mag1,
+      ident.ei_mag2,
+      ident.ei_mag3,
+      ident.ei_class,
+      ident.ei_data,
+      ident.ei_version,
+      ident.ei_osabi,
+      ident.ei_abiversion,
+    ]
+    .into_iter()
+    .chain(ident.ei_pad)
+    .map(|byte| format!("{:02x}", byte.into()))
+    .collect::<Vec<_>>()
+    .join(" ");
+
+    let uchar = |uchar: ElfType::Uchar| -> u8 { uchar.into() as u8 };
+    let e_type: u64 = header.e_type.into();
+    let e_machine: u64 = header.e_machine.into();
+    let e_version: u64 = header.e_version.into();
+    let e_entry: u64 = header.e_entry.into();
+    let e_phoff: u64 = header.e_phoff.into();
+    let e_shoff: u64 = header.e_shoff.into();
+    let e_flags: u64 = header.e_flags.into();
+    let e_phnum: u64 = header.e_phnum.into();
+    let e_shnum: u64 = header.e_shnum.into();
+    let e_shstrndx: u64 = header.e_shstrndx.into();
+    let bytes = |size: ElfType::Half| format!("{} (bytes)", size.into());
+
+    let is_64 = uchar(ident.ei_class) == abi::ei_class::ELFCLASS64;
+    let flags = MachineFlags::new(e_machine as u16, e_flags as u32, is_64);
+
+    // As readelf: the name without prefix followed by the meaning, and the
+    // raw value of the OS and processor-specific types.
+    let machine = formatter.alternate();
+    let osabi = uchar(ident.ei_osabi);
+    let file_type = match abi::object_type(e_machine as u16, osabi, e_type as u16) {
+      constant if machine => format!("{:#}", constant),
+      _ if self.is_pie => String::from("DYN (Position-Independent Executable file)"),
+      constant => match constant.meaning() {
+        Some(meaning) => {
+          let name = constant.to_string();
+          let name = name.strip_prefix("ET_").unwrap_or(&name);
+          match e_type >= abi::e_type::ET_LOOS as u64 {
+            true => format!("{} ({}, {:#x})", name, meaning, e_type),
+            false => format!("{} ({})", name, meaning),
+          }
+        }
+        None => constant.to_string(),
+      },
+    };
+
+    // Values that do not fit in the header are stored in the first section,
+    // as readelf the actual value follows the raw one.
+    let initial = self.initial_section;
+    let extended = |field: &str, value: Option<u64>| match value {
+      Some(value) => format!("({})", value),
+      None => format!("(see {} of section 0)", field),
+    };
+
+    let phnum = match e_phnum == PN_XNUM as u64 {
+      true => format!(
+        "{} {}",
+        e_phnum,
+        extended("sh_info", initial.map(|section| section.sh_info()))
+      ),
+      false => e_phnum.to_string(),
+    };
+
+    let shnum = match e_shnum == 0 && e_shoff != 0 {
+      true => format!(
+        "{} {}",
+        e_shnum,
+        extended("sh_size", initial.map(|section| section.sh_size()))
+      ),
+      false => e_shnum.to_string(),
+    };
+
+    // The name or meaning of the unexpected versions, as `EV_NONE` is invalid.
+    let version = |raw: String, value: u64| match abi::e_version::from(value as u32) {
+      constant if machine => format!("{:#}", constant),
+      constant if constant.value() == abi::e_version::EV_CURRENT => raw,
+      constant => format!(
+        "{} ({}, EV_CURRENT is {})",
+        raw,
+        constant.meaning().unwrap_or("unexpected"),
+        abi::e_version::EV_CURRENT
+      ),
+    };
+    let ei_version = uchar(ident.ei_version) as u64;
+
+    let shstrndx = match e_shstrndx == sections::SHN_XINDEX as u64 {
+      true => format!(
+        "{} {}",
+        e_shstrndx,
+        extended("sh_link", initial.map(|section| section.sh_link()))
+      ),
+      false => e_shstrndx.to_string(),
+    };
+
+    display_table!(
+      formatter,
+      [
+        ["Magic:", magic],
+        [
+          "Class:",
+          describe(abi::ei_class::from(uchar(ident.ei_class)), machine)
+        ],
+        [
+          "Data:",
+          describe(abi::ei_data::from(uchar(ident.ei_data)), machine)
+        ],
+        ["Version:", version(ei_version.to_string(), ei_version)],
+        [
+          "OS/ABI:",
+          describe(abi::ei_osabi::from(uchar(ident.ei_osabi)), machine)
+        ],
+        ["ABI Version:", uchar(ident.ei_abiversion)],
+        ["Type:", file_type],
+        [
+          "Machine:",
+          describe(abi::e_machine::from(e_machine as u16), machine)
+        ],
+        ["Version:", version(format!("{:#x}", e_version), e_version)],
+        ["Entry point address:", ElfType::address(e_entry)],
+        ["Start of program headers:", FileOffset(e_phoff)],
+        ["Start of section headers:", FileOffset(e_shoff)],
+        ["Flags:", flags],
+        ["Size of this header:", bytes(header.e_ehsize)],
+        ["Size of program headers:", bytes(header.e_phentsize)],
+        ["Number of program headers:", phnum],
+        ["Size of section headers:", bytes(header.e_shentsize)],
+        ["Number of section headers:", shnum],
+        ["Section header string table index:", shstrndx],
+      ]
+    )
+  }
+}
+
 // ╔═╗┬┬  ┌─┐
 // ╠╣ ││  ├┤
 // ╚  ┴┴─┘└─┘
@@ -154,87 +499,337 @@ use crate::reader::Reader;
 use crate::hex::hex;
 
 // #[derive(Debug)]
-pub struct _ElfFile<'data, Reader, ElfType>
+pub struct ElfObject<'data, Reader, ElfType>
 where
   Reader: self::Reader<'data>,
   ElfType: self::ElfType,
 {
   header: &'data ElfHeader<ElfType>,
-  // program_header: &'data ElfType::ProgramHeader,
   data: Reader,
+  /// Section header string table, resolved once by `section_names()`.
+  names: OnceLock<StringTable<'data>>,
+  /// Section headers up to the first one out of the file, read once.
+  section_table: OnceLock<Vec<&'data ElfSection<ElfType>>>,
+  /// `.symtab` and `.dynsym`, parsed once by `symbol_table()`.
+  symbol_tables: [OnceLock<Option<SymbolTable<'data, ElfType>>>; 2],
+  options: ParseOptions,
+  diagnostics: Diagnostics,
+  /// Start of the time budget of the limits.
+  #[cfg(feature = "std")]
+  started: std::time::Instant,
 }
 
-impl<'data, Reader, ElfType> Debug for _ElfFile<'data, Reader, ElfType>
+impl<'data, Reader, ElfType> Debug for ElfObject<'data, Reader, ElfType>
 where
   Reader: self::Reader<'data>,
   ElfType: self::ElfType,
 {
-  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    f.debug_struct("_ElfFile").field("header", &self.header).finish()
+  fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+    f.debug_struct("ElfObject").field("header", &self.header).finish()
   }
 }
 
-impl<'data, Reader, ElfType> _ElfFile<'data, Reader, ElfType>
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
 where
   Reader: self::Reader<'data>,
   ElfType: self::ElfType,
 {
-  fn parse(data: Reader) -> Result<Self, BytesError> {
+  ///
+  /// Reads the header and checks that `ei_class` and `ei_data` agree with
+  /// the type it is parsed as, so that a corrupted file cannot be read with
+  /// the wrong layout. Versions other than `EV_CURRENT` are rejected in
+  /// strict mode, recorded in lenient mode. Inputs larger than the limits
+  /// are rejected before anything is read.
+  ///
+  fn parse(data: Reader, options: ParseOptions) -> Result<Self, ElfError> {
+    options.limits.check_bytes(data.length() as u64)?;
+    let needed = ::core::mem::size_of::<ElfHeader<ElfType>>();
+    if data.length() < needed {
+      let available = data.length();
+      return Err(ElfError::Truncated { needed, available });
+    }
+
     let header = data.read_pod::<ElfHeader<ElfType>>(0)?;
-    Ok(Self { header, data })
+    let ident = &header.e_ident;
+
+    let checks = [
+      ("ei_class", ElfType::CLASS, ident.ei_class.into() as u8),
+      ("ei_data", ElfType::data(), ident.ei_data.into() as u8),
+    ];
+
+    if let Some((field, expected, actual)) = checks
+      .into_iter()
+      .find(|(_, expected, actual)| expected != actual)
+    {
+      return Err(ElfError::IdentificationMismatch {
+        field,
+        expected,
+        actual,
+      });
+    }
+
+    let diagnostics = Diagnostics::default();
+    let versions = [
+      ("ei_version", ident.ei_version.into()),
+      ("e_version", header.e_version.into()),
+    ];
+    for (field, version) in versions {
+      let result = match version == abi::e_version::EV_CURRENT as u64 {
+        true => Ok(()),
+        false => Err(ElfError::UnexpectedVersion { field, version }),
+      };
+      diagnostics.recover(options, result, ())?;
+    }
+
+    Ok(Self {
+      header,
+      data,
+      names: OnceLock::new(),
+      section_table: OnceLock::new(),
+      symbol_tables: Default::default(),
+      options,
+      diagnostics,
+      #[cfg(feature = "std")]
+      started: std::time::Instant::now(),
+    })
+  }
+
+  /// Returns the ELF header.
+  pub fn header(&self) -> &'data ElfHeader<ElfType> {
+    self.header
+  }
+
+  ///
+  /// Returns the OS ABI (`ei_osabi`), which the OS-specific object file,
+  /// section, segment and dynamic entry types depend on (see
+  /// [`section_type()`]).
+  ///
+  pub fn osabi(&self) -> u8 {
+    self.header.e_ident.ei_osabi.into() as u8
+  }
+
+  ///
+  /// Returns the ELF header display, unlike the [`ElfHeader`] one the type
+  /// of position-independent executables is not displayed as a shared
+  /// object (see [`Self::is_pie()`]), unless they have a `DT_SONAME` (e.g.
+  /// `libc.so.6`).
+  ///
+  pub fn display_header(&self) -> HeaderDisplay<'data, ElfType> {
+    HeaderDisplay {
+      header: self.header,
+      is_pie: self.is_pie() && self.soname().is_none(),
+      initial_section: self.initial_section(),
+    }
+  }
+
+  /// Returns the underlying reader.
+  pub fn data(&self) -> Reader {
+    self.data
+  }
+
+  /// Returns the options the object was parsed with.
+  pub fn options(&self) -> ParseOptions {
+    self.options
+  }
+
+  /// Returns the warnings recorded while reading the tables, see [`ParseOptions`].
+  pub fn diagnostics(&self) -> &Diagnostics {
+    &self.diagnostics
+  }
+
+  ///
+  /// Returns the number of entries of a table to read: the declared count,
+  /// but no more than the whole file could hold with entries of the given
+  /// size, so that a forged count (`e_shnum = 0xFF00`) never drives more
+  /// work or memory than the size of the file. A clamped count is recorded
+  /// as a warning ([`ElfError::CountClamped`]), in strict mode too since the
+  /// reading fails further anyway.
+  ///
+  /// Reading a table is a phase of the time budget, more entries than the
+  /// limit fail with [`ElfError::LimitExceeded`] (see [`Limits`]).
+  ///
+  pub fn bounded_count(&self, table: &'static str, count: u64, entsize: usize) -> Result<u64, ElfError> {
+    let max = (self.data.length() / entsize.max(1)) as u64;
+    if count > max {
+      self
+        .diagnostics
+        .warn(ElfError::CountClamped { table, count, max });
+    }
+    self.check_time_budget()?;
+    self.options.limits.check_entries(count.min(max))?;
+    Ok(count.min(max))
+  }
+
+  ///
+  /// Same as [`Self::bounded_count()`] for the iterators, which cannot fail:
+  /// they stop at the limit, recorded as a warning.
+  ///
+  pub(crate) fn limited_count(&self, count: u64) -> u64 {
+    match self.options.limits.check_entries(count) {
+      Ok(()) => count,
+      Err(error) => {
+        self.diagnostics.warn(error);
+        self.options.limits.max_entries.unwrap_or(count)
+      }
+    }
+  }
+
+  ///
+  /// Fails with [`ElfError::LimitExceeded`] once the time budget of the
+  /// [`Limits`] is spent, counted from the parsing. The tables check it
+  /// when they are read, callers running several phases between them.
+  ///
+  pub fn check_time_budget(&self) -> Result<(), ElfError> {
+    #[cfg(feature = "std")]
+    if let Some(budget) = self.options.limits.time_budget {
+      if self.started.elapsed() > budget {
+        return Err(ElfError::LimitExceeded(Limit::Time));
+      }
+    }
+    Ok(())
+  }
+
+  /// Same as [`Diagnostics::recover()`] with the options of the object.
+  pub(crate) fn recover<T>(&self, result: Result<(), ElfError>, value: T) -> Result<T, ElfError> {
+    self.diagnostics.recover(self.options, result, value)
   }
 }
 
 #[derive(Debug)]
 pub enum ElfFile<'data, Reader: self::Reader<'data>> {
-  Elf32Be(_ElfFile<'data, Reader, ElfType32<BigEndian>>),
-  Elf64Be(_ElfFile<'data, Reader, ElfType64<BigEndian>>),
-  Elf32Le(_ElfFile<'data, Reader, ElfType32<LittleEndian>>),
-  Elf64Le(_ElfFile<'data, Reader, ElfType64<LittleEndian>>),
+  Elf32Be(ElfObject<'data, Reader, ElfType32<BigEndian>>),
+  Elf64Be(ElfObject<'data, Reader, ElfType64<BigEndian>>),
+  Elf32Le(ElfObject<'data, Reader, ElfType32<LittleEndian>>),
+  Elf64Le(ElfObject<'data, Reader, ElfType64<LittleEndian>>),
+}
+
+/// Expands to a `match` calling the same expression on every [`ElfFile`] variant.
+macro_rules! dispatch {
+  ($file: expr, $object: ident => $expression: expr) => {
+    match $file {
+      ElfFile::Elf32Be($object) => $expression,
+      ElfFile::Elf64Be($object) => $expression,
+      ElfFile::Elf32Le($object) => $expression,
+      ElfFile::Elf64Le($object) => $expression,
+    }
+  };
 }
 
-#[allow(unused)]
-pub fn parse_elf<'data, Reader>(data: Reader) -> Result<ElfFile<'data, Reader>, BytesError>
+///
+/// Accessors of the most common header fields whatever the class and the
+/// data encoding, the strongly-typed [`ElfObject`] remains available through
+/// the variants.
+///
+impl<'data, Reader: self::Reader<'data>> ElfFile<'data, Reader> {
+  /// Returns the target machine (`e_machine`).
+  pub fn machine(&self) -> utils::Constant<u16> {
+    let e_machine: u64 = dispatch!(self, object => object.header.e_machine.into());
+    abi::e_machine::from(e_machine as u16)
+  }
+
+  /// Returns the object file type (`e_type`), see [`object_type()`].
+  pub fn object_type(&self) -> utils::Constant<u16> {
+    let e_type: u64 = dispatch!(self, object => object.header.e_type.into());
+    let e_machine: u64 = dispatch!(self, object => object.header.e_machine.into());
+    let osabi = dispatch!(self, object => object.osabi());
+    abi::object_type(e_machine as u16, osabi, e_type as u16)
+  }
+
+  /// Returns the entry point virtual address (`e_entry`).
+  pub fn entry(&self) -> u64 {
+    dispatch!(self, object => object.header.e_entry.into())
+  }
+
+  /// Returns `true` for `ELFCLASS64`.
+  pub fn is_64(&self) -> bool {
+    matches!(self, Self::Elf64Be(_) | Self::Elf64Le(_))
+  }
+
+  /// Returns the data encoding long name (`big-endian` or `little-endian`).
+  pub fn endianness(&self) -> &'static str {
+    match self {
+      Self::Elf32Be(_) | Self::Elf64Be(_) => BigEndian::long_name(),
+      Self::Elf32Le(_) | Self::Elf64Le(_) => LittleEndian::long_name(),
+    }
+  }
+
+  /// Returns the ELF header display, see [`ElfObject::display_header()`].
+  pub fn header_display(&self) -> impl Display + 'data {
+    dispatch!(self, object => Box::new(object.display_header()) as Box<dyn Display + 'data>)
+  }
+
+  /// Returns the warnings recorded while reading the tables, see [`ElfObject::diagnostics()`].
+  pub fn diagnostics(&self) -> &Diagnostics {
+    dispatch!(self, object => object.diagnostics())
+  }
+}
+
+/// Parses an ELF file with the default (strict) options, see [`parse_elf_with()`].
+pub fn parse_elf<'data, Reader>(data: Reader) -> Result<ElfFile<'data, Reader>, ElfError>
 where
   Reader: self::Reader<'data>,
 {
-  let magic = data.read_bytes(4, 0);
-  if magic != Some(&[0x7f, b'E', b'L', b'F']) {
-    return Err(BytesError::Empty); // TODO: TMP Err("Bad magic");
-  }
-
-  match data.read_bytes(2, 4) {
-    None => Err(BytesError::Empty), // TODO: TMP Err("No class/data"),
-    Some(format) => {
-      match *format {
-        [1, 1] => Ok(ElfFile::Elf32Le(_ElfFile::parse(data)?)),
-        [2, 1] => Ok(ElfFile::Elf64Le(_ElfFile::parse(data)?)),
-        [1, 2] => Ok(ElfFile::Elf32Be(_ElfFile::parse(data)?)),
-        [2, 2] => Ok(ElfFile::Elf64Be(_ElfFile::parse(data)?)),
-        _ => Err(BytesError::Empty), // TODO: TMP Err("Bad class/data"),
-      }
+  parse_elf_with(data, ParseOptions::default())
+}
+
+///
+/// Parses an ELF file, the options tell how the malformed tables met later
+/// on are handled (the header itself must always be valid).
+///
+pub fn parse_elf_with<'data, Reader>(
+  data: Reader,
+  options: ParseOptions,
+) -> Result<ElfFile<'data, Reader>, ElfError>
+where
+  Reader: self::Reader<'data>,
+{
+  let truncated = |needed| ElfError::Truncated {
+    needed,
+    available: data.length(),
+  };
+
+  let magic = data.read_bytes(4, 0).ok_or_else(|| truncated(4))?;
+  if magic != ELFMAG {
+    // Some libraries are text linker scripts (e.g. libc.so).
+    let bytes = data.read_bytes(data.length(), 0).unwrap_or_default();
+    if is_linker_script(bytes) {
+      let input = linker_script_input(bytes).map(String::from);
+      return Err(ElfError::LinkerScript { input });
     }
+
+    return Err(ElfError::BadMagic([magic[0], magic[1], magic[2], magic[3]]));
+  }
+
+  let format = data.read_bytes(2, 4).ok_or_else(|| truncated(6))?;
+  match (format[0], format[1]) {
+    (1, 1) => Ok(ElfFile::Elf32Le(ElfObject::parse(data, options)?)),
+    (2, 1) => Ok(ElfFile::Elf64Le(ElfObject::parse(data, options)?)),
+    (1, 2) => Ok(ElfFile::Elf32Be(ElfObject::parse(data, options)?)),
+    (2, 2) => Ok(ElfFile::Elf64Be(ElfObject::parse(data, options)?)),
+    (class, _) if class != 1 && class != 2 => Err(ElfError::InvalidClass(class)),
+    (class, data) => Err(ElfError::UnsupportedClassData { class, data }),
   }
 }
 
 // Program header/table
 // Section header/table
 
-#[test]
-fn parser() {
-  let bytes = hex(
+/// Big endian elf32 header, every field is `0x0102...` but the versions (`EV_CURRENT`).
+#[cfg(test)]
+fn header_fixture() -> Vec<u8> {
+  hex(
     r"
       7F 'ELF ; Magic
       01 ; ei_class
       02 ; ei_data
-      00 ; ei_version
+      01 ; ei_version
       00 ; ei_osabi
       00 ; ei_abiversion
       00 00 00 00 00 00 00 ; ei_pad
 
       0102 ; e_type
       0102 ; e_machine
-      0102 0304 ; e_version
+      0000 0001 ; e_version
       0102 0304 ; e_entry
       0102 0304 ; e_phoff
       0102 0304 ; e_shoff
@@ -246,10 +841,307 @@ fn parser() {
       0102 ; e_shnum
       0102 ; e_shstrndx
     ",
-  );
+  )
+  .unwrap()
+}
 
-  let binding = bytes.unwrap();
+#[test]
+fn parser() {
+  let binding = header_fixture();
   let result = parse_elf(binding.as_slice());
 
   println!("{:#x?}", result);
 }
+
+#[test]
+fn display_header() {
+  let bytes = header_fixture();
+  let Ok(ElfFile::Elf32Be(object)) = parse_elf(bytes.as_slice()) else {
+    panic!("Expected a big endian elf32");
+  };
+
+  assert_eq!(
+    object.header().to_string(),
+    concat!(
+      "Magic:                              7f 45 4c 46 01 02 01 00 00 00 00 00 00 00 00 00\n",
+      "Class:                              ELF32\n",
+      "Data:                               2's complement, big endian\n",
+      "Version:                            1\n",
+      "OS/ABI:                             UNIX - System V\n",
+      "ABI Version:                        0\n",
+      "Type:                               0x102\n",
+      "Machine:                            LoongArch\n",
+      "Version:                            0x1\n",
+      "Entry point address:                0x01020304\n",
+      "Start of program headers:           16909060 (bytes into file)\n",
+      "Start of section headers:           16909060 (bytes into file)\n",
+      "Flags:                              0x1020304\n",
+      "Size of this header:                258 (bytes)\n",
+      "Size of program headers:            258 (bytes)\n",
+      "Number of program headers:          258\n",
+      "Size of section headers:            258 (bytes)\n",
+      "Number of section headers:          258\n",
+      "Section header string table index:  258\n",
+    )
+  );
+}
+
+#[test]
+fn display_header_machine() {
+  let bytes = header_fixture();
+  let Ok(ElfFile::Elf32Be(object)) = parse_elf(bytes.as_slice()) else {
+    panic!("Expected a big endian elf32");
+  };
+
+  let header = format!("{:#}", object.header());
+  let lines = header.lines().skip(1).take(8).collect::<Vec<_>>();
+  assert_eq!(
+    lines,
+    [
+      "Class:                              ELFCLASS32(0x1)",
+      "Data:                               ELFDATA2MSB(0x2)",
+      "Version:                            EV_CURRENT(0x1)",
+      "OS/ABI:                             ELFOSABI_SYSV(0x0)",
+      "ABI Version:                        0",
+      "Type:                               0x102",
+      "Machine:                            EM_LOONGARCH(0x102)",
+      "Version:                            EV_CURRENT(0x1)",
+    ]
+  );
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serialize_header() {
+  let bytes = header_fixture();
+  let Ok(ElfFile::Elf32Be(object)) = parse_elf(bytes.as_slice()) else {
+    panic!("Expected a big endian elf32");
+  };
+
+  let json = serde_json::to_value(object.header()).unwrap();
+  assert_eq!(json["e_ident"]["ei_mag1"], 0x45);
+  assert_eq!(
+    json["e_ident"]["ei_pad"],
+    serde_json::json!([0, 0, 0, 0, 0, 0, 0])
+  );
+  assert_eq!(json["e_entry"], 0x01020304, "Native value, not stored bytes");
+  assert_eq!(json["e_shstrndx"], 0x0102);
+}
+
+#[test]
+fn header_as_bytes() {
+  let bytes = header_fixture();
+  let header = ElfHeader::<ElfType32<BigEndian>>::from_bytes(&bytes).unwrap();
+  assert_eq!(header.as_bytes(), bytes);
+  assert_eq!(header.e_ident.as_bytes(), &bytes[..16]);
+}
+
+#[test]
+fn patch_header() {
+  use crate::writer::Writer;
+
+  let mut bytes = header_fixture();
+  let header = bytes.write_pod::<ElfHeader<ElfType32<BigEndian>>>(0).unwrap();
+  header.e_entry.set(0x401000);
+  header.e_flags = U32::from(0);
+
+  let Ok(ElfFile::Elf32Be(object)) = parse_elf(bytes.as_slice()) else {
+    panic!("Expected a big endian elf32");
+  };
+  assert_eq!(object.header().e_entry.get(), 0x401000);
+  assert_eq!(object.header().e_flags.get(), 0);
+  assert_eq!(bytes[24..28], [0x00, 0x40, 0x10, 0x00], "Stored big-endian");
+}
+
+#[test]
+fn display_specific_object_types() {
+  let mut bytes = header_fixture();
+  bytes[7] = abi::ei_osabi::ELFOSABI_FREEBSD;
+  bytes[16..18].copy_from_slice(&[0xfe, 0x01]); // e_type: ET_SCE_REPLAY_EXEC
+
+  let Ok(ElfFile::Elf32Be(object)) = parse_elf(bytes.as_slice()) else {
+    panic!("Expected a big endian elf32");
+  };
+  let line = |header: String| header.lines().nth(6).map(String::from).unwrap();
+  assert_eq!(
+    line(object.header().to_string()),
+    "Type:                               SCE_REPLAY_EXEC (SCE replay executable, 0xfe01)"
+  );
+  assert_eq!(
+    line(format!("{:#}", object.header())),
+    "Type:                               ET_SCE_REPLAY_EXEC(0xfe01)"
+  );
+
+  // The range with the raw value otherwise.
+  bytes[7] = abi::ei_osabi::ELFOSABI_SYSV;
+  let Ok(ElfFile::Elf32Be(object)) = parse_elf(bytes.as_slice()) else {
+    panic!("Expected a big endian elf32");
+  };
+  assert_eq!(
+    line(object.header().to_string()),
+    "Type:                               LOOS+0x1 (Environment-specific use, 0xfe01)"
+  );
+}
+
+#[test]
+fn display_header_extended() {
+  let mut bytes = header_fixture();
+  bytes[44..46].copy_from_slice(&[0xff, 0xff]); // e_phnum: PN_XNUM
+  bytes[48..50].copy_from_slice(&[0x00, 0x00]); // e_shnum
+  bytes[50..52].copy_from_slice(&[0xff, 0xff]); // e_shstrndx: SHN_XINDEX
+
+  let Ok(ElfFile::Elf32Be(object)) = parse_elf(bytes.as_slice()) else {
+    panic!("Expected a big endian elf32");
+  };
+
+  let output = object.header().to_string();
+  let lines = output.lines().collect::<Vec<_>>();
+  assert_eq!(
+    lines[lines.len() - 4..],
+    [
+      "Number of program headers:          65535 (see sh_info of section 0)",
+      "Size of section headers:            258 (bytes)",
+      "Number of section headers:          0 (see sh_size of section 0)",
+      "Section header string table index:  65535 (see sh_link of section 0)",
+    ]
+  );
+}
+
+#[test]
+fn unexpected_versions() {
+  let mut bytes = header_fixture();
+  bytes[6] = 0; // ei_version: EV_NONE
+  bytes[23] = 2; // e_version
+
+  // Only recorded in lenient mode.
+  assert_eq!(
+    parse_elf(bytes.as_slice()).unwrap_err(),
+    ElfError::UnexpectedVersion {
+      field: "ei_version",
+      version: 0
+    }
+  );
+  let Ok(ElfFile::Elf32Be(object)) = parse_elf_with(bytes.as_slice(), ParseOptions::LENIENT) else {
+    panic!("Expected a big endian elf32");
+  };
+  assert_eq!(
+    object
+      .diagnostics()
+      .take()
+      .iter()
+      .map(ToString::to_string)
+      .collect::<Vec<_>>(),
+    [
+      "ei_version is 0x0, EV_CURRENT is 1",
+      "e_version is 0x2, EV_CURRENT is 1"
+    ]
+  );
+
+  let output = object.header().to_string();
+  let versions = output
+    .lines()
+    .filter(|line| line.starts_with("Version:"))
+    .collect::<Vec<_>>();
+  assert_eq!(
+    versions,
+    [
+      "Version:                            0 (Invalid ELF version, EV_CURRENT is 1)",
+      "Version:                            0x2 (unexpected, EV_CURRENT is 1)",
+    ]
+  );
+
+  let output = format!("{:#}", object.header());
+  assert!(output.contains("Version:                            EV_NONE(0x0)\n"));
+
+  bytes[6] = 1;
+  assert_eq!(
+    parse_elf(bytes.as_slice()).unwrap_err().to_string(),
+    "e_version is 0x2, EV_CURRENT is 1"
+  );
+}
+
+#[test]
+fn identification_mismatch() {
+  let mut bytes = header_fixture();
+  bytes.resize(64, 0); // Large enough for an elf64 header.
+  let result = ElfObject::<_, ElfType64<BigEndian>>::parse(bytes.as_slice(), ParseOptions::default());
+  assert_eq!(
+    result.unwrap_err(),
+    ElfError::IdentificationMismatch {
+      field: "ei_class",
+      expected: 2,
+      actual: 1
+    }
+  );
+
+  let result = ElfObject::<_, ElfType32<LittleEndian>>::parse(bytes.as_slice(), ParseOptions::default());
+  assert_eq!(
+    result.unwrap_err(),
+    ElfError::IdentificationMismatch {
+      field: "ei_data",
+      expected: 1,
+      actual: 2
+    }
+  );
+
+  bytes[4] = 0; // ELFCLASSNONE
+  assert_eq!(
+    parse_elf(bytes.as_slice()).unwrap_err(),
+    ElfError::InvalidClass(0)
+  );
+}
+
+#[test]
+fn parse_errors() {
+  let mut bytes = header_fixture();
+  let error = |bytes: &[u8]| parse_elf(bytes).unwrap_err();
+
+  bytes[5] = 3; // ei_data
+  assert_eq!(
+    error(&bytes),
+    ElfError::UnsupportedClassData { class: 1, data: 3 }
+  );
+  assert_eq!(
+    error(&bytes).to_string(),
+    "unsupported data encoding (ei_data) 0x3 for file class 0x1"
+  );
+
+  assert_eq!(
+    error(&bytes[..5]),
+    ElfError::Truncated {
+      needed: 6,
+      available: 5
+    }
+  );
+  assert_eq!(
+    error(&header_fixture()[..40]),
+    ElfError::Truncated {
+      needed: 52,
+      available: 40
+    }
+  );
+
+  bytes[3] = 0;
+  assert_eq!(error(&bytes), ElfError::BadMagic(*b"\x7fEL\0"));
+  assert_eq!(
+    error(&bytes).to_string(),
+    "bad magic 7f 45 4c 00, expected 7f 45 4c 46 (not an ELF file?)"
+  );
+  assert_eq!(
+    error(&bytes[..2]).to_string(),
+    "file truncated, 4 bytes needed but only 2 available"
+  );
+}
+
+#[test]
+fn elf_file_accessors() {
+  let bytes = header_fixture();
+  let file = parse_elf(bytes.as_slice()).unwrap();
+
+  assert_eq!(file.machine().to_string(), "EM_LOONGARCH");
+  assert_eq!(file.object_type().to_string(), "0x102");
+  assert_eq!(file.entry(), 0x01020304);
+  assert!(!file.is_64());
+  assert_eq!(file.endianness(), "big-endian");
+  assert!(file.header_display().to_string().starts_with("Magic:"));
+}
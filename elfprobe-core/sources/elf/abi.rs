@@ -0,0 +1,407 @@
+use crate::utils::{define_constants, define_flags, Constant};
+
+// ╦┌┬┐┌─┐┌┐┌┌┬┐┬┌─┐┬┌─┐┌─┐┌┬┐┬┌─┐┌┐┌
+// ║ ││├┤ │││ │ │├┤ ││  ├─┤ │ ││ ││││
+// ╩─┴┘└─┘┘└┘ ┴ ┴└  ┴└─┘┴ ┴ ┴ ┴└─┘┘└┘
+
+define_constants! {
+  /// File class (`ei_class`).
+  pub ei_class: u8 {
+    ELFCLASSNONE = 0 => "Invalid class",
+    ELFCLASS32 = 1 => "ELF32",
+    ELFCLASS64 = 2 => "ELF64",
+  }
+}
+
+define_constants! {
+  /// Data encoding (`ei_data`).
+  pub ei_data: u8 {
+    ELFDATANONE = 0 => "Invalid data encoding",
+    ELFDATA2LSB = 1 => "2's complement, little endian",
+    ELFDATA2MSB = 2 => "2's complement, big endian",
+  }
+}
+
+define_constants! {
+  /// Operating system ABI (`ei_osabi`).
+  pub ei_osabi: u8 {
+    ELFOSABI_SYSV = 0 => "UNIX - System V",
+    ELFOSABI_HPUX = 1 => "HP-UX",
+    ELFOSABI_NETBSD = 2 => "NetBSD",
+    ELFOSABI_GNU = 3 => "UNIX - GNU",
+    ELFOSABI_SOLARIS = 6 => "Sun Solaris",
+    ELFOSABI_AIX = 7 => "IBM AIX",
+    ELFOSABI_IRIX = 8 => "SGI Irix",
+    ELFOSABI_FREEBSD = 9 => "FreeBSD",
+    ELFOSABI_TRU64 = 10 => "Compaq TRU64 UNIX",
+    ELFOSABI_MODESTO = 11 => "Novell Modesto",
+    ELFOSABI_OPENBSD = 12 => "OpenBSD",
+    ELFOSABI_ARM_AEABI = 64 => "ARM EABI",
+    ELFOSABI_ARM = 97 => "ARM",
+    ELFOSABI_STANDALONE = 255 => "Standalone (embedded) application",
+  }
+}
+
+// ╦ ╦┌─┐┌─┐┌┬┐┌─┐┬─┐
+// ╠═╣├┤ ├─┤ ││├┤ ├┬┘
+// ╩ ╩└─┘┴ ┴─┴┘└─┘┴└─
+
+define_constants! {
+  /// Object file type (`e_type`).
+  pub e_type: u16 {
+    ET_NONE = 0 => "No file type",
+    ET_REL = 1 => "Relocatable file",
+    ET_EXEC = 2 => "Executable file",
+    ET_DYN = 3 => "Shared object file",
+    ET_CORE = 4 => "Core file",
+    [ET_LOOS .. ET_HIOS] = 0xfe00 .. 0xfeff => "Environment-specific use",
+    [ET_LOPROC .. ET_HIPROC] = 0xff00 .. 0xffff => "Processor-specific use",
+  }
+}
+
+define_constants! {
+  /// Sony PlayStation 4 and 5 object file types (FreeBSD OS ABI), see [`object_type()`].
+  pub e_type_freebsd: u16 {
+    ET_SCE_EXEC = 0xfe00 => "SCE executable",
+    ET_SCE_REPLAY_EXEC = 0xfe01 => "SCE replay executable",
+    ET_SCE_RELEXEC = 0xfe04 => "SCE relocatable executable",
+    ET_SCE_STUBLIB = 0xfe0c => "SCE stub library",
+    ET_SCE_DYNEXEC = 0xfe10 => "SCE dynamic executable",
+    ET_SCE_DYNAMIC = 0xfe18 => "SCE dynamic library",
+  }
+}
+
+define_constants! {
+  /// Sony PlayStation 2 and PSP object file types (MIPS), see [`object_type()`].
+  pub e_type_mips: u16 {
+    ET_SCE_IOPRELEXEC = 0xff80 => "SCE IOP relocatable executable",
+    ET_SCE_IOPRELEXEC2 = 0xff81 => "SCE IOP relocatable executable (version 2)",
+    ET_SCE_EERELEXEC = 0xff90 => "SCE EE relocatable executable",
+    ET_SCE_EERELEXEC2 = 0xff91 => "SCE EE relocatable executable (version 2)",
+    ET_SCE_PSPRELEXEC = 0xffa0 => "SCE PSP relocatable executable",
+  }
+}
+
+define_constants! {
+  /// Sony PlayStation 3 object file types (PowerPC 64), see [`object_type()`].
+  pub e_type_ppc64: u16 {
+    ET_SCE_PPURELEXEC = 0xffa4 => "SCE PPU relocatable executable",
+  }
+}
+
+///
+/// Returns the object file type of the given machine and OS ABI, as
+/// [`dynamic_tag()`](super::dynamic_tag()): processor-specific values are
+/// looked up in the machine table first, OS-specific ones in the OS ABI
+/// table, and the ranges of [`e_type`] describe the remaining values.
+///
+pub fn object_type(machine: u16, osabi: u8, e_type: u16) -> Constant<u16> {
+  use e_machine::{EM_MIPS, EM_MIPS_RS3_LE, EM_PPC64};
+
+  let constant = match machine {
+    EM_MIPS | EM_MIPS_RS3_LE => e_type_mips::from(e_type),
+    EM_PPC64 => e_type_ppc64::from(e_type),
+    _ => Constant::unknown(e_type),
+  };
+  let constant = match (constant.name(), osabi) {
+    (None, ei_osabi::ELFOSABI_FREEBSD) => e_type_freebsd::from(e_type),
+    _ => constant,
+  };
+
+  match constant.name() {
+    Some(_) => constant,
+    None => e_type::from(e_type),
+  }
+}
+
+define_constants! {
+  /// Object file version (`e_version` and `ei_version`).
+  pub e_version: u32 {
+    EV_NONE = 0 => "Invalid ELF version",
+    EV_CURRENT = 1 => "Current version",
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┬ ┬┬┌┐┌┌─┐
+// ║║║├─┤│  ├─┤││││├┤
+// ╩ ╩┴ ┴└─┘┴ ┴┴┘└┘└─┘
+
+define_constants! {
+  /// Target architectures (`e_machine`), see /usr/include/elf.h.
+  pub e_machine: u16 {
+    EM_NONE = 0 => "No machine",
+    EM_M32 = 1 => "AT&T WE 32100",
+    EM_SPARC = 2 => "SUN SPARC",
+    EM_386 = 3 => "Intel 80386",
+    EM_68K = 4 => "Motorola m68k family",
+    EM_88K = 5 => "Motorola m88k family",
+    EM_IAMCU = 6 => "Intel MCU",
+    EM_860 = 7 => "Intel 80860",
+    EM_MIPS = 8 => "MIPS R3000 big-endian",
+    EM_S370 = 9 => "IBM System/370",
+    EM_MIPS_RS3_LE = 10 => "MIPS R3000 little-endian",
+    EM_PARISC = 15 => "HPPA",
+    EM_SPARC32PLUS = 18 => "Sun's \"v8plus\"",
+    EM_PPC = 20 => "PowerPC",
+    EM_PPC64 = 21 => "PowerPC 64-bit",
+    EM_S390 = 22 => "IBM S390",
+    EM_ARM = 40 => "ARM",
+    EM_SH = 42 => "Hitachi SH",
+    EM_SPARCV9 = 43 => "SPARC v9 64-bit",
+    EM_IA_64 = 50 => "Intel Merced",
+    EM_X86_64 = 62 => "AMD x86-64 architecture",
+    EM_AVR = 83 => "Atmel AVR 8-bit microcontroller",
+    EM_XTENSA = 94 => "Tensilica Xtensa Architecture",
+    EM_MSP430 = 105 => "Texas Instruments msp430",
+    EM_AARCH64 = 183 => "ARM AARCH64",
+    EM_CUDA = 190 => "NVIDIA CUDA architecture",
+    EM_AMDGPU = 224 => "AMD GPU",
+    EM_RISCV = 243 => "RISC-V",
+    EM_BPF = 247 => "Linux BPF -- in-kernel virtual machine",
+    EM_CSKY = 252 => "C-SKY",
+    EM_LOONGARCH = 258 => "LoongArch",
+  }
+}
+
+// ╔═╗┌─┐┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ╚═╗├┤ │ ┬│││├┤ │││ │ └─┐
+// ╚═╝└─┘└─┘┴ ┴└─┘┘└┘ ┴ └─┘
+
+define_constants! {
+  /// Segment type (`p_type`).
+  pub p_type: u32 {
+    PT_NULL = 0 => "Program header table entry unused",
+    PT_LOAD = 1 => "Loadable program segment",
+    PT_DYNAMIC = 2 => "Dynamic linking information",
+    PT_INTERP = 3 => "Program interpreter",
+    PT_NOTE = 4 => "Auxiliary information",
+    PT_SHLIB = 5 => "Reserved",
+    PT_PHDR = 6 => "Entry for header table itself",
+    PT_TLS = 7 => "Thread-local storage segment",
+    PT_GNU_EH_FRAME = 0x6474e550 => "GCC .eh_frame_hdr segment",
+    PT_GNU_STACK = 0x6474e551 => "Indicates stack executability",
+    PT_GNU_RELRO = 0x6474e552 => "Read-only after relocation",
+    PT_GNU_PROPERTY = 0x6474e553 => "GNU property notes for linker and run-time loaders",
+    PT_GNU_SFRAME = 0x6474e554 => "SFrame stack trace information",
+    [PT_GNU_MBIND_LO .. PT_GNU_MBIND_HI] = 0x6474e555 .. 0x6474f554 => "Memory binding range",
+    PT_SUNWBSS = 0x6ffffffa => "Sun specific segment",
+    PT_SUNWSTACK = 0x6ffffffb => "Stack segment",
+    [PT_LOOS .. PT_HIOS] = 0x60000000 .. 0x6fffffff => "OS-specific",
+    [PT_LOPROC .. PT_HIPROC] = 0x70000000 .. 0x7fffffff => "Processor-specific",
+  }
+}
+
+define_constants! {
+  /// Solaris segment types, see [`segment_type()`].
+  pub p_type_solaris: u32 {
+    PT_SUNW_UNWIND = 0x6464e550 => "Unwind information",
+    PT_SUNW_EH_FRAME = 0x6474e550 => "Exception frame information",
+    PT_SUNWBSS = 0x6ffffffa => "Sun specific segment",
+    PT_SUNWSTACK = 0x6ffffffb => "Stack segment",
+    PT_SUNWDTRACE = 0x6ffffffc => "Private DTrace segment",
+    PT_SUNWCAP = 0x6ffffffd => "Hardware and software capabilities",
+  }
+}
+
+define_constants! {
+  /// OpenBSD segment types, see [`segment_type()`].
+  pub p_type_openbsd: u32 {
+    PT_OPENBSD_MUTABLE = 0x65a3dbe5 => "Mutable .bss",
+    PT_OPENBSD_RANDOMIZE = 0x65a3dbe6 => "Fill with random data",
+    PT_OPENBSD_WXNEEDED = 0x65a3dbe7 => "Program does W^X violations",
+    PT_OPENBSD_NOBTCFI = 0x65a3dbe8 => "No branch target CFI",
+    PT_OPENBSD_SYSCALLS = 0x65a3dbe9 => "System call sites",
+    PT_OPENBSD_BOOTDATA = 0x65a41be6 => "Section for boot arguments",
+  }
+}
+
+///
+/// Returns the segment type of the given OS ABI (`ei_osabi`): OS-specific
+/// values are looked up in the Solaris or OpenBSD table first, the GNU ones
+/// otherwise (Linux and FreeBSD objects follow the GNU conventions).
+///
+pub fn segment_type(osabi: u8, p_type: u32) -> Constant<u32> {
+  let constant = match osabi {
+    ei_osabi::ELFOSABI_SOLARIS => p_type_solaris::from(p_type),
+    ei_osabi::ELFOSABI_OPENBSD => p_type_openbsd::from(p_type),
+    _ => Constant::unknown(p_type),
+  };
+
+  match constant.name() {
+    Some(_) => constant,
+    None => p_type::from(p_type),
+  }
+}
+
+define_flags! {
+  /// Segment permissions (`p_flags`), the short aliases are the readelf ones.
+  pub p_flags: u32 {
+    PF_X / E = 0x1 => "Segment is executable",
+    PF_W / W = 0x2 => "Segment is writable",
+    PF_R / R = 0x4 => "Segment is readable",
+    PF_MASKOS / o = 0x0ff00000 => "OS-specific",
+    PF_MASKPROC / p = 0xf0000000 => "Processor-specific",
+  }
+}
+
+// ╔═╗┌─┐┌─┐┌┬┐┬┌─┐┌┐┌┌─┐
+// ╚═╗├┤ │   │ ││ ││││└─┐
+// ╚═╝└─┘└─┘ ┴ ┴└─┘┘└┘└─┘
+
+define_constants! {
+  /// Section type (`sh_type`).
+  pub sh_type: u32 {
+    SHT_NULL = 0 => "Section header table entry unused",
+    SHT_PROGBITS = 1 => "Program data",
+    SHT_SYMTAB = 2 => "Symbol table",
+    SHT_STRTAB = 3 => "String table",
+    SHT_RELA = 4 => "Relocation entries with addends",
+    SHT_HASH = 5 => "Symbol hash table",
+    SHT_DYNAMIC = 6 => "Dynamic linking information",
+    SHT_NOTE = 7 => "Notes",
+    SHT_NOBITS = 8 => "Program space with no data (bss)",
+    SHT_REL = 9 => "Relocation entries, no addends",
+    SHT_SHLIB = 10 => "Reserved",
+    SHT_DYNSYM = 11 => "Dynamic linker symbol table",
+    SHT_INIT_ARRAY = 14 => "Array of constructors",
+    SHT_FINI_ARRAY = 15 => "Array of destructors",
+    SHT_PREINIT_ARRAY = 16 => "Array of pre-constructors",
+    SHT_GROUP = 17 => "Section group",
+    SHT_SYMTAB_SHNDX = 18 => "Extended section indices",
+    SHT_RELR = 19 => "Relative relocation entries",
+    SHT_GNU_ATTRIBUTES = 0x6ffffff5 => "Object attributes",
+    SHT_GNU_HASH = 0x6ffffff6 => "GNU-style hash table",
+    SHT_GNU_LIBLIST = 0x6ffffff7 => "Prelink library list",
+    SHT_GNU_verdef = 0x6ffffffd => "Version definition section",
+    SHT_GNU_verneed = 0x6ffffffe => "Version needs section",
+    SHT_GNU_versym = 0x6fffffff => "Version symbol table",
+    [SHT_LOOS .. SHT_HIOS] = 0x60000000 .. 0x6fffffff => "OS-specific",
+    [SHT_LOPROC .. SHT_HIPROC] = 0x70000000 .. 0x7fffffff => "Processor-specific",
+    [SHT_LOUSER .. SHT_HIUSER] = 0x80000000 .. 0xffffffff => "Application-specific",
+  }
+}
+
+define_constants! {
+  /// Solaris section types, see [`section_type()`]. Most of them share
+  /// their values with different GNU types (`SHT_SUNW_cap` and
+  /// `SHT_GNU_ATTRIBUTES`, `SHT_SUNW_SIGNATURE` and `SHT_GNU_HASH`).
+  pub sh_type_solaris: u32 {
+    SHT_SUNW_ancillary = 0x6fffffee => "Ancillary information",
+    SHT_SUNW_capchain = 0x6fffffef => "Capabilities chain",
+    SHT_SUNW_capinfo = 0x6ffffff0 => "Capabilities information",
+    SHT_SUNW_symsort = 0x6ffffff1 => "Sorted symbol indices",
+    SHT_SUNW_tlssort = 0x6ffffff2 => "Sorted TLS symbol indices",
+    SHT_SUNW_LDYNSYM = 0x6ffffff3 => "Local dynamic symbol table",
+    SHT_SUNW_dof = 0x6ffffff4 => "DTrace object format",
+    SHT_SUNW_cap = 0x6ffffff5 => "Hardware and software capabilities",
+    SHT_SUNW_SIGNATURE = 0x6ffffff6 => "Signature",
+    SHT_SUNW_ANNOTATE = 0x6ffffff7 => "Annotation",
+    SHT_SUNW_DEBUGSTR = 0x6ffffff8 => "Debug strings",
+    SHT_SUNW_DEBUG = 0x6ffffff9 => "Debug information",
+    SHT_SUNW_move = 0x6ffffffa => "Move table",
+    SHT_SUNW_COMDAT = 0x6ffffffb => "COMDAT section",
+    SHT_SUNW_syminfo = 0x6ffffffc => "Symbol information",
+    SHT_SUNW_verdef = 0x6ffffffd => "Version definition section",
+    SHT_SUNW_verneed = 0x6ffffffe => "Version needs section",
+    SHT_SUNW_versym = 0x6fffffff => "Version symbol table",
+  }
+}
+
+///
+/// Returns the section type of the given OS ABI (`ei_osabi`): OS-specific
+/// values are looked up in the Solaris table first, the GNU ones otherwise
+/// (Linux and FreeBSD objects follow the GNU conventions).
+///
+pub fn section_type(osabi: u8, sh_type: u32) -> Constant<u32> {
+  let constant = match osabi {
+    ei_osabi::ELFOSABI_SOLARIS => sh_type_solaris::from(sh_type),
+    _ => Constant::unknown(sh_type),
+  };
+
+  match constant.name() {
+    Some(_) => constant,
+    None => sh_type::from(sh_type),
+  }
+}
+
+define_constants! {
+  /// Special section indices (`st_shndx`, `e_shstrndx`...), reserved indices
+  /// do not refer to an entry of the section header table.
+  pub st_shndx: u16 {
+    SHN_UNDEF = 0 => "Undefined section",
+    SHN_ABS = 0xfff1 => "Associated symbol is absolute",
+    SHN_COMMON = 0xfff2 => "Associated symbol is common",
+    SHN_XINDEX = 0xffff => "Index is in extra table",
+    [SHN_LOPROC .. SHN_HIPROC] = 0xff00 .. 0xff1f => "Processor-specific",
+    [SHN_LOOS .. SHN_HIOS] = 0xff20 .. 0xff3f => "OS-specific",
+    [SHN_LORESERVE .. SHN_HIRESERVE] = 0xff00 .. 0xffff => "Reserved indices",
+  }
+}
+
+define_flags! {
+  /// Section flags (`sh_flags`), the short aliases are the readelf ones.
+  pub sh_flags: u64 {
+    SHF_WRITE / W = 0x1 => "Writable",
+    SHF_ALLOC / A = 0x2 => "Occupies memory during execution",
+    SHF_EXECINSTR / X = 0x4 => "Executable",
+    SHF_MERGE / M = 0x10 => "Might be merged",
+    SHF_STRINGS / S = 0x20 => "Contains nul-terminated strings",
+    SHF_INFO_LINK / I = 0x40 => "`sh_info` contains a section index",
+    SHF_LINK_ORDER / L = 0x80 => "Preserve order after combining",
+    SHF_OS_NONCONFORMING / O = 0x100 => "Non-standard OS specific handling required",
+    SHF_GROUP / G = 0x200 => "Section is member of a group",
+    SHF_TLS / T = 0x400 => "Section hold thread-local data",
+    SHF_COMPRESSED / C = 0x800 => "Section with compressed data",
+    SHF_GNU_RETAIN / R = 0x200000 => "Not to be garbage collected by the linker",
+    SHF_EXCLUDE / E = 0x80000000 => "Excluded unless referenced or allocated",
+    SHF_MASKOS / o = 0x0ff00000 => "OS-specific",
+    SHF_MASKPROC / p = 0xf0000000 => "Processor-specific",
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::ei_osabi::*;
+  use super::*;
+
+  #[test]
+  fn section_types() {
+    let name = |osabi, sh_type| section_type(osabi, sh_type).to_string();
+    assert_eq!(name(ELFOSABI_GNU, 0x6ffffff6), "SHT_GNU_HASH");
+    assert_eq!(name(ELFOSABI_SOLARIS, 0x6ffffff6), "SHT_SUNW_SIGNATURE");
+    assert_eq!(name(ELFOSABI_SYSV, 0x6ffffff5), "SHT_GNU_ATTRIBUTES");
+    assert_eq!(name(ELFOSABI_SOLARIS, 0x6ffffff5), "SHT_SUNW_cap");
+    assert_eq!(name(ELFOSABI_FREEBSD, 0x6ffffff5), "SHT_GNU_ATTRIBUTES");
+    assert_eq!(name(ELFOSABI_SOLARIS, 1), "SHT_PROGBITS", "Generic value");
+    assert_eq!(name(ELFOSABI_SOLARIS, 0x60000003), "SHT_LOOS+0x3");
+  }
+
+  #[test]
+  fn segment_types() {
+    let name = |osabi, p_type| segment_type(osabi, p_type).to_string();
+    assert_eq!(name(ELFOSABI_SYSV, 0x6ffffffd), "PT_LOOS+0xffffffd");
+    assert_eq!(name(ELFOSABI_SOLARIS, 0x6ffffffd), "PT_SUNWCAP");
+    assert_eq!(name(ELFOSABI_SOLARIS, 0x6474e550), "PT_SUNW_EH_FRAME");
+    assert_eq!(name(ELFOSABI_GNU, 0x6474e550), "PT_GNU_EH_FRAME");
+    assert_eq!(name(ELFOSABI_OPENBSD, 0x65a3dbe6), "PT_OPENBSD_RANDOMIZE");
+    assert_eq!(name(ELFOSABI_OPENBSD, 1), "PT_LOAD");
+  }
+
+  #[test]
+  fn object_types() {
+    use super::e_machine::{EM_MIPS, EM_PPC64, EM_X86_64};
+
+    let name = |machine, osabi, e_type| object_type(machine, osabi, e_type).to_string();
+    assert_eq!(name(EM_X86_64, ELFOSABI_FREEBSD, 0xfe01), "ET_SCE_REPLAY_EXEC");
+    assert_eq!(name(EM_X86_64, ELFOSABI_SYSV, 0xfe01), "ET_LOOS+0x1");
+    assert_eq!(name(EM_MIPS, ELFOSABI_SYSV, 0xffa0), "ET_SCE_PSPRELEXEC");
+    assert_eq!(name(EM_PPC64, ELFOSABI_SYSV, 0xffa4), "ET_SCE_PPURELEXEC");
+    assert_eq!(name(EM_X86_64, ELFOSABI_SYSV, 0xffa4), "ET_LOPROC+0xa4");
+    assert_eq!(name(EM_MIPS, ELFOSABI_FREEBSD, 0xfe10), "ET_SCE_DYNEXEC");
+    assert_eq!(name(EM_MIPS, ELFOSABI_FREEBSD, 2), "ET_EXEC", "Generic value");
+  }
+}
@@ -0,0 +1,332 @@
+use ::core::fmt;
+use ::core::mem::size_of;
+
+use super::offsets::HeaderOffsets;
+use super::*;
+use crate::pod::FieldLayout;
+use crate::reader::Reader;
+use crate::utils::Constant;
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+/// Bytes shown past the end of the header when `e_ehsize` is larger.
+const EXTRA_BYTES: usize = 16;
+
+///
+/// A header field with its file offset, its bytes as stored in the file and
+/// its value read with the data encoding of the file, see [`Annotated`].
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotatedField<'data> {
+  pub name: &'static str,
+  pub offset: usize,
+  pub bytes: &'data [u8],
+  /// `None` for the arrays (`ei_pad`) and the bytes past the header.
+  pub value: Option<u64>,
+  /// The constant or the flags of the value (`ET_DYN`, `SHF_WRITE | SHF_ALLOC`), when known.
+  pub decoded: Option<String>,
+}
+
+///
+/// The fields of a header one per line, a guided hex dump to tell which
+/// bytes of a corrupted header are wrong. See [`ElfObject::annotate_header()`],
+/// [`ElfObject::annotate_section()`] and [`ElfObject::annotate_segment()`].
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Annotated<'data> {
+  pub fields: Vec<AnnotatedField<'data>>,
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Annotates the ELF header, the fields of `e_ident` first. When `e_ehsize`
+  /// is larger than the header, the first bytes past it follow as `(extra)`.
+  ///
+  pub fn annotate_header(&self) -> Annotated<'data> {
+    let mut fields = self.annotate::<ElfIdentification<ElfType>>(0).unwrap_or_default();
+    if let Some(header) = self.annotate::<ElfHeader<ElfType>>(0) {
+      fields.extend(header.into_iter().filter(|field| field.name != "e_ident"));
+    }
+
+    let end = HeaderOffsets::of::<ElfType>().size;
+    let e_ehsize: u64 = self.header.e_ehsize.into();
+    let extra = (e_ehsize as usize).saturating_sub(end).min(EXTRA_BYTES);
+    let bytes = (extra > 0).then(|| self.data.read_bytes(extra, end)).flatten();
+    if let Some(bytes) = bytes {
+      fields.push(AnnotatedField {
+        name: "(extra)",
+        offset: end,
+        bytes,
+        value: None,
+        decoded: None,
+      });
+    }
+
+    Annotated { fields }
+  }
+
+  /// Annotates the section header at the given index, `None` when out of the table or the file.
+  pub fn annotate_section(&self, index: usize) -> Option<Annotated<'data>> {
+    let e_shoff: u64 = self.header.e_shoff.into();
+    let e_shentsize: u64 = self.header.e_shentsize.into();
+    if e_shoff == 0 || index as u64 >= self.section_count() {
+      return None;
+    }
+
+    let offset = (index as u64).checked_mul(e_shentsize)?.checked_add(e_shoff)?;
+    let fields = self.annotate::<ElfSection<ElfType>>(usize::try_from(offset).ok()?)?;
+    Some(Annotated { fields })
+  }
+
+  /// Annotates the program header at the given index, `None` when out of the table or the file.
+  pub fn annotate_segment(&self, index: usize) -> Option<Annotated<'data>> {
+    let e_phoff: u64 = self.header.e_phoff.into();
+    let e_phentsize: u64 = self.header.e_phentsize.into();
+    if index >= self.segments().count() {
+      return None;
+    }
+
+    let offset = (index as u64).checked_mul(e_phentsize)?.checked_add(e_phoff)?;
+    let fields = self.annotate::<ElfType::ProgramHeader>(usize::try_from(offset).ok()?)?;
+    Some(Annotated { fields })
+  }
+
+  /// Annotates the fields of the structure at the given offset, `None` when out of the file.
+  fn annotate<Header: FieldLayout>(&self, offset: usize) -> Option<Vec<AnnotatedField<'data>>> {
+    let little_endian = ElfType::Endian::is_little_endian();
+    let bytes = self.data.read_bytes(size_of::<Header>(), offset)?;
+    let e_machine: u64 = self.header.e_machine.into();
+
+    let fields = Header::FIELDS.iter().map(|field| {
+      let bytes = &bytes[field.offset..field.offset + field.size];
+      let value = (field.size <= 8 && field.name != "ei_pad").then(|| {
+        let mut value = [0; 8];
+        match little_endian {
+          true => value[..bytes.len()].copy_from_slice(bytes),
+          false => value[8 - bytes.len()..].copy_from_slice(bytes),
+        }
+        match little_endian {
+          true => u64::from_le_bytes(value),
+          false => u64::from_be_bytes(value),
+        }
+      });
+
+      AnnotatedField {
+        name: field.name,
+        offset: offset + field.offset,
+        bytes,
+        value,
+        decoded: value.and_then(|value| decode(field.name, value, e_machine as u16, self.osabi())),
+      }
+    });
+    Some(fields.collect())
+  }
+}
+
+///
+/// Returns the constant or the flags of a field value, `None` when unknown or
+/// not an enumeration. The OS-specific types depend on the OS ABI, the
+/// processor-specific object file types on the machine.
+///
+fn decode(field: &str, value: u64, machine: u16, osabi: u8) -> Option<String> {
+  fn known<Type: Copy>(constant: Constant<Type>) -> Option<String>
+  where
+    Constant<Type>: fmt::Display,
+  {
+    constant.name().map(|_| constant.to_string())
+  }
+
+  match field {
+    "ei_class" => known(abi::ei_class::from(value as u8)),
+    "ei_data" => known(abi::ei_data::from(value as u8)),
+    "ei_version" | "e_version" => known(abi::e_version::from(value as u32)),
+    "ei_osabi" => known(abi::ei_osabi::from(value as u8)),
+    "e_type" => known(abi::object_type(machine, osabi, value as u16)),
+    "e_machine" => known(abi::e_machine::from(value as u16)),
+    "sh_type" => known(abi::section_type(osabi, value as u32)),
+    "p_type" => known(abi::segment_type(osabi, value as u32)),
+    "sh_flags" if value != 0 => Some(abi::sh_flags::from(value).to_string()),
+    "p_flags" if value != 0 => Some(abi::p_flags::from(value as u32).to_string()),
+    _ => None,
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+/// One line per field: the file offset, the bytes as stored, the name and the value.
+impl fmt::Display for Annotated<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use crate::utils::display_table;
+
+    display_table!(formatter, ["Offset", "Bytes", "Field", "Value"], field in &self.fields => [
+      format!("{:#x}", field.offset),
+      field.bytes.iter().map(|byte| format!("{:02x}", byte)).collect::<Vec<_>>().join(" "),
+      field.name,
+      match (field.value, &field.decoded) {
+        (Some(value), Some(decoded)) => format!("{:#x} ({})", value, decoded),
+        (Some(value), None) => format!("{:#x}", value),
+        (None, _) => String::new(),
+      },
+    ])
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::elf::abi::sh_type::SHT_PROGBITS;
+  use crate::elf::builder::ElfBuilder;
+
+  #[test]
+  fn header_fields() {
+    let bytes = header_fixture();
+    let Ok(ElfFile::Elf32Be(object)) = parse_elf(bytes.as_slice()) else {
+      panic!("Expected a big endian elf32");
+    };
+
+    let annotated = object.annotate_header();
+    let names = annotated
+      .fields
+      .iter()
+      .map(|field| field.name)
+      .collect::<Vec<_>>();
+    assert_eq!(names.len(), 10 + 13, "e_ident flattened");
+    assert_eq!(names[..3], ["ei_mag0", "ei_mag1", "ei_mag2"]);
+    assert_eq!(names[names.len() - 1], "e_shstrndx");
+
+    let output = annotated.to_string();
+    let lines = output.lines().collect::<Vec<_>>();
+    assert_eq!(lines[0], "Offset  Bytes                 Field          Value");
+    assert_eq!(
+      lines[5],
+      "0x4     01                    ei_class       0x1 (ELFCLASS32)"
+    );
+    assert_eq!(lines[10], "0x9     00 00 00 00 00 00 00  ei_pad");
+    assert_eq!(
+      lines[12],
+      "0x12    01 02                 e_machine      0x102 (EM_LOONGARCH)"
+    );
+    assert_eq!(
+      lines[13],
+      "0x14    00 00 00 01           e_version      0x1 (EV_CURRENT)"
+    );
+    assert_eq!(
+      lines[14],
+      "0x18    01 02 03 04           e_entry        0x1020304"
+    );
+  }
+
+  #[test]
+  fn corrupted_header() {
+    // e_ehsize past the header, e_type unknown.
+    let mut bytes = ElfBuilder::<ElfType64<LittleEndian>>::new().build();
+    let (e_type, e_ehsize) = (ELF64_HEADER.e_type, ELF64_HEADER.e_ehsize);
+    bytes[e_type..e_type + 2].copy_from_slice(&0x1234_u16.to_le_bytes());
+    bytes[e_ehsize..e_ehsize + 2].copy_from_slice(&0x48_u16.to_le_bytes());
+
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    let annotated = object.annotate_header();
+    let field = |name| annotated.fields.iter().find(|field| field.name == name).unwrap();
+
+    assert_eq!(field("e_type").bytes, [0x34, 0x12], "Stored little-endian");
+    assert_eq!(
+      (field("e_type").value, field("e_type").decoded.as_deref()),
+      (Some(0x1234), None)
+    );
+    assert_eq!(
+      (field("e_ehsize").offset, field("e_ehsize").value),
+      (0x34, Some(0x48))
+    );
+    assert_eq!((field("(extra)").offset, field("(extra)").bytes.len()), (0x40, 8));
+  }
+
+  #[test]
+  fn header_offsets() {
+    // The annotated fields and the patching constants agree, in both classes.
+    let bytes = header_fixture();
+    let Ok(ElfFile::Elf32Be(object)) = parse_elf(bytes.as_slice()) else {
+      panic!("Expected a big endian elf32");
+    };
+    let annotated = object.annotate_header();
+    let offset = |name| {
+      annotated
+        .fields
+        .iter()
+        .find(|field| field.name == name)
+        .unwrap()
+        .offset
+    };
+    assert_eq!(offset("ei_osabi"), EI_OSABI);
+    assert_eq!(offset("e_shoff"), ELF32_HEADER.e_shoff);
+
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new().build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    let annotated = object.annotate_header();
+    let offset = |name| {
+      annotated
+        .fields
+        .iter()
+        .find(|field| field.name == name)
+        .unwrap()
+        .offset
+    };
+    assert_eq!(offset("e_shoff"), ELF64_HEADER.e_shoff);
+    assert_eq!(offset("e_shstrndx"), ELF64_HEADER.e_shstrndx);
+  }
+
+  #[test]
+  fn section_and_segment_fields() {
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .object_type(abi::e_type::ET_EXEC)
+      .section(".text", SHT_PROGBITS, &[0xC3; 0x10])
+      .segment(segments::PT_LOAD, 5, &[1])
+      .build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let section = object.annotate_section(1).unwrap();
+    let e_shoff = u64::from(object.header().e_shoff) as usize;
+    let field = |name| {
+      section
+        .fields
+        .iter()
+        .find(|field| field.name == name)
+        .unwrap()
+        .clone()
+    };
+    assert_eq!(section.fields.len(), 10);
+    assert_eq!(field("sh_type").offset, e_shoff + 0x40 + 4);
+    assert_eq!(field("sh_type").decoded.as_deref(), Some("SHT_PROGBITS"));
+    assert_eq!(field("sh_size").value, Some(0x10));
+    assert_eq!(object.annotate_section(3), None);
+
+    // p_flags follows p_type in elf64.
+    let segment = object.annotate_segment(0).unwrap();
+    let names = segment.fields.iter().map(|field| field.name).collect::<Vec<_>>();
+    assert_eq!(names[..2], ["p_type", "p_flags"]);
+    assert_eq!(segment.fields[1].offset, 0x44);
+    assert_eq!(segment.fields[1].decoded.as_deref(), Some("PF_X | PF_R"));
+    assert_eq!(object.annotate_segment(1), None);
+  }
+}
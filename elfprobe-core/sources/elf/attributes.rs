@@ -0,0 +1,732 @@
+use ::core::fmt;
+
+use super::abi::e_machine::{EM_ARM, EM_RISCV};
+use super::notes::read_unsigned;
+use super::strings::printable;
+use super::*;
+use crate::reader::Reader;
+use crate::utils::{define_constants, read_uleb128, Constant};
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+/// ARM attributes section.
+pub const SHT_ARM_ATTRIBUTES: u32 = 0x70000003;
+
+/// RISC-V attributes section (same value as `SHT_ARM_ATTRIBUTES`).
+pub const SHT_RISCV_ATTRIBUTES: u32 = 0x70000003;
+
+/// Format version of the attributes section.
+const FORMAT_VERSION: u8 = b'A';
+
+define_constants! {
+  /// Scope of an attributes sub-subsection.
+  pub attribute_scope: u64 {
+    Tag_File = 1 => "Attributes of the whole file",
+    Tag_Section = 2 => "Attributes of the listed sections",
+    Tag_Symbol = 3 => "Attributes of the listed symbols",
+  }
+}
+
+define_constants! {
+  /// Public `aeabi` attributes, see the ARM "Addenda to, and Errata in, the
+  /// ABI for the Arm Architecture".
+  pub tag_aeabi: u64 {
+    Tag_CPU_raw_name = 4 => "Raw name of the target CPU",
+    Tag_CPU_name = 5 => "Name of the target CPU",
+    Tag_CPU_arch = 6 => "Target architecture",
+    Tag_CPU_arch_profile = 7 => "Architecture profile",
+    Tag_ARM_ISA_use = 8 => "ARM instructions usage",
+    Tag_THUMB_ISA_use = 9 => "Thumb instructions usage",
+    Tag_FP_arch = 10 => "Floating point architecture",
+    Tag_WMMX_arch = 11 => "WMMX architecture",
+    Tag_Advanced_SIMD_arch = 12 => "Advanced SIMD architecture",
+    Tag_PCS_config = 13 => "Procedure call standard configuration",
+    Tag_ABI_PCS_R9_use = 14 => "Usage of R9",
+    Tag_ABI_PCS_RW_data = 15 => "Addressing of RW static data",
+    Tag_ABI_PCS_RO_data = 16 => "Addressing of RO static data",
+    Tag_ABI_PCS_GOT_use = 17 => "Addressing of imported data",
+    Tag_ABI_PCS_wchar_t = 18 => "Size of wchar_t",
+    Tag_ABI_FP_rounding = 19 => "Floating point rounding mode",
+    Tag_ABI_FP_denormal = 20 => "Floating point denormal numbers",
+    Tag_ABI_FP_exceptions = 21 => "Floating point exceptions",
+    Tag_ABI_FP_user_exceptions = 22 => "Floating point user exceptions",
+    Tag_ABI_FP_number_model = 23 => "Floating point number model",
+    Tag_ABI_align_needed = 24 => "Alignment of 8-byte data needed",
+    Tag_ABI_align_preserved = 25 => "Alignment of 8-byte data preserved",
+    Tag_ABI_enum_size = 26 => "Size of enums",
+    Tag_ABI_HardFP_use = 27 => "Hardware floating point usage",
+    Tag_ABI_VFP_args = 28 => "Floating point parameters passing",
+    Tag_ABI_WMMX_args = 29 => "WMMX parameters passing",
+    Tag_ABI_optimization_goals = 30 => "Optimization goals",
+    Tag_ABI_FP_optimization_goals = 31 => "Floating point optimization goals",
+    Tag_compatibility = 32 => "Toolchain compatibility",
+    Tag_CPU_unaligned_access = 34 => "Unaligned accesses",
+    Tag_FP_HP_extension = 36 => "Half-precision floating point extension",
+    Tag_ABI_FP_16bit_format = 38 => "Half-precision floating point format",
+    Tag_MPextension_use = 42 => "Multiprocessing extension usage",
+    Tag_DIV_use = 44 => "Integer division instructions usage",
+    Tag_DSP_extension = 46 => "DSP extension usage",
+    Tag_MVE_arch = 48 => "M-profile vector extension",
+    Tag_PAC_extension = 50 => "Pointer authentication extension",
+    Tag_BTI_extension = 52 => "Branch target identification extension",
+    Tag_nodefaults = 64 => "No defaults",
+    Tag_also_compatible_with = 65 => "Also compatible with",
+    Tag_T2EE_use = 66 => "ThumbEE usage",
+    Tag_conformance = 67 => "ABI conformance",
+    Tag_Virtualization_use = 68 => "Virtualization extensions usage",
+    Tag_MPextension_use_legacy = 70 => "Multiprocessing extension usage (legacy)",
+    Tag_BTI_use = 74 => "Branch target identification usage",
+    Tag_PACRET_use = 76 => "Pointer authentication usage",
+  }
+}
+
+define_constants! {
+  /// Values of `Tag_CPU_arch`.
+  pub aeabi_cpu_arch: u64 {
+    Pre_v4 = 0 => "Pre-v4",
+    v4 = 1 => "ARM v4",
+    v4T = 2 => "ARM v4T",
+    v5T = 3 => "ARM v5T",
+    v5TE = 4 => "ARM v5TE",
+    v5TEJ = 5 => "ARM v5TEJ",
+    v6 = 6 => "ARM v6",
+    v6KZ = 7 => "ARM v6KZ",
+    v6T2 = 8 => "ARM v6T2",
+    v6K = 9 => "ARM v6K",
+    v7 = 10 => "ARM v7",
+    v6_M = 11 => "ARM v6-M",
+    v6S_M = 12 => "ARM v6S-M",
+    v7E_M = 13 => "ARM v7E-M",
+    v8_A = 14 => "ARM v8-A",
+    v8_R = 15 => "ARM v8-R",
+    v8_M_baseline = 16 => "ARM v8-M baseline",
+    v8_M_mainline = 17 => "ARM v8-M mainline",
+    v8_1_A = 18 => "ARM v8.1-A",
+    v8_2_A = 19 => "ARM v8.2-A",
+    v8_3_A = 20 => "ARM v8.3-A",
+    v8_1_M_mainline = 21 => "ARM v8.1-M mainline",
+    v9_A = 22 => "ARM v9-A",
+  }
+}
+
+define_constants! {
+  /// Values of `Tag_THUMB_ISA_use`.
+  pub aeabi_thumb_isa_use: u64 {
+    Thumb_none = 0 => "Thumb instructions were not permitted",
+    Thumb_1 = 1 => "Thumb-1 instructions were permitted",
+    Thumb_2 = 2 => "Thumb-2 instructions were permitted",
+    Thumb_allowed = 3 => "Thumb instructions were permitted (architecture-defined)",
+  }
+}
+
+define_constants! {
+  /// Values of `Tag_FP_arch`.
+  pub aeabi_fp_arch: u64 {
+    FP_none = 0 => "No floating point instructions",
+    VFPv1 = 1 => "VFPv1",
+    VFPv2 = 2 => "VFPv2",
+    VFPv3 = 3 => "VFPv3",
+    VFPv3_D16 = 4 => "VFPv3-D16",
+    VFPv4 = 5 => "VFPv4",
+    VFPv4_D16 = 6 => "VFPv4-D16",
+    FP_ARMv8 = 7 => "ARMv8-A FP",
+    FPv5_D16 = 8 => "FPv5-D16 (ARMv8-R / ARMv8-M)",
+  }
+}
+
+define_constants! {
+  /// Values of `Tag_ABI_VFP_args`.
+  pub aeabi_vfp_args: u64 {
+    VFP_args_base = 0 => "AAPCS (soft-float, core registers)",
+    VFP_args_vfp = 1 => "AAPCS VFP (hard-float, VFP registers)",
+    VFP_args_toolchain = 2 => "Toolchain-specific",
+    VFP_args_compatible = 3 => "Compatible with both base and VFP",
+  }
+}
+
+define_constants! {
+  /// Public `riscv` attributes, see the RISC-V ELF psABI.
+  pub tag_riscv: u64 {
+    Tag_RISCV_stack_align = 4 => "Stack alignment (in bytes)",
+    Tag_RISCV_arch = 5 => "Target ISA string",
+    Tag_RISCV_unaligned_access = 6 => "Unaligned memory accesses",
+    Tag_RISCV_priv_spec = 8 => "Privileged specification (major version)",
+    Tag_RISCV_priv_spec_minor = 10 => "Privileged specification (minor version)",
+    Tag_RISCV_priv_spec_revision = 12 => "Privileged specification (revision)",
+    Tag_RISCV_atomic_abi = 14 => "Atomic ABI",
+    Tag_RISCV_x3_reg_usage = 16 => "Usage of the x3 (gp) register",
+  }
+}
+
+define_constants! {
+  /// Values of `Tag_RISCV_atomic_abi`.
+  pub riscv_atomic_abi: u64 {
+    ATOMIC_ABI_UNKNOWN = 0 => "Unknown",
+    ATOMIC_ABI_A6C = 1 => "A6C (compatible with A6S and A7)",
+    ATOMIC_ABI_A6S = 2 => "A6S",
+    ATOMIC_ABI_A7 = 3 => "A7",
+  }
+}
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+///
+/// Value of a build attribute, its type is given by the tag (and the vendor)
+/// as the format is not self-describing.
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AttributeValue<'data> {
+  /// ULEB128 integer.
+  Integer(u64),
+  /// NUL-terminated string (without its NUL).
+  String(&'data [u8]),
+  /// ULEB128 integer followed by a NUL-terminated string (`Tag_compatibility`).
+  Compatibility(u64, &'data [u8]),
+}
+
+/// A single build attribute (tag/value pair).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Attribute<'data> {
+  pub tag: u64,
+  pub value: AttributeValue<'data>,
+}
+
+///
+/// A group of attributes sharing the same vendor (`aeabi`, `riscv`, `gnu`...)
+/// and scope (`Tag_File`, `Tag_Section` or `Tag_Symbol`). Section and symbol
+/// scopes also list the indices they apply to.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AttributeSubsection<'data> {
+  pub vendor: &'data [u8],
+  pub scope: u64,
+  pub indices: Vec<u64>,
+  pub attributes: Vec<Attribute<'data>>,
+}
+
+impl<'data> AttributeSubsection<'data> {
+  /// Returns the value of the first attribute with the given tag.
+  pub fn get(&self, tag: u64) -> Option<AttributeValue<'data>> {
+    self
+      .attributes
+      .iter()
+      .find(|attribute| attribute.tag == tag)
+      .map(|attribute| attribute.value)
+  }
+
+  /// Returns the name of the given tag for this vendor.
+  pub fn tag_name(&self, tag: u64) -> Constant<u64> {
+    match self.vendor {
+      b"aeabi" => tag_aeabi::from(tag),
+      b"riscv" => tag_riscv::from(tag),
+      _ => Constant::unknown(tag),
+    }
+  }
+
+  /// Returns the name of the given value when the tag is an enumeration.
+  pub fn value_name(&self, tag: u64, value: u64) -> Option<Constant<u64>> {
+    match (self.vendor, tag) {
+      (b"aeabi", tag_aeabi::Tag_CPU_arch) => Some(aeabi_cpu_arch::from(value)),
+      (b"aeabi", tag_aeabi::Tag_THUMB_ISA_use) => Some(aeabi_thumb_isa_use::from(value)),
+      (b"aeabi", tag_aeabi::Tag_FP_arch) => Some(aeabi_fp_arch::from(value)),
+      (b"aeabi", tag_aeabi::Tag_ABI_VFP_args) => Some(aeabi_vfp_args::from(value)),
+      (b"riscv", tag_riscv::Tag_RISCV_atomic_abi) => Some(riscv_atomic_abi::from(value)),
+      _ => None,
+    }
+  }
+}
+
+///
+/// RISC-V ISA string (`Tag_RISCV_arch`), e.g. `rv64i2p1_m2p0_c2p0_zicsr2p0`
+/// or the shorter `rv64gc`: the base ISA followed by extensions, each one
+/// optionally suffixed by its version (`<major>p<minor>`).
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RiscvIsa<'data>(&'data str);
+
+impl<'data> RiscvIsa<'data> {
+  /// Returns the whole ISA string.
+  pub fn as_str(&self) -> &'data str {
+    self.0
+  }
+
+  /// Returns the register width (`32`, `64` or `128`), `None` if malformed.
+  pub fn xlen(&self) -> Option<u32> {
+    let digits = self.0.strip_prefix("rv")?;
+    let end = digits.find(|char: char| !char.is_ascii_digit())?;
+    digits[..end].parse().ok()
+  }
+
+  ///
+  /// Returns the extension names without their version, single-letter
+  /// extensions first (including the base ISA, `i`, `e` or `g`).
+  ///
+  pub fn extensions(&self) -> Vec<&'data str> {
+    let Some(xlen) = self.xlen() else {
+      return Vec::new();
+    };
+
+    let isa = &self.0["rv".len() + xlen.to_string().len()..];
+    let mut extensions = Vec::new();
+
+    for (index, component) in isa.split('_').enumerate() {
+      match component.as_bytes().first() {
+        Some(b'z' | b's' | b'x') if index > 0 => {
+          extensions.push(strip_version(component));
+        }
+        Some(_) => {
+          // Single-letter extensions may be concatenated (`imafdc`).
+          let mut rest = component;
+          while let Some(letter) = rest.get(..1) {
+            let version = rest[1..].len()
+              - rest[1..]
+                .trim_start_matches(|char: char| char.is_ascii_digit())
+                .len();
+            let next = &rest[1 + version..];
+            rest = match next.strip_prefix('p') {
+              Some(minor) if version > 0 && minor.starts_with(|char: char| char.is_ascii_digit()) => {
+                minor.trim_start_matches(|char: char| char.is_ascii_digit())
+              }
+              _ => next,
+            };
+            extensions.push(letter);
+          }
+        }
+        None => (),
+      }
+    }
+
+    extensions
+  }
+
+  /// Returns true if the given extension is present, `g` implies `imafd`.
+  pub fn has_extension(&self, name: &str) -> bool {
+    let extensions = self.extensions();
+    extensions.contains(&name)
+      || (extensions.contains(&"g") && matches!(name, "i" | "m" | "a" | "f" | "d" | "zicsr" | "zifencei"))
+  }
+}
+
+/// Strips the `<major>p<minor>` (or `<major>`) version suffix of an extension.
+fn strip_version(extension: &str) -> &str {
+  let is_digit = |char: char| char.is_ascii_digit();
+  let name = extension.trim_end_matches(is_digit);
+  name
+    .strip_suffix('p')
+    .filter(|major| major.ends_with(is_digit))
+    .map_or(name, |major| major.trim_end_matches(is_digit))
+}
+
+/// Type of a value, given by the vendor and the tag.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum ValueKind {
+  Integer,
+  String,
+  Compatibility,
+}
+
+impl ValueKind {
+  ///
+  /// Generic rule (`gnu`, `riscv`...): even tags are integers and odd tags
+  /// are strings. The `aeabi` vendor follows it from tag 32 onwards only.
+  ///
+  fn of(vendor: &[u8], tag: u64) -> Self {
+    match (vendor, tag) {
+      (b"aeabi", tag_aeabi::Tag_CPU_raw_name | tag_aeabi::Tag_CPU_name) => Self::String,
+      (b"aeabi", tag_aeabi::Tag_compatibility) => Self::Compatibility,
+      (b"aeabi", ..32) => Self::Integer,
+      (_, tag) if tag % 2 == 0 => Self::Integer,
+      _ => Self::String,
+    }
+  }
+}
+
+// ╔═╗┌─┐┬─┐┌─┐┌─┐┬─┐
+// ╠═╝├─┤├┬┘└─┐├┤ ├┬┘
+// ╩  ┴ ┴┴└─└─┘└─┘┴└─
+
+///
+/// Parses a build attributes section:
+///
+/// ```text
+/// 'A' ; Format version
+/// ( <length: u32> <vendor: NTBS> ; Vendor subsection
+///   ( <scope: ULEB128> <length: u32> [<index: ULEB128>* 0] ; Sub-subsection
+///     ( <tag: ULEB128> <value: ULEB128 | NTBS> )*
+///   )*
+/// )*
+/// ```
+///
+/// Lengths include their own field (and the scope tag for sub-subsections).
+/// Parsing stops at the first malformed (or truncated) entry, what has been
+/// read so far is kept.
+///
+pub fn parse_attributes<E: Endianness>(bytes: &[u8]) -> Vec<AttributeSubsection<'_>> {
+  let mut subsections = Vec::new();
+  if bytes.first() != Some(&FORMAT_VERSION) {
+    return subsections;
+  }
+
+  let mut offset = 1;
+  while let Some(length) = read_unsigned::<E>(bytes, offset, 4) {
+    let Some(vendor) = bytes
+      .get(offset..offset.saturating_add(length as usize))
+      .filter(|_| length > 4)
+    else {
+      break;
+    };
+
+    offset += vendor.len();
+    if !parse_vendor::<E>(&vendor[4..], &mut subsections) {
+      break;
+    }
+  }
+
+  subsections
+}
+
+/// Parses a vendor subsection (without its length), returns false on error.
+fn parse_vendor<'data, E: Endianness>(
+  bytes: &'data [u8],
+  subsections: &mut Vec<AttributeSubsection<'data>>,
+) -> bool {
+  let Some(end) = bytes.iter().position(|&byte| byte == 0) else {
+    return false;
+  };
+
+  let vendor = &bytes[..end];
+  let mut offset = end + 1;
+
+  while offset < bytes.len() {
+    let start = offset;
+    let Some(scope) = read_uleb128(bytes, &mut offset) else {
+      return false;
+    };
+
+    let Some(length) = read_unsigned::<E>(bytes, offset, 4) else {
+      return false;
+    };
+
+    let Some(content) = bytes.get(offset + 4..start.saturating_add(length as usize)) else {
+      return false;
+    };
+
+    offset = start + length as usize;
+    let mut subsection = AttributeSubsection {
+      vendor,
+      scope,
+      ..Default::default()
+    };
+    if parse_subsection(content, &mut subsection).is_none() {
+      subsections.push(subsection);
+      return false;
+    }
+
+    subsections.push(subsection);
+  }
+
+  true
+}
+
+/// Parses the indices (if any) and the attributes of a sub-subsection.
+fn parse_subsection<'data>(bytes: &'data [u8], subsection: &mut AttributeSubsection<'data>) -> Option<()> {
+  let mut offset = 0;
+
+  if subsection.scope != attribute_scope::Tag_File {
+    loop {
+      match read_uleb128(bytes, &mut offset)? {
+        0 => break,
+        index => subsection.indices.push(index),
+      }
+    }
+  }
+
+  let string = |offset: &mut usize| {
+    let string = bytes.get(*offset..)?;
+    let end = string.iter().position(|&byte| byte == 0)?;
+    *offset += end + 1;
+    Some(&string[..end])
+  };
+
+  while offset < bytes.len() {
+    let tag = read_uleb128(bytes, &mut offset)?;
+    let value = match ValueKind::of(subsection.vendor, tag) {
+      ValueKind::Integer => AttributeValue::Integer(read_uleb128(bytes, &mut offset)?),
+      ValueKind::String => AttributeValue::String(string(&mut offset)?),
+      ValueKind::Compatibility => {
+        let flag = read_uleb128(bytes, &mut offset)?;
+        AttributeValue::Compatibility(flag, string(&mut offset)?)
+      }
+    };
+
+    subsection.attributes.push(Attribute { tag, value });
+  }
+
+  Some(())
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the build attributes of the processor-specific attributes
+  /// section (`SHT_ARM_ATTRIBUTES` for `EM_ARM`, `SHT_RISCV_ATTRIBUTES` for
+  /// `EM_RISCV`), empty for other machines.
+  ///
+  pub fn attributes(&self) -> Vec<AttributeSubsection<'data>> {
+    let machine: u64 = self.header.e_machine.into();
+    let sh_type = match machine as u16 {
+      EM_ARM => SHT_ARM_ATTRIBUTES,
+      EM_RISCV => SHT_RISCV_ATTRIBUTES,
+      _ => return Vec::new(),
+    };
+
+    let sections = self.section_headers().unwrap_or_default();
+    let section = sections
+      .into_iter()
+      .find(|section| section.sh_type() == sh_type as u64);
+
+    match section.map(|section| self.section_bytes(section)) {
+      Some(Ok(bytes)) => parse_attributes::<ElfType::Endian>(bytes),
+      _ => Vec::new(),
+    }
+  }
+
+  /// Returns the ISA string of a RISC-V object (`Tag_RISCV_arch`).
+  pub fn riscv_isa(&self) -> Option<RiscvIsa<'data>> {
+    let subsection = self
+      .attributes()
+      .into_iter()
+      .find(|subsection| subsection.vendor == b"riscv" && subsection.scope == attribute_scope::Tag_File)?;
+
+    match subsection.get(tag_riscv::Tag_RISCV_arch)? {
+      AttributeValue::String(string) => ::core::str::from_utf8(string).ok().map(RiscvIsa),
+      _ => None,
+    }
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+impl fmt::Display for AttributeSubsection<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      formatter,
+      "  Vendor: {}, {}",
+      printable(self.vendor),
+      attribute_scope::from(self.scope)
+    )?;
+
+    match self.indices.is_empty() {
+      true => writeln!(formatter)?,
+      false => writeln!(formatter, " {:?}", self.indices)?,
+    }
+
+    for attribute in &self.attributes {
+      write!(formatter, "    {}: ", self.tag_name(attribute.tag))?;
+      match attribute.value {
+        AttributeValue::Integer(value) => {
+          match self
+            .value_name(attribute.tag, value)
+            .and_then(|name| name.meaning())
+          {
+            Some(meaning) => writeln!(formatter, "{} ({})", value, meaning)?,
+            None => writeln!(formatter, "{}", value)?,
+          }
+        }
+        AttributeValue::String(string) => writeln!(formatter, "\"{}\"", printable(string))?,
+        AttributeValue::Compatibility(flag, vendor) => {
+          writeln!(formatter, "flag = {}, vendor = \"{}\"", flag, printable(vendor))?
+        }
+      }
+    }
+
+    Ok(())
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+
+  fn fixture() -> Vec<u8> {
+    hex(
+      r"
+        'A                        ; Format version
+        28000000 'aeabi 00        ; Vendor subsection
+        01 1E000000               ; Tag_File
+        05 'cortex-a9 00          ; Tag_CPU_name
+        06 0A                     ; Tag_CPU_arch
+        09 02                     ; Tag_THUMB_ISA_use
+        1C 01                     ; Tag_ABI_VFP_args
+        20 01 'gnu 00             ; Tag_compatibility
+        62 07                     ; Unknown (even)
+        0F000000 'gnu 00          ; Vendor subsection
+        02 06000000 03 00         ; Tag_Section
+        FF000000 'bad 00          ; Truncated
+      ",
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn parse_attributes() {
+    let bytes = fixture();
+    let subsections = super::parse_attributes::<LittleEndian>(&bytes);
+    assert_eq!(subsections.len(), 2);
+
+    let aeabi = &subsections[0];
+    assert_eq!(aeabi.vendor, b"aeabi");
+    assert_eq!(aeabi.scope, attribute_scope::Tag_File);
+    assert_eq!(
+      aeabi.attributes,
+      vec![
+        Attribute {
+          tag: 5,
+          value: AttributeValue::String(b"cortex-a9")
+        },
+        Attribute {
+          tag: 6,
+          value: AttributeValue::Integer(10)
+        },
+        Attribute {
+          tag: 9,
+          value: AttributeValue::Integer(2)
+        },
+        Attribute {
+          tag: 28,
+          value: AttributeValue::Integer(1)
+        },
+        Attribute {
+          tag: 32,
+          value: AttributeValue::Compatibility(1, b"gnu")
+        },
+        Attribute {
+          tag: 98,
+          value: AttributeValue::Integer(7)
+        },
+      ]
+    );
+    assert_eq!(
+      aeabi.get(tag_aeabi::Tag_CPU_arch),
+      Some(AttributeValue::Integer(10))
+    );
+
+    let gnu = &subsections[1];
+    assert_eq!(gnu.vendor, b"gnu");
+    assert_eq!(gnu.scope, attribute_scope::Tag_Section);
+    assert_eq!(gnu.indices, vec![3]);
+    assert!(gnu.attributes.is_empty());
+  }
+
+  #[test]
+  fn parse_attributes_version() {
+    assert!(super::parse_attributes::<LittleEndian>(b"B").is_empty());
+    assert!(super::parse_attributes::<LittleEndian>(b"").is_empty());
+  }
+
+  #[test]
+  fn value_kind() {
+    assert_eq!(ValueKind::of(b"aeabi", 5), ValueKind::String);
+    assert_eq!(ValueKind::of(b"aeabi", 7), ValueKind::Integer);
+    assert_eq!(ValueKind::of(b"aeabi", 65), ValueKind::String);
+    assert_eq!(ValueKind::of(b"riscv", 5), ValueKind::String);
+    assert_eq!(ValueKind::of(b"riscv", 4), ValueKind::Integer);
+  }
+
+  #[test]
+  fn display() {
+    let bytes = fixture();
+    let subsections = super::parse_attributes::<LittleEndian>(&bytes);
+    assert_eq!(
+      subsections[0].to_string(),
+      concat!(
+        "  Vendor: aeabi, Tag_File\n",
+        "    Tag_CPU_name: \"cortex-a9\"\n",
+        "    Tag_CPU_arch: 10 (ARM v7)\n",
+        "    Tag_THUMB_ISA_use: 2 (Thumb-2 instructions were permitted)\n",
+        "    Tag_ABI_VFP_args: 1 (AAPCS VFP (hard-float, VFP registers))\n",
+        "    Tag_compatibility: flag = 1, vendor = \"gnu\"\n",
+        "    0x62: 7\n",
+      )
+    );
+    assert_eq!(subsections[1].to_string(), "  Vendor: gnu, Tag_Section [3]\n");
+  }
+
+  #[test]
+  fn parse_riscv_attributes() {
+    let bytes = hex(
+      r"
+        'A                        ; Format version
+        2B000000 'riscv 00        ; Vendor subsection
+        01 21000000               ; Tag_File
+        04 10                     ; Tag_RISCV_stack_align
+        05 'rv64i2p1_c2p0_zicsr2p0 00 ; Tag_RISCV_arch
+        0E 01                     ; Tag_RISCV_atomic_abi
+      ",
+    )
+    .unwrap();
+
+    let subsections = super::parse_attributes::<LittleEndian>(&bytes);
+    assert_eq!(subsections.len(), 1);
+    assert_eq!(
+      subsections[0].get(tag_riscv::Tag_RISCV_arch),
+      Some(AttributeValue::String(b"rv64i2p1_c2p0_zicsr2p0"))
+    );
+    assert_eq!(
+      subsections[0].to_string(),
+      concat!(
+        "  Vendor: riscv, Tag_File\n",
+        "    Tag_RISCV_stack_align: 16\n",
+        "    Tag_RISCV_arch: \"rv64i2p1_c2p0_zicsr2p0\"\n",
+        "    Tag_RISCV_atomic_abi: 1 (A6C (compatible with A6S and A7))\n",
+      )
+    );
+  }
+
+  #[test]
+  fn riscv_isa() {
+    let isa = RiscvIsa("rv64i2p1_m2p0_a2p1_c2p0_zicsr2p0_zifencei2p0_xtheadba1p0");
+    assert_eq!(isa.xlen(), Some(64));
+    assert_eq!(
+      isa.extensions(),
+      vec!["i", "m", "a", "c", "zicsr", "zifencei", "xtheadba"]
+    );
+    assert!(isa.has_extension("c"));
+    assert!(!isa.has_extension("f"));
+
+    let isa = RiscvIsa("rv32gcv_zba");
+    assert_eq!(isa.xlen(), Some(32));
+    assert_eq!(isa.extensions(), vec!["g", "c", "v", "zba"]);
+    assert!(isa.has_extension("d"), "Implied by g");
+
+    let isa = RiscvIsa("rv32i2pp");
+    assert_eq!(isa.extensions(), vec!["i", "p", "p"], "Not a version");
+    assert_eq!(RiscvIsa("x86").extensions(), Vec::<&str>::new());
+  }
+
+  #[test]
+  fn strip_version() {
+    assert_eq!(super::strip_version("zicsr2p0"), "zicsr");
+    assert_eq!(super::strip_version("zicsr2"), "zicsr");
+    assert_eq!(super::strip_version("zvl128b"), "zvl128b");
+    assert_eq!(super::strip_version("zvl128b1p0"), "zvl128b");
+  }
+}
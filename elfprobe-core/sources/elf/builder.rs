@@ -0,0 +1,603 @@
+use ::core::marker::PhantomData;
+
+use super::abi::sh_type::{SHT_NOBITS, SHT_STRTAB};
+use super::*;
+
+// ╔═╗┌─┐┌─┐┌┬┐┬┌─┐┌┐┌
+// ╚═╗├┤ │   │ ││ ││││
+// ╚═╝└─┘└─┘ ┴ ┴└─┘┘└┘
+
+///
+/// A section of an [`ElfBuilder`], the offset is computed when the image is
+/// built and the size is the length of the data unless overridden (e.g.
+/// `SHT_NOBITS` sections have a size but no data). The address of a section
+/// loaded by a segment, but the first one, is computed when left to zero.
+///
+#[derive(Debug, Clone, Default)]
+pub struct BuilderSection {
+  pub name: String,
+  pub sh_type: u32,
+  pub sh_flags: u64,
+  pub sh_addr: u64,
+  pub sh_link: u32,
+  pub sh_info: u32,
+  pub sh_addralign: u64,
+  pub sh_entsize: u64,
+  pub data: Vec<u8>,
+  pub size: Option<u64>,
+}
+
+///
+/// A segment of an [`ElfBuilder`] covering the given sections (by index,
+/// index 0 being the null section), in file order.
+///
+#[derive(Debug, Clone, Default)]
+struct BuilderSegment {
+  pub p_type: u32,
+  pub p_flags: u32,
+  pub p_align: u64,
+  pub sections: Vec<usize>,
+}
+
+// ╔╗ ┬ ┬┬┬  ┌┬┐┌─┐┬─┐
+// ╠╩╗│ │││   ││├┤ ├┬┘
+// ╚═╝└─┘┴┴─┘─┴┘└─┘┴└─
+
+///
+/// Builds minimal ELF images in memory (stubs, fixtures): the header, the
+/// program header table, the section contents (aligned), the section header
+/// string table (`.shstrtab`, generated) then the section header table. The
+/// class and the data encoding are the ones of `ElfType`.
+///
+/// The image always parses back with [`parse_elf()`], checked in debug
+/// builds. For the tests, corruptions are injected on purpose by overriding
+/// header fields or by truncating the image (the `corrupt_*` and `truncate`
+/// methods), the check is then skipped.
+///
+/// ```
+/// use elfprobe_core::elf::{p_flags, p_type, sh_flags, sh_type, BuilderSection, ElfBuilder};
+/// use elfprobe_core::prelude::*;
+///
+/// // A do-nothing shared object: `DT_SONAME`, `DT_STRTAB`, `DT_STRSZ` and `DT_NULL`.
+/// let mut dynamic = Vec::new();
+/// for (d_tag, d_val) in [(14u64, 1u64), (5, 0x1000), (10, 14), (0, 0)] {
+///   dynamic.extend(d_tag.to_le_bytes());
+///   dynamic.extend(d_val.to_le_bytes());
+/// }
+/// let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+///   .object_type(3) // ET_DYN
+///   .add_section(BuilderSection {
+///     name: String::from(".dynstr"),
+///     sh_type: sh_type::SHT_STRTAB,
+///     sh_flags: sh_flags::SHF_ALLOC,
+///     sh_addr: 0x1000,
+///     data: b"\0libstub.so.1\0".to_vec(),
+///     ..Default::default()
+///   })
+///   .add_section(BuilderSection {
+///     name: String::from(".dynamic"),
+///     sh_type: sh_type::SHT_DYNAMIC,
+///     sh_flags: sh_flags::SHF_ALLOC | sh_flags::SHF_WRITE,
+///     sh_link: 1,
+///     sh_addralign: 8,
+///     sh_entsize: 16,
+///     data: dynamic,
+///     ..Default::default()
+///   })
+///   .segment(p_type::PT_LOAD, p_flags::PF_R | p_flags::PF_W, &[1, 2])
+///   .segment(p_type::PT_DYNAMIC, p_flags::PF_R | p_flags::PF_W, &[2])
+///   .build();
+///
+/// let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice())? else {
+///   unreachable!();
+/// };
+/// assert_eq!(object.soname(), Some("libstub.so.1"));
+/// # Ok::<(), ElfError>(())
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct ElfBuilder<ElfType: self::ElfType> {
+  e_type: u16,
+  e_machine: u16,
+  e_entry: u64,
+  sections: Vec<BuilderSection>,
+  segments: Vec<BuilderSegment>,
+  e_shoff: Option<u64>,
+  e_shnum: Option<u16>,
+  e_shentsize: Option<u16>,
+  e_shstrndx: Option<u16>,
+  length: Option<usize>,
+  elf_type: PhantomData<ElfType>,
+}
+
+impl<ElfType: self::ElfType> Default for ElfBuilder<ElfType> {
+  fn default() -> Self {
+    Self {
+      e_type: 1,     // ET_REL
+      e_machine: 62, // EM_X86_64
+      e_entry: 0,
+      sections: Vec::new(),
+      segments: Vec::new(),
+      e_shoff: None,
+      e_shnum: None,
+      e_shentsize: None,
+      e_shstrndx: None,
+      length: None,
+      elf_type: PhantomData,
+    }
+  }
+}
+
+impl<ElfType: self::ElfType> ElfBuilder<ElfType> {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  fn is_64() -> bool {
+    ElfType::CLASS == abi::ei_class::ELFCLASS64
+  }
+
+  /// Sets the object file type (`e_type`), `ET_REL` by default.
+  pub fn object_type(&mut self, e_type: u16) -> &mut Self {
+    self.e_type = e_type;
+    self
+  }
+
+  /// Sets the target machine (`e_machine`), `EM_X86_64` by default.
+  pub fn machine(&mut self, e_machine: u16) -> &mut Self {
+    self.e_machine = e_machine;
+    self
+  }
+
+  /// Sets the entry point (`e_entry`).
+  pub fn entry(&mut self, e_entry: u64) -> &mut Self {
+    self.e_entry = e_entry;
+    self
+  }
+
+  /// Appends a section with the given contents, its index is the number of sections so far.
+  pub fn section(&mut self, name: &str, sh_type: u32, data: &[u8]) -> &mut Self {
+    self.add_section(BuilderSection {
+      name: String::from(name),
+      sh_type,
+      data: data.to_vec(),
+      ..Default::default()
+    })
+  }
+
+  /// Appends a fully described section.
+  pub fn add_section(&mut self, section: BuilderSection) -> &mut Self {
+    self.sections.push(section);
+    self
+  }
+
+  ///
+  /// Appends a segment covering the sections at the given indexes (index 0
+  /// is the null section, the first one added is 1), in file order.
+  ///
+  pub fn segment(&mut self, p_type: u32, p_flags: u32, sections: &[usize]) -> &mut Self {
+    self.segments.push(BuilderSegment {
+      p_type,
+      p_flags,
+      p_align: 1,
+      sections: sections.to_vec(),
+    });
+    self
+  }
+
+  /// Overrides the section header table offset (`e_shoff`).
+  #[cfg(test)]
+  pub fn corrupt_shoff(&mut self, e_shoff: u64) -> &mut Self {
+    self.e_shoff = Some(e_shoff);
+    self
+  }
+
+  /// Overrides the number of section headers (`e_shnum`).
+  #[cfg(test)]
+  pub fn corrupt_shnum(&mut self, e_shnum: u16) -> &mut Self {
+    self.e_shnum = Some(e_shnum);
+    self
+  }
+
+  /// Overrides the section header size (`e_shentsize`).
+  #[cfg(test)]
+  pub fn corrupt_shentsize(&mut self, e_shentsize: u16) -> &mut Self {
+    self.e_shentsize = Some(e_shentsize);
+    self
+  }
+
+  /// Overrides the section header string table index (`e_shstrndx`).
+  #[cfg(test)]
+  pub fn corrupt_shstrndx(&mut self, e_shstrndx: u16) -> &mut Self {
+    self.e_shstrndx = Some(e_shstrndx);
+    self
+  }
+
+  /// Truncates the image to the given length once built.
+  #[cfg(test)]
+  pub fn truncate(&mut self, length: usize) -> &mut Self {
+    self.length = Some(length);
+    self
+  }
+
+  ///
+  /// Lays out and returns the image. Sections are aligned on their
+  /// `sh_addralign`, the tables on the size of an address.
+  ///
+  pub fn build(&self) -> Vec<u8> {
+    let (ehsize, phentsize, shentsize) = match Self::is_64() {
+      true => (64, 56, 64),
+      false => (52, 32, 40),
+    };
+
+    // Section header string table, generated and appended.
+    let mut shstrtab = vec![0u8];
+    let mut names = Vec::new();
+    for section in &self.sections {
+      names.push(shstrtab.len() as u32);
+      shstrtab.extend_from_slice(section.name.as_bytes());
+      shstrtab.push(0);
+    }
+    let shstrtab_name = shstrtab.len() as u32;
+    shstrtab.extend_from_slice(b".shstrtab\0");
+
+    let mut sections = self.sections.clone();
+    sections.push(BuilderSection {
+      name: String::from(".shstrtab"),
+      sh_type: SHT_STRTAB,
+      sh_addralign: 1,
+      data: shstrtab,
+      ..Default::default()
+    });
+    names.push(shstrtab_name);
+
+    // Layout.
+    let word = if Self::is_64() { 8 } else { 4 };
+    let phoff = if self.segments.is_empty() { 0 } else { ehsize };
+    let mut length = ehsize + self.segments.len() * phentsize;
+    let mut offsets = Vec::new();
+    for section in &sections {
+      length = length.next_multiple_of(section.sh_addralign.max(1) as usize);
+      offsets.push(length);
+      if section.sh_type != SHT_NOBITS {
+        length += section.data.len();
+      }
+    }
+    let shoff = length.next_multiple_of(word);
+    let shnum = sections.len() + 1; // Null section included.
+
+    // Addresses of the sections following the first one of a segment, at the same distance as in the file.
+    for segment in &self.segments {
+      for &index in &segment.sections {
+        assert!(
+          (1..=self.sections.len()).contains(&index),
+          "a segment covers section {} of {}",
+          index,
+          self.sections.len()
+        );
+      }
+      let Some(&first) = segment.sections.first() else {
+        continue;
+      };
+      let (base, vaddr) = (offsets[first - 1], sections[first - 1].sh_addr);
+      for &index in &segment.sections[1..] {
+        if sections[index - 1].sh_addr == 0 && offsets[index - 1] >= base {
+          sections[index - 1].sh_addr = vaddr + (offsets[index - 1] - base) as u64;
+        }
+      }
+    }
+
+    // Header.
+    let mut output = Output::<ElfType>::default();
+    output.bytes(&[0x7F, b'E', b'L', b'F', ElfType::CLASS, ElfType::data(), 1, 0]);
+    output.bytes(&[0; 8]);
+    output.half(self.e_type);
+    output.half(self.e_machine);
+    output.word(1); // e_version
+    output.address(self.e_entry);
+    output.address(phoff as u64);
+    output.address(self.e_shoff.unwrap_or(shoff as u64));
+    output.word(0); // e_flags
+    output.half(ehsize as u16);
+    output.half(phentsize as u16);
+    output.half(self.segments.len() as u16);
+    output.half(self.e_shentsize.unwrap_or(shentsize as u16));
+    output.half(self.e_shnum.unwrap_or(shnum as u16));
+    output.half(self.e_shstrndx.unwrap_or(sections.len() as u16));
+
+    // Program headers, the file range of their sections.
+    for segment in &self.segments {
+      let range = |index: usize| {
+        let section = &sections[index - 1];
+        let size = match section.sh_type {
+          SHT_NOBITS => 0,
+          _ => section.data.len(),
+        };
+        (offsets[index - 1], offsets[index - 1] + size, section)
+      };
+      let ranges = segment
+        .sections
+        .iter()
+        .map(|&index| range(index))
+        .collect::<Vec<_>>();
+      let start = ranges.iter().map(|range| range.0).min().unwrap_or(0) as u64;
+      // The alignment of a trailing SHT_NOBITS section does not extend the file bytes.
+      let end = ranges
+        .iter()
+        .filter(|range| range.2.sh_type != SHT_NOBITS)
+        .map(|range| range.1 as u64)
+        .max()
+        .unwrap_or(start);
+      let vaddr = ranges.first().map_or(start, |range| range.2.sh_addr);
+      let memsz = ranges
+        .iter()
+        .map(|range| range.2.sh_addr + range.2.size.unwrap_or(range.2.data.len() as u64))
+        .max()
+        .map_or(0, |end| end.saturating_sub(vaddr));
+      let filesz = end - start;
+
+      output.word(segment.p_type);
+      if Self::is_64() {
+        output.word(segment.p_flags);
+      }
+      output.address(start);
+      output.address(vaddr);
+      output.address(vaddr);
+      output.address(filesz);
+      output.address(memsz.max(filesz));
+      if !Self::is_64() {
+        output.word(segment.p_flags);
+      }
+      output.address(segment.p_align);
+    }
+
+    // Section contents.
+    for (section, &offset) in sections.iter().zip(&offsets) {
+      output.pad(offset);
+      if section.sh_type != SHT_NOBITS {
+        output.bytes(&section.data);
+      }
+    }
+
+    // Section headers.
+    output.pad(shoff);
+    output.bytes(&vec![0; shentsize]);
+    for ((section, &offset), &name) in sections.iter().zip(&offsets).zip(&names) {
+      output.word(name);
+      output.word(section.sh_type);
+      output.address(section.sh_flags);
+      output.address(section.sh_addr);
+      output.address(offset as u64);
+      output.address(section.size.unwrap_or(section.data.len() as u64));
+      output.word(section.sh_link);
+      output.word(section.sh_info);
+      output.address(section.sh_addralign);
+      output.address(section.sh_entsize);
+    }
+
+    let mut bytes = output.bytes;
+    if let Some(length) = self.length {
+      bytes.truncate(length);
+    }
+    debug_assert!(
+      self.is_corrupted() || Self::parses_back(&bytes),
+      "the image does not parse back"
+    );
+    bytes
+  }
+
+  /// Returns `true` when a header field is overridden or the image truncated.
+  fn is_corrupted(&self) -> bool {
+    self.e_shoff.is_some()
+      || self.e_shnum.is_some()
+      || self.e_shentsize.is_some()
+      || self.e_shstrndx.is_some()
+      || self.length.is_some()
+  }
+
+  ///
+  /// Returns `true` when the image parses back strictly with [`parse_elf()`]:
+  /// the header, the tables and the contents of every section.
+  ///
+  fn parses_back(bytes: &[u8]) -> bool {
+    let strict = ParseOptions::default();
+    let Ok(object) = ElfObject::<_, ElfType>::parse(bytes, strict) else {
+      return false;
+    };
+    let sections = object.section_headers().unwrap_or_default();
+    parse_elf(bytes).is_ok()
+      && object.program_headers().is_ok()
+      && object.section_headers().is_ok()
+      && object.section_names().is_ok()
+      && sections
+        .iter()
+        .all(|section| object.section_bytes(section).is_ok())
+  }
+}
+
+// ╔═╗┬ ┬┌┬┐┌─┐┬ ┬┌┬┐
+// ║ ║│ │ │ ├─┘│ │ │
+// ╚═╝└─┘ ┴ ┴  └─┘ ┴
+
+/// Writes the fields in the byte order and with the address size of the type.
+struct Output<ElfType: self::ElfType> {
+  bytes: Vec<u8>,
+  elf_type: PhantomData<ElfType>,
+}
+
+impl<ElfType: self::ElfType> Default for Output<ElfType> {
+  fn default() -> Self {
+    Self {
+      bytes: Vec::new(),
+      elf_type: PhantomData,
+    }
+  }
+}
+
+impl<ElfType: self::ElfType> Output<ElfType> {
+  fn bytes(&mut self, bytes: &[u8]) {
+    self.bytes.extend_from_slice(bytes);
+  }
+
+  fn pad(&mut self, offset: usize) {
+    self.bytes.resize(offset, 0);
+  }
+
+  fn half(&mut self, value: u16) {
+    match ElfType::Endian::is_little_endian() {
+      true => self.bytes(&value.to_le_bytes()),
+      false => self.bytes(&value.to_be_bytes()),
+    }
+  }
+
+  fn word(&mut self, value: u32) {
+    match ElfType::Endian::is_little_endian() {
+      true => self.bytes(&value.to_le_bytes()),
+      false => self.bytes(&value.to_be_bytes()),
+    }
+  }
+
+  /// Addresses, offsets and sizes (`Addr`, `Off` and `Xword`).
+  fn address(&mut self, value: u64) {
+    match (
+      ElfBuilder::<ElfType>::is_64(),
+      ElfType::Endian::is_little_endian(),
+    ) {
+      (true, true) => self.bytes(&value.to_le_bytes()),
+      (true, false) => self.bytes(&value.to_be_bytes()),
+      (false, true) => self.bytes(&(value as u32).to_le_bytes()),
+      (false, false) => self.bytes(&(value as u32).to_be_bytes()),
+    }
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::elf::p_type::PT_LOAD;
+  use crate::elf::sh_type::SHT_PROGBITS;
+
+  fn roundtrip<ElfType: self::ElfType>() {
+    let bytes = ElfBuilder::<ElfType>::new()
+      .section(".text", SHT_PROGBITS, &[0xC3; 3])
+      .add_section(BuilderSection {
+        name: String::from(".data"),
+        sh_type: SHT_PROGBITS,
+        sh_addr: 0x2000,
+        sh_addralign: 8,
+        data: vec![1, 2, 3, 4],
+        ..Default::default()
+      })
+      .section(".bss", SHT_NOBITS, &[])
+      .segment(PT_LOAD, 6, &[2])
+      .build();
+
+    let object = ElfObject::<_, ElfType>::parse(bytes.as_slice(), ParseOptions::default()).unwrap();
+    let names = object
+      .sections()
+      .map(|section| section.display_name().unwrap_or_default())
+      .collect::<Vec<_>>();
+    assert_eq!(names, ["", ".text", ".data", ".bss", ".shstrtab"]);
+
+    let sections = object.section_headers().unwrap();
+    assert_eq!(object.section_bytes(sections[1]).unwrap(), [0xC3; 3]);
+    assert_eq!(object.section_bytes(sections[2]).unwrap(), [1, 2, 3, 4]);
+    assert_eq!(sections[2].sh_offset() % 8, 0);
+
+    let segments = object.program_headers().unwrap();
+    assert_eq!(segments.len(), 1);
+    assert_eq!(segments[0].p_offset(), sections[2].sh_offset());
+    assert_eq!((segments[0].p_vaddr(), segments[0].p_filesz()), (0x2000, 4));
+  }
+
+  #[test]
+  fn elf_builder() {
+    roundtrip::<ElfType32<BigEndian>>();
+    roundtrip::<ElfType64<BigEndian>>();
+    roundtrip::<ElfType32<LittleEndian>>();
+    roundtrip::<ElfType64<LittleEndian>>();
+  }
+
+  #[test]
+  fn segment_addresses() {
+    let section = |name: &str, sh_addr, sh_addralign, data: &[u8]| BuilderSection {
+      name: String::from(name),
+      sh_type: SHT_PROGBITS,
+      sh_flags: abi::sh_flags::SHF_ALLOC,
+      sh_addr,
+      sh_addralign,
+      data: data.to_vec(),
+      ..Default::default()
+    };
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .object_type(abi::e_type::ET_EXEC)
+      .add_section(section(".text", 0x401000, 1, &[0xC3; 3]))
+      .add_section(section(".rodata", 0, 16, &[1; 5]))
+      .add_section(BuilderSection {
+        sh_type: SHT_NOBITS,
+        size: Some(0x20),
+        ..section(".bss", 0, 8, &[])
+      })
+      .segment(PT_LOAD, 5, &[1, 2, 3])
+      .build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    // At the same distance from .text as in the file, .bss past the file bytes.
+    let sections = object.section_headers().unwrap();
+    let delta = |index: usize| sections[index].sh_offset() - sections[1].sh_offset();
+    assert_eq!(sections[2].sh_addr(), 0x401000 + delta(2));
+    assert_eq!(sections[3].sh_addr(), 0x401000 + delta(3));
+
+    let segment = object.program_headers().unwrap()[0];
+    assert_eq!(segment.p_filesz(), delta(2) + 5);
+    assert_eq!(segment.p_memsz(), delta(3) + 0x20);
+    assert_eq!(object.cross_check().unwrap().divergences, []);
+  }
+
+  #[test]
+  #[should_panic(expected = "the image does not parse back")]
+  #[cfg(debug_assertions)]
+  fn parses_back() {
+    // More bytes than the data, past the end of the file.
+    ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .add_section(BuilderSection {
+        name: String::from(".text"),
+        sh_type: SHT_PROGBITS,
+        size: Some(0x10000),
+        ..Default::default()
+      })
+      .build();
+  }
+
+  #[test]
+  fn corruptions() {
+    let mut builder = ElfBuilder::<ElfType64<LittleEndian>>::new();
+    builder.section(".text", SHT_PROGBITS, &[0xC3]);
+    let length = builder.build().len();
+
+    let bytes = builder.clone().corrupt_shoff(0xFFFF).build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert!(object.section_headers().is_err());
+
+    let Ok(ElfFile::Elf64Le(object)) = parse_elf_with(bytes.as_slice(), ParseOptions::LENIENT) else {
+      unreachable!();
+    };
+    assert_eq!(object.section_headers().map(|headers| headers.len()), Ok(0));
+    assert!(!object.diagnostics().is_empty());
+
+    let bytes = builder.clone().truncate(length - 1).build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert!(object.section_headers().is_err());
+    assert_eq!(object.sections().count(), 2, "The last header is cut");
+  }
+}
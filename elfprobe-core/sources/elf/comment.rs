@@ -0,0 +1,141 @@
+use alloc::borrow::Cow;
+
+use super::strings::printable;
+use super::*;
+use crate::reader::Reader;
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the entries of the `.comment` section (toolchain provenance such
+  /// as `GCC: (Debian 12.2.0-14) 12.2.0` or `rustc version 1.80.0`), in file
+  /// order and without duplicates (each object file brings its own copy).
+  ///
+  /// The section is a sequence of NUL-separated strings, a missing final NUL
+  /// is tolerated, invalid UTF-8 bytes and control characters are escaped, see [`printable()`].
+  ///
+  pub fn comments(&self) -> Vec<Cow<'data, str>> {
+    let Some(section) = self.find_section(".comment") else {
+      return Vec::new();
+    };
+
+    let Ok(bytes) = self.section_bytes(section) else {
+      return Vec::new();
+    };
+
+    let mut comments: Vec<Cow<'data, str>> = Vec::new();
+    for comment in bytes
+      .split(|&byte| byte == 0)
+      .filter(|comment| !comment.is_empty())
+    {
+      let comment = printable(comment);
+      if !comments.contains(&comment) {
+        comments.push(comment);
+      }
+    }
+
+    comments
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+
+  ///
+  /// ELF64 little-endian with a null section, a `.comment` section holding
+  /// the given bytes and the section header string table.
+  ///
+  fn fixture(comment: &str) -> Vec<u8> {
+    let mut comment = hex(comment).unwrap();
+    let size = format!("{:016x}", (comment.len() as u64).swap_bytes());
+    comment.resize(0x40, 0);
+
+    let mut bytes = hex(
+      r"
+        7F 'ELF 02 01 01 00 00 00000000 000000 ; e_ident
+        0100 3E00 01000000 ; e_type, e_machine, e_version
+        0000000000000000 ; e_entry
+        0000000000000000 ; e_phoff
+        A000000000000000 ; e_shoff
+        00000000 4000 ; e_flags, e_ehsize
+        3800 0000 ; e_phentsize, e_phnum
+        4000 0300 0200 ; e_shentsize, e_shnum, e_shstrndx
+      ",
+    )
+    .unwrap();
+
+    bytes.extend(comment); // 0x40
+    bytes.extend(hex("00 '.comment 00 '.shstrtab 00 000000000000").unwrap()); // 0x80
+    bytes.resize(0xA0, 0);
+    bytes.extend([0; 0x40]); // Null section.
+    bytes.extend(
+      hex(&format!(
+        r"
+          01000000 01000000 ; sh_name, sh_type (SHT_PROGBITS)
+          3000000000000000 ; sh_flags (SHF_MERGE | SHF_STRINGS)
+          0000000000000000 ; sh_addr
+          4000000000000000 ; sh_offset
+          {size} ; sh_size
+          00000000 00000000 ; sh_link, sh_info
+          0100000000000000 ; sh_addralign
+          0100000000000000 ; sh_entsize
+
+          0A000000 03000000 ; sh_name, sh_type (SHT_STRTAB)
+          0000000000000000 ; sh_flags
+          0000000000000000 ; sh_addr
+          8000000000000000 ; sh_offset
+          1400000000000000 ; sh_size
+          00000000 00000000 ; sh_link, sh_info
+          0100000000000000 ; sh_addralign
+          0000000000000000 ; sh_entsize
+        "
+      ))
+      .unwrap(),
+    );
+
+    bytes
+  }
+
+  fn comments(bytes: &[u8]) -> Vec<Cow<'_, str>> {
+    let ElfFile::Elf64Le(object) = parse_elf(bytes).unwrap() else {
+      unreachable!();
+    };
+
+    object.comments()
+  }
+
+  #[test]
+  fn comments_ok() {
+    let bytes = fixture("'GCC: 00 'rustc 00 00 'GCC: 00");
+    assert_eq!(comments(&bytes), vec!["GCC:", "rustc"]);
+  }
+
+  #[test]
+  fn comments_edge_cases() {
+    // Missing final NUL.
+    let bytes = fixture("'GCC: 00 'clang");
+    assert_eq!(comments(&bytes), vec!["GCC:", "clang"]);
+
+    // Non UTF-8 bytes.
+    let bytes = fixture("'GCC FF 00");
+    assert_eq!(comments(&bytes), vec!["GCC\\xFF"]);
+
+    // No .comment section.
+    let mut bytes = fixture("'GCC: 00");
+    bytes[0x81] = b'_';
+    assert!(comments(&bytes).is_empty());
+  }
+}
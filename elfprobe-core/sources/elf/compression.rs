@@ -0,0 +1,339 @@
+use ::core::mem::size_of;
+use alloc::borrow::Cow;
+
+use super::sections::SHF_COMPRESSED;
+use super::*;
+use crate::error::ElfError;
+use crate::reader::Reader;
+use elfprobe_macro::Pod;
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+/// ZLIB/DEFLATE algorithm.
+pub const ELFCOMPRESS_ZLIB: u32 = 1;
+
+/// Zstandard algorithm.
+pub const ELFCOMPRESS_ZSTD: u32 = 2;
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Pod)]
+pub struct Elf32CompressionHeader<E: Endianness> {
+  pub ch_type: Elf32_Word<E>,
+  pub ch_size: Elf32_Word<E>,
+  pub ch_addralign: Elf32_Word<E>,
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Pod)]
+pub struct Elf64CompressionHeader<E: Endianness> {
+  pub ch_type: Elf64_Word<E>,
+  pub ch_reserved: Elf64_Word<E>,
+  pub ch_size: Elf64_Xword<E>,
+  pub ch_addralign: Elf64_Xword<E>,
+}
+
+///
+/// Compression header (`Elf_Chdr`) found at the beginning of `SHF_COMPRESSED`
+/// sections. The elf64 version has a reserved field, this trait gives a common
+/// access to both of them.
+///
+pub trait CompressionHeader: Type {
+  fn ch_type(&self) -> u32;
+  fn ch_size(&self) -> u64;
+  fn ch_addralign(&self) -> u64;
+}
+
+/// The compression header of the given ELF type.
+pub type ElfCompressionHeader<ElfType> = <ElfType as self::ElfType>::CompressionHeader;
+
+macro_rules! impl_compression_header {
+  ($struct: ident) => {
+    impl<E: Endianness> CompressionHeader for $struct<E> {
+      #[inline]
+      fn ch_type(&self) -> u32 {
+        self.ch_type.get()
+      }
+
+      #[inline]
+      fn ch_size(&self) -> u64 {
+        self.ch_size.into()
+      }
+
+      #[inline]
+      fn ch_addralign(&self) -> u64 {
+        self.ch_addralign.into()
+      }
+    }
+  };
+}
+
+impl_compression_header!(Elf32CompressionHeader);
+impl_compression_header!(Elf64CompressionHeader);
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the compression header of the given section, `None` is returned
+  /// when the section does not have the `SHF_COMPRESSED` flag.
+  ///
+  pub fn compression_header(
+    &self,
+    section: &ElfSection<ElfType>,
+  ) -> Result<Option<&'data ElfCompressionHeader<ElfType>>, ElfError> {
+    if section.sh_flags() & SHF_COMPRESSED == 0 {
+      return Ok(None);
+    }
+
+    let bytes = self.section_bytes(section)?;
+    Ok(Some(bytes.read_pod::<ElfCompressionHeader<ElfType>>(0)?))
+  }
+
+  ///
+  /// Returns the (decompressed) data of the given section.
+  ///
+  /// Sections without the `SHF_COMPRESSED` flag are returned as is (without
+  /// any copy, see [`Self::section_bytes()`]), otherwise the section data is
+  /// decompressed according to the compression header (`ch_type`) and its
+  /// length is checked against `ch_size`.
+  ///
+  /// Decompression requires the `zlib` and/or `zstd` features (both enabled
+  /// by the `compression` feature), other algorithms are reported as
+  /// [`ElfError::UnsupportedCompression`]. The decompressed data is a nested
+  /// input of the [`Limits`](super::Limits), `ch_size` is checked against them.
+  ///
+  pub fn section_data(&self, section: &ElfSection<ElfType>) -> Result<Cow<'data, [u8]>, ElfError> {
+    let bytes = self.section_bytes(section)?;
+    let Some(header) = self.compression_header(section)? else {
+      return Ok(Cow::Borrowed(bytes));
+    };
+
+    self.options().nested()?;
+    self.options().limits.check_bytes(header.ch_size())?;
+    let compressed = &bytes[size_of::<ElfCompressionHeader<ElfType>>()..];
+    let data = decompress(header.ch_type(), compressed, header.ch_size())?;
+
+    if data.len() as u64 != header.ch_size() {
+      return Err(ElfError::DecompressedSizeMismatch {
+        expected: header.ch_size(),
+        actual: data.len() as u64,
+      });
+    }
+
+    Ok(Cow::Owned(data))
+  }
+}
+
+/// Decompresses the given bytes according to `ch_type`.
+#[cfg_attr(not(any(feature = "zlib", feature = "zstd")), allow(unused_variables))]
+fn decompress(ch_type: u32, bytes: &[u8], size: u64) -> Result<Vec<u8>, ElfError> {
+  match ch_type {
+    #[cfg(feature = "zlib")]
+    ELFCOMPRESS_ZLIB => decompress_zlib(bytes, size),
+    #[cfg(feature = "zstd")]
+    ELFCOMPRESS_ZSTD => decompress_zstd(bytes, size),
+    _ => Err(ElfError::UnsupportedCompression(ch_type)),
+  }
+}
+
+///
+/// Inflates a zlib stream, the output is limited to one byte more than the
+/// expected size so that a lying `ch_size` is detected without inflating the
+/// whole stream (zip bombs).
+///
+#[cfg(feature = "zlib")]
+fn decompress_zlib(bytes: &[u8], size: u64) -> Result<Vec<u8>, ElfError> {
+  use miniz_oxide::inflate::{decompress_to_vec_zlib_with_limit, TINFLStatus};
+
+  let limit = usize::try_from(size).unwrap_or(usize::MAX).saturating_add(1);
+  match decompress_to_vec_zlib_with_limit(bytes, limit) {
+    Ok(data) => Ok(data),
+    Err(error) if error.status == TINFLStatus::HasMoreOutput => Ok(error.output),
+    Err(error) => Err(ElfError::DecompressionFailed(error.to_string())),
+  }
+}
+
+/// Decodes a zstd frame, the output is limited the same way as zlib.
+#[cfg(feature = "zstd")]
+fn decompress_zstd(bytes: &[u8], size: u64) -> Result<Vec<u8>, ElfError> {
+  use ruzstd::decoding::StreamingDecoder;
+  use std::io::Read;
+
+  let decoder =
+    StreamingDecoder::new(bytes).map_err(|error| ElfError::DecompressionFailed(error.to_string()))?;
+  let mut data = Vec::new();
+  decoder
+    .take(size.saturating_add(1))
+    .read_to_end(&mut data)
+    .map_err(|error| ElfError::DecompressionFailed(error.to_string()))?;
+  Ok(data)
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+
+  #[test]
+  fn compression_header_memory_size() {
+    assert_eq!(size_of::<Elf32CompressionHeader<BigEndian>>(), 12);
+    assert_eq!(size_of::<Elf64CompressionHeader<BigEndian>>(), 24);
+    assert_eq!(size_of::<Elf32CompressionHeader<LittleEndian>>(), 12);
+    assert_eq!(size_of::<Elf64CompressionHeader<LittleEndian>>(), 24);
+  }
+
+  ///
+  /// ELF64 little-endian with a single (compressed) section whose data is
+  /// stored right after the ELF header, followed by the (aligned) section header
+  /// table.
+  ///
+  fn fixture(flags: u64, data: &[u8]) -> Vec<u8> {
+    let size = format!("{:016x}", (data.len() as u64).swap_bytes());
+    let padding = data.len().next_multiple_of(8) - data.len(); // Aligned section headers.
+    let shoff = format!("{:016x}", (0x40 + (data.len() + padding) as u64).swap_bytes());
+    let flags = format!("{:016x}", flags.swap_bytes());
+
+    let mut bytes = hex(&format!(
+      r"
+        7F 'ELF 02 01 01 00 00 00000000 000000 ; e_ident
+        0100 3E00 01000000 ; e_type, e_machine, e_version
+        0000000000000000 ; e_entry
+        0000000000000000 ; e_phoff
+        {shoff} ; e_shoff
+        00000000 4000 ; e_flags, e_ehsize
+        3800 0000 ; e_phentsize, e_phnum
+        4000 0100 0000 ; e_shentsize, e_shnum, e_shstrndx
+      "
+    ))
+    .unwrap();
+
+    bytes.extend_from_slice(data);
+    bytes.resize(bytes.len() + padding, 0);
+    bytes.extend(
+      hex(&format!(
+        r"
+          00000000 01000000 ; sh_name, sh_type (SHT_PROGBITS)
+          {flags} ; sh_flags
+          0000000000000000 ; sh_addr
+          4000000000000000 ; sh_offset
+          {size} ; sh_size
+          00000000 00000000 ; sh_link, sh_info
+          0100000000000000 ; sh_addralign
+          0000000000000000 ; sh_entsize
+        "
+      ))
+      .unwrap(),
+    );
+
+    bytes
+  }
+
+  fn section_data(bytes: &[u8]) -> Result<Cow<'_, [u8]>, ElfError> {
+    let ElfFile::Elf64Le(object) = parse_elf(bytes).unwrap() else {
+      unreachable!();
+    };
+
+    let sections = object.section_headers().unwrap();
+    object.section_data(sections[0])
+  }
+
+  /// Prepends an `Elf64_Chdr` to the given data.
+  fn compressed(ch_type: u32, ch_size: u64, data: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend(ch_type.to_le_bytes());
+    bytes.extend(0u32.to_le_bytes()); // ch_reserved
+    bytes.extend(ch_size.to_le_bytes());
+    bytes.extend(1u64.to_le_bytes()); // ch_addralign
+    bytes.extend(data);
+    bytes
+  }
+
+  #[test]
+  fn uncompressed_section() {
+    let bytes = fixture(0, b"Hello");
+    let data = section_data(&bytes).unwrap();
+    assert!(matches!(data, Cow::Borrowed(b"Hello")));
+  }
+
+  #[test]
+  fn unsupported_compression() {
+    let bytes = fixture(SHF_COMPRESSED, &compressed(0x42, 5, b"Hello"));
+    assert_eq!(section_data(&bytes), Err(ElfError::UnsupportedCompression(0x42)));
+  }
+
+  #[test]
+  #[cfg(feature = "zlib")]
+  fn zlib_compression() {
+    use miniz_oxide::deflate::compress_to_vec_zlib;
+
+    let payload = b"Hello world! ".repeat(42);
+    let zlib = compress_to_vec_zlib(&payload, 6);
+
+    let bytes = fixture(
+      SHF_COMPRESSED,
+      &compressed(ELFCOMPRESS_ZLIB, payload.len() as u64, &zlib),
+    );
+    assert_eq!(section_data(&bytes).unwrap(), payload.as_slice());
+
+    let bytes = fixture(SHF_COMPRESSED, &compressed(ELFCOMPRESS_ZLIB, 42, &zlib));
+    assert_eq!(
+      section_data(&bytes),
+      Err(ElfError::DecompressedSizeMismatch {
+        expected: 42,
+        actual: 43, // The output is limited to ch_size + 1.
+      })
+    );
+
+    let bytes = fixture(SHF_COMPRESSED, &compressed(ELFCOMPRESS_ZLIB, 42, b"garbage"));
+    assert!(matches!(
+      section_data(&bytes),
+      Err(ElfError::DecompressionFailed(_))
+    ));
+  }
+
+  #[test]
+  #[cfg(feature = "zstd")]
+  fn zstd_compression() {
+    use ruzstd::encoding::{compress_to_vec, CompressionLevel};
+
+    let payload = b"Hello world! ".repeat(42);
+    let zstd = compress_to_vec(payload.as_slice(), CompressionLevel::Fastest);
+
+    let bytes = fixture(
+      SHF_COMPRESSED,
+      &compressed(ELFCOMPRESS_ZSTD, payload.len() as u64, &zstd),
+    );
+    assert_eq!(section_data(&bytes).unwrap(), payload.as_slice());
+
+    let bytes = fixture(SHF_COMPRESSED, &compressed(ELFCOMPRESS_ZSTD, 42, &zstd));
+    assert_eq!(
+      section_data(&bytes),
+      Err(ElfError::DecompressedSizeMismatch {
+        expected: 42,
+        actual: 43, // The output is limited to ch_size + 1.
+      })
+    );
+
+    let bytes = fixture(SHF_COMPRESSED, &compressed(ELFCOMPRESS_ZSTD, 42, b"garbage"));
+    assert!(matches!(
+      section_data(&bytes),
+      Err(ElfError::DecompressionFailed(_))
+    ));
+  }
+}
@@ -0,0 +1,512 @@
+use ::core::fmt;
+use ::core::mem::size_of;
+
+use super::abi::e_machine::{EM_386, EM_AARCH64, EM_ARM, EM_X86_64};
+use super::notes::read_unsigned;
+use super::strings::printable;
+use super::*;
+use crate::error::BytesError;
+use crate::reader::Reader;
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+pub use super::notes::nt_core::{NT_FILE, NT_PRPSINFO, NT_PRSTATUS};
+
+/// General purpose registers of `user_regs_struct` (x86-64).
+const X86_64_REGISTERS: &[&str] = &[
+  "r15", "r14", "r13", "r12", "rbp", "rbx", "r11", "r10", "r9", "r8", "rax", "rcx", "rdx", "rsi", "rdi",
+  "orig_rax", "rip", "cs", "eflags", "rsp", "ss", "fs_base", "gs_base", "ds", "es", "fs", "gs",
+];
+
+/// General purpose registers of `user_regs_struct` (i386).
+const I386_REGISTERS: &[&str] = &[
+  "ebx", "ecx", "edx", "esi", "edi", "ebp", "eax", "xds", "xes", "xfs", "xgs", "orig_eax", "eip", "xcs",
+  "eflags", "esp", "xss",
+];
+
+/// General purpose registers of `user_pt_regs` (aarch64).
+const AARCH64_REGISTERS: &[&str] = &[
+  "x0", "x1", "x2", "x3", "x4", "x5", "x6", "x7", "x8", "x9", "x10", "x11", "x12", "x13", "x14", "x15",
+  "x16", "x17", "x18", "x19", "x20", "x21", "x22", "x23", "x24", "x25", "x26", "x27", "x28", "x29", "x30",
+  "sp", "pc", "pstate",
+];
+
+/// General purpose registers of `user_regs` (arm).
+const ARM_REGISTERS: &[&str] = &[
+  "r0", "r1", "r2", "r3", "r4", "r5", "r6", "r7", "r8", "r9", "r10", "fp", "ip", "sp", "lr", "pc", "cpsr",
+  "orig_r0",
+];
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+///
+/// A general purpose register, the name is only known for some architectures
+/// (x86-64, i386, aarch64 and arm). The value is decoded from the raw bytes
+/// according to the file endianness.
+///
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Register<'data> {
+  pub name: Option<&'static str>,
+  pub bytes: &'data [u8],
+  pub value: u64,
+}
+
+/// Process status (`NT_PRSTATUS`), one per thread.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PrStatus<'data> {
+  /// Current signal (`pr_cursig`).
+  pub signal: u16,
+  pub pid: u32,
+  pub ppid: u32,
+  pub pgrp: u32,
+  pub sid: u32,
+  /// General purpose registers (`pr_reg`), grouped per register.
+  pub registers: Vec<Register<'data>>,
+}
+
+/// Process information (`NT_PRPSINFO`).
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct PrPsInfo<'data> {
+  /// Numeric process state (`pr_state`).
+  pub state: u8,
+  /// Char for `pr_state` (`R`, `S`, `D`...).
+  pub sname: u8,
+  pub zombie: u8,
+  pub nice: i8,
+  pub flags: u64,
+  pub uid: u32,
+  pub gid: u32,
+  pub pid: u32,
+  pub ppid: u32,
+  pub pgrp: u32,
+  pub sid: u32,
+  /// Executable name (`pr_fname`) without its trailing NULs.
+  pub name: &'data [u8],
+  /// Initial part of the argument list (`pr_psargs`) without its trailing NULs.
+  pub args: &'data [u8],
+}
+
+/// A file mapped in memory when the core was dumped.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct MappedFile<'data> {
+  pub start: u64,
+  pub end: u64,
+  /// Offset within the file in bytes (the note stores it in pages).
+  pub offset: u64,
+  pub path: &'data [u8],
+}
+
+/// Mapped files (`NT_FILE`).
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FileTable<'data> {
+  pub page_size: u64,
+  pub files: Vec<MappedFile<'data>>,
+}
+
+/// A decoded core note (named `CORE`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoreNote<'data> {
+  PrStatus(PrStatus<'data>),
+  PrPsInfo(PrPsInfo<'data>),
+  File(FileTable<'data>),
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the decoded core notes (`NT_PRSTATUS`, `NT_PRPSINFO` and
+  /// `NT_FILE`), other notes and truncated descriptors are skipped.
+  ///
+  /// `elf_prstatus` and `elf_prpsinfo` layouts are the Linux generic ones,
+  /// `long` being 32 or 64 bits depending on the file class.
+  ///
+  pub fn core_notes(&self) -> Result<Vec<CoreNote<'data>>, ElfError> {
+    let machine: u64 = self.header.e_machine.into();
+    let decoder = CoreDecoder::<ElfType::Endian> {
+      word: size_of::<ElfType::Xword>(),
+      machine: machine as u16,
+      endian: PhantomData,
+    };
+
+    let notes = self.notes()?.into_iter().filter(|note| note.name == b"CORE");
+    Ok(notes.filter_map(|note| decoder.decode(&note)).collect())
+  }
+}
+
+/// Decodes core notes according to the `long` size and the architecture.
+struct CoreDecoder<E: Endianness> {
+  word: usize,
+  machine: u16,
+  endian: PhantomData<E>,
+}
+
+impl<E: Endianness> CoreDecoder<E> {
+  fn decode<'data>(&self, note: &ElfNote<'data>) -> Option<CoreNote<'data>> {
+    match note.n_type {
+      NT_PRSTATUS => self.prstatus(note.desc).map(CoreNote::PrStatus),
+      NT_PRPSINFO => self.prpsinfo(note.desc).map(CoreNote::PrPsInfo),
+      NT_FILE => self.file_table(note.desc).map(CoreNote::File),
+      _ => None,
+    }
+  }
+
+  fn read(&self, desc: &[u8], offset: usize, size: usize) -> Option<u64> {
+    read_unsigned::<E>(desc, offset, size)
+  }
+
+  fn prstatus<'data>(&self, desc: &'data [u8]) -> Option<PrStatus<'data>> {
+    let word = self.word;
+    // elf_siginfo (3 ints), pr_cursig (short), pr_sigpend and pr_sighold.
+    let pid = 16 + 2 * word;
+    // 4 ids then pr_utime, pr_stime, pr_cutime and pr_cstime (2 longs each).
+    let reg = pid + 16 + 8 * word;
+
+    let names = match self.machine {
+      EM_X86_64 if word == 8 => X86_64_REGISTERS,
+      EM_386 if word == 4 => I386_REGISTERS,
+      EM_AARCH64 if word == 8 => AARCH64_REGISTERS,
+      EM_ARM if word == 4 => ARM_REGISTERS,
+      _ => &[],
+    };
+
+    // Unknown architectures: everything up to pr_fpvalid (int).
+    let count = match names.len() {
+      0 => desc.len().checked_sub(reg + 4)? / word,
+      count => count,
+    };
+
+    let registers = desc.get(reg..reg + count * word)?;
+    let registers = registers
+      .chunks_exact(word)
+      .enumerate()
+      .map(|(index, bytes)| Register {
+        name: names.get(index).copied(),
+        bytes,
+        value: self.read(bytes, 0, word).unwrap_or_default(),
+      })
+      .collect();
+
+    Some(PrStatus {
+      signal: self.read(desc, 12, 2)? as u16,
+      pid: self.read(desc, pid, 4)? as u32,
+      ppid: self.read(desc, pid + 4, 4)? as u32,
+      pgrp: self.read(desc, pid + 8, 4)? as u32,
+      sid: self.read(desc, pid + 12, 4)? as u32,
+      registers,
+    })
+  }
+
+  fn prpsinfo<'data>(&self, desc: &'data [u8]) -> Option<PrPsInfo<'data>> {
+    let word = self.word;
+    // Legacy 16-bit uid and gid in elf32.
+    let id = if word == 4 { 2 } else { 4 };
+    let pid = 2 * word + 2 * id;
+    let name = pid + 16;
+    let args = name + 16;
+
+    let string = |bytes: &'data [u8]| match bytes.iter().position(|&byte| byte == 0) {
+      Some(end) => &bytes[..end],
+      None => bytes,
+    };
+
+    Some(PrPsInfo {
+      state: self.read(desc, 0, 1)? as u8,
+      sname: self.read(desc, 1, 1)? as u8,
+      zombie: self.read(desc, 2, 1)? as u8,
+      nice: self.read(desc, 3, 1)? as i8,
+      flags: self.read(desc, word, word)?,
+      uid: self.read(desc, 2 * word, id)? as u32,
+      gid: self.read(desc, 2 * word + id, id)? as u32,
+      pid: self.read(desc, pid, 4)? as u32,
+      ppid: self.read(desc, pid + 4, 4)? as u32,
+      pgrp: self.read(desc, pid + 8, 4)? as u32,
+      sid: self.read(desc, pid + 12, 4)? as u32,
+      name: string(desc.get(name..name + 16)?),
+      args: string(desc.get(args..args + 80)?),
+    })
+  }
+
+  ///
+  /// `NT_FILE` layout: count, page size, `count` entries (start, end and file
+  /// offset in pages) then `count` NUL-terminated paths. Entries beyond the
+  /// descriptor or without a (complete) path are dropped.
+  ///
+  fn file_table<'data>(&self, desc: &'data [u8]) -> Option<FileTable<'data>> {
+    let word = self.word;
+    let count = self.read(desc, 0, word)?;
+    let page_size = self.read(desc, word, word)?;
+
+    let entries = desc.get(2 * word..)?;
+    let count = usize::try_from(count)
+      .unwrap_or(usize::MAX)
+      .min(entries.len() / (3 * word));
+    let (entries, paths) = entries.split_at(count * 3 * word);
+
+    let files = entries
+      .chunks_exact(3 * word)
+      .zip(paths.split_inclusive(|&byte| byte == 0))
+      .map_while(|(entry, path)| {
+        Some(MappedFile {
+          start: self.read(entry, 0, word)?,
+          end: self.read(entry, word, word)?,
+          offset: self.read(entry, 2 * word, word)?.saturating_mul(page_size),
+          path: path.strip_suffix(b"\0")?, // Truncated string area.
+        })
+      })
+      .collect();
+
+    Some(FileTable { page_size, files })
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+impl fmt::Display for PrStatus<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(
+      formatter,
+      "  NT_PRSTATUS  signal: {}, pid: {}, ppid: {}, pgrp: {}, sid: {}",
+      self.signal, self.pid, self.ppid, self.pgrp, self.sid
+    )?;
+
+    for (index, register) in self.registers.iter().enumerate() {
+      let width = register.bytes.len() * 2;
+      let name = register.name.map_or_else(|| format!("r{}", index), String::from);
+      write!(formatter, " {:>10}: {:0width$x}", name, register.value)?;
+
+      if index % 4 == 3 || index + 1 == self.registers.len() {
+        writeln!(formatter)?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+impl fmt::Display for PrPsInfo<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(
+      formatter,
+      "  NT_PRPSINFO  name: {}, state: {} ({}), pid: {}, ppid: {}, uid: {}, gid: {}",
+      printable(self.name),
+      self.state,
+      self.sname as char,
+      self.pid,
+      self.ppid,
+      self.uid,
+      self.gid,
+    )?;
+    writeln!(formatter, "    args: {}", printable(self.args))
+  }
+}
+
+impl fmt::Display for FileTable<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(
+      formatter,
+      "  NT_FILE  {} files, page size: {}",
+      self.files.len(),
+      self.page_size
+    )?;
+
+    for file in &self.files {
+      writeln!(
+        formatter,
+        "    {:#018x}-{:#018x} {:#010x}  {}",
+        file.start,
+        file.end,
+        file.offset,
+        printable(file.path)
+      )?;
+    }
+
+    Ok(())
+  }
+}
+
+impl fmt::Display for CoreNote<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::PrStatus(prstatus) => fmt::Display::fmt(prstatus, formatter),
+      Self::PrPsInfo(prpsinfo) => fmt::Display::fmt(prpsinfo, formatter),
+      Self::File(files) => fmt::Display::fmt(files, formatter),
+    }
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+
+  /// Builds a note entry (4 bytes padding).
+  fn note(name: &str, n_type: u32, desc: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    bytes.extend((name.len() as u32 + 1).to_le_bytes());
+    bytes.extend((desc.len() as u32).to_le_bytes());
+    bytes.extend(n_type.to_le_bytes());
+    bytes.extend(name.as_bytes());
+    bytes.resize((bytes.len() + 1).next_multiple_of(4), 0);
+    bytes.extend(desc);
+    bytes.resize(bytes.len().next_multiple_of(4), 0);
+    bytes
+  }
+
+  /// ELF64 little-endian x86-64 core file with a single `PT_NOTE` segment.
+  fn fixture(notes: &[u8]) -> Vec<u8> {
+    let filesz = format!("{:016x}", (notes.len() as u64).swap_bytes());
+    let mut bytes = hex(&format!(
+      r"
+        7F 'ELF 02 01 01 00 00 00000000 000000 ; e_ident
+        0400 3E00 01000000 ; ET_CORE, EM_X86_64, e_version
+        0000000000000000 ; e_entry
+        4000000000000000 ; e_phoff
+        0000000000000000 ; e_shoff
+        00000000 4000 ; e_flags, e_ehsize
+        3800 0100 ; e_phentsize, e_phnum
+        4000 0000 0000 ; e_shentsize, e_shnum, e_shstrndx
+
+        04000000 00000000 ; PT_NOTE, p_flags
+        7800000000000000 ; p_offset
+        0000000000000000 ; p_vaddr
+        0000000000000000 ; p_paddr
+        {filesz} ; p_filesz
+        0000000000000000 ; p_memsz
+        0400000000000000 ; p_align
+      "
+    ))
+    .unwrap();
+
+    bytes.extend(notes);
+    bytes
+  }
+
+  /// Returns the single core note of the fixture.
+  fn core_note(bytes: &[u8]) -> CoreNote<'_> {
+    let ElfFile::Elf64Le(object) = parse_elf(bytes).unwrap() else {
+      unreachable!();
+    };
+
+    let mut notes = object.core_notes().unwrap();
+    assert_eq!(notes.len(), 1);
+    notes.remove(0)
+  }
+
+  fn words(values: &[u64]) -> Vec<u8> {
+    values.iter().flat_map(|value| value.to_le_bytes()).collect()
+  }
+
+  #[test]
+  fn prstatus() {
+    let mut desc = vec![0; 336]; // sizeof(struct elf_prstatus)
+    desc[12..14].copy_from_slice(&11u16.to_le_bytes()); // SIGSEGV
+    desc[32..36].copy_from_slice(&1234u32.to_le_bytes());
+    desc[36..40].copy_from_slice(&1u32.to_le_bytes());
+    desc[112 + 16 * 8..112 + 17 * 8].copy_from_slice(&0x401000u64.to_le_bytes()); // rip
+
+    let bytes = fixture(&note("CORE", NT_PRSTATUS, &desc));
+    let CoreNote::PrStatus(prstatus) = core_note(&bytes) else {
+      panic!("Expected a single NT_PRSTATUS");
+    };
+
+    assert_eq!((prstatus.signal, prstatus.pid, prstatus.ppid), (11, 1234, 1));
+    assert_eq!(prstatus.registers.len(), 27);
+    assert_eq!(prstatus.registers[16].name, Some("rip"));
+    assert_eq!(prstatus.registers[16].value, 0x401000);
+    assert_eq!(prstatus.registers[16].bytes, &0x401000u64.to_le_bytes());
+  }
+
+  #[test]
+  fn prpsinfo() {
+    let mut desc = vec![0; 136]; // sizeof(struct elf_prpsinfo)
+    desc[1] = b'R';
+    desc[16..20].copy_from_slice(&1000u32.to_le_bytes()); // uid
+    desc[24..28].copy_from_slice(&1234u32.to_le_bytes()); // pid
+    desc[40..43].copy_from_slice(b"cat");
+    desc[56..71].copy_from_slice(b"cat /dev/random");
+
+    let bytes = fixture(&note("CORE", NT_PRPSINFO, &desc));
+    let CoreNote::PrPsInfo(prpsinfo) = core_note(&bytes) else {
+      panic!("Expected a single NT_PRPSINFO");
+    };
+
+    assert_eq!((prpsinfo.sname, prpsinfo.uid, prpsinfo.pid), (b'R', 1000, 1234));
+    assert_eq!(prpsinfo.name, b"cat");
+    assert_eq!(prpsinfo.args, b"cat /dev/random");
+  }
+
+  #[test]
+  fn file_table() {
+    let mut desc = words(&[3, 0x1000]);
+    desc.extend(words(&[0x400000, 0x401000, 0]));
+    desc.extend(words(&[0x401000, 0x403000, 1])); // Offset in pages.
+    desc.extend(words(&[0x7f0000, 0x7f1000, 0]));
+    desc.extend(b"/usr/bin/cat\0/usr/bin/cat\0/usr/lib/libc.so"); // Truncated.
+
+    let mut notes = note("CORE", NT_FILE, &desc);
+    notes.extend(note("LINUX", NT_FILE, &[])); // Not a core note.
+
+    let bytes = fixture(&notes);
+    let CoreNote::File(table) = core_note(&bytes) else {
+      panic!("Expected a single NT_FILE");
+    };
+
+    assert_eq!(table.page_size, 0x1000);
+    assert_eq!(
+      table.files,
+      vec![
+        MappedFile {
+          start: 0x400000,
+          end: 0x401000,
+          offset: 0,
+          path: b"/usr/bin/cat"
+        },
+        MappedFile {
+          start: 0x401000,
+          end: 0x403000,
+          offset: 0x1000,
+          path: b"/usr/bin/cat"
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn file_table_count_overflow() {
+    let mut desc = words(&[u64::MAX, 0x1000]);
+    desc.extend(words(&[0x400000, 0x401000, 2]));
+    desc.extend(b"/bin/sh\0");
+
+    let bytes = fixture(&note("CORE", NT_FILE, &desc));
+    let CoreNote::File(table) = core_note(&bytes) else {
+      panic!("Expected a single NT_FILE");
+    };
+
+    assert_eq!(
+      table.files,
+      vec![MappedFile {
+        start: 0x400000,
+        end: 0x401000,
+        offset: 0x2000,
+        path: b"/bin/sh"
+      }]
+    );
+  }
+}
@@ -0,0 +1,372 @@
+use ::core::fmt;
+use alloc::borrow::Cow;
+
+use super::abi::e_type::ET_REL;
+use super::sections::{SHF_EXECINSTR, SHN_LORESERVE, SHT_NOBITS};
+use super::symbols::st_type::{STT_FUNC, STT_GNU_IFUNC};
+use super::symbols::SHN_UNDEF;
+use super::*;
+use crate::reader::Reader;
+use crate::utils::Size;
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+/// Uncovered ranges listed by the display of [`SymbolCoverage`], the largest first.
+pub const COVERAGE_GAPS: usize = 10;
+
+/// An executable section and its bytes covered by function symbols.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoveredSection<'data> {
+  pub name: Cow<'data, str>,
+  pub address: u64,
+  pub size: u64,
+  pub covered: u64,
+  /// Function symbols overlapping the section.
+  pub symbols: usize,
+}
+
+/// A range of an executable section covered by no function symbol, `end` excluded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageGap<'data> {
+  pub section: Cow<'data, str>,
+  pub start: u64,
+  pub end: u64,
+}
+
+impl CoverageGap<'_> {
+  pub fn size(&self) -> u64 {
+    self.end - self.start
+  }
+}
+
+///
+/// How much of the executable sections (`SHF_EXECINSTR`) the defined
+/// function symbols of nonzero size describe, a "not stripped" binary with
+/// a handful of symbols covers little. See [`ElfObject::symbol_coverage()`].
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolCoverage<'data> {
+  /// `.symtab`, or `.dynsym` when stripped, `None` without symbol table.
+  pub table: Option<&'static str>,
+  pub sections: Vec<CoveredSection<'data>>,
+  /// Sorted by decreasing size, then by address.
+  pub gaps: Vec<CoverageGap<'data>>,
+}
+
+impl SymbolCoverage<'_> {
+  /// Returns the size of the executable sections.
+  pub fn size(&self) -> u64 {
+    self.sections.iter().map(|section| section.size).sum()
+  }
+
+  /// Returns the bytes of the executable sections covered by a function symbol.
+  pub fn covered(&self) -> u64 {
+    self.sections.iter().map(|section| section.covered).sum()
+  }
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the coverage of the executable sections by the defined
+  /// `STT_FUNC` and `STT_GNU_IFUNC` symbols of nonzero size of `.symtab` (or
+  /// of `.dynsym` when stripped), `None` without executable section.
+  ///
+  /// A symbol only covers its own section (`st_shndx`), clamped to it: a
+  /// symbol extending past the end of its section (as hand-written assembly
+  /// with a wrong `.size`) does not cover the next one. Overlapping symbols
+  /// (aliases, nested functions) are merged. The `st_value` of a relocatable
+  /// object is an offset into the section rather than an address.
+  ///
+  pub fn symbol_coverage(&self) -> Option<SymbolCoverage<'data>> {
+    let headers = self.section_headers().unwrap_or_default();
+    let executable = |section: &ElfSection<ElfType>| {
+      section.sh_flags() & SHF_EXECINSTR != 0
+        && section.sh_type() != SHT_NOBITS as u64
+        && section.sh_size() != 0
+    };
+    if !headers.iter().any(|section| executable(section)) {
+      return None;
+    }
+
+    let (table, symbols) = [(SHT_SYMTAB, ".symtab"), (SHT_DYNSYM, ".dynsym")]
+      .into_iter()
+      .find_map(|(sh_type, name)| Some((Some(name), self.symbol_table(sh_type).ok()??.symbols)))
+      .unwrap_or_default();
+
+    let e_type: u64 = self.header().e_type.into();
+    let relocatable = e_type == ET_REL as u64;
+
+    let mut coverage = SymbolCoverage {
+      table,
+      ..Default::default()
+    };
+    for (index, section) in headers
+      .iter()
+      .enumerate()
+      .filter(|(_, section)| executable(section))
+    {
+      let name = self.section_name(section).unwrap_or_default();
+      let (address, size) = (section.sh_addr(), section.sh_size());
+      let base = if relocatable { 0 } else { address };
+
+      let mut ranges = symbols
+        .iter()
+        .filter(|symbol| matches!(symbol.st_type(), STT_FUNC | STT_GNU_IFUNC))
+        .filter(|symbol| symbol.st_size() != 0)
+        .filter(|symbol| !matches!(symbol.st_shndx(), SHN_UNDEF | SHN_LORESERVE..))
+        .filter(|symbol| symbol.st_shndx() as usize == index)
+        .filter_map(|symbol| {
+          let start = symbol.st_value().saturating_sub(base).min(size);
+          let end = symbol
+            .st_value()
+            .saturating_add(symbol.st_size())
+            .saturating_sub(base)
+            .min(size);
+          (start < end).then_some((start, end))
+        })
+        .collect::<Vec<_>>();
+      ranges.sort_unstable();
+
+      let mut covered = 0;
+      let mut cursor = 0;
+      for &(start, end) in &ranges {
+        if start > cursor {
+          coverage.gaps.push(CoverageGap {
+            section: name.clone(),
+            start: address + cursor,
+            end: address + start,
+          });
+        }
+        if end > cursor {
+          covered += end - start.max(cursor);
+          cursor = end;
+        }
+      }
+      if cursor < size {
+        coverage.gaps.push(CoverageGap {
+          section: name.clone(),
+          start: address + cursor,
+          end: address + size,
+        });
+      }
+
+      coverage.sections.push(CoveredSection {
+        name,
+        address,
+        size,
+        covered,
+        symbols: ranges.len(),
+      });
+    }
+
+    coverage
+      .gaps
+      .sort_by(|a, b| b.size().cmp(&a.size()).then(a.start.cmp(&b.start)));
+    Some(coverage)
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+/// Percentage of `part` in `total`, with one decimal.
+struct Percent(u64, u64);
+
+impl fmt::Display for Percent {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let ratio = match self.1 {
+      0 => 0.0,
+      total => self.0 as f64 * 100.0 / total as f64,
+    };
+    formatter.pad(&format!("{:.1}%", ratio))
+  }
+}
+
+/// Table of the executable sections, the largest gaps then the total coverage.
+impl fmt::Display for SymbolCoverage<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use crate::utils::display_table;
+
+    writeln!(
+      formatter,
+      "Symbols: {}",
+      self.table.unwrap_or("none (no symbol table)")
+    )?;
+    writeln!(formatter)?;
+    display_table!(
+      formatter,
+      align [Left, Right, Right, Right, Right],
+      ["Section", "Address", "Size", "Covered", "Symbols"],
+      section in &self.sections => [
+        section.name,
+        format!("{:#x}", section.address),
+        format!("{:#x}", section.size),
+        Percent(section.covered, section.size),
+        section.symbols,
+      ]
+    )?;
+
+    if !self.gaps.is_empty() {
+      writeln!(formatter)?;
+      match self.gaps.len() > COVERAGE_GAPS {
+        true => writeln!(
+          formatter,
+          "Largest gaps ({} of {}):",
+          COVERAGE_GAPS,
+          self.gaps.len()
+        )?,
+        false => writeln!(formatter, "Gaps:")?,
+      }
+      display_table!(
+        formatter,
+        align [Right, Right, Right, Left],
+        ["Start", "End", "Size", "Section"],
+        gap in self.gaps.iter().take(COVERAGE_GAPS) => [
+          format!("{:#x}", gap.start),
+          format!("{:#x}", gap.end),
+          format!("{:#x}", gap.size()),
+          gap.section,
+        ]
+      )?;
+    }
+
+    writeln!(formatter)?;
+    writeln!(
+      formatter,
+      "Covered: {} of {} bytes ({})",
+      Size(self.covered()),
+      Size(self.size()),
+      Percent(self.covered(), self.size())
+    )
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::elf::abi::sh_type::{SHT_PROGBITS, SHT_STRTAB};
+  use crate::elf::builder::{BuilderSection, ElfBuilder};
+
+  /// `.text` at 0x1000 (0x100 bytes) and `.init` at 0x2000 (0x10 bytes), then the given symbols.
+  fn build(e_type: u16, symbols: &[(u8, u16, u64, u64)]) -> Vec<u8> {
+    let mut symtab = vec![0; 24];
+    for &(st_info, st_shndx, st_value, st_size) in symbols {
+      symtab.extend([0; 4]);
+      symtab.extend([st_info, 0]);
+      symtab.extend(st_shndx.to_le_bytes());
+      symtab.extend(st_value.to_le_bytes());
+      symtab.extend(st_size.to_le_bytes());
+    }
+
+    let code = |name: &str, sh_addr, size| BuilderSection {
+      name: String::from(name),
+      sh_type: SHT_PROGBITS,
+      sh_flags: SHF_EXECINSTR,
+      sh_addr,
+      data: vec![0xCC; size],
+      ..Default::default()
+    };
+    ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .object_type(e_type)
+      .add_section(code(".text", 0x1000, 0x100))
+      .add_section(code(".init", 0x2000, 0x10))
+      .add_section(BuilderSection {
+        name: String::from(".symtab"),
+        sh_type: SHT_SYMTAB,
+        sh_link: 4,
+        sh_addralign: 8,
+        sh_entsize: 24,
+        data: symtab,
+        ..Default::default()
+      })
+      .section(".strtab", SHT_STRTAB, &[0])
+      .build()
+  }
+
+  #[test]
+  fn symbol_coverage() {
+    let bytes = build(
+      abi::e_type::ET_EXEC,
+      &[
+        (0x12, 1, 0x1000, 0x40), // FUNC
+        (0x22, 1, 0x1020, 0x40), // Overlapping WEAK FUNC.
+        (0x12, 1, 0x1000, 0x10), // Alias.
+        (0x12, 1, 0x10F0, 0x40), // Past the end of .text.
+        (0x11, 1, 0x1080, 0x20), // OBJECT
+        (0x12, 1, 0x10A0, 0),    // Without size.
+        (0x12, 0, 0x10C0, 0x10), // Undefined.
+        (0x1A, 2, 0x2004, 0x4),  // GNU_IFUNC
+      ],
+    );
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let coverage = object.symbol_coverage().unwrap();
+    assert_eq!(coverage.table, Some(".symtab"));
+    let sections = coverage
+      .sections
+      .iter()
+      .map(|section| (section.name.as_ref(), section.covered, section.symbols))
+      .collect::<Vec<_>>();
+    assert_eq!(sections, [(".text", 0x60 + 0x10, 4), (".init", 0x4, 1)]);
+    assert_eq!((coverage.covered(), coverage.size()), (0x74, 0x110));
+
+    let gaps = coverage
+      .gaps
+      .iter()
+      .map(|gap| (gap.section.as_ref(), gap.start, gap.end))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      gaps,
+      [
+        (".text", 0x1060, 0x10F0),
+        (".init", 0x2008, 0x2010),
+        (".init", 0x2000, 0x2004)
+      ]
+    );
+
+    let output = coverage.to_string();
+    assert!(output.contains("\nGaps:\n"));
+    assert!(
+      output.ends_with("\nCovered: 116 of 272 bytes (42.6%)\n"),
+      "{}",
+      output
+    );
+  }
+
+  #[test]
+  fn relocatable_coverage() {
+    // Offsets into the sections, both at address 0.
+    let mut bytes = build(abi::e_type::ET_REL, &[(0x12, 1, 0, 0x100), (0x12, 2, 0x8, 0x8)]);
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    let coverage = object.symbol_coverage().unwrap();
+    assert_eq!(coverage.covered(), 0x108);
+    assert_eq!(coverage.gaps.len(), 1);
+    assert_eq!((coverage.gaps[0].start, coverage.gaps[0].end), (0x2000, 0x2008));
+
+    // Without executable section.
+    bytes = ElfBuilder::<ElfType64<LittleEndian>>::new().build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(object.symbol_coverage(), None);
+  }
+}
@@ -0,0 +1,355 @@
+use ::core::fmt;
+use alloc::borrow::Cow;
+
+use super::sections::{SHF_ALLOC, SHF_TLS, SHT_NOBITS};
+use super::segments::PT_LOAD;
+use super::*;
+use crate::reader::Reader;
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+/// How the program headers contradict a section header, see [`Divergence`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DivergenceKind {
+  /// No `PT_LOAD` segment holds the address of the section.
+  Unmapped,
+  ///
+  /// The address of a section with file bytes is in the zero-filled tail of
+  /// the segment (`p_memsz` beyond `p_filesz`): its bytes are not loaded.
+  ///
+  ZeroFilled,
+  ///
+  /// The segment loads the bytes at `offset` at the address of the section,
+  /// not the ones at `sh_offset`. `same_bytes` tells whether both ranges hold
+  /// the same bytes (a copy), `false` when one of them is out of the file.
+  ///
+  Moved { offset: u64, same_bytes: bool },
+  /// Only the first `loaded` bytes of the section are in the file bytes of the segment.
+  Truncated { loaded: u64 },
+}
+
+///
+/// An allocated section which the program headers load elsewhere or not at
+/// all, in both coordinate systems: the section header (`sh_addr` and
+/// `sh_offset`) and the segment (index and the offset it loads at `sh_addr`).
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence<'data> {
+  pub index: usize,
+  pub name: Cow<'data, str>,
+  pub sh_addr: u64,
+  pub sh_offset: u64,
+  pub sh_size: u64,
+  /// Index of the `PT_LOAD` segment holding `sh_addr` in the program header table.
+  pub segment: Option<usize>,
+  /// File offset which the segment loads at `sh_addr`.
+  pub mapped_offset: Option<u64>,
+  pub kind: DivergenceKind,
+}
+
+///
+/// The comparison of the section view with the segment view of a file, see
+/// [`ElfObject::cross_check()`].
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CrossCheck<'data> {
+  /// Allocated sections compared.
+  pub sections: usize,
+  /// In section header order.
+  pub divergences: Vec<Divergence<'data>>,
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Compares the section headers with the program headers: every
+  /// `SHF_ALLOC` section of nonzero size must be loaded by a `PT_LOAD`
+  /// segment from the bytes its header points to. The segment is the first
+  /// one (in table order) whose memory holds `sh_addr`, as for
+  /// [`Self::segment_containing()`], and the section must lie in its file
+  /// bytes at the same offset. The `SHT_NOBITS` sections only have to be
+  /// mapped, `.tbss` (`SHF_TLS`) occupies no memory of its own and is
+  /// skipped.
+  ///
+  /// On a tampered file, the two views describe different files: the
+  /// loader only reads the program headers while the tools (disassemblers,
+  /// debuggers) trust the section headers. `None` is returned when one of
+  /// the views is missing (relocatable objects, stripped section headers).
+  ///
+  pub fn cross_check(&self) -> Option<CrossCheck<'data>> {
+    let segments = self
+      .program_headers()
+      .ok()
+      .filter(|headers| !headers.is_empty())?;
+    if !segments.iter().any(|header| header.p_type() == PT_LOAD) {
+      return None;
+    }
+    let sections = self.sections().skip(1).collect::<Vec<_>>();
+    if sections.is_empty() {
+      return None;
+    }
+
+    let mut check = CrossCheck::default();
+    for section in sections {
+      let header = section.header;
+      let flags = header.sh_flags();
+      let nobits = header.sh_type() == SHT_NOBITS as u64;
+      if flags & SHF_ALLOC == 0 || header.sh_size() == 0 || (nobits && flags & SHF_TLS != 0) {
+        continue;
+      }
+      check.sections += 1;
+
+      let (sh_addr, sh_offset, sh_size) = (header.sh_addr(), header.sh_offset(), header.sh_size());
+      let segment = self.segment_containing(sh_addr);
+      let delta = segment.map(|segment| sh_addr - segment.header.p_vaddr());
+      let mapped_offset = segment
+        .zip(delta)
+        .and_then(|(segment, delta)| segment.header.p_offset().checked_add(delta));
+
+      let kind = match (segment, delta, mapped_offset) {
+        (None, ..) => Some(DivergenceKind::Unmapped),
+        _ if nobits => None,
+        (Some(segment), Some(delta), _) if delta >= segment.header.p_filesz() => {
+          Some(DivergenceKind::ZeroFilled)
+        }
+        (Some(segment), Some(delta), Some(offset)) => {
+          let loaded = segment.header.p_filesz() - delta;
+          if offset != sh_offset {
+            let same_bytes = self.same_bytes(sh_offset, offset, sh_size.min(loaded));
+            Some(DivergenceKind::Moved { offset, same_bytes })
+          } else if sh_size > loaded {
+            Some(DivergenceKind::Truncated { loaded })
+          } else {
+            None
+          }
+        }
+        _ => Some(DivergenceKind::Unmapped),
+      };
+
+      if let Some(kind) = kind {
+        check.divergences.push(Divergence {
+          index: section.index,
+          name: section.display_name().unwrap_or(Cow::Borrowed("<corrupt>")),
+          sh_addr,
+          sh_offset,
+          sh_size,
+          segment: segment.map(|segment| segment.index),
+          mapped_offset,
+          kind,
+        });
+      }
+    }
+
+    Some(check)
+  }
+
+  /// Returns whether the `len` bytes at both offsets are in the file and the same.
+  fn same_bytes(&self, first: u64, second: u64, len: u64) -> bool {
+    let read = |offset: u64| {
+      let (offset, len) = (usize::try_from(offset).ok()?, usize::try_from(len).ok()?);
+      self.data.read_bytes(len, offset)
+    };
+    matches!((read(first), read(second)), (Some(first), Some(second)) if first == second)
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+impl fmt::Display for DivergenceKind {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match *self {
+      Self::Unmapped => formatter.pad("not loaded by any PT_LOAD segment"),
+      Self::ZeroFilled => formatter.pad("zero-filled by the segment, its file bytes are not loaded"),
+      Self::Moved { same_bytes, .. } => formatter.pad(match same_bytes {
+        true => "loaded from other bytes, the same content",
+        false => "loaded from other bytes, a different content",
+      }),
+      Self::Truncated { loaded } => formatter.pad(&format!("only {:#x} bytes loaded from the file", loaded)),
+    }
+  }
+}
+
+/// Table of the divergences, both coordinate systems side by side, then the count of agreeing sections.
+impl fmt::Display for CrossCheck<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use crate::utils::display_table;
+
+    let hex = |value: Option<u64>| value.map_or(String::from("-"), |value| format!("{:#x}", value));
+    if !self.divergences.is_empty() {
+      display_table!(
+        formatter,
+        align [Right, Left, Right, Right, Right, Right, Right, Left],
+        ["Nr", "Section", "Address", "Size", "Offset", "Segment", "Loaded from", "Divergence"],
+        divergence in &self.divergences => [
+          divergence.index,
+          divergence.name,
+          format!("{:#x}", divergence.sh_addr),
+          format!("{:#x}", divergence.sh_size),
+          format!("{:#x}", divergence.sh_offset),
+          divergence.segment.map_or(String::from("-"), |segment| segment.to_string()),
+          hex(divergence.mapped_offset),
+          divergence.kind,
+        ]
+      )?;
+      writeln!(formatter)?;
+    }
+
+    writeln!(
+      formatter,
+      "{} of {} allocated sections agree with the program headers",
+      self.sections - self.divergences.len(),
+      self.sections
+    )
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::elf::abi::e_type::ET_EXEC;
+  use crate::elf::abi::p_flags::{PF_R, PF_W, PF_X};
+  use crate::elf::abi::sh_type::SHT_PROGBITS;
+  use crate::elf::builder::{BuilderSection, ElfBuilder};
+  use crate::elf::offsets::ELF64_HEADER;
+  use crate::elf::sections::SHF_EXECINSTR;
+
+  /// `.text` and `.init` (the same bytes), `.data` and `.bss`, loaded by three segments.
+  fn build() -> Vec<u8> {
+    let section = |name: &str, sh_type, sh_flags, sh_addr, data: Vec<u8>| BuilderSection {
+      name: String::from(name),
+      sh_type,
+      sh_flags: SHF_ALLOC | sh_flags,
+      sh_addr,
+      data,
+      ..Default::default()
+    };
+    ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .object_type(ET_EXEC)
+      .add_section(section(
+        ".text",
+        SHT_PROGBITS,
+        SHF_EXECINSTR,
+        0x401000,
+        vec![0xCC; 0x20],
+      ))
+      .add_section(section(
+        ".init",
+        SHT_PROGBITS,
+        SHF_EXECINSTR,
+        0x402000,
+        vec![0xCC; 0x20],
+      ))
+      .add_section(section(".data", SHT_PROGBITS, 0, 0x403000, vec![0x11; 0x20]))
+      .add_section(BuilderSection {
+        size: Some(0x40),
+        ..section(".bss", SHT_NOBITS, 0, 0x403020, Vec::new())
+      })
+      .segment(PT_LOAD, PF_R | PF_X, &[1])
+      .segment(PT_LOAD, PF_R | PF_X, &[2])
+      .segment(PT_LOAD, PF_R | PF_W, &[3, 4])
+      .build()
+  }
+
+  /// Writes a field of a section header (`sh_addr` at 0x10, `sh_offset` at 0x18, `sh_size` at 0x20).
+  fn patch(bytes: &mut [u8], index: usize, field: usize, value: u64) {
+    let offset = ELF64_HEADER.e_shoff;
+    let shoff = u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap()) as usize;
+    let offset = shoff + 64 * index + field;
+    bytes[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+  }
+
+  fn kinds(bytes: &[u8]) -> Vec<(usize, DivergenceKind)> {
+    let ElfFile::Elf64Le(object) = parse_elf(bytes).unwrap() else {
+      unreachable!();
+    };
+    let check = object.cross_check().unwrap();
+    assert_eq!(check.sections, 4);
+    check
+      .divergences
+      .into_iter()
+      .map(|divergence| (divergence.index, divergence.kind))
+      .collect()
+  }
+
+  #[test]
+  fn cross_check() {
+    let bytes = build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    let check = object.cross_check().unwrap();
+    assert_eq!(check.divergences, []);
+    assert_eq!(
+      check.to_string(),
+      "4 of 4 allocated sections agree with the program headers\n"
+    );
+
+    // `.text` pointing at the bytes of `.init` (a copy), then at the ones of `.data`.
+    let offset = |index: usize| object.section_headers().unwrap()[index].sh_offset();
+    let (init, data) = (offset(2), offset(3));
+    let mut moved = bytes.clone();
+    patch(&mut moved, 1, 0x18, init);
+    let expected = DivergenceKind::Moved {
+      offset: offset(1),
+      same_bytes: true,
+    };
+    assert_eq!(kinds(&moved), [(1, expected)]);
+    patch(&mut moved, 1, 0x18, data);
+    let expected = DivergenceKind::Moved {
+      offset: offset(1),
+      same_bytes: false,
+    };
+    assert_eq!(kinds(&moved), [(1, expected)]);
+
+    // `.text` larger than its segment, `.data` at the address of `.bss`, `.bss` out of the segments.
+    let mut tampered = bytes.clone();
+    patch(&mut tampered, 1, 0x20, 0x30);
+    patch(&mut tampered, 3, 0x10, 0x403020);
+    patch(&mut tampered, 4, 0x10, 0x500000);
+    assert_eq!(
+      kinds(&tampered),
+      [
+        (1, DivergenceKind::Truncated { loaded: 0x20 }),
+        (3, DivergenceKind::ZeroFilled),
+        (4, DivergenceKind::Unmapped)
+      ]
+    );
+
+    let ElfFile::Elf64Le(object) = parse_elf(tampered.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    let output = object.cross_check().unwrap().to_string();
+    assert!(
+      output.contains("only 0x20 bytes loaded from the file"),
+      "{}",
+      output
+    );
+    assert!(output.ends_with("\n1 of 4 allocated sections agree with the program headers\n"));
+  }
+
+  #[test]
+  fn without_segments() {
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".text", SHT_PROGBITS, &[0xCC; 4])
+      .build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(object.cross_check(), None);
+  }
+}
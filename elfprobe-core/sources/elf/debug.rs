@@ -0,0 +1,347 @@
+use ::core::fmt;
+use alloc::borrow::Cow;
+
+use super::compression::{ELFCOMPRESS_ZLIB, ELFCOMPRESS_ZSTD};
+use super::strings::printable;
+use super::*;
+use crate::reader::Reader;
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+/// Size limit of the ELF file embedded in `.gnu_debugdata` (a few MiB in practice).
+#[cfg(feature = "xz")]
+pub const DEBUGDATA_LIMIT: usize = 256 << 20;
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+/// Compression of a debug section.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DebugCompression {
+  None,
+  /// `SHF_COMPRESSED` section, with the compression header `ch_type`.
+  Chdr(u32),
+  /// Legacy GNU compression of `.zdebug_*` sections (`ZLIB` magic and the
+  /// uncompressed size in big-endian).
+  Gnu,
+}
+
+///
+/// A DWARF section (`.debug_*` or `.zdebug_*`), `size` is the size in the
+/// file and `uncompressed_size` the size once decompressed (the same when
+/// not compressed).
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebugSection<'data> {
+  pub name: Cow<'data, str>,
+  pub size: u64,
+  pub compression: DebugCompression,
+  pub uncompressed_size: u64,
+}
+
+impl DebugSection<'_> {
+  /// Returns the name without its `.debug_` or `.zdebug_` prefix (`info`, `line`...).
+  pub fn category(&self) -> &str {
+    let name = self.name.as_ref();
+    name
+      .strip_prefix(".debug_")
+      .or_else(|| name.strip_prefix(".zdebug_"))
+      .unwrap_or(name)
+  }
+}
+
+/// Sizes of the debug sections of the same category, see [`debug_totals()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebugTotal<'section> {
+  pub category: &'section str,
+  pub size: u64,
+  pub uncompressed_size: u64,
+}
+
+///
+/// Sums the sizes of the given sections per category (in order of first
+/// appearance), `.debug_info` and `.zdebug_info` belong to the same one.
+///
+pub fn debug_totals<'section>(sections: &'section [DebugSection<'_>]) -> Vec<DebugTotal<'section>> {
+  let mut totals: Vec<DebugTotal> = Vec::new();
+  for section in sections {
+    let category = section.category();
+    let index = match totals.iter().position(|total| total.category == category) {
+      Some(index) => index,
+      None => {
+        totals.push(DebugTotal {
+          category,
+          size: 0,
+          uncompressed_size: 0,
+        });
+        totals.len() - 1
+      }
+    };
+
+    totals[index].size += section.size;
+    totals[index].uncompressed_size += section.uncompressed_size;
+  }
+
+  totals
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the DWARF sections (in section order) with their compression
+  /// state. Nothing is decompressed, the uncompressed size is read from the
+  /// compression header (`ch_size`), or from the `.zdebug_*` header.
+  ///
+  pub fn debug_sections(&self) -> Vec<DebugSection<'data>> {
+    let mut sections = Vec::new();
+
+    for section in self.section_headers().unwrap_or_default() {
+      let name = self.section_name(section).unwrap_or_default();
+      if !name.starts_with(".debug_") && !name.starts_with(".zdebug_") {
+        continue;
+      }
+
+      let size = section.sh_size();
+      let (compression, uncompressed_size) = match self.compression_header(section) {
+        Ok(Some(header)) => (DebugCompression::Chdr(header.ch_type()), header.ch_size()),
+        _ if name.starts_with(".zdebug_") => {
+          let bytes = self.section_bytes(section).unwrap_or_default();
+          match bytes.get(..12) {
+            Some([b'Z', b'L', b'I', b'B', size @ ..]) => (
+              DebugCompression::Gnu,
+              u64::from_be_bytes(size.try_into().unwrap_or_default()),
+            ),
+            _ => (DebugCompression::None, size),
+          }
+        }
+        _ => (DebugCompression::None, size),
+      };
+
+      sections.push(DebugSection {
+        name,
+        size,
+        compression,
+        uncompressed_size,
+      });
+    }
+
+    sections
+  }
+
+  ///
+  /// Returns the file name and the CRC-32 (see [`crate::utils::crc32()`]) of
+  /// the separate debug file named by `.gnu_debuglink`. The name ends with a
+  /// NUL, then NULs pad it to a multiple of 4 bytes: the CRC (in the byte
+  /// order of the file) is at the next 4-byte boundary after the first NUL,
+  /// not right after it, nor at the end of the section.
+  ///
+  pub fn debuglink(&self) -> Option<(Cow<'data, str>, u32)> {
+    let section = self.find_section(".gnu_debuglink")?;
+    let bytes = self.section_bytes(section).ok()?;
+    let end = bytes.iter().position(|&byte| byte == 0)?;
+    let crc: u64 = (*bytes
+      .read_pod::<ElfType::Word>((end + 1).next_multiple_of(4))
+      .ok()?)
+    .into();
+    Some((printable(&bytes[..end]), crc as u32))
+  }
+
+  ///
+  /// Returns the bytes of `.gnu_debugdata` (MiniDebugInfo, Fedora and RHEL):
+  /// an xz-compressed ELF file holding the symbols stripped from `.symtab`
+  /// (local functions) for the backtraces, see [`Self::debugdata_elf()`].
+  ///
+  pub fn debugdata(&self) -> Option<&'data [u8]> {
+    let section = self.find_section(".gnu_debugdata")?;
+    self.section_bytes(section).ok()
+  }
+
+  ///
+  /// Returns the ELF file embedded in `.gnu_debugdata` (see
+  /// [`Self::debugdata()`]), decompressed up to [`DEBUGDATA_LIMIT`] bytes
+  /// or the `max_bytes` of the [`Limits`](super::Limits) if lower. It is a
+  /// nested input, parse it with `self.options().nested()`.
+  ///
+  #[cfg(feature = "xz")]
+  pub fn debugdata_elf(&self) -> Result<Vec<u8>, ElfError> {
+    use crate::utils::decompress_xz;
+
+    let limits = self.options().nested()?.limits;
+    let bytes = self
+      .debugdata()
+      .ok_or(ElfError::DecompressionFailed(String::from(
+        "no .gnu_debugdata section",
+      )))?;
+    let limit = limits.max_bytes.map_or(DEBUGDATA_LIMIT, |max_bytes| {
+      DEBUGDATA_LIMIT.min(usize::try_from(max_bytes).unwrap_or(usize::MAX))
+    });
+    decompress_xz(bytes, limit).map_err(|error| match limit < DEBUGDATA_LIMIT {
+      true if error == "decompressed size above the limit" => ElfError::LimitExceeded(super::Limit::Bytes),
+      _ => ElfError::DecompressionFailed(error.to_string()),
+    })
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+impl fmt::Display for DebugCompression {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::None => formatter.pad("none"),
+      Self::Chdr(ELFCOMPRESS_ZLIB) => formatter.pad("ZLIB"),
+      Self::Chdr(ELFCOMPRESS_ZSTD) => formatter.pad("ZSTD"),
+      Self::Chdr(ch_type) => formatter.pad(&format!("{:#x}", ch_type)),
+      Self::Gnu => formatter.pad("ZLIB (GNU)"),
+    }
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+
+  #[test]
+  fn debug_sections() {
+    #[rustfmt::skip]
+    let bytes = hex(
+      r"
+        7F 'ELF 02 01 01 00 00 000000 00000000 ; ELFCLASS64, ELFDATA2LSB
+        0100 3E00 01000000 0000000000000000 ; ET_REL, EM_X86_64, e_version, e_entry
+        0000000000000000 4000000000000000 00000000 ; e_phoff, e_shoff, e_flags
+        4000 3800 0000 4000 0500 0400 ; e_ehsize, e_phentsize, e_phnum, e_shentsize, e_shnum, e_shstrndx
+
+        ; Section headers (null, .debug_info, .zdebug_info, .debug_str, .shstrtab)
+        00000000 00000000 0000000000000000 0000000000000000 0000000000000000
+        0000000000000000 00000000 00000000 0000000000000000 0000000000000000
+        01000000 01000000 0008000000000000 0000000000000000 8001000000000000
+        2000000000000000 00000000 00000000 0100000000000000 0000000000000000
+        0D000000 01000000 0000000000000000 0000000000000000 A001000000000000
+        1000000000000000 00000000 00000000 0100000000000000 0000000000000000
+        1A000000 01000000 0000000000000000 0000000000000000 B001000000000000
+        0400000000000000 00000000 00000000 0100000000000000 0000000000000000
+        25000000 03000000 0000000000000000 0000000000000000 B401000000000000
+        2F00000000000000 00000000 00000000 0100000000000000 0000000000000000
+
+        ; .debug_info (SHF_COMPRESSED, ELFCOMPRESS_ZSTD, ch_size 0x100)
+        02000000 00000000 0001000000000000 0100000000000000 0000000000000000
+
+        ; .zdebug_info (uncompressed size 0x40)
+        'ZLIB 0000000000000040 00000000
+
+        'str 00 ; .debug_str
+        00 '.debug_info 00 '.zdebug_info 00 '.debug_str 00 '.shstrtab 00
+      ",
+    )
+    .unwrap();
+
+    let Ok(ElfFile::Elf64Le(object)) = parse_elf(bytes.as_slice()) else {
+      panic!("Not an elf64 little-endian");
+    };
+
+    let sections = object.debug_sections();
+    assert_eq!(
+      sections,
+      vec![
+        DebugSection {
+          name: ".debug_info".into(),
+          size: 0x20,
+          compression: DebugCompression::Chdr(ELFCOMPRESS_ZSTD),
+          uncompressed_size: 0x100,
+        },
+        DebugSection {
+          name: ".zdebug_info".into(),
+          size: 0x10,
+          compression: DebugCompression::Gnu,
+          uncompressed_size: 0x40,
+        },
+        DebugSection {
+          name: ".debug_str".into(),
+          size: 4,
+          compression: DebugCompression::None,
+          uncompressed_size: 4,
+        },
+      ]
+    );
+
+    assert_eq!(
+      debug_totals(&sections),
+      vec![
+        DebugTotal {
+          category: "info",
+          size: 0x30,
+          uncompressed_size: 0x140,
+        },
+        DebugTotal {
+          category: "str",
+          size: 4,
+          uncompressed_size: 4,
+        },
+      ]
+    );
+
+    assert_eq!(DebugCompression::Chdr(ELFCOMPRESS_ZSTD).to_string(), "ZSTD");
+    assert_eq!(DebugCompression::Chdr(7).to_string(), "0x7");
+  }
+
+  #[test]
+  fn debuglink() {
+    use crate::elf::abi::sh_type::SHT_PROGBITS;
+    use crate::elf::builder::ElfBuilder;
+
+    let debuglink = |section: &[u8]| {
+      let bytes = ElfBuilder::<ElfType64<BigEndian>>::new()
+        .section(".gnu_debuglink", SHT_PROGBITS, section)
+        .build();
+      let ElfFile::Elf64Be(object) = parse_elf(bytes.as_slice()).unwrap() else {
+        unreachable!();
+      };
+      object.debuglink().map(|(name, crc)| (name.into_owned(), crc))
+    };
+
+    // Name and NUL padded from 7 to 8 bytes, then from 9 to 12 bytes.
+    let section = [b"ls.dbg\0\0".as_slice(), &0xcbf43926_u32.to_be_bytes()].concat();
+    assert_eq!(debuglink(&section), Some((String::from("ls.dbg"), 0xcbf43926)));
+    let section = [b"ls.debug\0\0\0\0".as_slice(), &0x12345678_u32.to_be_bytes()].concat();
+    assert_eq!(debuglink(&section), Some((String::from("ls.debug"), 0x12345678)));
+
+    assert_eq!(debuglink(b"ls.debug\0\0"), None, "Truncated CRC");
+    assert_eq!(debuglink(b"ls.debug"), None, "Missing NUL");
+  }
+
+  #[test]
+  fn debugdata() {
+    use crate::elf::abi::sh_type::SHT_PROGBITS;
+    use crate::elf::builder::ElfBuilder;
+
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".gnu_debugdata", SHT_PROGBITS, b"\xfd7zXZ\0\0\x04")
+      .build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    assert_eq!(object.debugdata(), Some(b"\xfd7zXZ\0\0\x04".as_slice()));
+    #[cfg(feature = "xz")]
+    assert!(matches!(
+      object.debugdata_elf(),
+      Err(ElfError::DecompressionFailed(_))
+    ));
+  }
+}
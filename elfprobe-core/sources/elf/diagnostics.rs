@@ -0,0 +1,782 @@
+#[cfg(not(feature = "std"))]
+use core::cell::{RefCell, RefMut};
+use core::fmt;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::alloc_prelude::*;
+
+use super::validation::{Severity, ValidationIssue};
+use crate::error::{chain, ElfError};
+
+// ╔═╗┌─┐┌┬┐┬┌─┐┌┐┌┌─┐
+// ║ ║├─┘ │ ││ ││││└─┐
+// ╚═╝┴   ┴ ┴└─┘┘└┘└─┘
+
+///
+/// How malformed tables are handled, see [`parse_elf_with()`](super::parse_elf_with()).
+///
+/// Strict parsing (the default) fails on the first malformed table, which
+/// suits validation pipelines. Lenient parsing records the problem (section
+/// offsets past the end of the file, bad `sh_link` targets, impossible entry
+/// sizes...) as a warning and returns the table empty or partial, as readelf
+/// does for deliberately broken files (malware, fuzzing corpora).
+///
+/// The [`Limits`] apply in both modes, `depth` is the nesting level of the
+/// input (0 for a file, see [`Self::nested()`]).
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+  pub strict: bool,
+  pub limits: Limits,
+  pub depth: u32,
+}
+
+impl ParseOptions {
+  /// Records the problems as warnings instead of failing.
+  pub const LENIENT: Self = Self {
+    strict: false,
+    limits: Limits::UNLIMITED,
+    depth: 0,
+  };
+
+  ///
+  /// Returns the options of an input nested in the one parsed with these
+  /// options: an archive member, a decompressed section or an ELF file
+  /// embedded in another one (`.gnu_debugdata`). Fails with
+  /// [`ElfError::LimitExceeded`] past [`Limits::max_depth`].
+  ///
+  pub fn nested(self) -> Result<Self, ElfError> {
+    let depth = self.depth.saturating_add(1);
+    match self.limits.max_depth {
+      Some(max_depth) if depth > max_depth => Err(ElfError::LimitExceeded(Limit::Depth)),
+      _ => Ok(Self { depth, ..self }),
+    }
+  }
+}
+
+impl Default for ParseOptions {
+  fn default() -> Self {
+    Self {
+      strict: true,
+      limits: Limits::UNLIMITED,
+      depth: 0,
+    }
+  }
+}
+
+// ╦  ┬┌┬┐┬┌┬┐┌─┐
+// ║  │││││ │ └─┐
+// ╩═╝┴┴ ┴┴ ┴ └─┘
+
+/// The ceiling of [`Limits`] which was hit, see [`ElfError::LimitExceeded`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Limit {
+  /// [`Limits::max_bytes`].
+  Bytes,
+  /// [`Limits::max_entries`].
+  Entries,
+  /// [`Limits::max_depth`].
+  Depth,
+  /// `Limits::time_budget`.
+  Time,
+}
+
+///
+/// Hard ceilings on the work done for an input, for services analyzing
+/// untrusted files. `None` is unlimited, the default (the command line).
+///
+/// ```
+/// use std::time::Duration;
+///
+/// use elfprobe_core::elf::{Limits, ParseOptions};
+///
+/// // An upload service: 64 MiB files, no archive in an archive, 2 seconds.
+/// let options = ParseOptions {
+///   limits: Limits {
+///     max_bytes: Some(64 << 20),
+///     max_entries: Some(1 << 16),
+///     max_depth: Some(1),
+///     time_budget: Some(Duration::from_secs(2)),
+///   },
+///   ..ParseOptions::default()
+/// };
+/// let member = options.nested().unwrap();
+/// assert!(member.nested().is_err());
+/// ```
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Limits {
+  /// Size of the input, and of each decompressed section.
+  pub max_bytes: Option<u64>,
+  /// Entries of a table: section and program headers, symbols, dynamic entries, relocations.
+  pub max_entries: Option<u64>,
+  /// Nesting of inputs, see [`ParseOptions::nested()`].
+  pub max_depth: Option<u32>,
+  /// Wall-clock time from the parsing, checked between the phases (see
+  /// [`ElfObject::check_time_budget()`](super::ElfObject::check_time_budget())),
+  /// ignored without the `std` feature (no clock).
+  pub time_budget: Option<Duration>,
+}
+
+impl Limits {
+  /// No limit.
+  pub const UNLIMITED: Self = Self {
+    max_bytes: None,
+    max_entries: None,
+    max_depth: None,
+    time_budget: None,
+  };
+
+  /// Fails when the size (input or decompressed data) exceeds `max_bytes`.
+  pub fn check_bytes(&self, size: u64) -> Result<(), ElfError> {
+    match self.max_bytes {
+      Some(max_bytes) if size > max_bytes => Err(ElfError::LimitExceeded(Limit::Bytes)),
+      _ => Ok(()),
+    }
+  }
+
+  /// Fails when the number of entries of a table exceeds `max_entries`.
+  pub fn check_entries(&self, count: u64) -> Result<(), ElfError> {
+    match self.max_entries {
+      Some(max_entries) if count > max_entries => Err(ElfError::LimitExceeded(Limit::Entries)),
+      _ => Ok(()),
+    }
+  }
+}
+
+impl Default for Limits {
+  fn default() -> Self {
+    Self::UNLIMITED
+  }
+}
+
+// ╔═╗┌─┐┌┬┐┌─┐┌─┐
+// ║  │ │ ││├┤ └─┐
+// ╚═╝└─┘─┴┘└─┘└─┘
+
+///
+/// Stable identifier of a check, the `code` of a [`Diagnostic`]. The strings
+/// are a contract with the tools matching them (CI jobs grepping the JSON
+/// output): a code is never renamed nor reused for another check.
+///
+/// The validation issues ([`ValidationIssue::code`]) come first, then the
+/// warnings of lenient parsing, one per [`ElfError`] variant.
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DiagnosticCode {
+  /// `ei_mag0` to `ei_mag3` are not `\x7fELF`.
+  BadMagic,
+  /// `ei_class` is neither `ELFCLASS32` nor `ELFCLASS64`.
+  BadClass,
+  /// `ei_data` is neither `ELFDATA2LSB` nor `ELFDATA2MSB`.
+  BadDataEncoding,
+  /// `ei_version` is not `EV_CURRENT`.
+  BadIdentVersion,
+  /// `ei_pad` is not zero.
+  NonZeroPadding,
+  /// `e_type` is `ET_NONE`.
+  NoFileType,
+  /// `e_version` is not `EV_CURRENT`.
+  BadVersion,
+  /// `e_ehsize` is not the size of the header.
+  BadHeaderSize,
+  /// `e_phentsize` is not the size of a program header.
+  BadProgramHeaderSize,
+  /// `e_shentsize` is not the size of a section header.
+  BadSectionHeaderSize,
+  /// The program header table runs past the end of the file.
+  TruncatedProgramHeaders,
+  /// The section header table runs past the end of the file.
+  TruncatedSectionHeaders,
+  /// `e_shstrndx` is not a section index.
+  BadStringTableIndex,
+  /// A field of the null section which is not zero.
+  NonZeroNullSection,
+  /// The contents of a section run past the end of the file.
+  TruncatedSection,
+  /// The contents of two sections overlap.
+  OverlappingSections,
+  /// The contents of a segment run past the end of the file.
+  TruncatedSegment,
+  /// `p_filesz` is greater than `p_memsz`.
+  FileSizeExceedsMemorySize,
+  /// `p_align` is neither zero nor a power of two.
+  BadSegmentAlignment,
+  /// `p_vaddr` and `p_offset` of a `PT_LOAD` are not congruent modulo `p_align`.
+  MisalignedSegment,
+  /// `PT_PHDR` or `PT_INTERP` after a `PT_LOAD` segment.
+  MisorderedSegment,
+  /// `PT_PHDR` does not match the program header table.
+  ProgramHeaderMismatch,
+  /// `PT_PHDR` is not loaded by a `PT_LOAD` segment.
+  UnloadedProgramHeaders,
+  /// `PT_INTERP` without `PT_PHDR`.
+  MissingProgramHeaders,
+  /// A relocatable object without `.note.GNU-stack`.
+  MissingStackNote,
+  /// `.note.GNU-stack` with `SHF_EXECINSTR`.
+  ExecutableStack,
+
+  /// [`ElfError::Io`].
+  Io,
+  /// [`ElfError::BytesError`].
+  UnreadableBytes,
+  /// [`ElfError::UnsupportedCompression`].
+  UnsupportedCompression,
+  /// [`ElfError::DecompressionFailed`].
+  DecompressionFailed,
+  /// [`ElfError::DecompressedSizeMismatch`].
+  DecompressedSizeMismatch,
+  /// [`ElfError::LinkerScript`].
+  LinkerScript,
+  /// [`ElfError::Truncated`].
+  Truncated,
+  /// [`ElfError::InvalidSectionIndex`].
+  BadSectionIndex,
+  /// [`ElfError::IdentificationMismatch`].
+  IdentificationMismatch,
+  /// [`ElfError::InvalidSymbolName`].
+  BadSymbolName,
+  /// [`ElfError::InvalidStringOffset`].
+  BadStringOffset,
+  /// [`ElfError::CountClamped`].
+  CountClamped,
+  /// [`ElfError::EntrySizeMismatch`].
+  BadEntrySize,
+  /// [`ElfError::UnknownRelativeType`].
+  UnknownRelativeType,
+  /// [`ElfError::UnmappedAddress`].
+  UnmappedAddress,
+  /// [`ElfError::UnbackedAddress`].
+  UnbackedAddress,
+  /// [`ElfError::InvalidArchive`].
+  BadArchive,
+  /// [`ElfError::NoInterpreter`].
+  NoInterpreter,
+  /// [`ElfError::InterpreterTooLong`].
+  InterpreterTooLong,
+  /// [`ElfError::NoStackSegment`].
+  NoStackSegment,
+  /// [`ElfError::CannotRemoveSection`].
+  CannotRemoveSection,
+  /// [`ElfError::LimitExceeded`].
+  LimitExceeded,
+}
+
+impl DiagnosticCode {
+  /// All the codes, in declaration order.
+  pub const ALL: &'static [Self] = &[
+    Self::BadMagic,
+    Self::BadClass,
+    Self::BadDataEncoding,
+    Self::BadIdentVersion,
+    Self::NonZeroPadding,
+    Self::NoFileType,
+    Self::BadVersion,
+    Self::BadHeaderSize,
+    Self::BadProgramHeaderSize,
+    Self::BadSectionHeaderSize,
+    Self::TruncatedProgramHeaders,
+    Self::TruncatedSectionHeaders,
+    Self::BadStringTableIndex,
+    Self::NonZeroNullSection,
+    Self::TruncatedSection,
+    Self::OverlappingSections,
+    Self::TruncatedSegment,
+    Self::FileSizeExceedsMemorySize,
+    Self::BadSegmentAlignment,
+    Self::MisalignedSegment,
+    Self::MisorderedSegment,
+    Self::ProgramHeaderMismatch,
+    Self::UnloadedProgramHeaders,
+    Self::MissingProgramHeaders,
+    Self::MissingStackNote,
+    Self::ExecutableStack,
+    Self::Io,
+    Self::UnreadableBytes,
+    Self::UnsupportedCompression,
+    Self::DecompressionFailed,
+    Self::DecompressedSizeMismatch,
+    Self::LinkerScript,
+    Self::Truncated,
+    Self::BadSectionIndex,
+    Self::IdentificationMismatch,
+    Self::BadSymbolName,
+    Self::BadStringOffset,
+    Self::CountClamped,
+    Self::BadEntrySize,
+    Self::UnknownRelativeType,
+    Self::UnmappedAddress,
+    Self::UnbackedAddress,
+    Self::BadArchive,
+    Self::NoInterpreter,
+    Self::InterpreterTooLong,
+    Self::NoStackSegment,
+    Self::CannotRemoveSection,
+    Self::LimitExceeded,
+  ];
+
+  /// Returns the identifier, in kebab case.
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::BadMagic => "bad-magic",
+      Self::BadClass => "bad-class",
+      Self::BadDataEncoding => "bad-data-encoding",
+      Self::BadIdentVersion => "bad-ident-version",
+      Self::NonZeroPadding => "non-zero-padding",
+      Self::NoFileType => "no-file-type",
+      Self::BadVersion => "bad-version",
+      Self::BadHeaderSize => "bad-ehsize",
+      Self::BadProgramHeaderSize => "bad-phentsize",
+      Self::BadSectionHeaderSize => "bad-shentsize",
+      Self::TruncatedProgramHeaders => "truncated-phdr-table",
+      Self::TruncatedSectionHeaders => "truncated-shdr-table",
+      Self::BadStringTableIndex => "bad-shstrndx",
+      Self::NonZeroNullSection => "non-zero-null-section",
+      Self::TruncatedSection => "truncated-section",
+      Self::OverlappingSections => "overlapping-sections",
+      Self::TruncatedSegment => "truncated-segment",
+      Self::FileSizeExceedsMemorySize => "filesz-exceeds-memsz",
+      Self::BadSegmentAlignment => "bad-segment-alignment",
+      Self::MisalignedSegment => "misaligned-segment",
+      Self::MisorderedSegment => "misordered-segment",
+      Self::ProgramHeaderMismatch => "phdr-mismatch",
+      Self::UnloadedProgramHeaders => "unloaded-phdr",
+      Self::MissingProgramHeaders => "missing-phdr",
+      Self::MissingStackNote => "missing-stack-note",
+      Self::ExecutableStack => "exec-stack",
+      Self::Io => "io-error",
+      Self::UnreadableBytes => "unreadable-bytes",
+      Self::UnsupportedCompression => "unsupported-compression",
+      Self::DecompressionFailed => "decompression-failed",
+      Self::DecompressedSizeMismatch => "decompressed-size-mismatch",
+      Self::LinkerScript => "linker-script",
+      Self::Truncated => "truncated",
+      Self::BadSectionIndex => "bad-section-index",
+      Self::IdentificationMismatch => "identification-mismatch",
+      Self::BadSymbolName => "bad-symbol-name",
+      Self::BadStringOffset => "bad-string-offset",
+      Self::CountClamped => "count-clamped",
+      Self::BadEntrySize => "bad-entry-size",
+      Self::UnknownRelativeType => "unknown-relative-type",
+      Self::UnmappedAddress => "unmapped-address",
+      Self::UnbackedAddress => "unbacked-address",
+      Self::BadArchive => "bad-archive",
+      Self::NoInterpreter => "no-interpreter",
+      Self::InterpreterTooLong => "interpreter-too-long",
+      Self::NoStackSegment => "no-stack-segment",
+      Self::CannotRemoveSection => "cannot-remove-section",
+      Self::LimitExceeded => "limit-exceeded",
+    }
+  }
+}
+
+impl From<&ElfError> for DiagnosticCode {
+  /// Returns the code of the low-level cause (the contexts are not checks).
+  fn from(error: &ElfError) -> Self {
+    match error {
+      #[cfg(feature = "std")]
+      ElfError::Io(_) => Self::Io,
+      ElfError::BytesError(_) => Self::UnreadableBytes,
+      ElfError::UnsupportedCompression(_) => Self::UnsupportedCompression,
+      ElfError::DecompressionFailed(_) => Self::DecompressionFailed,
+      ElfError::DecompressedSizeMismatch { .. } => Self::DecompressedSizeMismatch,
+      ElfError::BadMagic(_) => Self::BadMagic,
+      ElfError::LinkerScript { .. } => Self::LinkerScript,
+      ElfError::Truncated { .. } => Self::Truncated,
+      ElfError::InvalidClass(_) => Self::BadClass,
+      ElfError::UnsupportedClassData { .. } => Self::BadDataEncoding,
+      ElfError::InvalidSectionIndex(_) => Self::BadSectionIndex,
+      ElfError::IdentificationMismatch { .. } => Self::IdentificationMismatch,
+      ElfError::UnexpectedVersion {
+        field: "ei_version", ..
+      } => Self::BadIdentVersion,
+      ElfError::UnexpectedVersion { .. } => Self::BadVersion,
+      ElfError::InvalidSymbolName { .. } => Self::BadSymbolName,
+      ElfError::InvalidStringOffset { .. } => Self::BadStringOffset,
+      ElfError::CountClamped { .. } => Self::CountClamped,
+      ElfError::EntrySizeMismatch { .. } => Self::BadEntrySize,
+      ElfError::UnknownRelativeType { .. } => Self::UnknownRelativeType,
+      ElfError::UnmappedAddress(_) => Self::UnmappedAddress,
+      ElfError::UnbackedAddress { .. } => Self::UnbackedAddress,
+      ElfError::InvalidArchive { .. } => Self::BadArchive,
+      ElfError::NoInterpreter => Self::NoInterpreter,
+      ElfError::InterpreterTooLong { .. } => Self::InterpreterTooLong,
+      ElfError::NoStackSegment => Self::NoStackSegment,
+      ElfError::CannotRemoveSection { .. } => Self::CannotRemoveSection,
+      ElfError::LimitExceeded(_) => Self::LimitExceeded,
+      ElfError::Context { source, .. } => Self::from(source.as_ref()),
+    }
+  }
+}
+
+///
+/// A warning of lenient parsing or a validation issue, in one shape for the
+/// machine-readable outputs (the `diagnostics` array of JSON documents):
+/// `{"severity": "warning", "code": "exec-stack", "message": "...", "offset": 600}`.
+/// The offset is the one of the faulty field when known, `null` otherwise.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Diagnostic {
+  pub severity: Severity,
+  pub code: DiagnosticCode,
+  pub message: String,
+  pub offset: Option<u64>,
+}
+
+impl From<&ValidationIssue> for Diagnostic {
+  fn from(issue: &ValidationIssue) -> Self {
+    Self {
+      severity: issue.severity,
+      code: issue.code,
+      message: format!(
+        "{} is {:#x}, expected {}",
+        issue.field, issue.actual, issue.expected
+      ),
+      offset: Some(issue.offset as u64),
+    }
+  }
+}
+
+impl From<&ElfError> for Diagnostic {
+  /// Lenient parsing only records warnings, the message is the error chain.
+  fn from(error: &ElfError) -> Self {
+    let offset = match error.root() {
+      ElfError::InvalidArchive { offset, .. } => Some(*offset as u64),
+      _ => None,
+    };
+    Self {
+      severity: Severity::Warning,
+      code: DiagnosticCode::from(error),
+      message: chain(error)
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(": "),
+      offset,
+    }
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┌┐┌┌─┐┌─┐┌┬┐┬┌─┐┌─┐
+//  ║║│├─┤│ ┬││││ │└─┐ │ ││  └─┐
+// ═╩╝┴┴ ┴└─┘┘└┘└─┘└─┘ ┴ ┴└─┘└─┘
+
+///
+/// Warnings recorded while reading the tables of an object. Tables are read
+/// again on every call, a warning is therefore only recorded once. They are
+/// behind a lock with `std`, so that the object can be shared between threads.
+///
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+  #[cfg(feature = "std")]
+  warnings: Mutex<Vec<ElfError>>,
+  #[cfg(not(feature = "std"))]
+  warnings: RefCell<Vec<ElfError>>,
+}
+
+impl Diagnostics {
+  /// Records the warning unless it was already recorded.
+  pub fn warn(&self, warning: ElfError) {
+    let mut warnings = self.warnings();
+    if !warnings.contains(&warning) {
+      warnings.push(warning);
+    }
+  }
+
+  /// Returns `true` when no warning has been recorded.
+  pub fn is_empty(&self) -> bool {
+    self.warnings().is_empty()
+  }
+
+  /// Returns the warnings recorded so far as [`Diagnostic`]s, without clearing them.
+  pub fn to_diagnostics(&self) -> Vec<Diagnostic> {
+    self.warnings().iter().map(Diagnostic::from).collect()
+  }
+
+  /// Returns the warnings recorded so far and clears them.
+  pub fn take(&self) -> Vec<ElfError> {
+    ::core::mem::take(&mut *self.warnings())
+  }
+
+  /// Locks the warnings, a thread which panicked cannot leave them halfway.
+  #[cfg(feature = "std")]
+  fn warnings(&self) -> MutexGuard<'_, Vec<ElfError>> {
+    self.warnings.lock().unwrap_or_else(PoisonError::into_inner)
+  }
+
+  #[cfg(not(feature = "std"))]
+  fn warnings(&self) -> RefMut<'_, Vec<ElfError>> {
+    self.warnings.borrow_mut()
+  }
+
+  ///
+  /// Returns the value when the result is `Ok`. Otherwise the error is
+  /// returned in strict mode, or recorded and the (partial) value returned
+  /// in lenient mode.
+  ///
+  pub(crate) fn recover<T>(
+    &self,
+    options: ParseOptions,
+    result: Result<(), ElfError>,
+    value: T,
+  ) -> Result<T, ElfError> {
+    match result {
+      Ok(()) => Ok(value),
+      Err(error) if options.strict => Err(error),
+      Err(error) => {
+        self.warn(error);
+        Ok(value)
+      }
+    }
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+impl fmt::Display for Limit {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    formatter.pad(match self {
+      Self::Bytes => "max_bytes",
+      Self::Entries => "max_entries",
+      Self::Depth => "max_depth",
+      Self::Time => "time_budget",
+    })
+  }
+}
+
+impl fmt::Display for DiagnosticCode {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    formatter.pad(self.as_str())
+  }
+}
+
+/// Serialized as its identifier, e.g. `"exec-stack"`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for DiagnosticCode {
+  fn serialize<Serializer: serde::Serializer>(
+    &self,
+    serializer: Serializer,
+  ) -> Result<Serializer::Ok, Serializer::Error> {
+    serializer.serialize_str(self.as_str())
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn recover() {
+    let diagnostics = Diagnostics::default();
+    let error = ElfError::InvalidSectionIndex(7);
+
+    let strict = ParseOptions::default();
+    assert_eq!(diagnostics.recover(strict, Ok(()), 1), Ok(1));
+    assert_eq!(
+      diagnostics.recover(strict, Err(error), 2),
+      Err(ElfError::InvalidSectionIndex(7))
+    );
+    assert!(diagnostics.is_empty());
+
+    let lenient = ParseOptions::LENIENT;
+    assert_eq!(
+      diagnostics.recover(lenient, Err(ElfError::InvalidSectionIndex(7)), 3),
+      Ok(3)
+    );
+    assert_eq!(
+      diagnostics.recover(lenient, Err(ElfError::InvalidSectionIndex(7)), 4),
+      Ok(4)
+    );
+    assert_eq!(diagnostics.take(), [ElfError::InvalidSectionIndex(7)]);
+    assert!(diagnostics.is_empty());
+  }
+
+  #[test]
+  fn limits() {
+    use crate::elf::abi::sh_type::SHT_PROGBITS;
+    use crate::elf::sections::SHF_COMPRESSED;
+    use crate::elf::{parse_elf_with, BuilderSection, ElfBuilder, ElfFile, ElfType64, LittleEndian};
+
+    // A compression header (ELFCOMPRESS_ZLIB) announcing 4 KiB.
+    let mut chdr = vec![1, 0, 0, 0, 0, 0, 0, 0];
+    chdr.extend(0x1000_u64.to_le_bytes());
+    chdr.extend(1_u64.to_le_bytes());
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".text", SHT_PROGBITS, &[0xC3; 0x10])
+      .section(".data", SHT_PROGBITS, &[0; 0x10])
+      .add_section(BuilderSection {
+        name: String::from(".debug_info"),
+        sh_type: SHT_PROGBITS,
+        sh_flags: SHF_COMPRESSED,
+        data: chdr,
+        ..Default::default()
+      })
+      .build();
+    let parse = |limits| {
+      let options = ParseOptions {
+        limits,
+        ..ParseOptions::default()
+      };
+      match parse_elf_with(bytes.as_slice(), options) {
+        Ok(ElfFile::Elf64Le(object)) => Ok(object),
+        Ok(_) => unreachable!(),
+        Err(error) => Err(error),
+      }
+    };
+    let exceeded = |limit| Err(ElfError::LimitExceeded(limit));
+
+    // Unlimited by default.
+    let object = parse(Limits::default()).unwrap();
+    assert_eq!(object.section_headers().unwrap().len(), 5);
+    assert!(object.diagnostics().is_empty());
+
+    // The input, then the decompressed data.
+    let limits = |max_bytes| Limits {
+      max_bytes: Some(max_bytes),
+      ..Limits::UNLIMITED
+    };
+    assert_eq!(
+      parse(limits(bytes.len() as u64 - 1)).unwrap_err(),
+      ElfError::LimitExceeded(Limit::Bytes)
+    );
+    let object = parse(limits(bytes.len() as u64)).unwrap();
+    let section = object.find_section(".debug_info").unwrap();
+    assert_eq!(object.section_data(section).map(|_| ()), exceeded(Limit::Bytes));
+
+    // Entries: the tables fail, the iterators stop.
+    let object = parse(Limits {
+      max_entries: Some(3),
+      ..Limits::UNLIMITED
+    })
+    .unwrap();
+    assert_eq!(object.section_headers().map(|_| ()), exceeded(Limit::Entries));
+    assert_eq!(object.sections().count(), 3);
+    assert_eq!(
+      object.diagnostics().take(),
+      [ElfError::LimitExceeded(Limit::Entries)]
+    );
+
+    // Nesting: the compressed section is one level down.
+    let object = parse(Limits {
+      max_depth: Some(0),
+      ..Limits::UNLIMITED
+    })
+    .unwrap();
+    assert_eq!(object.section_data(section).map(|_| ()), exceeded(Limit::Depth));
+    assert_eq!(object.options().nested().map(|_| ()), exceeded(Limit::Depth));
+    let options = ParseOptions::LENIENT.nested().unwrap().nested().unwrap();
+    assert_eq!((options.depth, options.strict), (2, false));
+
+    // Time, from the parsing (no clock without std).
+    let object = parse(Limits {
+      time_budget: Some(Duration::ZERO),
+      ..Limits::UNLIMITED
+    })
+    .unwrap();
+    #[cfg(not(feature = "std"))]
+    assert_eq!(object.check_time_budget(), Ok(()));
+    #[cfg(feature = "std")]
+    {
+      ::std::thread::sleep(Duration::from_millis(1));
+      assert_eq!(object.check_time_budget(), exceeded(Limit::Time));
+      assert_eq!(object.program_headers().map(|_| ()), exceeded(Limit::Time));
+      assert_eq!(
+        DiagnosticCode::from(&object.program_headers().unwrap_err()).as_str(),
+        "limit-exceeded"
+      );
+    }
+  }
+
+  #[test]
+  fn codes() {
+    let mut codes = DiagnosticCode::ALL
+      .iter()
+      .map(|code| code.as_str())
+      .collect::<Vec<_>>();
+    assert!(codes.iter().all(|code| code
+      .split('-')
+      .all(|word| !word.is_empty() && word.bytes().all(|byte| byte.is_ascii_lowercase()))));
+    codes.sort_unstable();
+    codes.dedup();
+    assert_eq!(codes.len(), DiagnosticCode::ALL.len(), "Codes are unique");
+    assert_eq!(DiagnosticCode::ExecutableStack.to_string(), "exec-stack");
+
+    // Contexts are not checks, the code is the one of the cause.
+    let error = ElfError::Context {
+      context: String::from("reading the dynamic table"),
+      source: Box::new(ElfError::Truncated {
+        needed: 16,
+        available: 8,
+      }),
+    };
+    assert_eq!(
+      Diagnostic::from(&error),
+      Diagnostic {
+        severity: Severity::Warning,
+        code: DiagnosticCode::Truncated,
+        message: String::from(
+          "reading the dynamic table: file truncated, 16 bytes needed but only 8 available"
+        ),
+        offset: None,
+      }
+    );
+  }
+
+  #[test]
+  fn findings() {
+    use crate::elf::{parse_elf_with, ElfBuilder, ElfFile, ElfType64, LittleEndian};
+
+    let mut bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .object_type(2)
+      .build();
+    bytes[6] = 0; // ei_version
+
+    let Ok(ElfFile::Elf64Le(object)) = parse_elf_with(bytes.as_slice(), ParseOptions::LENIENT) else {
+      panic!("Expected a little endian elf64");
+    };
+    let findings = object.findings();
+    let codes = findings
+      .iter()
+      .map(|finding| (finding.code, finding.offset))
+      .collect::<Vec<_>>();
+    // The warning of lenient parsing, then the validation issue.
+    assert_eq!(
+      codes,
+      [
+        (DiagnosticCode::BadIdentVersion, None),
+        (DiagnosticCode::BadIdentVersion, Some(6))
+      ]
+    );
+    assert_eq!(findings[1].message, "ei_version is 0x0, expected EV_CURRENT (1)");
+    assert!(!object.diagnostics().is_empty(), "The warnings are kept");
+  }
+
+  #[test]
+  #[cfg(feature = "serde")]
+  fn serialize() {
+    let diagnostic = Diagnostic {
+      severity: Severity::Error,
+      code: DiagnosticCode::TruncatedSectionHeaders,
+      message: String::from("e_shoff is 0x1000, expected section headers within the file (64 bytes)"),
+      offset: Some(0x28),
+    };
+    assert_eq!(
+      serde_json::to_value(&diagnostic).unwrap(),
+      serde_json::json!({
+        "severity": "error",
+        "code": "truncated-shdr-table",
+        "message": "e_shoff is 0x1000, expected section headers within the file (64 bytes)",
+        "offset": 0x28,
+      })
+    );
+  }
+}
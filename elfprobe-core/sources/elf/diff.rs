@@ -0,0 +1,658 @@
+use ::core::fmt;
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+
+use super::sections::SHT_NOBITS;
+use super::view::{ElfView, HeaderEntry, SectionEntry, SegmentEntry, SymbolEntry};
+use super::*;
+
+// ╔═╗┬ ┬┌─┐┌┐┌┌─┐┌─┐┌─┐
+// ║  ├─┤├─┤││││ ┬├┤ └─┐
+// ╚═╝┴ ┴┴ ┴┘└┘└─┘└─┘└─┘
+
+/// Number of sections with different contents shown by [`ElfDiff`].
+pub const CONTENT_CHANGES: usize = 8;
+
+/// Difference between two entries matched by [`ElfDiff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change<Item> {
+  /// Only in the new file.
+  Added(Item),
+  /// Only in the old file.
+  Removed(Item),
+  /// In both files with different values (old, new).
+  Changed(Item, Item),
+}
+
+///
+/// Parts of the files allowed to differ between two builds of the same
+/// sources, masked by [`ElfDiff::with_ignored()`] (`--ignore`).
+///
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Ignored {
+  /// The build ID descriptor (`NT_GNU_BUILD_ID`), a hash of the contents.
+  pub build_id: bool,
+  /// The DWARF sections (`.debug_*`, `.zdebug_*`) with the debug links and
+  /// the section header table fields they move (`e_shoff`, `.shstrtab`).
+  pub debug: bool,
+  /// The `.comment` section (compiler and linker versions).
+  pub comment: bool,
+}
+
+impl Ignored {
+  fn section(&self, name: &str) -> bool {
+    let debug = [".debug", ".zdebug"]
+      .iter()
+      .any(|prefix| name.starts_with(prefix))
+      || matches!(name, ".gnu_debuglink" | ".gnu_debugaltlink" | ".shstrtab");
+    (self.debug && debug) || (self.comment && name == ".comment")
+  }
+
+  fn header(&self, label: &str) -> bool {
+    let moved = [
+      "Start of section headers",
+      "Number of section headers",
+      "Section header string table index",
+    ];
+    self.debug && moved.contains(&label)
+  }
+}
+
+///
+/// First difference in the contents of a section present in both files with
+/// the same size: its offset in the section then in each file.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentChange<'data> {
+  pub name: Cow<'data, str>,
+  pub offset: u64,
+  pub old_offset: u64,
+  pub new_offset: u64,
+}
+
+///
+/// Structural differences between two ELF files, e.g. to review what a
+/// patch changed in a shipped binary. The files may differ in class and
+/// byte order, they are compared through their [`ElfView`].
+///
+/// Entries are matched by key, the n-th entry with a given key in the old
+/// file with the n-th one in the new file (names may repeat):
+///
+/// - Sections by name, compared on their type, flags, address and size
+///   (offsets follow the sizes of the previous sections).
+/// - Segments by type, compared on all their fields.
+/// - Symbols by name and table (`.symtab` or `.dynsym`), compared on their
+///   value, size and binding. Symbols without name are ignored.
+///
+/// With [`Self::with_ignored()`], the contents of the sections are compared
+/// too and the [`Ignored`] parts are left out, to check that two builds are
+/// reproducible.
+///
+/// Displayed as a unified diff, `-` for the old file and `+` for the new
+/// (`!` for the contents).
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ElfDiff<'data> {
+  /// Header fields which differ (label, old value, new value).
+  pub header: Vec<(String, String, String)>,
+  pub sections: Vec<Change<SectionEntry<'data>>>,
+  pub segments: Vec<Change<SegmentEntry>>,
+  pub symbols: Vec<Change<SymbolEntry<'data>>>,
+  pub contents: Vec<ContentChange<'data>>,
+}
+
+impl<'data> ElfDiff<'data> {
+  /// Compares the structure of the old file with the new one.
+  pub fn new(old: &dyn ElfView<'data>, new: &dyn ElfView<'data>) -> Self {
+    Self::compare(old, new, None)
+  }
+
+  /// Compares the structure and the contents, but the ignored parts.
+  pub fn with_ignored(old: &dyn ElfView<'data>, new: &dyn ElfView<'data>, ignored: Ignored) -> Self {
+    Self::compare(old, new, Some(ignored))
+  }
+
+  fn compare(old: &dyn ElfView<'data>, new: &dyn ElfView<'data>, ignored: Option<Ignored>) -> Self {
+    let kept = |name: &Option<Cow<str>>| {
+      !ignored.is_some_and(|ignored| ignored.section(name.as_deref().unwrap_or_default()))
+    };
+
+    let (old_header, new_header) = (header_fields(&old.header()), header_fields(&new.header()));
+    let header = old_header
+      .into_iter()
+      .zip(new_header)
+      .filter(|(old, new)| old.1 != new.1)
+      .filter(|(old, _)| !ignored.is_some_and(|ignored| ignored.header(old.0)))
+      .map(|((label, old), (_, new))| (String::from(label), old, new))
+      .collect();
+
+    let sections = |view: &dyn ElfView<'data>| {
+      view
+        .sections()
+        .skip(1)
+        .filter(|section| kept(&section.name))
+        .collect::<Vec<_>>()
+    };
+    let contents = match ignored {
+      Some(ignored) => contents(old, new, &sections(old), &sections(new), ignored),
+      None => Vec::new(),
+    };
+    let sections = matching(
+      sections(old),
+      sections(new),
+      |section| section.name.clone(),
+      |old, new| {
+        (old.sh_type, old.sh_flags, old.sh_addr, old.sh_size)
+          == (new.sh_type, new.sh_flags, new.sh_addr, new.sh_size)
+      },
+    );
+
+    let segments = matching(
+      old.segments().collect(),
+      new.segments().collect(),
+      |segment| segment.p_type,
+      |old, new| old == new,
+    );
+
+    let named = |view: &dyn ElfView<'data>| {
+      view
+        .symbols()
+        .filter(|symbol| symbol.name.as_deref().is_some_and(|name| !name.is_empty()))
+        .collect()
+    };
+    let symbols = matching(
+      named(old),
+      named(new),
+      |symbol| (symbol.dynamic, symbol.name.clone()),
+      |old, new| {
+        (old.st_value, old.st_size, old.st_info >> 4) == (new.st_value, new.st_size, new.st_info >> 4)
+      },
+    );
+
+    Self {
+      header,
+      sections,
+      segments,
+      symbols,
+      contents,
+    }
+  }
+
+  /// Returns `true` when the files do not differ (but in the ignored parts).
+  pub fn is_empty(&self) -> bool {
+    self.header.is_empty()
+      && self.sections.is_empty()
+      && self.segments.is_empty()
+      && self.symbols.is_empty()
+      && self.contents.is_empty()
+  }
+}
+
+///
+/// Returns the fields of the header with their values, the constants with
+/// their name and value (`ET_DYN(0x3)`) as [`ElfView::header_text()`].
+///
+fn header_fields(header: &HeaderEntry) -> [(&'static str, String); 20] {
+  let ident = header.e_ident;
+  let bytes = |bytes: &[u8]| {
+    bytes
+      .iter()
+      .map(|byte| format!("{:02x}", byte))
+      .collect::<Vec<_>>()
+      .join(" ")
+  };
+  let osabi = ident[EI_OSABI];
+
+  [
+    ("Magic", bytes(&ident[EI_MAG0..EI_CLASS])),
+    ("Class", format!("{:#}", abi::ei_class::from(ident[EI_CLASS]))),
+    ("Data", format!("{:#}", abi::ei_data::from(ident[EI_DATA]))),
+    (
+      "Version",
+      format!("{:#}", abi::e_version::from(ident[EI_VERSION] as u32)),
+    ),
+    ("OS/ABI", format!("{:#}", abi::ei_osabi::from(osabi))),
+    ("ABI Version", ident[EI_ABIVERSION].to_string()),
+    ("Padding", bytes(&ident[EI_PAD..])),
+    (
+      "Type",
+      format!("{:#}", abi::object_type(header.e_machine, osabi, header.e_type)),
+    ),
+    ("Machine", format!("{:#}", abi::e_machine::from(header.e_machine))),
+    ("Version", format!("{:#}", abi::e_version::from(header.e_version))),
+    ("Entry point address", format!("{:#x}", header.e_entry)),
+    ("Start of program headers", format!("{:#x}", header.e_phoff)),
+    ("Start of section headers", format!("{:#x}", header.e_shoff)),
+    ("Flags", format!("{:#x}", header.e_flags)),
+    ("Size of this header", header.e_ehsize.to_string()),
+    ("Size of program headers", header.e_phentsize.to_string()),
+    ("Number of program headers", header.e_phnum.to_string()),
+    ("Size of section headers", header.e_shentsize.to_string()),
+    ("Number of section headers", header.e_shnum.to_string()),
+    ("Section header string table index", header.e_shstrndx.to_string()),
+  ]
+}
+
+///
+/// Compares the contents of the sections with the same name and size (the
+/// n-th occurrence of a name with the n-th one), the build ID descriptor is
+/// masked when ignored. `SHT_NOBITS` sections have no contents.
+///
+fn contents<'data>(
+  old: &dyn ElfView<'data>,
+  new: &dyn ElfView<'data>,
+  old_sections: &[SectionEntry<'data>],
+  new_sections: &[SectionEntry<'data>],
+  ignored: Ignored,
+) -> Vec<ContentChange<'data>> {
+  // Range of the build ID descriptor in the section data.
+  let build_id = |view: &dyn ElfView<'data>, data: &[u8]| {
+    let id = view.build_id().filter(|_| ignored.build_id)?;
+    let start = (id.as_ptr() as usize).checked_sub(data.as_ptr() as usize)?;
+    (start + id.len() <= data.len()).then_some(start..start + id.len())
+  };
+
+  let mut counts = BTreeMap::new();
+  let mut new_sections = new_sections
+    .iter()
+    .map(|section| {
+      let count = counts.entry(&section.name).or_insert(0usize);
+      *count += 1;
+      ((&section.name, *count), section)
+    })
+    .collect::<BTreeMap<_, _>>();
+
+  let mut counts = BTreeMap::new();
+  let mut changes = Vec::new();
+  for section in old_sections {
+    let count = counts.entry(&section.name).or_insert(0usize);
+    *count += 1;
+    let Some(other) = new_sections.remove(&(&section.name, *count)) else {
+      continue;
+    };
+    if section.sh_type == SHT_NOBITS || section.sh_size != other.sh_size {
+      continue;
+    }
+
+    let (Ok(old_data), Ok(new_data)) = (old.section_data(section.index), new.section_data(other.index))
+    else {
+      continue;
+    };
+    let masks = [build_id(old, old_data), build_id(new, new_data)];
+    let first = old_data
+      .iter()
+      .zip(new_data)
+      .enumerate()
+      .position(|(index, (old, new))| {
+        old != new && !masks.iter().flatten().any(|mask| mask.contains(&index))
+      });
+
+    if let Some(offset) = first {
+      changes.push(ContentChange {
+        name: section.name.clone().unwrap_or(Cow::Borrowed("<invalid>")),
+        offset: offset as u64,
+        old_offset: section.sh_offset + offset as u64,
+        new_offset: other.sh_offset + offset as u64,
+      });
+    }
+  }
+
+  changes
+}
+
+///
+/// Matches the entries by key (the n-th occurrence of a key with the n-th
+/// one), returns the removed and changed entries in the old order followed
+/// by the added ones in the new order.
+///
+fn matching<Item, Key>(
+  old: Vec<Item>,
+  new: Vec<Item>,
+  key: impl Fn(&Item) -> Key,
+  same: impl Fn(&Item, &Item) -> bool,
+) -> Vec<Change<Item>>
+where
+  Key: Ord,
+{
+  // Keys with their occurrence, to tell duplicates apart.
+  let keys = |items: &[Item]| {
+    let mut counts = BTreeMap::new();
+    items
+      .iter()
+      .map(|item| {
+        let count = counts.entry(key(item)).or_insert(0usize);
+        *count += 1;
+        (key(item), *count)
+      })
+      .collect::<Vec<_>>()
+  };
+
+  let old_keys = keys(&old);
+  let mut new_indexes = keys(&new)
+    .into_iter()
+    .enumerate()
+    .map(|(index, key)| (key, index))
+    .collect::<BTreeMap<_, _>>();
+  let mut new = new.into_iter().map(Some).collect::<Vec<_>>();
+
+  let mut changes = Vec::new();
+  for (item, key) in old.into_iter().zip(old_keys) {
+    match new_indexes.remove(&key).and_then(|index| new[index].take()) {
+      Some(other) if same(&item, &other) => {}
+      Some(other) => changes.push(Change::Changed(item, other)),
+      None => changes.push(Change::Removed(item)),
+    }
+  }
+
+  changes.extend(new.into_iter().flatten().map(Change::Added));
+  changes
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+impl fmt::Display for ElfDiff<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if !self.header.is_empty() {
+      writeln!(formatter, "@@ Header @@")?;
+      for (label, old, new) in &self.header {
+        writeln!(formatter, "-{}: {}", label, old)?;
+        writeln!(formatter, "+{}: {}", label, new)?;
+      }
+    }
+
+    write_changes(formatter, "Sections", &self.sections, |section| {
+      let flags = sh_flags::from(section.sh_flags).short();
+      format!(
+        "{}  {}  addr {:#x}  size {:#x}{}{}",
+        section.name.as_deref().unwrap_or("<invalid>"),
+        short_name(sh_type::from(section.sh_type), "SHT_"),
+        section.sh_addr,
+        section.sh_size,
+        if flags.is_empty() { "" } else { "  flags " },
+        flags,
+      )
+    })?;
+
+    write_changes(formatter, "Segments", &self.segments, |segment| {
+      format!(
+        "{}  offset {:#x}  vaddr {:#x}  filesz {:#x}  memsz {:#x}  flags {}  align {:#x}",
+        short_name(p_type::from(segment.p_type), "PT_"),
+        segment.p_offset,
+        segment.p_vaddr,
+        segment.p_filesz,
+        segment.p_memsz,
+        p_flags::from(segment.p_flags).rwe().trim_end(),
+        segment.p_align,
+      )
+    })?;
+
+    write_changes(formatter, "Symbols", &self.symbols, |symbol| {
+      format!(
+        "{}  value {:#x}  size {:#x}  {}{}",
+        symbol.name.as_deref().unwrap_or_default(),
+        symbol.st_value,
+        symbol.st_size,
+        short_name(st_bind::from(symbol.st_info >> 4), "STB_"),
+        if symbol.dynamic { "  (dynamic)" } else { "" },
+      )
+    })?;
+
+    if !self.contents.is_empty() {
+      writeln!(formatter, "@@ Contents @@")?;
+    }
+    for change in self.contents.iter().take(CONTENT_CHANGES) {
+      writeln!(
+        formatter,
+        "!{}  differs at +{:#x}  offset {:#x}  {:#x}",
+        change.name, change.offset, change.old_offset, change.new_offset
+      )?;
+    }
+    match self.contents.len().checked_sub(CONTENT_CHANGES) {
+      Some(more @ 1..) => writeln!(formatter, "!... {} more sections", more),
+      _ => Ok(()),
+    }
+  }
+}
+
+/// Returns the name of the constant without its prefix.
+fn short_name(constant: impl ToString, prefix: &str) -> String {
+  let name = constant.to_string();
+  String::from(name.strip_prefix(prefix).unwrap_or(&name))
+}
+
+/// Writes the changes under a hunk header, the changed entries as removed then added.
+fn write_changes<Item>(
+  formatter: &mut fmt::Formatter<'_>,
+  title: &str,
+  changes: &[Change<Item>],
+  line: impl Fn(&Item) -> String,
+) -> fmt::Result {
+  if changes.is_empty() {
+    return Ok(());
+  }
+
+  writeln!(formatter, "@@ {} @@", title)?;
+  for change in changes {
+    match change {
+      Change::Added(item) => writeln!(formatter, "+{}", line(item))?,
+      Change::Removed(item) => writeln!(formatter, "-{}", line(item))?,
+      Change::Changed(old, new) => {
+        writeln!(formatter, "-{}", line(old))?;
+        writeln!(formatter, "+{}", line(new))?;
+      }
+    }
+  }
+
+  Ok(())
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::elf::abi::sh_type::{SHT_NOBITS, SHT_PROGBITS, SHT_STRTAB};
+  use crate::elf::builder::{BuilderSection, ElfBuilder};
+  use crate::endian::LittleEndian;
+
+  type Builder = ElfBuilder<ElfType64<LittleEndian>>;
+
+  /// Appends `.strtab` then `.symtab` with global functions (name, value, size).
+  fn symbols(builder: &mut Builder, strtab: u32, symbols: &[(&str, u64, u64)]) {
+    let mut names = vec![0u8];
+    let mut table = vec![0u8; 24];
+    for &(name, value, size) in symbols {
+      table.extend((names.len() as u32).to_le_bytes());
+      table.extend([0x12, 0]); // STB_GLOBAL, STT_FUNC
+      table.extend(1u16.to_le_bytes());
+      table.extend(value.to_le_bytes());
+      table.extend(size.to_le_bytes());
+      names.extend(name.bytes().chain([0]));
+    }
+
+    builder.section(".strtab", SHT_STRTAB, &names);
+    builder.add_section(BuilderSection {
+      name: String::from(".symtab"),
+      sh_type: SHT_SYMTAB,
+      sh_link: strtab,
+      sh_entsize: 24,
+      data: table,
+      ..Default::default()
+    });
+  }
+
+  #[test]
+  fn diff() {
+    let mut old = Builder::new();
+    old.entry(0x1000);
+    old.section(".text", SHT_PROGBITS, &[0xC3]);
+    old.section(".data", SHT_PROGBITS, &[1, 2, 3, 4]);
+    old.segment(1, 5, &[1]);
+    symbols(&mut old, 3, &[("main", 0x10, 1), ("gone", 0x20, 4)]);
+    let old = old.build();
+
+    let mut new = Builder::new();
+    new.entry(0x2000);
+    new.section(".text", SHT_PROGBITS, &[0x90, 0xC3]);
+    new.add_section(BuilderSection {
+      name: String::from(".bss"),
+      sh_type: SHT_NOBITS,
+      size: Some(0x100),
+      ..Default::default()
+    });
+    new.segment(1, 5, &[1]);
+    symbols(&mut new, 3, &[("fresh", 0x30, 8), ("main", 0x10, 2)]);
+    let new = new.build();
+
+    let (old, new) = (
+      parse_elf_view(old.as_slice()).unwrap(),
+      parse_elf_view(new.as_slice()).unwrap(),
+    );
+    let diff = ElfDiff::new(old.as_ref(), new.as_ref());
+    assert_eq!(
+      diff.header,
+      [(
+        String::from("Entry point address"),
+        String::from("0x1000"),
+        String::from("0x2000"),
+      )]
+    );
+
+    let text = diff.to_string();
+    let lines = |prefix: &str| {
+      text
+        .lines()
+        .filter(|line| line.starts_with(prefix))
+        .map(|line| line.split("  ").next().unwrap_or_default())
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+      lines("-"),
+      [
+        "-Entry point address: 0x1000",
+        "-.text",
+        "-.data",
+        "-.strtab",
+        "-.shstrtab",
+        "-LOAD",
+        "-main",
+        "-gone"
+      ]
+    );
+    assert_eq!(
+      lines("+"),
+      [
+        "+Entry point address: 0x2000",
+        "+.text",
+        "+.strtab",
+        "+.shstrtab",
+        "+.bss",
+        "+LOAD",
+        "+main",
+        "+fresh"
+      ]
+    );
+    assert!(text.contains("@@ Sections @@\n-.text  PROGBITS  addr 0x0  size 0x1\n"));
+    assert!(text.contains("+main  value 0x10  size 0x2  GLOBAL\n"));
+
+    let same = ElfDiff::new(old.as_ref(), old.as_ref());
+    assert!(same.is_empty());
+    assert_eq!(same.to_string(), "");
+  }
+
+  #[test]
+  fn header() {
+    let old = Builder::new().build();
+    let mut new = old.clone();
+    new[EI_PAD] = 1;
+    new[0x10] = 3; // ET_DYN
+
+    let (old, new) = (
+      parse_elf_view(old.as_slice()).unwrap(),
+      parse_elf_view(new.as_slice()).unwrap(),
+    );
+    let diff = ElfDiff::new(old.as_ref(), new.as_ref());
+    assert_eq!(
+      diff.to_string(),
+      "@@ Header @@\n-Padding: 00 00 00 00 00 00 00\n+Padding: 01 00 00 00 00 00 00\n\
+        -Type: ET_REL(0x1)\n+Type: ET_DYN(0x3)\n",
+      "The raw fields, padding included"
+    );
+  }
+
+  #[test]
+  fn ignored() {
+    let build = |text: &[u8], comment: &[u8], debug: &[u8]| {
+      let mut builder = Builder::new();
+      builder.section(".text", SHT_PROGBITS, text);
+      builder.section(".comment", SHT_PROGBITS, comment);
+      builder.section(".debug_info", SHT_PROGBITS, debug);
+      builder.build()
+    };
+
+    let old = build(&[0x90, 0xC3], b"GCC: 13.2", &[1; 8]);
+    let new = build(&[0x90, 0xC3], b"GCC: 14.1", &[2; 0x10]);
+    let changed = build(&[0xCC, 0xC3], b"GCC: 13.2", &[3; 8]);
+    let (old, new, changed) = (
+      parse_elf_view(old.as_slice()).unwrap(),
+      parse_elf_view(new.as_slice()).unwrap(),
+      parse_elf_view(changed.as_slice()).unwrap(),
+    );
+
+    let ignored = Ignored {
+      comment: true,
+      debug: true,
+      ..Default::default()
+    };
+    assert!(ElfDiff::with_ignored(old.as_ref(), new.as_ref(), ignored).is_empty());
+    assert!(
+      !ElfDiff::new(old.as_ref(), new.as_ref()).is_empty(),
+      "Sizes of .debug_info"
+    );
+
+    let diff = ElfDiff::with_ignored(
+      old.as_ref(),
+      new.as_ref(),
+      Ignored {
+        comment: false,
+        ..ignored
+      },
+    );
+    assert_eq!(
+      diff.to_string(),
+      "@@ Contents @@\n!.comment  differs at +0x6  offset 0x48  0x48\n"
+    );
+
+    let diff = ElfDiff::with_ignored(old.as_ref(), changed.as_ref(), ignored);
+    let names = diff
+      .contents
+      .iter()
+      .map(|change| change.name.as_ref())
+      .collect::<Vec<_>>();
+    assert_eq!(names, [".text"]);
+  }
+
+  #[test]
+  fn duplicates() {
+    let key = |item: &(&'static str, u32)| item.0;
+    let same = |old: &(&str, u32), new: &(&str, u32)| old == new;
+    let old = vec![("a", 1), ("b", 1), ("a", 2)];
+    let new = vec![("a", 1), ("a", 3), ("a", 4), ("c", 1)];
+
+    assert_eq!(
+      matching(old, new, key, same),
+      [
+        Change::Removed(("b", 1)),
+        Change::Changed(("a", 2), ("a", 3)),
+        Change::Added(("a", 4)),
+        Change::Added(("c", 1)),
+      ],
+      "The n-th occurrence is matched with the n-th one"
+    );
+  }
+}
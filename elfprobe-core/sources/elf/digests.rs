@@ -0,0 +1,168 @@
+use ::core::fmt;
+use alloc::borrow::Cow;
+
+use super::sections::{SHF_COMPRESSED, SHT_NOBITS};
+use super::*;
+use crate::reader::Reader;
+use crate::utils::DigestAlgorithm;
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+/// Digest of the bytes of a section, see [`ElfObject::digests()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionDigest<'data> {
+  pub index: usize,
+  pub name: Cow<'data, str>,
+  /// Number of bytes hashed.
+  pub size: u64,
+  pub digest: String,
+  /// Why the bytes hashed are not the ones in the file, or are empty.
+  pub note: Option<&'static str>,
+}
+
+///
+/// Digests of the sections and of the whole file, to tell whether two builds
+/// differ only in a few sections (e.g. `.note.gnu.build-id`).
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Digests<'data> {
+  pub algorithm: DigestAlgorithm,
+  pub file_size: u64,
+  pub file: String,
+  pub sections: Vec<SectionDigest<'data>>,
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the digests of the bytes of every section (the null one
+  /// excepted) and of the whole file. `SHT_NOBITS` sections hash as empty.
+  /// Compressed sections (`SHF_COMPRESSED`) hash their bytes in the file,
+  /// or their decompressed contents with `decompressed` (see
+  /// [`Self::section_data()`], the bytes in the file when it fails).
+  ///
+  pub fn digests(&self, algorithm: DigestAlgorithm, decompressed: bool) -> Digests<'data> {
+    // The whole file is read, the sections roughly in order.
+    self.data.advise_sequential(0, self.data.length());
+
+    let mut sections = Vec::new();
+    for section in self.sections().skip(1) {
+      let header = section.header;
+      let bytes = self.section_bytes(header).unwrap_or_default();
+      let compressed = header.sh_flags() & SHF_COMPRESSED != 0;
+
+      let (data, note) = match () {
+        _ if header.sh_type() == SHT_NOBITS as u64 => (Cow::Borrowed(bytes), Some("NOBITS, empty")),
+        _ if compressed && decompressed => match self.section_data(header) {
+          Ok(data) => (data, Some("decompressed")),
+          Err(_) => (Cow::Borrowed(bytes), Some("compressed, decompression failed")),
+        },
+        _ if compressed => (Cow::Borrowed(bytes), Some("compressed")),
+        _ => (Cow::Borrowed(bytes), None),
+      };
+
+      sections.push(SectionDigest {
+        index: section.index,
+        name: section.display_name().unwrap_or(Cow::Borrowed("<invalid>")),
+        size: data.len() as u64,
+        digest: algorithm.hex(&data),
+        note,
+      });
+    }
+
+    let file = self.data.read_bytes(self.data.length(), 0).unwrap_or_default();
+    Digests {
+      algorithm,
+      file_size: file.len() as u64,
+      file: algorithm.hex(file),
+      sections,
+    }
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+/// Table of the section digests followed by the file digest.
+impl fmt::Display for Digests<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use crate::utils::display_table;
+
+    writeln!(formatter, "Section digests ({}):", self.algorithm)?;
+    display_table!(
+      formatter,
+      align [Left, Left, Right, Left, Left],
+      ["[Nr]", "Name", "Size", "Digest", "Note"],
+      section in &self.sections => [
+        format!("[{:2}]", section.index),
+        section.name,
+        format!("{:#x}", section.size),
+        section.digest,
+        section.note.unwrap_or_default(),
+      ]
+    )?;
+
+    writeln!(formatter)?;
+    writeln!(formatter, "File: {} ({} bytes)", self.file, self.file_size)
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::elf::abi::sh_type::SHT_PROGBITS;
+  use crate::elf::builder::{BuilderSection, ElfBuilder};
+  use crate::utils::crc32;
+
+  #[test]
+  fn section_digests() {
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".text", SHT_PROGBITS, b"123456789")
+      .add_section(BuilderSection {
+        name: String::from(".bss"),
+        sh_type: SHT_NOBITS,
+        size: Some(0x100),
+        ..Default::default()
+      })
+      .build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let digests = object.digests(DigestAlgorithm::Crc32, false);
+    let sections = digests
+      .sections
+      .iter()
+      .map(|section| {
+        (
+          section.name.as_ref(),
+          section.size,
+          section.digest.as_str(),
+          section.note,
+        )
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      sections[..2],
+      [
+        (".text", 9, "cbf43926", None),
+        (".bss", 0, "00000000", Some("NOBITS, empty"))
+      ]
+    );
+    assert_eq!(digests.file, format!("{:08x}", crc32(&bytes)));
+  }
+}
@@ -0,0 +1,565 @@
+use ::core::mem::size_of;
+
+use super::abi::e_machine::*;
+use super::abi::ei_osabi::{ELFOSABI_SOLARIS, ELFOSABI_SYSV};
+use super::segments::PT_DYNAMIC;
+use super::*;
+use crate::error::{BytesError, ResultExt};
+use crate::reader::Reader;
+use crate::utils::{define_constants, define_flags, Constant};
+use elfprobe_macro::Pod;
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+define_constants! {
+  /// Dynamic entry type (`d_tag`), see [`dynamic_tag()`] for the
+  /// processor-specific ones.
+  pub d_tag: i64 {
+    DT_NULL = 0 => "Marks end of dynamic section",
+    DT_NEEDED = 1 => "Name of needed library",
+    DT_PLTRELSZ = 2 => "Size in bytes of PLT relocs",
+    DT_PLTGOT = 3 => "Processor defined value",
+    DT_HASH = 4 => "Address of symbol hash table",
+    DT_STRTAB = 5 => "Address of string table",
+    DT_SYMTAB = 6 => "Address of symbol table",
+    DT_RELA = 7 => "Address of Rela relocs",
+    DT_RELASZ = 8 => "Total size of Rela relocs",
+    DT_RELAENT = 9 => "Size of one Rela reloc",
+    DT_STRSZ = 10 => "Size of string table",
+    DT_SYMENT = 11 => "Size of one symbol table entry",
+    DT_INIT = 12 => "Address of init function",
+    DT_FINI = 13 => "Address of termination function",
+    DT_SONAME = 14 => "Name of shared object",
+    DT_RPATH = 15 => "Library search path (deprecated)",
+    DT_SYMBOLIC = 16 => "Start symbol search here",
+    DT_REL = 17 => "Address of Rel relocs",
+    DT_RELSZ = 18 => "Total size of Rel relocs",
+    DT_RELENT = 19 => "Size of one Rel reloc",
+    DT_PLTREL = 20 => "Type of reloc in PLT",
+    DT_DEBUG = 21 => "For debugging; unspecified",
+    DT_TEXTREL = 22 => "Reloc might modify .text",
+    DT_JMPREL = 23 => "Address of PLT relocs",
+    DT_BIND_NOW = 24 => "Process relocations of object before execution",
+    DT_INIT_ARRAY = 25 => "Array with addresses of init functions",
+    DT_FINI_ARRAY = 26 => "Array with addresses of fini functions",
+    DT_INIT_ARRAYSZ = 27 => "Size in bytes of DT_INIT_ARRAY",
+    DT_FINI_ARRAYSZ = 28 => "Size in bytes of DT_FINI_ARRAY",
+    DT_RUNPATH = 29 => "Library search path",
+    DT_FLAGS = 30 => "Flags for the object being loaded",
+    DT_PREINIT_ARRAY = 32 => "Array with addresses of preinit functions",
+    DT_PREINIT_ARRAYSZ = 33 => "Size in bytes of DT_PREINIT_ARRAY",
+    DT_SYMTAB_SHNDX = 34 => "Address of SYMTAB_SHNDX section",
+    DT_RELRSZ = 35 => "Total size of RELR relative relocations",
+    DT_RELR = 36 => "Address of RELR relative relocations",
+    DT_RELRENT = 37 => "Size of one RELR relative relocation",
+    DT_GNU_PRELINKED = 0x6ffffdf5 => "Prelinking timestamp",
+    DT_GNU_CONFLICTSZ = 0x6ffffdf6 => "Size of conflict section",
+    DT_GNU_LIBLISTSZ = 0x6ffffdf7 => "Size of library list",
+    DT_CHECKSUM = 0x6ffffdf8 => "Checksum",
+    DT_PLTPADSZ = 0x6ffffdf9 => "Size of PLT padding",
+    DT_MOVEENT = 0x6ffffdfa => "Size of one move table entry",
+    DT_MOVESZ = 0x6ffffdfb => "Size of move table",
+    DT_FEATURE_1 = 0x6ffffdfc => "Feature selection (DTF_*)",
+    DT_POSFLAG_1 = 0x6ffffdfd => "Flags for the following DT_* entry",
+    DT_SYMINSZ = 0x6ffffdfe => "Size of syminfo table",
+    DT_SYMINENT = 0x6ffffdff => "Size of one syminfo entry",
+    DT_GNU_HASH = 0x6ffffef5 => "GNU-style hash table",
+    DT_TLSDESC_PLT = 0x6ffffef6 => "TLS descriptor PLT entry",
+    DT_TLSDESC_GOT = 0x6ffffef7 => "TLS descriptor GOT entry",
+    DT_GNU_CONFLICT = 0x6ffffef8 => "Start of conflict section",
+    DT_GNU_LIBLIST = 0x6ffffef9 => "Library list",
+    DT_CONFIG = 0x6ffffefa => "Configuration information",
+    DT_DEPAUDIT = 0x6ffffefb => "Dependency auditing",
+    DT_AUDIT = 0x6ffffefc => "Object auditing",
+    DT_PLTPAD = 0x6ffffefd => "PLT padding",
+    DT_MOVETAB = 0x6ffffefe => "Move table",
+    DT_SYMINFO = 0x6ffffeff => "Syminfo table",
+    DT_VERSYM = 0x6ffffff0 => "Address of the version symbol table",
+    DT_RELACOUNT = 0x6ffffff9 => "Number of relative Rela relocs",
+    DT_RELCOUNT = 0x6ffffffa => "Number of relative Rel relocs",
+    DT_FLAGS_1 = 0x6ffffffb => "State flags",
+    DT_VERDEF = 0x6ffffffc => "Address of version definition table",
+    DT_VERDEFNUM = 0x6ffffffd => "Number of version definitions",
+    DT_VERNEED = 0x6ffffffe => "Address of table with needed versions",
+    DT_VERNEEDNUM = 0x6fffffff => "Number of needed versions",
+    DT_AUXILIARY = 0x7ffffffd => "Shared object to load before self",
+    DT_FILTER = 0x7fffffff => "Shared object to get values from",
+    [DT_LOOS .. DT_HIOS] = 0x6000000d .. 0x6ffff000 => "OS-specific",
+    [DT_LOPROC .. DT_HIPROC] = 0x70000000 .. 0x7fffffff => "Processor-specific",
+  }
+}
+
+define_constants! {
+  /// MIPS dynamic entry types.
+  pub d_tag_mips: i64 {
+    DT_MIPS_RLD_VERSION = 0x70000001 => "Runtime linker interface version",
+    DT_MIPS_TIME_STAMP = 0x70000002 => "Timestamp",
+    DT_MIPS_ICHECKSUM = 0x70000003 => "Checksum",
+    DT_MIPS_IVERSION = 0x70000004 => "Version string (string tbl index)",
+    DT_MIPS_FLAGS = 0x70000005 => "Flags",
+    DT_MIPS_BASE_ADDRESS = 0x70000006 => "Base address",
+    DT_MIPS_MSYM = 0x70000007 => "Msym table",
+    DT_MIPS_CONFLICT = 0x70000008 => "Address of CONFLICT section",
+    DT_MIPS_LIBLIST = 0x70000009 => "Address of LIBLIST section",
+    DT_MIPS_LOCAL_GOTNO = 0x7000000a => "Number of local GOT entries",
+    DT_MIPS_CONFLICTNO = 0x7000000b => "Number of CONFLICT entries",
+    DT_MIPS_LIBLISTNO = 0x70000010 => "Number of LIBLIST entries",
+    DT_MIPS_SYMTABNO = 0x70000011 => "Number of DYNSYM entries",
+    DT_MIPS_UNREFEXTNO = 0x70000012 => "First external DYNSYM",
+    DT_MIPS_GOTSYM = 0x70000013 => "First GOT entry in DYNSYM",
+    DT_MIPS_HIPAGENO = 0x70000014 => "Number of GOT page table entries",
+    DT_MIPS_RLD_MAP = 0x70000016 => "Address of run time loader map",
+    DT_MIPS_OPTIONS = 0x70000029 => "Address of .options",
+    DT_MIPS_PLTGOT = 0x70000032 => "Address of the PLT GOT",
+    DT_MIPS_RWPLT = 0x70000034 => "Address of a writable PLT",
+    DT_MIPS_RLD_MAP_REL = 0x70000035 => "Relative address of run time loader map",
+  }
+}
+
+define_constants! {
+  /// PowerPC dynamic entry types.
+  pub d_tag_ppc: i64 {
+    DT_PPC_GOT = 0x70000000 => "Address of the GOT",
+    DT_PPC_OPT = 0x70000001 => "Options",
+  }
+}
+
+define_constants! {
+  /// PowerPC 64-bit dynamic entry types.
+  pub d_tag_ppc64: i64 {
+    DT_PPC64_GLINK = 0x70000000 => "Address of the glink stubs",
+    DT_PPC64_OPD = 0x70000001 => "Address of .opd",
+    DT_PPC64_OPDSZ = 0x70000002 => "Size of .opd",
+    DT_PPC64_OPT = 0x70000003 => "Options",
+  }
+}
+
+define_constants! {
+  /// AArch64 dynamic entry types.
+  pub d_tag_aarch64: i64 {
+    DT_AARCH64_BTI_PLT = 0x70000001 => "PLT entries are BTI compatible",
+    DT_AARCH64_PAC_PLT = 0x70000003 => "PLT entries are PAC compatible",
+    DT_AARCH64_VARIANT_PCS = 0x70000005 => "Symbols with a variant PCS",
+  }
+}
+
+define_constants! {
+  /// Solaris dynamic entry types, in the OS-specific range.
+  pub d_tag_solaris: i64 {
+    DT_SUNW_AUXILIARY = 0x6000000d => "Symbol auxiliary name",
+    DT_SUNW_RTLDINF = 0x6000000e => "ld.so.1 information (private)",
+    DT_SUNW_FILTER = 0x6000000f => "Symbol filter name",
+    DT_SUNW_CAP = 0x60000010 => "Hardware and software capabilities",
+    DT_SUNW_SYMTAB = 0x60000011 => "Symbol table including the local symbols",
+    DT_SUNW_SYMSZ = 0x60000012 => "Size of DT_SUNW_SYMTAB",
+    DT_SUNW_SORTENT = 0x60000013 => "Size of one sort entry",
+    DT_SUNW_SYMSORT = 0x60000014 => "Address of the symbol sort section",
+    DT_SUNW_SYMSORTSZ = 0x60000015 => "Size of the symbol sort section",
+    DT_SUNW_TLSSORT = 0x60000016 => "Address of the TLS sort section",
+    DT_SUNW_TLSSORTSZ = 0x60000017 => "Size of the TLS sort section",
+    DT_SUNW_CAPINFO = 0x60000018 => "Address of the capabilities information",
+    DT_SUNW_STRPAD = 0x60000019 => "Dynamic string table padding",
+    DT_SUNW_CAPCHAIN = 0x6000001a => "Address of the capabilities chain",
+    DT_SUNW_LDMACH = 0x6000001b => "Machine of the link-editor",
+    DT_SUNW_CAPCHAINENT = 0x6000001d => "Size of one capabilities chain entry",
+    DT_SUNW_CAPCHAINSZ = 0x6000001f => "Size of the capabilities chain",
+  }
+}
+
+pub use d_tag::{
+  DT_BIND_NOW, DT_FINI, DT_FINI_ARRAY, DT_FINI_ARRAYSZ, DT_FLAGS, DT_FLAGS_1, DT_INIT, DT_INIT_ARRAY,
+  DT_INIT_ARRAYSZ, DT_NEEDED, DT_NULL, DT_PREINIT_ARRAY, DT_PREINIT_ARRAYSZ, DT_RPATH, DT_RUNPATH, DT_SONAME,
+  DT_STRSZ, DT_STRTAB, DT_TEXTREL,
+};
+
+define_flags! {
+  /// Flags of the object (`DT_FLAGS`).
+  pub df: u64 {
+    DF_ORIGIN = 0x1 => "Object may use DF_ORIGIN",
+    DF_SYMBOLIC = 0x2 => "Symbol resolutions starts here",
+    DF_TEXTREL = 0x4 => "Object contains text relocations",
+    DF_BIND_NOW = 0x8 => "No lazy binding for this object",
+    DF_STATIC_TLS = 0x10 => "Module uses the static TLS model",
+  }
+}
+
+define_flags! {
+  /// State flags of the object (`DT_FLAGS_1`), read by the dynamic loader.
+  pub df_1: u64 {
+    DF_1_NOW = 0x1 => "Set RTLD_NOW for this object",
+    DF_1_GLOBAL = 0x2 => "Set RTLD_GLOBAL for this object",
+    DF_1_GROUP = 0x4 => "Set RTLD_GROUP for this object",
+    DF_1_NODELETE = 0x8 => "Set RTLD_NODELETE for this object",
+    DF_1_LOADFLTR = 0x10 => "Trigger filtee loading at runtime",
+    DF_1_INITFIRST = 0x20 => "Set RTLD_INITFIRST for this object",
+    DF_1_NOOPEN = 0x40 => "Set RTLD_NOOPEN for this object",
+    DF_1_ORIGIN = 0x80 => "$ORIGIN must be handled",
+    DF_1_DIRECT = 0x100 => "Direct binding enabled",
+    DF_1_TRANS = 0x200 => "Transparent",
+    DF_1_INTERPOSE = 0x400 => "Object is used to interpose",
+    DF_1_NODEFLIB = 0x800 => "Ignore default lib search path",
+    DF_1_NODUMP = 0x1000 => "Object can't be dldump'ed",
+    DF_1_CONFALT = 0x2000 => "Configuration alternative created",
+    DF_1_ENDFILTEE = 0x4000 => "Filtee terminates filters search",
+    DF_1_DISPRELDNE = 0x8000 => "Disp reloc applied at build time",
+    DF_1_DISPRELPND = 0x10000 => "Disp reloc applied at run-time",
+    DF_1_NODIRECT = 0x20000 => "Object has no-direct binding",
+    DF_1_IGNMULDEF = 0x40000 => "Ignore multiple definitions",
+    DF_1_NOKSYMS = 0x80000 => "No symbols in the kernel symbol table",
+    DF_1_NOHDR = 0x100000 => "Headers not in a loadable segment",
+    DF_1_EDITED = 0x200000 => "Object is modified after built",
+    DF_1_NORELOC = 0x400000 => "No relocations",
+    DF_1_SYMINTPOSE = 0x800000 => "Object has individual interposers",
+    DF_1_GLOBAUDIT = 0x1000000 => "Global auditing required",
+    DF_1_SINGLETON = 0x2000000 => "Singleton symbols are used",
+    DF_1_STUB = 0x4000000 => "Stub object",
+    DF_1_PIE = 0x8000000 => "Position-independent executable",
+    DF_1_KMOD = 0x10000000 => "Kernel module",
+    DF_1_WEAKFILTER = 0x20000000 => "Weak filter",
+    DF_1_NOCOMMON = 0x40000000 => "No common symbols",
+  }
+}
+
+pub use df::{DF_BIND_NOW, DF_TEXTREL};
+pub use df_1::{DF_1_NOW, DF_1_PIE};
+
+///
+/// Returns the names of the set flags without their prefix, separated by
+/// spaces as readelf (`NOW PIE`), unknown bits in hexadecimal.
+///
+pub fn flag_names(flags: impl Iterator<Item = Constant<u64>>, prefix: &str) -> String {
+  let names: Vec<_> = flags
+    .map(|flag| match flag.name() {
+      Some(name) => String::from(name.strip_prefix(prefix).unwrap_or(name)),
+      None => format!("{:#x}", flag.value()),
+    })
+    .collect();
+  names.join(" ")
+}
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+///
+/// Dynamic section entry, `d_un` is either a value (`d_val`) or an address
+/// (`d_ptr`) depending on `d_tag` but both have the same representation.
+///
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Pod)]
+pub struct ElfDynamic<ElfType: self::ElfType> {
+  pub d_tag: ElfType::Sxword,
+  pub d_val: ElfType::Xword,
+}
+
+impl<ElfType: self::ElfType> ElfDynamic<ElfType> {
+  /// Returns the dynamic entry type widened to 64 bits.
+  #[inline]
+  pub fn d_tag(&self) -> i64 {
+    self.d_tag.into()
+  }
+
+  /// Returns the dynamic entry value (or address) widened to 64 bits.
+  #[inline]
+  pub fn d_val(&self) -> u64 {
+    self.d_val.into()
+  }
+}
+
+///
+/// Returns the dynamic entry type of the given machine and OS ABI:
+/// processor-specific values are looked up in the machine table first (the
+/// same value has different meanings on MIPS, PowerPC and AArch64), and
+/// OS-specific ones in the Solaris table for Solaris objects.
+///
+pub fn dynamic_tag(machine: u16, osabi: u8, tag: i64) -> Constant<i64> {
+  let constant = match machine {
+    EM_MIPS | EM_MIPS_RS3_LE => d_tag_mips::from(tag),
+    EM_PPC => d_tag_ppc::from(tag),
+    EM_PPC64 => d_tag_ppc64::from(tag),
+    EM_AARCH64 => d_tag_aarch64::from(tag),
+    _ => Constant::unknown(tag),
+  };
+  let constant = match (constant.name(), osabi) {
+    (None, ELFOSABI_SOLARIS) => d_tag_solaris::from(tag),
+    _ => constant,
+  };
+
+  match constant.name() {
+    Some(_) => constant,
+    None => d_tag::from(tag),
+  }
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the dynamic table pointed to by the `PT_DYNAMIC` segment (hence
+  /// no need for section headers) up to the first `DT_NULL` entry. The table
+  /// is empty when there is no such segment (static executables, relocatable
+  /// objects...).
+  ///
+  pub fn dynamic(&self) -> Result<Vec<&'data ElfDynamic<ElfType>>, ElfError> {
+    let headers = self.program_headers()?;
+    let Some(header) = headers.into_iter().find(|header| header.p_type() == PT_DYNAMIC) else {
+      return Ok(Vec::new());
+    };
+
+    let size = size_of::<ElfDynamic<ElfType>>() as u64;
+    let mut entries = Vec::new();
+
+    let read = |index: u64| {
+      let offset = (header.p_offset().checked_add(index * size))
+        .and_then(|offset| usize::try_from(offset).ok())
+        .ok_or(BytesError::Empty)?;
+      self.data.read_pod::<ElfDynamic<ElfType>>(offset)
+    };
+
+    // The entries read so far are kept in lenient mode.
+    let count = self.bounded_count("dynamic table", header.p_filesz() / size, size as usize)?;
+    let result = (0..count)
+      .map(read)
+      .take_while(|entry| !entry.as_ref().is_ok_and(|entry| entry.d_tag() == DT_NULL))
+      .try_for_each(|entry| entry.map(|entry| entries.push(entry)));
+
+    let context = || format!("reading the dynamic table at offset {:#x}", header.p_offset());
+    self.recover(result.with_context(context), entries)
+  }
+
+  ///
+  /// Returns the string table referenced by `DT_STRTAB` (a virtual address)
+  /// and bounded by `DT_STRSZ`.
+  ///
+  pub(crate) fn dynamic_strings(&self, entries: &[&'data ElfDynamic<ElfType>]) -> Option<StringTable<'data>> {
+    let find = |tag| {
+      entries
+        .iter()
+        .find(|entry| entry.d_tag() == tag)
+        .map(|entry| entry.d_val())
+    };
+
+    let offset = self.vaddr_to_offset(find(DT_STRTAB)?)?;
+    let size = find(DT_STRSZ)?;
+
+    let bytes = self
+      .data
+      .read_bytes(usize::try_from(size).ok()?, usize::try_from(offset).ok()?)?;
+
+    Some(StringTable::from(bytes))
+  }
+
+  ///
+  /// Returns all strings referenced by the given dynamic tag. Strings out of
+  /// the string table bounds or not valid UTF-8 are skipped.
+  ///
+  fn dynamic_values(&self, tag: i64) -> Vec<&'data str> {
+    let Ok(entries) = self.dynamic() else {
+      return Vec::new();
+    };
+
+    let Some(strings) = self.dynamic_strings(&entries) else {
+      return Vec::new();
+    };
+
+    entries
+      .iter()
+      .filter(|entry| entry.d_tag() == tag)
+      .filter_map(|entry| strings.get_str(usize::try_from(entry.d_val()).ok()?))
+      .collect()
+  }
+
+  /// Returns the names of the needed libraries (`DT_NEEDED`).
+  pub fn needed_libraries(&self) -> Vec<&'data str> {
+    self.dynamic_values(DT_NEEDED)
+  }
+
+  /// Returns the shared object name (`DT_SONAME`).
+  pub fn soname(&self) -> Option<&'data str> {
+    self.dynamic_values(DT_SONAME).first().copied()
+  }
+
+  ///
+  /// Returns the flags of the object (`DT_FLAGS`), all of them when the tag
+  /// is repeated, none without dynamic table.
+  ///
+  pub fn dynamic_flags(&self) -> df::Flags {
+    df::from(self.dynamic_bits(DT_FLAGS))
+  }
+
+  /// Returns the state flags of the object (`DT_FLAGS_1`), see [`Self::dynamic_flags()`].
+  pub fn dynamic_flags_1(&self) -> df_1::Flags {
+    df_1::from(self.dynamic_bits(DT_FLAGS_1))
+  }
+
+  /// Returns the union of the values of the given tag.
+  fn dynamic_bits(&self, tag: i64) -> u64 {
+    let entries = self.dynamic().unwrap_or_default();
+    entries
+      .iter()
+      .filter(|entry| entry.d_tag() == tag)
+      .fold(0, |bits, entry| bits | entry.d_val())
+  }
+
+  ///
+  /// Returns the deprecated library search path (`DT_RPATH`), searched before
+  /// `LD_LIBRARY_PATH`. Tokens such as `$ORIGIN` are not expanded.
+  ///
+  pub fn rpath(&self) -> Option<&'data str> {
+    self.dynamic_values(DT_RPATH).first().copied()
+  }
+
+  ///
+  /// Returns the library search path (`DT_RUNPATH`), searched after
+  /// `LD_LIBRARY_PATH`. Tokens such as `$ORIGIN` are not expanded.
+  ///
+  pub fn runpath(&self) -> Option<&'data str> {
+    self.dynamic_values(DT_RUNPATH).first().copied()
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+
+  #[test]
+  fn dynamic_memory_size() {
+    assert_eq!(size_of::<ElfDynamic<ElfType32<BigEndian>>>(), 8);
+    assert_eq!(size_of::<ElfDynamic<ElfType64<BigEndian>>>(), 16);
+    assert_eq!(size_of::<ElfDynamic<ElfType32<LittleEndian>>>(), 8);
+    assert_eq!(size_of::<ElfDynamic<ElfType64<LittleEndian>>>(), 16);
+  }
+
+  #[test]
+  fn dynamic_tags() {
+    let name = |machine, tag| dynamic_tag(machine, ELFOSABI_SYSV, tag).to_string();
+
+    assert_eq!(name(EM_X86_64, DT_NEEDED), "DT_NEEDED");
+    assert_eq!(name(EM_X86_64, 0x6ffffef5), "DT_GNU_HASH");
+    assert_eq!(name(EM_X86_64, 0x6ffffff9), "DT_RELACOUNT");
+    assert_eq!(name(EM_X86_64, 0x6000000f), "DT_LOOS+0x2");
+    assert_eq!(name(EM_MIPS, 0x70000016), "DT_MIPS_RLD_MAP");
+    assert_eq!(name(EM_PPC64, 0x70000000), "DT_PPC64_GLINK");
+    assert_eq!(name(EM_AARCH64, 0x70000001), "DT_AARCH64_BTI_PLT");
+    assert_eq!(name(EM_X86_64, 0x70000001), "DT_LOPROC+0x1");
+    assert_eq!(name(EM_MIPS, 0x7fffffff), "DT_FILTER");
+    assert_eq!(name(EM_MIPS, 31), "0x1f");
+
+    // OS-specific values depend on the OS ABI.
+    let name = |osabi, tag| dynamic_tag(EM_SPARCV9, osabi, tag).to_string();
+    assert_eq!(name(ELFOSABI_SOLARIS, 0x6000000f), "DT_SUNW_FILTER");
+    assert_eq!(name(ELFOSABI_SYSV, 0x6000000f), "DT_LOOS+0x2");
+    assert_eq!(name(ELFOSABI_SOLARIS, 0x6ffffef5), "DT_GNU_HASH");
+    assert_eq!(name(ELFOSABI_SOLARIS, DT_NEEDED), "DT_NEEDED");
+  }
+
+  /// ELF64 little-endian with a `PT_LOAD` and a `PT_DYNAMIC` segments.
+  fn fixture() -> Vec<u8> {
+    hex(
+      r"
+      7F 'ELF 02 01 01 00 00 00000000 000000 ; e_ident
+      0300 3E00 01000000 ; e_type, e_machine, e_version
+      0000000000000000 ; e_entry
+      4000000000000000 ; e_phoff
+      0000000000000000 ; e_shoff
+      00000000 4000 ; e_flags, e_ehsize
+      3800 0200 ; e_phentsize, e_phnum
+      4000 0000 0000 ; e_shentsize, e_shnum, e_shstrndx
+
+      01000000 04000000 ; PT_LOAD, PF_R
+      0000000000000000 ; p_offset
+      0000400000000000 ; p_vaddr
+      0000400000000000 ; p_paddr
+      7701000000000000 ; p_filesz
+      7701000000000000 ; p_memsz
+      0010000000000000 ; p_align
+
+      02000000 06000000 ; PT_DYNAMIC, PF_R | PF_W
+      B000000000000000 ; p_offset
+      B000400000000000 ; p_vaddr
+      B000400000000000 ; p_paddr
+      9000000000000000 ; p_filesz
+      9000000000000000 ; p_memsz
+      0800000000000000 ; p_align
+
+      0100000000000000 0100000000000000 ; DT_NEEDED
+      0100000000000000 0B00000000000000 ; DT_NEEDED
+      0100000000000000 3C00000000000000 ; DT_NEEDED (out of bounds)
+      0E00000000000000 1500000000000000 ; DT_SONAME
+      0F00000000000000 1F00000000000000 ; DT_RPATH
+      1D00000000000000 2800000000000000 ; DT_RUNPATH
+      0500000000000000 4001400000000000 ; DT_STRTAB
+      0A00000000000000 3700000000000000 ; DT_STRSZ
+      0000000000000000 0000000000000000 ; DT_NULL
+
+      00 'libc.so.6 00 'libm.so.6 00 'libfoo.so 00 '/opt/lib 00 '$ORIGIN/../lib 00
+    ",
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn dynamic_dependencies() {
+    let bytes = fixture();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    assert_eq!(object.dynamic().unwrap().len(), 8);
+    assert_eq!(object.needed_libraries(), vec!["libc.so.6", "libm.so.6"]);
+    assert_eq!(object.soname(), Some("libfoo.so"));
+    assert_eq!(object.rpath(), Some("/opt/lib"));
+    assert_eq!(object.runpath(), Some("$ORIGIN/../lib"));
+  }
+
+  #[test]
+  fn no_dynamic_segment() {
+    let mut bytes = fixture();
+    bytes[0x78] = 0x00; // PT_DYNAMIC -> PT_NULL
+
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    assert!(object.dynamic().unwrap().is_empty());
+    assert!(object.needed_libraries().is_empty());
+    assert_eq!(object.soname(), None);
+  }
+
+  #[test]
+  fn dynamic_flags() {
+    let mut bytes = fixture();
+    let mut entry = |offset: usize, tag: i64, value: u64| {
+      bytes[offset..offset + 8].copy_from_slice(&tag.to_le_bytes());
+      bytes[offset + 8..offset + 16].copy_from_slice(&value.to_le_bytes());
+    };
+    entry(0xF0, DT_FLAGS_1, DF_1_NOW | DF_1_PIE); // DT_RPATH
+    entry(0x100, DT_FLAGS_1, 0x80000000 | df_1::DF_1_NODELETE); // DT_RUNPATH
+
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let flags = object.dynamic_flags_1();
+    assert_eq!(flags.bits(), 0x88000009, "Repeated tags are merged");
+    assert_eq!(flag_names(flags.iter(), "DF_1_"), "NOW NODELETE PIE 0x80000000");
+    assert_eq!(object.dynamic_flags(), df::Flags::default());
+    assert_eq!(
+      df::from(DF_BIND_NOW | DF_TEXTREL).to_string(),
+      "DF_TEXTREL | DF_BIND_NOW"
+    );
+  }
+}
@@ -0,0 +1,296 @@
+use ::core::mem::size_of;
+
+use super::notes::read_unsigned;
+use super::segments::PT_GNU_EH_FRAME;
+use super::*;
+use crate::reader::Reader;
+use crate::utils::{read_sleb128, read_uleb128};
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+/// Value format (low nibble): pointer size.
+pub const DW_EH_PE_ABSPTR: u8 = 0x00;
+pub const DW_EH_PE_ULEB128: u8 = 0x01;
+pub const DW_EH_PE_UDATA2: u8 = 0x02;
+pub const DW_EH_PE_UDATA4: u8 = 0x03;
+pub const DW_EH_PE_UDATA8: u8 = 0x04;
+pub const DW_EH_PE_SLEB128: u8 = 0x09;
+pub const DW_EH_PE_SDATA2: u8 = 0x0a;
+pub const DW_EH_PE_SDATA4: u8 = 0x0b;
+pub const DW_EH_PE_SDATA8: u8 = 0x0c;
+
+/// Application (high nibble): relative to the address of the value.
+pub const DW_EH_PE_PCREL: u8 = 0x10;
+
+/// Application (high nibble): relative to the start of `.eh_frame_hdr`.
+pub const DW_EH_PE_DATAREL: u8 = 0x30;
+
+/// The value is the address of the actual pointer.
+pub const DW_EH_PE_INDIRECT: u8 = 0x80;
+
+/// No value.
+pub const DW_EH_PE_OMIT: u8 = 0xff;
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+/// An entry of the `.eh_frame_hdr` binary search table (absolute addresses).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EhFrameEntry {
+  /// Start address of the function covered by the FDE.
+  pub initial_location: u64,
+  /// Address of the FDE in `.eh_frame`.
+  pub fde_address: u64,
+}
+
+///
+/// The `.eh_frame_hdr` section (`PT_GNU_EH_FRAME` segment): a pointer to
+/// `.eh_frame` and an optional sorted table of the FDEs used by unwinders to
+/// find the FDE of a given address.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EhFrameHdr {
+  /// Address of the header itself.
+  pub address: u64,
+  pub version: u8,
+  pub eh_frame_ptr_enc: u8,
+  pub fde_count_enc: u8,
+  pub table_enc: u8,
+  /// Address of `.eh_frame`, `None` if not decoded.
+  pub eh_frame_ptr: Option<u64>,
+  /// Entries of the table (decoded up to the first unsupported entry).
+  pub table: Vec<EhFrameEntry>,
+  fde_count: Option<u64>,
+}
+
+impl EhFrameHdr {
+  ///
+  /// Returns the number of FDEs in the table as declared in the header,
+  /// `None` without table (`DW_EH_PE_omit`).
+  ///
+  pub fn fde_count(&self) -> Option<u64> {
+    self.fde_count
+  }
+
+  /// Returns the entries of the table, sorted by initial location.
+  pub fn entries(&self) -> impl Iterator<Item = EhFrameEntry> + '_ {
+    self.table.iter().copied()
+  }
+}
+
+///
+/// Reads a pointer encoded with the given `DW_EH_PE_*` encoding at the given
+/// offset, `address` being the address of `bytes` (for `DW_EH_PE_pcrel` and
+/// `DW_EH_PE_datarel`, the only applications supported). The offset is moved
+/// past the value.
+///
+/// `None` is returned for truncated values and unsupported encodings
+/// (including `DW_EH_PE_indirect` which would need the memory image).
+///
+pub fn read_encoded<ElfType: self::ElfType>(
+  bytes: &[u8],
+  offset: &mut usize,
+  encoding: u8,
+  address: u64,
+) -> Option<u64> {
+  if encoding == DW_EH_PE_OMIT || encoding & DW_EH_PE_INDIRECT != 0 {
+    return None;
+  }
+
+  let start = *offset;
+  let mut fixed = |size: usize| {
+    let value = read_unsigned::<ElfType::Endian>(bytes, *offset, size)?;
+    *offset += size;
+    Some(value)
+  };
+
+  // Sign-extended to 64 bits, then wrapping arithmetic.
+  let value = match encoding & 0x0f {
+    DW_EH_PE_ABSPTR => fixed(size_of::<ElfType::Addr>())?,
+    DW_EH_PE_UDATA2 => fixed(2)?,
+    DW_EH_PE_UDATA4 => fixed(4)?,
+    DW_EH_PE_UDATA8 => fixed(8)?,
+    DW_EH_PE_SDATA2 => fixed(2)? as i16 as u64,
+    DW_EH_PE_SDATA4 => fixed(4)? as i32 as u64,
+    DW_EH_PE_SDATA8 => fixed(8)?,
+    DW_EH_PE_ULEB128 => read_uleb128(bytes, offset)?,
+    DW_EH_PE_SLEB128 => read_sleb128(bytes, offset)? as u64,
+    _ => return None,
+  };
+
+  let value = match encoding & 0x70 {
+    0 => value,
+    DW_EH_PE_PCREL => value.wrapping_add(address.wrapping_add(start as u64)),
+    DW_EH_PE_DATAREL => value.wrapping_add(address),
+    _ => {
+      *offset = start;
+      return None;
+    }
+  };
+
+  // Addresses are truncated to the class size.
+  match size_of::<ElfType::Addr>() {
+    4 => Some(value & 0xffff_ffff),
+    _ => Some(value),
+  }
+}
+
+///
+/// Parses an `.eh_frame_hdr` section located at the given address. `None` is
+/// returned for an unknown version or a truncated header.
+///
+pub fn parse_eh_frame_hdr<ElfType: self::ElfType>(bytes: &[u8], address: u64) -> Option<EhFrameHdr> {
+  let [version, eh_frame_ptr_enc, fde_count_enc, table_enc, ..] = *bytes else {
+    return None;
+  };
+
+  if version != 1 {
+    return None;
+  }
+
+  let mut offset = 4;
+  let eh_frame_ptr = read_encoded::<ElfType>(bytes, &mut offset, eh_frame_ptr_enc, address);
+  if eh_frame_ptr.is_none() && eh_frame_ptr_enc != DW_EH_PE_OMIT {
+    return None; // The offset of the following fields is unknown.
+  }
+
+  let fde_count = read_encoded::<ElfType>(bytes, &mut offset, fde_count_enc, address);
+
+  let mut table = Vec::new();
+  if table_enc != DW_EH_PE_OMIT {
+    for _ in 0..fde_count.unwrap_or(0) {
+      let initial_location = read_encoded::<ElfType>(bytes, &mut offset, table_enc, address);
+      let fde_address = read_encoded::<ElfType>(bytes, &mut offset, table_enc, address);
+      let (Some(initial_location), Some(fde_address)) = (initial_location, fde_address) else {
+        break;
+      };
+
+      table.push(EhFrameEntry {
+        initial_location,
+        fde_address,
+      });
+    }
+  }
+
+  Some(EhFrameHdr {
+    address,
+    version,
+    eh_frame_ptr_enc,
+    fde_count_enc,
+    table_enc,
+    eh_frame_ptr,
+    table,
+    fde_count: fde_count.filter(|_| table_enc != DW_EH_PE_OMIT),
+  })
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the parsed `.eh_frame_hdr`, found through the `PT_GNU_EH_FRAME`
+  /// segment (stripped files) or else the section of that name.
+  ///
+  pub fn eh_frame_hdr(&self) -> Option<EhFrameHdr> {
+    let headers = self.program_headers().unwrap_or_default();
+    let segment = headers
+      .into_iter()
+      .find(|header| header.p_type() == PT_GNU_EH_FRAME);
+
+    let (bytes, address) = match segment {
+      Some(segment) => {
+        let offset = usize::try_from(segment.p_offset()).ok()?;
+        let size = usize::try_from(segment.p_filesz()).ok()?;
+        (self.data.read_bytes(size, offset)?, segment.p_vaddr())
+      }
+      None => {
+        let section = self.find_section(".eh_frame_hdr")?;
+        (self.section_bytes(section).ok()?, section.sh_addr())
+      }
+    };
+
+    parse_eh_frame_hdr::<ElfType>(bytes, address)
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+
+  type Elf64 = ElfType64<LittleEndian>;
+
+  #[test]
+  fn parse_eh_frame_hdr() {
+    let bytes = hex(
+      r"
+        01 1B 03 3B         ; version, pcrel|sdata4, udata4, datarel|sdata4
+        F4FFFFFF            ; eh_frame_ptr: 0x2004 - 0xc
+        02000000            ; fde_count
+        00F0FFFF 20000000   ; 0x2000 - 0x1000, 0x2000 + 0x20
+        10F0FFFF 40000000
+      ",
+    )
+    .unwrap();
+
+    let header = super::parse_eh_frame_hdr::<Elf64>(&bytes, 0x2000).unwrap();
+    assert_eq!(header.eh_frame_ptr, Some(0x1ff8));
+    assert_eq!(header.fde_count(), Some(2));
+    assert_eq!(
+      header.entries().collect::<Vec<_>>(),
+      vec![
+        EhFrameEntry {
+          initial_location: 0x1000,
+          fde_address: 0x2020,
+        },
+        EhFrameEntry {
+          initial_location: 0x1010,
+          fde_address: 0x2040,
+        },
+      ]
+    );
+
+    // Truncated table.
+    let header = super::parse_eh_frame_hdr::<Elf64>(&bytes[..20], 0x2000).unwrap();
+    assert_eq!(header.fde_count(), Some(2));
+    assert_eq!(header.table.len(), 1);
+
+    assert_eq!(super::parse_eh_frame_hdr::<Elf64>(&[2, 0x1b, 3, 0x3b], 0), None);
+  }
+
+  #[test]
+  fn read_encoded() {
+    let read = |bytes: &[u8], encoding: u8| {
+      let mut offset = 0;
+      let value = super::read_encoded::<ElfType32<BigEndian>>(bytes, &mut offset, encoding, 0x100);
+      value.map(|value| (value, offset))
+    };
+
+    assert_eq!(
+      read(&[0x00, 0x00, 0x10, 0x00], DW_EH_PE_ABSPTR),
+      Some((0x1000, 4))
+    );
+    assert_eq!(read(&[0xff, 0xfe], DW_EH_PE_SDATA2), Some((0xffff_fffe, 2)));
+    assert_eq!(read(&[0x7e], DW_EH_PE_SLEB128 | DW_EH_PE_PCREL), Some((0xfe, 1)));
+    assert_eq!(
+      read(&[0x10], DW_EH_PE_ULEB128 | DW_EH_PE_DATAREL),
+      Some((0x110, 1))
+    );
+    assert_eq!(read(&[0x10], DW_EH_PE_ULEB128 | 0x20), None, "DW_EH_PE_textrel");
+    assert_eq!(read(&[0x10], DW_EH_PE_ULEB128 | DW_EH_PE_INDIRECT), None);
+    assert_eq!(read(&[0x00, 0x10], DW_EH_PE_UDATA4), None, "Truncated");
+  }
+}
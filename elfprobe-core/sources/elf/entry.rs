@@ -0,0 +1,144 @@
+use ::core::fmt;
+use alloc::borrow::Cow;
+
+use super::sections::SHF_EXECINSTR;
+use super::*;
+use crate::reader::Reader;
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+///
+/// The entry point (`e_entry`) with the section and the `PT_LOAD` segment
+/// holding it, see [`ElfObject::entry_point()`].
+///
+/// Entry points outside every section (packed binaries unpacking themselves
+/// from a segment) or in a non-executable section are suspicious.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryPoint<'data> {
+  pub address: u64,
+  /// Name of the section and offset of the entry point in it.
+  pub section: Option<(Cow<'data, str>, u64)>,
+  pub executable: bool,
+  /// Index of the segment in the program header table.
+  pub segment: Option<usize>,
+}
+
+impl EntryPoint<'_> {
+  /// Returns `true` when outside every section or in a non-executable one.
+  pub fn is_suspicious(&self) -> bool {
+    self.section.is_none() || !self.executable
+  }
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the entry point with the section and the segment holding it
+  /// (see [`Self::section_containing()`] and [`Self::segment_containing()`]),
+  /// `None` without entry point (zero, e.g. most shared libraries).
+  ///
+  pub fn entry_point(&self) -> Option<EntryPoint<'data>> {
+    let address: u64 = self.header.e_entry.into();
+    if address == 0 {
+      return None;
+    }
+
+    let section = self.section_containing(address);
+    Some(EntryPoint {
+      address,
+      section: section.map(|section| {
+        let name = section.display_name().unwrap_or(Cow::Borrowed("<invalid>"));
+        (name, address - section.header.sh_addr())
+      }),
+      executable: section.is_some_and(|section| section.header.sh_flags() & SHF_EXECINSTR != 0),
+      segment: self.segment_containing(address).map(|segment| segment.index),
+    })
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+/// As `0x1040 (.text + 0x40)`, or `0x1040 (no section)`.
+impl fmt::Display for EntryPoint<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match &self.section {
+      Some((name, 0)) => write!(formatter, "{:#x} ({})", self.address, name),
+      Some((name, offset)) => write!(formatter, "{:#x} ({} + {:#x})", self.address, name, offset),
+      None => write!(formatter, "{:#x} (no section)", self.address),
+    }
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::elf::abi::sh_type::SHT_PROGBITS;
+  use crate::elf::builder::{BuilderSection, ElfBuilder};
+  use crate::elf::segments::PT_LOAD;
+
+  fn entry_point(e_entry: u64) -> Option<EntryPoint<'static>> {
+    let section = |name: &str, sh_flags, sh_addr| BuilderSection {
+      name: String::from(name),
+      sh_type: SHT_PROGBITS,
+      sh_flags,
+      sh_addr,
+      data: vec![0; 0x100],
+      ..Default::default()
+    };
+
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .object_type(2)
+      .entry(e_entry)
+      .add_section(section(".text", 0x6, 0x1000))
+      .add_section(section(".rodata", 0x2, 0x1100))
+      .segment(PT_LOAD, 5, &[1, 2])
+      .build();
+
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    object.entry_point().map(|entry| EntryPoint {
+      section: entry
+        .section
+        .map(|(name, offset)| (Cow::Owned(name.into_owned()), offset)),
+      ..entry
+    })
+  }
+
+  #[test]
+  fn entry_point_section() {
+    let entry = entry_point(0x1040).unwrap();
+    assert_eq!(entry.to_string(), "0x1040 (.text + 0x40)");
+    assert_eq!(entry.segment, Some(0));
+    assert!(!entry.is_suspicious());
+
+    assert_eq!(entry_point(0x1000).unwrap().to_string(), "0x1000 (.text)");
+    assert_eq!(entry_point(0).map(|entry| entry.address), None, "No entry point");
+
+    let entry = entry_point(0x1108).unwrap();
+    assert_eq!(entry.to_string(), "0x1108 (.rodata + 0x8)");
+    assert!(entry.is_suspicious(), "Not executable");
+
+    let entry = entry_point(0x9000).unwrap();
+    assert_eq!(entry.to_string(), "0x9000 (no section)");
+    assert_eq!(entry.segment, None);
+    assert!(entry.is_suspicious());
+  }
+}
@@ -0,0 +1,282 @@
+use ::core::fmt;
+use alloc::borrow::Cow;
+
+use super::abi::e_machine::*;
+use super::mips::{ef_mips, ef_mips_abi, MipsAbi, EF_MIPS_ABI, EF_MIPS_ARCH};
+use crate::alloc_prelude::*;
+use crate::utils::{define_constants, define_flags};
+
+// ╔═╗┬─┐┌┬┐
+// ╠═╣├┬┘│││
+// ╩ ╩┴└─┴ ┴
+
+/// EABI version field of `e_flags`.
+pub const EF_ARM_EABIMASK: u32 = 0xff000000;
+
+define_flags! {
+  /// ARM `e_flags` (EABI version 5).
+  pub ef_arm: u32 {
+    EF_ARM_ABI_FLOAT_SOFT = 0x00000200 => "soft-float ABI",
+    EF_ARM_ABI_FLOAT_HARD = 0x00000400 => "hard-float ABI",
+    EF_ARM_LE8 = 0x00400000 => "LE8",
+    EF_ARM_BE8 = 0x00800000 => "BE8",
+  }
+}
+
+define_constants! {
+  /// EABI versions (`e_flags & EF_ARM_EABIMASK`).
+  pub ef_arm_eabi: u32 {
+    EF_ARM_EABI_UNKNOWN = 0x00000000 => "GNU EABI",
+    EF_ARM_EABI_VER1 = 0x01000000 => "Version1 EABI",
+    EF_ARM_EABI_VER2 = 0x02000000 => "Version2 EABI",
+    EF_ARM_EABI_VER3 = 0x03000000 => "Version3 EABI",
+    EF_ARM_EABI_VER4 = 0x04000000 => "Version4 EABI",
+    EF_ARM_EABI_VER5 = 0x05000000 => "Version5 EABI",
+  }
+}
+
+// ╦═╗┬┌─┐┌─┐   ╦  ╦
+// ╠╦╝│└─┐│  ───╚╗╔╝
+// ╩╚═┴└─┘└─┘    ╚╝
+
+/// Floating point ABI field of `e_flags`.
+pub const EF_RISCV_FLOAT_ABI: u32 = 0x0006;
+
+define_flags! {
+  /// RISC-V `e_flags`.
+  pub ef_riscv: u32 {
+    EF_RISCV_RVC = 0x0001 => "RVC",
+    EF_RISCV_RVE = 0x0008 => "RVE",
+    EF_RISCV_TSO = 0x0010 => "TSO",
+  }
+}
+
+define_constants! {
+  /// Floating point ABIs (`e_flags & EF_RISCV_FLOAT_ABI`).
+  pub ef_riscv_float_abi: u32 {
+    EF_RISCV_FLOAT_ABI_SOFT = 0x0000 => "soft-float ABI",
+    EF_RISCV_FLOAT_ABI_SINGLE = 0x0002 => "single-float ABI",
+    EF_RISCV_FLOAT_ABI_DOUBLE = 0x0004 => "double-float ABI",
+    EF_RISCV_FLOAT_ABI_QUAD = 0x0006 => "quad-float ABI",
+  }
+}
+
+// ╔═╗╔═╗╔═╗
+// ╠═╝╠═╝║
+// ╩  ╩  ╚═╝
+
+/// ABI version field of `e_flags` (PPC64).
+pub const EF_PPC64_ABI: u32 = 0x0003;
+
+define_constants! {
+  /// ABI versions (`e_flags & EF_PPC64_ABI`), `0` for unspecified.
+  pub ef_ppc64_abi: u32 {
+    EF_PPC64_ABI_V1 = 1 => "abiv1",
+    EF_PPC64_ABI_V2 = 2 => "abiv2",
+  }
+}
+
+// ╔═╗╔═╗╔═╗╦═╗╔═╗
+// ╚═╗╠═╝╠═╣╠╦╝║
+// ╚═╝╩  ╩ ╩╩╚═╚═╝
+
+/// Memory model field of `e_flags` (SPARC v9).
+pub const EF_SPARCV9_MM: u32 = 0x0003;
+
+define_flags! {
+  /// SPARC `e_flags`.
+  pub ef_sparc: u32 {
+    EF_SPARC_32PLUS = 0x000100 => "v8+",
+    EF_SPARC_SUN_US1 = 0x000200 => "ultrasparcI",
+    EF_SPARC_HAL_R1 = 0x000400 => "halr1",
+    EF_SPARC_SUN_US3 = 0x000800 => "ultrasparcIII",
+  }
+}
+
+define_constants! {
+  /// Memory models (`e_flags & EF_SPARCV9_MM`).
+  pub ef_sparcv9_mm: u32 {
+    EF_SPARCV9_TSO = 0 => "tso",
+    EF_SPARCV9_PSO = 1 => "pso",
+    EF_SPARCV9_RMO = 2 => "rmo",
+  }
+}
+
+// ╔═╗┬  ┌─┐┌─┐┌─┐
+// ╠╣ │  ├─┤│ ┬└─┐
+// ╚  ┴─┘┴ ┴└─┘└─┘
+
+///
+/// Processor-specific flags (`e_flags`) and their description, decoded
+/// according to the target architecture. Displayed as the raw value followed
+/// by the description (e.g. `0x5000200, Version5 EABI, soft-float ABI`), bits
+/// not understood are appended in hexadecimal.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MachineFlags {
+  value: u32,
+  description: Vec<Cow<'static, str>>,
+}
+
+impl MachineFlags {
+  ///
+  /// Decodes `e_flags` for the given machine (`e_machine`), the description
+  /// is empty for machines without decoder.
+  ///
+  pub fn new(machine: u16, value: u32, is_64: bool) -> Self {
+    let mut flags = Self {
+      value,
+      description: Vec::new(),
+    };
+
+    let unknown = match machine {
+      EM_ARM => flags.arm(),
+      EM_MIPS | EM_MIPS_RS3_LE => flags.mips(is_64),
+      EM_RISCV => flags.riscv(),
+      EM_PPC64 => flags.ppc64(),
+      EM_SPARC | EM_SPARC32PLUS | EM_SPARCV9 => flags.sparc(machine == EM_SPARCV9),
+      _ => return flags,
+    };
+
+    if unknown != 0 {
+      flags.description.push(Cow::Owned(format!("{:#x}", unknown)));
+    }
+
+    flags
+  }
+
+  /// Returns the raw value.
+  pub fn value(&self) -> u32 {
+    self.value
+  }
+
+  /// Returns the description of the flags.
+  pub fn description(&self) -> &[Cow<'static, str>] {
+    &self.description
+  }
+
+  /// Appends the meaning of each flag set, returns the remaining bits.
+  fn push_flags(&mut self, flags: &[(&str, u32, &'static str)], value: u32) -> u32 {
+    let mut unknown = value;
+    for &(_, flag, meaning) in flags {
+      if value & flag == flag {
+        self.description.push(Cow::Borrowed(meaning));
+        unknown &= !flag;
+      }
+    }
+
+    unknown
+  }
+
+  fn arm(&mut self) -> u32 {
+    let eabi = ef_arm_eabi::from(self.value & EF_ARM_EABIMASK);
+    match eabi.meaning() {
+      Some(meaning) => self.description.push(Cow::Borrowed(meaning)),
+      None => self.description.push(Cow::Owned(format!("{} EABI", eabi))),
+    }
+
+    self.push_flags(ef_arm::FLAGS, self.value & !EF_ARM_EABIMASK)
+  }
+
+  fn mips(&mut self, is_64: bool) -> u32 {
+    // EF_MIPS_ABI2 is described as the n32 ABI by MipsAbi.
+    let unknown = self.push_flags(ef_mips::FLAGS, self.value & !ef_mips::EF_MIPS_ABI2);
+    self
+      .description
+      .push(Cow::Owned(MipsAbi::from_flags(self.value, is_64).to_string()));
+
+    match ef_mips_abi::from(self.value & EF_MIPS_ABI).meaning() {
+      Some(_) => unknown & !(EF_MIPS_ARCH | EF_MIPS_ABI),
+      None => unknown & !EF_MIPS_ARCH,
+    }
+  }
+
+  fn riscv(&mut self) -> u32 {
+    let float_abi = ef_riscv_float_abi::from(self.value & EF_RISCV_FLOAT_ABI);
+    let unknown = self.push_flags(ef_riscv::FLAGS, self.value & !EF_RISCV_FLOAT_ABI);
+    self.description.extend(float_abi.meaning().map(Cow::Borrowed));
+    unknown
+  }
+
+  fn ppc64(&mut self) -> u32 {
+    let abi = self.value & EF_PPC64_ABI;
+    self
+      .description
+      .extend(ef_ppc64_abi::from(abi).meaning().map(Cow::Borrowed));
+    self.value & !EF_PPC64_ABI
+  }
+
+  fn sparc(&mut self, is_v9: bool) -> u32 {
+    let mut value = self.value;
+    if is_v9 {
+      let model = ef_sparcv9_mm::from(value & EF_SPARCV9_MM);
+      self.description.extend(model.meaning().map(Cow::Borrowed));
+      value &= !EF_SPARCV9_MM;
+    }
+
+    self.push_flags(ef_sparc::FLAGS, value)
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+impl fmt::Display for MachineFlags {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(formatter, "{:#x}", self.value)?;
+    self
+      .description
+      .iter()
+      .try_for_each(|description| write!(formatter, ", {}", description))
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn decode(machine: u16, value: u32) -> String {
+    MachineFlags::new(machine, value, false).to_string()
+  }
+
+  #[test]
+  fn arm() {
+    assert_eq!(
+      decode(EM_ARM, 0x05000200),
+      "0x5000200, Version5 EABI, soft-float ABI"
+    );
+    assert_eq!(
+      decode(EM_ARM, 0x05000400),
+      "0x5000400, Version5 EABI, hard-float ABI"
+    );
+    assert_eq!(decode(EM_ARM, 0x07000001), "0x7000001, 0x7000000 EABI, 0x1");
+  }
+
+  #[test]
+  fn mips() {
+    assert_eq!(
+      decode(EM_MIPS, 0x70001007),
+      "0x70001007, noreorder, pic, cpic, mips32r2, o32"
+    );
+    assert_eq!(decode(EM_MIPS, 0x80000020), "0x80000020, mips64r2, n32");
+    assert_eq!(decode(EM_MIPS, 0xf0005000), "0xf0005000, 0xf0000000, 0x5000");
+  }
+
+  #[test]
+  fn riscv() {
+    assert_eq!(decode(EM_RISCV, 0x5), "0x5, RVC, double-float ABI");
+    assert_eq!(decode(EM_RISCV, 0x0), "0x0, soft-float ABI");
+  }
+
+  #[test]
+  fn others() {
+    assert_eq!(decode(EM_PPC64, 0x2), "0x2, abiv2");
+    assert_eq!(decode(EM_SPARCV9, 0x202), "0x202, rmo, ultrasparcI");
+    assert_eq!(decode(EM_X86_64, 0x42), "0x42");
+  }
+}
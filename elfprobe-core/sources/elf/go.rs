@@ -0,0 +1,328 @@
+use ::core::fmt;
+use ::core::mem::size_of;
+
+use super::strings::escape_controls;
+use super::*;
+use crate::reader::Reader;
+use crate::utils::read_uleb128;
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+/// Magic number of the build information header, aligned on 16 bytes.
+pub const GO_BUILDINFO_MAGIC: &[u8; 14] = b"\xff Go buildinf:";
+
+/// Size of the header (magic, pointer size, flags then padding or pointers).
+const HEADER_SIZE: usize = 32;
+
+/// Flag of the header: the strings follow the header (Go 1.18 and later).
+const FLAG_INLINE: u8 = 0x2;
+
+/// Size of the sentinels around the module information.
+const SENTINEL_SIZE: usize = 16;
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+/// A module of [`GoBuildInfo`], `replace` is the module it is replaced with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoModule<'data> {
+  pub path: &'data str,
+  pub version: &'data str,
+  /// Checksum of the module (`h1:...`), empty for the main module.
+  pub sum: &'data str,
+  pub replace: Option<Box<GoModule<'data>>>,
+}
+
+///
+/// Build information embedded by the Go toolchain in `.go.buildinfo`: the
+/// version of Go, the package and the module built, the dependencies and the
+/// build settings (`-compiler=gc`, `GOOS=linux`, `vcs.revision=...`).
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GoBuildInfo<'data> {
+  pub version: &'data str,
+  /// Import path of the main package.
+  pub path: Option<&'data str>,
+  pub main: Option<GoModule<'data>>,
+  pub deps: Vec<GoModule<'data>>,
+  /// Settings as `key=value` strings split at the first `=`.
+  pub settings: Vec<(&'data str, &'data str)>,
+}
+
+impl<'data> GoBuildInfo<'data> {
+  ///
+  /// Parses the module information (`runtime/debug.BuildInfo` without the Go
+  /// version), tab-separated lines with the sentinels removed. Unknown lines
+  /// are skipped.
+  ///
+  fn parse_modules(&mut self, text: &'data str) {
+    let module = |fields: &[&'data str]| GoModule {
+      path: fields.first().copied().unwrap_or_default(),
+      version: fields.get(1).copied().unwrap_or_default(),
+      sum: fields.get(2).copied().unwrap_or_default(),
+      replace: None,
+    };
+
+    for line in text.lines() {
+      let (kind, rest) = line.split_once('\t').unwrap_or((line, ""));
+      let fields = rest.split('\t').collect::<Vec<_>>();
+      match kind {
+        "path" => self.path = Some(rest),
+        "mod" => self.main = Some(module(&fields)),
+        "dep" => self.deps.push(module(&fields)),
+        "=>" => {
+          let replaced = self.deps.last_mut().or(self.main.as_mut());
+          if let Some(replaced) = replaced {
+            replaced.replace = Some(Box::new(module(&fields)));
+          }
+        }
+        "build" => self.settings.push(rest.split_once('=').unwrap_or((rest, ""))),
+        _ => {}
+      }
+    }
+  }
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the Go build information, `None` when there is no
+  /// `.go.buildinfo` section (other binaries are not scanned) or when it
+  /// cannot be parsed.
+  ///
+  /// The header is looked for in the section on a 16-byte boundary. Since
+  /// Go 1.18 the version and the module information follow it as
+  /// varint-prefixed strings, older versions store pointers to Go string
+  /// headers (pointer, length) which are resolved through the segments.
+  ///
+  pub fn go_buildinfo(&self) -> Option<GoBuildInfo<'data>> {
+    let section = self.find_section(".go.buildinfo")?;
+    let bytes = self.section_bytes(section).ok()?;
+    let start = (0..bytes.len())
+      .step_by(16)
+      .find(|&start| bytes[start..].starts_with(GO_BUILDINFO_MAGIC))?;
+    let header = bytes.get(start..start + HEADER_SIZE)?;
+    let (pointer_size, flags) = (header[14] as usize, header[15]);
+
+    let (version, modules) = match flags & FLAG_INLINE {
+      0 if pointer_size == size_of::<ElfType::Addr>() => {
+        let version = self.go_string(header, 16)?;
+        let modules = self.go_string(header, 16 + pointer_size)?;
+        (version, modules)
+      }
+      0 => return None,
+      _ => {
+        let mut offset = start + HEADER_SIZE;
+        let mut string = || {
+          let length = usize::try_from(read_uleb128(bytes, &mut offset)?).ok()?;
+          let string = bytes.get(offset..offset.checked_add(length)?)?;
+          offset += length;
+          Some(string)
+        };
+        (string()?, string()?)
+      }
+    };
+
+    let mut info = GoBuildInfo {
+      version: ::core::str::from_utf8(version).ok()?,
+      ..Default::default()
+    };
+
+    // The module information is framed by two 16-byte sentinels.
+    if modules.len() > 2 * SENTINEL_SIZE {
+      let modules = &modules[SENTINEL_SIZE..modules.len() - SENTINEL_SIZE];
+      info.parse_modules(::core::str::from_utf8(modules).ok()?);
+    }
+
+    Some(info)
+  }
+
+  /// Reads the Go string whose header address is at the given offset of `bytes`.
+  fn go_string(&self, bytes: &'data [u8], offset: usize) -> Option<&'data [u8]> {
+    let address = |bytes: &'data [u8], offset| -> Option<u64> {
+      Some((*bytes.read_pod::<ElfType::Addr>(offset).ok()?).into())
+    };
+
+    let word = size_of::<ElfType::Addr>();
+    let header = self.read_at_vaddr(address(bytes, offset)?, 2 * word).ok()?;
+    let length = usize::try_from(address(header, word)?).ok()?;
+    self.read_at_vaddr(address(header, 0)?, length).ok()
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+/// As `example.com/tool v1.2.0 h1:...`, with ` => ...` when replaced.
+impl fmt::Display for GoModule<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let fields = [self.path, self.version, self.sum];
+    let fields = fields.iter().filter(|field| !field.is_empty());
+    write!(
+      formatter,
+      "{}",
+      escape_controls(&fields.copied().collect::<Vec<_>>().join(" "))
+    )?;
+    match &self.replace {
+      Some(module) => write!(formatter, " => {}", module),
+      None => Ok(()),
+    }
+  }
+}
+
+/// The version, the package and the modules, then the build settings.
+impl fmt::Display for GoBuildInfo<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(formatter, "Go version: {}", escape_controls(self.version))?;
+    if let Some(path) = self.path {
+      writeln!(formatter, "Path: {}", escape_controls(path))?;
+    }
+    if let Some(main) = &self.main {
+      writeln!(formatter, "Main module: {}", main)?;
+    }
+
+    if !self.deps.is_empty() {
+      writeln!(formatter, "Dependencies:")?;
+      for module in &self.deps {
+        writeln!(formatter, "  {}", module)?;
+      }
+    }
+
+    if !self.settings.is_empty() {
+      writeln!(formatter, "Build settings:")?;
+      for (key, value) in &self.settings {
+        writeln!(formatter, "  {}={}", escape_controls(key), escape_controls(value))?;
+      }
+    }
+
+    Ok(())
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::elf::abi::sh_type::SHT_PROGBITS;
+  use crate::elf::builder::{BuilderSection, ElfBuilder};
+  use crate::elf::segments::PT_LOAD;
+
+  const MODULES: &str = concat!(
+    "path\texample.com/tool/cmd/tool\n",
+    "mod\texample.com/tool\t(devel)\t\n",
+    "dep\tgolang.org/x/sys\tv0.15.0\th1:abc=\n",
+    "=>\t../sys\t(devel)\t\n",
+    "build\t-compiler=gc\n",
+    "build\tGOOS=linux\n",
+  );
+
+  /// Module information between the sentinels.
+  fn modules() -> Vec<u8> {
+    [
+      &[0x30; SENTINEL_SIZE][..],
+      MODULES.as_bytes(),
+      &[0xf9; SENTINEL_SIZE],
+    ]
+    .concat()
+  }
+
+  fn buildinfo(bytes: &[u8]) -> Option<GoBuildInfo<'_>> {
+    let ElfFile::Elf64Le(object) = parse_elf(bytes).unwrap() else {
+      unreachable!();
+    };
+    object.go_buildinfo()
+  }
+
+  #[test]
+  fn inline_buildinfo() {
+    let mut section = GO_BUILDINFO_MAGIC.to_vec();
+    section.extend([8, FLAG_INLINE]);
+    section.resize(HEADER_SIZE, 0);
+    section.extend([8].iter().chain(b"go1.21.5"));
+    let modules = modules();
+    section.extend([modules.len() as u8 | 0x80, (modules.len() >> 7) as u8]); // Two-byte varint.
+    section.extend(&modules);
+
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".go.buildinfo", SHT_PROGBITS, &section)
+      .build();
+    let info = buildinfo(&bytes).unwrap();
+
+    assert_eq!(info.version, "go1.21.5");
+    assert_eq!(info.path, Some("example.com/tool/cmd/tool"));
+    assert_eq!(
+      info.main.as_ref().unwrap().to_string(),
+      "example.com/tool (devel)"
+    );
+    assert_eq!(
+      info.deps[0].to_string(),
+      "golang.org/x/sys v0.15.0 h1:abc= => ../sys (devel)"
+    );
+    assert_eq!(info.settings, [("-compiler", "gc"), ("GOOS", "linux")]);
+
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".data", SHT_PROGBITS, &section)
+      .build();
+    assert_eq!(buildinfo(&bytes), None, "Only .go.buildinfo is looked at");
+  }
+
+  #[test]
+  fn pointer_buildinfo() {
+    // Header at 0x1000 pointing to the string headers at 0x1020 and 0x1030,
+    // pointing to the version at 0x1040 and the modules at 0x1050.
+    let version = b"go1.17";
+    let modules = modules();
+    let mut section = GO_BUILDINFO_MAGIC.to_vec();
+    section.extend([8, 0]);
+    for word in [
+      0x1020_u64,
+      0x1030,
+      0x1040,
+      version.len() as u64,
+      0x1050,
+      modules.len() as u64,
+    ] {
+      section.extend(word.to_le_bytes());
+    }
+    section.resize(0x40, 0);
+    section.extend(version);
+    section.resize(0x50, 0);
+    section.extend(&modules);
+
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .add_section(BuilderSection {
+        name: String::from(".go.buildinfo"),
+        sh_type: SHT_PROGBITS,
+        sh_flags: 0x3,
+        sh_addr: 0x1000,
+        sh_addralign: 16,
+        data: section,
+        ..Default::default()
+      })
+      .segment(PT_LOAD, 6, &[1])
+      .build();
+    let info = buildinfo(&bytes).unwrap();
+
+    assert_eq!(info.version, "go1.17");
+    assert_eq!(info.deps.len(), 1);
+    assert!(info.to_string().starts_with(concat!(
+      "Go version: go1.17\n",
+      "Path: example.com/tool/cmd/tool\n",
+      "Main module: example.com/tool (devel)\n",
+    )));
+  }
+}
@@ -0,0 +1,193 @@
+use ::core::mem::size_of;
+use alloc::borrow::Cow;
+
+use super::dynamic::*;
+use super::notes::read_unsigned;
+use super::*;
+use crate::reader::Reader;
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+pub use super::abi::sh_type::{SHT_FINI_ARRAY, SHT_INIT_ARRAY, SHT_PREINIT_ARRAY};
+
+/// Dynamic tags related to initialization and termination.
+const INIT_TAGS: &[(i64, &str)] = &[
+  (DT_INIT, "DT_INIT"),
+  (DT_FINI, "DT_FINI"),
+  (DT_PREINIT_ARRAY, "DT_PREINIT_ARRAY"),
+  (DT_PREINIT_ARRAYSZ, "DT_PREINIT_ARRAYSZ"),
+  (DT_INIT_ARRAY, "DT_INIT_ARRAY"),
+  (DT_INIT_ARRAYSZ, "DT_INIT_ARRAYSZ"),
+  (DT_FINI_ARRAY, "DT_FINI_ARRAY"),
+  (DT_FINI_ARRAYSZ, "DT_FINI_ARRAYSZ"),
+];
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+///
+/// Content of an `.init_array`, `.fini_array` or `.preinit_array` section:
+/// the addresses of the functions called by the dynamic loader (or the C
+/// runtime) before `main()` and at exit.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitArray<'data> {
+  pub name: Cow<'data, str>,
+  pub sh_type: u32,
+  pub entries: Vec<u64>,
+  /// Trailing bytes ignored when `sh_size` is not a multiple of the entry size.
+  pub trailing: usize,
+}
+
+/// An initialization or termination dynamic entry (`DT_INIT`, `DT_FINI`...).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct InitEntry {
+  pub tag: i64,
+  pub name: &'static str,
+  pub value: u64,
+}
+
+impl InitEntry {
+  /// Returns true if the value is an address (false for sizes).
+  pub fn is_address(&self) -> bool {
+    !matches!(self.tag, DT_PREINIT_ARRAYSZ | DT_INIT_ARRAYSZ | DT_FINI_ARRAYSZ)
+  }
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the constructors and destructors arrays (in section order).
+  /// Entries are `sh_entsize` bytes wide (the address size by default), the
+  /// declared `sh_size` is honored even when it is not a multiple of it: the
+  /// array is truncated and the remainder reported in
+  /// [`InitArray::trailing`].
+  ///
+  pub fn init_arrays(&self) -> Vec<InitArray<'data>> {
+    let mut arrays = Vec::new();
+
+    for section in self.section_headers().unwrap_or_default() {
+      let sh_type = section.sh_type() as u32;
+      if !matches!(sh_type, SHT_INIT_ARRAY | SHT_FINI_ARRAY | SHT_PREINIT_ARRAY) {
+        continue;
+      }
+
+      let Ok(bytes) = self.section_bytes(section) else {
+        continue;
+      };
+
+      let entsize = match section.sh_entsize() {
+        entsize @ (4 | 8) => entsize as usize,
+        _ => size_of::<ElfType::Addr>(),
+      };
+
+      let entries = (0..bytes.len() / entsize)
+        .filter_map(|index| read_unsigned::<ElfType::Endian>(bytes, index * entsize, entsize))
+        .collect();
+
+      arrays.push(InitArray {
+        name: self.section_name(section).unwrap_or_default(),
+        sh_type,
+        entries,
+        trailing: bytes.len() % entsize,
+      });
+    }
+
+    arrays
+  }
+
+  /// Returns the initialization and termination entries of the dynamic table.
+  pub fn init_entries(&self) -> Vec<InitEntry> {
+    let entries = self.dynamic().unwrap_or_default();
+    entries
+      .iter()
+      .filter_map(|entry| {
+        let &(tag, name) = INIT_TAGS.iter().find(|(tag, _)| *tag == entry.d_tag())?;
+        let value = entry.d_val();
+        Some(InitEntry { tag, name, value })
+      })
+      .collect()
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+
+  #[test]
+  fn init_arrays() {
+    #[rustfmt::skip]
+    let bytes = hex(
+      r"
+        7F 'ELF 02 01 01 00 00 000000 00000000 ; ELFCLASS64, ELFDATA2LSB
+        0300 3E00 01000000 0000000000000000 ; ET_DYN, EM_X86_64, e_version, e_entry
+        0000000000000000 4000000000000000 00000000 ; e_phoff, e_shoff, e_flags
+        4000 3800 0000 4000 0400 0300 ; e_ehsize, e_phentsize, e_phnum, e_shentsize, e_shnum, e_shstrndx
+
+        ; Section headers (null, .init_array, .fini_array, .shstrtab)
+        00000000 00000000 0000000000000000 0000000000000000 0000000000000000
+        0000000000000000 00000000 00000000 0000000000000000 0000000000000000
+        01000000 0E000000 0300000000000000 0000000000000000 4001000000000000
+        1000000000000000 00000000 00000000 0800000000000000 0800000000000000
+        0D000000 0F000000 0300000000000000 0000000000000000 5001000000000000
+        0B00000000000000 00000000 00000000 0800000000000000 0800000000000000
+        19000000 03000000 0000000000000000 0000000000000000 6001000000000000
+        2300000000000000 00000000 00000000 0000000000000000 0000000000000000
+
+        4011000000000000 0011000000000000 ; .init_array
+        0011000000000000 AABBCC 00000000 00 ; .fini_array (truncated)
+        00 '.init_array 00 '.fini_array 00 '.shstrtab 00
+      ",
+    )
+    .unwrap();
+
+    let Ok(ElfFile::Elf64Le(object)) = parse_elf(bytes.as_slice()) else {
+      panic!("Not an elf64 little-endian");
+    };
+
+    assert_eq!(
+      object.init_arrays(),
+      vec![
+        InitArray {
+          name: ".init_array".into(),
+          sh_type: SHT_INIT_ARRAY,
+          entries: vec![0x1140, 0x1100],
+          trailing: 0,
+        },
+        InitArray {
+          name: ".fini_array".into(),
+          sh_type: SHT_FINI_ARRAY,
+          entries: vec![0x1100],
+          trailing: 3,
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn is_address() {
+    let entry = |tag| InitEntry {
+      tag,
+      name: "",
+      value: 0,
+    };
+    assert!(entry(DT_INIT).is_address());
+    assert!(entry(DT_FINI_ARRAY).is_address());
+    assert!(!entry(DT_INIT_ARRAYSZ).is_address());
+  }
+}
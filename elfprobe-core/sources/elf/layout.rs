@@ -0,0 +1,426 @@
+use ::core::fmt;
+
+use super::sections::SHT_NOBITS;
+use super::*;
+use crate::reader::Reader;
+use crate::utils::{entropy, Size, HIGH_ENTROPY};
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+/// A part of the file accounted for by the headers, see [`ElfObject::layout()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+  pub offset: u64,
+  pub size: u64,
+  /// As `ELF header`, `section [1] .text` or `segment [2] LOAD`.
+  pub name: String,
+}
+
+impl Region {
+  pub fn end(&self) -> u64 {
+    self.offset + self.size
+  }
+}
+
+/// What a [`Gap`] is made of.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum GapContents {
+  Zeros,
+  /// The same byte repeated (e.g. `0xcc` or `0x90` padding).
+  Fill(u8),
+  /// Anything else, with its entropy in bits per byte.
+  Data(f64),
+}
+
+impl GapContents {
+  pub fn classify(bytes: &[u8]) -> Self {
+    match bytes.split_first() {
+      Some((&first, rest)) if rest.iter().all(|&byte| byte == first) => match first {
+        0 => Self::Zeros,
+        byte => Self::Fill(byte),
+      },
+      _ => Self::Data(entropy(bytes)),
+    }
+  }
+
+  /// Returns `true` for data above [`HIGH_ENTROPY`] (compressed or encrypted).
+  pub fn is_high_entropy(&self) -> bool {
+    matches!(*self, Self::Data(entropy) if entropy > HIGH_ENTROPY)
+  }
+}
+
+///
+/// Bytes of the file outside every region, with the region ending last
+/// before them (`None` when the gap is at the start of the file).
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gap<'data> {
+  pub offset: u64,
+  pub bytes: &'data [u8],
+  pub contents: GapContents,
+  pub after: Option<String>,
+}
+
+///
+/// The regions of the file sorted by offset and the gaps between them, see
+/// [`ElfObject::layout()`]. The bytes after the last region are not a gap
+/// but the overlay (offset and size), see [`ElfObject::overlay()`].
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout<'data> {
+  pub file_size: u64,
+  pub overlay: Option<(u64, u64)>,
+  pub regions: Vec<Region>,
+  pub gaps: Vec<Gap<'data>>,
+}
+
+impl Layout<'_> {
+  /// Returns the number of bytes covered by the regions, overlaps counted once.
+  pub fn covered(&self) -> u64 {
+    let mut end = 0;
+    let mut covered = 0;
+    for region in &self.regions {
+      covered += region.end().saturating_sub(region.offset.max(end));
+      end = end.max(region.end());
+    }
+    covered
+  }
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the file-backed regions (ELF header, program and section header
+  /// tables, sections but `SHT_NOBITS`, segments) sorted by offset, and the
+  /// gaps between them. Regions may overlap (segments hold sections) and
+  /// appear in any order (section header table before the contents), the
+  /// parts out of the file are ignored.
+  ///
+  /// Data hidden by packers or appended by tools often sits in the gaps.
+  ///
+  pub fn layout(&self) -> Layout<'data> {
+    let regions = self.regions();
+
+    // Sweep in file order, the bytes before each region and after all the
+    // previous ones (whatever their order in the headers) are a gap.
+    let mut gaps = Vec::new();
+    let mut last: Option<&Region> = None;
+    for region in &regions {
+      let end = last.map_or(0, Region::end);
+      if region.offset > end {
+        let bytes = self
+          .data
+          .read_bytes((region.offset - end) as usize, end as usize)
+          .unwrap_or_default();
+        gaps.push(Gap {
+          offset: end,
+          bytes,
+          contents: GapContents::classify(bytes),
+          after: last.map(|last| last.name.clone()),
+        });
+      }
+      if last.is_none_or(|last| region.end() > last.end()) {
+        last = Some(region);
+      }
+    }
+
+    Layout {
+      file_size: self.data.length() as u64,
+      overlay: self.overlay().map(|(offset, bytes)| (offset, bytes.len() as u64)),
+      regions,
+      gaps,
+    }
+  }
+
+  ///
+  /// Returns the offset and the bytes after the end of the last region (see
+  /// [`Self::layout()`]): data appended to the file by installers and
+  /// self-extracting archives, not loaded nor described by any header.
+  /// `None` when the file ends with a region (e.g. the section header table).
+  ///
+  pub fn overlay(&self) -> Option<(u64, &'data [u8])> {
+    let end = self.image_size();
+    let size = (self.data.length() as u64)
+      .checked_sub(end)
+      .filter(|&size| size != 0)?;
+    let bytes = self.data.read_bytes(size as usize, end as usize)?;
+    Some((end, bytes))
+  }
+
+  ///
+  /// Returns the size of the ELF image, up to the end of its last region
+  /// (see [`Self::layout()`]), to carve an object out of a larger file
+  /// (firmware, memory dump) where the bytes past it belong to something else.
+  ///
+  pub fn image_size(&self) -> u64 {
+    self.regions().iter().map(Region::end).max().unwrap_or(0)
+  }
+
+  /// Returns the file-backed regions sorted by offset, clipped to the file.
+  fn regions(&self) -> Vec<Region> {
+    let file_size = self.data.length() as u64;
+    let mut regions = Vec::new();
+    let mut region = |offset: u64, size: u64, name: String| {
+      let size = size.min(file_size.saturating_sub(offset));
+      if size != 0 {
+        regions.push(Region { offset, size, name });
+      }
+    };
+
+    let e_ehsize: u64 = self.header.e_ehsize.into();
+    let e_phentsize: u64 = self.header.e_phentsize.into();
+    let e_phnum: u64 = self.header.e_phnum.into();
+    let e_shentsize: u64 = self.header.e_shentsize.into();
+    let e_shoff: u64 = self.header.e_shoff.into();
+    region(0, e_ehsize, String::from("ELF header"));
+    region(
+      self.header.e_phoff.into(),
+      e_phnum.saturating_mul(e_phentsize),
+      String::from("program headers"),
+    );
+    if e_shoff != 0 {
+      region(
+        e_shoff,
+        self.section_count().saturating_mul(e_shentsize),
+        String::from("section headers"),
+      );
+    }
+
+    for section in self.sections().skip(1) {
+      let header = section.header;
+      if header.sh_type() != SHT_NOBITS as u64 {
+        let name = section.display_name().unwrap_or_default();
+        let name = format!("section [{}] {}", section.index, name);
+        region(header.sh_offset(), header.sh_size(), name);
+      }
+    }
+
+    for segment in self.segments() {
+      let name = format!(
+        "segment [{}] {}",
+        segment.index,
+        segment.header.type_name(self.osabi())
+      );
+      region(segment.header.p_offset(), segment.header.p_filesz(), name);
+    }
+
+    regions.sort_by_key(|region| (region.offset, region.size));
+    regions
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+/// As `zeros`, `fill 0xcc`, `data (entropy 5.21)`.
+impl fmt::Display for GapContents {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match *self {
+      Self::Zeros => formatter.pad("zeros"),
+      Self::Fill(byte) => formatter.pad(&format!("fill {:#04x}", byte)),
+      Self::Data(entropy) if self.is_high_entropy() => {
+        formatter.pad(&format!("high entropy ({:.2})", entropy))
+      }
+      Self::Data(entropy) => formatter.pad(&format!("data (entropy {:.2})", entropy)),
+    }
+  }
+}
+
+/// Table of the gaps followed by the coverage of the file.
+impl fmt::Display for Layout<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use crate::utils::display_table;
+
+    let unaccounted: u64 = self.gaps.iter().map(|gap| gap.bytes.len() as u64).sum();
+    writeln!(formatter, "Gaps: {}", self.gaps.len())?;
+    if !self.gaps.is_empty() {
+      writeln!(formatter)?;
+      display_table!(
+        formatter,
+        align [Right, Right, Left, Left],
+        ["Offset", "Size", "Contents", "After"],
+        gap in &self.gaps => [
+          format!("{:#x}", gap.offset),
+          format!("{:#x}", gap.bytes.len()),
+          gap.contents,
+          gap.after.as_deref().unwrap_or("-"),
+        ]
+      )?;
+      writeln!(formatter)?;
+    }
+
+    writeln!(
+      formatter,
+      "Covered: {} of {} bytes, {} in gaps",
+      Size(self.covered()),
+      Size(self.file_size),
+      Size(unaccounted)
+    )?;
+    match self.overlay {
+      Some((offset, size)) => writeln!(formatter, "Overlay: {} at {:#x}", Size(size), offset),
+      None => Ok(()),
+    }
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::elf::abi::sh_type::SHT_PROGBITS;
+  use crate::elf::builder::{BuilderSection, ElfBuilder};
+  use crate::elf::segments::PT_LOAD;
+
+  #[test]
+  fn gap_contents() {
+    assert_eq!(GapContents::classify(&[0; 8]), GapContents::Zeros);
+    assert_eq!(GapContents::classify(&[0xcc; 3]).to_string(), "fill 0xcc");
+    assert_eq!(GapContents::classify(b"abcd").to_string(), "data (entropy 2.00)");
+
+    let random = (0..=255).collect::<Vec<u8>>();
+    assert!(GapContents::classify(&random).is_high_entropy());
+  }
+
+  #[test]
+  fn layout_gaps() {
+    let section = |name: &str, sh_addralign, data: &[u8]| BuilderSection {
+      name: String::from(name),
+      sh_type: SHT_PROGBITS,
+      sh_addralign,
+      data: data.to_vec(),
+      ..Default::default()
+    };
+
+    let build = |segment: bool| {
+      let mut builder = ElfBuilder::<ElfType64<LittleEndian>>::new();
+      builder
+        .add_section(section(".text", 0x10, &[0xC3; 0x11]))
+        .add_section(section(".data", 0x100, &[1; 0x10]));
+      if segment {
+        builder.segment(PT_LOAD, 5, &[1, 2]);
+      }
+      builder.build()
+    };
+
+    // .text from 0x40 to 0x51, .data aligned at 0x100, .shstrtab up to
+    // 0x127 then the section header table aligned at 0x128.
+    let mut bytes = build(false);
+    bytes[0x80..0x90].copy_from_slice(b"hidden payload!!");
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let layout = object.layout();
+    let gaps = layout
+      .gaps
+      .iter()
+      .map(|gap| (gap.offset, gap.bytes.len(), gap.after.as_deref()))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      gaps,
+      [
+        (0x51, 0xaf, Some("section [1] .text")),
+        (0x127, 1, Some("section [3] .shstrtab"))
+      ]
+    );
+    assert!(matches!(layout.gaps[0].contents, GapContents::Data(_)));
+    assert_eq!(layout.gaps[1].contents, GapContents::Zeros);
+    assert_eq!(layout.covered(), bytes.len() as u64 - 0xb0);
+    assert_eq!(layout.overlay, None, "Ends with the section header table");
+
+    // The segment covers the sections and the padding in between (.text
+    // now aligned at 0x80 after the program headers), the overlapping
+    // regions are counted once.
+    let bytes = build(true);
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let layout = object.layout();
+    let names = layout
+      .regions
+      .iter()
+      .map(|region| region.name.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(
+      names,
+      [
+        "ELF header",
+        "program headers",
+        "section [1] .text",
+        "segment [0] LOAD",
+        "section [2] .data",
+        "section [3] .shstrtab",
+        "section headers"
+      ]
+    );
+    let gaps = layout
+      .gaps
+      .iter()
+      .map(|gap| (gap.offset, gap.bytes.len()))
+      .collect::<Vec<_>>();
+    assert_eq!(gaps, [(0x78, 8), (0x127, 1)], "Only the padding");
+    assert_eq!(layout.covered(), bytes.len() as u64 - 9);
+  }
+
+  #[test]
+  fn overlay() {
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".text", SHT_PROGBITS, &[0xC3; 0x10])
+      .build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(object.overlay(), None);
+
+    let mut appended = bytes.clone();
+    appended.extend_from_slice(b"PK\x03\x04 archive");
+    let ElfFile::Elf64Le(object) = parse_elf(appended.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    let overlay = object.overlay().unwrap();
+    assert_eq!(overlay, (bytes.len() as u64, &b"PK\x03\x04 archive"[..]));
+    assert_eq!(object.layout().overlay, Some((bytes.len() as u64, 12)));
+  }
+
+  #[test]
+  fn embedded_image() {
+    use crate::pod::Pod;
+    use crate::reader::SubRange;
+
+    let image = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".text", SHT_PROGBITS, &[0xC3; 0x10])
+      .build();
+    let mut blob = vec![0xFF; 0x1a001];
+    blob.extend_from_slice(&image);
+    blob.extend_from_slice(b"trailing firmware data");
+
+    let range = SubRange::new(blob.as_slice(), 0x1a001, None).unwrap();
+    let ElfFile::Elf64Le(object) = parse_elf(range).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(object.image_size(), image.len() as u64);
+
+    // Carved out, the image parses as the original file.
+    let carved = &blob[0x1a001..0x1a001 + object.image_size() as usize];
+    assert_eq!(carved, image.as_slice());
+    let ElfFile::Elf64Le(extracted) = parse_elf(carved).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(extracted.header().as_bytes(), object.header().as_bytes());
+    assert_eq!(extracted.sections().count(), object.sections().count());
+  }
+}
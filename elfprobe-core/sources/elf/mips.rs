@@ -0,0 +1,290 @@
+use ::core::fmt;
+use ::core::mem::size_of;
+
+use super::abi::e_machine::{EM_MIPS, EM_MIPS_RS3_LE};
+use super::*;
+use crate::reader::Reader;
+use crate::utils::{define_constants, define_flags, Constant};
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+/// MIPS ABI flags section (`.MIPS.abiflags`).
+pub const SHT_MIPS_ABIFLAGS: u32 = 0x7000002a;
+
+/// Architecture level field of `e_flags`.
+pub const EF_MIPS_ARCH: u32 = 0xf0000000;
+
+/// ABI field of `e_flags`.
+pub const EF_MIPS_ABI: u32 = 0x0000f000;
+
+define_flags! {
+  /// MIPS single-bit `e_flags`.
+  pub ef_mips: u32 {
+    EF_MIPS_NOREORDER = 0x00000001 => "noreorder",
+    EF_MIPS_PIC = 0x00000002 => "pic",
+    EF_MIPS_CPIC = 0x00000004 => "cpic",
+    EF_MIPS_XGOT = 0x00000008 => "xgot",
+    EF_MIPS_UCODE = 0x00000010 => "ucode",
+    EF_MIPS_ABI2 = 0x00000020 => "abi2",
+    EF_MIPS_ABI_ON32 = 0x00000040 => "abi on32",
+    EF_MIPS_FP64 = 0x00000200 => "fp64",
+    EF_MIPS_NAN2008 = 0x00000400 => "nan2008",
+    EF_MIPS_MICROMIPS = 0x02000000 => "micromips",
+    EF_MIPS_ARCH_ASE_M16 = 0x04000000 => "mips16",
+    EF_MIPS_ARCH_ASE_MDMX = 0x08000000 => "mdmx",
+  }
+}
+
+define_constants! {
+  /// Architecture levels (`e_flags & EF_MIPS_ARCH`).
+  pub ef_mips_arch: u32 {
+    EF_MIPS_ARCH_1 = 0x00000000 => "mips1",
+    EF_MIPS_ARCH_2 = 0x10000000 => "mips2",
+    EF_MIPS_ARCH_3 = 0x20000000 => "mips3",
+    EF_MIPS_ARCH_4 = 0x30000000 => "mips4",
+    EF_MIPS_ARCH_5 = 0x40000000 => "mips5",
+    EF_MIPS_ARCH_32 = 0x50000000 => "mips32",
+    EF_MIPS_ARCH_64 = 0x60000000 => "mips64",
+    EF_MIPS_ARCH_32R2 = 0x70000000 => "mips32r2",
+    EF_MIPS_ARCH_64R2 = 0x80000000 => "mips64r2",
+    EF_MIPS_ARCH_32R6 = 0x90000000 => "mips32r6",
+    EF_MIPS_ARCH_64R6 = 0xa0000000 => "mips64r6",
+  }
+}
+
+define_constants! {
+  /// ABIs (`e_flags & EF_MIPS_ABI`), n32 and n64 are given by `EF_MIPS_ABI2`
+  /// and `ELFCLASS64` instead.
+  pub ef_mips_abi: u32 {
+    E_MIPS_ABI_O32 = 0x00001000 => "o32",
+    E_MIPS_ABI_O64 = 0x00002000 => "o64",
+    E_MIPS_ABI_EABI32 = 0x00003000 => "eabi32",
+    E_MIPS_ABI_EABI64 = 0x00004000 => "eabi64",
+  }
+}
+
+define_constants! {
+  /// Floating point ABIs (`fp_abi` of `.MIPS.abiflags`).
+  pub mips_fp_abi: u8 {
+    Val_GNU_MIPS_ABI_FP_ANY = 0 => "any float ABI",
+    Val_GNU_MIPS_ABI_FP_DOUBLE = 1 => "hard-float",
+    Val_GNU_MIPS_ABI_FP_SINGLE = 2 => "hard-float (single precision)",
+    Val_GNU_MIPS_ABI_FP_SOFT = 3 => "soft-float",
+    Val_GNU_MIPS_ABI_FP_OLD_64 = 4 => "hard-float (64-bit FPU, deprecated)",
+    Val_GNU_MIPS_ABI_FP_XX = 5 => "hard-float (FPXX)",
+    Val_GNU_MIPS_ABI_FP_64 = 6 => "hard-float (FP64)",
+    Val_GNU_MIPS_ABI_FP_64A = 7 => "hard-float (FP64A)",
+  }
+}
+
+define_constants! {
+  /// Register sizes (`gpr_size`, `cpr1_size` and `cpr2_size` of `.MIPS.abiflags`).
+  pub mips_reg_size: u8 {
+    AFL_REG_NONE = 0 => "none",
+    AFL_REG_32 = 1 => "32",
+    AFL_REG_64 = 2 => "64",
+    AFL_REG_128 = 3 => "128",
+  }
+}
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+///
+/// Content of the `.MIPS.abiflags` section (`Elf_MIPS_ABIFlags_v0`), the same
+/// in elf32 and elf64. It supersedes `e_flags` (and `.gnu.attributes`) to
+/// describe the ISA, register sizes and floating point ABI.
+///
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Pod)]
+pub struct ElfMipsAbiFlags<ElfType: self::ElfType> {
+  pub version: ElfType::Half,
+  pub isa_level: ElfType::Uchar,
+  pub isa_rev: ElfType::Uchar,
+  pub gpr_size: ElfType::Uchar,
+  pub cpr1_size: ElfType::Uchar,
+  pub cpr2_size: ElfType::Uchar,
+  pub fp_abi: ElfType::Uchar,
+  pub isa_ext: ElfType::Word,
+  pub ases: ElfType::Word,
+  pub flags1: ElfType::Word,
+  pub flags2: ElfType::Word,
+}
+
+///
+/// Decoded MIPS ABI, e.g. `mips32r2, o32, hard-float`. The floating point
+/// ABI is only known from `.MIPS.abiflags`.
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MipsAbi {
+  pub arch: Constant<u32>,
+  pub abi: Option<&'static str>,
+  pub fp_abi: Option<Constant<u8>>,
+}
+
+impl MipsAbi {
+  /// Decodes the architecture level and the ABI from `e_flags`.
+  pub fn from_flags(e_flags: u32, is_64: bool) -> Self {
+    let abi = match (e_flags & ef_mips::EF_MIPS_ABI2 != 0, is_64) {
+      (true, _) => Some("n32"),
+      (false, true) => Some("n64"),
+      (false, false) => ef_mips_abi::from(e_flags & EF_MIPS_ABI).meaning(),
+    };
+
+    Self {
+      arch: ef_mips_arch::from(e_flags & EF_MIPS_ARCH),
+      abi,
+      fp_abi: None,
+    }
+  }
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  /// Returns true if the object targets a MIPS processor.
+  fn is_mips(&self) -> bool {
+    let machine: u64 = self.header.e_machine.into();
+    matches!(machine as u16, EM_MIPS | EM_MIPS_RS3_LE)
+  }
+
+  /// Returns the `.MIPS.abiflags` content (MIPS only).
+  pub fn mips_abiflags(&self) -> Option<&'data ElfMipsAbiFlags<ElfType>> {
+    if !self.is_mips() {
+      return None;
+    }
+
+    let sections = self.section_headers().ok()?;
+    let section = sections
+      .iter()
+      .find(|section| section.sh_type() == SHT_MIPS_ABIFLAGS as u64)?;
+
+    self.section_bytes(section).ok()?.read_pod(0).ok()
+  }
+
+  /// Returns the decoded MIPS ABI (MIPS only), see [`MipsAbi`].
+  pub fn mips_abi(&self) -> Option<MipsAbi> {
+    if !self.is_mips() {
+      return None;
+    }
+
+    let e_flags: u64 = self.header.e_flags.into();
+    let is_64 = size_of::<ElfType::Addr>() == 8;
+    let mut abi = MipsAbi::from_flags(e_flags as u32, is_64);
+
+    abi.fp_abi = self.mips_abiflags().map(|abiflags| {
+      let fp_abi: u64 = abiflags.fp_abi.into();
+      mips_fp_abi::from(fp_abi as u8)
+    });
+
+    Some(abi)
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+impl fmt::Display for MipsAbi {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.arch.meaning() {
+      Some(arch) => write!(formatter, "{}", arch)?,
+      None => write!(formatter, "{}", self.arch)?,
+    }
+
+    if let Some(abi) = self.abi {
+      write!(formatter, ", {}", abi)?;
+    }
+
+    match self.fp_abi {
+      Some(fp_abi) => match fp_abi.meaning() {
+        Some(meaning) => write!(formatter, ", {}", meaning),
+        None => write!(formatter, ", fp_abi {}", fp_abi),
+      },
+      None => Ok(()),
+    }
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+
+  #[test]
+  fn abiflags_memory_size() {
+    assert_eq!(size_of::<ElfMipsAbiFlags<ElfType32<BigEndian>>>(), 24);
+    assert_eq!(size_of::<ElfMipsAbiFlags<ElfType64<BigEndian>>>(), 24);
+    assert_eq!(size_of::<ElfMipsAbiFlags<ElfType32<LittleEndian>>>(), 24);
+    assert_eq!(size_of::<ElfMipsAbiFlags<ElfType64<LittleEndian>>>(), 24);
+  }
+
+  #[test]
+  fn from_flags() {
+    let abi = MipsAbi::from_flags(0x70001007, false);
+    assert_eq!(abi.arch, ef_mips_arch::from(ef_mips_arch::EF_MIPS_ARCH_32R2));
+    assert_eq!(abi.to_string(), "mips32r2, o32");
+
+    assert_eq!(
+      MipsAbi::from_flags(0x80000027, false).to_string(),
+      "mips64r2, n32"
+    );
+    assert_eq!(MipsAbi::from_flags(0xa0000407, true).to_string(), "mips64r6, n64");
+    assert_eq!(MipsAbi::from_flags(0xf0000000, false).to_string(), "0xf0000000");
+    assert_eq!(
+      ef_mips::from(0x70001007).to_string(),
+      "EF_MIPS_NOREORDER | EF_MIPS_PIC | EF_MIPS_CPIC | 0x70001000"
+    );
+  }
+
+  #[test]
+  fn mips_abi() {
+    #[rustfmt::skip]
+    let bytes = hex(
+      r"
+        7F 'ELF 01 02 01 00 00 000000 00000000 ; ELFCLASS32, ELFDATA2MSB
+        0002 0008 00000001 00400000 ; ET_EXEC, EM_MIPS, e_version, e_entry
+        00000000 00000034 70001007 ; e_phoff, e_shoff, e_flags
+        0034 0020 0000 0028 0002 0000 ; e_ehsize, e_phentsize, e_phnum, e_shentsize, e_shnum, e_shstrndx
+
+        ; Section headers (null, .MIPS.abiflags)
+        00000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000 00000000
+        00000000 7000002A 00000002 00000000 00000084 00000018 00000000 00000000 00000008 00000018
+
+        ; .MIPS.abiflags
+        0000 20 02 01 01 00 01 ; version, mips32r2, GPR 32, FPR 32, fp_abi double
+        00000000 00000000 00000000 00000000
+      ",
+    )
+    .unwrap();
+
+    let Ok(ElfFile::Elf32Be(object)) = parse_elf(bytes.as_slice()) else {
+      panic!("Not an elf32 big-endian");
+    };
+
+    let abiflags = object.mips_abiflags().unwrap();
+    let isa_level: u64 = abiflags.isa_level.into();
+    assert_eq!(isa_level, 32);
+    assert_eq!(
+      object.mips_abi().unwrap().to_string(),
+      "mips32r2, o32, hard-float"
+    );
+    assert!(object
+      .header()
+      .to_string()
+      .contains("Flags:                              0x70001007, noreorder, pic, cpic, mips32r2, o32\n"));
+  }
+}
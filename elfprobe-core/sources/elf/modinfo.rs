@@ -0,0 +1,167 @@
+use ::core::fmt;
+use alloc::borrow::Cow;
+
+use super::strings::printable;
+use super::*;
+use crate::reader::Reader;
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+///
+/// The `key=value` entries of the `.modinfo` section of a Linux kernel
+/// module (`license`, `vermagic`, `depends`, `srcversion`...), in file order.
+/// Keys repeat (one `parm=` and `parmtype=` per parameter, one `alias=` per
+/// device), the entries are therefore kept as a list rather than a map.
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModInfo<'data> {
+  pub entries: Vec<(Cow<'data, str>, Cow<'data, str>)>,
+}
+
+impl<'data> ModInfo<'data> {
+  /// Returns the values of the given key, in file order.
+  pub fn get<'info>(&'info self, key: &'info str) -> impl Iterator<Item = &'info str> + 'info {
+    self
+      .entries
+      .iter()
+      .filter(move |(name, _)| name == key)
+      .map(|(_, value)| value.as_ref())
+  }
+
+  /// Returns the first value of the given key.
+  pub fn first(&self, key: &str) -> Option<&str> {
+    let (_, value) = self.entries.iter().find(|(name, _)| name == key)?;
+    Some(value)
+  }
+
+  ///
+  /// Returns `true` when the `vermagic` entry (kernel release and build
+  /// options, e.g. `6.1.0-18-amd64 SMP preempt mod_unload modversions`)
+  /// is the expected one, surrounding spaces aside. The kernel refuses the
+  /// module otherwise, `false` when there is no such entry.
+  ///
+  pub fn vermagic_matches(&self, expected: &str) -> bool {
+    self
+      .first("vermagic")
+      .is_some_and(|vermagic| vermagic.trim() == expected.trim())
+  }
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  /// Returns `true` for Linux kernel modules (`.gnu.linkonce.this_module` section).
+  pub fn is_kernel_module(&self) -> bool {
+    self.find_section(".gnu.linkonce.this_module").is_some()
+  }
+
+  ///
+  /// Returns the entries of the `.modinfo` section, `None` without it. The
+  /// section is a sequence of NUL-separated `key=value` strings (padded with
+  /// NULs), an entry without `=` has an empty value, invalid UTF-8 bytes and
+  /// control characters are escaped, see [`printable()`].
+  ///
+  pub fn modinfo(&self) -> Option<ModInfo<'data>> {
+    let section = self.find_section(".modinfo")?;
+    let bytes = self.section_bytes(section).ok()?;
+
+    let entries = bytes
+      .split(|&byte| byte == 0)
+      .filter(|entry| !entry.is_empty())
+      .map(|entry| match entry.iter().position(|&byte| byte == b'=') {
+        Some(equal) => (printable(&entry[..equal]), printable(&entry[equal + 1..])),
+        None => (printable(entry), Cow::Borrowed("")),
+      })
+      .collect();
+
+    Some(ModInfo { entries })
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+/// Table of the entries, repeated keys included.
+impl fmt::Display for ModInfo<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use crate::utils::display_table;
+
+    display_table!(
+      formatter,
+      align [Left, Left],
+      ["Key", "Value"],
+      (key, value) in &self.entries => [key, value]
+    )
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::elf::abi::sh_type::SHT_PROGBITS;
+  use crate::elf::builder::ElfBuilder;
+
+  const MODINFO: &[u8] = concat!(
+    "parmtype=debug:int\0",
+    "parm=debug:Debug level (0-3)\0",
+    "parmtype=quirks:charp\0",
+    "parm=quirks:Device quirks\0",
+    "license=GPL\0",
+    "srcversion=8C1A4E3B5F2D\0\0\0",
+    "depends=usbcore\0",
+    "name=example\0",
+    "vermagic=6.1.0-18-amd64 SMP preempt mod_unload modversions \0",
+  )
+  .as_bytes();
+
+  #[test]
+  fn kernel_module() {
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".modinfo", SHT_PROGBITS, MODINFO)
+      .section(".gnu.linkonce.this_module", SHT_PROGBITS, &[0; 0x40])
+      .build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    assert!(object.is_kernel_module());
+    let modinfo = object.modinfo().unwrap();
+    assert_eq!(modinfo.entries.len(), 9);
+    assert_eq!(
+      modinfo.get("parm").collect::<Vec<_>>(),
+      ["debug:Debug level (0-3)", "quirks:Device quirks"]
+    );
+    assert_eq!(modinfo.first("depends"), Some("usbcore"));
+    assert!(modinfo.vermagic_matches("6.1.0-18-amd64 SMP preempt mod_unload modversions"));
+    assert!(!modinfo.vermagic_matches("6.1.0-17-amd64 SMP preempt mod_unload modversions"));
+    assert!(modinfo.to_string().contains("\nlicense     GPL\n"));
+  }
+
+  #[test]
+  fn not_a_module() {
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".modinfo", SHT_PROGBITS, b"license=GPL\0flag")
+      .build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    assert!(!object.is_kernel_module());
+    let modinfo = object.modinfo().unwrap();
+    assert_eq!(modinfo.entries[1], (Cow::Borrowed("flag"), Cow::Borrowed("")));
+    assert!(!modinfo.vermagic_matches(""), "No vermagic");
+  }
+}
@@ -0,0 +1,380 @@
+use ::core::fmt;
+use ::core::mem::size_of;
+
+use super::segments::PT_NOTE;
+use super::strings::printable;
+use super::*;
+use crate::endian::UnalignedEndianOperation;
+use crate::error::BytesError;
+use crate::reader::Reader;
+use crate::utils::{define_constants, Constant};
+use elfprobe_macro::Pod;
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+define_constants! {
+  /// Note types of the `GNU` owner.
+  pub nt_gnu: u32 {
+    NT_GNU_ABI_TAG = 1 => "ABI version tag",
+    NT_GNU_HWCAP = 2 => "DSO-supplied software HWCAP info",
+    NT_GNU_BUILD_ID = 3 => "unique build ID bitstring",
+    NT_GNU_GOLD_VERSION = 4 => "gold version",
+    NT_GNU_PROPERTY_TYPE_0 = 5 => "program property",
+  }
+}
+
+define_constants! {
+  /// Note types of the `CORE` owner (core dumps).
+  pub nt_core: u32 {
+    NT_PRSTATUS = 1 => "prstatus structure",
+    NT_PRFPREG = 2 => "prfpregset structure",
+    NT_PRPSINFO = 3 => "prpsinfo structure",
+    NT_TASKSTRUCT = 4 => "task structure",
+    NT_AUXV = 6 => "auxiliary vector",
+    NT_SIGINFO = 0x53494749 => "siginfo_t data",
+    NT_FILE = 0x46494c45 => "mapped files",
+    NT_PRXFPREG = 0x46e62b7f => "user_xfpregs structure",
+  }
+}
+
+define_constants! {
+  /// Note types of the `LINUX` owner (core dumps, register sets).
+  pub nt_linux: u32 {
+    NT_386_TLS = 0x200 => "x86 TLS information",
+    NT_386_IOPERM = 0x201 => "x86 I/O permissions",
+    NT_X86_XSTATE = 0x202 => "x86 XSAVE extended state",
+    NT_X86_SHSTK = 0x204 => "x86 SHSTK state",
+    NT_ARM_VFP = 0x400 => "ARM VFP/NEON registers",
+    NT_ARM_TLS = 0x401 => "AArch TLS registers",
+    NT_ARM_HW_BREAK = 0x402 => "AArch hardware breakpoint registers",
+    NT_ARM_HW_WATCH = 0x403 => "AArch hardware watchpoint registers",
+    NT_ARM_SYSTEM_CALL = 0x404 => "AArch system call number",
+    NT_ARM_SVE = 0x405 => "AArch SVE registers",
+    NT_ARM_PAC_MASK = 0x406 => "AArch pointer authentication code masks",
+    NT_RISCV_CSR = 0x900 => "RISC-V control and status registers",
+    NT_RISCV_VECTOR = 0x901 => "RISC-V vector registers",
+  }
+}
+
+define_constants! {
+  /// Note types of the `FreeBSD` owner.
+  pub nt_freebsd: u32 {
+    NT_FREEBSD_ABI_TAG = 1 => "ABI version tag",
+    NT_FREEBSD_NOINIT_TAG = 2 => "no .init",
+    NT_FREEBSD_ARCH_TAG = 3 => "architecture",
+    NT_FREEBSD_FEATURE_CTL = 4 => "feature control",
+  }
+}
+
+/// Lookup function of a note types table (see [`define_constants!`]).
+type NoteTable = fn(u32) -> Constant<u32>;
+
+///
+/// Note type tables by owner, `n_type` has no meaning without the owner so
+/// notes of unregistered owners are displayed in hexadecimal.
+///
+const NOTE_TABLES: &[(&str, NoteTable)] = &[
+  ("GNU", nt_gnu::from),
+  ("CORE", nt_core::from),
+  ("LINUX", nt_linux::from),
+  ("FreeBSD", nt_freebsd::from),
+];
+
+/// Returns the note type of the given owner (the note name).
+pub fn note_type_name(owner: &str, n_type: u32) -> Constant<u32> {
+  NOTE_TABLES
+    .iter()
+    .find(|(table_owner, _)| *table_owner == owner)
+    .map_or(Constant::unknown(n_type), |(_, from)| from(n_type))
+}
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+///
+/// Note header, the same in elf32 and elf64 (all fields are `Word`). It is
+/// followed by the name and the descriptor, both padded to the note alignment.
+///
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Pod)]
+pub struct ElfNoteHeader<ElfType: self::ElfType> {
+  pub n_namesz: ElfType::Word,
+  pub n_descsz: ElfType::Word,
+  pub n_type: ElfType::Word,
+}
+
+///
+/// A single note entry, the name is the owner of the note (`CORE`, `GNU`,
+/// `LINUX`...) without its trailing NUL and gives meaning to `n_type`.
+///
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ElfNote<'data> {
+  pub name: &'data [u8],
+  pub n_type: u32,
+  pub desc: &'data [u8],
+}
+
+impl ElfNote<'_> {
+  /// Returns the note type, named after the owner (see [`note_type_name()`]).
+  pub fn note_type(&self) -> Constant<u32> {
+    let owner = ::core::str::from_utf8(self.name).unwrap_or_default();
+    note_type_name(owner, self.n_type)
+  }
+}
+
+/// Same columns as `readelf -n`: owner, descriptor size and note type.
+impl fmt::Display for ElfNote<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let n_type = self.note_type();
+    write!(
+      formatter,
+      "{:<20} {:#010x}  {}",
+      printable(self.name),
+      self.desc.len(),
+      n_type
+    )?;
+
+    match n_type.meaning() {
+      Some(meaning) => write!(formatter, " ({})", meaning),
+      None => Ok(()),
+    }
+  }
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the notes of all `PT_NOTE` segments (in file order). Entries are
+  /// padded to 4 bytes, or 8 bytes when the segment is aligned on 8 bytes
+  /// (e.g. `NT_GNU_PROPERTY_TYPE_0`).
+  ///
+  /// A truncated entry stops the parsing of its segment, the notes read so
+  /// far are kept.
+  ///
+  pub fn notes(&self) -> Result<Vec<ElfNote<'data>>, ElfError> {
+    let mut notes = Vec::new();
+
+    for header in self.program_headers()? {
+      if header.p_type() != PT_NOTE {
+        continue;
+      }
+
+      let offset = usize::try_from(header.p_offset()).map_err(|_| BytesError::Empty)?;
+      let size = usize::try_from(header.p_filesz()).map_err(|_| BytesError::Empty)?;
+      let Some(bytes) = self.data.read_bytes(size, offset) else {
+        continue;
+      };
+
+      let align = if header.p_align() == 8 { 8 } else { 4 };
+      notes.extend(parse_notes::<ElfType>(bytes, align));
+    }
+
+    Ok(notes)
+  }
+
+  ///
+  /// Returns the build ID (descriptor of the `GNU` `NT_GNU_BUILD_ID` note),
+  /// looked up in the `PT_NOTE` segments then in the `.note.gnu.build-id`
+  /// section for relocatable objects.
+  ///
+  pub fn build_id(&self) -> Option<&'data [u8]> {
+    let is_build_id = |note: &ElfNote<'data>| note.name == b"GNU" && note.n_type == nt_gnu::NT_GNU_BUILD_ID;
+
+    let segments = self.notes().unwrap_or_default();
+    if let Some(note) = segments.into_iter().find(is_build_id) {
+      return Some(note.desc);
+    }
+
+    let section = self.find_section(".note.gnu.build-id")?;
+    let bytes = self.section_bytes(section).ok()?;
+    parse_notes::<ElfType>(bytes, 4)
+      .into_iter()
+      .find(is_build_id)
+      .map(|note| note.desc)
+  }
+}
+
+/// Splits a note area into entries, see [`ElfObject::notes()`].
+fn parse_notes<ElfType: self::ElfType>(bytes: &[u8], align: usize) -> Vec<ElfNote<'_>> {
+  let mut notes = Vec::new();
+  let mut offset = 0;
+
+  while let Ok(header) = bytes.read_pod::<ElfNoteHeader<ElfType>>(offset) {
+    let namesz: u64 = header.n_namesz.into();
+    let descsz: u64 = header.n_descsz.into();
+    let n_type: u64 = header.n_type.into();
+    let (Ok(namesz), Ok(descsz)) = (usize::try_from(namesz), usize::try_from(descsz)) else {
+      break;
+    };
+
+    let name_offset = offset + size_of::<ElfNoteHeader<ElfType>>();
+    // The padding is relative to the start of the area (the header is 12 bytes).
+    let Some(desc_offset) = name_offset
+      .checked_add(namesz)
+      .and_then(|end| end.checked_next_multiple_of(align))
+    else {
+      break;
+    };
+
+    let name = bytes.read_bytes(namesz, name_offset);
+    let desc = bytes.read_bytes(descsz, desc_offset);
+    let (Some(name), Some(desc)) = (name, desc) else {
+      break;
+    };
+
+    notes.push(ElfNote {
+      name: name.strip_suffix(b"\0").unwrap_or(name),
+      n_type: n_type as u32,
+      desc,
+    });
+
+    match desc_offset
+      .checked_add(descsz)
+      .and_then(|end| end.checked_next_multiple_of(align))
+    {
+      Some(next) => offset = next,
+      None => break,
+    }
+  }
+
+  notes
+}
+
+///
+/// Reads an unsigned integer of the given size (1, 2, 4 or 8 bytes) at the
+/// given offset. Note descriptors are only aligned on 4 bytes hence the
+/// unaligned read (whatever the `unaligned` feature).
+///
+pub(crate) fn read_unsigned<E: Endianness>(bytes: &[u8], offset: usize, size: usize) -> Option<u64> {
+  let bytes = bytes.get(offset..offset.checked_add(size)?)?;
+  match size {
+    1 => Some(bytes[0] as u64),
+    2 => Some(<E as UnalignedEndianOperation<u16, 2>>::read(bytes.try_into().ok()?) as u64),
+    4 => Some(<E as UnalignedEndianOperation<u32, 4>>::read(bytes.try_into().ok()?) as u64),
+    8 => Some(<E as UnalignedEndianOperation<u64, 8>>::read(
+      bytes.try_into().ok()?,
+    )),
+    _ => None,
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+
+  #[test]
+  fn note_header_memory_size() {
+    assert_eq!(size_of::<ElfNoteHeader<ElfType32<BigEndian>>>(), 12);
+    assert_eq!(size_of::<ElfNoteHeader<ElfType64<BigEndian>>>(), 12);
+    assert_eq!(size_of::<ElfNoteHeader<ElfType32<LittleEndian>>>(), 12);
+    assert_eq!(size_of::<ElfNoteHeader<ElfType64<LittleEndian>>>(), 12);
+  }
+
+  #[test]
+  fn parse_notes() {
+    let bytes = hex(
+      r"
+        04000000 03000000 01000000 'GNU 00 616263 00 ; Padded descriptor
+        05000000 00000000 2A000000 'CORE 00 000000   ; Padded name, empty descriptor
+        05000000 10000000 02000000 'CORE 00 000000   ; Truncated descriptor
+      ",
+    )
+    .unwrap();
+
+    let notes = super::parse_notes::<ElfType64<LittleEndian>>(&bytes, 4);
+    assert_eq!(
+      notes,
+      vec![
+        ElfNote {
+          name: b"GNU",
+          n_type: 1,
+          desc: b"abc"
+        },
+        ElfNote {
+          name: b"CORE",
+          n_type: 42,
+          desc: b""
+        },
+      ]
+    );
+  }
+
+  #[test]
+  fn parse_notes_aligned() {
+    let bytes = hex(
+      r"
+        04000000 10000000 05000000 'GNU 00 ; NT_GNU_PROPERTY_TYPE_0, descriptor at 16
+        020000C0 04000000 03000000 00000000
+        04000000 01000000 01000000 'GNU 00 2A 00000000000000
+      ",
+    )
+    .unwrap();
+
+    let notes = super::parse_notes::<ElfType64<LittleEndian>>(&bytes, 8);
+    assert_eq!(notes.len(), 2);
+    assert_eq!(notes[0].desc, &bytes[16..32]);
+    assert_eq!(notes[1].desc, b"*");
+  }
+
+  #[test]
+  fn note_type_name() {
+    let name = |owner, n_type| super::note_type_name(owner, n_type).to_string();
+
+    assert_eq!(name("GNU", 3), "NT_GNU_BUILD_ID");
+    assert_eq!(name("CORE", 3), "NT_PRPSINFO");
+    assert_eq!(name("FreeBSD", 3), "NT_FREEBSD_ARCH_TAG");
+    assert_eq!(name("LINUX", 0x202), "NT_X86_XSTATE");
+    assert_eq!(name("GNU", 0x42), "0x42", "Unknown type");
+    assert_eq!(name("stapsdt", 3), "0x3", "Unknown owner");
+  }
+
+  #[test]
+  fn display_note() {
+    let note = ElfNote {
+      name: b"GNU",
+      n_type: 3,
+      desc: &[0; 20],
+    };
+
+    assert_eq!(
+      note.to_string(),
+      "GNU                  0x00000014  NT_GNU_BUILD_ID (unique build ID bitstring)"
+    );
+  }
+
+  #[test]
+  fn read_unsigned() {
+    let bytes = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+    assert_eq!(super::read_unsigned::<LittleEndian>(&bytes, 1, 2), Some(0x0302));
+    assert_eq!(super::read_unsigned::<BigEndian>(&bytes, 1, 4), Some(0x02030405));
+    assert_eq!(
+      super::read_unsigned::<LittleEndian>(&bytes, 0, 8),
+      Some(0x0807060504030201)
+    );
+    assert_eq!(
+      super::read_unsigned::<LittleEndian>(&bytes, 1, 8),
+      None,
+      "Out of bounds"
+    );
+    assert_eq!(
+      super::read_unsigned::<LittleEndian>(&bytes, 0, 3),
+      None,
+      "Bad size"
+    );
+  }
+}
@@ -0,0 +1,131 @@
+use ::core::mem::{offset_of, size_of};
+
+use super::*;
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+/// Identification bytes, the same in both classes.
+type Identification = ElfIdentification<ElfType32<LittleEndian>>;
+
+pub const EI_MAG0: usize = offset_of!(Identification, ei_mag0);
+pub const EI_CLASS: usize = offset_of!(Identification, ei_class);
+pub const EI_DATA: usize = offset_of!(Identification, ei_data);
+pub const EI_VERSION: usize = offset_of!(Identification, ei_version);
+pub const EI_OSABI: usize = offset_of!(Identification, ei_osabi);
+pub const EI_ABIVERSION: usize = offset_of!(Identification, ei_abiversion);
+pub const EI_PAD: usize = offset_of!(Identification, ei_pad);
+pub const EI_NIDENT: usize = size_of::<Identification>();
+
+/// Offsets of the ELF header fields of a class, see [`ELF32_HEADER`] and [`ELF64_HEADER`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HeaderOffsets {
+  pub e_type: usize,
+  pub e_machine: usize,
+  pub e_version: usize,
+  pub e_entry: usize,
+  pub e_phoff: usize,
+  pub e_shoff: usize,
+  pub e_flags: usize,
+  pub e_ehsize: usize,
+  pub e_phentsize: usize,
+  pub e_phnum: usize,
+  pub e_shentsize: usize,
+  pub e_shnum: usize,
+  pub e_shstrndx: usize,
+  /// Size of the header, the minimum `e_ehsize`.
+  pub size: usize,
+}
+
+impl HeaderOffsets {
+  ///
+  /// Returns the offsets of the header fields of the given class, derived
+  /// from the structure itself so that a field of a 64-bit file is never
+  /// written at its 32-bit offset (`e_shoff` is at 0x20 in elf32, 0x28 in
+  /// elf64). The data encoding does not change them.
+  ///
+  pub const fn of<ElfType: self::ElfType>() -> Self {
+    Self {
+      e_type: offset_of!(ElfHeader<ElfType>, e_type),
+      e_machine: offset_of!(ElfHeader<ElfType>, e_machine),
+      e_version: offset_of!(ElfHeader<ElfType>, e_version),
+      e_entry: offset_of!(ElfHeader<ElfType>, e_entry),
+      e_phoff: offset_of!(ElfHeader<ElfType>, e_phoff),
+      e_shoff: offset_of!(ElfHeader<ElfType>, e_shoff),
+      e_flags: offset_of!(ElfHeader<ElfType>, e_flags),
+      e_ehsize: offset_of!(ElfHeader<ElfType>, e_ehsize),
+      e_phentsize: offset_of!(ElfHeader<ElfType>, e_phentsize),
+      e_phnum: offset_of!(ElfHeader<ElfType>, e_phnum),
+      e_shentsize: offset_of!(ElfHeader<ElfType>, e_shentsize),
+      e_shnum: offset_of!(ElfHeader<ElfType>, e_shnum),
+      e_shstrndx: offset_of!(ElfHeader<ElfType>, e_shstrndx),
+      size: size_of::<ElfHeader<ElfType>>(),
+    }
+  }
+}
+
+pub const ELF32_HEADER: HeaderOffsets = HeaderOffsets::of::<ElfType32<LittleEndian>>();
+pub const ELF64_HEADER: HeaderOffsets = HeaderOffsets::of::<ElfType64<LittleEndian>>();
+
+///
+/// Checks at compile time the offsets of both classes against `offset_of!`
+/// on the other data encoding and against the numbers of the gABI.
+///
+macro_rules! assert_offsets {
+  ($offsets: ident, $elf_type: ty, [$($field: ident = $expected: expr),+ $(,)?]) => {
+    const _: () = {
+      $(
+        assert!($offsets.$field == offset_of!(ElfHeader<$elf_type>, $field));
+        assert!($offsets.$field == $expected);
+      )+
+      assert!($offsets.size == size_of::<ElfHeader<$elf_type>>());
+    };
+  };
+}
+
+assert_offsets!(
+  ELF32_HEADER,
+  ElfType32<BigEndian>,
+  [
+    e_type = 0x10,
+    e_machine = 0x12,
+    e_version = 0x14,
+    e_entry = 0x18,
+    e_phoff = 0x1C,
+    e_shoff = 0x20,
+    e_flags = 0x24,
+    e_ehsize = 0x28,
+    e_phentsize = 0x2A,
+    e_phnum = 0x2C,
+    e_shentsize = 0x2E,
+    e_shnum = 0x30,
+    e_shstrndx = 0x32,
+  ]
+);
+assert_offsets!(
+  ELF64_HEADER,
+  ElfType64<BigEndian>,
+  [
+    e_type = 0x10,
+    e_machine = 0x12,
+    e_version = 0x14,
+    e_entry = 0x18,
+    e_phoff = 0x20,
+    e_shoff = 0x28,
+    e_flags = 0x30,
+    e_ehsize = 0x34,
+    e_phentsize = 0x36,
+    e_phnum = 0x38,
+    e_shentsize = 0x3A,
+    e_shnum = 0x3C,
+    e_shstrndx = 0x3E,
+  ]
+);
+
+const _: () = {
+  assert!(EI_CLASS == 4 && EI_DATA == 5 && EI_OSABI == 7 && EI_PAD == 9 && EI_NIDENT == 16);
+  assert!(EI_MAG0 == offset_of!(ElfIdentification<ElfType64<BigEndian>>, ei_mag0));
+  assert!(EI_ABIVERSION == offset_of!(ElfIdentification<ElfType64<BigEndian>>, ei_abiversion));
+  assert!(ELF32_HEADER.size == 52 && ELF64_HEADER.size == 64);
+};
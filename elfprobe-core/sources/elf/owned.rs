@@ -0,0 +1,110 @@
+use std::fmt;
+use std::path::Path;
+
+use super::*;
+use crate::file;
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+///
+/// An ELF file memory-mapped or read when it cannot be (see
+/// [`file::InputData`]), see [`ElfFile::open()`].
+///
+/// The parsed [`ElfFile`] borrows the content, it is therefore not stored
+/// alongside it but parsed again by [`Self::elf()`], which only reads the
+/// header in place. The file is checked when opened, yet a mapping shared
+/// with other processes may change afterwards: the parsing can still fail.
+///
+pub struct OwnedElfFile {
+  file: file::InputData,
+}
+
+impl OwnedElfFile {
+  /// Returns the bytes of the whole file.
+  #[inline]
+  pub fn as_bytes(&self) -> &[u8] {
+    self.file.as_ref()
+  }
+
+  /// Returns the parsed file, borrowing the content.
+  pub fn elf(&self) -> Result<ElfFile<'_, &[u8]>, ElfError> {
+    parse_elf(self.as_bytes())
+  }
+
+  /// Returns the file behind the [`ElfView`] trait, see [`Self::elf()`].
+  pub fn view(&self) -> Result<Box<dyn ElfView<'_> + '_>, ElfError> {
+    self.elf().map(ElfFile::into_view)
+  }
+}
+
+impl fmt::Debug for OwnedElfFile {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.elf() {
+      Ok(elf) => elf.fmt(formatter),
+      Err(error) => formatter
+        .debug_struct("OwnedElfFile")
+        .field("error", &error)
+        .finish(),
+    }
+  }
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data> ElfFile<'data, &'data [u8]> {
+  ///
+  /// Maps (or reads) the file at the given path and checks that it is a
+  /// valid ELF file (see [`parse_elf()`]), I/O errors are
+  /// [`ElfError::Io`].
+  ///
+  pub fn open<P: AsRef<Path>>(path: P) -> Result<OwnedElfFile, ElfError> {
+    let file = file::InputData::try_from(path.as_ref())?;
+    parse_elf(file.as_ref())?;
+    Ok(OwnedElfFile { file })
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::{env, fs, io, process};
+
+  #[test]
+  fn open() {
+    let path = env::temp_dir().join(format!("elfprobe-open-{}", process::id()));
+
+    fs::write(&path, header_fixture()).unwrap();
+    let file = ElfFile::open(&path).unwrap();
+    assert!(!file.elf().unwrap().is_64());
+    assert_eq!(file.elf().unwrap().entry(), 0x01020304);
+    assert_eq!(file.as_bytes().len(), 52);
+    assert!(file.view().is_ok());
+
+    fs::write(&path, b"!<arch>\n").unwrap();
+    let result = ElfFile::open(&path);
+    assert!(matches!(result, Err(ElfError::BadMagic(_))));
+
+    // Empty files are not mapped, they are not ELF files rather than I/O errors.
+    fs::write(&path, b"").unwrap();
+    let result = ElfFile::open(&path);
+    let truncated = ElfError::Truncated {
+      needed: 4,
+      available: 0,
+    };
+    assert_eq!(result.err(), Some(truncated));
+
+    fs::remove_file(&path).unwrap();
+    let Err(ElfError::Io(error)) = ElfFile::open(&path) else {
+      panic!("Expected an I/O error");
+    };
+    assert_eq!(error.kind(), io::ErrorKind::NotFound);
+  }
+}
@@ -0,0 +1,556 @@
+use ::core::mem::{offset_of, size_of};
+
+use super::abi::sh_flags::SHF_INFO_LINK;
+use super::abi::sh_type::{SHT_GROUP, SHT_REL, SHT_RELA, SHT_SYMTAB_SHNDX};
+use super::offsets::HeaderOffsets;
+use super::sections::{SHF_ALLOC, SHN_LORESERVE, SHN_XINDEX, SHT_NOBITS};
+use super::segments::{PF_X, PT_GNU_STACK, PT_INTERP};
+use super::symbols::{SHT_DYNSYM, SHT_SYMTAB};
+use super::*;
+use crate::error::BytesError;
+use crate::primitive::{U16, U32, U64};
+use crate::reader::{to_usize, Reader};
+use crate::writer::Writer;
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+///
+/// Writes a field of a section header at the given offset of the entry, its
+/// type (`Half`, `Word`, `Off`, `Xword`) is 2, 4 or 8 bytes depending on the
+/// class. See [`write_field()`], and [`HeaderOffsets`] for the ELF header.
+///
+macro_rules! set_field {
+  ($bytes: expr, $struct: ident<$elf_type: ident>, $entry: expr, $field: ident: $type: ident, $value: expr) => {
+    write_field::<$elf_type::$type, $elf_type::Endian>(
+      $bytes,
+      $entry + offset_of!($struct<$elf_type>, $field),
+      $value,
+    )
+  };
+}
+
+/// Writes the value as the given generic field type, truncated to its size.
+fn write_field<Field: Type, Endian: crate::endian::Endianness>(
+  bytes: &mut [u8],
+  offset: usize,
+  value: u64,
+) -> Result<(), BytesError> {
+  match size_of::<Field>() {
+    2 => bytes.write_pod::<U16<Endian>>(offset)?.set(value as u16),
+    4 => bytes.write_pod::<U32<Endian>>(offset)?.set(value as u32),
+    _ => bytes.write_pod::<U64<Endian>>(offset)?.set(value),
+  }
+  Ok(())
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns a copy of the file with the program interpreter replaced by the
+  /// given path (without NUL), as `patchelf --set-interpreter` but in place
+  /// only: the path and its NUL must fit in the `PT_INTERP` segment.
+  ///
+  /// The path is written at the start of the segment and the rest of it is
+  /// cleared. The `p_filesz` and `p_memsz` of the segment, and the `sh_size`
+  /// of the `.interp` section at the same offset if any, are set to the new
+  /// length since the kernel expects the NUL as the last byte of the segment.
+  /// The cleared bytes are then out of the segment, a later patch cannot
+  /// use them for a longer path.
+  ///
+  pub fn set_interpreter(&self, path: &[u8]) -> Result<Vec<u8>, ElfError> {
+    let headers = self.program_headers()?;
+    let (index, segment) = headers
+      .iter()
+      .enumerate()
+      .find(|(_, header)| header.p_type() == PT_INTERP)
+      .ok_or(ElfError::NoInterpreter)?;
+
+    let length = path.len() + 1;
+    let capacity = segment.p_filesz();
+    if length as u64 > capacity {
+      return Err(ElfError::InterpreterTooLong { length, capacity });
+    }
+
+    let mut bytes = self.copy();
+    let available = bytes.len();
+
+    // The path followed by zeros up to the former end of the segment.
+    let offset = to_usize(segment.p_offset())?;
+    let interpreter = bytes
+      .write_bytes(to_usize(capacity)?, offset)
+      .ok_or(ElfError::Truncated {
+        needed: offset.saturating_add(capacity as usize),
+        available,
+      })?;
+    interpreter.fill(0);
+    interpreter[..path.len()].copy_from_slice(path);
+
+    let entry = bytes.write_pod::<ElfType::ProgramHeader>(self.program_header_offset(index)?)?;
+    entry.set_p_filesz(length as u64);
+    entry.set_p_memsz(length as u64);
+
+    let section = self.sections().find(|section| {
+      section.name_bytes() == Some(b".interp") && section.header.sh_offset() == segment.p_offset()
+    });
+    if let Some(section) = section {
+      let shoff: u64 = self.header.e_shoff.into();
+      let shentsize: u64 = self.header.e_shentsize.into();
+      let entry = to_usize(shoff + section.index as u64 * shentsize)?;
+      set_field!(&mut bytes, ElfSection<ElfType>, entry, sh_size: Xword, length as u64)?;
+    }
+
+    Ok(bytes)
+  }
+
+  ///
+  /// Returns a copy of the file with the `PF_X` flag of the `PT_GNU_STACK`
+  /// segment set or cleared (`execstack -s` or `-c`), nothing else changes.
+  /// Without the segment the stack is executable, but adding one is not
+  /// supported.
+  ///
+  pub fn set_stack_executable(&self, executable: bool) -> Result<Vec<u8>, ElfError> {
+    let headers = self.program_headers()?;
+    let (index, segment) = headers
+      .iter()
+      .enumerate()
+      .find(|(_, header)| header.p_type() == PT_GNU_STACK)
+      .ok_or(ElfError::NoStackSegment)?;
+
+    let p_flags = match executable {
+      true => segment.p_flags() | PF_X,
+      false => segment.p_flags() & !PF_X,
+    };
+
+    let mut bytes = self.copy();
+    let entry = bytes.write_pod::<ElfType::ProgramHeader>(self.program_header_offset(index)?)?;
+    entry.set_p_flags(p_flags);
+
+    Ok(bytes)
+  }
+
+  ///
+  /// Returns a copy of the file without the named sections (`objcopy -R`),
+  /// all the sections of a repeated name are removed. Only sections out of
+  /// the memory image (without `SHF_ALLOC`) can be, so that the segments do
+  /// not change.
+  ///
+  /// The program (headers, segments and allocated sections) stays in place,
+  /// the kept sections after it are packed in their order and followed by
+  /// the section header table. The section indices of `e_shstrndx`,
+  /// `sh_link`, `sh_info` (relocation sections and `SHF_INFO_LINK`), the
+  /// symbols and the groups are updated, a reference to a removed section is
+  /// refused. The names of the removed sections are left in `.shstrtab`,
+  /// their bytes inside the program too.
+  ///
+  pub fn remove_sections(&self, names: &[&str]) -> Result<Vec<u8>, ElfError> {
+    let sections = self.section_headers()?;
+    let shstrndx: u64 = self.header.e_shstrndx.into();
+    let name = |index: usize| {
+      self
+        .section_name(sections[index])
+        .unwrap_or_default()
+        .into_owned()
+    };
+    let refuse = |name: String, reason: &str| ElfError::CannotRemoveSection {
+      name,
+      reason: String::from(reason),
+    };
+
+    // The count or the index would be in the first section header.
+    let shnum: u64 = self.header.e_shnum.into();
+    if (shnum == 0 && !sections.is_empty()) || shstrndx == SHN_XINDEX as u64 {
+      let name = names.first().copied().unwrap_or_default();
+      return Err(refuse(
+        String::from(name),
+        "extended section numbering is not supported",
+      ));
+    } else if shnum as usize != sections.len() {
+      let (shoff, shentsize): (u64, u64) = (self.header.e_shoff.into(), self.header.e_shentsize.into());
+      return Err(ElfError::Truncated {
+        needed: to_usize(shoff + shnum * shentsize)?,
+        available: self.data.length(),
+      });
+    }
+
+    let mut removed = vec![false; sections.len()];
+    for &wanted in names {
+      // Raw bytes or printed form, see SectionRef::has_name().
+      let matching = self
+        .sections()
+        .filter(|section| section.index != 0 && section.has_name(wanted))
+        .map(|section| section.index);
+      let mut found = false;
+      for index in matching {
+        if sections[index].sh_flags() & SHF_ALLOC != 0 {
+          return Err(refuse(
+            name(index),
+            "allocated (SHF_ALLOC), the segments would change",
+          ));
+        } else if index as u64 == shstrndx {
+          return Err(refuse(name(index), "it holds the section names (e_shstrndx)"));
+        }
+        removed[index] = true;
+        found = true;
+      }
+      if !found {
+        return Err(refuse(String::from(wanted), "no such section"));
+      }
+    }
+
+    // New indices of the kept sections, references to removed ones fail.
+    let indices = removed
+      .iter()
+      .scan(0, |next, &removed| {
+        *next += !removed as u64;
+        Some(next.saturating_sub(1))
+      })
+      .collect::<Vec<_>>();
+    let remap = |index: u64, by: usize, what: &str| match usize::try_from(index) {
+      Ok(index) if index < sections.len() && removed[index] => Err(refuse(
+        name(index),
+        &format!("{} of {} refers to it", what, name(by)),
+      )),
+      Ok(index) if index < sections.len() => Ok(indices[index]),
+      _ => Ok(index),
+    };
+    let kept = (0..sections.len())
+      .filter(|&index| !removed[index])
+      .collect::<Vec<_>>();
+
+    let mut bytes = self.copy();
+    for &index in &kept {
+      let section = sections[index];
+      let offset = to_usize(section.sh_offset())?;
+      let size = to_usize(section.sh_size())?;
+      match section.sh_type() as u32 {
+        SHT_SYMTAB | SHT_DYNSYM => {
+          let entsize = to_usize(section.sh_entsize())?.max(size_of::<ElfType::Symbol>());
+          for symbol_index in 0..size / entsize {
+            let symbol = bytes.write_pod::<ElfType::Symbol>(offset + symbol_index * entsize)?;
+            let shndx = symbol.st_shndx();
+            if shndx != 0 && shndx < SHN_LORESERVE {
+              let what = format!("symbol {}", symbol_index);
+              symbol.set_st_shndx(remap(shndx as u64, index, &what)? as u16);
+            }
+          }
+        }
+        // Flags then the members, or the section index of each symbol.
+        SHT_GROUP | SHT_SYMTAB_SHNDX => {
+          let first = (section.sh_type() == SHT_GROUP as u64) as usize;
+          for entry in first..size / 4 {
+            let word = bytes.write_pod::<U32<ElfType::Endian>>(offset + entry * 4)?;
+            if word.get() != 0 {
+              word.set(remap(word.get() as u64, index, "an entry")? as u32);
+            }
+          }
+        }
+        _ => {}
+      }
+    }
+
+    // The end of the program, the kept sections starting before stay there.
+    let phoff: u64 = self.header.e_phoff.into();
+    let phentsize: u64 = self.header.e_phentsize.into();
+    let headers = self.program_headers()?;
+    let mut fixed = size_of::<ElfHeader<ElfType>>() as u64;
+    if !headers.is_empty() {
+      fixed = fixed.max(phoff + headers.len() as u64 * phentsize);
+    }
+    for header in &headers {
+      fixed = fixed.max(header.p_offset().saturating_add(header.p_filesz()));
+    }
+    let has_bytes = |index: usize| sections[index].sh_type() != SHT_NOBITS as u64;
+    for index in (1..sections.len()).filter(|&index| has_bytes(index)) {
+      if sections[index].sh_flags() & SHF_ALLOC != 0 {
+        fixed = fixed.max(
+          sections[index]
+            .sh_offset()
+            .saturating_add(sections[index].sh_size()),
+        );
+      }
+    }
+    let mut ordered = kept[1..].to_vec();
+    ordered.sort_by_key(|&index| sections[index].sh_offset());
+    for &index in ordered.iter().filter(|&&index| has_bytes(index)) {
+      if sections[index].sh_offset() < fixed {
+        fixed = fixed.max(
+          sections[index]
+            .sh_offset()
+            .saturating_add(sections[index].sh_size()),
+        );
+      }
+    }
+
+    let fixed = to_usize(fixed)?.min(bytes.len());
+    let mut output = bytes[..fixed].to_vec();
+    let mut offsets = vec![None; sections.len()];
+    for &index in ordered
+      .iter()
+      .filter(|&&index| sections[index].sh_offset() >= fixed as u64)
+    {
+      let section = sections[index];
+      let align = to_usize(section.sh_addralign())?.max(1);
+      output.resize(output.len().next_multiple_of(align), 0);
+      offsets[index] = Some(output.len() as u64);
+      if has_bytes(index) {
+        let (offset, size) = (to_usize(section.sh_offset())?, to_usize(section.sh_size())?);
+        let end = offset.saturating_add(size);
+        let data = bytes.get(offset..end).ok_or(ElfError::Truncated {
+          needed: end,
+          available: bytes.len(),
+        })?;
+        output.extend_from_slice(data);
+      }
+    }
+
+    // The section header table, the entries are copied then updated.
+    let shoff = to_usize(self.header.e_shoff.into())?;
+    let shentsize = to_usize(self.header.e_shentsize.into())?;
+    output.resize(output.len().next_multiple_of(size_of::<ElfType::Addr>()), 0);
+    let table = output.len();
+    for &index in &kept {
+      let section = sections[index];
+      let entry = output.len();
+      output.extend_from_slice(&bytes[shoff + index * shentsize..][..shentsize]);
+
+      if let Some(offset) = offsets[index] {
+        set_field!(&mut output, ElfSection<ElfType>, entry, sh_offset: Off, offset)?;
+      }
+      if section.sh_link() != 0 {
+        let link = remap(section.sh_link(), index, "sh_link")?;
+        set_field!(&mut output, ElfSection<ElfType>, entry, sh_link: Word, link)?;
+      }
+      let relocations = [SHT_REL, SHT_RELA].contains(&(section.sh_type() as u32));
+      if section.sh_info() != 0 && (relocations || section.sh_flags() & SHF_INFO_LINK != 0) {
+        let info = remap(section.sh_info(), index, "sh_info")?;
+        set_field!(&mut output, ElfSection<ElfType>, entry, sh_info: Word, info)?;
+      }
+    }
+
+    let shstrndx = remap(shstrndx, 0, "e_shstrndx")?;
+    let header = HeaderOffsets::of::<ElfType>();
+    write_field::<ElfType::Off, ElfType::Endian>(&mut output, header.e_shoff, table as u64)?;
+    write_field::<ElfType::Half, ElfType::Endian>(&mut output, header.e_shnum, kept.len() as u64)?;
+    write_field::<ElfType::Half, ElfType::Endian>(&mut output, header.e_shstrndx, shstrndx)?;
+
+    Ok(output)
+  }
+
+  /// Returns a copy of the whole file to patch.
+  fn copy(&self) -> Vec<u8> {
+    let bytes = self.data.read_bytes(self.data.length(), 0);
+    bytes.unwrap_or_default().to_vec()
+  }
+
+  /// Returns the offset of the program header at the given index.
+  fn program_header_offset(&self, index: usize) -> Result<usize, ElfError> {
+    let phoff: u64 = self.header.e_phoff.into();
+    let phentsize: u64 = self.header.e_phentsize.into();
+    Ok(to_usize(phoff + index as u64 * phentsize)?)
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::elf::abi::sh_type::SHT_PROGBITS;
+  use crate::elf::builder::ElfBuilder;
+  use crate::error::ElfError;
+
+  fn executable<ElfType: self::ElfType>() -> Vec<u8> {
+    ElfBuilder::<ElfType>::new()
+      .object_type(abi::e_type::ET_EXEC)
+      .section(".interp", SHT_PROGBITS, b"/lib64/ld-linux-x86-64.so.2\0")
+      .segment(PT_INTERP, 4, &[1])
+      .build()
+  }
+
+  #[test]
+  fn set_interpreter() {
+    let bytes = executable::<ElfType64<LittleEndian>>();
+    let ElfFile::Elf64Le(original) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    let patched = original.set_interpreter(b"/opt/ld.so").unwrap();
+
+    let ElfFile::Elf64Le(object) = parse_elf(patched.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(object.interpreter(), Some("/opt/ld.so"));
+    let segment = object.program_headers().unwrap()[0];
+    assert_eq!((segment.p_filesz(), segment.p_memsz()), (11, 11));
+    assert_eq!(object.find_section(".interp").unwrap().sh_size(), 11);
+    assert_eq!(patched.len(), bytes.len());
+
+    // The NUL takes the last byte of the segment.
+    let path = b"/lib64/ld-linux-aarch64.so1";
+    assert!(original.set_interpreter(path).is_ok());
+    assert_eq!(
+      object.set_interpreter(path),
+      Err(ElfError::InterpreterTooLong {
+        length: 28,
+        capacity: 11
+      })
+    );
+  }
+
+  #[test]
+  fn set_interpreter_elf32() {
+    let bytes = executable::<ElfType32<BigEndian>>();
+    let ElfFile::Elf32Be(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    let path = b"/lib/ld-linux-armhf.so.3";
+    let patched = object.set_interpreter(path).unwrap();
+
+    let ElfFile::Elf32Be(object) = parse_elf(patched.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(object.interpreter_bytes(), Some(path.as_slice()));
+    assert_eq!(object.find_section(".interp").unwrap().sh_size(), 25);
+
+    let bytes = ElfBuilder::<ElfType32<BigEndian>>::new().build();
+    let ElfFile::Elf32Be(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(object.set_interpreter(path), Err(ElfError::NoInterpreter));
+  }
+
+  #[test]
+  fn remove_sections() {
+    use crate::elf::abi::sh_type::SHT_STRTAB;
+    use crate::elf::builder::BuilderSection;
+
+    // `main` in .text and the section symbol of .debug_str.
+    let mut symtab = vec![0; 24];
+    symtab.extend([1, 0, 0, 0, 0x12, 0, 2, 0]);
+    symtab.extend(0x1000_u64.to_le_bytes());
+    symtab.extend(4_u64.to_le_bytes());
+    symtab.extend([0, 0, 0, 0, 0x03, 0, 3, 0]);
+    symtab.extend([0; 16]);
+
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .object_type(abi::e_type::ET_EXEC)
+      .section(".comment", SHT_PROGBITS, b"GCC: (GNU) 13.2.1\0")
+      .add_section(BuilderSection {
+        name: String::from(".text"),
+        sh_type: SHT_PROGBITS,
+        sh_flags: 0x6,
+        sh_addr: 0x1000,
+        sh_addralign: 16,
+        data: vec![0xc3; 4],
+        ..Default::default()
+      })
+      .section(".debug_str", SHT_PROGBITS, b"main.c\0")
+      .add_section(BuilderSection {
+        name: String::from(".symtab"),
+        sh_type: SHT_SYMTAB,
+        sh_link: 5,
+        sh_info: 2,
+        sh_addralign: 8,
+        sh_entsize: 24,
+        data: symtab,
+        ..Default::default()
+      })
+      .section(".strtab", SHT_STRTAB, b"\0main\0")
+      .segment(abi::p_type::PT_LOAD, 5, &[2])
+      .build();
+    let ElfFile::Elf64Le(original) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let patched = original.remove_sections(&[".comment"]).unwrap();
+    let ElfFile::Elf64Le(object) = parse_elf(patched.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    let names = object
+      .sections()
+      .map(|section| section.display_name().unwrap_or_default());
+    assert_eq!(
+      names.collect::<Vec<_>>(),
+      ["", ".text", ".debug_str", ".symtab", ".strtab", ".shstrtab"]
+    );
+    assert_eq!(
+      object.section_names().unwrap().get(1),
+      Some(b".comment".as_slice()),
+      "Name left"
+    );
+    assert_eq!(object.find_section(".symtab").unwrap().sh_link(), 4);
+    let shndx = object.symbols().map(|symbol| symbol.unwrap().section_index());
+    assert_eq!(shndx.collect::<Vec<_>>(), [0, 1, 2]);
+    assert_eq!(object.symbols().nth(1).unwrap().unwrap().display_name(), "main");
+    assert_eq!(
+      object.section_bytes(object.find_section(".debug_str").unwrap()),
+      Ok(b"main.c\0".as_slice())
+    );
+
+    // The program is in place.
+    let text = original.find_section(".text").unwrap();
+    assert_eq!(
+      object.find_section(".text").unwrap().sh_offset(),
+      text.sh_offset()
+    );
+    let end = (text.sh_offset() + text.sh_size()) as usize;
+    assert_eq!(patched[64..end], bytes[64..end], "Program headers to .text");
+    assert!(patched.len() < bytes.len());
+
+    let refused = |name: &str| match original.remove_sections(&[name]) {
+      Err(ElfError::CannotRemoveSection { reason, .. }) => reason,
+      result => panic!("{:?}", result),
+    };
+    assert_eq!(
+      refused(".text"),
+      "allocated (SHF_ALLOC), the segments would change"
+    );
+    assert_eq!(refused(".debug_str"), "symbol 2 of .symtab refers to it");
+    assert_eq!(refused(".strtab"), "sh_link of .symtab refers to it");
+    assert_eq!(refused(".shstrtab"), "it holds the section names (e_shstrndx)");
+    assert_eq!(refused(".comment.gnu"), "no such section");
+  }
+
+  #[test]
+  fn set_stack_executable() {
+    let bytes = ElfBuilder::<ElfType64<BigEndian>>::new()
+      .object_type(abi::e_type::ET_EXEC)
+      .section(".text", SHT_PROGBITS, &[0xc3; 16])
+      .segment(PT_GNU_STACK, 6, &[])
+      .segment(abi::p_type::PT_LOAD, 5, &[1])
+      .build();
+    let ElfFile::Elf64Be(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(object.stack_executable(), Some(false));
+
+    // Only the 4 bytes of `p_flags` differ (`PF_X` is in the last one).
+    let patched = object.set_stack_executable(true).unwrap();
+    let p_flags = object.program_header_offset(0).unwrap() + 4;
+    let differing = (0..bytes.len()).filter(|&offset| bytes[offset] != patched[offset]);
+    assert_eq!(differing.collect::<Vec<_>>(), [p_flags + 3]);
+
+    let ElfFile::Elf64Be(object) = parse_elf(patched.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(object.stack_executable(), Some(true));
+    assert_eq!(object.set_stack_executable(false).unwrap(), bytes);
+    assert_eq!(object.set_stack_executable(true).unwrap(), patched, "Already set");
+
+    let bytes = executable::<ElfType32<LittleEndian>>();
+    let ElfFile::Elf32Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(object.set_stack_executable(false), Err(ElfError::NoStackSegment));
+  }
+}
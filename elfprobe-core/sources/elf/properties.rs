@@ -0,0 +1,268 @@
+use ::core::fmt;
+use ::core::mem::size_of;
+
+use super::abi::e_machine::*;
+use super::notes::read_unsigned;
+use super::*;
+use crate::reader::Reader;
+use crate::utils::{define_constants, define_flags};
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+pub use super::notes::nt_gnu::NT_GNU_PROPERTY_TYPE_0;
+
+/// x86 features (bitwise AND of all the input objects).
+pub const GNU_PROPERTY_X86_FEATURE_1_AND: u32 = 0xc0000002;
+
+/// AArch64 features (bitwise AND of all the input objects).
+pub const GNU_PROPERTY_AARCH64_FEATURE_1_AND: u32 = 0xc0000000;
+
+define_constants! {
+  /// Program property types, processor-specific ones depend on `e_machine`.
+  pub gnu_property_type: u32 {
+    GNU_PROPERTY_STACK_SIZE = 1 => "Stack size",
+    GNU_PROPERTY_NO_COPY_ON_PROTECTED = 2 => "No copy relocation on protected data symbol",
+    [GNU_PROPERTY_UINT32_AND_LO .. GNU_PROPERTY_UINT32_AND_HI] = 0xb0000000 .. 0xb0007fff => "Bitwise AND",
+    [GNU_PROPERTY_UINT32_OR_LO .. GNU_PROPERTY_UINT32_OR_HI] = 0xb0008000 .. 0xb000ffff => "Bitwise OR",
+    [GNU_PROPERTY_LOPROC .. GNU_PROPERTY_HIPROC] = 0xc0000000 .. 0xdfffffff => "Processor-specific",
+    [GNU_PROPERTY_LOUSER .. GNU_PROPERTY_HIUSER] = 0xe0000000 .. 0xffffffff => "Application-specific",
+  }
+}
+
+define_flags! {
+  /// Bits of `GNU_PROPERTY_X86_FEATURE_1_AND`.
+  pub gnu_property_x86_feature_1: u32 {
+    GNU_PROPERTY_X86_FEATURE_1_IBT = 0x1 => "IBT",
+    GNU_PROPERTY_X86_FEATURE_1_SHSTK = 0x2 => "SHSTK",
+    GNU_PROPERTY_X86_FEATURE_1_LAM_U48 = 0x4 => "LAM_U48",
+    GNU_PROPERTY_X86_FEATURE_1_LAM_U57 = 0x8 => "LAM_U57",
+  }
+}
+
+define_flags! {
+  /// Bits of `GNU_PROPERTY_AARCH64_FEATURE_1_AND`.
+  pub gnu_property_aarch64_feature_1: u32 {
+    GNU_PROPERTY_AARCH64_FEATURE_1_BTI = 0x1 => "BTI",
+    GNU_PROPERTY_AARCH64_FEATURE_1_PAC = 0x2 => "PAC",
+    GNU_PROPERTY_AARCH64_FEATURE_1_GCS = 0x4 => "GCS",
+  }
+}
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+///
+/// A program property of a `NT_GNU_PROPERTY_TYPE_0` note (`.note.gnu.property`),
+/// the properties not decoded keep their raw data.
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GnuProperty<'data> {
+  StackSize(u64),
+  NoCopyOnProtected,
+  X86Feature1(gnu_property_x86_feature_1::Flags),
+  Aarch64Feature1(gnu_property_aarch64_feature_1::Flags),
+  Other { pr_type: u32, data: &'data [u8] },
+}
+
+impl GnuProperty<'_> {
+  /// Returns true for the x86 Indirect Branch Tracking (CET) marker.
+  pub fn is_ibt(&self) -> bool {
+    use gnu_property_x86_feature_1::GNU_PROPERTY_X86_FEATURE_1_IBT as IBT;
+    matches!(self, Self::X86Feature1(flags) if flags.0 & IBT != 0)
+  }
+
+  /// Returns true for the x86 Shadow Stack (CET) marker.
+  pub fn is_shstk(&self) -> bool {
+    use gnu_property_x86_feature_1::GNU_PROPERTY_X86_FEATURE_1_SHSTK as SHSTK;
+    matches!(self, Self::X86Feature1(flags) if flags.0 & SHSTK != 0)
+  }
+}
+
+///
+/// Splits the descriptor of a `NT_GNU_PROPERTY_TYPE_0` note into properties:
+/// `pr_type` and `pr_datasz` (both `Word`) followed by the data, padded to 8
+/// bytes in elf64 and 4 bytes in elf32. Processor-specific properties are
+/// decoded according to `machine` (`e_machine`).
+///
+/// A truncated property stops the parsing, the properties read so far are
+/// kept.
+///
+pub fn parse_properties<ElfType: self::ElfType>(desc: &[u8], machine: u16) -> Vec<GnuProperty<'_>> {
+  let align = size_of::<ElfType::Addr>();
+  let mut properties = Vec::new();
+  let mut offset = 0;
+
+  while let (Some(pr_type), Some(datasz)) = (
+    read_unsigned::<ElfType::Endian>(desc, offset, 4),
+    read_unsigned::<ElfType::Endian>(desc, offset + 4, 4),
+  ) {
+    let Some(data) = desc
+      .get(offset + 8..)
+      .and_then(|data| data.get(..datasz as usize))
+    else {
+      break;
+    };
+
+    let word = read_unsigned::<ElfType::Endian>(data, 0, 4).filter(|_| data.len() == 4);
+    let property = match (pr_type as u32, machine, word) {
+      (gnu_property_type::GNU_PROPERTY_STACK_SIZE, _, _) => {
+        match read_unsigned::<ElfType::Endian>(data, 0, data.len()) {
+          Some(size) => GnuProperty::StackSize(size),
+          None => GnuProperty::Other {
+            pr_type: pr_type as u32,
+            data,
+          },
+        }
+      }
+      (gnu_property_type::GNU_PROPERTY_NO_COPY_ON_PROTECTED, _, _) => GnuProperty::NoCopyOnProtected,
+      (GNU_PROPERTY_X86_FEATURE_1_AND, EM_386 | EM_X86_64, Some(word)) => {
+        GnuProperty::X86Feature1(gnu_property_x86_feature_1::from(word as u32))
+      }
+      (GNU_PROPERTY_AARCH64_FEATURE_1_AND, EM_AARCH64, Some(word)) => {
+        GnuProperty::Aarch64Feature1(gnu_property_aarch64_feature_1::from(word as u32))
+      }
+      (pr_type, _, _) => GnuProperty::Other { pr_type, data },
+    };
+
+    properties.push(property);
+    offset += 8 + (datasz as usize).next_multiple_of(align);
+  }
+
+  properties
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  /// Returns the properties of all the `NT_GNU_PROPERTY_TYPE_0` notes.
+  pub fn gnu_properties(&self) -> Vec<GnuProperty<'data>> {
+    let machine: u64 = self.header().e_machine.into();
+    let notes = self.notes().unwrap_or_default();
+    notes
+      .iter()
+      .filter(|note| note.name == b"GNU" && note.n_type == NT_GNU_PROPERTY_TYPE_0)
+      .flat_map(|note| parse_properties::<ElfType>(note.desc, machine as u16))
+      .collect()
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+/// Writes the meaning of each flag set, unknown bits in hexadecimal.
+fn write_flags(formatter: &mut fmt::Formatter<'_>, flags: &[(&str, u32, &str)], value: u32) -> fmt::Result {
+  let mut names = Vec::new();
+  let mut unknown = value;
+  for &(_, flag, meaning) in flags {
+    if value & flag == flag {
+      names.push(meaning.to_string());
+      unknown &= !flag;
+    }
+  }
+
+  if unknown != 0 {
+    names.push(format!("{:#x}", unknown));
+  }
+
+  match names.is_empty() {
+    true => write!(formatter, "<None>"),
+    false => write!(formatter, "{}", names.join(", ")),
+  }
+}
+
+impl fmt::Display for GnuProperty<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::StackSize(size) => write!(formatter, "stack size: {:#x}", size),
+      Self::NoCopyOnProtected => write!(formatter, "no copy on protected"),
+      Self::X86Feature1(flags) => {
+        write!(formatter, "x86 feature: ")?;
+        write_flags(formatter, gnu_property_x86_feature_1::FLAGS, flags.0)
+      }
+      Self::Aarch64Feature1(flags) => {
+        write!(formatter, "AArch64 feature: ")?;
+        write_flags(formatter, gnu_property_aarch64_feature_1::FLAGS, flags.0)
+      }
+      Self::Other { pr_type, data } => {
+        write!(formatter, "{}:", gnu_property_type::from(*pr_type))?;
+        data
+          .iter()
+          .try_for_each(|byte| write!(formatter, " {:02x}", byte))
+      }
+    }
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+
+  #[test]
+  fn parse_properties() {
+    let bytes = hex(
+      r"
+        020000C0 04000000 03000000 00000000 ; X86_FEATURE_1_AND (IBT, SHSTK), padded to 8
+        028000C0 04000000 01000000 00000000 ; X86_ISA_1_NEEDED (not decoded)
+        01000000 08000000 0000100000000000  ; STACK_SIZE
+        020000C0 04000000 03                ; Truncated
+      ",
+    )
+    .unwrap();
+
+    let properties = super::parse_properties::<ElfType64<LittleEndian>>(&bytes, EM_X86_64);
+    assert_eq!(properties.len(), 3);
+    assert!(properties[0].is_ibt() && properties[0].is_shstk());
+    assert_eq!(properties[0].to_string(), "x86 feature: IBT, SHSTK");
+    assert_eq!(
+      properties[1].to_string(),
+      "GNU_PROPERTY_LOPROC+0x8002: 01 00 00 00"
+    );
+    assert_eq!(properties[2], GnuProperty::StackSize(0x100000));
+  }
+
+  #[test]
+  fn parse_properties_elf32() {
+    let bytes = hex(
+      r"
+        C0000002 00000004 00000001 ; X86_FEATURE_1_AND (IBT), padded to 4
+        00000002 00000000          ; NO_COPY_ON_PROTECTED
+      ",
+    )
+    .unwrap();
+
+    let properties = super::parse_properties::<ElfType32<BigEndian>>(&bytes, EM_386);
+    assert_eq!(
+      properties,
+      vec![
+        GnuProperty::X86Feature1(gnu_property_x86_feature_1::from(1)),
+        GnuProperty::NoCopyOnProtected,
+      ]
+    );
+  }
+
+  #[test]
+  fn aarch64() {
+    let bytes = hex("000000C0 04000000 07000000 00000000").unwrap();
+    let properties = super::parse_properties::<ElfType64<LittleEndian>>(&bytes, EM_AARCH64);
+    assert_eq!(properties[0].to_string(), "AArch64 feature: BTI, PAC, GCS");
+    assert!(!properties[0].is_ibt());
+
+    let properties = super::parse_properties::<ElfType64<LittleEndian>>(&bytes, EM_X86_64);
+    assert_eq!(properties[0].to_string(), "GNU_PROPERTY_LOPROC: 07 00 00 00");
+  }
+}
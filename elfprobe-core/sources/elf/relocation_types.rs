@@ -0,0 +1,317 @@
+use super::abi::e_machine::*;
+use crate::utils::{define_constants, Constant};
+
+// ═╗ ╦╔═╗╔═╗
+// ╔╩╦╝╠═╣╠═╝
+// ╩ ╚═╩ ╩╩  x86
+
+define_constants! {
+  /// AMD x86-64 relocation types.
+  pub r_x86_64: u32 {
+    R_X86_64_NONE = 0 => "No reloc",
+    R_X86_64_64 = 1 => "Direct 64 bit",
+    R_X86_64_PC32 = 2 => "PC relative 32 bit signed",
+    R_X86_64_GOT32 = 3 => "32 bit GOT entry",
+    R_X86_64_PLT32 = 4 => "32 bit PLT address",
+    R_X86_64_COPY = 5 => "Copy symbol at runtime",
+    R_X86_64_GLOB_DAT = 6 => "Create GOT entry",
+    R_X86_64_JUMP_SLOT = 7 => "Create PLT entry",
+    R_X86_64_RELATIVE = 8 => "Adjust by program base",
+    R_X86_64_GOTPCREL = 9 => "32 bit signed PC relative offset to GOT",
+    R_X86_64_32 = 10 => "Direct 32 bit zero extended",
+    R_X86_64_32S = 11 => "Direct 32 bit sign extended",
+    R_X86_64_16 = 12 => "Direct 16 bit zero extended",
+    R_X86_64_PC16 = 13 => "16 bit sign extended pc relative",
+    R_X86_64_8 = 14 => "Direct 8 bit sign extended",
+    R_X86_64_PC8 = 15 => "8 bit sign extended pc relative",
+    R_X86_64_DTPMOD64 = 16 => "ID of module containing symbol",
+    R_X86_64_DTPOFF64 = 17 => "Offset in module's TLS block",
+    R_X86_64_TPOFF64 = 18 => "Offset in initial TLS block",
+    R_X86_64_TLSGD = 19 => "32 bit signed PC relative offset to two GOT entries for GD symbol",
+    R_X86_64_TLSLD = 20 => "32 bit signed PC relative offset to two GOT entries for LD symbol",
+    R_X86_64_DTPOFF32 = 21 => "Offset in TLS block",
+    R_X86_64_GOTTPOFF = 22 => "32 bit signed PC relative offset to GOT entry for IE symbol",
+    R_X86_64_TPOFF32 = 23 => "Offset in initial TLS block",
+    R_X86_64_PC64 = 24 => "PC relative 64 bit",
+    R_X86_64_GOTOFF64 = 25 => "64 bit offset to GOT",
+    R_X86_64_GOTPC32 = 26 => "32 bit signed pc relative offset to GOT",
+    R_X86_64_GOT64 = 27 => "64-bit GOT entry offset",
+    R_X86_64_GOTPCREL64 = 28 => "64-bit PC relative offset to GOT entry",
+    R_X86_64_GOTPC64 = 29 => "64-bit PC relative offset to GOT",
+    R_X86_64_GOTPLT64 = 30 => "Like GOT64, says PLT entry needed",
+    R_X86_64_PLTOFF64 = 31 => "64-bit GOT relative offset to PLT entry",
+    R_X86_64_SIZE32 = 32 => "Size of symbol plus 32-bit addend",
+    R_X86_64_SIZE64 = 33 => "Size of symbol plus 64-bit addend",
+    R_X86_64_GOTPC32_TLSDESC = 34 => "GOT offset for TLS descriptor",
+    R_X86_64_TLSDESC_CALL = 35 => "Marker for call through TLS descriptor",
+    R_X86_64_TLSDESC = 36 => "TLS descriptor",
+    R_X86_64_IRELATIVE = 37 => "Adjust indirectly by program base",
+    R_X86_64_RELATIVE64 = 38 => "64-bit adjust by program base",
+    R_X86_64_GOTPCRELX = 41 => "Load from 32 bit signed pc relative offset to GOT entry without REX prefix, relaxable",
+    R_X86_64_REX_GOTPCRELX = 42 => "Load from 32 bit signed pc relative offset to GOT entry with REX prefix, relaxable",
+  }
+}
+
+define_constants! {
+  /// Intel 80386 relocation types.
+  pub r_386: u32 {
+    R_386_NONE = 0 => "No reloc",
+    R_386_32 = 1 => "Direct 32 bit",
+    R_386_PC32 = 2 => "PC relative 32 bit",
+    R_386_GOT32 = 3 => "32 bit GOT entry",
+    R_386_PLT32 = 4 => "32 bit PLT address",
+    R_386_COPY = 5 => "Copy symbol at runtime",
+    R_386_GLOB_DAT = 6 => "Create GOT entry",
+    R_386_JMP_SLOT = 7 => "Create PLT entry",
+    R_386_RELATIVE = 8 => "Adjust by program base",
+    R_386_GOTOFF = 9 => "32 bit offset to GOT",
+    R_386_GOTPC = 10 => "32 bit PC relative offset to GOT",
+    R_386_TLS_TPOFF = 14 => "Offset in static TLS block",
+    R_386_TLS_IE = 15 => "Address of GOT entry for static TLS block offset",
+    R_386_TLS_GOTIE = 16 => "GOT entry for static TLS block offset",
+    R_386_TLS_LE = 17 => "Offset relative to static TLS block",
+    R_386_TLS_GD = 18 => "Direct 32 bit for GNU version of general dynamic thread local data",
+    R_386_TLS_LDM = 19 => "Direct 32 bit for GNU version of local dynamic thread local data in LE code",
+    R_386_16 = 20 => "Direct 16 bit",
+    R_386_PC16 = 21 => "PC relative 16 bit",
+    R_386_8 = 22 => "Direct 8 bit",
+    R_386_PC8 = 23 => "PC relative 8 bit",
+    R_386_TLS_DTPMOD32 = 35 => "ID of module containing symbol",
+    R_386_TLS_DTPOFF32 = 36 => "Offset in TLS block",
+    R_386_TLS_TPOFF32 = 37 => "Negated offset in static TLS block",
+    R_386_SIZE32 = 38 => "32-bit symbol size",
+    R_386_TLS_GOTDESC = 39 => "GOT offset for TLS descriptor",
+    R_386_TLS_DESC_CALL = 40 => "Marker of call through TLS descriptor for relaxation",
+    R_386_TLS_DESC = 41 => "TLS descriptor containing pointer to code and to argument",
+    R_386_IRELATIVE = 42 => "Adjust indirectly by program base",
+    R_386_GOT32X = 43 => "Load from 32 bit GOT entry, relaxable",
+  }
+}
+
+// ╔═╗╔═╗╦═╗╔═╗╦ ╦╔═╗╦ ╦
+// ╠═╣╠═╣╠╦╝║  ╠═╣╠═╝╚═╣
+// ╩ ╩╩ ╩╩╚═╚═╝╩ ╩╩    ╩ ARM
+
+define_constants! {
+  /// AArch64 relocation types (common subset).
+  pub r_aarch64: u32 {
+    R_AARCH64_NONE = 0 => "No relocation",
+    R_AARCH64_ABS64 = 257 => "Direct 64 bit",
+    R_AARCH64_ABS32 = 258 => "Direct 32 bit",
+    R_AARCH64_ABS16 = 259 => "Direct 16-bit",
+    R_AARCH64_PREL64 = 260 => "PC-relative 64-bit",
+    R_AARCH64_PREL32 = 261 => "PC-relative 32-bit",
+    R_AARCH64_PREL16 = 262 => "PC-relative 16-bit",
+    R_AARCH64_MOVW_UABS_G0 = 263 => "Dir. MOVZ imm. from bits 15:0",
+    R_AARCH64_MOVW_UABS_G0_NC = 264 => "Likewise for MOVK; no check",
+    R_AARCH64_MOVW_UABS_G1 = 265 => "Dir. MOVZ imm. from bits 31:16",
+    R_AARCH64_MOVW_UABS_G1_NC = 266 => "Likewise for MOVK; no check",
+    R_AARCH64_MOVW_UABS_G2 = 267 => "Dir. MOVZ imm. from bits 47:32",
+    R_AARCH64_MOVW_UABS_G2_NC = 268 => "Likewise for MOVK; no check",
+    R_AARCH64_MOVW_UABS_G3 = 269 => "Dir. MOV{K,Z} imm. from 63:48",
+    R_AARCH64_LD_PREL_LO19 = 273 => "PC-rel. LD imm. from bits 20:2",
+    R_AARCH64_ADR_PREL_LO21 = 274 => "PC-rel. ADR imm. from bits 20:0",
+    R_AARCH64_ADR_PREL_PG_HI21 = 275 => "Page-rel. ADRP imm. from 32:12",
+    R_AARCH64_ADR_PREL_PG_HI21_NC = 276 => "Likewise; no overflow check",
+    R_AARCH64_ADD_ABS_LO12_NC = 277 => "Dir. ADD imm. from bits 11:0",
+    R_AARCH64_LDST8_ABS_LO12_NC = 278 => "Likewise for LD/ST; no check",
+    R_AARCH64_TSTBR14 = 279 => "PC-rel. TBZ/TBNZ imm. from 15:2",
+    R_AARCH64_CONDBR19 = 280 => "PC-rel. cond. br. imm. from 20:2",
+    R_AARCH64_JUMP26 = 282 => "PC-rel. B imm. from bits 27:2",
+    R_AARCH64_CALL26 = 283 => "Likewise for CALL",
+    R_AARCH64_LDST16_ABS_LO12_NC = 284 => "Dir. ADD imm. from bits 11:1",
+    R_AARCH64_LDST32_ABS_LO12_NC = 285 => "Likewise for bits 11:2",
+    R_AARCH64_LDST64_ABS_LO12_NC = 286 => "Likewise for bits 11:3",
+    R_AARCH64_LDST128_ABS_LO12_NC = 299 => "Likewise for bits 11:4",
+    R_AARCH64_ADR_GOT_PAGE = 311 => "P-page-rel. GOT off. ADRP 32:12",
+    R_AARCH64_LD64_GOT_LO12_NC = 312 => "Dir. GOT off. LD/ST imm. 11:3",
+    R_AARCH64_TLSIE_ADR_GOTTPREL_PAGE21 = 541 => "Page-rel. ADRP imm. 32:12",
+    R_AARCH64_TLSIE_LD64_GOTTPREL_LO12_NC = 542 => "Direct LD off. 11:3",
+    R_AARCH64_TLSLE_ADD_TPREL_HI12 = 549 => "TP-rel. ADD imm. 23:12",
+    R_AARCH64_TLSLE_ADD_TPREL_LO12_NC = 551 => "Likewise; no ovfl. check",
+    R_AARCH64_TLSDESC_ADR_PAGE21 = 562 => "Page-rel. ADRP imm. 32:12",
+    R_AARCH64_TLSDESC_LD64_LO12 = 563 => "Direct LD off. from 11:3",
+    R_AARCH64_TLSDESC_ADD_LO12 = 564 => "Direct ADD imm. from 11:0",
+    R_AARCH64_TLSDESC_CALL = 569 => "Relax BLR",
+    R_AARCH64_COPY = 1024 => "Copy symbol at runtime",
+    R_AARCH64_GLOB_DAT = 1025 => "Create GOT entry",
+    R_AARCH64_JUMP_SLOT = 1026 => "Create PLT entry",
+    R_AARCH64_RELATIVE = 1027 => "Adjust by program base",
+    R_AARCH64_TLS_DTPMOD = 1028 => "Module number, 64 bit",
+    R_AARCH64_TLS_DTPREL = 1029 => "Module-relative offset, 64 bit",
+    R_AARCH64_TLS_TPREL = 1030 => "TP-relative offset, 64 bit",
+    R_AARCH64_TLSDESC = 1031 => "TLS Descriptor",
+    R_AARCH64_IRELATIVE = 1032 => "STT_GNU_IFUNC relocation",
+  }
+}
+
+define_constants! {
+  /// ARM relocation types (common subset).
+  pub r_arm: u32 {
+    R_ARM_NONE = 0 => "No reloc",
+    R_ARM_PC24 = 1 => "Deprecated PC relative 26 bit branch",
+    R_ARM_ABS32 = 2 => "Direct 32 bit",
+    R_ARM_REL32 = 3 => "PC relative 32 bit",
+    R_ARM_ABS16 = 5 => "Direct 16 bit",
+    R_ARM_ABS12 = 6 => "Direct 12 bit",
+    R_ARM_THM_ABS5 = 7 => "Direct & 0x7C (LDR, STR)",
+    R_ARM_ABS8 = 8 => "Direct 8 bit",
+    R_ARM_THM_CALL = 10 => "PC relative 24 bit (Thumb32 BL)",
+    R_ARM_TLS_DTPMOD32 = 17 => "ID of module containing symbol",
+    R_ARM_TLS_DTPOFF32 = 18 => "Offset in TLS block",
+    R_ARM_TLS_TPOFF32 = 19 => "Offset in static TLS block",
+    R_ARM_COPY = 20 => "Copy symbol at runtime",
+    R_ARM_GLOB_DAT = 21 => "Create GOT entry",
+    R_ARM_JUMP_SLOT = 22 => "Create PLT entry",
+    R_ARM_RELATIVE = 23 => "Adjust by program base",
+    R_ARM_GOTOFF = 24 => "32 bit offset to GOT",
+    R_ARM_GOTPC = 25 => "32 bit PC relative offset to GOT",
+    R_ARM_GOT32 = 26 => "32 bit GOT entry",
+    R_ARM_PLT32 = 27 => "Deprecated, 32 bit PLT address",
+    R_ARM_CALL = 28 => "PC relative 24 bit (BL, BLX)",
+    R_ARM_JUMP24 = 29 => "PC relative 24 bit (B, BL<cond>)",
+    R_ARM_THM_JUMP24 = 30 => "PC relative 24 bit (Thumb32 B.W)",
+    R_ARM_BASE_ABS = 31 => "Adjust by program base",
+    R_ARM_TARGET1 = 38 => "Static or dynamic data",
+    R_ARM_V4BX = 40 => "Marker for BX instructions",
+    R_ARM_TARGET2 = 41 => "Platform-specific data",
+    R_ARM_PREL31 = 42 => "32 bit PC relative",
+    R_ARM_MOVW_ABS_NC = 43 => "Direct 16-bit (MOVW)",
+    R_ARM_MOVT_ABS = 44 => "Direct high 16-bit (MOVT)",
+    R_ARM_MOVW_PREL_NC = 45 => "PC relative 16-bit (MOVW)",
+    R_ARM_MOVT_PREL = 46 => "PC relative (MOVT)",
+    R_ARM_THM_MOVW_ABS_NC = 47 => "Direct 16 bit (Thumb32 MOVW)",
+    R_ARM_THM_MOVT_ABS = 48 => "Direct high 16 bit (Thumb32 MOVT)",
+    R_ARM_GOT_BREL = 96 => "GOT entry relative to GOT origin (LDR)",
+    R_ARM_TLS_GD32 = 104 => "PC-rel 32 bit for global dynamic thread local data",
+    R_ARM_TLS_LDM32 = 105 => "PC-rel 32 bit for local dynamic thread local data",
+    R_ARM_TLS_LDO32 = 106 => "32 bit offset relative to TLS block",
+    R_ARM_TLS_IE32 = 107 => "PC-rel 32 bit for GOT entry of static TLS block offset",
+    R_ARM_TLS_LE32 = 108 => "32 bit offset relative to static TLS block",
+    R_ARM_IRELATIVE = 160 => "Adjust indirectly by program base",
+  }
+}
+
+// ╦═╗╦╔═╗╔═╗   ╦  ╦
+// ╠╦╝║╚═╗║  ───╚╗╔╝
+// ╩╚═╩╚═╝╚═╝    ╚╝
+
+define_constants! {
+  /// RISC-V relocation types.
+  pub r_riscv: u32 {
+    R_RISCV_NONE = 0 => "No reloc",
+    R_RISCV_32 = 1 => "Direct 32 bit",
+    R_RISCV_64 = 2 => "Direct 64 bit",
+    R_RISCV_RELATIVE = 3 => "Adjust by program base",
+    R_RISCV_COPY = 4 => "Copy symbol at runtime",
+    R_RISCV_JUMP_SLOT = 5 => "Create PLT entry",
+    R_RISCV_TLS_DTPMOD32 = 6 => "ID of module containing symbol (32 bit)",
+    R_RISCV_TLS_DTPMOD64 = 7 => "ID of module containing symbol (64 bit)",
+    R_RISCV_TLS_DTPREL32 = 8 => "Offset in TLS block (32 bit)",
+    R_RISCV_TLS_DTPREL64 = 9 => "Offset in TLS block (64 bit)",
+    R_RISCV_TLS_TPREL32 = 10 => "Offset in static TLS block (32 bit)",
+    R_RISCV_TLS_TPREL64 = 11 => "Offset in static TLS block (64 bit)",
+    R_RISCV_TLSDESC = 12 => "TLS descriptor",
+    R_RISCV_BRANCH = 16 => "PC-relative branch (SB-Type)",
+    R_RISCV_JAL = 17 => "PC-relative jump (UJ-Type)",
+    R_RISCV_CALL = 18 => "PC-relative call (AUIPC+JALR)",
+    R_RISCV_CALL_PLT = 19 => "PC-relative call through the PLT",
+    R_RISCV_GOT_HI20 = 20 => "PC-relative GOT reference",
+    R_RISCV_TLS_GOT_HI20 = 21 => "PC-relative TLS IE GOT offset",
+    R_RISCV_TLS_GD_HI20 = 22 => "PC-relative TLS GD reference",
+    R_RISCV_PCREL_HI20 = 23 => "PC-relative reference (U-Type)",
+    R_RISCV_PCREL_LO12_I = 24 => "PC-relative reference (I-Type)",
+    R_RISCV_PCREL_LO12_S = 25 => "PC-relative reference (S-Type)",
+    R_RISCV_HI20 = 26 => "Absolute address (U-Type)",
+    R_RISCV_LO12_I = 27 => "Absolute address (I-Type)",
+    R_RISCV_LO12_S = 28 => "Absolute address (S-Type)",
+    R_RISCV_TPREL_HI20 = 29 => "TLS LE thread offset (U-Type)",
+    R_RISCV_TPREL_LO12_I = 30 => "TLS LE thread offset (I-Type)",
+    R_RISCV_TPREL_LO12_S = 31 => "TLS LE thread offset (S-Type)",
+    R_RISCV_TPREL_ADD = 32 => "TLS LE thread usage",
+    R_RISCV_ADD8 = 33 => "8-bit label addition",
+    R_RISCV_ADD16 = 34 => "16-bit label addition",
+    R_RISCV_ADD32 = 35 => "32-bit label addition",
+    R_RISCV_ADD64 = 36 => "64-bit label addition",
+    R_RISCV_SUB8 = 37 => "8-bit label subtraction",
+    R_RISCV_SUB16 = 38 => "16-bit label subtraction",
+    R_RISCV_SUB32 = 39 => "32-bit label subtraction",
+    R_RISCV_SUB64 = 40 => "64-bit label subtraction",
+    R_RISCV_ALIGN = 43 => "Alignment statement",
+    R_RISCV_RVC_BRANCH = 44 => "PC-relative branch offset (CB-Type)",
+    R_RISCV_RVC_JUMP = 45 => "PC-relative jump offset (CJ-Type)",
+    R_RISCV_RELAX = 51 => "Instruction pair can be relaxed",
+    R_RISCV_SUB6 = 52 => "Local label subtraction",
+    R_RISCV_SET6 = 53 => "Local label assignment",
+    R_RISCV_SET8 = 54 => "Local label assignment",
+    R_RISCV_SET16 = 55 => "Local label assignment",
+    R_RISCV_SET32 = 56 => "Local label assignment",
+    R_RISCV_32_PCREL = 57 => "32-bit PC relative",
+    R_RISCV_IRELATIVE = 58 => "Relocation against a non-preemptible ifunc symbol",
+    R_RISCV_PLT32 = 59 => "32-bit relative offset to a function or its PLT entry",
+    R_RISCV_SET_ULEB128 = 60 => "Local label assignment (ULEB128)",
+    R_RISCV_SUB_ULEB128 = 61 => "Local label subtraction (ULEB128)",
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┌─┐┌┬┐┌─┐┬ ┬┌─┐┬─┐
+//  ║║│└─┐├─┘├─┤ │ │  ├─┤├┤ ├┬┘
+// ═╩╝┴└─┘┴  ┴ ┴ ┴ └─┘┴ ┴└─┘┴└─
+
+/// Lookup function of a relocation types table (see [`define_constants!`]).
+type RelocationTable = fn(u32) -> Constant<u32>;
+
+///
+/// Relocation type tables by machine (`e_machine`), a new machine only needs
+/// its table to be registered here.
+///
+const RELOCATION_TABLES: &[(u16, RelocationTable)] = &[
+  (EM_386, r_386::from),
+  (EM_X86_64, r_x86_64::from),
+  (EM_ARM, r_arm::from),
+  (EM_AARCH64, r_aarch64::from),
+  (EM_RISCV, r_riscv::from),
+];
+
+///
+/// Returns the relocation type of the given machine, displayed in
+/// hexadecimal when the machine or the type is unknown.
+///
+pub fn relocation_name(machine: u16, r_type: u32) -> Constant<u32> {
+  RELOCATION_TABLES
+    .iter()
+    .find(|(table_machine, _)| *table_machine == machine)
+    .map_or(Constant::unknown(r_type), |(_, from)| from(r_type))
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn relocation_name() {
+    let name = |machine, r_type| super::relocation_name(machine, r_type).to_string();
+
+    assert_eq!(name(EM_X86_64, 7), "R_X86_64_JUMP_SLOT");
+    assert_eq!(name(EM_386, 7), "R_386_JMP_SLOT");
+    assert_eq!(name(EM_AARCH64, 1027), "R_AARCH64_RELATIVE");
+    assert_eq!(name(EM_ARM, 2), "R_ARM_ABS32");
+    assert_eq!(name(EM_RISCV, 19), "R_RISCV_CALL_PLT");
+    assert_eq!(name(EM_X86_64, 0x100), "0x100", "Unknown type");
+    assert_eq!(name(EM_SPARC, 7), "0x7", "Unknown machine");
+  }
+
+  #[test]
+  fn machines() {
+    for (index, (machine, _)) in RELOCATION_TABLES.iter().enumerate() {
+      let duplicate = RELOCATION_TABLES[..index]
+        .iter()
+        .any(|(other, _)| other == machine);
+      assert!(!duplicate, "Machine {} registered twice", machine);
+    }
+  }
+}
@@ -0,0 +1,771 @@
+use ::core::fmt;
+use ::core::mem::size_of;
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+
+use super::abi::e_machine::*;
+use super::abi::sh_type::{SHT_REL, SHT_RELA, SHT_RELR};
+use super::relocation_types::{r_386::*, r_aarch64::*, r_arm::*, r_riscv::*, r_x86_64::*};
+use super::strings::printable;
+use super::*;
+use crate::reader::Reader;
+use elfprobe_macro::Pod;
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+pub use super::dynamic::d_tag::{
+  DT_JMPREL, DT_PLTREL, DT_PLTRELSZ, DT_REL, DT_RELA, DT_RELACOUNT, DT_RELCOUNT, DT_SYMENT, DT_SYMTAB,
+};
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+///
+/// Relocation entry without addend (`.rel.*`), the same layout in elf32 and
+/// elf64 (but `r_info` is not split the same way, see [`r_info()`]).
+///
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Pod)]
+pub struct ElfRel<ElfType: self::ElfType> {
+  pub r_offset: ElfType::Addr,
+  pub r_info: ElfType::Xword,
+}
+
+/// Relocation entry with addend (`.rela.*`).
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Pod)]
+pub struct ElfRela<ElfType: self::ElfType> {
+  pub r_offset: ElfType::Addr,
+  pub r_info: ElfType::Xword,
+  pub r_addend: ElfType::Sxword,
+}
+
+///
+/// A PLT relocation (`.rela.plt` or `.rel.plt`) joined with the imported
+/// symbol: `got` is the address of the GOT slot patched by the dynamic
+/// loader.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PltRelocation<'data> {
+  pub got: u64,
+  pub r_type: u32,
+  pub addend: Option<i64>,
+  pub symbol: Option<Cow<'data, str>>,
+}
+
+///
+/// A relocation entry of a relocation section, whatever its kind (see
+/// [`ElfObject::relocations()`]). `symbol` is the index in the symbol table
+/// linked to the section, `addend` is only stored in `SHT_RELA` sections.
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Relocation {
+  pub offset: u64,
+  pub r_type: u32,
+  pub symbol: u32,
+  pub addend: Option<i64>,
+}
+
+///
+/// What a relocation patches, for the statistics: relative relocations add
+/// the load address (PIE, shared objects), GOT and PLT relocations fill or
+/// reference the tables of the imported symbols, absolute relocations store
+/// the address of a symbol.
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RelocationKind {
+  Relative,
+  Got,
+  Plt,
+  Absolute,
+  Other,
+}
+
+///
+/// Splits `r_info` into the symbol index and the relocation type, elf32
+/// uses 24 and 8 bits, elf64 uses 32 and 32 bits.
+///
+pub fn r_info<ElfType: self::ElfType>(r_info: u64) -> (u32, u32) {
+  match size_of::<ElfType::Xword>() {
+    8 => ((r_info >> 32) as u32, r_info as u32),
+    _ => ((r_info >> 8) as u32, (r_info & 0xff) as u32),
+  }
+}
+
+///
+/// Returns the name of the PLT relocation types of the given machine
+/// (`JUMP_SLOT` or `IRELATIVE`), `None` for other types and machines.
+///
+pub fn plt_relocation_name(machine: u16, r_type: u32) -> Option<&'static str> {
+  match (machine, r_type) {
+    (EM_X86_64, 7) | (EM_386, 7) | (EM_AARCH64, 1026) | (EM_ARM, 22) | (EM_RISCV, 5) => Some("JUMP_SLOT"),
+    (EM_X86_64, 37) | (EM_386, 42) | (EM_AARCH64, 1032) | (EM_ARM, 160) | (EM_RISCV, 58) => Some("IRELATIVE"),
+    _ => None,
+  }
+}
+
+///
+/// Returns what the relocation type of the given machine patches, `Other`
+/// for the TLS, PC-relative, copy... types and the unknown machines.
+///
+pub fn relocation_kind(machine: u16, r_type: u32) -> RelocationKind {
+  use RelocationKind::*;
+
+  match (machine, r_type) {
+    (EM_X86_64, R_X86_64_RELATIVE | R_X86_64_RELATIVE64)
+    | (EM_386, R_386_RELATIVE)
+    | (EM_AARCH64, R_AARCH64_RELATIVE)
+    | (EM_ARM, R_ARM_RELATIVE)
+    | (EM_RISCV, R_RISCV_RELATIVE) => Relative,
+
+    (
+      EM_X86_64,
+      R_X86_64_GLOB_DAT
+      | R_X86_64_GOT32
+      | R_X86_64_GOTPCREL
+      | R_X86_64_GOT64
+      | R_X86_64_GOTPCREL64
+      | R_X86_64_GOTPLT64
+      | R_X86_64_GOTPCRELX
+      | R_X86_64_REX_GOTPCRELX,
+    )
+    | (EM_386, R_386_GLOB_DAT | R_386_GOT32 | R_386_GOT32X)
+    | (EM_AARCH64, R_AARCH64_GLOB_DAT | R_AARCH64_ADR_GOT_PAGE | R_AARCH64_LD64_GOT_LO12_NC)
+    | (EM_ARM, R_ARM_GLOB_DAT | R_ARM_GOT32 | R_ARM_GOT_BREL)
+    | (EM_RISCV, R_RISCV_GOT_HI20) => Got,
+
+    (EM_X86_64, R_X86_64_JUMP_SLOT | R_X86_64_PLT32 | R_X86_64_PLTOFF64)
+    | (EM_386, R_386_JMP_SLOT | R_386_PLT32)
+    | (EM_AARCH64, R_AARCH64_JUMP_SLOT)
+    | (EM_ARM, R_ARM_JUMP_SLOT | R_ARM_PLT32)
+    | (EM_RISCV, R_RISCV_JUMP_SLOT | R_RISCV_CALL_PLT | R_RISCV_PLT32) => Plt,
+
+    (EM_X86_64, R_X86_64_64 | R_X86_64_32 | R_X86_64_32S | R_X86_64_16 | R_X86_64_8)
+    | (EM_386, R_386_32 | R_386_16 | R_386_8)
+    | (EM_AARCH64, R_AARCH64_ABS64 | R_AARCH64_ABS32 | R_AARCH64_ABS16)
+    | (EM_ARM, R_ARM_ABS32 | R_ARM_ABS16 | R_ARM_ABS8)
+    | (EM_RISCV, R_RISCV_32 | R_RISCV_64) => Absolute,
+
+    _ => Other,
+  }
+}
+
+///
+/// Returns the relative relocation type of the given machine, which the
+/// entries of the `SHT_RELR` sections stand for (`None` when unknown).
+///
+pub fn relative_type(machine: u16) -> Option<u32> {
+  match machine {
+    EM_X86_64 => Some(R_X86_64_RELATIVE),
+    EM_386 => Some(R_386_RELATIVE),
+    EM_AARCH64 => Some(R_AARCH64_RELATIVE),
+    EM_ARM => Some(R_ARM_RELATIVE),
+    EM_RISCV => Some(R_RISCV_RELATIVE),
+    _ => None,
+  }
+}
+
+impl fmt::Display for RelocationKind {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    formatter.pad(match self {
+      Self::Relative => "relative",
+      Self::Got => "GOT",
+      Self::Plt => "PLT",
+      Self::Absolute => "absolute",
+      Self::Other => "other",
+    })
+  }
+}
+
+// ╔═╗┌┬┐┌─┐┌┬┐┬┌─┐┌┬┐┬┌─┐┌─┐
+// ╚═╗ │ ├─┤ │ │└─┐ │ ││  └─┐
+// ╚═╝ ┴ ┴ ┴ ┴ ┴└─┘ ┴ ┴└─┘└─┘
+
+///
+/// Relocation counts of an object, per type and per kind (see
+/// [`ElfObject::relocation_stats()`]).
+///
+/// The relative relocations of the `SHT_REL` and `SHT_RELA` sections should
+/// match `DT_RELCOUNT` / `DT_RELACOUNT` (`relative_count`), the ones packed
+/// in the `SHT_RELR` sections (`relr`) have no such count.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RelocationStats {
+  pub machine: u16,
+  pub total: u64,
+  /// Count per relocation type, the most frequent first.
+  pub types: Vec<(u32, u64)>,
+  /// Count per kind, the most frequent first (empty ones omitted).
+  pub kinds: Vec<(RelocationKind, u64)>,
+  pub relr: u64,
+  /// Sum of `DT_RELACOUNT` and `DT_RELCOUNT`, `None` without these tags.
+  pub relative_count: Option<u64>,
+}
+
+impl RelocationStats {
+  /// Returns the number of relative relocations, packed ones included.
+  pub fn relative(&self) -> u64 {
+    self
+      .kinds
+      .iter()
+      .find(|(kind, _)| *kind == RelocationKind::Relative)
+      .map_or(0, |(_, count)| *count)
+  }
+}
+
+impl fmt::Display for RelocationStats {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use crate::utils::display_table;
+
+    let percent = |count: u64| format!("{:.1}%", count as f64 * 100.0 / self.total.max(1) as f64);
+
+    writeln!(formatter, "Relocations: {}", self.total)?;
+    if self.total == 0 {
+      return Ok(());
+    }
+
+    writeln!(formatter)?;
+    display_table!(
+      formatter,
+      align [Left, Right, Right],
+      ["Type", "Count", "Share"],
+      &(r_type, count) in &self.types => [relocation_name(self.machine, r_type), count, percent(count)]
+    )?;
+
+    writeln!(formatter)?;
+    display_table!(
+      formatter,
+      align [Left, Right, Right],
+      ["Kind", "Count", "Share"],
+      &(kind, count) in &self.kinds => [kind, count, percent(count)]
+    )?;
+
+    let relative = self.relative();
+    writeln!(formatter)?;
+    write!(formatter, "Relative: {}", relative)?;
+    if self.relr != 0 {
+      write!(formatter, " ({} packed in SHT_RELR)", self.relr)?;
+    }
+    match self.relative_count {
+      Some(count) if count == relative.saturating_sub(self.relr) => {
+        writeln!(formatter, ", DT_REL[A]COUNT: {} (matching)", count)
+      }
+      Some(count) => writeln!(formatter, ", DT_REL[A]COUNT: {} (mismatch)", count),
+      None => writeln!(formatter),
+    }
+  }
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the PLT relocations (`DT_JMPREL`, `DT_PLTRELSZ` and `DT_PLTREL`)
+  /// with the name of their symbol in the dynamic symbol table (`DT_SYMTAB`
+  /// and `DT_STRTAB`). Only the dynamic table is used, so that it also works
+  /// without section headers.
+  ///
+  pub fn plt_relocations(&self) -> Vec<PltRelocation<'data>> {
+    let entries = self.dynamic().unwrap_or_default();
+    let find = |tag| {
+      entries
+        .iter()
+        .find(|entry| entry.d_tag() == tag)
+        .map(|entry| entry.d_val())
+    };
+
+    let (Some(jmprel), Some(size)) = (find(DT_JMPREL), find(DT_PLTRELSZ)) else {
+      return Vec::new();
+    };
+
+    let Some(offset) = self.vaddr_to_offset(jmprel) else {
+      return Vec::new();
+    };
+
+    let strings = self.dynamic_strings(&entries).unwrap_or_default();
+    let symtab = find(DT_SYMTAB).and_then(|address| self.vaddr_to_offset(address));
+    let syment = find(DT_SYMENT).unwrap_or(size_of::<ElfType::Symbol>() as u64);
+
+    let symbol = |index: u32| {
+      let offset = (index as u64).checked_mul(syment)?.checked_add(symtab?)?;
+      let symbol = self
+        .data
+        .read_pod::<ElfType::Symbol>(usize::try_from(offset).ok()?)
+        .ok()?;
+      strings.get(symbol.st_name() as usize).map(printable)
+    };
+
+    let is_rela = find(DT_PLTREL) == Some(DT_RELA as u64);
+    let entsize = match is_rela {
+      true => size_of::<ElfRela<ElfType>>(),
+      false => size_of::<ElfRel<ElfType>>(),
+    } as u64;
+
+    // Past the limits, the table is as unreadable as a missing one.
+    let Ok(count) = self.bounded_count("PLT relocation table", size / entsize, entsize as usize) else {
+      return Vec::new();
+    };
+    let mut relocations = Vec::new();
+    for index in 0..count {
+      let Some(offset) = (index * entsize)
+        .checked_add(offset)
+        .and_then(|offset| usize::try_from(offset).ok())
+      else {
+        break;
+      };
+
+      let (r_offset, info, addend) = match is_rela {
+        true => match self.data.read_pod::<ElfRela<ElfType>>(offset) {
+          Ok(rela) => (
+            rela.r_offset.into(),
+            rela.r_info.into(),
+            Some(rela.r_addend.into()),
+          ),
+          Err(_) => break,
+        },
+        false => match self.data.read_pod::<ElfRel<ElfType>>(offset) {
+          Ok(rel) => (rel.r_offset.into(), rel.r_info.into(), None),
+          Err(_) => break,
+        },
+      };
+
+      let (sym, r_type) = r_info::<ElfType>(info);
+      relocations.push(PltRelocation {
+        got: r_offset,
+        r_type,
+        addend,
+        symbol: (sym != 0).then(|| symbol(sym)).flatten(),
+      });
+    }
+
+    relocations
+  }
+
+  ///
+  /// Returns an iterator over the entries of the relocation sections
+  /// (`SHT_REL`, `SHT_RELA` and `SHT_RELR`), in the order of the section
+  /// header table. Entries are read on the fly, so that counting the tens of
+  /// millions of relocations of a large binary takes no memory. The
+  /// iteration of a section stops at its first entry out of the file.
+  ///
+  /// `SHT_RELR` entries are expanded to one relative relocation per address
+  /// (see [`relative_type()`]), without symbol nor addend. They are skipped
+  /// with a warning on the machines without known relative type.
+  ///
+  pub fn relocations(&self) -> impl Iterator<Item = Relocation> + use<'_, 'data, Reader, ElfType> {
+    self
+      .sections()
+      .flat_map(move |section| self.section_relocations(section.header))
+  }
+
+  ///
+  /// Returns an iterator over the entries of the given section, empty when
+  /// it is not a relocation section (see [`Self::relocations()`]).
+  ///
+  pub fn section_relocations(
+    &self,
+    header: &ElfSection<ElfType>,
+  ) -> Box<dyn Iterator<Item = Relocation> + '_> {
+    match header.sh_type() as u32 {
+      SHT_REL => Box::new(self.rel_relocations(header, false)),
+      SHT_RELA => Box::new(self.rel_relocations(header, true)),
+      SHT_RELR => Box::new(self.relr_relocations(header)),
+      _ => Box::new(::core::iter::empty()),
+    }
+  }
+
+  /// Entries of a `SHT_REL` or `SHT_RELA` section, see [`Self::relocations()`].
+  fn rel_relocations(
+    &self,
+    header: &ElfSection<ElfType>,
+    is_rela: bool,
+  ) -> impl Iterator<Item = Relocation> + use<'_, 'data, Reader, ElfType> {
+    let entsize = match is_rela {
+      true => size_of::<ElfRela<ElfType>>(),
+      false => size_of::<ElfRel<ElfType>>(),
+    } as u64;
+    let offset = header.sh_offset();
+
+    (0..self.limited_count(header.sh_size() / entsize)).map_while(move |index| {
+      let offset = usize::try_from(offset.checked_add(index * entsize)?).ok()?;
+      let (r_offset, info, addend) = match is_rela {
+        true => {
+          let rela = self.data.read_pod::<ElfRela<ElfType>>(offset).ok()?;
+          (
+            rela.r_offset.into(),
+            rela.r_info.into(),
+            Some(rela.r_addend.into()),
+          )
+        }
+        false => {
+          let rel = self.data.read_pod::<ElfRel<ElfType>>(offset).ok()?;
+          (rel.r_offset.into(), rel.r_info.into(), None)
+        }
+      };
+
+      let (symbol, r_type) = r_info::<ElfType>(info);
+      Some(Relocation {
+        offset: r_offset,
+        r_type,
+        symbol,
+        addend,
+      })
+    })
+  }
+
+  ///
+  /// Entries of a `SHT_RELR` section: an even entry is the address of a
+  /// relocation, an odd entry is a bitmap of the relocations following the
+  /// last address (one bit per word, the least significant bit excluded).
+  ///
+  fn relr_relocations(
+    &self,
+    header: &ElfSection<ElfType>,
+  ) -> impl Iterator<Item = Relocation> + use<'_, 'data, Reader, ElfType> {
+    let word = size_of::<ElfType::Addr>() as u64;
+    let bits = word * 8 - 1;
+    let machine = self.header.e_machine.into() as u16;
+    let offset = header.sh_offset();
+
+    // Skipped rather than labelled R_*_NONE.
+    let (r_type, count) = match relative_type(machine) {
+      Some(r_type) => (r_type, header.sh_size() / word),
+      None => {
+        if header.sh_size() != 0 {
+          self.diagnostics.warn(ElfError::UnknownRelativeType { machine });
+        }
+        (0, 0)
+      }
+    };
+
+    let entries = (0..count).map_while(move |index| {
+      let offset = usize::try_from(offset.checked_add(index * word)?).ok()?;
+      let entry: u64 = (*self.data.read_pod::<ElfType::Addr>(offset).ok()?).into();
+      Some(entry)
+    });
+
+    // The next address of a bitmap is tracked from one entry to the next.
+    entries
+      .scan(0u64, move |next, entry| {
+        let (start, bitmap, count) = match entry & 1 {
+          0 => (entry, 1, 1),
+          _ => (*next, entry >> 1, bits),
+        };
+        *next = start.wrapping_add(count * word);
+        Some(
+          (0..count)
+            .filter(move |bit| bitmap >> bit & 1 != 0)
+            .map(move |bit| Relocation {
+              offset: start.wrapping_add(bit * word),
+              r_type,
+              symbol: 0,
+              addend: None,
+            }),
+        )
+      })
+      .flatten()
+  }
+
+  ///
+  /// Counts the relocations per type and per kind while iterating over them
+  /// (see [`Self::relocations()`]), `DT_RELACOUNT` and `DT_RELCOUNT` are read
+  /// from the dynamic table to be compared with the relative relocations.
+  ///
+  pub fn relocation_stats(&self) -> RelocationStats {
+    let machine = self.header.e_machine.into() as u16;
+    let mut stats = RelocationStats {
+      machine,
+      ..Default::default()
+    };
+
+    let mut types = BTreeMap::<u32, u64>::new();
+    let mut kinds = BTreeMap::<RelocationKind, u64>::new();
+    for relocation in self.relocations() {
+      stats.total += 1;
+      *types.entry(relocation.r_type).or_default() += 1;
+      *kinds
+        .entry(relocation_kind(machine, relocation.r_type))
+        .or_default() += 1;
+    }
+
+    // Packed relocations are counted apart, being compact they are cheap to
+    // read twice.
+    stats.relr = self
+      .sections()
+      .filter(|section| section.header.sh_type() == SHT_RELR as u64)
+      .map(|section| self.relr_relocations(section.header).count() as u64)
+      .sum();
+
+    // Stable sorts, the ties stay in the order of the types (and kinds).
+    let mut types: Vec<_> = types.into_iter().collect();
+    types.sort_by(|(_, left), (_, right)| right.cmp(left));
+    stats.types = types;
+
+    let mut kinds: Vec<_> = kinds.into_iter().collect();
+    kinds.sort_by(|(_, left), (_, right)| right.cmp(left));
+    stats.kinds = kinds;
+
+    let entries = self.dynamic().unwrap_or_default();
+    stats.relative_count = entries
+      .iter()
+      .filter(|entry| matches!(entry.d_tag(), DT_RELACOUNT | DT_RELCOUNT))
+      .map(|entry| entry.d_val())
+      .reduce(u64::saturating_add);
+
+    stats
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+
+  #[test]
+  fn relocation_memory_size() {
+    assert_eq!(size_of::<ElfRel<ElfType32<LittleEndian>>>(), 8);
+    assert_eq!(size_of::<ElfRel<ElfType64<LittleEndian>>>(), 16);
+    assert_eq!(size_of::<ElfRela<ElfType32<BigEndian>>>(), 12);
+    assert_eq!(size_of::<ElfRela<ElfType64<BigEndian>>>(), 24);
+  }
+
+  #[test]
+  fn r_info() {
+    assert_eq!(super::r_info::<ElfType32<LittleEndian>>(0x0302), (3, 2));
+    assert_eq!(
+      super::r_info::<ElfType64<LittleEndian>>(0x0000000300000007),
+      (3, 7)
+    );
+  }
+
+  #[test]
+  fn plt_relocations() {
+    #[rustfmt::skip]
+    let bytes = hex(
+      r"
+        7F 'ELF 02 01 01 00 00 000000 00000000 ; ELFCLASS64, ELFDATA2LSB
+        0200 3E00 01000000 0000000000000000 ; ET_EXEC, EM_X86_64, e_version, e_entry
+        4000000000000000 0000000000000000 00000000 ; e_phoff, e_shoff (none), e_flags
+        4000 3800 0200 4000 0000 0000 ; e_ehsize, e_phentsize, e_phnum, e_shentsize, e_shnum, e_shstrndx
+
+        ; PT_LOAD (whole file at 0x400000), PT_DYNAMIC
+        01000000 05000000 0000000000000000 0000400000000000 0000400000000000
+        8C01000000000000 8C01000000000000 0010000000000000
+        02000000 06000000 B000000000000000 B000400000000000 B000400000000000
+        7000000000000000 7000000000000000 0800000000000000
+
+        ; Dynamic table
+        1700000000000000 2001400000000000 ; DT_JMPREL
+        0200000000000000 3000000000000000 ; DT_PLTRELSZ
+        1400000000000000 0700000000000000 ; DT_PLTREL = DT_RELA
+        0600000000000000 5001400000000000 ; DT_SYMTAB
+        0B00000000000000 1800000000000000 ; DT_SYMENT
+        0500000000000000 8001400000000000 ; DT_STRTAB
+        0A00000000000000 0C00000000000000 ; DT_STRSZ
+
+        ; .rela.plt
+        1840400000000000 0700000001000000 0000000000000000 ; JUMP_SLOT puts
+        2040400000000000 2500000000000000 9011400000000000 ; IRELATIVE
+
+        ; .dynsym (null, puts)
+        00000000 00 00 0000 0000000000000000 0000000000000000
+        01000000 12 00 0000 0000000000000000 0000000000000000
+
+        00 'puts 00 'libc 00 00 ; .dynstr
+      ",
+    )
+    .unwrap();
+
+    let Ok(ElfFile::Elf64Le(object)) = parse_elf(bytes.as_slice()) else {
+      panic!("Not an elf64 little-endian");
+    };
+
+    assert_eq!(
+      object.plt_relocations(),
+      vec![
+        PltRelocation {
+          got: 0x404018,
+          r_type: 7,
+          addend: Some(0),
+          symbol: Some("puts".into()),
+        },
+        PltRelocation {
+          got: 0x404020,
+          r_type: 37,
+          addend: Some(0x401190),
+          symbol: None,
+        },
+      ]
+    );
+
+    assert_eq!(plt_relocation_name(EM_X86_64, 7), Some("JUMP_SLOT"));
+    assert_eq!(plt_relocation_name(EM_X86_64, 37), Some("IRELATIVE"));
+    assert_eq!(plt_relocation_name(EM_X86_64, 1), None);
+  }
+
+  ///
+  /// ELF64 little-endian shared object with `.rela.dyn` (2 relative, a
+  /// `GLOB_DAT` and a `64`), `.rela.plt` (a `JUMP_SLOT`), `.relr.dyn` (3
+  /// packed relative) and `DT_RELACOUNT`.
+  ///
+  fn stats_fixture() -> Vec<u8> {
+    use crate::elf::abi::sh_type::SHT_DYNAMIC;
+    use crate::elf::builder::ElfBuilder;
+    use crate::elf::segments::PT_DYNAMIC;
+
+    let rela = |r_offset: u64, symbol: u64, r_type: u64| {
+      [r_offset, symbol << 32 | r_type, 0]
+        .into_iter()
+        .flat_map(u64::to_le_bytes)
+    };
+    let words = |words: &[u64]| {
+      words
+        .iter()
+        .flat_map(|word| word.to_le_bytes())
+        .collect::<Vec<_>>()
+    };
+
+    let rela_dyn: Vec<u8> = [
+      rela(0x3000, 0, 8).collect::<Vec<_>>(), // R_X86_64_RELATIVE
+      rela(0x3008, 0, 8).collect(),
+      rela(0x3010, 1, 6).collect(), // R_X86_64_GLOB_DAT
+      rela(0x3018, 2, 1).collect(), // R_X86_64_64
+    ]
+    .concat();
+    let rela_plt: Vec<u8> = rela(0x4000, 3, 7).collect(); // R_X86_64_JUMP_SLOT
+
+    // 0x5000, then 0x5008 and 0x5018 (bits 0 and 2 of the bitmap).
+    let relr_dyn = words(&[0x5000, 0b1010 | 1]);
+    let dynamic = words(&[0x6ffffff9, 2, 0, 0]); // DT_RELACOUNT, DT_NULL
+
+    ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .object_type(3)
+      .section(".rela.dyn", SHT_RELA, &rela_dyn)
+      .section(".rela.plt", SHT_RELA, &rela_plt)
+      .section(".relr.dyn", SHT_RELR, &relr_dyn)
+      .section(".dynamic", SHT_DYNAMIC, &dynamic)
+      .segment(PT_DYNAMIC, 6, &[4])
+      .build()
+  }
+
+  #[test]
+  fn relocation_stats() {
+    let bytes = stats_fixture();
+
+    let Ok(ElfFile::Elf64Le(object)) = parse_elf(bytes.as_slice()) else {
+      panic!("Not an elf64 little-endian");
+    };
+
+    let offsets: Vec<_> = object.relocations().map(|relocation| relocation.offset).collect();
+    assert_eq!(
+      offsets,
+      [0x3000, 0x3008, 0x3010, 0x3018, 0x4000, 0x5000, 0x5008, 0x5018]
+    );
+    assert_eq!(
+      object.relocations().nth(2),
+      Some(Relocation {
+        offset: 0x3010,
+        r_type: 6,
+        symbol: 1,
+        addend: Some(0),
+      })
+    );
+
+    let stats = object.relocation_stats();
+    assert_eq!(stats.total, 8);
+    assert_eq!(
+      stats.types,
+      [(8, 5), (1, 1), (6, 1), (7, 1)],
+      "Most frequent first"
+    );
+    assert_eq!(
+      stats.kinds,
+      [
+        (RelocationKind::Relative, 5),
+        (RelocationKind::Got, 1),
+        (RelocationKind::Plt, 1),
+        (RelocationKind::Absolute, 1),
+      ]
+    );
+    assert_eq!((stats.relr, stats.relative_count), (3, Some(2)));
+
+    let text = stats.to_string();
+    assert!(text.starts_with("Relocations: 8\n"), "{}", text);
+    assert!(text.contains("R_X86_64_RELATIVE       5  62.5%\n"), "{}", text);
+    assert!(
+      text.ends_with("Relative: 5 (3 packed in SHT_RELR), DT_REL[A]COUNT: 2 (matching)\n"),
+      "{}",
+      text
+    );
+  }
+
+  #[test]
+  fn relr_unknown_machine() {
+    let mut bytes = stats_fixture();
+    bytes[0x12..0x14].copy_from_slice(&EM_SPARC.to_le_bytes()); // e_machine
+
+    let Ok(ElfFile::Elf64Le(object)) = parse_elf(bytes.as_slice()) else {
+      panic!("Not an elf64 little-endian");
+    };
+    let offsets: Vec<_> = object.relocations().map(|relocation| relocation.offset).collect();
+    assert_eq!(
+      offsets,
+      [0x3000, 0x3008, 0x3010, 0x3018, 0x4000],
+      "Not R_SPARC_NONE"
+    );
+    assert_eq!(object.relocation_stats().relr, 0);
+    assert_eq!(
+      object.diagnostics().take(),
+      [ElfError::UnknownRelativeType { machine: EM_SPARC }]
+    );
+  }
+
+  #[test]
+  fn deterministic_output() {
+    // Two runs over the same files print the same bytes.
+    let run = || {
+      [stats_fixture(), stats_fixture()]
+        .iter()
+        .map(|bytes| {
+          let Ok(ElfFile::Elf64Le(object)) = parse_elf(bytes.as_slice()) else {
+            panic!("Not an elf64 little-endian");
+          };
+          format!("{}{}", object.relocation_stats(), object.layout())
+        })
+        .collect::<String>()
+    };
+    assert_eq!(run(), run());
+  }
+
+  #[test]
+  fn relocation_kind() {
+    assert_eq!(super::relocation_kind(EM_AARCH64, 1027), RelocationKind::Relative);
+    assert_eq!(super::relocation_kind(EM_386, 43), RelocationKind::Got);
+    assert_eq!(super::relocation_kind(EM_RISCV, 19), RelocationKind::Plt);
+    assert_eq!(super::relocation_kind(EM_ARM, 2), RelocationKind::Absolute);
+    assert_eq!(
+      super::relocation_kind(EM_X86_64, 18),
+      RelocationKind::Other,
+      "TLS"
+    );
+    assert_eq!(
+      super::relocation_kind(EM_SPARC, 8),
+      RelocationKind::Other,
+      "Unknown machine"
+    );
+    assert_eq!(relative_type(EM_SPARC), None);
+  }
+}
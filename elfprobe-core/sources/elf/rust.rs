@@ -0,0 +1,140 @@
+use super::*;
+use crate::reader::Reader;
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+/// Prefix of the `.comment` entry written by rustc.
+const RUSTC_COMMENT: &[u8] = b"rustc version ";
+
+/// Sections of the crate metadata and of the embedded bitcode (rlibs).
+const RUST_SECTIONS: [&str; 2] = [".rustc", ".llvmbc"];
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the version of rustc (`1.81.0`) from the first
+  /// `rustc version 1.81.0 (eeb90cda1 2024-09-04)` entry of `.comment`.
+  ///
+  pub fn rust_version(&self) -> Option<&'data str> {
+    let section = self.find_section(".comment")?;
+    let bytes = self.section_bytes(section).ok()?;
+    let comment = bytes
+      .split(|&byte| byte == 0)
+      .find_map(|comment| comment.strip_prefix(RUSTC_COMMENT))?;
+    let version = comment.split(|&byte| byte == b' ').next()?;
+    ::core::str::from_utf8(version)
+      .ok()
+      .filter(|version| !version.is_empty())
+  }
+
+  ///
+  /// Returns `true` when the object looks built by rustc, from the strongest
+  /// evidence to the weakest: the version in `.comment` (see
+  /// [`Self::rust_version()`]), the `.rustc` or `.llvmbc` sections, then a
+  /// v0-mangled (`_R...`) or `__rust_*` symbol in `.symtab` or `.dynsym`.
+  ///
+  /// C programs linking a Rust static library are reported too.
+  ///
+  pub fn is_rust(&self) -> bool {
+    let is_rust_symbol = |name: &[u8]| match name {
+      [b'_', b'R', next, ..] => next.is_ascii_uppercase() || next.is_ascii_digit(),
+      _ => name.starts_with(b"__rust_"),
+    };
+
+    self.rust_version().is_some()
+      || RUST_SECTIONS.iter().any(|name| self.find_section(name).is_some())
+      || self
+        .symbols()
+        .chain(self.dynamic_symbols())
+        .filter_map(Result::ok)
+        .any(|symbol| is_rust_symbol(symbol.name_bytes()))
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::elf::abi::sh_type::{SHT_PROGBITS, SHT_STRTAB, SHT_SYMTAB};
+  use crate::elf::builder::{BuilderSection, ElfBuilder};
+
+  /// `.symtab` with the null symbol and the given names, then its `.strtab`.
+  fn symbols(names: &[&str]) -> Vec<u8> {
+    let mut strtab = vec![0];
+    let mut symtab = vec![0; 24];
+    for name in names {
+      symtab.extend((strtab.len() as u32).to_le_bytes());
+      symtab.extend([0x12, 0, 1, 0]); // STB_GLOBAL STT_FUNC, section 1.
+      symtab.extend([0; 16]);
+      strtab.extend(name.as_bytes().iter().chain([&0]));
+    }
+
+    ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .add_section(BuilderSection {
+        name: String::from(".symtab"),
+        sh_type: SHT_SYMTAB,
+        sh_link: 2,
+        sh_addralign: 8,
+        sh_entsize: 24,
+        data: symtab,
+        ..Default::default()
+      })
+      .section(".strtab", SHT_STRTAB, &strtab)
+      .build()
+  }
+
+  fn is_rust(bytes: &[u8]) -> (bool, Option<&str>) {
+    let ElfFile::Elf64Le(object) = parse_elf(bytes).unwrap() else {
+      unreachable!();
+    };
+    (object.is_rust(), object.rust_version())
+  }
+
+  #[test]
+  fn rust_comment() {
+    let comment = b"GCC: (Debian 12.2.0-14) 12.2.0\0rustc version 1.81.0 (eeb90cda1 2024-09-04)\0";
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".comment", SHT_PROGBITS, comment)
+      .build();
+    assert_eq!(is_rust(&bytes), (true, Some("1.81.0")));
+
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".comment", SHT_PROGBITS, b"GCC: (GNU) 13.2.1\0")
+      .build();
+    assert_eq!(is_rust(&bytes), (false, None));
+
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".rustc", SHT_PROGBITS, b"rust\0\0\0\x08")
+      .build();
+    assert_eq!(is_rust(&bytes), (true, None));
+  }
+
+  #[test]
+  fn rust_symbols() {
+    assert_eq!(
+      is_rust(&symbols(&["main", "_RNvCs1234_4main4main"])),
+      (true, None)
+    );
+    assert_eq!(is_rust(&symbols(&["__rust_alloc"])), (true, None));
+    assert_eq!(
+      is_rust(&symbols(&[
+        "main",
+        "_Rb",
+        "_ZN4core3fmt5write17h0123456789abcdefE"
+      ])),
+      (false, None)
+    );
+  }
+}
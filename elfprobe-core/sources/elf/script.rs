@@ -0,0 +1,75 @@
+use crate::alloc_prelude::*;
+
+// https://sourceware.org/binutils/docs/ld/File-Commands.html
+
+// ╔═╗┌─┐┬─┐┬┌─┐┌┬┐
+// ╚═╗│  ├┬┘│├─┘ │
+// ╚═╝└─┘┴└─┴┴   ┴
+
+///
+/// Returns whether the data looks like a linker script standing in for a
+/// library (e.g. `/usr/lib/x86_64-linux-gnu/libc.so`): ASCII text starting
+/// with `GROUP`, `INPUT` or a comment.
+///
+pub fn is_linker_script(data: &[u8]) -> bool {
+  let text = data.trim_ascii_start();
+  data.is_ascii()
+    && [&b"GROUP"[..], b"INPUT", b"/*"]
+      .iter()
+      .any(|start| text.starts_with(start))
+}
+
+///
+/// Returns the first file referenced by a `GROUP` or `INPUT` command of the
+/// linker script, those inside `AS_NEEDED` included.
+///
+pub fn linker_script_input(data: &[u8]) -> Option<&str> {
+  let mut text = ::core::str::from_utf8(data).ok()?;
+  let mut tokens = Vec::new();
+
+  // Comments are separators.
+  while !text.is_empty() {
+    let (code, rest) = text.split_once("/*").unwrap_or((text, ""));
+    tokens.extend(code.split(|char: char| char.is_ascii_whitespace() || "(),".contains(char)));
+    text = rest.split_once("*/").map_or("", |(_, rest)| rest);
+  }
+
+  let mut tokens = tokens.into_iter().filter(|token| !token.is_empty());
+  tokens.find(|&token| token == "GROUP" || token == "INPUT")?;
+  tokens.find(|&token| token != "AS_NEEDED")
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn linker_script() {
+    let libc = concat!(
+      "/* GNU ld script\n",
+      "   Use the shared library, but some functions are only in\n",
+      "   the static library, so try that secondarily.  */\n",
+      "OUTPUT_FORMAT(elf64-x86-64)\n",
+      "GROUP ( /lib/x86_64-linux-gnu/libc.so.6 /usr/lib/x86_64-linux-gnu/libc_nonshared.a",
+      "  AS_NEEDED ( /lib64/ld-linux-x86-64.so.2 ) )\n",
+    );
+
+    assert!(is_linker_script(libc.as_bytes()));
+    assert_eq!(
+      linker_script_input(libc.as_bytes()),
+      Some("/lib/x86_64-linux-gnu/libc.so.6")
+    );
+
+    let script = b"\nINPUT(AS_NEEDED(-lfoo)) /* GROUP(bar) */";
+    assert!(is_linker_script(script));
+    assert_eq!(linker_script_input(script), Some("-lfoo"));
+
+    assert!(!is_linker_script(b"\x7fELF"));
+    assert!(!is_linker_script(b"/* \xff */"));
+    assert_eq!(linker_script_input(b"/* GROUP(a) */"), None);
+  }
+}
@@ -0,0 +1,794 @@
+use super::strings::printable;
+use super::*;
+use crate::error::{BytesError, ResultExt};
+use crate::reader::{to_usize, Reader};
+use crate::utils::Entropy;
+use alloc::borrow::Cow;
+use elfprobe_macro::{FieldLayout, Pod};
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+pub use super::abi::sh_flags::{SHF_ALLOC, SHF_COMPRESSED, SHF_EXECINSTR, SHF_TLS};
+pub use super::abi::sh_type::SHT_NOBITS;
+pub use super::abi::st_shndx::{SHN_LORESERVE, SHN_XINDEX};
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+///
+/// Section header, unlike the program header the fields are declared in the
+/// same order in elf32 and elf64 (`Xword` are `Word` in elf32).
+///
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Pod, FieldLayout)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ElfSection<ElfType: self::ElfType> {
+  pub sh_name: ElfType::Word,
+  pub sh_type: ElfType::Word,
+  pub sh_flags: ElfType::Xword,
+  pub sh_addr: ElfType::Addr,
+  pub sh_offset: ElfType::Off,
+  pub sh_size: ElfType::Xword,
+  pub sh_link: ElfType::Word,
+  pub sh_info: ElfType::Word,
+  pub sh_addralign: ElfType::Xword,
+  pub sh_entsize: ElfType::Xword,
+}
+
+macro_rules! impl_section_getters {
+  ($($field: ident: $type: ident,)+) => {
+    impl<ElfType: self::ElfType> ElfSection<ElfType> {
+      $(
+        #[inline]
+        #[doc = concat!("Returns `", stringify!($field), "` widened to `", stringify!($type), "`.")]
+        pub fn $field(&self) -> $type {
+          self.$field.into()
+        }
+      )+
+    }
+  };
+}
+
+impl_section_getters! {
+  sh_name: u64,
+  sh_type: u64,
+  sh_flags: u64,
+  sh_addr: u64,
+  sh_offset: u64,
+  sh_size: u64,
+  sh_link: u64,
+  sh_info: u64,
+  sh_addralign: u64,
+  sh_entsize: u64,
+}
+
+impl<ElfType: self::ElfType> ElfSection<ElfType> {
+  ///
+  /// Returns the section type name without its `SHT_` prefix, as readelf
+  /// (`PROGBITS`, `NOBITS`, `LOPROC+0x3`...), in hexadecimal when unknown.
+  /// OS-specific types are named after the OS ABI, see [`abi::section_type()`].
+  ///
+  pub fn type_name(&self, osabi: u8) -> String {
+    let name = abi::section_type(osabi, self.sh_type() as u32).to_string();
+    match name.strip_prefix("SHT_") {
+      Some(name) => String::from(name),
+      None => name,
+    }
+  }
+}
+
+///
+/// A section header with its index in the section header table (which
+/// `sh_link` and `st_shndx` refer to), the name is only resolved on demand.
+/// See [`ElfObject::sections()`].
+///
+#[derive(Debug, Copy, Clone)]
+pub struct SectionRef<'data, ElfType: self::ElfType> {
+  pub index: usize,
+  pub header: &'data ElfSection<ElfType>,
+  names: StringTable<'data>,
+}
+
+/// Serialized as its index, printable name (JSON strings are UTF-8) and header.
+#[cfg(feature = "serde")]
+impl<ElfType: self::ElfType> serde::Serialize for SectionRef<'_, ElfType>
+where
+  ElfSection<ElfType>: serde::Serialize,
+{
+  fn serialize<Serializer: serde::Serializer>(
+    &self,
+    serializer: Serializer,
+  ) -> Result<Serializer::Ok, Serializer::Error> {
+    use serde::ser::SerializeStruct;
+
+    let mut state = serializer.serialize_struct("Section", 3)?;
+    state.serialize_field("index", &self.index)?;
+    state.serialize_field("name", &self.display_name())?;
+    state.serialize_field("header", self.header)?;
+    state.end()
+  }
+}
+
+impl<'data, ElfType: self::ElfType> SectionRef<'data, ElfType> {
+  /// Returns the raw name, `None` when out of the string table bounds.
+  pub fn name_bytes(&self) -> Option<&'data [u8]> {
+    self.names.get(self.header.sh_name() as usize)
+  }
+
+  /// Returns the printable name, see [`printable()`].
+  pub fn display_name(&self) -> Option<Cow<'data, str>> {
+    self.name_bytes().map(printable)
+  }
+
+  ///
+  /// Returns `true` when the section has the given name, as raw bytes or as
+  /// printed (non UTF-8 names are given back escaped, `.donn\xE9es`).
+  ///
+  pub fn has_name(&self, name: impl AsRef<[u8]>) -> bool {
+    let (name, bytes) = (name.as_ref(), self.name_bytes());
+    bytes == Some(name)
+      || bytes
+        .map(printable)
+        .is_some_and(|escaped| escaped.as_bytes() == name)
+  }
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the section header table (`e_shoff`, `e_shnum` and
+  /// `e_shentsize`).
+  ///
+  /// When the number of sections does not fit in `e_shnum` (greater than or
+  /// equal to `SHN_LORESERVE`), `e_shnum` is zero and the actual number is
+  /// stored in the `sh_size` field of the first section header.
+  ///
+  /// In lenient mode, the headers read before the first one out of the file
+  /// are returned (none when the entry size is impossible).
+  ///
+  pub fn section_headers(&self) -> Result<Vec<&'data ElfSection<ElfType>>, ElfError> {
+    let offset: u64 = self.header.e_shoff.into();
+    let entsize: u64 = self.header.e_shentsize.into();
+    let mut count: u64 = self.header.e_shnum.into();
+
+    if offset == 0 {
+      return Ok(Vec::new());
+    }
+
+    // Larger entries are fine (extensions), smaller ones would overlap.
+    let expected = ::core::mem::size_of::<ElfSection<ElfType>>();
+    if entsize < expected as u64 {
+      let table = "section header table";
+      let error = ElfError::EntrySizeMismatch {
+        table,
+        entsize,
+        expected,
+      };
+      return self.recover(Err(error), Vec::new());
+    }
+
+    // The table up to the end of the entry.
+    let read = |index: u64| {
+      let size = (index + 1).saturating_mul(entsize);
+      let end = offset
+        .checked_add(size)
+        .ok_or(BytesError::RangeOverflow { offset, size })?;
+      self
+        .data
+        .read_pod::<ElfSection<ElfType>>(to_usize(end - entsize)?)
+    };
+
+    let context = || format!("reading the section header table at offset {:#x}", offset);
+
+    if count == 0 {
+      match read(0).with_context(context) {
+        Ok(initial) => count = initial.sh_size(),
+        Err(error) => return self.recover(Err(error), Vec::new()),
+      }
+    }
+    let count = self.bounded_count("section header table", count, expected)?;
+
+    // The entries are read once, unless one is out of the file.
+    let table = self.section_table();
+    if table.len() as u64 == count {
+      return Ok(table.to_vec());
+    }
+
+    let mut headers = Vec::new();
+    let result = (0..count).try_for_each(|index| read(index).map(|header| headers.push(header)));
+    self.recover(result.with_context(context), headers)
+  }
+
+  ///
+  /// Returns the number of sections, `e_shnum` or the `sh_size` of the
+  /// first section header when the number does not fit in `e_shnum`. Zero
+  /// when there is no section header table.
+  ///
+  pub fn section_count(&self) -> u64 {
+    match self.header.e_shnum.into() {
+      0 => self.initial_section().map_or(0, |section| section.sh_size()),
+      count => count,
+    }
+  }
+
+  ///
+  /// Returns an iterator over the section headers, index 0 (the null entry)
+  /// included so that indices match `sh_link` and `st_shndx`. The iteration
+  /// stops at the first header out of the file.
+  ///
+  pub fn sections(&self) -> impl Iterator<Item = SectionRef<'data, ElfType>> + '_ {
+    let names = self.section_names().unwrap_or_default();
+    self
+      .section_table()
+      .iter()
+      .enumerate()
+      .map(move |(index, &header)| SectionRef { index, header, names })
+  }
+
+  ///
+  /// Returns the section headers up to the first one out of the file, read
+  /// on the first call and cached in the object. Unlike
+  /// [`Self::section_headers()`], nothing is checked nor reported.
+  ///
+  fn section_table(&self) -> &[&'data ElfSection<ElfType>] {
+    self.section_table.get_or_init(|| {
+      let offset: u64 = self.header.e_shoff.into();
+      let entsize: u64 = self.header.e_shentsize.into();
+      let count = match offset {
+        0 => 0,
+        _ => self.section_count(),
+      };
+
+      // No more headers than the file holds, whatever the count says.
+      let count = count.min((self.data.length() / ::core::mem::size_of::<ElfSection<ElfType>>()) as u64);
+      let count = self.limited_count(count);
+      (0..count)
+        .map_while(|index| {
+          let offset = index.checked_mul(entsize)?.checked_add(offset)?;
+          self.data.read_pod(usize::try_from(offset).ok()?).ok()
+        })
+        .collect()
+    })
+  }
+
+  ///
+  /// Returns the first section header (the null entry), which holds the
+  /// extended section count, string table index and program header count.
+  /// `None` is returned when there is no section header table.
+  ///
+  pub(crate) fn initial_section(&self) -> Option<&'data ElfSection<ElfType>> {
+    match usize::try_from(self.header.e_shoff.into()) {
+      Ok(0) | Err(_) => None,
+      Ok(offset) => self.data.read_pod::<ElfSection<ElfType>>(offset).ok(),
+    }
+  }
+
+  ///
+  /// Returns the section header string table (`e_shstrndx`). When the index
+  /// does not fit in `e_shstrndx` (`SHN_XINDEX`), the actual index is stored
+  /// in the `sh_link` field of the first section header.
+  ///
+  /// The table is resolved once and cached in the object, errors are not.
+  ///
+  pub fn section_names(&self) -> Result<StringTable<'data>, ElfError> {
+    if let Some(names) = self.names.get() {
+      return Ok(*names);
+    }
+
+    let sections = self.section_headers()?;
+    let mut index: u64 = self.header.e_shstrndx.into();
+
+    if index == SHN_XINDEX as u64 {
+      index = sections.first().map_or(0, |section| section.sh_link());
+    }
+
+    let names = match usize::try_from(index).ok().and_then(|index| sections.get(index)) {
+      Some(section) => StringTable::from(
+        self
+          .section_bytes(section)
+          .with_context(|| format!("reading the section header string table (section {})", index))?,
+      ),
+      None => StringTable::default(),
+    };
+
+    Ok(*self.names.get_or_init(|| names))
+  }
+
+  ///
+  /// Returns the printable name of the given section (see [`printable()`]),
+  /// `None` is returned when the name is out of the section header string
+  /// table bounds.
+  ///
+  pub fn section_name(&self, section: &ElfSection<ElfType>) -> Option<Cow<'data, str>> {
+    let names = self.section_names().ok()?;
+    let bytes = names.get(usize::try_from(section.sh_name()).ok()?)?;
+    Some(printable(bytes))
+  }
+
+  ///
+  /// Returns the first section with the given name, `None` is returned when
+  /// there is no such section (or no section header table). Names are
+  /// compared as raw bytes or as printed, so non UTF-8 names are found with a
+  /// byte string or their escaped form, see [`SectionRef::has_name()`].
+  ///
+  pub fn section_by_name(&self, name: impl AsRef<[u8]>) -> Option<SectionRef<'data, ElfType>> {
+    let name = name.as_ref();
+    self.sections().find(|section| section.has_name(name))
+  }
+
+  ///
+  /// Returns the first allocated section (in table order) whose memory holds
+  /// the given virtual address, `SHT_NOBITS` sections included but `.tbss`
+  /// (its template occupies no memory, the next sections share its address).
+  ///
+  pub fn section_containing(&self, vaddr: u64) -> Option<SectionRef<'data, ElfType>> {
+    self.sections().find(|section| {
+      let header = section.header;
+      let start = header.sh_addr();
+      let tbss = header.sh_type() == SHT_NOBITS as u64 && header.sh_flags() & SHF_TLS != 0;
+      header.sh_flags() & SHF_ALLOC != 0
+        && !tbss
+        && (start..start.saturating_add(header.sh_size())).contains(&vaddr)
+    })
+  }
+
+  /// Same as [`Self::section_by_name()`] but only returns the header.
+  pub(crate) fn find_section(&self, name: &str) -> Option<&'data ElfSection<ElfType>> {
+    self.section_by_name(name).map(|section| section.header)
+  }
+
+  ///
+  /// Returns the entropy of the bytes of the given section as stored in the
+  /// file, `None` for empty and `SHT_NOBITS` sections. Compressed sections
+  /// (`SHF_COMPRESSED`) are expected to be high.
+  ///
+  pub fn section_entropy(&self, section: &ElfSection<ElfType>) -> Option<Entropy> {
+    let bytes = self.section_bytes(section).ok()?;
+    self
+      .data
+      .advise_sequential(section.sh_offset() as usize, bytes.len());
+    (!bytes.is_empty()).then(|| Entropy::new(bytes))
+  }
+
+  ///
+  /// Returns the bytes of the given section as stored in the file (without
+  /// any copy). `SHT_NOBITS` sections occupy no file space and are empty,
+  /// as sections out of the file in lenient mode.
+  ///
+  pub fn section_bytes(&self, section: &ElfSection<ElfType>) -> Result<&'data [u8], ElfError> {
+    if section.sh_type() == SHT_NOBITS as u64 {
+      return Ok(&[]);
+    }
+
+    let offset = to_usize(section.sh_offset())?;
+    let size = to_usize(section.sh_size())?;
+
+    // TODO: Same as Reader::read_pod(), it is not empty.
+    match self.data.read_bytes(size, offset) {
+      Some(bytes) => Ok(bytes),
+      None => {
+        let error = Err(BytesError::Empty);
+        let context = format!("reading {} bytes of section data at offset {:#x}", size, offset);
+        self.recover(error.context(context), &[])
+      }
+    }
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+  use ::core::mem::size_of;
+
+  #[test]
+  fn section_memory_size() {
+    assert_eq!(size_of::<ElfSection<ElfType32<BigEndian>>>(), 40);
+    assert_eq!(size_of::<ElfSection<ElfType64<BigEndian>>>(), 64);
+    assert_eq!(size_of::<ElfSection<ElfType32<LittleEndian>>>(), 40);
+    assert_eq!(size_of::<ElfSection<ElfType64<LittleEndian>>>(), 64);
+  }
+
+  ///
+  /// ELF64 little-endian without program headers and with five sections:
+  /// null, `.text`, `.data`, `.bss` and `.shstrtab`.
+  ///
+  fn fixture() -> Vec<u8> {
+    hex(
+      r"
+        7F 'ELF 02 01 01 00 00 00000000 000000 ; e_ident
+        0100 3E00 01000000 ; e_type, e_machine, e_version
+        0000000000000000 ; e_entry
+        0000000000000000 ; e_phoff
+        7000000000000000 ; e_shoff
+        00000000 4000 ; e_flags, e_ehsize
+        3800 0000 ; e_phentsize, e_phnum
+        4000 0500 0400 ; e_shentsize, e_shnum, e_shstrndx
+
+        00 '.text 00 '.data 00 '.bss 00 '.shstrtab 00 00000000 ; 0x40
+        C3C3C3C3C3C3C3C3 ; 0x60, .text
+        2A00000000000000 ; 0x68, .data
+
+        00*64 ; 0x70, null section
+
+        01000000 01000000 0600000000000000 0000000000000000 ; .text
+        6000000000000000 0800000000000000 00000000 00000000
+        1000000000000000 0000000000000000
+
+        07000000 01000000 0300000000000000 0000000000000000 ; .data
+        6800000000000000 0800000000000000 00000000 00000000
+        0800000000000000 0000000000000000
+
+        0D000000 08000000 0300000000000000 0000000000000000 ; .bss
+        7000000000000000 0001000000000000 00000000 00000000
+        0800000000000000 0000000000000000
+
+        12000000 03000000 0000000000000000 0000000000000000 ; .shstrtab
+        4000000000000000 1C00000000000000 00000000 00000000
+        0100000000000000 0000000000000000
+      ",
+    )
+    .unwrap()
+  }
+
+  fn object(bytes: &[u8]) -> ElfObject<'_, &[u8], ElfType64<LittleEndian>> {
+    let ElfFile::Elf64Le(object) = parse_elf(bytes).unwrap() else {
+      unreachable!();
+    };
+
+    object
+  }
+
+  #[test]
+  fn entry_size_mismatch() {
+    let mut bytes = fixture();
+    bytes[0x3A] = 0x20; // e_shentsize
+    let object = object(&bytes);
+
+    let error = ElfError::EntrySizeMismatch {
+      table: "section header table",
+      entsize: 0x20,
+      expected: 64,
+    };
+    assert_eq!(object.section_headers().err(), Some(error));
+  }
+
+  #[test]
+  fn lenient_section_headers() {
+    let bytes = &fixture()[..0x130]; // Three section headers out of five.
+    assert!(object(bytes).section_headers().is_err());
+
+    let Ok(ElfFile::Elf64Le(object)) = parse_elf_with(bytes, ParseOptions::LENIENT) else {
+      unreachable!();
+    };
+    assert_eq!(object.section_headers().unwrap().len(), 3);
+    assert_eq!(object.section_headers().unwrap().len(), 3);
+
+    // The fifth header cannot fit in the file, the fourth is cut.
+    let warnings = object.diagnostics().take();
+    assert_eq!(warnings.len(), 2, "Recorded once");
+    assert_eq!(
+      warnings[0],
+      ElfError::CountClamped {
+        table: "section header table",
+        count: 5,
+        max: 4
+      }
+    );
+    assert_eq!(
+      warnings[1].to_string(),
+      "reading the section header table at offset 0x70"
+    );
+    assert_eq!(warnings[1].root(), &BytesError::Empty.into());
+  }
+
+  #[test]
+  fn offset_past_address_space() {
+    let mut bytes = fixture();
+    let e_shoff = ELF64_HEADER.e_shoff;
+    bytes[e_shoff..e_shoff + 8].copy_from_slice(&0x1_0000_0070u64.to_le_bytes()); // Past 4 GiB
+    let object = object(&bytes);
+
+    // A clean error on every host, the offset cannot be truncated to 0x70.
+    let error = object.section_headers().unwrap_err();
+    assert_eq!(
+      error.to_string(),
+      "reading the section header table at offset 0x100000070"
+    );
+    #[cfg(target_pointer_width = "32")]
+    assert_eq!(error.root(), &BytesError::OffsetOverflow(0x1_0000_0070).into());
+    #[cfg(target_pointer_width = "64")]
+    assert_eq!(error.root(), &BytesError::Empty.into());
+    assert_eq!(object.sections().count(), 0);
+
+    assert_eq!(
+      BytesError::OffsetOverflow(0x1_0000_0070).to_string(),
+      "offset 0x100000070 exceeds the addressable range"
+    );
+
+    // The end of the first header past 2^64.
+    let offset = u64::MAX - 0x10;
+    bytes[e_shoff..e_shoff + 8].copy_from_slice(&offset.to_le_bytes());
+    let error = self::object(&bytes).section_headers().unwrap_err();
+    assert_eq!(
+      error.root(),
+      &BytesError::RangeOverflow { offset, size: 64 }.into()
+    );
+    assert_eq!(
+      error.root().to_string(),
+      "64 bytes at offset 0xffffffffffffffef overflow 64 bits"
+    );
+  }
+
+  #[test]
+  fn sections() {
+    let bytes = fixture();
+    let object = object(&bytes);
+
+    let sections = object.sections().collect::<Vec<_>>();
+    let names = sections
+      .iter()
+      .map(|section| (section.index, section.display_name().unwrap_or_default()))
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      names,
+      [
+        (0, "".into()),
+        (1, ".text".into()),
+        (2, ".data".into()),
+        (3, ".bss".into()),
+        (4, ".shstrtab".into())
+      ]
+    );
+
+    assert_eq!(sections[3].header.sh_type(), SHT_NOBITS as u64);
+    assert_eq!(
+      object.section_bytes(sections[2].header).unwrap(),
+      b"*\0\0\0\0\0\0\0"
+    );
+    assert_eq!(object.sections().count() as u64, object.section_count());
+  }
+
+  #[test]
+  fn sections_count() {
+    let mut bytes = fixture();
+    bytes[ELF64_HEADER.e_shnum] = 0x00; // Extended
+    bytes[0x70 + 0x20] = 0x05; // sh_size of the null section
+    assert_eq!(object(&bytes).section_count(), 5);
+    assert_eq!(object(&bytes).sections().count(), 5);
+
+    bytes[ELF64_HEADER.e_shnum] = 0x06; // One past the end of the file
+    assert_eq!(object(&bytes).section_count(), 6);
+    assert_eq!(object(&bytes).sections().count(), 5, "Truncated");
+
+    bytes[ELF64_HEADER.e_shoff] = 0x00;
+    assert_eq!(object(&bytes).sections().count(), 0);
+  }
+
+  #[test]
+  fn section_by_name() {
+    let mut bytes = fixture();
+    bytes[0x4e] = 0xff; // .bss renamed .\xFFss
+    let object = object(&bytes);
+
+    assert!(object.names.get().is_none());
+    assert_eq!(
+      object.section_by_name(".data").map(|section| section.index),
+      Some(2)
+    );
+    assert!(object.names.get().is_some(), "Cached");
+
+    assert_eq!(
+      object.section_by_name(b".\xffss").map(|section| section.index),
+      Some(3)
+    );
+    assert!(object.section_by_name(".bss").is_none());
+    assert!(object.section_by_name(".text.hot").is_none());
+
+    // Found as printed too.
+    let section = object.section_by_name(".\\xFFss").unwrap();
+    assert_eq!(section.index, 3);
+    assert_eq!(section.display_name().as_deref(), Some(".\\xFFss"));
+    assert_eq!(object.section_name(section.header).as_deref(), Some(".\\xFFss"));
+  }
+
+  /// ELF64 little-endian with a `.text` section renamed `.donn\xE9es` (Latin-1).
+  fn latin1() -> Vec<u8> {
+    use super::super::builder::ElfBuilder;
+    use abi::sh_type::SHT_PROGBITS;
+
+    let mut bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".donnXes", SHT_PROGBITS, &[0xC3])
+      .build();
+    let at = bytes.windows(8).position(|name| name == b".donnXes").unwrap();
+    bytes[at + 5] = 0xE9;
+    bytes
+  }
+
+  #[test]
+  fn latin1_names() {
+    let bytes = latin1();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let section = object.sections().nth(1).unwrap();
+    assert_eq!(section.name_bytes(), Some(&b".donn\xE9es"[..]));
+    assert_eq!(section.display_name().as_deref(), Some(".donn\\xE9es"));
+    assert_eq!(object.section_to_segment_mapping(), Ok(vec![]));
+
+    for name in [&b".donn\xE9es"[..], b".donn\\xE9es"] {
+      assert_eq!(object.section_by_name(name).map(|section| section.index), Some(1));
+    }
+    assert!(
+      object.section_by_name(".données").is_none(),
+      "Not Latin-1 decoded"
+    );
+    assert!(object.section_by_name(".donn\u{FFFD}es").is_none());
+  }
+
+  #[test]
+  fn escape_sequence_name() {
+    use super::super::builder::ElfBuilder;
+    use crate::utils::{DigestAlgorithm, TableFormat};
+    use abi::sh_type::SHT_PROGBITS;
+
+    // Clears the screen when written to a terminal as is.
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".text\x1B[2J", SHT_PROGBITS, &[0xC3])
+      .build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let section = object.sections().nth(1).unwrap();
+    assert_eq!(section.display_name().as_deref(), Some(".text\\x1B[2J"));
+    assert!(section.has_name(".text\\x1B[2J") && section.has_name(b".text\x1B[2J"));
+
+    for format in [TableFormat::Text, TableFormat::Csv, TableFormat::Tsv] {
+      let output = TableFormat::with(format, || {
+        object.digests(DigestAlgorithm::Crc32, false).to_string()
+      });
+      assert!(output.contains(".text\\x1B[2J"), "{:?}: {}", format, output);
+      assert!(!output.contains('\x1B'), "{:?}: {:?}", format, output);
+    }
+  }
+
+  #[test]
+  #[cfg(feature = "serde")]
+  fn serialize_latin1_name() {
+    let bytes = latin1();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let json = serde_json::to_value(object.sections().nth(1).unwrap()).unwrap();
+    assert_eq!(json["index"], 1);
+    assert_eq!(json["name"], ".donn\\xE9es");
+    assert_eq!(json["header"]["sh_size"], 1);
+  }
+
+  #[test]
+  fn type_name() {
+    use abi::ei_osabi::{ELFOSABI_SOLARIS, ELFOSABI_SYSV};
+
+    let section = |sh_type: u32| ElfSection::<ElfType32<LittleEndian>> {
+      sh_type: sh_type.into(),
+      ..Default::default()
+    };
+
+    assert_eq!(section(1).type_name(ELFOSABI_SYSV), "PROGBITS");
+    assert_eq!(section(SHT_NOBITS).type_name(ELFOSABI_SYSV), "NOBITS");
+    assert_eq!(section(0x6ffffff6).type_name(ELFOSABI_SYSV), "GNU_HASH");
+    assert_eq!(section(0x6fffffff).type_name(ELFOSABI_SYSV), "GNU_versym");
+    assert_eq!(section(0x60000003).type_name(ELFOSABI_SYSV), "LOOS+0x3");
+    assert_eq!(section(0x70000003).type_name(ELFOSABI_SYSV), "LOPROC+0x3");
+    assert_eq!(section(0x0c).type_name(ELFOSABI_SYSV), "0xc");
+    assert_eq!(section(0x6ffffff6).type_name(ELFOSABI_SOLARIS), "SUNW_SIGNATURE");
+    assert_eq!(section(0x6fffffff).type_name(ELFOSABI_SOLARIS), "SUNW_versym");
+  }
+
+  #[test]
+  fn flags_short() {
+    use abi::sh_flags::*;
+
+    assert_eq!(from(SHF_WRITE | SHF_ALLOC | SHF_TLS).short(), "WAT");
+    assert_eq!(from(SHF_MERGE | SHF_STRINGS).short(), "MS");
+    assert_eq!(
+      from(SHF_EXCLUDE | 0x10000000).short(),
+      "Ep",
+      "Remaining processor bit"
+    );
+    assert_eq!(from(SHF_GNU_RETAIN | 0x00100000).short(), "Ro");
+    assert_eq!(from(0x8).short(), "x");
+  }
+
+  #[test]
+  fn section_entropy() {
+    use crate::elf::abi::sh_type::SHT_PROGBITS;
+    use crate::elf::builder::ElfBuilder;
+
+    // Uniform pseudo-random bytes (xorshift64).
+    let mut state = 0x2545_F491_4F6C_DD1D_u64;
+    let random = (0..0x10000)
+      .map(|_| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state >> 56) as u8
+      })
+      .collect::<Vec<_>>();
+
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".zeros", SHT_PROGBITS, &[0; 0x100])
+      .section(".random", SHT_PROGBITS, &random)
+      .section(".bss", SHT_NOBITS, &[])
+      .build();
+    let object = object(&bytes);
+    let entropy = |name| object.section_entropy(object.find_section(name).unwrap());
+
+    assert_eq!(entropy(".zeros").map(|entropy| entropy.value), Some(0.0));
+    let random = entropy(".random").unwrap();
+    assert!((random.value - 8.0).abs() < 0.01, "{}", random);
+    assert!(random.peak.unwrap() > 7.9 && random.is_high());
+    assert_eq!(entropy(".bss"), None);
+  }
+
+  /// Reader counting the reads within the section header table.
+  #[derive(Clone, Copy)]
+  struct CountingReader<'data> {
+    bytes: &'data [u8],
+    reads: &'data ::core::cell::Cell<usize>,
+  }
+
+  impl<'data> Reader<'data> for CountingReader<'data> {
+    fn length(self) -> usize {
+      self.bytes.len()
+    }
+
+    fn read_bytes(self, size: usize, offset: usize) -> Option<&'data [u8]> {
+      if (0x70..0x1B0).contains(&offset) {
+        self.reads.set(self.reads.get() + 1);
+      }
+      self.bytes.read_bytes(size, offset)
+    }
+  }
+
+  #[test]
+  fn section_table_read_once() {
+    let bytes = fixture();
+    let reads = ::core::cell::Cell::new(0);
+    let reader = CountingReader {
+      bytes: &bytes,
+      reads: &reads,
+    };
+    let ElfFile::Elf64Le(object) = parse_elf(reader).unwrap() else {
+      unreachable!();
+    };
+
+    assert_eq!(object.sections().count(), 5);
+    assert_eq!(reads.get(), 5, "One read per header");
+
+    assert_eq!(object.sections().count(), 5);
+    assert_eq!(object.section_by_name(".data").unwrap().index, 2);
+    assert_eq!(object.section_headers().unwrap().len(), 5);
+    assert_eq!(object.symbols().count(), 0);
+    assert!(object.symbol_table(SHT_SYMTAB).unwrap().is_none());
+    assert_eq!(reads.get(), 5, "Cached");
+  }
+}
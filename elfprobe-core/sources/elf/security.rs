@@ -0,0 +1,688 @@
+use ::core::fmt;
+
+use super::abi::e_type::{ET_DYN, ET_EXEC, ET_REL};
+use super::dynamic::*;
+use super::sections::{SHF_ALLOC, SHF_EXECINSTR};
+use super::segments::{PF_W, PT_DYNAMIC, PT_GNU_RELRO, PT_INTERP, PT_LOAD};
+use super::symbols::SHN_UNDEF;
+use super::*;
+use crate::reader::Reader;
+use crate::utils::Constant;
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+///
+/// Read-only relocations: with a `PT_GNU_RELRO` segment the dynamic loader
+/// makes part of the data (GOT, `.dynamic`...) read-only after relocation
+/// (partial), with immediate binding the whole GOT is covered (full).
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RelroLevel {
+  None,
+  Partial,
+  Full,
+}
+
+///
+/// How an executable is linked, as reported by `file`: static executables
+/// have no `PT_DYNAMIC` segment, static PIE have one (for their own
+/// relocations) but neither an interpreter nor needed libraries.
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Linkage {
+  Static,
+  Dynamic,
+  StaticPie,
+}
+
+///
+/// Result of a single check, unknown when the information is missing (e.g.
+/// no symbol table in a stripped static executable).
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Status {
+  Yes,
+  No,
+  Unknown,
+}
+
+impl From<bool> for Status {
+  fn from(value: bool) -> Self {
+    match value {
+      true => Self::Yes,
+      false => Self::No,
+    }
+  }
+}
+
+///
+/// A relocation patching a read-only segment, with the section holding the
+/// patched address (see [`TextRelocations`]).
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextRelocation {
+  pub offset: u64,
+  pub r_type: Constant<u32>,
+  pub section: Option<String>,
+}
+
+///
+/// Text relocations: relocations of the dynamic loader patching a `PT_LOAD`
+/// segment mapped without `PF_W`, which the loader has to make writable for
+/// a while (and which defeat the sharing of the pages). They should be
+/// flagged with `DT_TEXTREL` or `DF_TEXTREL`, but some linkers forget to.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TextRelocations {
+  /// `DT_TEXTREL`, or `DF_TEXTREL` in `DT_FLAGS`.
+  pub flagged: bool,
+  /// Number of relocations patching a read-only segment.
+  pub count: u64,
+  /// The first of them (up to [`TEXTREL_SAMPLES`]).
+  pub samples: Vec<TextRelocation>,
+}
+
+/// Number of text relocations kept for the diagnosis.
+pub const TEXTREL_SAMPLES: usize = 4;
+
+impl TextRelocations {
+  /// Returns `true` when flagged or when a relocation patches a read-only segment.
+  pub fn found(&self) -> bool {
+    self.flagged || self.count != 0
+  }
+}
+
+///
+/// Hardening summary (as the `checksec` script): PIE, RELRO, non-executable
+/// stack, stack protector, `_FORTIFY_SOURCE` and text relocations.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityReport {
+  pub pie: Status,
+  pub relro: RelroLevel,
+  pub nx: Status,
+  /// Whether the `PT_GNU_STACK` segment exists (the stack is executable by default),
+  /// the `.note.GNU-stack` section for relocatable objects.
+  pub gnu_stack: bool,
+  /// Whether the object is relocatable (`ET_REL`), NX is then the one of the link.
+  pub relocatable: bool,
+  pub canary: Status,
+  pub fortify: Status,
+  /// Number of fortified functions (`__*_chk`).
+  pub fortified: usize,
+  pub textrel: TextRelocations,
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  /// Returns true for `ET_DYN` objects.
+  fn is_dyn(&self) -> bool {
+    let e_type: u64 = self.header().e_type.into();
+    e_type == ET_DYN as u64
+  }
+
+  ///
+  /// Returns true for position-independent executables: `ET_DYN` objects
+  /// with either a `PT_INTERP` segment or `DF_1_PIE` in `DT_FLAGS_1` (e.g.
+  /// static PIE, which have no interpreter).
+  ///
+  /// Libraries which may also be run (`libc.so.6`) are both PIE and shared
+  /// libraries.
+  ///
+  pub fn is_pie(&self) -> bool {
+    if !self.is_dyn() {
+      return false;
+    }
+
+    self.dynamic_flags_1().contains(DF_1_PIE) || self.interpreter_bytes().is_some()
+  }
+
+  ///
+  /// Returns true for shared libraries: `ET_DYN` objects with a `DT_SONAME`
+  /// or without interpreter (see [`Self::is_pie()`]).
+  ///
+  pub fn is_shared_library(&self) -> bool {
+    self.is_dyn() && (self.soname().is_some() || self.interpreter_bytes().is_none())
+  }
+
+  ///
+  /// Returns the linkage: dynamic with a `PT_INTERP` segment or `DT_NEEDED`
+  /// entries, static PIE for `ET_DYN` objects with only a `PT_DYNAMIC`
+  /// segment, static otherwise.
+  ///
+  pub fn linkage(&self) -> Linkage {
+    let headers = self.program_headers().unwrap_or_default();
+    let has_segment = |p_type| headers.iter().any(|header| header.p_type() == p_type);
+
+    if has_segment(PT_INTERP) {
+      return Linkage::Dynamic;
+    }
+
+    let entries = self.dynamic().unwrap_or_default();
+    match (has_segment(PT_DYNAMIC), self.is_dyn()) {
+      _ if entries.iter().any(|entry| entry.d_tag() == DT_NEEDED) => Linkage::Dynamic,
+      (true, true) => Linkage::StaticPie,
+      _ => Linkage::Static,
+    }
+  }
+
+  ///
+  /// Returns true if lazy binding is disabled, which may be expressed in
+  /// three ways: a `DT_BIND_NOW` entry, `DF_BIND_NOW` in `DT_FLAGS` or
+  /// `DF_1_NOW` in `DT_FLAGS_1`.
+  ///
+  pub fn bind_now(&self) -> bool {
+    let entries = self.dynamic().unwrap_or_default();
+    entries.iter().any(|entry| entry.d_tag() == DT_BIND_NOW)
+      || self.dynamic_flags().contains(DF_BIND_NOW)
+      || self.dynamic_flags_1().contains(DF_1_NOW)
+  }
+
+  /// Returns the RELRO level (`PT_GNU_RELRO` segment and [`Self::bind_now()`]).
+  pub fn relro(&self) -> RelroLevel {
+    let headers = self.program_headers().unwrap_or_default();
+    if !headers.iter().any(|header| header.p_type() == PT_GNU_RELRO) {
+      return RelroLevel::None;
+    }
+
+    match self.bind_now() {
+      true => RelroLevel::Full,
+      false => RelroLevel::Partial,
+    }
+  }
+
+  ///
+  /// Returns the text relocations: the flags of the dynamic table, checked
+  /// against the relocations of the allocated relocation sections (those of
+  /// the dynamic loader, `--emit-relocs` leaves the others in the file).
+  ///
+  pub fn text_relocations(&self) -> TextRelocations {
+    let entries = self.dynamic().unwrap_or_default();
+    let flagged =
+      entries.iter().any(|entry| entry.d_tag() == DT_TEXTREL) || self.dynamic_flags().contains(DF_TEXTREL);
+
+    let read_only: Vec<_> = self
+      .segments()
+      .filter(|segment| segment.header.p_type() == PT_LOAD && !segment.p_flags().contains(PF_W))
+      .map(|segment| segment.vaddr_range())
+      .collect();
+
+    let machine = self.header().e_machine.into() as u16;
+    let mut textrel = TextRelocations {
+      flagged,
+      ..Default::default()
+    };
+
+    let sections = self
+      .sections()
+      .filter(|section| section.header.sh_flags() & SHF_ALLOC != 0);
+    for section in sections {
+      let relocations = self.section_relocations(section.header);
+      for relocation in
+        relocations.filter(|relocation| read_only.iter().any(|range| range.contains(&relocation.offset)))
+      {
+        textrel.count += 1;
+        if textrel.samples.len() < TEXTREL_SAMPLES {
+          textrel.samples.push(TextRelocation {
+            offset: relocation.offset,
+            r_type: relocation_name(machine, relocation.r_type),
+            section: self
+              .section_containing(relocation.offset)
+              .and_then(|section| section.display_name())
+              .map(String::from),
+          });
+        }
+      }
+    }
+
+    textrel
+  }
+
+  ///
+  /// Returns whether the `.note.GNU-stack` section of a relocatable object
+  /// requests an executable stack (`SHF_EXECINSTR`), `None` without the
+  /// section: GNU ld then makes the stack of the whole link executable.
+  ///
+  pub fn stack_note_executable(&self) -> Option<bool> {
+    let section = self.find_section(".note.GNU-stack")?;
+    Some(section.sh_flags() & SHF_EXECINSTR != 0)
+  }
+
+  ///
+  /// Returns the hardening report. The stack protector check looks for
+  /// `__stack_chk_fail` in `.dynsym` and `.symtab` (unknown without both),
+  /// the fortify one for imported `__*_chk` functions in `.dynsym` (unknown
+  /// for static executables). The stack check of relocatable objects is
+  /// the one of `.note.GNU-stack` (see [`Self::stack_note_executable()`]),
+  /// the stack and PIE checks are unknown for the other objects which are
+  /// neither executables nor shared objects.
+  ///
+  pub fn security_report(&self) -> SecurityReport {
+    let e_type: u64 = self.header().e_type.into();
+    let loadable = [ET_EXEC as u64, ET_DYN as u64].contains(&e_type);
+    let relocatable = e_type == ET_REL as u64;
+    let stack = match relocatable {
+      true => self.stack_note_executable(),
+      false => self.stack_executable(),
+    };
+
+    let (pie, nx) = match (loadable, relocatable) {
+      (false, false) => (Status::Unknown, Status::Unknown),
+      (false, true) => (Status::Unknown, (stack == Some(false)).into()),
+      (true, _) => (self.is_pie().into(), (stack == Some(false)).into()),
+    };
+
+    let dynsym = self.symbol_table(SHT_DYNSYM).ok().flatten();
+    let symtab = self.symbol_table(SHT_SYMTAB).ok().flatten();
+
+    let canary = match (&dynsym, &symtab) {
+      (None, None) => Status::Unknown,
+      _ => [&dynsym, &symtab]
+        .into_iter()
+        .flatten()
+        .any(|table| {
+          let mut symbols = table.symbols.iter();
+          symbols.any(|symbol| table.name_bytes(symbol) == Some(b"__stack_chk_fail"))
+        })
+        .into(),
+    };
+
+    // Only imported functions, libc defines them all.
+    let mut fortified = Vec::new();
+    if let Some(table) = &dynsym {
+      let imports = table
+        .symbols
+        .iter()
+        .filter(|symbol| symbol.st_shndx() == SHN_UNDEF);
+      fortified.extend(
+        imports
+          .filter_map(|symbol| table.display_name(symbol))
+          .filter(|name| name.starts_with("__") && name.ends_with("_chk") && name != "__stack_chk_fail"),
+      );
+    }
+
+    fortified.sort();
+    fortified.dedup();
+
+    let fortify = match dynsym {
+      Some(_) => (!fortified.is_empty()).into(),
+      None => Status::Unknown,
+    };
+
+    SecurityReport {
+      pie,
+      relro: self.relro(),
+      nx,
+      gnu_stack: stack.is_some(),
+      relocatable,
+      canary,
+      fortify,
+      fortified: fortified.len(),
+      textrel: self.text_relocations(),
+    }
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+impl fmt::Display for RelroLevel {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    formatter.pad(match self {
+      Self::None => "No RELRO",
+      Self::Partial => "Partial RELRO",
+      Self::Full => "Full RELRO",
+    })
+  }
+}
+
+impl fmt::Display for Linkage {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    formatter.pad(match self {
+      Self::Static => "statically linked",
+      Self::Dynamic => "dynamically linked",
+      Self::StaticPie => "static-pie linked",
+    })
+  }
+}
+
+impl fmt::Display for Status {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    formatter.pad(match self {
+      Self::Yes => "yes",
+      Self::No => "no",
+      Self::Unknown => "unknown",
+    })
+  }
+}
+
+impl fmt::Display for SecurityReport {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use crate::utils::display_table;
+
+    let nx = match (self.nx, self.gnu_stack, self.relocatable) {
+      (Status::No, false, false) => String::from("no (no PT_GNU_STACK, executable by default)"),
+      (Status::No, false, true) => String::from("no (no .note.GNU-stack, executable once linked)"),
+      (Status::No, true, true) => String::from("no (executable .note.GNU-stack)"),
+      (nx, _, _) => nx.to_string(),
+    };
+
+    let fortify = match self.fortify {
+      Status::Yes => format!("yes ({} functions)", self.fortified),
+      fortify => fortify.to_string(),
+    };
+
+    let textrel = &self.textrel;
+    let textrel_status = match (textrel.flagged, textrel.count) {
+      (false, 0) => String::from("no"),
+      (true, 0) => String::from("yes (DT_TEXTREL, no relocation found)"),
+      (true, count) => format!("yes (DT_TEXTREL, {} relocations)", count),
+      (false, count) => format!("yes ({} relocations, not flagged)", count),
+    };
+
+    display_table!(
+      formatter,
+      [
+        ["PIE:", self.pie],
+        ["RELRO:", self.relro],
+        ["NX:", nx],
+        ["Canary:", self.canary],
+        ["FORTIFY:", fortify],
+        ["TEXTREL:", textrel_status],
+      ]
+    )?;
+
+    for sample in &textrel.samples {
+      let section = sample.section.as_deref().unwrap_or("no section");
+      writeln!(
+        formatter,
+        "  {:#x} {} ({})",
+        sample.offset, sample.r_type, section
+      )?;
+    }
+    if textrel.count > textrel.samples.len() as u64 {
+      writeln!(formatter, "  ...")?;
+    }
+
+    Ok(())
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::elf::abi::e_machine::EM_X86_64;
+  use crate::hex::hex;
+
+  ///
+  /// ELF64 little-endian with a `PT_DYNAMIC` segment holding the given
+  /// entries, preceded by a `PT_GNU_RELRO` segment when `relro` is set.
+  ///
+  fn fixture(relro: bool, entries: &str) -> Vec<u8> {
+    let p_type = if relro { "52E57464" } else { "00000000" };
+    let mut bytes = hex(&format!(
+      r"
+        7F 'ELF 02 01 01 00 00 00000000 000000 ; e_ident
+        0300 3E00 01000000 ; e_type, e_machine, e_version
+        0000000000000000 ; e_entry
+        4000000000000000 ; e_phoff
+        0000000000000000 ; e_shoff
+        00000000 4000 ; e_flags, e_ehsize
+        3800 0200 ; e_phentsize, e_phnum
+        4000 0000 0000 ; e_shentsize, e_shnum, e_shstrndx
+
+        {p_type} 04000000 ; PT_GNU_RELRO (or PT_NULL), PF_R
+        B000000000000000 B000000000000000 B000000000000000
+        4000000000000000 4000000000000000 0100000000000000
+
+        02000000 06000000 ; PT_DYNAMIC, PF_R | PF_W
+        B000000000000000 B000000000000000 B000000000000000
+        4000000000000000 4000000000000000 0800000000000000
+      "
+    ))
+    .unwrap();
+
+    bytes.extend(hex(entries).unwrap());
+    bytes.resize(0xF0, 0); // DT_NULL
+    bytes
+  }
+
+  #[test]
+  fn is_pie() {
+    let classify = |bytes: &[u8]| {
+      let ElfFile::Elf64Le(object) = parse_elf(bytes).unwrap() else {
+        unreachable!();
+      };
+
+      (object.is_pie(), object.is_shared_library())
+    };
+
+    // Without interpreter.
+    assert_eq!(classify(&fixture(true, "")), (false, true));
+    assert_eq!(
+      classify(&fixture(true, "FBFFFF6F00000000 0000000800000000")),
+      (true, true),
+      "DF_1_PIE (static PIE)"
+    );
+
+    // With an interpreter (PT_GNU_RELRO -> PT_INTERP).
+    let mut bytes = fixture(true, "");
+    bytes[0x40..0x44].copy_from_slice(&[0x03, 0, 0, 0]);
+    assert_eq!(classify(&bytes), (true, false));
+
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let header = object.display_header().to_string();
+    assert!(
+      header.contains("Type:                               DYN (Position-Independent Executable file)\n")
+    );
+
+    let header = object.header().to_string();
+    assert!(header.contains("Type:                               DYN (Shared object file)\n"));
+
+    bytes[0x10] = 0x02; // ET_EXEC
+    assert_eq!(classify(&bytes), (false, false));
+  }
+
+  #[test]
+  fn linkage() {
+    let linkage = |bytes: &[u8]| {
+      let ElfFile::Elf64Le(object) = parse_elf(bytes).unwrap() else {
+        unreachable!();
+      };
+
+      object.linkage()
+    };
+
+    // ET_DYN with PT_DYNAMIC, no PT_INTERP nor DT_NEEDED.
+    let mut bytes = fixture(true, "FBFFFF6F00000000 0000000800000000");
+    assert_eq!(linkage(&bytes), Linkage::StaticPie);
+    assert_eq!(Linkage::StaticPie.to_string(), "static-pie linked");
+
+    bytes[0x40..0x44].copy_from_slice(&[0x03, 0, 0, 0]); // PT_GNU_RELRO -> PT_INTERP
+    assert_eq!(linkage(&bytes), Linkage::Dynamic);
+
+    let bytes = fixture(true, "0100000000000000 0000000000000000");
+    assert_eq!(linkage(&bytes), Linkage::Dynamic, "DT_NEEDED without interpreter");
+
+    let mut bytes = fixture(true, "");
+    bytes[0x10] = 0x02; // ET_EXEC
+    assert_eq!(linkage(&bytes), Linkage::Static, "ET_EXEC with PT_DYNAMIC");
+
+    bytes[0x78..0x7C].copy_from_slice(&[0x01, 0, 0, 0]); // PT_DYNAMIC -> PT_LOAD
+    assert_eq!(linkage(&bytes), Linkage::Static);
+  }
+
+  fn relro(bytes: &[u8]) -> RelroLevel {
+    let ElfFile::Elf64Le(object) = parse_elf(bytes).unwrap() else {
+      unreachable!();
+    };
+
+    object.relro()
+  }
+
+  #[test]
+  fn relro_level() {
+    assert_eq!(relro(&fixture(false, "")), RelroLevel::None);
+    assert_eq!(relro(&fixture(true, "")), RelroLevel::Partial);
+    assert_eq!(
+      relro(&fixture(false, "1800000000000000 0000000000000000")),
+      RelroLevel::None,
+      "DT_BIND_NOW alone"
+    );
+
+    // Partial RELRO plus any of the three forms of BIND_NOW.
+    let full = [
+      "1800000000000000 0000000000000000", // DT_BIND_NOW
+      "1E00000000000000 0800000000000000", // DT_FLAGS (DF_BIND_NOW)
+      "FBFFFF6F00000000 0100000000000000", // DT_FLAGS_1 (DF_1_NOW)
+    ];
+
+    for entry in full {
+      assert_eq!(relro(&fixture(true, entry)), RelroLevel::Full, "{}", entry);
+    }
+
+    assert_eq!(
+      relro(&fixture(true, "1E00000000000000 1000000000000000")),
+      RelroLevel::Partial,
+      "DT_FLAGS without DF_BIND_NOW"
+    );
+    assert_eq!(RelroLevel::Full.to_string(), "Full RELRO");
+  }
+
+  #[test]
+  fn security_report() {
+    let mut bytes = fixture(true, "1800000000000000 0000000000000000");
+    bytes[0x78..0x7C].copy_from_slice(&[0x51, 0xE5, 0x74, 0x64]); // PT_DYNAMIC -> PT_GNU_STACK, PF_R | PF_W
+
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let report = object.security_report();
+    assert_eq!(
+      report,
+      SecurityReport {
+        pie: Status::No,
+        relro: RelroLevel::Partial,
+        nx: Status::Yes,
+        gnu_stack: true,
+        relocatable: false,
+        canary: Status::Unknown,
+        fortify: Status::Unknown,
+        fortified: 0,
+        textrel: TextRelocations::default(),
+      },
+      "No dynamic table (BIND_NOW lost) nor symbol tables"
+    );
+
+    let report = SecurityReport {
+      pie: Status::Yes,
+      relro: RelroLevel::Full,
+      nx: Status::No,
+      gnu_stack: false,
+      relocatable: false,
+      canary: Status::Yes,
+      fortify: Status::Yes,
+      fortified: 3,
+      textrel: TextRelocations {
+        flagged: false,
+        count: 5,
+        samples: vec![TextRelocation {
+          offset: 0x1008,
+          r_type: relocation_name(EM_X86_64, 1),
+          section: Some(String::from(".text")),
+        }],
+      },
+    };
+
+    assert_eq!(
+      report.to_string(),
+      concat!(
+        "PIE:      yes\n",
+        "RELRO:    Full RELRO\n",
+        "NX:       no (no PT_GNU_STACK, executable by default)\n",
+        "Canary:   yes\n",
+        "FORTIFY:  yes (3 functions)\n",
+        "TEXTREL:  yes (5 relocations, not flagged)\n",
+        "  0x1008 R_X86_64_64 (.text)\n",
+        "  ...\n",
+      )
+    );
+  }
+
+  #[test]
+  fn text_relocations() {
+    use crate::elf::abi::sh_type::{SHT_PROGBITS, SHT_RELA};
+    use crate::elf::builder::{BuilderSection, ElfBuilder};
+
+    let rela = |relocations: &[(u64, u64)]| {
+      let entry = |&(r_offset, r_type): &(u64, u64)| [r_offset, r_type, 0].map(u64::to_le_bytes).concat();
+      relocations.iter().flat_map(entry).collect::<Vec<_>>()
+    };
+
+    let section = |name: &str, sh_type, sh_flags, sh_addr, data| BuilderSection {
+      name: String::from(name),
+      sh_type,
+      sh_flags,
+      sh_addr,
+      data,
+      ..Default::default()
+    };
+
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .object_type(ET_DYN)
+      .add_section(section(".text", SHT_PROGBITS, 0x6, 0x1000, vec![0xC3; 16]))
+      .add_section(section(".data", SHT_PROGBITS, 0x3, 0x3000, vec![0; 16]))
+      // R_X86_64_64 in .text, R_X86_64_RELATIVE in .data.
+      .add_section(section(
+        ".rela.dyn",
+        SHT_RELA,
+        0x2,
+        0,
+        rela(&[(0x1008, 1), (0x3000, 8)]),
+      ))
+      // Left by --emit-relocs, not applied by the loader.
+      .add_section(section(".rela.text", SHT_RELA, 0, 0, rela(&[(0x1000, 2)])))
+      .segment(PT_LOAD, 5, &[1])
+      .segment(PT_LOAD, 6, &[2])
+      .build();
+
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let textrel = object.text_relocations();
+    assert_eq!(
+      textrel,
+      TextRelocations {
+        flagged: false,
+        count: 1,
+        samples: vec![TextRelocation {
+          offset: 0x1008,
+          r_type: relocation_name(EM_X86_64, 1),
+          section: Some(String::from(".text")),
+        }],
+      },
+      "Not flagged by the linker"
+    );
+    assert!(textrel.found());
+    assert!(!TextRelocations::default().found());
+  }
+}
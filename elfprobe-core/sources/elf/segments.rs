@@ -0,0 +1,1029 @@
+use ::core::fmt::Debug;
+use ::core::ops::Range;
+use alloc::borrow::Cow;
+
+use super::sections::{SHF_ALLOC, SHF_TLS, SHT_NOBITS};
+use super::*;
+use crate::endian::Endianness;
+use crate::error::{BytesError, ElfError, ResultExt};
+use crate::reader::{to_usize, Reader};
+use crate::utils::Constant;
+use elfprobe_macro::{FieldLayout, Pod};
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+pub use super::abi::p_type::{
+  PT_DYNAMIC, PT_GNU_EH_FRAME, PT_GNU_MBIND_HI, PT_GNU_MBIND_LO, PT_GNU_RELRO, PT_GNU_SFRAME, PT_GNU_STACK,
+  PT_INTERP, PT_LOAD, PT_NOTE, PT_PHDR, PT_TLS,
+};
+
+pub use super::abi::p_flags::{PF_R, PF_W, PF_X};
+
+/// Extended program header count, the actual count is in `sh_info` of the first section.
+pub const PN_XNUM: u16 = 0xffff;
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Pod, FieldLayout)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(bound = ""))]
+pub struct Elf32ProgramHeader<E: Endianness> {
+  pub p_type: Elf32_Word<E>,
+  pub p_offset: Elf32_Off<E>,
+  pub p_vaddr: Elf32_Addr<E>,
+  pub p_paddr: Elf32_Addr<E>,
+  pub p_filesz: Elf32_Word<E>,
+  pub p_memsz: Elf32_Word<E>,
+  pub p_flags: Elf32_Word<E>,
+  pub p_align: Elf32_Word<E>,
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Pod, FieldLayout)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(bound = ""))]
+pub struct Elf64ProgramHeader<E: Endianness> {
+  pub p_type: Elf64_Word<E>,
+  pub p_flags: Elf64_Word<E>, // Moved here for alignment purposes.
+  pub p_offset: Elf64_Off<E>,
+  pub p_vaddr: Elf64_Addr<E>,
+  pub p_paddr: Elf64_Addr<E>,
+  pub p_filesz: Elf64_Xword<E>,
+  pub p_memsz: Elf64_Xword<E>,
+  pub p_align: Elf64_Xword<E>,
+}
+
+///
+/// Program header fields are not declared in the same order in elf32 and
+/// elf64 (`p_flags` has been moved for alignment purposes), this trait gives a
+/// common access to both of them. All values are widened to 64 bits.
+///
+pub trait ProgramHeader: Type + crate::pod::FieldLayout {
+  fn p_type(&self) -> u32;
+  fn p_flags(&self) -> u32;
+  fn p_offset(&self) -> u64;
+  fn p_vaddr(&self) -> u64;
+  fn p_paddr(&self) -> u64;
+  fn p_filesz(&self) -> u64;
+  fn p_memsz(&self) -> u64;
+  fn p_align(&self) -> u64;
+
+  /// Sets `p_flags` (see [`Writer`](crate::writer::Writer)).
+  fn set_p_flags(&mut self, value: u32);
+
+  /// Sets `p_filesz`, truncated to 32 bits in elf32.
+  fn set_p_filesz(&mut self, value: u64);
+
+  /// Sets `p_memsz`, truncated to 32 bits in elf32.
+  fn set_p_memsz(&mut self, value: u64);
+
+  /// Returns the segment permissions (`p_flags`).
+  fn p_flags_decoded(&self) -> abi::p_flags::Flags {
+    abi::p_flags::from(self.p_flags())
+  }
+
+  ///
+  /// Returns the segment type name without its `PT_` prefix, as readelf
+  /// (`LOAD`, `GNU_STACK`, `LOOS+0x10`...), in hexadecimal when unknown.
+  /// OS-specific types are named after the OS ABI, see [`abi::segment_type()`].
+  ///
+  fn type_name(&self, osabi: u8) -> String {
+    let name = abi::segment_type(osabi, self.p_type()).to_string();
+    match name.strip_prefix("PT_") {
+      Some(name) => String::from(name),
+      None => name,
+    }
+  }
+}
+
+macro_rules! impl_program_header {
+  ($struct: ident) => {
+    impl<E: Endianness> ProgramHeader for $struct<E> {
+      #[inline]
+      fn p_type(&self) -> u32 {
+        self.p_type.get()
+      }
+
+      #[inline]
+      fn p_flags(&self) -> u32 {
+        self.p_flags.get()
+      }
+
+      #[inline]
+      fn p_offset(&self) -> u64 {
+        self.p_offset.into()
+      }
+
+      #[inline]
+      fn p_vaddr(&self) -> u64 {
+        self.p_vaddr.into()
+      }
+
+      #[inline]
+      fn p_paddr(&self) -> u64 {
+        self.p_paddr.into()
+      }
+
+      #[inline]
+      fn p_filesz(&self) -> u64 {
+        self.p_filesz.into()
+      }
+
+      #[inline]
+      fn p_memsz(&self) -> u64 {
+        self.p_memsz.into()
+      }
+
+      #[inline]
+      fn p_align(&self) -> u64 {
+        self.p_align.into()
+      }
+
+      #[inline]
+      fn set_p_flags(&mut self, value: u32) {
+        self.p_flags.set(value);
+      }
+
+      #[inline]
+      fn set_p_filesz(&mut self, value: u64) {
+        self.p_filesz.set(value as _);
+      }
+
+      #[inline]
+      fn set_p_memsz(&mut self, value: u64) {
+        self.p_memsz.set(value as _);
+      }
+    }
+  };
+}
+
+impl_program_header!(Elf32ProgramHeader);
+impl_program_header!(Elf64ProgramHeader);
+
+impl abi::p_flags::Flags {
+  ///
+  /// Returns the permissions as the readelf `Flg` column: `R`, `W` and `E`
+  /// at fixed positions, with spaces for unset ones (`R E`, `RW `).
+  ///
+  pub fn rwe(&self) -> String {
+    [(PF_R, 'R'), (PF_W, 'W'), (PF_X, 'E')]
+      .into_iter()
+      .map(|(flag, letter)| if self.contains(flag) { letter } else { ' ' })
+      .collect()
+  }
+}
+
+///
+/// The zero-filled tail of a `PT_LOAD` segment (`p_memsz` beyond `p_filesz`),
+/// with the `SHT_NOBITS` sections mapped into it (`.bss`), see
+/// [`ElfObject::zero_fill()`].
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZeroFill<'data> {
+  /// Index of the segment in the program header table.
+  pub segment: usize,
+  pub range: Range<u64>,
+  pub sections: Vec<Cow<'data, str>>,
+}
+
+impl ZeroFill<'_> {
+  /// Returns the number of zero-filled bytes.
+  pub fn size(&self) -> u64 {
+    self.range.end - self.range.start
+  }
+}
+
+///
+/// A program header with its index in the program header table, see
+/// [`ElfObject::segments()`]. Ranges saturate instead of overflowing on
+/// malformed headers.
+///
+#[derive(Debug, Copy, Clone)]
+pub struct SegmentRef<'data, ElfType: self::ElfType> {
+  pub index: usize,
+  pub header: &'data ElfType::ProgramHeader,
+}
+
+impl<ElfType: self::ElfType> SegmentRef<'_, ElfType> {
+  /// Returns the segment type (`p_type`).
+  pub fn p_type(&self) -> Constant<u32> {
+    abi::p_type::from(self.header.p_type())
+  }
+
+  /// Returns the segment permissions (`p_flags`).
+  pub fn p_flags(&self) -> abi::p_flags::Flags {
+    self.header.p_flags_decoded()
+  }
+
+  /// Returns the bytes of the file in the segment (`p_offset` and `p_filesz`).
+  pub fn file_range(&self) -> Range<u64> {
+    let start = self.header.p_offset();
+    start..start.saturating_add(self.header.p_filesz())
+  }
+
+  /// Returns the memory occupied by the segment (`p_vaddr` and `p_memsz`).
+  pub fn vaddr_range(&self) -> Range<u64> {
+    let start = self.header.p_vaddr();
+    start..start.saturating_add(self.header.p_memsz())
+  }
+
+  ///
+  /// Returns the memory of the segment past its file bytes (`p_memsz`
+  /// beyond `p_filesz`), zero-filled by the loader. The range is empty when
+  /// `p_filesz` is larger (invalid, see [`ElfObject::validate_segments()`]).
+  ///
+  pub fn zero_fill_range(&self) -> Range<u64> {
+    let (filesz, memsz) = (self.header.p_filesz(), self.header.p_memsz());
+    let start = self.header.p_vaddr().saturating_add(filesz.min(memsz));
+    start..self.header.p_vaddr().saturating_add(memsz)
+  }
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the program header table (`e_phoff`, `e_phnum` and
+  /// `e_phentsize`), the table is empty when `e_phnum` is zero.
+  ///
+  /// In lenient mode, the headers read before the first one out of the file
+  /// are returned (none when the entry size is impossible).
+  ///
+  pub fn program_headers(&self) -> Result<Vec<&'data ElfType::ProgramHeader>, ElfError> {
+    let offset: u64 = self.header.e_phoff.into();
+    let entsize: u64 = self.header.e_phentsize.into();
+    let count: u64 = self.header.e_phnum.into();
+
+    // Larger entries are fine (extensions), smaller ones would overlap.
+    let expected = ::core::mem::size_of::<ElfType::ProgramHeader>();
+    if count != 0 && entsize < expected as u64 {
+      let table = "program header table";
+      let error = ElfError::EntrySizeMismatch {
+        table,
+        entsize,
+        expected,
+      };
+      return self.recover(Err(error), Vec::new());
+    }
+
+    // The table up to the end of the entry.
+    let read = |index: u64| {
+      let size = (index + 1).saturating_mul(entsize);
+      let end = offset
+        .checked_add(size)
+        .ok_or(BytesError::RangeOverflow { offset, size })?;
+      self
+        .data
+        .read_pod::<ElfType::ProgramHeader>(to_usize(end - entsize)?)
+    };
+
+    let count = self.bounded_count("program header table", count, expected)?;
+    let mut headers = Vec::new();
+    let result = (0..count).try_for_each(|index| read(index).map(|header| headers.push(header)));
+    let context = || format!("reading the program header table at offset {:#x}", offset);
+    self.recover(result.with_context(context), headers)
+  }
+
+  ///
+  /// Returns an iterator over the program headers, read on the fly. The
+  /// iteration stops at the first header out of the file.
+  ///
+  pub fn segments(&self) -> impl Iterator<Item = SegmentRef<'data, ElfType>> + '_ {
+    let offset: u64 = self.header.e_phoff.into();
+    let entsize: u64 = self.header.e_phentsize.into();
+    let count = self.limited_count(self.header.e_phnum.into());
+
+    (0..count).map_while(move |index| {
+      let offset = index.checked_mul(entsize)?.checked_add(offset)?;
+      let header = self.data.read_pod(usize::try_from(offset).ok()?).ok()?;
+      Some(SegmentRef {
+        index: index as usize,
+        header,
+      })
+    })
+  }
+
+  ///
+  /// Returns the `PT_LOAD` segments sorted by virtual address, segments at
+  /// the same address keep their table order (overlapping segments of
+  /// malformed files are kept as is).
+  ///
+  pub fn loadable(&self) -> Vec<SegmentRef<'data, ElfType>> {
+    let mut segments = self
+      .segments()
+      .filter(|segment| segment.header.p_type() == PT_LOAD)
+      .collect::<Vec<_>>();
+
+    segments.sort_by_key(|segment| segment.header.p_vaddr());
+    segments
+  }
+
+  ///
+  /// Returns the first `PT_LOAD` segment (in table order) whose memory holds
+  /// the given virtual address, the zero-filled part included.
+  ///
+  pub fn segment_containing(&self, vaddr: u64) -> Option<SegmentRef<'data, ElfType>> {
+    self
+      .segments()
+      .find(|segment| segment.header.p_type() == PT_LOAD && segment.vaddr_range().contains(&vaddr))
+  }
+
+  ///
+  /// Reads `len` bytes at the given virtual address, from the file bytes of
+  /// the segment holding it (see [`Self::segment_containing()`]).
+  ///
+  /// Reads are never shortened nor continued in another segment: the bytes
+  /// must all lie in the first `p_filesz` bytes of the segment, otherwise
+  /// [`ElfError::UnbackedAddress`] is returned rather than the adjacent
+  /// bytes of the file (the `p_memsz > p_filesz` tail is zero-filled).
+  ///
+  pub fn read_at_vaddr(&self, vaddr: u64, len: usize) -> Result<&'data [u8], ElfError> {
+    let segment = self
+      .segment_containing(vaddr)
+      .ok_or(ElfError::UnmappedAddress(vaddr))?;
+
+    let delta = vaddr - segment.header.p_vaddr();
+    match delta.checked_add(len as u64) {
+      Some(end) if end <= segment.header.p_filesz() => (),
+      _ => return Err(ElfError::UnbackedAddress { vaddr, len }),
+    }
+
+    let offset = segment.header.p_offset().checked_add(delta);
+    let offset = offset.and_then(|offset| usize::try_from(offset).ok());
+
+    // TODO: Same as Reader::read_pod(), it is not empty.
+    let bytes = offset.and_then(|offset| self.data.read_bytes(len, offset));
+    Ok(bytes.ok_or(BytesError::Empty)?)
+  }
+
+  ///
+  /// Translates a virtual address into a file offset through the `PT_LOAD`
+  /// segments, `None` is returned when the address is not backed by the file
+  /// (between segments or in a zero-filled `p_memsz > p_filesz` tail).
+  ///
+  pub fn vaddr_to_offset(&self, vaddr: u64) -> Option<u64> {
+    let segment = self.segments().find(|segment| {
+      let header = segment.header;
+      header.p_type() == PT_LOAD && vaddr >= header.p_vaddr() && vaddr - header.p_vaddr() < header.p_filesz()
+    })?;
+
+    (vaddr - segment.header.p_vaddr()).checked_add(segment.header.p_offset())
+  }
+
+  ///
+  /// Translates a file offset into a virtual address through the `PT_LOAD`
+  /// segments (the reverse of [`Self::vaddr_to_offset()`]), `None` is
+  /// returned when the offset is not loaded (headers, alignment padding
+  /// between segments, non-allocated sections...).
+  ///
+  pub fn offset_to_vaddr(&self, offset: u64) -> Option<u64> {
+    let segment = self
+      .segments()
+      .find(|segment| segment.header.p_type() == PT_LOAD && segment.file_range().contains(&offset))?;
+
+    (offset - segment.header.p_offset()).checked_add(segment.header.p_vaddr())
+  }
+
+  ///
+  /// Returns, for every segment (in program header order), the names of the
+  /// sections it contains. Same rules as the "Section to Segment mapping" of
+  /// `readelf -l`, see [`section_in_segment()`].
+  ///
+  pub fn section_to_segment_mapping(&self) -> Result<Vec<Vec<Cow<'data, str>>>, ElfError> {
+    let segments = self.program_headers()?;
+    let sections = self.section_headers()?;
+    let names = self.section_names()?;
+
+    let name = |section: &ElfSection<ElfType>| {
+      let offset = usize::try_from(section.sh_name()).ok();
+      match offset.and_then(|offset| names.get(offset)) {
+        Some(bytes) => super::strings::printable(bytes),
+        None => Cow::Borrowed("<corrupt>"),
+      }
+    };
+
+    let mapping = segments.iter().map(|segment| {
+      sections
+        .iter()
+        .skip(1) // The null section is never mapped.
+        .filter(|section| section_in_segment(section, *segment))
+        .map(|section| name(section))
+        .collect()
+    });
+
+    Ok(mapping.collect())
+  }
+
+  ///
+  /// Returns the zero-filled tails of the `PT_LOAD` segments (in program
+  /// header order, the segments without one are skipped) and the
+  /// `SHT_NOBITS` sections accounting for them, the memory footprint which
+  /// the file size does not show.
+  ///
+  pub fn zero_fill(&self) -> Vec<ZeroFill<'data>> {
+    let sections = self.sections().skip(1).collect::<Vec<_>>();
+    let loadable = self
+      .segments()
+      .filter(|segment| segment.header.p_type() == PT_LOAD);
+
+    let tails = loadable.map(|segment| {
+      let range = segment.zero_fill_range();
+      let sections = sections
+        .iter()
+        .filter(|section| section.header.sh_type() == SHT_NOBITS as u64)
+        .filter(|section| section_in_segment(section.header, segment.header))
+        .filter(|section| {
+          let start = section.header.sh_addr();
+          start < range.end && start.saturating_add(section.header.sh_size()) > range.start
+        })
+        .map(|section| section.display_name().unwrap_or(Cow::Borrowed("<corrupt>")))
+        .collect();
+
+      ZeroFill {
+        segment: segment.index,
+        range,
+        sections,
+      }
+    });
+
+    tails.filter(|tail| tail.size() != 0).collect()
+  }
+
+  /// Returns the number of zero-filled bytes of the `PT_LOAD` segments, see [`Self::zero_fill()`].
+  pub fn zero_fill_bytes(&self) -> u64 {
+    self
+      .segments()
+      .filter(|segment| segment.header.p_type() == PT_LOAD)
+      .map(|segment| segment.zero_fill_range())
+      .map(|range| range.end - range.start)
+      .fold(0, u64::saturating_add)
+  }
+
+  ///
+  /// Returns the raw path of the program interpreter (`PT_INTERP` segment)
+  /// without its trailing NUL.
+  ///
+  /// The segment is read up to `p_filesz` bytes and stops at the first NUL,
+  /// a missing terminator is tolerated. `None` is returned when there is no
+  /// `PT_INTERP` segment or when it lies outside the file.
+  ///
+  pub fn interpreter_bytes(&self) -> Option<&'data [u8]> {
+    let headers = self.program_headers().ok()?;
+    let header = headers.into_iter().find(|header| header.p_type() == PT_INTERP)?;
+
+    let offset = usize::try_from(header.p_offset()).ok()?;
+    let size = usize::try_from(header.p_filesz()).ok()?;
+    let bytes = self.data.read_bytes(size, offset)?;
+
+    match bytes.iter().position(|&byte| byte == 0) {
+      Some(end) => Some(&bytes[..end]),
+      None => Some(bytes),
+    }
+  }
+
+  ///
+  /// Returns the path of the program interpreter (e.g.
+  /// `/lib64/ld-linux-x86-64.so.2`), see [`Self::interpreter_bytes()`].
+  ///
+  /// `None` is also returned when the path is not valid UTF-8.
+  ///
+  pub fn interpreter(&self) -> Option<&'data str> {
+    ::core::str::from_utf8(self.interpreter_bytes()?).ok()
+  }
+
+  ///
+  /// Returns whether the stack is executable according to the `PF_X` flag of
+  /// the `PT_GNU_STACK` segment, `None` when there is no such segment (the
+  /// Linux kernel then maps an executable stack).
+  ///
+  /// Only the program headers are used, so that it works on stripped files.
+  ///
+  pub fn stack_executable(&self) -> Option<bool> {
+    let headers = self.program_headers().ok()?;
+    let header = headers
+      .into_iter()
+      .find(|header| header.p_type() == PT_GNU_STACK)?;
+    Some(header.p_flags_decoded().contains(PF_X))
+  }
+}
+
+///
+/// Whether the given section lies inside the given segment, in the file (but
+/// for `SHT_NOBITS` sections) and in memory (but for non-`SHF_ALLOC` ones).
+/// Port of binutils' `ELF_SECTION_IN_SEGMENT_STRICT`, where:
+///
+/// - `SHF_TLS` sections only belong to `PT_TLS`, `PT_GNU_RELRO` or `PT_LOAD`
+///   segments, and `PT_TLS` only holds `SHF_TLS` sections.
+/// - `.tbss` (`SHF_TLS` and `SHT_NOBITS`) occupies no space outside of the
+///   `PT_TLS` segment, it is therefore only listed in the latter (as readelf
+///   does with `ELF_TBSS_SPECIAL`).
+/// - zero-size sections at the edges of `PT_DYNAMIC` and `PT_NOTE` segments
+///   are excluded.
+///
+pub fn section_in_segment<ElfType: self::ElfType>(
+  section: &ElfSection<ElfType>,
+  segment: &ElfType::ProgramHeader,
+) -> bool {
+  let p_type = segment.p_type();
+  let flags = abi::sh_flags::from(section.sh_flags());
+  let tls = flags.contains(SHF_TLS);
+  let alloc = flags.contains(SHF_ALLOC);
+  let nobits = section.sh_type() == SHT_NOBITS as u64;
+
+  if tls && nobits && p_type != PT_TLS {
+    return false; // ELF_TBSS_SPECIAL
+  }
+
+  let tls_ok = if tls {
+    matches!(p_type, PT_TLS | PT_GNU_RELRO | PT_LOAD)
+  } else {
+    p_type != PT_TLS && p_type != PT_PHDR
+  };
+
+  // Only SHF_ALLOC sections in loadable-like segments.
+  let alloc_only = matches!(
+    p_type,
+    PT_LOAD | PT_DYNAMIC | PT_GNU_EH_FRAME | PT_GNU_STACK | PT_GNU_RELRO | PT_GNU_SFRAME
+  ) || (PT_GNU_MBIND_LO..=PT_GNU_MBIND_HI).contains(&p_type);
+
+  if !tls_ok || (!alloc && alloc_only) {
+    return false;
+  }
+
+  // Same wrapping arithmetic as binutils (bfd_vma).
+  let size = section.sh_size();
+  let in_file = nobits || {
+    let delta = section.sh_offset().wrapping_sub(segment.p_offset());
+    section.sh_offset() >= segment.p_offset()
+      && delta <= segment.p_filesz().wrapping_sub(1)
+      && delta.wrapping_add(size) <= segment.p_filesz()
+  };
+
+  let in_memory = !alloc || {
+    let delta = section.sh_addr().wrapping_sub(segment.p_vaddr());
+    section.sh_addr() >= segment.p_vaddr()
+      && delta <= segment.p_memsz().wrapping_sub(1)
+      && delta.wrapping_add(size) <= segment.p_memsz()
+  };
+
+  let edges = !matches!(p_type, PT_DYNAMIC | PT_NOTE) || size != 0 || segment.p_memsz() == 0 || {
+    let file = nobits
+      || (section.sh_offset() > segment.p_offset()
+        && section.sh_offset() - segment.p_offset() < segment.p_filesz());
+    let memory = !alloc
+      || (section.sh_addr() > segment.p_vaddr() && section.sh_addr() - segment.p_vaddr() < segment.p_memsz());
+    file && memory
+  };
+
+  in_file && in_memory && edges
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+  use ::core::mem::size_of;
+
+  #[test]
+  fn program_header_memory_size() {
+    assert_eq!(size_of::<Elf32ProgramHeader<BigEndian>>(), 32);
+    assert_eq!(size_of::<Elf64ProgramHeader<BigEndian>>(), 56);
+    assert_eq!(size_of::<Elf32ProgramHeader<LittleEndian>>(), 32);
+    assert_eq!(size_of::<Elf64ProgramHeader<LittleEndian>>(), 56);
+  }
+
+  #[test]
+  fn p_flags_decoded() {
+    let mut header = segment(PT_LOAD, 0, 0, 0, 0);
+    header.p_flags = (PF_R | PF_X).into();
+    assert_eq!(header.p_flags_decoded(), abi::p_flags::Flags(5));
+    assert!(header.p_flags_decoded().contains(PF_X));
+    assert!(!header.p_flags_decoded().contains(PF_R | PF_W));
+    assert_eq!(header.p_flags_decoded().rwe(), "R E");
+    assert_eq!(abi::p_flags::from(PF_R | PF_W).rwe(), "RW ");
+    assert_eq!(abi::p_flags::from(0x00100000).rwe(), "   ");
+    assert_eq!(
+      abi::p_flags::from(PF_R | 0x00100000).to_string(),
+      "PF_R | 0x100000"
+    );
+  }
+
+  #[test]
+  fn type_name() {
+    use abi::ei_osabi::{ELFOSABI_SOLARIS, ELFOSABI_SYSV};
+
+    let name = |p_type: u32| segment(p_type, 0, 0, 0, 0).type_name(ELFOSABI_SYSV);
+
+    assert_eq!(name(PT_LOAD), "LOAD");
+    assert_eq!(name(PT_GNU_STACK), "GNU_STACK");
+    assert_eq!(name(0x6474e553), "GNU_PROPERTY");
+    assert_eq!(name(0x6474e600), "GNU_MBIND_LO+0xab");
+    assert_eq!(name(0x6ffffffa), "SUNWBSS");
+    assert_eq!(name(0x6ffffffc), "LOOS+0xffffffc", "Unnamed OS-specific value");
+    assert_eq!(name(0x70000001), "LOPROC+0x1");
+    assert_eq!(name(8), "0x8");
+
+    let solaris = segment(0x6ffffffc, 0, 0, 0, 0).type_name(ELFOSABI_SOLARIS);
+    assert_eq!(solaris, "SUNWDTRACE");
+  }
+
+  /// ELF64 little-endian with a single `PT_INTERP` segment at 0x78.
+  fn fixture(filesz: u64, interpreter: &str) -> Vec<u8> {
+    let filesz = format!("{:016x}", filesz.swap_bytes());
+    hex(&format!(
+      r"
+        7F 'ELF 02 01 01 00 00 00000000 000000 ; e_ident
+        0300 3E00 01000000 ; e_type, e_machine, e_version
+        0000000000000000 ; e_entry
+        4000000000000000 ; e_phoff
+        0000000000000000 ; e_shoff
+        00000000 4000 ; e_flags, e_ehsize
+        3800 0100 ; e_phentsize, e_phnum
+        4000 0000 0000 ; e_shentsize, e_shnum, e_shstrndx
+
+        03000000 04000000 ; p_type, p_flags
+        7800000000000000 ; p_offset
+        7800000000000000 ; p_vaddr
+        7800000000000000 ; p_paddr
+        {filesz} ; p_filesz
+        {filesz} ; p_memsz
+        0100000000000000 ; p_align
+
+        {interpreter}
+      "
+    ))
+    .unwrap()
+  }
+
+  #[test]
+  fn segments() {
+    let bytes = hex(
+      r"
+        7F 'ELF 02 01 01 00 00 00000000 000000 ; e_ident
+        0300 3E00 01000000 ; e_type, e_machine, e_version
+        0000000000000000 ; e_entry
+        4000000000000000 ; e_phoff
+        0000000000000000 ; e_shoff
+        00000000 4000 ; e_flags, e_ehsize
+        3800 0400 ; e_phentsize, e_phnum
+        4000 0000 0000 ; e_shentsize, e_shnum, e_shstrndx
+
+        01000000 06000000 ; PT_LOAD, PF_R | PF_W
+        0010000000000000 0020000000000000 0020000000000000
+        0001000000000000 0002000000000000 0010000000000000
+
+        04000000 04000000 ; PT_NOTE, PF_R
+        4000000000000000 4000000000000000 4000000000000000
+        2000000000000000 2000000000000000 0400000000000000
+
+        01000000 05000000 ; PT_LOAD, PF_R | PF_X
+        0000000000000000 0000000000000000 0000000000000000
+        2001000000000000 2001000000000000 0010000000000000
+
+        01000000 04000000 ; PT_LOAD, PF_R (overlapping)
+        0011000000000000 0020000000000000 0020000000000000
+        FFFFFFFFFFFFFFFF FFFFFFFFFFFFFFFF 0010000000000000
+      ",
+    )
+    .unwrap();
+
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let segments = object.segments().collect::<Vec<_>>();
+    assert_eq!(segments.len(), 4);
+    assert_eq!(segments[1].p_type().to_string(), "PT_NOTE");
+    assert_eq!(segments[0].p_flags().rwe(), "RW ");
+    assert_eq!(segments[0].file_range(), 0x1000..0x1100);
+    assert_eq!(segments[0].vaddr_range(), 0x2000..0x2200);
+    assert_eq!(segments[3].vaddr_range(), 0x2000..u64::MAX, "Saturated");
+
+    let loadable = object.loadable();
+    let indices = loadable.iter().map(|segment| segment.index).collect::<Vec<_>>();
+    assert_eq!(indices, [2, 0, 3]);
+  }
+
+  #[test]
+  fn table_past_address_space() {
+    let mut bytes = fixture(0x10, "2F 00");
+    let offset = u64::MAX - 0x10;
+    bytes[0x20..0x28].copy_from_slice(&offset.to_le_bytes()); // e_phoff
+
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    let error = object.program_headers().unwrap_err();
+    assert_eq!(
+      error.root(),
+      &BytesError::RangeOverflow { offset, size: 56 }.into()
+    );
+    assert_eq!(object.segments().count(), 0);
+  }
+
+  #[test]
+  fn address_translation() {
+    let bytes = hex(
+      r"
+        7F 'ELF 02 01 01 00 00 00000000 000000 ; e_ident
+        0200 3E00 01000000 ; e_type, e_machine, e_version
+        0000000000000000 ; e_entry
+        4000000000000000 ; e_phoff
+        0000000000000000 ; e_shoff
+        00000000 4000 ; e_flags, e_ehsize
+        3800 0200 ; e_phentsize, e_phnum
+        4000 0000 0000 ; e_shentsize, e_shnum, e_shstrndx
+
+        01000000 05000000 ; PT_LOAD, PF_R | PF_X (headers included)
+        0000000000000000 0000400000000000 0000400000000000
+        0001000000000000 0001000000000000 0010000000000000
+
+        01000000 06000000 ; PT_LOAD, PF_R | PF_W, padded to 0x180 in the file
+        8001000000000000 8011600000000000 8011600000000000
+        2000000000000000 4000000000000000 4000000000000000
+      ",
+    )
+    .unwrap();
+
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    assert_eq!(object.offset_to_vaddr(0x40), Some(0x400040));
+    assert_eq!(object.offset_to_vaddr(0xff), Some(0x4000ff));
+    assert_eq!(object.offset_to_vaddr(0x100), None, "Padding");
+    assert_eq!(object.offset_to_vaddr(0x17f), None, "Padding");
+    assert_eq!(object.offset_to_vaddr(0x180), Some(0x601180));
+    assert_eq!(object.offset_to_vaddr(0x19f), Some(0x60119f));
+    assert_eq!(object.offset_to_vaddr(0x1a0), None);
+
+    assert_eq!(object.vaddr_to_offset(0x400000), Some(0));
+    assert_eq!(object.vaddr_to_offset(0x400100), None);
+    assert_eq!(object.vaddr_to_offset(0x601190), Some(0x190));
+    assert_eq!(object.vaddr_to_offset(0x6011a0), None, "Zero-filled");
+    assert_eq!(object.vaddr_to_offset(0x500000), None);
+
+    for offset in [0x0, 0x80, 0x180, 0x19f] {
+      let vaddr = object.offset_to_vaddr(offset).unwrap();
+      assert_eq!(object.vaddr_to_offset(vaddr), Some(offset));
+    }
+  }
+
+  #[test]
+  fn read_at_vaddr() {
+    let bytes = hex(
+      r"
+        7F 'ELF 02 01 01 00 00 00000000 000000 ; e_ident
+        0200 3E00 01000000 ; e_type, e_machine, e_version
+        0000000000000000 ; e_entry
+        4000000000000000 ; e_phoff
+        0000000000000000 ; e_shoff
+        00000000 4000 ; e_flags, e_ehsize
+        3800 0100 ; e_phentsize, e_phnum
+        4000 0000 0000 ; e_shentsize, e_shnum, e_shstrndx
+
+        01000000 06000000 ; PT_LOAD, PF_R | PF_W
+        7800000000000000 0010000000000000 0010000000000000
+        0800000000000000 1000000000000000 0800000000000000
+
+        0011223344556677 ; 0x78, data
+        8899AABBCCDDEEFF ; 0x80, next bytes of the file (not in the segment)
+      ",
+    )
+    .unwrap();
+
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    assert_eq!(
+      object.segment_containing(0x100f).map(|segment| segment.index),
+      Some(0)
+    );
+    assert!(object.segment_containing(0x1010).is_none());
+
+    assert_eq!(object.read_at_vaddr(0x1002, 4), Ok(&[0x22, 0x33, 0x44, 0x55][..]));
+    assert_eq!(object.read_at_vaddr(0x1000, 8).map(<[u8]>::len), Ok(8));
+    assert_eq!(
+      object.read_at_vaddr(0x1006, 4),
+      Err(ElfError::UnbackedAddress {
+        vaddr: 0x1006,
+        len: 4
+      }),
+      "Runs into the zero-filled tail"
+    );
+    assert_eq!(
+      object.read_at_vaddr(0x1008, 1),
+      Err(ElfError::UnbackedAddress {
+        vaddr: 0x1008,
+        len: 1
+      })
+    );
+    assert_eq!(
+      object.read_at_vaddr(0x2000, 1),
+      Err(ElfError::UnmappedAddress(0x2000))
+    );
+  }
+
+  fn interpreter(bytes: &[u8]) -> Option<&[u8]> {
+    match parse_elf(bytes).unwrap() {
+      ElfFile::Elf64Le(object) => object.interpreter_bytes(),
+      _ => panic!("Expected an ELF64 little-endian"),
+    }
+  }
+
+  #[test]
+  fn interpreter_ok() {
+    let bytes = fixture(28, "'/lib64/ld-linux-x86-64.so.2 00");
+    let expected: &[u8] = b"/lib64/ld-linux-x86-64.so.2";
+    assert_eq!(interpreter(&bytes), Some(expected));
+
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    assert_eq!(object.interpreter(), Some("/lib64/ld-linux-x86-64.so.2"));
+  }
+
+  #[test]
+  fn interpreter_edge_cases() {
+    // Zero file size.
+    let bytes = fixture(0, "'/lib/ld.so 00");
+    assert_eq!(interpreter(&bytes), Some(b"" as &[u8]));
+
+    // Missing trailing NUL.
+    let bytes = fixture(10, "'/lib/ld.so");
+    assert_eq!(interpreter(&bytes), Some(b"/lib/ld.so" as &[u8]));
+
+    // Outside the file.
+    let bytes = fixture(11, "'/lib/ld.so");
+    assert_eq!(interpreter(&bytes), None);
+
+    // Non UTF-8 bytes.
+    let bytes = fixture(5, "'/lib FF 00");
+    assert_eq!(interpreter(&bytes), Some(b"/lib\xFF" as &[u8]));
+
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    assert_eq!(object.interpreter(), None);
+  }
+
+  #[test]
+  fn stack_executable() {
+    let stack = |p_type: &str, p_flags: &str| {
+      let bytes = fixture(0, "");
+      let bytes = [
+        &bytes[..0x40],
+        &hex(p_type).unwrap(),
+        &hex(p_flags).unwrap(),
+        &bytes[0x48..],
+      ]
+      .concat();
+      let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+        unreachable!();
+      };
+
+      object.stack_executable()
+    };
+
+    assert_eq!(stack("51E57464", "06000000"), Some(false), "PF_R | PF_W");
+    assert_eq!(stack("51E57464", "07000000"), Some(true), "PF_R | PF_W | PF_X");
+    assert_eq!(stack("03000000", "04000000"), None, "No PT_GNU_STACK");
+  }
+
+  type Elf64 = ElfType64<LittleEndian>;
+
+  fn section(sh_type: u32, sh_flags: u64, sh_offset: u64, sh_addr: u64, sh_size: u64) -> ElfSection<Elf64> {
+    ElfSection {
+      sh_type: sh_type.into(),
+      sh_flags: sh_flags.into(),
+      sh_offset: sh_offset.into(),
+      sh_addr: sh_addr.into(),
+      sh_size: sh_size.into(),
+      ..Default::default()
+    }
+  }
+
+  fn segment(
+    p_type: u32,
+    p_offset: u64,
+    p_vaddr: u64,
+    p_filesz: u64,
+    p_memsz: u64,
+  ) -> Elf64ProgramHeader<LittleEndian> {
+    Elf64ProgramHeader {
+      p_type: p_type.into(),
+      p_offset: p_offset.into(),
+      p_vaddr: p_vaddr.into(),
+      p_filesz: p_filesz.into(),
+      p_memsz: p_memsz.into(),
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn zero_fill() {
+    use crate::elf::abi::sh_flags::SHF_WRITE;
+    use crate::elf::abi::sh_type::SHT_PROGBITS;
+    use crate::elf::builder::{BuilderSection, ElfBuilder};
+
+    let section = |name: &str, sh_type, sh_addr, size: u64| BuilderSection {
+      name: String::from(name),
+      sh_type,
+      sh_flags: SHF_ALLOC | SHF_WRITE,
+      sh_addr,
+      sh_addralign: 1,
+      data: vec![1; if sh_type == SHT_NOBITS { 0 } else { size as usize }],
+      size: Some(size),
+      ..Default::default()
+    };
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .object_type(abi::e_type::ET_EXEC)
+      .add_section(section(".data", SHT_PROGBITS, 0x1000, 0x10))
+      .add_section(section(".bss", SHT_NOBITS, 0x1010, 0x30))
+      .segment(PT_LOAD, PF_R | PF_W, &[1, 2])
+      .segment(PT_LOAD, PF_R, &[1])
+      .build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let tails = object.zero_fill();
+    assert_eq!(
+      tails,
+      [ZeroFill {
+        segment: 0,
+        range: 0x1010..0x1040,
+        sections: vec![Cow::Borrowed(".bss")],
+      }],
+      "The second segment has no tail"
+    );
+    assert_eq!(tails[0].size(), 0x30);
+    assert_eq!(object.zero_fill_bytes(), 0x30);
+
+    // p_filesz > p_memsz (invalid) does not underflow.
+    let header = segment(PT_LOAD, 0, 0x1000, 0x20, 0x10);
+    let segment = SegmentRef::<ElfType64<LittleEndian>> {
+      index: 0,
+      header: &header,
+    };
+    assert!(segment.zero_fill_range().is_empty());
+  }
+
+  #[test]
+  fn section_in_segment() {
+    const PROGBITS: u32 = 1;
+    const NOTE: u32 = 7;
+    use super::section_in_segment as contains;
+
+    let load = segment(PT_LOAD, 0x1000, 0x401000, 0x200, 0x400);
+    let tls = segment(PT_TLS, 0x1100, 0x401100, 0x10, 0x30);
+    let note = segment(PT_NOTE, 0x1000, 0x401000, 0x20, 0x20);
+
+    let text = section(PROGBITS, SHF_ALLOC, 0x1000, 0x401000, 0x100);
+    assert!(contains(&text, &load));
+    assert!(!contains(&text, &tls), "PT_TLS only holds SHF_TLS sections");
+
+    let tdata = section(PROGBITS, SHF_ALLOC | SHF_TLS, 0x1100, 0x401100, 0x10);
+    assert!(contains(&tdata, &load));
+    assert!(contains(&tdata, &tls));
+
+    let tbss = section(SHT_NOBITS, SHF_ALLOC | SHF_TLS, 0x1110, 0x401110, 0x20);
+    assert!(
+      !contains(&tbss, &load),
+      ".tbss occupies no space outside of PT_TLS"
+    );
+    assert!(contains(&tbss, &tls));
+
+    let bss = section(SHT_NOBITS, SHF_ALLOC, 0x1200, 0x401200, 0x200);
+    assert!(contains(&bss, &load), "Beyond p_filesz but within p_memsz");
+
+    let comment = section(PROGBITS, 0, 0x1000, 0, 0x10);
+    assert!(
+      !contains(&comment, &load),
+      "PT_LOAD only holds SHF_ALLOC sections"
+    );
+
+    let empty = section(NOTE, SHF_ALLOC, 0x1020, 0x401020, 0);
+    assert!(
+      !contains(&empty, &note),
+      "Zero-size section at the end of PT_NOTE"
+    );
+    assert!(contains(&empty, &load));
+  }
+}
@@ -0,0 +1,162 @@
+use alloc::borrow::Cow;
+
+use crate::alloc_prelude::*;
+use crate::error::ElfError;
+
+///
+/// A string table (`.strtab`, `.dynstr`, `.shstrtab`...) is a sequence of
+/// NUL-terminated strings referenced by their byte offset within the table.
+///
+/// Offsets come from the file itself and are therefore checked, a string
+/// without NUL before the end of the table is considered out of bounds.
+///
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct StringTable<'data>(&'data [u8]);
+
+impl<'data> From<&'data [u8]> for StringTable<'data> {
+  #[inline]
+  fn from(bytes: &'data [u8]) -> Self {
+    Self(bytes)
+  }
+}
+
+impl<'data> StringTable<'data> {
+  /// Returns the raw bytes of the table.
+  #[inline]
+  #[allow(unused)]
+  pub fn as_bytes(&self) -> &'data [u8] {
+    self.0
+  }
+
+  /// Returns the string at the given offset without its trailing NUL.
+  pub fn get(&self, offset: usize) -> Option<&'data [u8]> {
+    let bytes = self.0.get(offset..)?;
+    let end = bytes.iter().position(|&byte| byte == 0)?;
+    Some(&bytes[..end])
+  }
+
+  /// Same as [`Self::get()`] with an error, for names that must be found.
+  pub fn try_get(&self, offset: usize) -> Result<&'data [u8], ElfError> {
+    self.get(offset).ok_or(ElfError::InvalidStringOffset {
+      offset,
+      size: self.0.len(),
+    })
+  }
+
+  /// Same as [`Self::get()`] but `None` is also returned on invalid UTF-8.
+  #[inline]
+  pub fn get_str(&self, offset: usize) -> Option<&'data str> {
+    ::core::str::from_utf8(self.get(offset)?).ok()
+  }
+}
+
+///
+/// Same as [`String::from_utf8_lossy()`] but invalid bytes are escaped
+/// (`\xFF`) instead of being replaced by `U+FFFD`, so that they can still be
+/// told apart.
+///
+pub fn escape_lossy(bytes: &[u8]) -> Cow<'_, str> {
+  if let Ok(string) = ::core::str::from_utf8(bytes) {
+    return Cow::Borrowed(string);
+  }
+
+  let mut string = String::with_capacity(bytes.len());
+  for chunk in bytes.utf8_chunks() {
+    string.push_str(chunk.valid());
+    chunk
+      .invalid()
+      .iter()
+      .for_each(|byte| string.push_str(&format!("\\x{:02X}", byte)));
+  }
+
+  Cow::Owned(string)
+}
+
+///
+/// Escapes the control characters of a string read from the file: `\x1B`
+/// for ESC and the other ASCII ones, `\u{9B}` for the C1 ones (which some
+/// terminals also take as the start of an escape sequence). Garbage in a
+/// malformed file then cannot move the cursor, clear or color the terminal.
+///
+pub fn escape_controls(string: &str) -> Cow<'_, str> {
+  if !string.chars().any(char::is_control) {
+    return Cow::Borrowed(string);
+  }
+
+  let mut escaped = String::with_capacity(string.len() + 4);
+  for char in string.chars() {
+    match char {
+      char if char.is_ascii_control() => escaped.push_str(&format!("\\x{:02X}", char as u32)),
+      char if char.is_control() => escaped.push_str(&format!("\\u{{{:X}}}", char as u32)),
+      char => escaped.push(char),
+    }
+  }
+  Cow::Owned(escaped)
+}
+
+///
+/// Returns the printable form of bytes read from the file (names, comments,
+/// paths...): as [`escape_lossy()`], then [`escape_controls()`]. Every text
+/// renderer goes through one of them. The names are matched on their raw
+/// bytes, or on this form when given back by the user (see
+/// [`SectionRef::has_name()`](super::SectionRef::has_name())).
+///
+pub fn printable(bytes: &[u8]) -> Cow<'_, str> {
+  match escape_lossy(bytes) {
+    Cow::Borrowed(string) => escape_controls(string),
+    Cow::Owned(string) => match escape_controls(&string) {
+      Cow::Borrowed(_) => Cow::Owned(string),
+      Cow::Owned(escaped) => Cow::Owned(escaped),
+    },
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn get() {
+    let table = StringTable::from(b"\0abc\0de\xFF\0fg" as &[u8]);
+    assert_eq!(table.get(0), Some(b"" as &[u8]));
+    assert_eq!(table.get(1), Some(b"abc" as &[u8]));
+    assert_eq!(table.get(2), Some(b"bc" as &[u8]));
+    assert_eq!(table.get(5), Some(b"de\xFF" as &[u8]));
+    assert_eq!(table.get_str(5), None);
+    assert_eq!(table.get(9), None, "Missing NUL");
+    assert_eq!(table.get(42), None, "Out of bounds");
+
+    let error = ElfError::InvalidStringOffset { offset: 9, size: 11 };
+    assert_eq!(table.try_get(1), Ok(b"abc" as &[u8]));
+    assert_eq!(table.try_get(9), Err(error));
+  }
+
+  #[test]
+  fn escape_lossy() {
+    assert!(matches!(
+      super::escape_lossy(b"GCC: 13.2"),
+      Cow::Borrowed("GCC: 13.2")
+    ));
+    assert_eq!(super::escape_lossy(b"a\xFFb\xC3"), "a\\xFFb\\xC3");
+    assert_eq!(super::escape_lossy(b"\xC3\xA9t\xC3\xA9"), "été");
+  }
+
+  #[test]
+  fn escape_controls() {
+    assert!(matches!(super::escape_controls("GCC: 13.2"), Cow::Borrowed(_)));
+    assert_eq!(super::escape_controls("\x1B[2Jgone"), "\\x1B[2Jgone");
+    assert_eq!(super::escape_controls("a\nb\u{9B}c"), "a\\x0Ab\\u{9B}c");
+  }
+
+  #[test]
+  fn printable() {
+    assert!(matches!(super::printable(b".text"), Cow::Borrowed(".text")));
+    assert_eq!(super::printable(b".donn\xE9es"), ".donn\\xE9es", "Latin-1");
+    assert_eq!(super::printable(b"\x1B[31mred\x7F"), "\\x1B[31mred\\x7F");
+    assert_eq!(super::printable(b"caf\xC3\xA9\t"), "café\\x09");
+  }
+}
@@ -0,0 +1,1250 @@
+use ::core::fmt;
+use ::core::mem::size_of;
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+
+use super::relocations::DT_SYMTAB;
+use super::strings::printable;
+use super::*;
+use crate::endian::Endianness;
+use crate::error::{BytesError, ElfError, ResultExt};
+use crate::reader::Reader;
+use crate::utils::{define_constants, Constant};
+use elfprobe_macro::Pod;
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+pub use super::abi::sh_type::{SHT_DYNSYM, SHT_SYMTAB};
+
+pub use super::abi::st_shndx::{SHN_ABS, SHN_COMMON, SHN_UNDEF};
+
+define_constants! {
+  /// Symbol binding (high nibble of `st_info`).
+  pub st_bind: u8 {
+    STB_LOCAL = 0 => "Local symbol",
+    STB_GLOBAL = 1 => "Global symbol",
+    STB_WEAK = 2 => "Weak symbol",
+    STB_GNU_UNIQUE = 10 => "Unique symbol",
+    [STB_LOOS .. STB_HIOS] = 10 .. 12 => "OS-specific",
+    [STB_LOPROC .. STB_HIPROC] = 13 .. 15 => "Processor-specific",
+  }
+}
+
+define_constants! {
+  /// Symbol type (low nibble of `st_info`).
+  pub st_type: u8 {
+    STT_NOTYPE = 0 => "Symbol type is unspecified",
+    STT_OBJECT = 1 => "Symbol is a data object",
+    STT_FUNC = 2 => "Symbol is a code object",
+    STT_SECTION = 3 => "Symbol associated with a section",
+    STT_FILE = 4 => "Symbol's name is file name",
+    STT_COMMON = 5 => "Symbol is a common data object",
+    STT_TLS = 6 => "Symbol is a thread-local data object",
+    STT_GNU_IFUNC = 10 => "Symbol is an indirect code object",
+    [STT_LOOS .. STT_HIOS] = 10 .. 12 => "OS-specific",
+    [STT_LOPROC .. STT_HIPROC] = 13 .. 15 => "Processor-specific",
+  }
+}
+
+define_constants! {
+  /// Symbol visibility (low 2 bits of `st_other`).
+  pub st_visibility: u8 {
+    STV_DEFAULT = 0 => "Default symbol visibility rules",
+    STV_INTERNAL = 1 => "Processor specific hidden class",
+    STV_HIDDEN = 2 => "Symbol unavailable in other modules",
+    STV_PROTECTED = 3 => "Not preemptible, not exported",
+  }
+}
+
+pub use st_type::{STT_FILE, STT_SECTION, STT_TLS};
+
+///
+/// Returns the `st_shndx` column of readelf: `UND`, `ABS` and `COM` for the
+/// special indices, the band for other reserved ones (`PRC[0xff00]`,
+/// `OS [0xff20]`, `RSV[0xfff3]`) and the index itself otherwise.
+///
+pub fn section_index_name(index: u16) -> String {
+  use super::abi::st_shndx::*;
+
+  match index {
+    SHN_UNDEF => String::from("UND"),
+    SHN_ABS => String::from("ABS"),
+    SHN_COMMON => String::from("COM"),
+    SHN_XINDEX => String::from("XINDEX"),
+    SHN_LOPROC..=SHN_HIPROC => format!("PRC[{:#06x}]", index),
+    SHN_LOOS..=SHN_HIOS => format!("OS [{:#06x}]", index),
+    index if index >= SHN_LORESERVE => format!("RSV[{:#06x}]", index),
+    index => index.to_string(),
+  }
+}
+
+/// Returns the symbol binding of `st_info` (see [`st_bind`]).
+#[inline]
+pub fn st_bind(info: u8) -> u8 {
+  info >> 4
+}
+
+/// Returns the symbol type of `st_info` (see [`st_type`]).
+#[inline]
+pub fn st_type(info: u8) -> u8 {
+  info & 0xf
+}
+
+/// Returns the symbol visibility of `st_other` (see [`st_visibility`]).
+#[inline]
+pub fn st_visibility(other: u8) -> u8 {
+  other & 0x3
+}
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Pod)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(bound = ""))]
+pub struct Elf32Symbol<E: Endianness> {
+  pub st_name: Elf32_Word<E>,
+  pub st_value: Elf32_Addr<E>,
+  pub st_size: Elf32_Word<E>,
+  pub st_info: u8,
+  pub st_other: u8,
+  pub st_shndx: Elf32_Half<E>,
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone, Pod)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(bound = ""))]
+pub struct Elf64Symbol<E: Endianness> {
+  pub st_name: Elf64_Word<E>,
+  pub st_info: u8, // Moved here for alignment purposes.
+  pub st_other: u8,
+  pub st_shndx: Elf64_Half<E>,
+  pub st_value: Elf64_Addr<E>,
+  pub st_size: Elf64_Xword<E>,
+}
+
+///
+/// Symbol fields are not declared in the same order in elf32 and elf64 (the
+/// same as [`ProgramHeader`]), this trait gives a common access to both of
+/// them. Values and sizes are widened to 64 bits.
+///
+pub trait Symbol: Type {
+  fn st_name(&self) -> u32;
+  fn st_value(&self) -> u64;
+  fn st_size(&self) -> u64;
+  fn st_info(&self) -> u8;
+  fn st_other(&self) -> u8;
+  fn st_shndx(&self) -> u16;
+
+  /// Sets `st_shndx` (see [`Writer`](crate::writer::Writer)).
+  fn set_st_shndx(&mut self, value: u16);
+
+  /// Returns the symbol binding (high nibble of `st_info`).
+  #[inline]
+  fn st_bind(&self) -> u8 {
+    st_bind(self.st_info())
+  }
+
+  /// Returns the symbol type (low nibble of `st_info`).
+  #[inline]
+  fn st_type(&self) -> u8 {
+    st_type(self.st_info())
+  }
+
+  /// Returns the symbol visibility (low 2 bits of `st_other`).
+  #[inline]
+  fn st_visibility(&self) -> u8 {
+    st_visibility(self.st_other())
+  }
+}
+
+macro_rules! impl_symbol {
+  ($struct: ident) => {
+    impl<E: Endianness> Symbol for $struct<E> {
+      #[inline]
+      fn st_name(&self) -> u32 {
+        self.st_name.get()
+      }
+
+      #[inline]
+      fn st_value(&self) -> u64 {
+        self.st_value.into()
+      }
+
+      #[inline]
+      fn st_size(&self) -> u64 {
+        self.st_size.into()
+      }
+
+      #[inline]
+      fn st_info(&self) -> u8 {
+        self.st_info
+      }
+
+      #[inline]
+      fn st_other(&self) -> u8 {
+        self.st_other
+      }
+
+      #[inline]
+      fn st_shndx(&self) -> u16 {
+        self.st_shndx.get()
+      }
+
+      #[inline]
+      fn set_st_shndx(&mut self, value: u16) {
+        self.st_shndx.set(value);
+      }
+    }
+  };
+}
+
+impl_symbol!(Elf32Symbol);
+impl_symbol!(Elf64Symbol);
+
+///
+/// A symbol table (`.symtab` or `.dynsym`) with the string table holding
+/// the symbol names (given by the `sh_link` of the table section).
+///
+#[derive(Debug)]
+pub struct SymbolTable<'data, ElfType: self::ElfType> {
+  pub symbols: Vec<&'data ElfType::Symbol>,
+  pub names: StringTable<'data>,
+}
+
+// Not derived, which would require `ElfType: Clone`.
+impl<ElfType: self::ElfType> Clone for SymbolTable<'_, ElfType> {
+  fn clone(&self) -> Self {
+    Self {
+      symbols: self.symbols.clone(),
+      names: self.names,
+    }
+  }
+}
+
+impl<'data, ElfType: self::ElfType> SymbolTable<'data, ElfType> {
+  /// Returns the raw name of the given symbol, `None` when out of the string table.
+  pub fn name_bytes(&self, symbol: &ElfType::Symbol) -> Option<&'data [u8]> {
+    self.names.get(symbol.st_name() as usize)
+  }
+
+  /// Returns the printable name of the given symbol, see [`printable()`].
+  pub fn display_name(&self, symbol: &ElfType::Symbol) -> Option<Cow<'data, str>> {
+    self.name_bytes(symbol).map(printable)
+  }
+
+  ///
+  /// Returns the symbols of the selection with their index. The filters are
+  /// applied on the fly, the symbols are only collected to be sorted.
+  ///
+  pub fn select<'table>(
+    &'table self,
+    selection: &'table SymbolSelection,
+  ) -> Box<dyn Iterator<Item = (usize, &'data ElfType::Symbol)> + 'table> {
+    let selected = self
+      .symbols
+      .iter()
+      .copied()
+      .enumerate()
+      .filter(|(index, symbol)| selection.matches(*index, *symbol));
+
+    let name = |symbol: &ElfType::Symbol| self.names.get(symbol.st_name() as usize).unwrap_or_default();
+    let mut symbols: Vec<_> = match selection.order {
+      SymbolOrder::Index => return Box::new(selected),
+      _ => selected.collect(),
+    };
+    symbols.sort_by(|(index, symbol), (other_index, other)| {
+      let key = match selection.order {
+        SymbolOrder::Value => symbol.st_value().cmp(&other.st_value()),
+        SymbolOrder::Size => symbol.st_size().cmp(&other.st_size()),
+        _ => ::core::cmp::Ordering::Equal,
+      };
+      key
+        .then_with(|| name(symbol).cmp(name(other)))
+        .then(index.cmp(other_index))
+    });
+    Box::new(symbols.into_iter())
+  }
+
+  /// Returns the display of the symbols of the selection, as [`SymbolTable`] display.
+  pub fn listing<'table>(
+    &'table self,
+    selection: &'table SymbolSelection,
+  ) -> SymbolListing<'table, 'data, ElfType> {
+    SymbolListing {
+      table: self,
+      selection,
+    }
+  }
+
+  ///
+  /// Returns the defined symbol covering the given address (from `st_value`
+  /// up to `st_size` bytes, or exactly at `st_value` for unsized symbols) and
+  /// the offset of the address from it. Section and file symbols are skipped,
+  /// as well as unnamed ones. The closest one wins if several symbols match.
+  ///
+  pub fn nearest(&self, address: u64) -> Option<(&'data ElfType::Symbol, u64)> {
+    self
+      .symbols
+      .iter()
+      .filter(|symbol| symbol.st_shndx() != SHN_UNDEF && symbol.st_name() != 0)
+      .filter(|symbol| !matches!(symbol.st_type(), STT_SECTION | STT_FILE))
+      .filter(|symbol| {
+        let offset = address.wrapping_sub(symbol.st_value());
+        symbol.st_value() <= address && (offset == 0 || offset < symbol.st_size())
+      })
+      .max_by_key(|symbol| symbol.st_value())
+      .map(|symbol| (*symbol, address - symbol.st_value()))
+  }
+}
+
+///
+/// A symbol with its index in the symbol table and its name, already
+/// resolved through the string table of the table section (`sh_link`).
+/// See [`ElfObject::symbols()`] and [`ElfObject::dynamic_symbols()`].
+///
+#[derive(Debug, Copy, Clone)]
+pub struct SymbolRef<'data, ElfType: self::ElfType> {
+  pub index: usize,
+  pub symbol: &'data ElfType::Symbol,
+  name: &'data [u8],
+}
+
+/// Serialized as its index, printable name (JSON strings are UTF-8) and entry.
+#[cfg(feature = "serde")]
+impl<ElfType: self::ElfType> serde::Serialize for SymbolRef<'_, ElfType>
+where
+  ElfType::Symbol: serde::Serialize,
+{
+  fn serialize<Serializer: serde::Serializer>(
+    &self,
+    serializer: Serializer,
+  ) -> Result<Serializer::Ok, Serializer::Error> {
+    use serde::ser::SerializeStruct;
+
+    let mut state = serializer.serialize_struct("Symbol", 3)?;
+    state.serialize_field("index", &self.index)?;
+    state.serialize_field("name", &self.display_name())?;
+    state.serialize_field("symbol", self.symbol)?;
+    state.end()
+  }
+}
+
+impl<'data, ElfType: self::ElfType> SymbolRef<'data, ElfType> {
+  /// Returns the raw name (empty for unnamed symbols).
+  #[inline]
+  pub fn name_bytes(&self) -> &'data [u8] {
+    self.name
+  }
+
+  /// Returns the printable name, see [`printable()`].
+  #[inline]
+  pub fn display_name(&self) -> Cow<'data, str> {
+    printable(self.name)
+  }
+
+  #[inline]
+  pub fn value(&self) -> u64 {
+    self.symbol.st_value()
+  }
+
+  #[inline]
+  pub fn size(&self) -> u64 {
+    self.symbol.st_size()
+  }
+
+  #[inline]
+  pub fn binding(&self) -> Constant<u8> {
+    st_bind::from(self.symbol.st_bind())
+  }
+
+  #[inline]
+  pub fn symbol_type(&self) -> Constant<u8> {
+    st_type::from(self.symbol.st_type())
+  }
+
+  #[inline]
+  pub fn visibility(&self) -> Constant<u8> {
+    st_visibility::from(self.symbol.st_visibility())
+  }
+
+  /// Returns `st_shndx`, see [`section_index_name()`] for special indices.
+  #[inline]
+  pub fn section_index(&self) -> u16 {
+    self.symbol.st_shndx()
+  }
+}
+
+/// Symbol information left in an object, see [`ElfObject::is_stripped()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SymbolInfo {
+  /// Full symbol table (`.symtab`), not stripped.
+  Symtab,
+  /// Only the dynamic symbols needed at run time (`.dynsym`).
+  DynsymOnly,
+  /// No symbol information at all.
+  None,
+}
+
+///
+/// Whether an object is stripped: the symbol tables left and the presence
+/// of debugging sections (`.debug_*`).
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct StripStatus {
+  pub symbols: SymbolInfo,
+  pub debug_info: bool,
+}
+
+impl StripStatus {
+  /// Returns true without `.symtab` (as `file` says "stripped").
+  pub fn stripped(&self) -> bool {
+    self.symbols != SymbolInfo::Symtab
+  }
+}
+
+/// Symbols kept in a listing (`--only`), see [`SymbolSelection`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SymbolFilter {
+  Defined,
+  Undefined,
+  /// `STB_GLOBAL` and `STB_GNU_UNIQUE`.
+  Global,
+  Local,
+  Weak,
+  /// `STT_FUNC` and `STT_GNU_IFUNC`.
+  Func,
+  /// `STT_OBJECT`, `STT_COMMON` and `STT_TLS`.
+  Object,
+}
+
+impl SymbolFilter {
+  pub const NAMES: [&'static str; 7] = [
+    "defined",
+    "undefined",
+    "global",
+    "local",
+    "weak",
+    "func",
+    "object",
+  ];
+
+  /// Returns the filter with the given name, see [`Self::NAMES`].
+  pub fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "defined" => Self::Defined,
+      "undefined" => Self::Undefined,
+      "global" => Self::Global,
+      "local" => Self::Local,
+      "weak" => Self::Weak,
+      "func" => Self::Func,
+      "object" => Self::Object,
+      _ => return None,
+    })
+  }
+
+  /// Returns `true` when the symbol is kept.
+  pub fn matches(self, symbol: &impl Symbol) -> bool {
+    use st_bind::*;
+    use st_type::*;
+
+    match self {
+      Self::Defined => symbol.st_shndx() != SHN_UNDEF,
+      Self::Undefined => symbol.st_shndx() == SHN_UNDEF,
+      Self::Global => matches!(symbol.st_bind(), STB_GLOBAL | STB_GNU_UNIQUE),
+      Self::Local => symbol.st_bind() == STB_LOCAL,
+      Self::Weak => symbol.st_bind() == STB_WEAK,
+      Self::Func => matches!(symbol.st_type(), STT_FUNC | STT_GNU_IFUNC),
+      Self::Object => matches!(symbol.st_type(), STT_OBJECT | STT_COMMON | STT_TLS),
+    }
+  }
+}
+
+/// Order of a listing (`--sort`), see [`SymbolSelection`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum SymbolOrder {
+  /// Table order, the symbols are not collected.
+  #[default]
+  Index,
+  Name,
+  Value,
+  Size,
+}
+
+impl SymbolOrder {
+  /// Returns the order with the given name (`name`, `value` or `size`).
+  pub fn from_name(name: &str) -> Option<Self> {
+    Some(match name {
+      "name" => Self::Name,
+      "value" => Self::Value,
+      "size" => Self::Size,
+      _ => return None,
+    })
+  }
+}
+
+///
+/// The symbols of a listing and their order, shared by the views so that
+/// they agree. A symbol is kept when it matches all the filters, the null
+/// symbol (index 0) only when there is none. Ties are broken by name then
+/// index, so that the output is deterministic.
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SymbolSelection {
+  pub filters: Vec<SymbolFilter>,
+  pub order: SymbolOrder,
+}
+
+impl SymbolSelection {
+  /// Returns `true` when every symbol is kept in table order.
+  pub fn is_all(&self) -> bool {
+    *self == Self::default()
+  }
+
+  /// Returns `true` when the symbol at the given index is kept.
+  pub fn matches(&self, index: usize, symbol: &impl Symbol) -> bool {
+    self.filters.is_empty() || (index != 0 && self.filters.iter().all(|filter| filter.matches(symbol)))
+  }
+}
+
+///
+/// A symbol table displayed with a [`SymbolSelection`], see
+/// [`SymbolTable::listing()`].
+///
+#[derive(Debug)]
+pub struct SymbolListing<'table, 'data, ElfType: self::ElfType> {
+  table: &'table SymbolTable<'data, ElfType>,
+  selection: &'table SymbolSelection,
+}
+
+///
+/// The source files named by the `STT_FILE` symbols of `.symtab` (the
+/// translation units of the link), sorted and deduplicated, with the number
+/// of symbols attributed to each. See [`ElfObject::source_files()`].
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceFiles<'data> {
+  pub files: BTreeMap<Cow<'data, str>, usize>,
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the first symbol table of the given type (`SHT_SYMTAB` or
+  /// `SHT_DYNSYM`), `None` if there is none. Entries are `sh_entsize` bytes
+  /// apart, a trailing partial entry is ignored.
+  ///
+  /// The table is parsed once and cached in the object, errors are not.
+  ///
+  pub fn symbol_table(&self, sh_type: u32) -> Result<Option<SymbolTable<'data, ElfType>>, ElfError> {
+    let cache = match sh_type {
+      SHT_SYMTAB => &self.symbol_tables[0],
+      SHT_DYNSYM => &self.symbol_tables[1],
+      _ => return self.parse_symbol_table(sh_type),
+    };
+
+    if let Some(table) = cache.get() {
+      return Ok(table.clone());
+    }
+    let table = self.parse_symbol_table(sh_type)?;
+    Ok(cache.get_or_init(|| table).clone())
+  }
+
+  /// Parses the symbol table, see [`Self::symbol_table()`].
+  fn parse_symbol_table(&self, sh_type: u32) -> Result<Option<SymbolTable<'data, ElfType>>, ElfError> {
+    let sections = self.section_headers()?;
+    let Some(section) = sections
+      .iter()
+      .find(|section| section.sh_type() == sh_type as u64)
+    else {
+      return Ok(None);
+    };
+
+    // Names are missing in lenient mode rather than the whole table.
+    let names = match sections.get(section.sh_link() as usize) {
+      Some(strings) => StringTable::from(self.section_bytes(strings)?),
+      None => {
+        let error = Err(ElfError::InvalidSectionIndex(section.sh_link() as usize));
+        let context = "resolving the string table of the symbol table (sh_link)";
+        self.recover(error.context(context), StringTable::default())?
+      }
+    };
+
+    // Smaller entries would overlap, they are read as if packed in lenient mode.
+    let bytes = self.section_bytes(section)?;
+    let entsize = match section.sh_entsize() as usize {
+      0 => size_of::<ElfType::Symbol>(),
+      entsize if entsize < size_of::<ElfType::Symbol>() => {
+        let error = ElfError::EntrySizeMismatch {
+          table: "symbol table",
+          entsize: entsize as u64,
+          expected: size_of::<ElfType::Symbol>(),
+        };
+        self.recover(Err(error), size_of::<ElfType::Symbol>())?
+      }
+      entsize => entsize,
+    };
+
+    self.check_time_budget()?;
+    self
+      .options()
+      .limits
+      .check_entries((bytes.len() / entsize) as u64)?;
+    let symbols = (0..bytes.len() / entsize)
+      .map(|index| bytes.read_pod::<ElfType::Symbol>(index * entsize))
+      .collect::<Result<_, _>>()
+      .with_context(|| format!("reading the symbol table at offset {:#x}", section.sh_offset()))?;
+
+    Ok(Some(SymbolTable { symbols, names }))
+  }
+
+  ///
+  /// Returns an iterator over the symbols of `.symtab` (index 0 included),
+  /// empty when there is no such table. Entries are read on the fly, an
+  /// error is yielded for a name out of the string table.
+  ///
+  pub fn symbols(&self) -> impl Iterator<Item = Result<SymbolRef<'data, ElfType>, ElfError>> + '_ {
+    self.symbol_refs(SHT_SYMTAB)
+  }
+
+  /// Same as [`Self::symbols()`] for `.dynsym`.
+  pub fn dynamic_symbols(&self) -> impl Iterator<Item = Result<SymbolRef<'data, ElfType>, ElfError>> + '_ {
+    self.symbol_refs(SHT_DYNSYM)
+  }
+
+  fn symbol_refs(
+    &self,
+    sh_type: u32,
+  ) -> impl Iterator<Item = Result<SymbolRef<'data, ElfType>, ElfError>> + '_ {
+    let table = || -> Result<(&'data [u8], StringTable<'data>, usize), ElfError> {
+      let Some(section) = self
+        .sections()
+        .find(|section| section.header.sh_type() == sh_type as u64)
+      else {
+        return Ok((&[], StringTable::default(), 1));
+      };
+
+      let link = section.header.sh_link() as usize;
+      let names = match self.sections().nth(link) {
+        Some(strings) => StringTable::from(self.section_bytes(strings.header)?),
+        None => StringTable::default(),
+      };
+
+      let entsize = match section.header.sh_entsize() as usize {
+        0 => size_of::<ElfType::Symbol>(),
+        entsize => entsize.max(size_of::<ElfType::Symbol>()),
+      };
+
+      let bytes = self.section_bytes(section.header)?;
+      self.check_time_budget()?;
+      self
+        .options()
+        .limits
+        .check_entries((bytes.len() / entsize) as u64)?;
+      Ok((bytes, names, entsize))
+    };
+
+    // A table out of the file is reported once, as the only item.
+    let (error, (bytes, names, entsize)) = match table() {
+      Ok(table) => (None, table),
+      Err(error) => (Some(Err(error)), (&[] as &[u8], StringTable::default(), 1)),
+    };
+
+    error
+      .into_iter()
+      .chain((0..bytes.len() / entsize).map(move |index| {
+        let symbol = bytes.read_pod::<ElfType::Symbol>(index * entsize)?;
+        let name = match symbol.st_name() {
+          0 => Some(&[] as &[u8]),
+          st_name => names.get(st_name as usize),
+        };
+
+        Ok(SymbolRef {
+          index,
+          symbol,
+          name: name.ok_or(ElfError::InvalidSymbolName {
+            index,
+            st_name: symbol.st_name(),
+          })?,
+        })
+      }))
+  }
+
+  ///
+  /// Returns the name of the symbol covering the given address and the
+  /// offset from it (see [`SymbolTable::nearest()`]), looked up in `.symtab`
+  /// or in `.dynsym` when the object is stripped.
+  ///
+  pub fn nearest_symbol(&self, address: u64) -> Option<(Cow<'data, str>, u64)> {
+    [SHT_SYMTAB, SHT_DYNSYM].into_iter().find_map(|sh_type| {
+      let table = self.symbol_table(sh_type).ok()??;
+      let (symbol, offset) = table.nearest(address)?;
+      Some((table.display_name(symbol)?, offset))
+    })
+  }
+
+  ///
+  /// Returns the source files of `.symtab`, `None` without the table. As by
+  /// convention, the symbols following an `STT_FILE` entry up to the next one
+  /// belong to its file, section symbols aside. A file named twice (one entry
+  /// per object in a relocatable link) adds up, and an unnamed entry (written
+  /// by some linkers after the local symbols) ends the attribution.
+  ///
+  pub fn source_files(&self) -> Option<SourceFiles<'data>> {
+    self.symbol_table(SHT_SYMTAB).ok()??;
+
+    let mut files = BTreeMap::new();
+    let mut current = None;
+    for symbol in self.symbols().skip(1).filter_map(Result::ok) {
+      match symbol.symbol.st_type() {
+        STT_FILE => {
+          current = (!symbol.name_bytes().is_empty()).then(|| symbol.display_name());
+          if let Some(name) = &current {
+            files.entry(name.clone()).or_insert(0);
+          }
+        }
+        STT_SECTION => {}
+        _ => {
+          if let Some(name) = &current {
+            *files.entry(name.clone()).or_insert(0) += 1;
+          }
+        }
+      }
+    }
+
+    Some(SourceFiles { files })
+  }
+
+  ///
+  /// Returns whether the object is stripped, as `file` does by looking for a
+  /// `SHT_SYMTAB` section. When the section header table itself is missing,
+  /// the dynamic symbol table is still found through `DT_SYMTAB`.
+  ///
+  pub fn is_stripped(&self) -> StripStatus {
+    let sections = self.section_headers().unwrap_or_default();
+    let has = |sh_type: u32| sections.iter().any(|section| section.sh_type() == sh_type as u64);
+
+    let dynamic = self.dynamic().unwrap_or_default();
+    let symbols = if has(SHT_SYMTAB) {
+      SymbolInfo::Symtab
+    } else if has(SHT_DYNSYM) || dynamic.iter().any(|entry| entry.d_tag() == DT_SYMTAB) {
+      SymbolInfo::DynsymOnly
+    } else {
+      SymbolInfo::None
+    };
+
+    let debug_info = sections.iter().any(|section| {
+      let name = self.section_name(section).unwrap_or_default();
+      name.starts_with(".debug_") || name.starts_with(".zdebug_")
+    });
+
+    StripStatus { symbols, debug_info }
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+/// Returns the name of a constant without its prefix (`FUNC`, `GLOBAL`...).
+fn short_name(constant: impl ToString, prefix: &str) -> String {
+  let name = constant.to_string();
+  let name = name.strip_prefix(prefix).unwrap_or(&name);
+  String::from(name.strip_prefix("GNU_").unwrap_or(name))
+}
+
+///
+/// Displays the table as `readelf --syms` (same columns, same names), the
+/// names are demangled with the alternate flag (`{:#}`, as `--demangle`).
+///
+impl<ElfType: self::ElfType> fmt::Display for SymbolTable<'_, ElfType> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fmt::Display::fmt(&self.listing(&SymbolSelection::default()), formatter)
+  }
+}
+
+/// Same as the [`SymbolTable`] display, the symbols of the selection only.
+impl<ElfType: self::ElfType> fmt::Display for SymbolListing<'_, '_, ElfType> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use crate::utils::{display_row, Demangled, Style, TableBuilder};
+
+    let demangle = formatter.alternate();
+    let name = |symbol| {
+      let name = self.table.display_name(symbol).unwrap_or_default();
+      match demangle {
+        true => Demangled(&name).to_string(),
+        false => name.into_owned(),
+      }
+    };
+
+    // Colored when enabled (terminal output), local symbols are dimmed.
+    let bind = |symbol: &ElfType::Symbol| {
+      let style = match symbol.st_bind() {
+        st_bind::STB_GLOBAL => Style::Green,
+        st_bind::STB_WEAK => Style::Yellow,
+        st_bind::STB_LOCAL => Style::Dim,
+        _ => Style::Magenta,
+      };
+      style.paint(short_name(st_bind::from(symbol.st_bind()), "STB_"))
+    };
+
+    // Long names are cut first to fit the line width.
+    let width = size_of::<ElfType::Addr>() * 2;
+    let mut table = TableBuilder::new();
+    table.shrink(&[7]);
+    display_row!(
+      table,
+      ["Num:", "Value", "Size", "Type", "Bind", "Vis", "Ndx", "Name"]
+    );
+    for (index, symbol) in self.table.select(self.selection) {
+      display_row!(
+        table,
+        [
+          format!("{}:", index),
+          format!("{:0width$x}", symbol.st_value(), width = width),
+          symbol.st_size(),
+          short_name(st_type::from(symbol.st_type()), "STT_"),
+          bind(symbol),
+          short_name(st_visibility::from(symbol.st_visibility()), "STV_"),
+          section_index_name(symbol.st_shndx()),
+          name(symbol),
+        ]
+      );
+    }
+
+    table.finish(formatter)
+  }
+}
+
+/// Table of the files and their symbol counts, then the number of files.
+impl fmt::Display for SourceFiles<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    use crate::utils::display_table;
+
+    if self.files.is_empty() {
+      return writeln!(formatter, "No STT_FILE symbols in .symtab.");
+    }
+
+    display_table!(
+      formatter,
+      align [Left, Right],
+      ["File", "Symbols"],
+      (file, count) in &self.files => [file, count]
+    )?;
+    writeln!(formatter)?;
+    writeln!(formatter, "Source files: {}", self.files.len())
+  }
+}
+
+impl fmt::Display for StripStatus {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.debug_info {
+      write!(formatter, "with debug_info, ")?;
+    }
+
+    match self.symbols {
+      SymbolInfo::Symtab => write!(formatter, "not stripped"),
+      SymbolInfo::DynsymOnly => write!(formatter, "stripped"),
+      SymbolInfo::None => write!(formatter, "stripped (no symbols)"),
+    }
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+  use crate::utils::TableBuilder;
+
+  #[test]
+  fn symbol_memory_size() {
+    assert_eq!(size_of::<Elf32Symbol<BigEndian>>(), 16);
+    assert_eq!(size_of::<Elf64Symbol<BigEndian>>(), 24);
+    assert_eq!(size_of::<Elf32Symbol<LittleEndian>>(), 16);
+    assert_eq!(size_of::<Elf64Symbol<LittleEndian>>(), 24);
+  }
+
+  #[test]
+  fn nearest() {
+    let bytes = hex(
+      r"
+        01000000 12 00 0100 0010000000000000 1000000000000000 ; main, FUNC
+        06000000 11 00 0200 0020000000000000 0800000000000000 ; data, OBJECT
+        0B000000 03 00 0100 0010000000000000 0000000000000000 ; SECTION
+        0B000000 12 00 0000 0018000000000000 0000000000000000 ; UNDEF
+      ",
+    )
+    .unwrap();
+
+    let table = SymbolTable::<ElfType64<LittleEndian>> {
+      symbols: (0..4).map(|index| bytes.read_pod(index * 24).unwrap()).collect(),
+      names: StringTable::from(b"\0main\0data\0puts\0" as &[u8]),
+    };
+
+    let name = |address| {
+      let (symbol, offset) = table.nearest(address)?;
+      Some((table.display_name(symbol)?, offset))
+    };
+
+    assert_eq!(name(0x1000), Some(("main".into(), 0)));
+    assert_eq!(name(0x100c), Some(("main".into(), 0xc)));
+    assert_eq!(name(0x1800), None, "Undefined symbol skipped");
+    assert_eq!(name(0x2004), Some(("data".into(), 0x4)));
+    assert_eq!(name(0x2008), None, "Out of the symbol");
+    assert_eq!(name(0x0fff), None);
+  }
+
+  #[test]
+  fn symbol_info() {
+    assert_eq!(st_bind(0x12), st_bind::STB_GLOBAL);
+    assert_eq!(st_type(0x12), st_type::STT_FUNC);
+    assert_eq!(st_type(0x1a), st_type::STT_GNU_IFUNC);
+    assert_eq!(st_visibility(0xfe), st_visibility::STV_HIDDEN);
+    assert_eq!(st_bind::from(10).to_string(), "STB_GNU_UNIQUE");
+    assert_eq!(st_type::from(14).to_string(), "STT_LOPROC+0x1");
+  }
+
+  #[test]
+  fn display() {
+    let bytes = hex(
+      r"
+        00000000 00000000 00000000 00 00 0000 ; NULL
+        01000000 00100000 10000000 12 00 0100 ; main, GLOBAL FUNC
+        06000000 00000000 00000000 1A 02 0000 ; puts, GLOBAL IFUNC HIDDEN
+        0B000000 04000000 08000000 21 03 F1FF ; value, WEAK OBJECT PROTECTED ABS
+      ",
+    )
+    .unwrap();
+
+    let table = SymbolTable::<ElfType32<LittleEndian>> {
+      symbols: (0..4).map(|index| bytes.read_pod(index * 16).unwrap()).collect(),
+      names: StringTable::from(b"\0main\0puts\0value\0" as &[u8]),
+    };
+
+    assert_eq!(
+      table.to_string(),
+      concat!(
+        "Num:  Value     Size  Type    Bind    Vis        Ndx  Name\n",
+        "0:    00000000  0     NOTYPE  LOCAL   DEFAULT    UND\n",
+        "1:    00001000  16    FUNC    GLOBAL  DEFAULT    1    main\n",
+        "2:    00000000  0     IFUNC   GLOBAL  HIDDEN     UND  puts\n",
+        "3:    00000004  8     OBJECT  WEAK    PROTECTED  ABS  value\n",
+      )
+    );
+  }
+
+  #[test]
+  fn selection() {
+    let bytes = hex(
+      r"
+        00000000 00000000 00000000 00 00 0000 ; NULL
+        01000000 00200000 10000000 12 00 0100 ; main, GLOBAL FUNC
+        06000000 00000000 00000000 12 00 0000 ; puts, GLOBAL FUNC UND
+        0B000000 00300000 08000000 21 00 0200 ; value, WEAK OBJECT
+        11000000 00100000 10000000 02 00 0100 ; local, LOCAL FUNC
+        01000000 00100000 04000000 12 00 0100 ; main, GLOBAL FUNC (again)
+      ",
+    )
+    .unwrap();
+    let table = SymbolTable::<ElfType32<LittleEndian>> {
+      symbols: (0..6).map(|index| bytes.read_pod(index * 16).unwrap()).collect(),
+      names: StringTable::from(b"\0main\0puts\0value\0local\0" as &[u8]),
+    };
+
+    let select = |filters: &[&str], order| {
+      let selection = SymbolSelection {
+        filters: filters
+          .iter()
+          .map(|name| SymbolFilter::from_name(name).unwrap())
+          .collect(),
+        order,
+      };
+      table
+        .select(&selection)
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(select(&[], SymbolOrder::Index), [0, 1, 2, 3, 4, 5]);
+    assert_eq!(
+      select(&["undefined"], SymbolOrder::Index),
+      [2],
+      "Null symbol skipped"
+    );
+    assert_eq!(select(&["defined", "func"], SymbolOrder::Index), [1, 4, 5]);
+    assert_eq!(select(&["global"], SymbolOrder::Index), [1, 2, 5]);
+    assert_eq!(select(&["weak", "object"], SymbolOrder::Index), [3]);
+    assert_eq!(select(&["local"], SymbolOrder::Index), [4]);
+
+    // Ties broken by name then index.
+    assert_eq!(select(&[], SymbolOrder::Name), [0, 4, 1, 5, 2, 3]);
+    assert_eq!(select(&[], SymbolOrder::Value), [0, 2, 4, 5, 1, 3]);
+    assert_eq!(select(&["defined"], SymbolOrder::Size), [5, 3, 4, 1]);
+    assert_eq!(SymbolOrder::from_name("address"), None);
+
+    let selection = SymbolSelection {
+      filters: vec![SymbolFilter::Func, SymbolFilter::Defined],
+      order: SymbolOrder::Size,
+    };
+    assert_eq!(
+      table.listing(&selection).to_string(),
+      concat!(
+        "Num:  Value     Size  Type  Bind    Vis      Ndx  Name\n",
+        "5:    00001000  4     FUNC  GLOBAL  DEFAULT  1    main\n",
+        "4:    00001000  16    FUNC  LOCAL   DEFAULT  1    local\n",
+        "1:    00002000  16    FUNC  GLOBAL  DEFAULT  1    main\n",
+      )
+    );
+
+    // The row limit applies once sorted and filtered: the smallest symbol, then the count of the kept ones.
+    TableBuilder::set_row_limit(Some(1));
+    let listing = table.listing(&selection).to_string();
+    TableBuilder::set_row_limit(None);
+    assert_eq!(
+      listing,
+      concat!(
+        "Num:  Value     Size  Type  Bind    Vis      Ndx  Name\n",
+        "5:    00001000  4     FUNC  GLOBAL  DEFAULT  1    main\n",
+        "… 2 more rows (use --no-limit)\n",
+      )
+    );
+  }
+
+  #[test]
+  fn display_demangled() {
+    let bytes = hex("01000000 00100000 10000000 12 00 0100 ; _Z3fooi, GLOBAL FUNC").unwrap();
+    let table = SymbolTable::<ElfType32<LittleEndian>> {
+      symbols: vec![bytes.read_pod(0).unwrap()],
+      names: StringTable::from(b"\0_Z3fooi\0" as &[u8]),
+    };
+
+    assert!(table.to_string().ends_with("  _Z3fooi\n"));
+    assert!(format!("{:#}", table).ends_with("  foo(int)\n"));
+  }
+
+  #[test]
+  fn section_index_name() {
+    assert_eq!(super::section_index_name(0), "UND");
+    assert_eq!(super::section_index_name(12), "12");
+    assert_eq!(super::section_index_name(0xff00), "PRC[0xff00]");
+    assert_eq!(super::section_index_name(0xff21), "OS [0xff21]");
+    assert_eq!(super::section_index_name(0xfff3), "RSV[0xfff3]");
+    assert_eq!(super::section_index_name(0xffff), "XINDEX");
+  }
+
+  ///
+  /// ELF64 little-endian relocatable with a `.symtab` of five symbols (null,
+  /// `counter`, `limit`, `ext` and an unnamed one) and no `.dynsym`.
+  ///
+  #[rustfmt::skip]
+  fn fixture() -> Vec<u8> {
+    hex(
+      r"
+        7F 'ELF 02 01 01 00 00 000000 00000000 ; ELFCLASS64, ELFDATA2LSB
+        0100 3E00 01000000 0000000000000000 ; ET_REL, EM_X86_64, e_version, e_entry
+        0000000000000000 4000000000000000 00000000 ; e_phoff, e_shoff, e_flags
+        4000 3800 0000 4000 0400 0300 ; e_ehsize, e_phentsize, e_phnum, e_shentsize, e_shnum, e_shstrndx
+
+        ; Section headers (null, .symtab, .strtab, .shstrtab)
+        00000000 00000000 0000000000000000 0000000000000000 0000000000000000
+        0000000000000000 00000000 00000000 0000000000000000 0000000000000000
+        01000000 02000000 0000000000000000 0000000000000000 4001000000000000
+        7800000000000000 02000000 01000000 0800000000000000 1800000000000000
+        09000000 03000000 0000000000000000 0000000000000000 B801000000000000
+        1300000000000000 00000000 00000000 0100000000000000 0000000000000000
+        11000000 03000000 0000000000000000 0000000000000000 CB01000000000000
+        1B00000000000000 00000000 00000000 0100000000000000 0000000000000000
+
+        ; .symtab
+        00000000 00 00 0000 0000000000000000 0000000000000000
+        01000000 11 00 F2FF 0400000000000000 0400000000000000 ; counter, GLOBAL OBJECT, SHN_COMMON
+        09000000 10 00 F1FF 2A00000000000000 0000000000000000 ; limit, GLOBAL NOTYPE, SHN_ABS
+        0F000000 10 00 0000 0000000000000000 0000000000000000 ; ext, GLOBAL NOTYPE, SHN_UNDEF
+        00000000 00 00 00FF 0000000000000000 0000000000000000 ; SHN_LOPROC
+
+        00 'counter 00 'limit 00 'ext 00 ; .strtab
+        00 '.symtab 00 '.strtab 00 '.shstrtab 00
+      ",
+    )
+    .unwrap()
+
+  }
+
+  #[test]
+  fn common_symbols() {
+    let bytes = fixture();
+    let Ok(ElfFile::Elf64Le(object)) = parse_elf(bytes.as_slice()) else {
+      panic!("Not an elf64 little-endian");
+    };
+
+    let table = object.symbol_table(SHT_SYMTAB).unwrap().unwrap();
+    let output = table.to_string();
+    let columns = output
+      .lines()
+      .skip(1)
+      .map(|line| line.split_whitespace().skip(6).collect::<Vec<_>>().join(" "))
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      columns,
+      ["UND", "COM counter", "ABS limit", "UND ext", "PRC[0xff00]"]
+    );
+  }
+
+  #[test]
+  fn source_files() {
+    use crate::elf::abi::sh_type::SHT_STRTAB;
+    use crate::elf::builder::{BuilderSection, ElfBuilder};
+
+    // (name, st_info): two objects of a relocatable link, `b.c` twice.
+    let entries: [(&str, u8); 9] = [
+      ("b.c", 0x04),
+      ("", 0x03), // Section symbol.
+      ("helper", 0x02),
+      ("a.c", 0x04),
+      ("counter", 0x01),
+      ("b.c", 0x04),
+      ("state", 0x01),
+      ("", 0x04), // End of the attribution.
+      ("main", 0x12),
+    ];
+    let mut strtab = vec![0];
+    let mut symtab = vec![0; 24];
+    for (name, st_info) in entries {
+      let st_name = if name.is_empty() { 0 } else { strtab.len() as u32 };
+      symtab.extend(st_name.to_le_bytes());
+      symtab.extend([st_info, 0, 1, 0]);
+      symtab.extend([0; 16]);
+      strtab.extend(name.as_bytes().iter().chain([&0]));
+    }
+
+    let build = |symtab: Vec<u8>| {
+      ElfBuilder::<ElfType64<LittleEndian>>::new()
+        .add_section(BuilderSection {
+          name: String::from(".symtab"),
+          sh_type: SHT_SYMTAB,
+          sh_link: 2,
+          sh_addralign: 8,
+          sh_entsize: 24,
+          data: symtab,
+          ..Default::default()
+        })
+        .section(".strtab", SHT_STRTAB, &strtab)
+        .build()
+    };
+
+    let bytes = build(symtab);
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    let files = object.source_files().unwrap();
+    assert_eq!(
+      files
+        .files
+        .iter()
+        .map(|(file, &count)| (file.as_ref(), count))
+        .collect::<Vec<_>>(),
+      [("a.c", 1), ("b.c", 2)]
+    );
+    assert!(files.to_string().ends_with("\nSource files: 2\n"));
+
+    let bytes = build(vec![0; 24]);
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(
+      object.source_files().unwrap().to_string(),
+      "No STT_FILE symbols in .symtab.\n"
+    );
+
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new().build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(object.source_files(), None);
+  }
+
+  #[test]
+  fn is_stripped() {
+    // No section header table nor dynamic table.
+    let bytes = hex(
+      r"
+        7F 'ELF 02 01 01 00 00 00000000 000000 ; e_ident
+        0200 3E00 01000000 0000000000000000 ; ET_EXEC, EM_X86_64, e_version, e_entry
+        0000000000000000 0000000000000000 00000000 ; e_phoff, e_shoff, e_flags
+        4000 3800 0000 4000 0000 0000 ; e_ehsize, e_phentsize, e_phnum, e_shentsize, e_shnum, e_shstrndx
+      ",
+    )
+    .unwrap();
+
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let status = object.is_stripped();
+    assert_eq!(status.symbols, SymbolInfo::None);
+    assert!(status.stripped() && !status.debug_info);
+    assert_eq!(status.to_string(), "stripped (no symbols)");
+
+    let status = StripStatus {
+      symbols: SymbolInfo::Symtab,
+      debug_info: true,
+    };
+
+    assert!(!status.stripped());
+    assert_eq!(status.to_string(), "with debug_info, not stripped");
+  }
+
+  #[test]
+  fn symbols() {
+    let mut bytes = fixture();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let symbols = object.symbols().collect::<Result<Vec<_>, _>>().unwrap();
+    let names = symbols
+      .iter()
+      .map(|symbol| symbol.display_name())
+      .collect::<Vec<_>>();
+    assert_eq!(names, ["", "counter", "limit", "ext", ""]);
+
+    let counter = &symbols[1];
+    assert_eq!((counter.index, counter.value(), counter.size()), (1, 4, 4));
+    assert_eq!(counter.binding().name(), Some("STB_GLOBAL"));
+    assert_eq!(counter.symbol_type().name(), Some("STT_OBJECT"));
+    assert_eq!(counter.visibility().name(), Some("STV_DEFAULT"));
+    assert_eq!(counter.section_index(), SHN_COMMON);
+    assert_eq!(object.dynamic_symbols().count(), 0, "No .dynsym");
+
+    bytes[0x188] = 0x40; // st_name of ext out of .strtab
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let symbols = object.symbols().collect::<Vec<_>>();
+    assert_eq!(symbols.len(), 5);
+    assert!(symbols[2].is_ok() && symbols[4].is_ok());
+    assert_eq!(
+      symbols[3].as_ref().unwrap_err(),
+      &ElfError::InvalidSymbolName {
+        index: 3,
+        st_name: 0x40
+      }
+    );
+  }
+}
@@ -0,0 +1,273 @@
+use alloc::borrow::Cow;
+
+use super::sections::SHN_LORESERVE;
+use super::sections::{SHF_TLS, SHT_NOBITS};
+use super::segments::PT_TLS;
+use super::symbols::{SHN_UNDEF, STT_TLS};
+use super::*;
+use crate::reader::Reader;
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+///
+/// The `PT_TLS` segment: the TLS initialization image is the first `filesz`
+/// bytes (`.tdata`), the remaining `memsz - filesz` bytes are zero-filled
+/// (`.tbss`).
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TlsSegment {
+  pub vaddr: u64,
+  pub filesz: u64,
+  pub memsz: u64,
+  pub align: u64,
+}
+
+impl TlsSegment {
+  /// Returns the size of the zero-filled part of the TLS block.
+  pub fn bss_size(&self) -> u64 {
+    self.memsz.saturating_sub(self.filesz)
+  }
+}
+
+///
+/// A `SHF_TLS` section (`.tdata`, `.tbss`...), `offset` is relative to the
+/// start of the TLS block.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsSection<'data> {
+  pub name: Cow<'data, str>,
+  pub address: u64,
+  pub offset: u64,
+  pub size: u64,
+  /// `SHT_NOBITS` section (`.tbss`), only occupies memory in the TLS block.
+  pub nobits: bool,
+}
+
+/// A `STT_TLS` symbol and its offset into the TLS block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsSymbol<'data> {
+  pub name: Cow<'data, str>,
+  pub offset: u64,
+  pub size: u64,
+  /// Name of the section holding the symbol (`.tdata` or `.tbss`).
+  pub section: Option<Cow<'data, str>>,
+}
+
+///
+/// Thread-local storage template: the `PT_TLS` segment, the `SHF_TLS`
+/// sections and the `STT_TLS` symbols (sorted by offset).
+///
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TlsLayout<'data> {
+  pub segment: Option<TlsSegment>,
+  pub sections: Vec<TlsSection<'data>>,
+  pub symbols: Vec<TlsSymbol<'data>>,
+}
+
+// ╔═╗┌┐  ┬┌─┐┌─┐┌┬┐
+// ║ ║├┴┐ │├┤ │   │
+// ╚═╝└─┘└┘└─┘└─┘ ┴
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  ///
+  /// Returns the thread-local storage layout, `None` if the object has
+  /// neither `PT_TLS` segment nor `SHF_TLS` section.
+  ///
+  /// Section offsets are relative to `p_vaddr` (or to the lowest `SHF_TLS`
+  /// section without segment). `.tbss` shares its address range with the
+  /// sections following it, as it is absent from the file and only extends
+  /// `p_memsz`: it is never merged with them nor counted in the
+  /// initialization image.
+  ///
+  /// In executables and shared objects, the `st_value` of a `STT_TLS` symbol
+  /// is already its offset into the TLS block.
+  ///
+  pub fn tls_layout(&self) -> Option<TlsLayout<'data>> {
+    let segment = self
+      .program_headers()
+      .unwrap_or_default()
+      .into_iter()
+      .find(|header| header.p_type() == PT_TLS)
+      .map(|header| TlsSegment {
+        vaddr: header.p_vaddr(),
+        filesz: header.p_filesz(),
+        memsz: header.p_memsz(),
+        align: header.p_align(),
+      });
+
+    let headers = self.section_headers().unwrap_or_default();
+    let tls = headers
+      .iter()
+      .filter(|section| section.sh_flags() & SHF_TLS != 0)
+      .collect::<Vec<_>>();
+
+    if segment.is_none() && tls.is_empty() {
+      return None;
+    }
+
+    let start = match segment {
+      Some(segment) => segment.vaddr,
+      None => tls.iter().map(|section| section.sh_addr()).min().unwrap_or(0),
+    };
+
+    let sections = tls
+      .iter()
+      .map(|section| TlsSection {
+        name: self.section_name(section).unwrap_or_default(),
+        address: section.sh_addr(),
+        offset: section.sh_addr().wrapping_sub(start),
+        size: section.sh_size(),
+        nobits: section.sh_type() == SHT_NOBITS as u64,
+      })
+      .collect();
+
+    let mut symbols = Vec::new();
+    let table = [SHT_SYMTAB, SHT_DYNSYM]
+      .into_iter()
+      .find_map(|sh_type| self.symbol_table(sh_type).ok().flatten());
+
+    if let Some(table) = &table {
+      for symbol in &table.symbols {
+        if symbol.st_type() != STT_TLS || symbol.st_shndx() == SHN_UNDEF {
+          continue;
+        }
+
+        symbols.push(TlsSymbol {
+          name: table.display_name(symbol).unwrap_or_default(),
+          offset: symbol.st_value(),
+          size: symbol.st_size(),
+          section: match symbol.st_shndx() {
+            index if index >= SHN_LORESERVE => None,
+            index => headers
+              .get(index as usize)
+              .and_then(|section| self.section_name(section)),
+          },
+        });
+      }
+    }
+
+    symbols.sort_by_key(|symbol| symbol.offset);
+
+    Some(TlsLayout {
+      segment,
+      sections,
+      symbols,
+    })
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+
+  #[test]
+  fn tls_layout() {
+    #[rustfmt::skip]
+    let bytes = hex(
+      r"
+        7F 'ELF 02 01 01 00 00 000000 00000000 ; ELFCLASS64, ELFDATA2LSB
+        0300 3E00 01000000 0000000000000000 ; ET_DYN, EM_X86_64, e_version, e_entry
+        4000000000000000 B000000000000000 00000000 ; e_phoff, e_shoff, e_flags
+        4000 3800 0100 4000 0700 0600 ; e_ehsize, e_phentsize, e_phnum, e_shentsize, e_shnum, e_shstrndx
+
+        ; PT_TLS (vaddr 0x3000, filesz 0x10, memsz 0x18, align 8)
+        07000000 04000000 7800000000000000 0030000000000000 0030000000000000
+        1000000000000000 1800000000000000 0800000000000000
+
+        ; .tdata
+        2A00000000000000 0700000000000000
+
+        ; Padding
+        0000000000000000 0000000000000000 0000000000000000 0000000000000000 0000000000000000
+
+        ; Section headers (null, .tdata, .tbss, .init_array, .symtab, .strtab, .shstrtab)
+        00000000 00000000 0000000000000000 0000000000000000 0000000000000000
+        0000000000000000 00000000 00000000 0000000000000000 0000000000000000
+        01000000 01000000 0304000000000000 0030000000000000 7800000000000000
+        1000000000000000 00000000 00000000 0800000000000000 0000000000000000
+        08000000 08000000 0304000000000000 1030000000000000 8800000000000000
+        0800000000000000 00000000 00000000 0800000000000000 0000000000000000
+        0E000000 0E000000 0300000000000000 1030000000000000 8800000000000000
+        0800000000000000 00000000 00000000 0800000000000000 0800000000000000
+        1A000000 02000000 0000000000000000 0000000000000000 7002000000000000
+        6000000000000000 05000000 00000000 0800000000000000 1800000000000000
+        22000000 03000000 0000000000000000 0000000000000000 D002000000000000
+        1200000000000000 00000000 00000000 0100000000000000 0000000000000000
+        2A000000 03000000 0000000000000000 0000000000000000 E202000000000000
+        3400000000000000 00000000 00000000 0100000000000000 0000000000000000
+
+        ; .symtab (null, counter, buffer, main)
+        00000000 00 00 0000 0000000000000000 0000000000000000
+        01000000 16 00 0200 1000000000000000 0800000000000000
+        09000000 16 00 0100 0800000000000000 0800000000000000
+        0D000000 12 00 0000 0010000000000000 0000000000000000
+
+        00 'counter 00 'buf 00 'main 00 ; .strtab
+        00 '.tdata 00 '.tbss 00 '.init_array 00 '.symtab 00 '.strtab 00 '.shstrtab 00
+      ",
+    )
+    .unwrap();
+
+    let Ok(ElfFile::Elf64Le(object)) = parse_elf(bytes.as_slice()) else {
+      panic!("Not an elf64 little-endian");
+    };
+
+    let layout = object.tls_layout().unwrap();
+    let segment = layout.segment.unwrap();
+    assert_eq!(
+      (segment.vaddr, segment.filesz, segment.memsz),
+      (0x3000, 0x10, 0x18)
+    );
+    assert_eq!(segment.bss_size(), 8);
+
+    // .init_array shares its address with .tbss but is not a TLS section.
+    assert_eq!(
+      layout.sections,
+      vec![
+        TlsSection {
+          name: ".tdata".into(),
+          address: 0x3000,
+          offset: 0,
+          size: 0x10,
+          nobits: false,
+        },
+        TlsSection {
+          name: ".tbss".into(),
+          address: 0x3010,
+          offset: 0x10,
+          size: 8,
+          nobits: true,
+        },
+      ]
+    );
+
+    assert_eq!(
+      layout.symbols,
+      vec![
+        TlsSymbol {
+          name: "buf".into(),
+          offset: 0x8,
+          size: 8,
+          section: Some(".tdata".into()),
+        },
+        TlsSymbol {
+          name: "counter".into(),
+          offset: 0x10,
+          size: 8,
+          section: Some(".tbss".into()),
+        },
+      ]
+    );
+  }
+}
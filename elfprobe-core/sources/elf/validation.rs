@@ -0,0 +1,1213 @@
+use ::core::fmt;
+use ::core::mem::{offset_of, size_of};
+
+use super::abi::e_type::{ET_NONE, ET_REL};
+use super::abi::e_version::EV_CURRENT;
+use super::abi::ei_class::{ELFCLASS32, ELFCLASS64};
+use super::abi::ei_data::{ELFDATA2LSB, ELFDATA2MSB};
+use super::sections::{SHF_EXECINSTR, SHN_XINDEX, SHT_NOBITS};
+use super::segments::{PN_XNUM, PT_INTERP, PT_LOAD, PT_PHDR};
+use super::*;
+use crate::reader::Reader;
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+/// Magic number (`ei_mag0` to `ei_mag3`).
+pub const ELFMAG: [u8; 4] = [0x7f, b'E', b'L', b'F'];
+
+///
+/// Offset of a program header field, the fields are not declared in the same
+/// order in elf32 and elf64 (the endianness does not change the layout).
+///
+macro_rules! program_header_offset {
+  ($elf_type: ty, $field: ident) => {
+    match <$elf_type>::CLASS == ELFCLASS64 {
+      true => offset_of!(Elf64ProgramHeader<LittleEndian>, $field),
+      false => offset_of!(Elf32ProgramHeader<LittleEndian>, $field),
+    }
+  };
+}
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+/// How much a [`ValidationIssue`] compromises the parsing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(rename_all = "lowercase"))]
+pub enum Severity {
+  /// Unusual value that the parsing copes with (e.g. `e_version`).
+  Warning,
+  /// Value that makes the related structures unreadable (e.g. `e_phoff`).
+  Error,
+}
+
+///
+/// A field whose value is not the expected one, `offset` is the file offset
+/// of the field so that it can be found in a hex editor, `code` identifies
+/// the check (see [`Diagnostic`] for the JSON outputs).
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+  pub severity: Severity,
+  pub code: DiagnosticCode,
+  pub field: &'static str,
+  pub offset: usize,
+  pub actual: u64,
+  pub expected: String,
+}
+
+impl ValidationIssue {
+  fn new(
+    severity: Severity,
+    code: DiagnosticCode,
+    field: &'static str,
+    offset: usize,
+    actual: u64,
+    expected: String,
+  ) -> Self {
+    Self {
+      severity,
+      code,
+      field,
+      offset,
+      actual,
+      expected,
+    }
+  }
+}
+
+// ╦┌┬┐┌─┐┌┐┌┌┬┐┬┌─┐┬┌─┐┌─┐┌┬┐┬┌─┐┌┐┌
+// ║ ││├┤ │││ │ │├┤ ││  ├─┤ │ ││ ││││
+// ╩─┴┘└─┘┘└┘ ┴ ┴└  ┴└─┘┴ ┴ ┴ ┴└─┘┘└┘
+
+impl<ElfType: self::ElfType> ElfIdentification<ElfType> {
+  ///
+  /// Checks the magic number, the class, the data encoding, the version and
+  /// the padding. Non-zero padding does not prevent parsing but is a common
+  /// marker of packed or hand-edited files, one issue is reported per byte.
+  ///
+  pub fn validate(&self) -> Vec<ValidationIssue> {
+    use Severity::*;
+
+    let mut issues = Vec::new();
+    let mut check = |valid: bool, severity, code, field, offset, actual: u64, expected: String| {
+      if !valid {
+        issues.push(ValidationIssue::new(
+          severity, code, field, offset, actual, expected,
+        ));
+      }
+    };
+
+    let magic = [self.ei_mag0, self.ei_mag1, self.ei_mag2, self.ei_mag3];
+    let fields = ["ei_mag0", "ei_mag1", "ei_mag2", "ei_mag3"];
+    for (offset, ((byte, expected), field)) in magic.into_iter().zip(ELFMAG).zip(fields).enumerate() {
+      let byte: u64 = byte.into();
+      check(
+        byte == expected as u64,
+        Error,
+        DiagnosticCode::BadMagic,
+        field,
+        offset,
+        byte,
+        format!("{:#04x} (\\x7fELF)", expected),
+      );
+    }
+
+    let ei_class: u64 = self.ei_class.into();
+    let ei_data: u64 = self.ei_data.into();
+    let ei_version: u64 = self.ei_version.into();
+
+    check(
+      ei_class == ELFCLASS32 as u64 || ei_class == ELFCLASS64 as u64,
+      Error,
+      DiagnosticCode::BadClass,
+      "ei_class",
+      offset_of!(Self, ei_class),
+      ei_class,
+      format!("ELFCLASS32 ({}) or ELFCLASS64 ({})", ELFCLASS32, ELFCLASS64),
+    );
+
+    check(
+      ei_data == ELFDATA2LSB as u64 || ei_data == ELFDATA2MSB as u64,
+      Error,
+      DiagnosticCode::BadDataEncoding,
+      "ei_data",
+      offset_of!(Self, ei_data),
+      ei_data,
+      format!("ELFDATA2LSB ({}) or ELFDATA2MSB ({})", ELFDATA2LSB, ELFDATA2MSB),
+    );
+
+    check(
+      ei_version == EV_CURRENT as u64,
+      Warning,
+      DiagnosticCode::BadIdentVersion,
+      "ei_version",
+      offset_of!(Self, ei_version),
+      ei_version,
+      format!("EV_CURRENT ({})", EV_CURRENT),
+    );
+
+    for (index, byte) in self.ei_pad.into_iter().enumerate() {
+      let byte: u64 = byte.into();
+      check(
+        byte == 0,
+        Warning,
+        DiagnosticCode::NonZeroPadding,
+        "ei_pad",
+        offset_of!(Self, ei_pad) + index,
+        byte,
+        String::from("0 (padding)"),
+      );
+    }
+
+    issues
+  }
+}
+
+// ╦ ╦┌─┐┌─┐┌┬┐┌─┐┬─┐
+// ╠═╣├┤ ├─┤ ││├┤ ├┬┘
+// ╩ ╩└─┘┴ ┴─┴┘└─┘┴└─
+
+impl<ElfType: self::ElfType> ElfHeader<ElfType> {
+  ///
+  /// Checks the header fields against the class of the file and the file
+  /// size, nothing is fatal by itself: the caller decides which issues to
+  /// reject. The extended counts and index (`PN_XNUM`, `e_shnum == 0` and
+  /// `SHN_XINDEX`) are not resolved, hence not checked.
+  ///
+  pub fn validate(&self, file_size: u64) -> Vec<ValidationIssue> {
+    use Severity::*;
+
+    // `e_ident` is at the start of the header, its offsets are unchanged.
+    let mut issues = self.e_ident.validate();
+    let mut check = |valid: bool, severity, code, field, offset, actual: u64, expected: String| {
+      if !valid {
+        issues.push(ValidationIssue::new(
+          severity, code, field, offset, actual, expected,
+        ));
+      }
+    };
+
+    let e_type: u64 = self.e_type.into();
+    let e_version: u64 = self.e_version.into();
+    let e_phoff: u64 = self.e_phoff.into();
+    let e_shoff: u64 = self.e_shoff.into();
+    let e_ehsize: u64 = self.e_ehsize.into();
+    let e_phentsize: u64 = self.e_phentsize.into();
+    let e_phnum: u64 = self.e_phnum.into();
+    let e_shentsize: u64 = self.e_shentsize.into();
+    let e_shnum: u64 = self.e_shnum.into();
+    let e_shstrndx: u64 = self.e_shstrndx.into();
+
+    let phentsize = size_of::<ElfType::ProgramHeader>() as u64;
+    let shentsize = size_of::<ElfSection<ElfType>>() as u64;
+
+    check(
+      e_type != ET_NONE as u64,
+      Warning,
+      DiagnosticCode::NoFileType,
+      "e_type",
+      offset_of!(ElfHeader<ElfType>, e_type),
+      e_type,
+      String::from("not ET_NONE"),
+    );
+
+    check(
+      e_version == EV_CURRENT as u64,
+      Warning,
+      DiagnosticCode::BadVersion,
+      "e_version",
+      offset_of!(ElfHeader<ElfType>, e_version),
+      e_version,
+      format!("EV_CURRENT ({})", EV_CURRENT),
+    );
+
+    check(
+      e_ehsize == size_of::<Self>() as u64,
+      Warning,
+      DiagnosticCode::BadHeaderSize,
+      "e_ehsize",
+      offset_of!(ElfHeader<ElfType>, e_ehsize),
+      e_ehsize,
+      format!("{} (header size)", size_of::<Self>()),
+    );
+
+    // Entry sizes only matter when there are entries.
+    check(
+      e_phnum == 0 || e_phentsize == phentsize,
+      Error,
+      DiagnosticCode::BadProgramHeaderSize,
+      "e_phentsize",
+      offset_of!(ElfHeader<ElfType>, e_phentsize),
+      e_phentsize,
+      format!("{} (program header size)", phentsize),
+    );
+
+    check(
+      e_shoff == 0 || e_shentsize == shentsize,
+      Error,
+      DiagnosticCode::BadSectionHeaderSize,
+      "e_shentsize",
+      offset_of!(ElfHeader<ElfType>, e_shentsize),
+      e_shentsize,
+      format!("{} (section header size)", shentsize),
+    );
+
+    // The whole table must be in the file, not only its start.
+    let end = |offset: u64, count: u64, size: u64| count.checked_mul(size)?.checked_add(offset);
+
+    check(
+      e_phnum == 0 || end(e_phoff, e_phnum, e_phentsize).is_some_and(|end| end <= file_size),
+      Error,
+      DiagnosticCode::TruncatedProgramHeaders,
+      "e_phoff",
+      offset_of!(ElfHeader<ElfType>, e_phoff),
+      e_phoff,
+      format!("program headers within the file ({} bytes)", file_size),
+    );
+
+    check(
+      e_shoff == 0 || end(e_shoff, e_shnum.max(1), e_shentsize).is_some_and(|end| end <= file_size),
+      Error,
+      DiagnosticCode::TruncatedSectionHeaders,
+      "e_shoff",
+      offset_of!(ElfHeader<ElfType>, e_shoff),
+      e_shoff,
+      format!("section headers within the file ({} bytes)", file_size),
+    );
+
+    check(
+      e_shnum == 0 || e_shstrndx == SHN_XINDEX as u64 || e_shstrndx < e_shnum,
+      Error,
+      DiagnosticCode::BadStringTableIndex,
+      "e_shstrndx",
+      offset_of!(ElfHeader<ElfType>, e_shstrndx),
+      e_shstrndx,
+      format!("less than e_shnum ({})", e_shnum),
+    );
+
+    issues
+  }
+}
+
+impl<'data, Reader, ElfType> ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  /// Validates the header against the file size, see [`ElfHeader::validate()`].
+  pub fn validate_header(&self) -> Vec<ValidationIssue> {
+    self.header.validate(self.data.length() as u64)
+  }
+
+  ///
+  /// Checks that the null entry (index 0) of the section header table is all
+  /// zeros, but the fields holding the extended numbering: `sh_size` when
+  /// `e_shnum` is 0, `sh_link` when `e_shstrndx` is `SHN_XINDEX` and `sh_info`
+  /// when `e_phnum` is `PN_XNUM`. One issue per unexpected non-zero field,
+  /// garbage left there is a common artifact of hand-crafted binaries.
+  ///
+  pub fn validate_null_section(&self) -> Vec<ValidationIssue> {
+    let Some(section) = self.initial_section() else {
+      return Vec::new();
+    };
+    let e_shoff: u64 = self.header.e_shoff.into();
+    let e_shnum: u64 = self.header.e_shnum.into();
+    let e_shstrndx: u64 = self.header.e_shstrndx.into();
+    let e_phnum: u64 = self.header.e_phnum.into();
+
+    // The header field telling whether the extended numbering is in use.
+    let fields = [
+      (
+        "sh_name",
+        offset_of!(ElfSection<ElfType>, sh_name),
+        section.sh_name(),
+        None,
+      ),
+      (
+        "sh_type",
+        offset_of!(ElfSection<ElfType>, sh_type),
+        section.sh_type(),
+        None,
+      ),
+      (
+        "sh_flags",
+        offset_of!(ElfSection<ElfType>, sh_flags),
+        section.sh_flags(),
+        None,
+      ),
+      (
+        "sh_addr",
+        offset_of!(ElfSection<ElfType>, sh_addr),
+        section.sh_addr(),
+        None,
+      ),
+      (
+        "sh_offset",
+        offset_of!(ElfSection<ElfType>, sh_offset),
+        section.sh_offset(),
+        None,
+      ),
+      (
+        "sh_size",
+        offset_of!(ElfSection<ElfType>, sh_size),
+        section.sh_size(),
+        Some(("e_shnum", e_shnum == 0)),
+      ),
+      (
+        "sh_link",
+        offset_of!(ElfSection<ElfType>, sh_link),
+        section.sh_link(),
+        Some(("e_shstrndx", e_shstrndx == SHN_XINDEX as u64)),
+      ),
+      (
+        "sh_info",
+        offset_of!(ElfSection<ElfType>, sh_info),
+        section.sh_info(),
+        Some(("e_phnum", e_phnum == PN_XNUM as u64)),
+      ),
+      (
+        "sh_addralign",
+        offset_of!(ElfSection<ElfType>, sh_addralign),
+        section.sh_addralign(),
+        None,
+      ),
+      (
+        "sh_entsize",
+        offset_of!(ElfSection<ElfType>, sh_entsize),
+        section.sh_entsize(),
+        None,
+      ),
+    ];
+
+    let mut issues = Vec::new();
+    for (field, offset, actual, extended) in fields {
+      let expected = match extended {
+        _ if actual == 0 => continue,
+        Some((_, true)) => continue,
+        Some((header_field, false)) => format!("0 in [0] (null), {} is not extended", header_field),
+        None => String::from("0 in [0] (null)"),
+      };
+      let offset = e_shoff.saturating_add(offset as u64) as usize;
+      issues.push(ValidationIssue::new(
+        Severity::Warning,
+        DiagnosticCode::NonZeroNullSection,
+        field,
+        offset,
+        actual,
+        expected,
+      ));
+    }
+    issues
+  }
+
+  ///
+  /// Checks that the contents of the sections (but `SHT_NOBITS` ones) lie
+  /// within the file and do not overlap, one issue per overlapping section
+  /// (reported on its `sh_offset` field, against the preceding section in
+  /// file order which reaches the furthest). readelf silently tolerates
+  /// overlaps, a classic trick of malformed binaries.
+  ///
+  pub fn validate_sections(&self) -> Vec<ValidationIssue> {
+    let file_size = self.data.length() as u64;
+    let e_shoff: u64 = self.header.e_shoff.into();
+    let e_shentsize: u64 = self.header.e_shentsize.into();
+
+    // Offset of the `sh_offset` field of the given section header.
+    let field = |index: usize| {
+      let header = (index as u64).saturating_mul(e_shentsize).saturating_add(e_shoff);
+      header.saturating_add(offset_of!(ElfSection<ElfType>, sh_offset) as u64) as usize
+    };
+    let name = |index: usize, name: Option<alloc::borrow::Cow<str>>| {
+      format!("[{}] {}", index, name.as_deref().unwrap_or("<invalid>"))
+    };
+
+    let mut issues = Vec::new();
+    let mut ranges = Vec::new();
+    for section in self.sections().skip(1) {
+      let header = section.header;
+      if header.sh_type() == SHT_NOBITS as u64 || header.sh_size() == 0 {
+        continue;
+      }
+
+      let (start, size) = (header.sh_offset(), header.sh_size());
+      match start.checked_add(size).filter(|&end| end <= file_size) {
+        Some(end) => ranges.push((
+          start,
+          end,
+          section.index,
+          name(section.index, section.display_name()),
+        )),
+        None => issues.push(ValidationIssue::new(
+          Severity::Error,
+          DiagnosticCode::TruncatedSection,
+          "sh_offset",
+          field(section.index),
+          start,
+          format!(
+            "{} ({:#x} bytes) within the file ({} bytes)",
+            name(section.index, section.display_name()),
+            size,
+            file_size
+          ),
+        )),
+      }
+    }
+
+    // Sweep in file order, comparing each section with the previous one
+    // reaching the furthest: one issue per section at most, so that
+    // thousands of headers over the same range stay linear.
+    ranges.sort_by_key(|&(start, end, index, _)| (start, end, index));
+    let mut furthest: Option<&(u64, u64, usize, String)> = None;
+    for range in &ranges {
+      let (start, end, index, ref name) = *range;
+      match furthest {
+        Some(&(_, other_end, _, ref other)) if other_end > start => {
+          issues.push(ValidationIssue::new(
+            Severity::Warning,
+            DiagnosticCode::OverlappingSections,
+            "sh_offset",
+            field(index),
+            start,
+            format!(
+              "{} not to overlap {} (bytes {:#x}..{:#x})",
+              name,
+              other,
+              start,
+              end.min(other_end)
+            ),
+          ));
+          if end > other_end {
+            furthest = Some(range);
+          }
+        }
+        _ => furthest = Some(range),
+      }
+    }
+
+    issues
+  }
+
+  ///
+  /// Checks the program headers: the contents within the file, `p_filesz`
+  /// not greater than `p_memsz`, `p_align` a power of two (or zero) with
+  /// `p_vaddr` and `p_offset` congruent modulo `p_align` for `PT_LOAD`.
+  ///
+  /// `PT_PHDR` and `PT_INTERP` must precede the `PT_LOAD` segments, the
+  /// former must match the program header table and be loaded, and is
+  /// expected when there is an interpreter (which locates the table with it).
+  ///
+  pub fn validate_segments(&self) -> Vec<ValidationIssue> {
+    use Severity::*;
+
+    let file_size = self.data.length() as u64;
+    let e_phoff: u64 = self.header.e_phoff.into();
+    let e_phentsize: u64 = self.header.e_phentsize.into();
+    let e_phnum: u64 = self.header.e_phnum.into();
+    let table_size = e_phnum.saturating_mul(e_phentsize);
+
+    let mut issues = Vec::new();
+    let mut check = |valid: bool, severity, code, field, offset: u64, actual: u64, expected: String| {
+      if !valid {
+        issues.push(ValidationIssue::new(
+          severity,
+          code,
+          field,
+          offset as usize,
+          actual,
+          expected,
+        ));
+      }
+    };
+
+    let segments: Vec<_> = self.segments().collect();
+    let loads: Vec<_> = segments
+      .iter()
+      .filter(|segment| segment.header.p_type() == PT_LOAD)
+      .collect();
+    let first_load = loads.first().map_or(usize::MAX, |segment| segment.index);
+
+    for segment in &segments {
+      let header = segment.header;
+      let base = (segment.index as u64)
+        .saturating_mul(e_phentsize)
+        .saturating_add(e_phoff);
+      let field = |offset: usize| base.saturating_add(offset as u64);
+      let name = format!("[{}] {}", segment.index, header.type_name(self.osabi()));
+      let (p_type, p_offset, p_filesz, p_memsz) = (
+        header.p_type(),
+        header.p_offset(),
+        header.p_filesz(),
+        header.p_memsz(),
+      );
+      let (p_vaddr, p_align) = (header.p_vaddr(), header.p_align());
+
+      check(
+        p_filesz == 0 || p_offset.checked_add(p_filesz).is_some_and(|end| end <= file_size),
+        Error,
+        DiagnosticCode::TruncatedSegment,
+        "p_offset",
+        field(program_header_offset!(ElfType, p_offset)),
+        p_offset,
+        format!(
+          "{} ({:#x} bytes) within the file ({} bytes)",
+          name, p_filesz, file_size
+        ),
+      );
+
+      check(
+        p_filesz <= p_memsz,
+        Error,
+        DiagnosticCode::FileSizeExceedsMemorySize,
+        "p_filesz",
+        field(program_header_offset!(ElfType, p_filesz)),
+        p_filesz,
+        format!("at most p_memsz ({:#x}) of {}", p_memsz, name),
+      );
+
+      check(
+        p_align == 0 || p_align.is_power_of_two(),
+        Warning,
+        DiagnosticCode::BadSegmentAlignment,
+        "p_align",
+        field(program_header_offset!(ElfType, p_align)),
+        p_align,
+        format!("zero or a power of two for {}", name),
+      );
+
+      check(
+        p_type != PT_LOAD || !p_align.is_power_of_two() || p_vaddr % p_align == p_offset % p_align,
+        Error,
+        DiagnosticCode::MisalignedSegment,
+        "p_vaddr",
+        field(program_header_offset!(ElfType, p_vaddr)),
+        p_vaddr,
+        format!(
+          "congruent to p_offset ({:#x}) modulo p_align ({:#x}) for {}",
+          p_offset, p_align, name
+        ),
+      );
+
+      if p_type == PT_PHDR || p_type == PT_INTERP {
+        check(
+          segment.index < first_load,
+          Error,
+          DiagnosticCode::MisorderedSegment,
+          "p_type",
+          field(program_header_offset!(ElfType, p_type)),
+          p_type as u64,
+          format!("{} before any PT_LOAD", name),
+        );
+      }
+
+      if p_type == PT_PHDR {
+        check(
+          p_offset == e_phoff,
+          Error,
+          DiagnosticCode::ProgramHeaderMismatch,
+          "p_offset",
+          field(program_header_offset!(ElfType, p_offset)),
+          p_offset,
+          format!("e_phoff ({:#x}) for {}", e_phoff, name),
+        );
+
+        check(
+          p_filesz >= table_size,
+          Error,
+          DiagnosticCode::ProgramHeaderMismatch,
+          "p_filesz",
+          field(program_header_offset!(ElfType, p_filesz)),
+          p_filesz,
+          format!("the program header table size ({:#x}) for {}", table_size, name),
+        );
+
+        let end = p_vaddr.saturating_add(p_memsz);
+        let loaded = loads.iter().any(|load| {
+          let range = load.vaddr_range();
+          range.start <= p_vaddr && end <= range.end
+        });
+        check(
+          loaded,
+          Error,
+          DiagnosticCode::UnloadedProgramHeaders,
+          "p_vaddr",
+          field(program_header_offset!(ElfType, p_vaddr)),
+          p_vaddr,
+          format!("{} within a PT_LOAD segment", name),
+        );
+      }
+    }
+
+    let has = |p_type| segments.iter().any(|segment| segment.header.p_type() == p_type);
+    check(
+      !has(PT_INTERP) || has(PT_PHDR),
+      Warning,
+      DiagnosticCode::MissingProgramHeaders,
+      "e_phnum",
+      offset_of!(ElfHeader<ElfType>, e_phnum) as u64,
+      e_phnum,
+      String::from("a PT_PHDR segment (there is a PT_INTERP one)"),
+    );
+
+    issues
+  }
+
+  ///
+  /// Checks the `.note.GNU-stack` section of relocatable objects: without
+  /// it GNU ld makes the stack of the whole link executable, as it does when
+  /// the section has `SHF_EXECINSTR` (see [`Self::stack_note_executable()`]).
+  /// Other objects are not checked.
+  ///
+  pub fn validate_stack_note(&self) -> Vec<ValidationIssue> {
+    let e_type: u64 = self.header.e_type.into();
+    if e_type != ET_REL as u64 {
+      return Vec::new();
+    }
+
+    match self
+      .sections()
+      .find(|section| section.name_bytes() == Some(b".note.GNU-stack"))
+    {
+      None => vec![ValidationIssue::new(
+        Severity::Warning,
+        DiagnosticCode::MissingStackNote,
+        "e_shnum",
+        offset_of!(ElfHeader<ElfType>, e_shnum),
+        self.header.e_shnum.into(),
+        String::from("a .note.GNU-stack section (the stack is executable once linked without it)"),
+      )],
+      Some(section) if section.header.sh_flags() & SHF_EXECINSTR != 0 => {
+        let e_shoff: u64 = self.header.e_shoff.into();
+        let e_shentsize: u64 = self.header.e_shentsize.into();
+        let header = (section.index as u64)
+          .saturating_mul(e_shentsize)
+          .saturating_add(e_shoff);
+        vec![ValidationIssue::new(
+          Severity::Warning,
+          DiagnosticCode::ExecutableStack,
+          "sh_flags",
+          header.saturating_add(offset_of!(ElfSection<ElfType>, sh_flags) as u64) as usize,
+          section.header.sh_flags(),
+          format!(
+            "no SHF_EXECINSTR for [{}] .note.GNU-stack (makes the stack executable once linked)",
+            section.index
+          ),
+        )]
+      }
+      Some(_) => Vec::new(),
+    }
+  }
+
+  ///
+  /// Runs all the validations: the header, the null section, the sections,
+  /// the segments then the `.note.GNU-stack` section of relocatable objects.
+  ///
+  pub fn validate(&self) -> Vec<ValidationIssue> {
+    let mut issues = self.validate_header();
+    issues.extend(self.validate_null_section());
+    issues.extend(self.validate_sections());
+    issues.extend(self.validate_segments());
+    issues.extend(self.validate_stack_note());
+    issues
+  }
+
+  ///
+  /// Returns the warnings recorded while reading the tables so far, then
+  /// the validation issues, see [`Diagnostic`]: the
+  /// `diagnostics` array of the machine-readable outputs.
+  ///
+  pub fn findings(&self) -> Vec<Diagnostic> {
+    let mut diagnostics = self.diagnostics.to_diagnostics();
+    diagnostics.extend(self.validate().iter().map(Diagnostic::from));
+    diagnostics
+  }
+}
+
+// ╔╦╗┬┌─┐┌─┐┬  ┌─┐┬ ┬
+//  ║║│└─┐├─┘│  ├─┤└┬┘
+// ═╩╝┴└─┘┴  ┴─┘┴ ┴ ┴
+
+impl fmt::Display for Severity {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    formatter.pad(match self {
+      Self::Warning => "warning",
+      Self::Error => "error",
+    })
+  }
+}
+
+impl fmt::Display for ValidationIssue {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      formatter,
+      "{}: {} (at {:#x}) is {:#x}, expected {}",
+      self.severity, self.field, self.offset, self.actual, self.expected
+    )
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+
+  /// Little endian elf64 executable, two program headers and three sections.
+  fn fixture() -> Vec<u8> {
+    hex(
+      r"
+        7F 'ELF 02 01 01 00 00 00000000 000000 ; e_ident
+        0200 3E00 01000000                     ; e_type, e_machine, e_version
+        0000000000000000                       ; e_entry
+        4000000000000000                       ; e_phoff
+        B000000000000000                       ; e_shoff
+        00000000 4000 3800 0200 4000 0300 0200 ; e_flags .. e_shstrndx
+      ",
+    )
+    .map(|mut bytes| {
+      bytes.resize(0xb0 + 3 * 0x40, 0);
+      bytes
+    })
+    .unwrap()
+  }
+
+  fn validate(bytes: &[u8]) -> Vec<ValidationIssue> {
+    let header = bytes.read_pod::<ElfHeader<ElfType64<LittleEndian>>>(0).unwrap();
+    header.validate(bytes.len() as u64)
+  }
+
+  #[test]
+  fn valid_header() {
+    assert_eq!(validate(&fixture()), []);
+  }
+
+  #[test]
+  fn invalid_header() {
+    let mut bytes = fixture();
+    bytes[0x06] = 0x00; // ei_version
+    bytes[0x14] = 0x02; // e_version
+    bytes[0x36] = 0x20; // e_phentsize
+    bytes[0x3e] = 0x03; // e_shstrndx
+
+    let issues = validate(&bytes);
+    let fields = issues.iter().map(|issue| issue.field).collect::<Vec<_>>();
+    assert_eq!(fields, ["ei_version", "e_version", "e_phentsize", "e_shstrndx"]);
+
+    assert_eq!(
+      issues[2].to_string(),
+      "error: e_phentsize (at 0x36) is 0x20, expected 56 (program header size)"
+    );
+    assert_eq!(issues[0].severity, Severity::Warning);
+  }
+
+  #[test]
+  fn invalid_identification() {
+    let mut bytes = fixture();
+    bytes[0x01] = b'e'; // ei_mag1
+    bytes[0x04] = 0x00; // ei_class
+    bytes[0x0c] = 0x42; // ei_pad[3]
+
+    let issues = validate(&bytes);
+    let fields = issues
+      .iter()
+      .map(|issue| (issue.field, issue.offset, issue.severity))
+      .collect::<Vec<_>>();
+
+    assert_eq!(
+      fields,
+      [
+        ("ei_mag1", 0x01, Severity::Error),
+        ("ei_class", 0x04, Severity::Error),
+        ("ei_pad", 0x0c, Severity::Warning),
+      ]
+    );
+
+    assert_eq!(
+      issues[2].to_string(),
+      "warning: ei_pad (at 0xc) is 0x42, expected 0 (padding)"
+    );
+  }
+
+  #[test]
+  fn truncated_tables() {
+    let bytes = fixture();
+    let issues = validate(&bytes[..0xb0 + 0x40]);
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].field, "e_shoff");
+
+    let mut bytes = fixture();
+    bytes[0x21] = 0x10; // e_phoff
+    bytes[0x3c] = 0x00; // e_shnum (extended), e_shstrndx is not checked
+    let fields = validate(&bytes)
+      .into_iter()
+      .map(|issue| issue.field)
+      .collect::<Vec<_>>();
+    assert_eq!(fields, ["e_phoff"]);
+  }
+
+  #[test]
+  fn section_overlaps() {
+    use crate::elf::abi::sh_type::SHT_PROGBITS;
+    use crate::elf::builder::ElfBuilder;
+
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".text", SHT_PROGBITS, &[0xC3; 0x10])
+      .section(".data", SHT_PROGBITS, &[0; 0x10])
+      .section(".bss", SHT_NOBITS, &[])
+      .section(".note.GNU-stack", SHT_PROGBITS, &[])
+      .build();
+
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(object.validate_sections(), []);
+
+    // .data moved 8 bytes back, over the end of .text.
+    let e_shoff = u64::from(object.header().e_shoff) as usize;
+    let sh_offset = |index: usize| e_shoff + index * 0x40 + 0x18;
+    let text = u64::from_le_bytes(bytes[sh_offset(1)..sh_offset(1) + 8].try_into().unwrap());
+
+    let mut corrupted = bytes.clone();
+    corrupted[sh_offset(2)..sh_offset(2) + 8].copy_from_slice(&(text + 8).to_le_bytes());
+    // .bss given file contents beyond the end of the file.
+    corrupted[sh_offset(3) - 0x14] = SHT_PROGBITS as u8;
+    corrupted[sh_offset(3)..sh_offset(3) + 8].copy_from_slice(&0xFFFF_u64.to_le_bytes());
+    corrupted[sh_offset(3) + 8..sh_offset(3) + 16].copy_from_slice(&0x10_u64.to_le_bytes());
+
+    let ElfFile::Elf64Le(object) = parse_elf(corrupted.as_slice()).unwrap() else {
+      unreachable!();
+    };
+
+    let issues = object.validate_sections();
+    assert_eq!(issues.len(), 2, "{:#?}", issues);
+    assert_eq!(
+      issues[0].to_string(),
+      format!(
+        "error: sh_offset (at {:#x}) is 0xffff, expected [3] .bss (0x10 bytes) within the file ({} bytes)",
+        sh_offset(3),
+        corrupted.len()
+      )
+    );
+    assert_eq!(
+      issues[1].to_string(),
+      format!(
+        "warning: sh_offset (at {:#x}) is {:#x}, expected [2] .data not to overlap [1] .text (bytes {:#x}..{:#x})",
+        sh_offset(2),
+        text + 8,
+        text + 8,
+        text + 0x10
+      )
+    );
+    assert_eq!(object.validate().len(), 2, "Header is valid");
+  }
+
+  #[test]
+  fn many_overlapping_sections() {
+    use crate::elf::abi::sh_type::SHT_PROGBITS;
+    use crate::elf::builder::ElfBuilder;
+
+    const COUNT: usize = 4000;
+
+    let mut builder = ElfBuilder::<ElfType64<LittleEndian>>::new();
+    for _ in 0..COUNT {
+      builder.section(".text", SHT_PROGBITS, &[0xC3; 0x10]);
+    }
+    let mut bytes = builder.build();
+
+    // Every section over the contents of the first one.
+    let e_shoff = u64::from_le_bytes(bytes[0x28..0x30].try_into().unwrap()) as usize;
+    let sh_offset = |index: usize| e_shoff + index * 0x40 + 0x18;
+    let text = bytes[sh_offset(1)..sh_offset(1) + 8].to_vec();
+    for index in 2..=COUNT {
+      bytes[sh_offset(index)..sh_offset(index) + 8].copy_from_slice(&text);
+    }
+
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    let issues = object.validate_sections();
+    assert_eq!(issues.len(), COUNT - 1, "One issue per section, not per pair");
+    assert!(issues
+      .iter()
+      .all(|issue| issue.code == DiagnosticCode::OverlappingSections));
+  }
+
+  #[test]
+  fn null_section() {
+    use crate::elf::abi::sh_type::SHT_PROGBITS;
+    use crate::elf::builder::ElfBuilder;
+
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".text", SHT_PROGBITS, &[0xC3])
+      .build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(object.validate_null_section(), []);
+    let e_shoff = u64::from(object.header().e_shoff) as usize;
+    let section_count = object.section_count();
+
+    // Extended numbering: the count in sh_size, the string table index in
+    // sh_link and the program header count in sh_info.
+    let mut extended = bytes.clone();
+    extended[0x38..0x3a].copy_from_slice(&PN_XNUM.to_le_bytes()); // e_phnum
+    extended[0x3c..0x3e].copy_from_slice(&[0, 0]); // e_shnum
+    extended[0x3e..0x40].copy_from_slice(&SHN_XINDEX.to_le_bytes()); // e_shstrndx
+    let shstrndx = u16::from_le_bytes(bytes[0x3e..0x40].try_into().unwrap()) as u32;
+    extended[e_shoff + 0x20..e_shoff + 0x28].copy_from_slice(&section_count.to_le_bytes());
+    extended[e_shoff + 0x28..e_shoff + 0x2c].copy_from_slice(&shstrndx.to_le_bytes());
+
+    let ElfFile::Elf64Le(object) = parse_elf(extended.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(object.validate_null_section(), []);
+    assert_eq!(object.section_count(), section_count);
+
+    // Garbage in sh_type, sh_flags and sh_link, e_shstrndx is not extended.
+    let mut corrupted = bytes.clone();
+    corrupted[e_shoff + 0x04] = 0x01;
+    corrupted[e_shoff + 0x08] = 0x06;
+    corrupted[e_shoff + 0x28] = 0x02;
+
+    let ElfFile::Elf64Le(object) = parse_elf(corrupted.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    let issues = object.validate_null_section();
+    let fields = issues
+      .iter()
+      .map(|issue| (issue.field, issue.offset - e_shoff, issue.actual))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      fields,
+      [("sh_type", 0x04, 1), ("sh_flags", 0x08, 6), ("sh_link", 0x28, 2)]
+    );
+    assert_eq!(
+      issues[2].to_string(),
+      format!(
+        "warning: sh_link (at {:#x}) is 0x2, expected 0 in [0] (null), e_shstrndx is not extended",
+        e_shoff + 0x28
+      )
+    );
+    assert_eq!(object.validate()[..3], issues, "The header is valid");
+  }
+
+  #[test]
+  fn segment_issues() {
+    use crate::elf::abi::sh_type::SHT_PROGBITS;
+    use crate::elf::builder::ElfBuilder;
+
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .object_type(2)
+      .section(".interp", SHT_PROGBITS, b"/lib/ld.so\0")
+      .section(".text", SHT_PROGBITS, &[0xC3; 0x10])
+      .segment(PT_LOAD, 5, &[1, 2])
+      .segment(PT_INTERP, 4, &[1])
+      .build();
+
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    let fields = object
+      .validate_segments()
+      .into_iter()
+      .map(|issue| (issue.field, issue.offset, issue.severity))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      fields,
+      [
+        ("p_type", 0x40 + 0x38, Severity::Error),
+        ("e_phnum", 0x38, Severity::Warning)
+      ]
+    );
+
+    // PT_LOAD aligned on 0x1000 at an offset not congruent to its address,
+    // PT_INTERP aligned on 3 and larger in the file than in memory.
+    let mut corrupted = bytes.clone();
+    corrupted[0x40 + 0x30..0x40 + 0x38].copy_from_slice(&0x1000_u64.to_le_bytes());
+    corrupted[0x78 + 0x30..0x78 + 0x38].copy_from_slice(&3_u64.to_le_bytes());
+    corrupted[0x78 + 0x20..0x78 + 0x28].copy_from_slice(&0x20_u64.to_le_bytes());
+
+    let ElfFile::Elf64Le(object) = parse_elf(corrupted.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    let issues = object.validate_segments();
+    let fields = issues.iter().map(|issue| issue.field).collect::<Vec<_>>();
+    assert_eq!(fields, ["p_vaddr", "p_filesz", "p_align", "p_type", "e_phnum"]);
+    assert_eq!(
+      issues[0].to_string(),
+      "error: p_vaddr (at 0x50) is 0x0, expected congruent to p_offset (0xb0) modulo p_align (0x1000) for [0] LOAD"
+    );
+  }
+
+  #[test]
+  fn stack_note() {
+    use crate::elf::abi::sh_type::SHT_PROGBITS;
+    use crate::elf::builder::{BuilderSection, ElfBuilder};
+
+    // Objects assembled without `.section .note.GNU-stack,"",@progbits`,
+    // and with `"x"` flags.
+    let missing = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".text", SHT_PROGBITS, &[0xC3])
+      .build();
+    let executable = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".text", SHT_PROGBITS, &[0xC3])
+      .add_section(BuilderSection {
+        name: String::from(".note.GNU-stack"),
+        sh_type: SHT_PROGBITS,
+        sh_flags: SHF_EXECINSTR,
+        ..Default::default()
+      })
+      .build();
+
+    let ElfFile::Elf64Le(object) = parse_elf(missing.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(
+      object.validate().iter().map(ToString::to_string).collect::<Vec<_>>(),
+      ["warning: e_shnum (at 0x3c) is 0x3, expected a .note.GNU-stack section (the stack is executable once linked without it)"]
+    );
+    assert_eq!(object.security_report().nx, crate::elf::Status::No);
+
+    let ElfFile::Elf64Le(object) = parse_elf(executable.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    let issues = object.validate_stack_note();
+    assert_eq!(issues.len(), 1);
+    assert_eq!((issues[0].field, issues[0].actual), ("sh_flags", SHF_EXECINSTR));
+    assert!(object
+      .security_report()
+      .to_string()
+      .contains("NX:       no (executable .note.GNU-stack)"));
+
+    // Not checked for executables.
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .object_type(abi::e_type::ET_EXEC)
+      .build();
+    let ElfFile::Elf64Le(object) = parse_elf(bytes.as_slice()).unwrap() else {
+      unreachable!();
+    };
+    assert_eq!(object.validate_stack_note(), []);
+  }
+
+  /// Every check has its own code, the contract of the JSON outputs.
+  #[test]
+  fn diagnostic_codes() {
+    use crate::elf::abi::sh_type::SHT_PROGBITS;
+    use crate::elf::builder::{BuilderSection, ElfBuilder};
+    use alloc::collections::BTreeSet;
+
+    let object_issues = |bytes: &[u8]| {
+      let ElfFile::Elf64Le(object) = parse_elf(bytes).unwrap() else {
+        unreachable!();
+      };
+      object.validate()
+    };
+    let patch = |bytes: &mut Vec<u8>, offset: usize, value: u64| {
+      bytes[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+    };
+
+    // The header: one byte per field.
+    let mut bytes = fixture();
+    let fields = [
+      (0x01, b'e'), // ei_mag1
+      (0x04, 0x00), // ei_class
+      (0x05, 0x00), // ei_data
+      (0x06, 0x00), // ei_version
+      (0x0c, 0x42), // ei_pad
+      (0x10, 0x00), // e_type
+      (0x14, 0x02), // e_version
+      (0x34, 0x20), // e_ehsize
+      (0x36, 0x20), // e_phentsize
+      (0x3a, 0x20), // e_shentsize
+      (0x3e, 0x03), // e_shstrndx
+    ];
+    for (offset, byte) in fields {
+      bytes[offset] = byte;
+    }
+    let mut issues = validate(&bytes);
+    issues.extend(validate(&fixture()[..0x40]));
+
+    // The sections: garbage in the null section, .data over .text, .rodata
+    // out of the file and an executable .note.GNU-stack.
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".text", SHT_PROGBITS, &[0xC3; 0x10])
+      .section(".data", SHT_PROGBITS, &[0; 0x10])
+      .section(".rodata", SHT_PROGBITS, &[0; 0x10])
+      .add_section(BuilderSection {
+        name: String::from(".note.GNU-stack"),
+        sh_type: SHT_PROGBITS,
+        sh_flags: SHF_EXECINSTR,
+        ..Default::default()
+      })
+      .build();
+    let e_shoff = u64::from_le_bytes(bytes[0x28..0x30].try_into().unwrap()) as usize;
+    let sh_offset = |index: usize| e_shoff + index * 0x40 + 0x18;
+    let text = u64::from_le_bytes(bytes[sh_offset(1)..sh_offset(1) + 8].try_into().unwrap());
+
+    let mut corrupted = bytes.clone();
+    corrupted[e_shoff + 0x04] = 0x01;
+    patch(&mut corrupted, sh_offset(2), text + 8);
+    patch(&mut corrupted, sh_offset(3) + 8, 0xFFFF);
+    issues.extend(object_issues(&corrupted));
+
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .section(".text", SHT_PROGBITS, &[0xC3])
+      .build();
+    issues.extend(object_issues(&bytes));
+
+    // The segments: PT_INTERP after PT_LOAD and without PT_PHDR, then the
+    // corruptions of `segment_issues`, then PT_INTERP turned into a PT_PHDR
+    // out of the table and of the PT_LOAD, itself out of the file.
+    let bytes = ElfBuilder::<ElfType64<LittleEndian>>::new()
+      .object_type(2)
+      .section(".interp", SHT_PROGBITS, b"/lib/ld.so\0")
+      .section(".text", SHT_PROGBITS, &[0xC3; 0x10])
+      .segment(PT_LOAD, 5, &[1, 2])
+      .segment(PT_INTERP, 4, &[1])
+      .build();
+    issues.extend(object_issues(&bytes));
+
+    let mut corrupted = bytes.clone();
+    patch(&mut corrupted, 0x40 + 0x30, 0x1000);
+    patch(&mut corrupted, 0x78 + 0x30, 3);
+    patch(&mut corrupted, 0x78 + 0x20, 0x20);
+    issues.extend(object_issues(&corrupted));
+
+    let mut corrupted = bytes.clone();
+    corrupted[0x78..0x7c].copy_from_slice(&PT_PHDR.to_le_bytes());
+    patch(&mut corrupted, 0x78 + 0x10, 0xDEAD_0000);
+    patch(&mut corrupted, 0x40 + 0x20, 0x10000);
+    patch(&mut corrupted, 0x40 + 0x28, 0x10000);
+    issues.extend(object_issues(&corrupted));
+
+    // The validation codes come first in DiagnosticCode::ALL.
+    let codes = issues.iter().map(|issue| issue.code).collect::<BTreeSet<_>>();
+    let checks = DiagnosticCode::ALL
+      .iter()
+      .take_while(|&&code| code != DiagnosticCode::Io)
+      .copied()
+      .collect::<BTreeSet<_>>();
+    assert_eq!(codes, checks);
+
+    #[cfg(feature = "serde")]
+    {
+      let diagnostics = issues.iter().map(Diagnostic::from).collect::<Vec<_>>();
+      let json = serde_json::json!({ "diagnostics": diagnostics });
+      let codes = json["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|diagnostic| diagnostic["code"].as_str().unwrap())
+        .collect::<BTreeSet<_>>();
+      assert_eq!(codes, checks.iter().map(|code| code.as_str()).collect());
+      assert_eq!(json["diagnostics"][0]["severity"], "error");
+      assert_eq!(json["diagnostics"][0]["offset"], 1);
+    }
+  }
+}
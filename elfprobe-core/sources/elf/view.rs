@@ -0,0 +1,333 @@
+use alloc::borrow::Cow;
+
+use super::strings::printable;
+use super::symbols::{SHT_DYNSYM, SHT_SYMTAB};
+use super::*;
+use crate::error::ElfError;
+use crate::pod::Pod;
+use crate::reader::Reader;
+
+// ╔═╗┌┬┐┬─┐┬ ┬┌─┐┌┬┐
+// ╚═╗ │ ├┬┘│ ││   │
+// ╚═╝ ┴ ┴└─└─┘└─┘ ┴
+
+///
+/// A section header widened to 64 bits with its index in the section header
+/// table and its printable name (see [`printable()`]), see [`ElfView::sections()`].
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionEntry<'data> {
+  pub index: usize,
+  pub name: Option<Cow<'data, str>>,
+  pub sh_type: u32,
+  pub sh_flags: u64,
+  pub sh_addr: u64,
+  pub sh_offset: u64,
+  pub sh_size: u64,
+  pub sh_link: u32,
+  pub sh_info: u32,
+  pub sh_addralign: u64,
+  pub sh_entsize: u64,
+}
+
+///
+/// The ELF header widened to 64 bits, `e_ident` as bytes (see the `EI_*`
+/// indexes of [`ElfIdentification`]), see [`ElfView::header()`].
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HeaderEntry {
+  pub e_ident: [u8; EI_NIDENT],
+  pub e_type: u16,
+  pub e_machine: u16,
+  pub e_version: u32,
+  pub e_entry: u64,
+  pub e_phoff: u64,
+  pub e_shoff: u64,
+  pub e_flags: u32,
+  pub e_ehsize: u16,
+  pub e_phentsize: u16,
+  pub e_phnum: u16,
+  pub e_shentsize: u16,
+  pub e_shnum: u16,
+  pub e_shstrndx: u16,
+}
+
+/// A program header widened to 64 bits, see [`ElfView::segments()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SegmentEntry {
+  pub p_type: u32,
+  pub p_flags: u32,
+  pub p_offset: u64,
+  pub p_vaddr: u64,
+  pub p_paddr: u64,
+  pub p_filesz: u64,
+  pub p_memsz: u64,
+  pub p_align: u64,
+}
+
+///
+/// A symbol widened to 64 bits with its name, `dynamic` tells whether it
+/// comes from `.dynsym` rather than `.symtab`, see [`ElfView::symbols()`].
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolEntry<'data> {
+  pub name: Option<Cow<'data, str>>,
+  pub dynamic: bool,
+  pub st_value: u64,
+  pub st_size: u64,
+  pub st_info: u8,
+  pub st_other: u8,
+  pub st_shndx: u16,
+}
+
+// ╔╦╗┬─┐┌─┐┬┌┬┐
+//  ║ ├┬┘├─┤│ │
+//  ╩ ┴└─┴ ┴┴ ┴
+
+///
+/// Class and data encoding agnostic view of an ELF file, implemented by the
+/// four [`ElfObject`] instantiations so that tools are written once (see
+/// [`ElfFile::into_view()`]).
+///
+/// Bytes are borrowed from the file (zero-copy) whereas iterators are boxed
+/// and yield entries widened to 64 bits, only the names are borrowed.
+///
+pub trait ElfView<'data> {
+  /// Returns the ELF header.
+  fn header(&self) -> HeaderEntry;
+
+  /// Returns the section headers, index 0 (the null section) included.
+  fn sections(&self) -> Box<dyn Iterator<Item = SectionEntry<'data>> + '_>;
+
+  /// Returns the program headers.
+  fn segments(&self) -> Box<dyn Iterator<Item = SegmentEntry> + '_>;
+
+  /// Returns the symbols of `.symtab` then those of `.dynsym`.
+  fn symbols(&self) -> Box<dyn Iterator<Item = SymbolEntry<'data>> + '_>;
+
+  /// Returns the bytes of the section at the given index.
+  fn section_data(&self, index: usize) -> Result<&'data [u8], ElfError>;
+
+  /// Returns the build ID (`NT_GNU_BUILD_ID`).
+  fn build_id(&self) -> Option<&'data [u8]>;
+
+  ///
+  /// Returns the header as [`ElfObject::display_header()`] with the
+  /// machine-readable constants (`ET_DYN(0x3)`), one field per line.
+  ///
+  fn header_text(&self) -> String;
+}
+
+impl<'data, Reader, ElfType> ElfView<'data> for ElfObject<'data, Reader, ElfType>
+where
+  Reader: self::Reader<'data>,
+  ElfType: self::ElfType,
+{
+  fn header(&self) -> HeaderEntry {
+    let header = ElfObject::header(self);
+    let mut e_ident = [0u8; EI_NIDENT];
+    e_ident.copy_from_slice(header.e_ident.as_bytes());
+
+    HeaderEntry {
+      e_ident,
+      e_type: header.e_type.into() as u16,
+      e_machine: header.e_machine.into() as u16,
+      e_version: header.e_version.into() as u32,
+      e_entry: header.e_entry.into(),
+      e_phoff: header.e_phoff.into(),
+      e_shoff: header.e_shoff.into(),
+      e_flags: header.e_flags.into() as u32,
+      e_ehsize: header.e_ehsize.into() as u16,
+      e_phentsize: header.e_phentsize.into() as u16,
+      e_phnum: header.e_phnum.into() as u16,
+      e_shentsize: header.e_shentsize.into() as u16,
+      e_shnum: header.e_shnum.into() as u16,
+      e_shstrndx: header.e_shstrndx.into() as u16,
+    }
+  }
+
+  fn sections(&self) -> Box<dyn Iterator<Item = SectionEntry<'data>> + '_> {
+    Box::new(ElfObject::sections(self).map(|section| {
+      let header = section.header;
+      SectionEntry {
+        index: section.index,
+        name: section.display_name(),
+        sh_type: header.sh_type() as u32,
+        sh_flags: header.sh_flags(),
+        sh_addr: header.sh_addr(),
+        sh_offset: header.sh_offset(),
+        sh_size: header.sh_size(),
+        sh_link: header.sh_link() as u32,
+        sh_info: header.sh_info() as u32,
+        sh_addralign: header.sh_addralign(),
+        sh_entsize: header.sh_entsize(),
+      }
+    }))
+  }
+
+  fn segments(&self) -> Box<dyn Iterator<Item = SegmentEntry> + '_> {
+    let headers = self.program_headers().unwrap_or_default();
+
+    Box::new(headers.into_iter().map(|header| SegmentEntry {
+      p_type: header.p_type(),
+      p_flags: header.p_flags(),
+      p_offset: header.p_offset(),
+      p_vaddr: header.p_vaddr(),
+      p_paddr: header.p_paddr(),
+      p_filesz: header.p_filesz(),
+      p_memsz: header.p_memsz(),
+      p_align: header.p_align(),
+    }))
+  }
+
+  fn symbols(&self) -> Box<dyn Iterator<Item = SymbolEntry<'data>> + '_> {
+    let tables = [SHT_SYMTAB, SHT_DYNSYM]
+      .into_iter()
+      .filter_map(|sh_type| Some((sh_type == SHT_DYNSYM, self.symbol_table(sh_type).ok()??)));
+
+    Box::new(tables.flat_map(|(dynamic, table)| {
+      let names = table.names;
+      table.symbols.into_iter().map(move |symbol| SymbolEntry {
+        name: names.get(symbol.st_name() as usize).map(printable),
+        dynamic,
+        st_value: symbol.st_value(),
+        st_size: symbol.st_size(),
+        st_info: symbol.st_info(),
+        st_other: symbol.st_other(),
+        st_shndx: symbol.st_shndx(),
+      })
+    }))
+  }
+
+  fn section_data(&self, index: usize) -> Result<&'data [u8], ElfError> {
+    let sections = self.section_headers()?;
+    let section = sections.get(index).ok_or(ElfError::InvalidSectionIndex(index))?;
+    self.section_bytes(section)
+  }
+
+  fn build_id(&self) -> Option<&'data [u8]> {
+    ElfObject::build_id(self)
+  }
+
+  fn header_text(&self) -> String {
+    format!("{:#}", self.display_header())
+  }
+}
+
+impl<'data, Reader> ElfFile<'data, Reader>
+where
+  Reader: self::Reader<'data> + 'data,
+{
+  /// Returns the file behind the class and data encoding agnostic [`ElfView`].
+  pub fn into_view(self) -> Box<dyn ElfView<'data> + 'data> {
+    match self {
+      Self::Elf32Be(object) => Box::new(object),
+      Self::Elf64Be(object) => Box::new(object),
+      Self::Elf32Le(object) => Box::new(object),
+      Self::Elf64Le(object) => Box::new(object),
+    }
+  }
+}
+
+/// Parses an ELF file as an [`ElfView`], see [`parse_elf()`].
+pub fn parse_elf_view<'data, Reader>(data: Reader) -> Result<Box<dyn ElfView<'data> + 'data>, ElfError>
+where
+  Reader: self::Reader<'data> + 'data,
+{
+  parse_elf(data).map(ElfFile::into_view)
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::hex::hex;
+
+  ///
+  /// ELF64 little-endian with a `PT_NOTE` segment holding a build ID and
+  /// five sections: null, `.note`, `.symtab`, `.strtab` and `.shstrtab`.
+  ///
+  fn fixture() -> Vec<u8> {
+    hex(
+      r"
+        7F 'ELF 02 01 01 00 00 00000000 000000 ; e_ident
+        0200 3E00 01000000 ; e_type, e_machine, e_version
+        0000000000000000 ; e_entry
+        4000000000000000 ; e_phoff
+        F000000000000000 ; e_shoff
+        00000000 4000 ; e_flags, e_ehsize
+        3800 0100 ; e_phentsize, e_phnum
+        4000 0500 0400 ; e_shentsize, e_shnum, e_shstrndx
+
+        04000000 04000000 ; PT_NOTE, PF_R
+        7800000000000000 7800000000000000 7800000000000000
+        1800000000000000 1800000000000000 0400000000000000
+
+        04000000 08000000 03000000 'GNU 00 0123456789ABCDEF ; 0x78, NT_GNU_BUILD_ID
+        00 'main 00 0000 ; 0x90, .strtab
+
+        00000000 00 00 0000 0000000000000000 0000000000000000 ; 0x98, .symtab
+        01000000 12 00 0100 0010000000000000 1000000000000000 ; main, GLOBAL FUNC
+
+        00 '.note 00 '.symtab 00 '.strtab 00 '.shstrtab 00 00000000000000 ; 0xC8
+
+        @0xF0 00*64 ; null section
+
+        01000000 07000000 0200000000000000 7800000000000000 ; .note
+        7800000000000000 1800000000000000 00000000 00000000
+        0400000000000000 0000000000000000
+
+        07000000 02000000 0000000000000000 0000000000000000 ; .symtab
+        9800000000000000 3000000000000000 03000000 01000000
+        0800000000000000 1800000000000000
+
+        0F000000 03000000 0000000000000000 0000000000000000 ; .strtab
+        9000000000000000 0600000000000000 00000000 00000000
+        0100000000000000 0000000000000000
+
+        17000000 03000000 0000000000000000 0000000000000000 ; .shstrtab
+        C800000000000000 2100000000000000 00000000 00000000
+        0100000000000000 0000000000000000
+      ",
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn elf_view() {
+    let bytes = fixture();
+    let view = parse_elf_view(bytes.as_slice()).unwrap();
+
+    let header = view.header();
+    assert_eq!(&header.e_ident[EI_MAG0..EI_CLASS], b"\x7FELF");
+    assert_eq!(
+      (header.e_ident[EI_CLASS], header.e_shnum, header.e_shstrndx),
+      (2, 5, 4)
+    );
+
+    let names = view
+      .sections()
+      .map(|section| section.name.unwrap_or_default())
+      .collect::<Vec<_>>();
+    assert_eq!(names, ["", ".note", ".symtab", ".strtab", ".shstrtab"]);
+
+    let segments = view.segments().collect::<Vec<_>>();
+    assert_eq!(segments.len(), 1);
+    assert_eq!((segments[0].p_type, segments[0].p_offset), (4, 0x78));
+
+    let symbols = view.symbols().collect::<Vec<_>>();
+    assert_eq!(symbols.len(), 2);
+    assert_eq!(symbols[1].name.as_deref(), Some("main"));
+    assert_eq!((symbols[1].st_value, symbols[1].dynamic), (0x1000, false));
+
+    assert_eq!(view.section_data(3).unwrap(), b"\0main\0");
+    assert_eq!(view.section_data(5), Err(ElfError::InvalidSectionIndex(5)));
+    assert_eq!(
+      view.build_id(),
+      Some(&[0x01, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF][..])
+    );
+  }
+}
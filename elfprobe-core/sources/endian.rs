@@ -1,4 +1,6 @@
-use std::fmt::Debug;
+use core::fmt::Debug;
+
+use crate::utils::{settings, Restore};
 
 // ╔═╗┌─┐┌─┐┬─┐┌─┐┌┬┐┬┌─┐┌┐┌┌─┐
 // ║ ║├─┘├┤ ├┬┘├─┤ │ ││ ││││└─┐
@@ -82,18 +84,26 @@ pub trait Endianness:
   + Default
   + Debug
   // I'm not particularly fond of this approach.
+  + AlignedEndianOperation<i8>
+  + AlignedEndianOperation<u8>
   + AlignedEndianOperation<i16>
   + AlignedEndianOperation<u16>
   + AlignedEndianOperation<i32>
   + AlignedEndianOperation<u32>
   + AlignedEndianOperation<i64>
   + AlignedEndianOperation<u64>
+  + AlignedEndianOperation<i128>
+  + AlignedEndianOperation<u128>
+  + UnalignedEndianOperation<i8, 1>
+  + UnalignedEndianOperation<u8, 1>
   + UnalignedEndianOperation<i16, 2>
   + UnalignedEndianOperation<u16, 2>
   + UnalignedEndianOperation<i32, 4>
   + UnalignedEndianOperation<u32, 4>
   + UnalignedEndianOperation<i64, 8>
   + UnalignedEndianOperation<u64, 8>
+  + UnalignedEndianOperation<i128, 16>
+  + UnalignedEndianOperation<u128, 16>
 {
   #[allow(unused)]
   /// Returns the endianness long name (lower case).
@@ -102,6 +112,16 @@ pub trait Endianness:
   #[allow(unused)]
   /// Returns the endianness short name (capitals).
   fn short_name() -> &'static str;
+
+  /// Returns `true` for little-endian, `false` for big-endian.
+  fn is_little_endian() -> bool;
+
+  /// Returns `true` when values are stored in the host byte order (reads and
+  /// writes are no-ops), see [`NativeEndian`].
+  #[inline]
+  fn is_native() -> bool {
+    Self::is_little_endian() == cfg!(target_endian = "little")
+  }
 }
 
 // ╦┌┬┐┌─┐┬  ┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐┌┬┐┬┌─┐┌┐┌┌─┐
@@ -139,12 +159,18 @@ macro_rules! impl_endian_operation {
 #[rustfmt::skip] // TODO: TMP
 macro_rules! impl_aligned_endian_operation {
   ($struct: ident, $endian: literal, $from: ident, $to: ident) => {
+    // Single bytes have no byte order, the operations are the identity but
+    // keep the wrappers (U8, I8) symmetric with wider ones.
+    impl_aligned_endian_operation!($struct, $endian, i8, $from, $to);
+    impl_aligned_endian_operation!($struct, $endian, u8, $from, $to);
     impl_aligned_endian_operation!($struct, $endian, i16, $from, $to);
     impl_aligned_endian_operation!($struct, $endian, u16, $from, $to);
     impl_aligned_endian_operation!($struct, $endian, i32, $from, $to);
     impl_aligned_endian_operation!($struct, $endian, u32, $from, $to);
     impl_aligned_endian_operation!($struct, $endian, i64, $from, $to);
     impl_aligned_endian_operation!($struct, $endian, u64, $from, $to);
+    impl_aligned_endian_operation!($struct, $endian, i128, $from, $to);
+    impl_aligned_endian_operation!($struct, $endian, u128, $from, $to);
   };
 
   ($struct: ident, $endian: literal, $type: ident, $from: ident, $to: ident) => {
@@ -158,12 +184,16 @@ macro_rules! impl_aligned_endian_operation {
 #[rustfmt::skip] // TODO: TMP
 macro_rules! impl_unaligned_endian_operation {
   ($struct: ident, $endian: literal, $from: ident, $to: ident) => {
+    impl_unaligned_endian_operation!($struct, $endian, i8, 1, $from, $to);
+    impl_unaligned_endian_operation!($struct, $endian, u8, 1, $from, $to);
     impl_unaligned_endian_operation!($struct, $endian, i16, 2, $from, $to);
     impl_unaligned_endian_operation!($struct, $endian, u16, 2, $from, $to);
     impl_unaligned_endian_operation!($struct, $endian, i32, 4, $from, $to);
     impl_unaligned_endian_operation!($struct, $endian, u32, 4, $from, $to);
     impl_unaligned_endian_operation!($struct, $endian, i64, 8, $from, $to);
     impl_unaligned_endian_operation!($struct, $endian, u64, 8, $from, $to);
+    impl_unaligned_endian_operation!($struct, $endian, i128, 16, $from, $to);
+    impl_unaligned_endian_operation!($struct, $endian, u128, 16, $from, $to);
   };
 
   ($struct: ident, $endian: literal, $type: ident, $bytes: literal, $from: ident, $to: ident) => {
@@ -215,6 +245,10 @@ impl Endianness for BigEndian {
   fn short_name() -> &'static str {
     "BE"
   }
+
+  fn is_little_endian() -> bool {
+    false
+  }
 }
 
 // ╦  ┬┌┬┐┌┬┐┬  ┌─┐    ┌─┐┌┐┌┌┬┐┬┌─┐┌┐┌
@@ -241,6 +275,118 @@ impl Endianness for LittleEndian {
   fn short_name() -> &'static str {
     "LE"
   }
+
+  fn is_little_endian() -> bool {
+    true
+  }
+}
+
+/// Byte order of the host, resolved at compile time.
+#[cfg(target_endian = "little")]
+pub type NativeEndian = LittleEndian;
+
+/// Byte order of the host, resolved at compile time.
+#[cfg(target_endian = "big")]
+pub type NativeEndian = BigEndian;
+
+// ╦═╗┬ ┬┌┐┌┌┬┐┬┌┬┐┌─┐
+// ╠╦╝│ ││││ │ ││││├┤
+// ╩╚═└─┘┘└┘ ┴ ┴┴ ┴└─┘
+
+settings! {
+  static LITTLE_ENDIAN: bool = cfg!(target_endian = "little");
+}
+
+///
+/// Byte order selected at runtime, per thread (native by default).
+///
+/// [`Endianness`] operations take no receiver, the byte order is therefore a
+/// thread-local flag rather than a value: code generic over the endianness
+/// can be instantiated once with `RuntimeEndian` instead of twice, at the
+/// cost of a branch for every read and write (see the `endianness`
+/// benchmark). [`BigEndian`] and [`LittleEndian`] remain the default.
+///
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct RuntimeEndian;
+
+impl RuntimeEndian {
+  /// Selects the byte order of the current thread.
+  pub fn set_little_endian(little_endian: bool) {
+    LITTLE_ENDIAN.with(|flag| flag.set(little_endian));
+  }
+
+  /// Runs `function` with the given byte order then restores the previous one.
+  pub fn with<Output>(little_endian: bool, function: impl FnOnce() -> Output) -> Output {
+    let previous = LITTLE_ENDIAN.with(|flag| flag.replace(little_endian));
+    let _restore = Restore(|| Self::set_little_endian(previous));
+    function()
+  }
+}
+
+impl<Primitive: 'static + Copy + Sized> AlignedEndianOperation<Primitive> for RuntimeEndian
+where
+  BigEndian: AlignedEndianOperation<Primitive>,
+  LittleEndian: AlignedEndianOperation<Primitive>,
+{
+  #[inline]
+  fn read(value: Primitive) -> Primitive {
+    match Self::is_little_endian() {
+      true => <LittleEndian as AlignedEndianOperation<Primitive>>::read(value),
+      false => <BigEndian as AlignedEndianOperation<Primitive>>::read(value),
+    }
+  }
+
+  #[inline]
+  fn write(value: Primitive) -> Primitive {
+    match Self::is_little_endian() {
+      true => <LittleEndian as AlignedEndianOperation<Primitive>>::write(value),
+      false => <BigEndian as AlignedEndianOperation<Primitive>>::write(value),
+    }
+  }
+}
+
+impl<Primitive: 'static + Copy + Sized, const BYTES: usize> UnalignedEndianOperation<Primitive, BYTES>
+  for RuntimeEndian
+where
+  BigEndian: UnalignedEndianOperation<Primitive, BYTES>,
+  LittleEndian: UnalignedEndianOperation<Primitive, BYTES>,
+{
+  #[inline]
+  fn read(value: [u8; BYTES]) -> Primitive {
+    match Self::is_little_endian() {
+      true => <LittleEndian as UnalignedEndianOperation<Primitive, BYTES>>::read(value),
+      false => <BigEndian as UnalignedEndianOperation<Primitive, BYTES>>::read(value),
+    }
+  }
+
+  #[inline]
+  fn write(value: Primitive) -> [u8; BYTES] {
+    match Self::is_little_endian() {
+      true => <LittleEndian as UnalignedEndianOperation<Primitive, BYTES>>::write(value),
+      false => <BigEndian as UnalignedEndianOperation<Primitive, BYTES>>::write(value),
+    }
+  }
+}
+
+impl Endianness for RuntimeEndian {
+  fn long_name() -> &'static str {
+    match Self::is_little_endian() {
+      true => LittleEndian::long_name(),
+      false => BigEndian::long_name(),
+    }
+  }
+
+  fn short_name() -> &'static str {
+    match Self::is_little_endian() {
+      true => LittleEndian::short_name(),
+      false => BigEndian::short_name(),
+    }
+  }
+
+  #[inline]
+  fn is_little_endian() -> bool {
+    LITTLE_ENDIAN.with(|flag| flag.get())
+  }
 }
 
 // ╔╦╗┌─┐┌─┐┌┬┐┌─┐
@@ -259,12 +405,16 @@ mod tests {
         // NOTE:
         // Do no use -1 when testing endianness because -1 is a bit sequence of
         // 1 and therefore has no impact on the endianness (same goes for 0).
+        test_endianness!($endian, i8, 1, 0x11);
+        test_endianness!($endian, u8, 1, 0x11);
         test_endianness!($endian, i16, 2, 0x1122);
         test_endianness!($endian, u16, 2, 0x1122);
         test_endianness!($endian, i32, 4, 0x1122_3344);
         test_endianness!($endian, u32, 4, 0x1122_3344);
         test_endianness!($endian, i64, 8, 0x1122_3344_5566_7788);
         test_endianness!($endian, u64, 8, 0x1122_3344_5566_7788);
+        test_endianness!($endian, i128, 16, 0x1122_3344_5566_7788_99AA_BBCC_DDEE_FF00);
+        test_endianness!($endian, u128, 16, 0x1122_3344_5566_7788_99AA_BBCC_DDEE_FF00);
       }
     };
 
@@ -297,4 +447,46 @@ mod tests {
 
   test_endianness!(BigEndian, big_endian);
   test_endianness!(LittleEndian, little_endian);
+  test_endianness!(RuntimeEndian, runtime_endian);
+
+  #[test]
+  fn native_endian() {
+    assert!(NativeEndian::is_native());
+    assert_ne!(BigEndian::is_native(), LittleEndian::is_native());
+    assert_eq!(
+      <NativeEndian as AlignedEndianOperation<u32>>::write(0x1122_3344),
+      0x1122_3344
+    );
+  }
+
+  #[test]
+  fn runtime_endian() {
+    let write = <RuntimeEndian as UnalignedEndianOperation<u32, 4>>::write;
+    assert_eq!(RuntimeEndian::with(false, || write(1)), [0, 0, 0, 1]);
+    assert_eq!(RuntimeEndian::with(true, || write(1)), [1, 0, 0, 0]);
+    assert_eq!(RuntimeEndian::with(false, RuntimeEndian::short_name), "BE");
+    let native = RuntimeEndian::short_name();
+    let result = std::panic::catch_unwind(|| RuntimeEndian::with(native != "LE", || panic!("Unwound")));
+    assert!(result.is_err());
+    assert_eq!(
+      RuntimeEndian::short_name(),
+      native,
+      "Restored when the function panics"
+    );
+
+    RuntimeEndian::set_little_endian(false);
+    assert_eq!(
+      <RuntimeEndian as AlignedEndianOperation<u16>>::write(0x1122),
+      0x1122u16.to_be()
+    );
+    assert_eq!(
+      <RuntimeEndian as UnalignedEndianOperation<u16, 2>>::read([0x11, 0x22]),
+      0x1122
+    );
+    RuntimeEndian::set_little_endian(true);
+    assert_eq!(
+      <RuntimeEndian as UnalignedEndianOperation<u16, 2>>::read([0x11, 0x22]),
+      0x2211
+    );
+  }
 }
@@ -1,8 +1,12 @@
-use std::{error, fmt, result};
+use core::{error, fmt, result};
+#[cfg(feature = "std")]
+use std::io;
 
-#[allow(unused)]
-// https://doc.rust-lang.org/rust-by-example/error/multiple_error_types/boxing_errors.html
-pub type Result<T> = result::Result<T, Box<dyn error::Error>>;
+use crate::alloc_prelude::*;
+use crate::elf::Limit;
+
+/// Result of the parsing functions, see [`ElfError`].
+pub type Result<T> = result::Result<T, ElfError>;
 
 // ╔╗ ┬ ┬┌┬┐┌─┐┌─┐
 // ╠╩╗└┬┘ │ ├┤ └─┐
@@ -16,13 +20,25 @@ pub enum BytesError {
   /// Bytes array size is not equal to the output type size.
   SizeOfMismatch { length: usize, size_of: usize },
 
+  /// The size of `count` values of the output type overflows.
+  Overflow { count: usize, size_of: usize },
+
+  /// A file offset or size (64-bit field) does not fit in `usize` (32-bit hosts).
+  OffsetOverflow(u64),
+
+  /// The `size` bytes at `offset` are not all within the data.
+  OutOfBounds { offset: usize, size: usize },
+
+  /// The end of the `size` bytes at a file `offset` overflows 64 bits.
+  RangeOverflow { offset: u64, size: u64 },
+
   /// Bytes array pointer is not aligned with the output type.
   #[allow(unused)] // Only used when cfg(not(feature = "unaligned"))
   AlignOfMismatch { pointer: usize, align_of: usize },
 }
 
 impl fmt::Display for BytesError {
-  fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> fmt::Result {
+  fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> fmt::Result {
     match self {
       Self::Empty => write!(formatter, "bytes.len() != 0"),
 
@@ -34,6 +50,30 @@ impl fmt::Display for BytesError {
         )
       }
 
+      Self::Overflow { count, size_of } => {
+        write!(
+          formatter,
+          "count * size_of::<Pod>() overflows, {} * {}",
+          count, size_of,
+        )
+      }
+
+      Self::OffsetOverflow(offset) => {
+        write!(formatter, "offset {:#x} exceeds the addressable range", offset)
+      }
+
+      Self::OutOfBounds { offset, size } => {
+        write!(formatter, "{} bytes at offset {:#x} out of bounds", size, offset)
+      }
+
+      Self::RangeOverflow { offset, size } => {
+        write!(
+          formatter,
+          "{} bytes at offset {:#x} overflow 64 bits",
+          size, offset
+        )
+      }
+
       Self::AlignOfMismatch { pointer, align_of } => {
         write!(
           formatter,
@@ -48,3 +88,564 @@ impl fmt::Display for BytesError {
 }
 
 impl error::Error for BytesError {}
+
+// ╔═╗┬  ┌─┐
+// ║╣ │  ├┤
+// ╚═╝┴─┘└
+
+///
+/// Error of the whole `elf` module (and archives), from I/O errors to
+/// malformed tables. Errors are comparable, I/O errors by kind.
+///
+#[derive(Debug)]
+pub enum ElfError {
+  /// The file could not be opened, mapped or read.
+  #[cfg(feature = "std")]
+  Io(io::Error),
+
+  /// Low-level error while reading bytes.
+  BytesError(BytesError),
+
+  /// The section is compressed with an unsupported algorithm (`ch_type`).
+  UnsupportedCompression(u32),
+
+  /// The compressed section could not be decompressed.
+  #[allow(unused)] // Only used when cfg(any(feature = "zlib", feature = "zstd"))
+  DecompressionFailed(String),
+
+  /// The decompressed length does not match the compression header `ch_size`.
+  DecompressedSizeMismatch { expected: u64, actual: u64 },
+
+  /// The file does not start with `\x7fELF` (the first bytes are kept).
+  BadMagic([u8; 4]),
+
+  /// The file is a text linker script, with the first file it references.
+  LinkerScript { input: Option<String> },
+
+  /// The file is too short to hold the given structure.
+  Truncated { needed: usize, available: usize },
+
+  /// The file class (`ei_class`) is neither `ELFCLASS32` nor `ELFCLASS64`.
+  InvalidClass(u8),
+
+  /// The data encoding (`ei_data`) is neither `ELFDATA2LSB` nor `ELFDATA2MSB`.
+  UnsupportedClassData { class: u8, data: u8 },
+
+  /// There is no section at the given index.
+  InvalidSectionIndex(usize),
+
+  /// A field of `e_ident` disagrees with the type the header was parsed as.
+  IdentificationMismatch {
+    field: &'static str,
+    expected: u8,
+    actual: u8,
+  },
+
+  /// The version (`ei_version` or `e_version`) is not `EV_CURRENT`.
+  UnexpectedVersion { field: &'static str, version: u64 },
+
+  /// The name of the symbol at the given index is out of its string table.
+  InvalidSymbolName { index: usize, st_name: u32 },
+
+  /// The offset is out of the string table (or the string is not terminated).
+  InvalidStringOffset { offset: usize, size: usize },
+
+  ///
+  /// The table declares more entries than the whole file could hold, only
+  /// the first `max` are read (a warning, see [`ElfObject::bounded_count()`]).
+  ///
+  /// [`ElfObject::bounded_count()`]: crate::elf::ElfObject::bounded_count()
+  ///
+  CountClamped {
+    table: &'static str,
+    count: u64,
+    max: u64,
+  },
+
+  /// The entries of the table are smaller than the structure they hold.
+  EntrySizeMismatch {
+    table: &'static str,
+    entsize: u64,
+    expected: usize,
+  },
+
+  ///
+  /// The relative relocation type of the machine, which the `SHT_RELR`
+  /// entries stand for, is unknown: the entries are skipped (a warning).
+  ///
+  UnknownRelativeType { machine: u16 },
+
+  /// No `PT_LOAD` segment maps the virtual address.
+  UnmappedAddress(u64),
+
+  ///
+  /// The bytes at the virtual address are not all stored in the file, they
+  /// run into the zero-filled part of the segment (`p_memsz > p_filesz`) or
+  /// past its end.
+  ///
+  UnbackedAddress { vaddr: u64, len: usize },
+
+  /// The archive (`ar`) is malformed at the given offset.
+  InvalidArchive { offset: usize, reason: &'static str },
+
+  /// There is no `PT_INTERP` segment to patch (statically linked file).
+  NoInterpreter,
+
+  ///
+  /// The new interpreter path (NUL included) is longer than the `PT_INTERP`
+  /// segment, growing the segment is not supported.
+  ///
+  InterpreterTooLong { length: usize, capacity: u64 },
+
+  /// There is no `PT_GNU_STACK` segment to patch, adding it is not supported.
+  NoStackSegment,
+
+  /// The section cannot be removed (allocated, still referenced...).
+  CannotRemoveSection { name: String, reason: String },
+
+  /// A ceiling of the [`Limits`](crate::elf::Limits) was hit.
+  LimitExceeded(Limit),
+
+  ///
+  /// What was being done when the error happened, e.g., `reading the section
+  /// header table at offset 0x40e8`, the cause is the [`source()`] of the
+  /// error, see [`ResultExt::context()`].
+  ///
+  /// [`source()`]: error::Error::source()
+  ///
+  Context { context: String, source: Box<ElfError> },
+}
+
+impl ElfError {
+  /// Returns the low-level cause, without the contexts around it.
+  pub fn root(&self) -> &ElfError {
+    match self {
+      Self::Context { source, .. } => source.root(),
+      error => error,
+    }
+  }
+}
+
+impl From<BytesError> for ElfError {
+  fn from(error: BytesError) -> Self {
+    Self::BytesError(error)
+  }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for ElfError {
+  fn from(error: io::Error) -> Self {
+    Self::Io(error)
+  }
+}
+
+// Not derived since io::Error is not PartialEq, I/O errors are compared by kind.
+impl PartialEq for ElfError {
+  fn eq(&self, other: &Self) -> bool {
+    match (self, other) {
+      #[cfg(feature = "std")]
+      (Self::Io(left), Self::Io(right)) => left.kind() == right.kind(),
+      (Self::BytesError(left), Self::BytesError(right)) => left == right,
+      (Self::UnsupportedCompression(left), Self::UnsupportedCompression(right)) => left == right,
+      (Self::DecompressionFailed(left), Self::DecompressionFailed(right)) => left == right,
+      (
+        Self::DecompressedSizeMismatch { expected, actual },
+        Self::DecompressedSizeMismatch {
+          expected: other_expected,
+          actual: other_actual,
+        },
+      ) => (expected, actual) == (other_expected, other_actual),
+      (Self::BadMagic(left), Self::BadMagic(right)) => left == right,
+      (Self::LinkerScript { input }, Self::LinkerScript { input: other }) => input == other,
+      (
+        Self::Truncated { needed, available },
+        Self::Truncated {
+          needed: other_needed,
+          available: other_available,
+        },
+      ) => (needed, available) == (other_needed, other_available),
+      (Self::InvalidClass(left), Self::InvalidClass(right)) => left == right,
+      (
+        Self::UnsupportedClassData { class, data },
+        Self::UnsupportedClassData {
+          class: other_class,
+          data: other_data,
+        },
+      ) => (class, data) == (other_class, other_data),
+      (Self::InvalidSectionIndex(left), Self::InvalidSectionIndex(right)) => left == right,
+      (
+        Self::IdentificationMismatch {
+          field,
+          expected,
+          actual,
+        },
+        Self::IdentificationMismatch {
+          field: other_field,
+          expected: other_expected,
+          actual: other_actual,
+        },
+      ) => (field, expected, actual) == (other_field, other_expected, other_actual),
+      (
+        Self::UnexpectedVersion { field, version },
+        Self::UnexpectedVersion {
+          field: other_field,
+          version: other_version,
+        },
+      ) => (field, version) == (other_field, other_version),
+      (
+        Self::InvalidSymbolName { index, st_name },
+        Self::InvalidSymbolName {
+          index: other_index,
+          st_name: other_st_name,
+        },
+      ) => (index, st_name) == (other_index, other_st_name),
+      (
+        Self::InvalidStringOffset { offset, size },
+        Self::InvalidStringOffset {
+          offset: other_offset,
+          size: other_size,
+        },
+      ) => (offset, size) == (other_offset, other_size),
+      (
+        Self::EntrySizeMismatch {
+          table,
+          entsize,
+          expected,
+        },
+        Self::EntrySizeMismatch {
+          table: other_table,
+          entsize: other_entsize,
+          expected: other_expected,
+        },
+      ) => (table, entsize, expected) == (other_table, other_entsize, other_expected),
+      (Self::UnknownRelativeType { machine }, Self::UnknownRelativeType { machine: other }) => {
+        machine == other
+      }
+      (
+        Self::CountClamped { table, count, max },
+        Self::CountClamped {
+          table: other_table,
+          count: other_count,
+          max: other_max,
+        },
+      ) => (table, count, max) == (other_table, other_count, other_max),
+      (Self::UnmappedAddress(left), Self::UnmappedAddress(right)) => left == right,
+      (
+        Self::UnbackedAddress { vaddr, len },
+        Self::UnbackedAddress {
+          vaddr: other_vaddr,
+          len: other_len,
+        },
+      ) => (vaddr, len) == (other_vaddr, other_len),
+      (
+        Self::InvalidArchive { offset, reason },
+        Self::InvalidArchive {
+          offset: other_offset,
+          reason: other_reason,
+        },
+      ) => (offset, reason) == (other_offset, other_reason),
+      (Self::NoInterpreter, Self::NoInterpreter) => true,
+      (Self::NoStackSegment, Self::NoStackSegment) => true,
+      (Self::LimitExceeded(left), Self::LimitExceeded(right)) => left == right,
+      (
+        Self::CannotRemoveSection { name, reason },
+        Self::CannotRemoveSection {
+          name: other_name,
+          reason: other_reason,
+        },
+      ) => (name, reason) == (other_name, other_reason),
+      (
+        Self::InterpreterTooLong { length, capacity },
+        Self::InterpreterTooLong {
+          length: other_length,
+          capacity: other_capacity,
+        },
+      ) => (length, capacity) == (other_length, other_capacity),
+      (
+        Self::Context { context, source },
+        Self::Context {
+          context: other_context,
+          source: other_source,
+        },
+      ) => (context, source) == (other_context, other_source),
+      _ => false,
+    }
+  }
+}
+
+impl Eq for ElfError {}
+
+impl fmt::Display for ElfError {
+  fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      #[cfg(feature = "std")]
+      Self::Io(error) => error.fmt(formatter),
+
+      Self::BytesError(error) => error.fmt(formatter),
+
+      Self::UnsupportedCompression(ch_type) => {
+        write!(formatter, "unsupported compression type {:#x}", ch_type)
+      }
+
+      Self::DecompressionFailed(reason) => {
+        write!(formatter, "decompression failed: {}", reason)
+      }
+
+      Self::DecompressedSizeMismatch { expected, actual } => {
+        write!(
+          formatter,
+          "decompressed size mismatch, expected {} bytes (ch_size) but got {}",
+          expected, actual,
+        )
+      }
+
+      Self::BadMagic(magic) => {
+        write!(
+          formatter,
+          "bad magic {:02x} {:02x} {:02x} {:02x}, expected 7f 45 4c 46 (not an ELF file?)",
+          magic[0], magic[1], magic[2], magic[3],
+        )
+      }
+
+      Self::LinkerScript { input: Some(input) } => {
+        write!(
+          formatter,
+          "linker script, not an ELF file (first input: {})",
+          input
+        )
+      }
+
+      Self::LinkerScript { input: None } => {
+        write!(formatter, "linker script, not an ELF file")
+      }
+
+      Self::Truncated { needed, available } => {
+        write!(
+          formatter,
+          "file truncated, {} bytes needed but only {} available",
+          needed, available,
+        )
+      }
+
+      Self::InvalidClass(ei_class) => {
+        write!(formatter, "invalid file class (ei_class) {:#x}", ei_class)
+      }
+
+      Self::UnsupportedClassData { class, data } => {
+        write!(
+          formatter,
+          "unsupported data encoding (ei_data) {:#x} for file class {:#x}",
+          data, class,
+        )
+      }
+
+      Self::InvalidSectionIndex(index) => {
+        write!(formatter, "no section at index {}", index)
+      }
+
+      Self::IdentificationMismatch {
+        field,
+        expected,
+        actual,
+      } => {
+        write!(
+          formatter,
+          "{} is {:#x} but the header was parsed as {:#x}",
+          field, actual, expected,
+        )
+      }
+
+      Self::UnexpectedVersion { field, version } => {
+        write!(formatter, "{} is {:#x}, EV_CURRENT is 1", field, version)
+      }
+
+      Self::InvalidSymbolName { index, st_name } => {
+        write!(
+          formatter,
+          "name of symbol {} (st_name {:#x}) is out of the string table",
+          index, st_name,
+        )
+      }
+
+      Self::InvalidStringOffset { offset, size } => {
+        write!(
+          formatter,
+          "string offset {:#x} is out of the string table ({} bytes)",
+          offset, size,
+        )
+      }
+
+      Self::CountClamped { table, count, max } => {
+        write!(
+          formatter,
+          "the {} declares {} entries but the file holds at most {}, clamped",
+          table, count, max
+        )
+      }
+
+      Self::EntrySizeMismatch {
+        table,
+        entsize,
+        expected,
+      } => {
+        write!(
+          formatter,
+          "{} entries are {} bytes, at least {} expected",
+          table, entsize, expected,
+        )
+      }
+
+      Self::UnknownRelativeType { machine } => {
+        write!(
+          formatter,
+          "relative relocation type of machine {:#x} unknown, SHT_RELR entries skipped",
+          machine,
+        )
+      }
+
+      Self::UnmappedAddress(vaddr) => {
+        write!(
+          formatter,
+          "virtual address {:#x} is not in a PT_LOAD segment",
+          vaddr
+        )
+      }
+
+      Self::UnbackedAddress { vaddr, len } => {
+        write!(
+          formatter,
+          "{} bytes at virtual address {:#x} are not all stored in the file",
+          len, vaddr,
+        )
+      }
+
+      Self::InvalidArchive { offset, reason } => {
+        write!(formatter, "invalid archive at {:#x}: {}", offset, reason)
+      }
+
+      Self::NoInterpreter => {
+        write!(formatter, "no PT_INTERP segment (statically linked?)")
+      }
+
+      Self::NoStackSegment => {
+        write!(formatter, "no PT_GNU_STACK segment, adding it is not supported")
+      }
+
+      Self::CannotRemoveSection { name, reason } => {
+        write!(formatter, "cannot remove section {}: {}", name, reason)
+      }
+
+      Self::InterpreterTooLong { length, capacity } => {
+        write!(
+          formatter,
+          "the interpreter needs {} bytes with its NUL but PT_INTERP holds {}, growing it is not supported",
+          length, capacity,
+        )
+      }
+
+      Self::LimitExceeded(limit) => {
+        write!(formatter, "resource limit exceeded ({})", limit)
+      }
+
+      Self::Context { context, .. } => formatter.write_str(context),
+    }
+  }
+}
+
+impl error::Error for ElfError {
+  // Wrappers displayed as their inner error are transparent, each error of
+  // the chain is then displayed once.
+  fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+    match self {
+      #[cfg(feature = "std")]
+      Self::Io(ref error) => error.source(),
+      Self::BytesError(ref error) => error.source(),
+      Self::Context { ref source, .. } => Some(source.as_ref()),
+      _ => None,
+    }
+  }
+}
+
+///
+/// Adds a context to the errors of the `elf` module to keep the plumbing terse,
+/// e.g., `read(offset).with_context(|| format!("reading ... at {:#x}", offset))?`.
+///
+pub trait ResultExt<T> {
+  /// Wraps the error in an [`ElfError::Context`].
+  fn context(self, context: impl Into<String>) -> result::Result<T, ElfError>;
+
+  /// Same as [`context()`](ResultExt::context()), the context is only built on error.
+  fn with_context<Context: Into<String>>(
+    self,
+    context: impl FnOnce() -> Context,
+  ) -> result::Result<T, ElfError>;
+}
+
+impl<T, Error: Into<ElfError>> ResultExt<T> for result::Result<T, Error> {
+  fn context(self, context: impl Into<String>) -> result::Result<T, ElfError> {
+    self.with_context(|| context)
+  }
+
+  fn with_context<Context: Into<String>>(
+    self,
+    context: impl FnOnce() -> Context,
+  ) -> result::Result<T, ElfError> {
+    self.map_err(|error| ElfError::Context {
+      context: context().into(),
+      source: Box::new(error.into()),
+    })
+  }
+}
+
+/// Returns the error followed by its sources, the first one is the error itself.
+pub fn chain<'error>(
+  error: &'error (dyn error::Error + 'static),
+) -> impl Iterator<Item = &'error (dyn error::Error + 'static)> {
+  core::iter::successors(Some(error), |error| error.source())
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn context() {
+    let result: result::Result<(), BytesError> = Err(BytesError::Empty);
+    let error = result.context("reading the dynamic table").unwrap_err();
+    let error = Err::<(), _>(error)
+      .with_context(|| format!("section {}", 3))
+      .unwrap_err();
+
+    assert_eq!(error.to_string(), "section 3");
+    assert_eq!(error.root(), &ElfError::BytesError(BytesError::Empty));
+    assert_eq!(Ok::<_, ElfError>(1).context("unused"), Ok(1));
+
+    let chain = chain(&error).map(|error| error.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+      chain,
+      ["section 3", "reading the dynamic table", "bytes.len() != 0"]
+    );
+  }
+
+  #[test]
+  fn transparent_wrappers() {
+    let error = ElfError::from(BytesError::Empty);
+    assert_eq!(chain(&error).count(), 1);
+  }
+
+  #[test]
+  #[cfg(feature = "std")]
+  fn io_error() {
+    let error = ElfError::from(io::Error::from(io::ErrorKind::NotFound));
+    assert_eq!(
+      error,
+      io::Error::new(io::ErrorKind::NotFound, "other message").into()
+    );
+    assert_ne!(error, io::Error::from(io::ErrorKind::PermissionDenied).into());
+    assert_ne!(error, BytesError::Empty.into());
+  }
+}
@@ -1,10 +1,17 @@
 extern crate libc;
 
-use std::fs::File;
+use std::collections::BTreeSet;
+use std::fs::{self, File};
+use std::io::Read;
 use std::ops::Deref;
+use std::ops::Range;
 use std::os::fd::{AsRawFd, RawFd};
-use std::path::Path;
-use std::{io, ptr, slice};
+use std::os::unix::fs::{FileExt, FileTypeExt, MetadataExt};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::{fmt, io, ptr, slice};
+
+use crate::reader::Reader;
 
 // https://github.com/danburkert/memmap-rs
 // https://github.com/RazrFalcon/memmap2-rs/tree/master
@@ -24,10 +31,21 @@ use std::{io, ptr, slice};
 ///
 // TODO: Better error message.
 pub struct MappedFile {
+  /// Page-aligned start of the mapping.
   data: *const libc::c_void,
+  /// Length of the mapping.
   length: libc::size_t,
+  /// Start of the content within the mapping, see [`MappedFile::range()`].
+  delta: usize,
 }
 
+// SAFETY: The mapping is owned (a private, read-only mapping of its own,
+// never handed out but as shared slices) and never written to, so that it can
+// be read from any thread. It is unmapped once, by `unmap()` which requires
+// `&mut self` (`close()` or drop), when no slice can be alive anymore.
+unsafe impl Send for MappedFile {}
+unsafe impl Sync for MappedFile {}
+
 // ╔═╗┬─┐┌─┐┌┬┐
 // ╠╣ ├┬┘│ ││││
 // ╚  ┴└─└─┘┴ ┴
@@ -42,6 +60,9 @@ impl TryFrom<&Path> for MappedFile {
     // Does `File` performs extra unnecessary operations and it is preferable to
     // use the native `open()` function instead to get a file descriptor?
 
+    // Checked before opening, a FIFO would block until a writer shows up.
+    check_mappable(&fs::metadata(path)?, path.display())?;
+
     // According to the mmap(2) manual:
     // After the mmap() call has returned, the file descriptor, fd, can be
     // closed immediately without invalidating the mapping.
@@ -53,14 +74,101 @@ impl TryFrom<&File> for MappedFile {
   type Error = io::Error;
 
   fn try_from(file: &File) -> io::Result<Self> {
-    // TODO:
-    // is_file() but breaks with process substitution "<()" (in other words
-    // with character and block device?), is !is_dir() enough?.
-    match file.metadata()?.len().try_into() {
+    let metadata = file.metadata()?;
+    check_mappable(&metadata, "the file")?;
+
+    match metadata.len().try_into() {
       // TryInto::<libc::size_t>::try_into(length)
       Err(error) => Err(io::Error::new(io::ErrorKind::InvalidData, error)),
-      Ok(length) => MappedFile::new(file.as_raw_fd(), length),
+      Ok(length) => MappedFile::new(file.as_raw_fd(), 0, length),
+    }
+  }
+}
+
+///
+/// Rejects directories, which can be opened but not read, and special files
+/// (process substitution `<(...)` is a FIFO for instance), whose content is a
+/// stream without size: they are read instead, see [`InputData`].
+///
+fn check_mappable(metadata: &fs::Metadata, name: impl fmt::Display) -> io::Result<()> {
+  if metadata.is_dir() {
+    return Err(io::Error::new(
+      io::ErrorKind::InvalidInput,
+      format!("{} is a directory", name),
+    ));
+  }
+
+  match special_file(metadata.file_type()) {
+    Some(kind) => Err(io::Error::new(
+      io::ErrorKind::InvalidInput,
+      format!(
+        "{} is {} and cannot be memory-mapped, read it instead (standard input)",
+        name, kind
+      ),
+    )),
+    None => Ok(()),
+  }
+}
+
+/// Returns the kind of special file (neither regular file nor directory).
+fn special_file(file_type: fs::FileType) -> Option<&'static str> {
+  match () {
+    _ if file_type.is_fifo() => Some("a FIFO"),
+    _ if file_type.is_socket() => Some("a socket"),
+    _ if file_type.is_char_device() => Some("a character device"),
+    _ if file_type.is_block_device() => Some("a block device"),
+    _ => None,
+  }
+}
+
+///
+/// Access hints given to the kernel when mapping a file, see
+/// [`MappedFile::options()`]. None is given by default and the hints are
+/// compiled away where unsupported, they never make the mapping fail.
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MappedFileOptions {
+  sequential: bool,
+  willneed: bool,
+}
+
+impl MappedFileOptions {
+  /// Pages are accessed in order (scans), read-ahead is more aggressive.
+  pub fn sequential(&mut self, sequential: bool) -> &mut Self {
+    self.sequential = sequential;
+    self
+  }
+
+  /// Pages are going to be accessed soon, they are read ahead at once.
+  pub fn willneed(&mut self, willneed: bool) -> &mut Self {
+    self.willneed = willneed;
+    self
+  }
+
+  /// Maps the file at the given path with the hints.
+  pub fn open<P: AsRef<Path>>(&self, path: P) -> io::Result<MappedFile> {
+    self.map(&File::options().read(true).open(path)?)
+  }
+
+  /// Maps an opened file with the hints.
+  pub fn map(&self, file: &File) -> io::Result<MappedFile> {
+    // Hints are advisory, their failures are ignored.
+    #[cfg(any(target_os = "linux", target_os = "android", target_os = "freebsd"))]
+    if self.sequential {
+      unsafe { libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_SEQUENTIAL) };
     }
+
+    let mmap = MappedFile::try_from(file)?;
+    for (enabled, advice) in [
+      (self.sequential, libc::MADV_SEQUENTIAL),
+      (self.willneed, libc::MADV_WILLNEED),
+    ] {
+      if enabled {
+        unsafe { libc::madvise(mmap.data.cast_mut(), mmap.length, advice) };
+      }
+    }
+
+    Ok(mmap)
   }
 }
 
@@ -76,7 +184,7 @@ impl Deref for MappedFile {
   // The lifetime is optional here but acts as a reminder that the output slice
   // must not outlive the mapped file.
   fn deref<'data>(&'data self) -> &'data [u8] {
-    unsafe { slice::from_raw_parts(self.data as *const u8, self.length) }
+    unsafe { slice::from_raw_parts((self.data as *const u8).add(self.delta), self.length - self.delta) }
   }
 }
 
@@ -94,7 +202,31 @@ impl AsRef<[u8]> for MappedFile {
 // ╩ ╩└─┘ ┴ ┴ ┴└─┘╶┴┘
 
 impl MappedFile {
-  fn new(fd: RawFd, length: libc::size_t) -> io::Result<Self> {
+  /// Returns a builder to map files with access hints.
+  pub fn options() -> MappedFileOptions {
+    MappedFileOptions::default()
+  }
+
+  ///
+  /// Maps `length` bytes of the file from `offset`, which needs not be
+  /// page-aligned: the mapping starts at the page containing the offset and
+  /// the bytes before it are hidden. The range must lie within the file.
+  ///
+  pub fn range(file: &File, offset: u64, length: usize) -> io::Result<Self> {
+    let size = file.metadata()?.len();
+    match offset.checked_add(length as u64) {
+      Some(end) if end <= size => MappedFile::new(file.as_raw_fd(), offset, length),
+      _ => Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!(
+          "range of {} bytes at {} past the end of the file ({} bytes)",
+          length, offset, size
+        ),
+      )),
+    }
+  }
+
+  fn new(fd: RawFd, offset: u64, length: libc::size_t) -> io::Result<Self> {
     if length == 0 {
       return Err(io::Error::new(
         io::ErrorKind::InvalidInput,
@@ -102,6 +234,19 @@ impl MappedFile {
       ));
     }
 
+    // The offset given to mmap(2) must be a multiple of the page size.
+    let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as u64;
+    let delta = (offset % page) as usize;
+    let (Ok(base), Some(length)) = (
+      libc::off_t::try_from(offset - delta as u64),
+      length.checked_add(delta),
+    ) else {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "Memory map range overflow",
+      ));
+    };
+
     let data = unsafe {
       libc::mmap(
         // Let the kernel choose the mapping address.
@@ -119,8 +264,8 @@ impl MappedFile {
         libc::MAP_PRIVATE,
         // Existing file descriptor otherwise EBADF.
         fd,
-        // Start at the beginning of the file.
-        0 as libc::off_t,
+        // Start at the page containing the offset.
+        base,
       )
     };
 
@@ -128,7 +273,61 @@ impl MappedFile {
       return Err(io::Error::last_os_error());
     }
 
-    Ok(Self { data, length })
+    Ok(Self { data, length, delta })
+  }
+
+  ///
+  /// Hints that the range of the content is about to be read in order
+  /// (`MADV_SEQUENTIAL`) and soon (`MADV_WILLNEED`): pages are read ahead
+  /// instead of faulted in one at a time on a cold cache. The range is
+  /// extended to the page containing its start.
+  ///
+  pub fn advise_sequential(&self, range: Range<usize>) -> io::Result<()> {
+    if range.start > range.end || range.end > self.len() {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!(
+          "range {:?} past the end of the mapping ({} bytes)",
+          range,
+          self.len()
+        ),
+      ));
+    }
+
+    if range.is_empty() {
+      return Ok(());
+    }
+
+    // The address given to madvise(2) must be page-aligned, the mapping is.
+    let page = unsafe { libc::sysconf(libc::_SC_PAGESIZE) } as usize;
+    let start = (self.delta + range.start) / page * page;
+    let length = self.delta + range.end - start;
+
+    for advice in [libc::MADV_SEQUENTIAL, libc::MADV_WILLNEED] {
+      let address = unsafe { self.data.cast::<u8>().add(start) };
+      if unsafe { libc::madvise(address.cast_mut().cast(), length, advice) } != 0 {
+        return Err(io::Error::last_os_error());
+      }
+    }
+
+    Ok(())
+  }
+}
+
+impl<'data> Reader<'data> for &'data MappedFile {
+  #[inline]
+  fn length(self) -> usize {
+    self.len()
+  }
+
+  fn read_bytes(self, size: usize, offset: usize) -> Option<&'data [u8]> {
+    self.as_ref().read_bytes(size, offset)
+  }
+
+  fn advise_sequential(self, offset: usize, size: usize) {
+    // Hints are advisory, their failures are ignored.
+    let end = offset.saturating_add(size).min(self.len());
+    let _ = MappedFile::advise_sequential(self, offset.min(end)..end);
   }
 }
 
@@ -138,20 +337,778 @@ impl MappedFile {
 
 impl Drop for MappedFile {
   fn drop(&mut self) {
-    if !self.data.is_null() && self.length != 0 {
-      let result = unsafe {
-        libc::munmap(
-          // Address must be page-aligned.
-          // See libc::sysconf(libc::_SC_PAGESIZE).
-          self.data.cast_mut(),
-          self.length,
-        )
+    if let Err(error) = self.unmap() {
+      // We could also use panicking() and panic!().
+      eprintln!("{:?}", error);
+    }
+  }
+}
+
+impl MappedFile {
+  /// Unmaps the file, reporting the error that dropping it would only print.
+  pub fn close(mut self) -> io::Result<()> {
+    self.unmap()
+  }
+
+  /// Unmaps the file once, later calls (drop after close) do nothing.
+  fn unmap(&mut self) -> io::Result<()> {
+    if self.data.is_null() || self.length == 0 {
+      return Ok(());
+    }
+
+    let result = unsafe {
+      libc::munmap(
+        // Address must be page-aligned (not shifted by the delta).
+        // See libc::sysconf(libc::_SC_PAGESIZE).
+        self.data.cast_mut(),
+        self.length,
+      )
+    };
+
+    // Not unmapped again even on failure, the error is reported once.
+    self.data = ptr::null();
+    self.length = 0;
+    self.delta = 0;
+
+    match result {
+      -1 => Err(io::Error::last_os_error()),
+      _ => Ok(()),
+    }
+  }
+}
+
+///
+/// The whole content of a file, memory-mapped when possible and otherwise
+/// read into a heap buffer up to a size limit: files of virtual file systems (`/proc`, `/sys`)
+/// report a zero size yet have content, and some file systems or devices
+/// refuse `mmap(2)` (`EACCES`, `ENODEV`).
+///
+pub enum InputData {
+  Mapped(MappedFile),
+  Buffered(Vec<u8>),
+}
+
+/// Opens the file, read up to [`READ_LIMIT`] bytes when it cannot be mapped.
+impl TryFrom<&Path> for InputData {
+  type Error = io::Error;
+
+  fn try_from(path: &Path) -> io::Result<Self> {
+    InputData::open(path, READ_LIMIT)
+  }
+}
+
+/// Maps the file, read up to [`READ_LIMIT`] bytes when it cannot be mapped.
+impl TryFrom<File> for InputData {
+  type Error = io::Error;
+
+  fn try_from(file: File) -> io::Result<Self> {
+    InputData::from_file(file, READ_LIMIT)
+  }
+}
+
+impl InputData {
+  ///
+  /// Opens the file, see [`Self::from_file()`] for the `limit` of the files
+  /// which cannot be mapped.
+  ///
+  pub fn open(path: &Path, limit: usize) -> io::Result<Self> {
+    if path.is_dir() {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("{} is a directory", path.display()),
+      ));
+    }
+
+    InputData::from_file(File::options().read(true).open(path)?, limit)
+  }
+
+  ///
+  /// Maps the file, or reads it up to `limit` bytes (see [`Self::read()`])
+  /// when it cannot be mapped.
+  ///
+  pub fn from_file(file: File, limit: usize) -> io::Result<Self> {
+    let metadata = file.metadata()?;
+    if metadata.is_dir() {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "the file is a directory",
+      ));
+    }
+
+    // The size is not reliable, the content is read until the end of file.
+    // Empty files are not mapped (mmap(2) rejects a zero length) and yield an
+    // empty buffer, parsing them fails like any other non-ELF input. Special
+    // files (devices, FIFOs...) are streams, they are read as well.
+    if metadata.len() == 0 || special_file(metadata.file_type()).is_some() {
+      return InputData::read(file, limit);
+    }
+
+    match MappedFile::try_from(&file) {
+      Ok(mmap) => Ok(Self::Mapped(mmap)),
+      Err(error) if matches!(error.raw_os_error(), Some(libc::EACCES | libc::ENODEV)) => {
+        InputData::read(file, limit)
+      }
+      Err(error) => Err(error),
+    }
+  }
+
+  ///
+  /// Reads the whole file into a heap buffer, even when it could be mapped,
+  /// up to `limit` bytes (see [`read_to_end_limited()`]): the size of
+  /// streams and virtual files is unknown, `/dev/zero` never ends.
+  ///
+  /// A mapped file truncated by another process kills this one with
+  /// `SIGBUS` on the next access past the new end of file, a copy is immune
+  /// to concurrent changes at the cost of reading everything up front.
+  ///
+  pub fn read(file: File, limit: usize) -> io::Result<Self> {
+    read_to_end_limited(file, limit).map(Self::Buffered)
+  }
+
+  /// Releases the content, reporting unmapping errors (see [`MappedFile::close()`]).
+  pub fn close(self) -> io::Result<()> {
+    match self {
+      Self::Mapped(mmap) => mmap.close(),
+      Self::Buffered(_) => Ok(()),
+    }
+  }
+
+  /// Returns whether the content is memory-mapped rather than buffered.
+  pub fn is_mapped(&self) -> bool {
+    matches!(self, Self::Mapped(_))
+  }
+}
+
+impl Deref for InputData {
+  type Target = [u8];
+
+  #[inline]
+  fn deref(&self) -> &[u8] {
+    match self {
+      Self::Mapped(mmap) => mmap,
+      Self::Buffered(bytes) => bytes,
+    }
+  }
+}
+
+impl AsRef<[u8]> for InputData {
+  #[inline]
+  fn as_ref(&self) -> &[u8] {
+    self.deref()
+  }
+}
+
+impl<'data> Reader<'data> for &'data InputData {
+  #[inline]
+  fn length(self) -> usize {
+    self.len()
+  }
+
+  fn read_bytes(self, size: usize, offset: usize) -> Option<&'data [u8]> {
+    self.as_ref().read_bytes(size, offset)
+  }
+
+  fn advise_sequential(self, offset: usize, size: usize) {
+    if let InputData::Mapped(mmap) = self {
+      Reader::advise_sequential(mmap, offset, size);
+    }
+  }
+}
+
+impl fmt::Debug for InputData {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    formatter
+      .debug_struct("InputData")
+      .field("length", &self.len())
+      .field("mapped", &self.is_mapped())
+      .finish()
+  }
+}
+
+///
+/// Reads a file with `pread(2)` (no seeking) when it cannot be memory-mapped
+/// (network file systems, sandboxes forbidding `mmap(2)`, files larger than
+/// the address space of 32-bit hosts...), see [`Reader`] implementation.
+///
+/// # Lifetime of the returned slices
+///
+/// [`Reader::read_bytes()`] returns slices borrowing the reader itself, not
+/// a temporary buffer. Fetched ranges are therefore kept in an append-only
+/// arena: every range is a separate heap allocation which never moves nor is
+/// freed while the reader is borrowed, so that previously returned slices
+/// stay valid however many ranges are fetched afterwards.
+///
+/// There is consequently no eviction while slices may be alive, only
+/// [`FileReader::clear()`] frees the arena and it requires `&mut self` (the
+/// borrow checker rejects it as long as a slice is in use). Memory usage is
+/// the sum of the ranges read, rounded up to [`FileReader::BLOCK_SIZE`].
+///
+pub struct FileReader {
+  file: File,
+  length: usize,
+  /// Fetched ranges: file offset and bytes, owned through raw pointers (a
+  /// `Box` would assert unique access whenever the vector reallocates).
+  chunks: Mutex<Vec<(usize, *mut [u8])>>,
+}
+
+// SAFETY: The chunks are owned through their pointers (see `chunks`), they
+// are reached through the lock only and freed with `&mut self`, hence a
+// `FileReader` can be sent and shared as a `Vec<Box<[u8]>>` in a `Mutex`.
+unsafe impl Send for FileReader {}
+unsafe impl Sync for FileReader {}
+
+// ╔═╗┬─┐┌─┐┌┬┐
+// ╠╣ ├┬┘│ ││││
+// ╚  ┴└─└─┘┴ ┴
+
+impl TryFrom<&Path> for FileReader {
+  type Error = io::Error;
+
+  fn try_from(path: &Path) -> io::Result<Self> {
+    FileReader::try_from(File::options().read(true).open(path)?)
+  }
+}
+
+impl TryFrom<File> for FileReader {
+  type Error = io::Error;
+
+  fn try_from(file: File) -> io::Result<Self> {
+    let metadata = file.metadata()?;
+    if metadata.is_dir() {
+      return Err(io::Error::new(
+        io::ErrorKind::InvalidInput,
+        "the file is a directory",
+      ));
+    }
+
+    // Offsets past usize::MAX cannot be addressed by the Reader trait anyway.
+    let length = usize::try_from(metadata.len()).unwrap_or(usize::MAX);
+    Ok(Self {
+      file,
+      length,
+      chunks: Mutex::default(),
+    })
+  }
+}
+
+// ╦═╗┌─┐┌─┐┌┬┐
+// ╠╦╝├┤ ├─┤ ││
+// ╩╚═└─┘┴ ┴─┴┘
+
+impl FileReader {
+  /// Reads are rounded to blocks of this size so that small nearby reads
+  /// (headers, table entries...) are served by the same range.
+  pub const BLOCK_SIZE: usize = 4096;
+
+  /// Returns the number of fetched ranges.
+  pub fn cached(&self) -> usize {
+    self.chunks.lock().unwrap_or_else(PoisonError::into_inner).len()
+  }
+
+  /// Frees the fetched ranges, no slice can be alive (`&mut self`).
+  pub fn clear(&mut self) {
+    let chunks = self.chunks.get_mut().unwrap_or_else(PoisonError::into_inner);
+    for (_, bytes) in chunks.drain(..) {
+      drop(unsafe { Box::from_raw(bytes) });
+    }
+  }
+
+  /// Returns the bytes from the arena, fetching them on a miss.
+  fn fetch(&self, size: usize, offset: usize) -> Option<&[u8]> {
+    let end = offset.checked_add(size).filter(|&end| end <= self.length)?;
+
+    let mut chunks = self.chunks.lock().unwrap_or_else(PoisonError::into_inner);
+    let found = chunks
+      .iter()
+      .find(|(start, bytes)| *start <= offset && end <= start + bytes.len());
+
+    let (start, bytes) = match found {
+      Some(&(start, bytes)) => (start, bytes.cast::<u8>()),
+      None => {
+        let start = offset - offset % Self::BLOCK_SIZE;
+        let stop = end.div_ceil(Self::BLOCK_SIZE).saturating_mul(Self::BLOCK_SIZE);
+        let mut bytes = vec![0; stop.min(self.length) - start].into_boxed_slice();
+        self.file.read_exact_at(&mut bytes, start as u64).ok()?;
+
+        let bytes = Box::into_raw(bytes);
+        chunks.push((start, bytes));
+        (start, bytes.cast::<u8>())
+      }
+    };
+
+    // The chunk is owned by the arena, neither moved (the Vec only moves the
+    // pointers) nor freed until clear() or drop, both of which need exclusive
+    // access: the bytes outlive the shared borrow.
+    Some(unsafe { slice::from_raw_parts(bytes.add(offset - start), size) })
+  }
+}
+
+impl<'data> Reader<'data> for &'data FileReader {
+  #[inline]
+  fn length(self) -> usize {
+    self.length
+  }
+
+  fn read_bytes(self, size: usize, offset: usize) -> Option<&'data [u8]> {
+    self.fetch(size, offset)
+  }
+}
+
+impl Drop for FileReader {
+  fn drop(&mut self) {
+    self.clear();
+  }
+}
+
+impl fmt::Debug for FileReader {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    formatter
+      .debug_struct("FileReader")
+      .field("length", &self.length)
+      .field("cached", &self.cached())
+      .finish()
+  }
+}
+
+/// Default size limit of the inputs read rather than mapped, see [`InputData`].
+pub const READ_LIMIT: usize = 256 << 20;
+
+///
+/// Reads everything from a stream that cannot be mapped (a pipe, standard
+/// input...) up to `limit` bytes, a larger input is an error rather than an
+/// unbounded allocation.
+///
+pub fn read_to_end_limited(input: impl Read, limit: usize) -> io::Result<Vec<u8>> {
+  let mut bytes = Vec::new();
+  // One more byte to tell an input of exactly `limit` bytes from a larger one.
+  input.take(limit as u64 + 1).read_to_end(&mut bytes)?;
+
+  if bytes.len() > limit {
+    return Err(io::Error::new(
+      io::ErrorKind::InvalidData,
+      format!("input larger than the limit of {} bytes", limit),
+    ));
+  }
+
+  Ok(bytes)
+}
+
+// ╦ ╦┌─┐┬  ┬┌─
+// ║║║├─┤│  ├┴┐
+// ╚╩╝┴ ┴┴─┘┴ ┴
+
+///
+/// Walks a directory tree for the ELF files (the regular files starting
+/// with the ELF magic), in the order of the names, e.g. to audit every
+/// binary of a root file system.
+///
+/// Symbolic links are followed but a directory is only read once (same
+/// device and inode), which breaks the loops. Errors (permissions...) are
+/// returned with the path and the walk goes on.
+///
+#[derive(Debug)]
+pub struct ElfFiles {
+  /// Paths left to visit with their depth, the next one last.
+  pending: Vec<(PathBuf, usize)>,
+  visited: BTreeSet<(u64, u64)>,
+  max_depth: Option<usize>,
+}
+
+impl ElfFiles {
+  ///
+  /// Walks the tree under `root`. The entries of `root` are at depth 1 and
+  /// the directories at `max_depth` are not read.
+  ///
+  pub fn new(root: impl Into<PathBuf>, max_depth: Option<usize>) -> Self {
+    Self {
+      pending: vec![(root.into(), 0)],
+      visited: BTreeSet::new(),
+      max_depth,
+    }
+  }
+
+  /// Queues the entries of the directory, sorted by name.
+  fn read_directory(&mut self, path: &Path, depth: usize) -> io::Result<()> {
+    let mut entries = fs::read_dir(path)?
+      .map(|entry| entry.map(|entry| entry.path()))
+      .collect::<io::Result<Vec<_>>>()?;
+
+    entries.sort_unstable_by(|a, b| b.cmp(a));
+    self
+      .pending
+      .extend(entries.into_iter().map(|entry| (entry, depth + 1)));
+    Ok(())
+  }
+}
+
+/// Returns `true` when the file starts with the ELF magic (short files are not).
+fn has_elf_magic(path: &Path) -> io::Result<bool> {
+  use crate::elf::ELFMAG;
+
+  let mut magic = [0u8; 4];
+  match File::open(path)?.read_exact(&mut magic) {
+    Ok(()) => Ok(magic == ELFMAG),
+    Err(error) if error.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+    Err(error) => Err(error),
+  }
+}
+
+impl Iterator for ElfFiles {
+  type Item = Result<PathBuf, (PathBuf, io::Error)>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    while let Some((path, depth)) = self.pending.pop() {
+      // Follows the symbolic links, broken ones are errors.
+      let metadata = match fs::metadata(&path) {
+        Ok(metadata) => metadata,
+        Err(error) => return Some(Err((path, error))),
       };
 
-      if result == -1 {
-        // We could also use panicking() and panic!().
-        eprintln!("{:?}", io::Error::last_os_error());
+      // Devices and FIFOs are never opened (reads could block).
+      let result = match metadata.file_type() {
+        file_type if file_type.is_dir() => {
+          let deeper = self.max_depth.is_none_or(|max| depth < max);
+          match deeper && self.visited.insert((metadata.dev(), metadata.ino())) {
+            true => self.read_directory(&path, depth).map(|()| false),
+            false => Ok(false),
+          }
+        }
+        file_type if file_type.is_file() => has_elf_magic(&path),
+        _ => Ok(false),
+      };
+
+      match result {
+        Ok(true) => return Some(Ok(path)),
+        Ok(false) => {}
+        Err(error) => return Some(Err((path, error))),
       }
     }
+
+    None
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::{env, fs, process};
+
+  #[test]
+  fn file_reader() {
+    let path = env::temp_dir().join(format!("elfprobe-reader-{}", process::id()));
+    let content = (0..3 * FileReader::BLOCK_SIZE + 10)
+      .map(|index| index as u8)
+      .collect::<Vec<_>>();
+    fs::write(&path, &content).unwrap();
+
+    let mut reader = FileReader::try_from(path.as_path()).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let first = (&reader).read_bytes(4, 1).unwrap();
+    assert_eq!(first, &content[1..5]);
+    assert_eq!((&reader).read_bytes(8, 100), Some(&content[100..108]));
+    assert_eq!(reader.cached(), 1, "Same block");
+
+    // Slices stay valid while the arena grows.
+    for offset in (0..content.len()).step_by(1000) {
+      assert_eq!((&reader).read_bytes(10, offset), content.get(offset..offset + 10));
+    }
+    assert_eq!(first, &content[1..5]);
+    assert!(reader.cached() > 1);
+
+    let last = (&reader).read_bytes(10, content.len() - 10);
+    assert_eq!(last, Some(&content[content.len() - 10..]));
+    assert_eq!((&reader).read_bytes(11, content.len() - 10), None);
+    assert_eq!((&reader).read_bytes(1, usize::MAX), None);
+    assert_eq!((&reader).length(), content.len());
+
+    reader.clear();
+    assert_eq!(reader.cached(), 0);
+  }
+
+  #[test]
+  fn file_reader_pod() {
+    let path = env::temp_dir().join(format!("elfprobe-reader-pod-{}", process::id()));
+    fs::write(&path, [0u8, 1, 2, 3, 4, 5, 6, 7]).unwrap();
+    let reader = FileReader::try_from(path.as_path()).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let values = (&reader).read_pod_slice::<[u8; 2]>(2, 3);
+    assert_eq!(values, Ok(&[[2, 3], [4, 5], [6, 7]][..]));
+    assert_eq!((&reader).read_pod::<[u8; 4]>(0), Ok(&[0, 1, 2, 3]));
+  }
+
+  #[test]
+  fn mapped_file_options() {
+    let path = env::temp_dir().join(format!("elfprobe-options-{}", process::id()));
+    fs::write(&path, [1u8, 2, 3]).unwrap();
+
+    let mmap = MappedFile::options()
+      .sequential(true)
+      .willneed(true)
+      .open(&path)
+      .unwrap();
+    assert_eq!(mmap.as_ref(), [1, 2, 3]);
+    let mmap = MappedFile::options().open(&path).unwrap();
+    assert_eq!(mmap.as_ref(), [1, 2, 3]);
+
+    fs::remove_file(&path).unwrap();
+  }
+
+  #[test]
+  fn mapped_file_range() {
+    let path = env::temp_dir().join(format!("elfprobe-range-{}", process::id()));
+    let content = (0..3 * 4096 + 10).map(|index| index as u8).collect::<Vec<_>>();
+    fs::write(&path, &content).unwrap();
+    let file = File::open(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    for (offset, length) in [(0, 10), (4097, 100), (4096, 4096), (4000, 200), (3 * 4096, 10)] {
+      let mmap = MappedFile::range(&file, offset as u64, length).unwrap();
+      assert_eq!(mmap.as_ref(), &content[offset..offset + length]);
+    }
+
+    let size = content.len() as u64;
+    assert!(MappedFile::range(&file, size - 10, 11).is_err());
+    assert!(MappedFile::range(&file, u64::MAX, 1).is_err());
+    assert!(MappedFile::range(&file, 0, 0).is_err());
+  }
+
+  #[test]
+  fn advise_sequential() {
+    let path = env::temp_dir().join(format!("elfprobe-advise-{}", process::id()));
+    fs::write(&path, vec![7u8; 3 * 4096]).unwrap();
+    let file = File::open(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    let mmap = MappedFile::range(&file, 100, 2 * 4096).unwrap();
+    assert!(mmap.advise_sequential(0..mmap.len()).is_ok());
+    assert!(mmap.advise_sequential(5000..6000).is_ok());
+    assert!(mmap.advise_sequential(10..10).is_ok());
+    assert!(mmap.advise_sequential(0..mmap.len() + 1).is_err());
+
+    // Clamped by the Reader, ignored past the end.
+    Reader::advise_sequential(&mmap, 4096, usize::MAX);
+    Reader::advise_sequential(&mmap, usize::MAX, 1);
+    assert_eq!((&mmap).read_bytes(2, 4096), Some(&[7u8, 7u8][..]));
+  }
+
+  ///
+  /// Time of a pass over a cold 512 MiB file, with and without the hints:
+  /// `cargo test --release advise_sequential_benchmark -- --ignored --nocapture`
+  ///
+  #[test]
+  #[ignore]
+  #[cfg(any(target_os = "linux", target_os = "android"))]
+  fn advise_sequential_benchmark() {
+    use std::time::Instant;
+
+    let path = env::temp_dir().join(format!("elfprobe-benchmark-{}", process::id()));
+    let block = (0..1 << 20)
+      .map(|index| ((index * 7919) >> 3) as u8)
+      .collect::<Vec<_>>();
+    let mut content = Vec::with_capacity(512 << 20);
+    (0..512).for_each(|_| content.extend_from_slice(&block));
+    fs::write(&path, &content).unwrap();
+    drop(content);
+
+    for advised in [false, true, false, true] {
+      // Evicts the (clean) pages of the file from the page cache.
+      let file = File::open(&path).unwrap();
+      file.sync_all().unwrap();
+      unsafe { libc::posix_fadvise(file.as_raw_fd(), 0, 0, libc::POSIX_FADV_DONTNEED) };
+
+      let start = Instant::now();
+      let mmap = MappedFile::try_from(&file).unwrap();
+      if advised {
+        mmap.advise_sequential(0..mmap.len()).unwrap();
+      }
+      // Cheap enough for the page faults to dominate, unlike a hash.
+      let sum = mmap.iter().fold(0u64, |sum, &byte| sum.wrapping_add(byte as u64));
+      println!("advised: {:5}, {:?} (sum {:#x})", advised, start.elapsed(), sum);
+    }
+
+    fs::remove_file(&path).unwrap();
+  }
+
+  #[test]
+  fn mapped_file_close() {
+    let path = env::temp_dir().join(format!("elfprobe-close-{}", process::id()));
+    fs::write(&path, [1u8, 2, 3]).unwrap();
+    let file = File::open(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    // Dropped by close() once unmapped.
+    MappedFile::try_from(&file).unwrap().close().unwrap();
+
+    let mut mmap = MappedFile::try_from(&file).unwrap();
+    mmap.unmap().unwrap();
+    assert!(mmap.data.is_null());
+    mmap.unmap().unwrap();
+    drop(mmap);
+  }
+
+  #[test]
+  fn input_data() {
+    let path = env::temp_dir().join(format!("elfprobe-input-{}", process::id()));
+    fs::write(&path, b"\x7FELF").unwrap();
+    let input = InputData::try_from(path.as_path()).unwrap();
+    assert!(input.is_mapped());
+    assert_eq!(input.as_ref(), b"\x7FELF");
+
+    fs::write(&path, b"").unwrap();
+    let input = InputData::try_from(path.as_path()).unwrap();
+    assert!(!input.is_mapped());
+    assert!(input.is_empty());
+
+    fs::write(&path, b"\x7FELF").unwrap();
+    let input = InputData::read(File::open(&path).unwrap(), 4).unwrap();
+    assert!(!input.is_mapped());
+    assert_eq!(input.as_ref(), b"\x7FELF");
+    assert!(InputData::read(File::open(&path).unwrap(), 3).is_err());
+    fs::remove_file(&path).unwrap();
+
+    // Virtual file systems report a zero size.
+    let input = InputData::try_from(Path::new("/proc/self/status")).unwrap();
+    assert!(!input.is_mapped());
+    assert!(input.starts_with(b"Name:"));
+  }
+
+  #[test]
+  fn special_files() {
+    let directory = env::temp_dir();
+    let message = format!("{} is a directory", directory.display());
+    let error = MappedFile::try_from(directory.as_path()).err().unwrap();
+    assert_eq!(
+      (error.kind(), error.to_string()),
+      (io::ErrorKind::InvalidInput, message.clone())
+    );
+    let error = InputData::try_from(directory.as_path()).unwrap_err();
+    assert_eq!(
+      (error.kind(), error.to_string()),
+      (io::ErrorKind::InvalidInput, message)
+    );
+    assert!(FileReader::try_from(directory.as_path()).is_err());
+
+    // Character devices are read rather than mapped.
+    let null = Path::new("/dev/null");
+    let error = MappedFile::try_from(null).err().unwrap();
+    assert!(error.to_string().starts_with("/dev/null is a character device"));
+    let input = InputData::try_from(null).unwrap();
+    assert!(!input.is_mapped() && input.is_empty());
+
+    // Endless streams are bounded.
+    let error = InputData::open(Path::new("/dev/zero"), 1 << 10).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+  }
+
+  #[test]
+  fn elf_files() {
+    use std::os::unix::fs::{symlink, PermissionsExt};
+
+    let root = env::temp_dir().join(format!("elfprobe-walk-{}", process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("bin")).unwrap();
+    fs::create_dir_all(root.join("lib/deep")).unwrap();
+    fs::create_dir_all(root.join("locked")).unwrap();
+    fs::write(root.join("bin/true"), b"\x7FELF\x02\x01").unwrap();
+    fs::write(root.join("bin/script"), b"#!/bin/sh\n").unwrap();
+    fs::write(root.join("bin/short"), b"\x7FE").unwrap();
+    fs::write(root.join("lib/deep/libc.so"), b"\x7FELF").unwrap();
+    fs::write(root.join("locked/secret"), b"\x7FELF").unwrap();
+    symlink(&root, root.join("lib/loop")).unwrap();
+    symlink(root.join("missing"), root.join("lib/broken")).unwrap();
+    fs::set_permissions(root.join("locked"), fs::Permissions::from_mode(0o000)).unwrap();
+    // Permissions do not apply to root.
+    let locked = fs::read_dir(root.join("locked")).is_err();
+
+    let walk = |max_depth| {
+      ElfFiles::new(&root, max_depth)
+        .map(|result| match result {
+          Ok(path) => path.strip_prefix(&root).unwrap().display().to_string(),
+          Err((path, _)) => format!("error {}", path.strip_prefix(&root).unwrap().display()),
+        })
+        .collect::<Vec<_>>()
+    };
+
+    let last = match locked {
+      true => "error locked",
+      false => "locked/secret",
+    };
+    assert_eq!(
+      walk(None),
+      ["bin/true", "error lib/broken", "lib/deep/libc.so", last],
+      "The loop is read once"
+    );
+    assert_eq!(walk(Some(2)), ["bin/true", "error lib/broken", last]);
+    assert_eq!(walk(Some(0)), Vec::<String>::new());
+
+    fs::set_permissions(root.join("locked"), fs::Permissions::from_mode(0o755)).unwrap();
+    fs::remove_dir_all(&root).unwrap();
+  }
+
+  #[test]
+  fn read_to_end_limited() {
+    let input: &[u8] = &[1, 2, 3, 4];
+    assert_eq!(super::read_to_end_limited(input, 4).unwrap(), input);
+    assert_eq!(super::read_to_end_limited(input, 100).unwrap(), input);
+
+    let error = super::read_to_end_limited(input, 3).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(error.to_string(), "input larger than the limit of 3 bytes");
+  }
+
+  #[test]
+  #[cfg(all(target_pointer_width = "64", target_endian = "little"))]
+  fn shared_between_threads() {
+    use crate::elf::{parse_elf, ElfFile, ElfObject, ElfType64};
+    use crate::endian::LittleEndian;
+    use std::thread;
+
+    fn shareable<T: Send + Sync>(_: &T) {}
+
+    // Names and first bytes of the sections, read by four threads at once.
+    fn read<'data, Reader: crate::reader::Reader<'data> + Sync>(
+      object: &ElfObject<'data, Reader, ElfType64<LittleEndian>>,
+    ) -> Vec<Vec<(Option<String>, Option<u8>)>> {
+      let read = || {
+        let sections = object.sections().take(16).map(|section| {
+          let bytes = object.section_bytes(section.header).ok();
+          (
+            section.display_name().map(String::from),
+            bytes.and_then(|bytes| bytes.first().copied()),
+          )
+        });
+        sections.collect::<Vec<_>>()
+      };
+      thread::scope(|scope| {
+        let threads: Vec<_> = (0..4).map(|_| scope.spawn(read)).collect();
+        threads.into_iter().map(|thread| thread.join().unwrap()).collect()
+      })
+    }
+
+    // The test executable itself.
+    let path = env::current_exe().unwrap();
+    let mmap = MappedFile::try_from(path.as_path()).unwrap();
+    let ElfFile::Elf64Le(object) = parse_elf(mmap.as_ref()).unwrap() else {
+      unreachable!();
+    };
+    shareable(&mmap);
+    shareable(&object);
+
+    let mapped = read(&object);
+    assert!(mapped[0].len() > 1);
+    assert!(mapped.iter().all(|sections| *sections == mapped[0]));
+
+    let reader = FileReader::try_from(path.as_path()).unwrap();
+    let ElfFile::Elf64Le(object) = parse_elf(&reader).unwrap() else {
+      unreachable!();
+    };
+    shareable(&object);
+    assert!(read(&object).iter().all(|sections| *sections == mapped[0]));
   }
 }
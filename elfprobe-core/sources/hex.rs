@@ -1,5 +1,7 @@
-use std::num::ParseIntError;
-use std::{error, fmt};
+use core::num::ParseIntError;
+use core::{error, fmt};
+
+use crate::alloc_prelude::*;
 
 // https://stackoverflow.com/a/38343355
 fn to_capitalized(string: &str) -> String {
@@ -66,6 +68,18 @@ pub enum HexError<'guilty> {
 
   /// So far only even word lengths are supported.
   InvalidWordLength(&'guilty str),
+
+  /// A double-quoted string is not closed before the end of its line.
+  UnterminatedQuote(&'guilty str),
+
+  /// The repeat count (`00*64`) is not a number.
+  InvalidRepeatCount(&'guilty str),
+
+  /// The padding offset (`@0x40`) is not a number.
+  InvalidOffset(&'guilty str),
+
+  /// The padding offset is before the end of the buffer (its length).
+  BackwardOffset(&'guilty str, usize),
 }
 
 impl<'guilty> error::Error for HexError<'guilty> {
@@ -93,6 +107,21 @@ impl<'guilty> fmt::Display for HexError<'guilty> {
       Self::InvalidWordLength(guilty) => {
         write!(formatter, "Word length must be even (\"{guilty:}\").")
       }
+      Self::UnterminatedQuote(guilty) => {
+        write!(formatter, "Double quote must be closed ({guilty:}).")
+      }
+      Self::InvalidRepeatCount(guilty) => {
+        write!(formatter, "Repeat count must be a number (\"{guilty:}\").")
+      }
+      Self::InvalidOffset(guilty) => {
+        write!(formatter, "Offset must be a number (\"{guilty:}\").")
+      }
+      Self::BackwardOffset(guilty, length) => {
+        write!(
+          formatter,
+          "Offset must not be before the end of the buffer {length:#x} (\"{guilty:}\")."
+        )
+      }
     }
   }
 }
@@ -128,7 +157,7 @@ impl<'data> IntoChunks<'data> for &'data str {
       return Err(ChunkError::InvalidChunkSize());
     }
 
-    if self.len() % size != 0 {
+    if !self.len().is_multiple_of(size) {
       return Err(ChunkError::InvalidWordLength(self, size));
     }
 
@@ -140,19 +169,69 @@ impl<'data> IntoChunks<'data> for &'data str {
 // ╠═╝├─┤├┬┘└─┐├┤ ├┬┘
 // ╩  ┴ ┴┴└─└─┘└─┘┴└─
 
-#[inline]
-#[allow(unused)]
-fn remove_comment(string: &str) -> &str {
-  match string.split_once(';') {
-    Some((string, _comment)) => string,
-    None => string,
+/// Returns the index of the closing double quote, escaped ones (`\"`) excluded.
+fn find_closing_quote(string: &str) -> Option<usize> {
+  let mut chars = string.char_indices().skip(1);
+  while let Some((index, char)) = chars.next() {
+    match char {
+      '\\' => _ = chars.next(),
+      '"' => return Some(index),
+      _ => (),
+    }
+  }
+  None
+}
+
+///
+/// Splits the line into words up to the comment. Words are separated by
+/// whitespaces, except double-quoted strings which are kept whole (quotes
+/// included).
+///
+fn split_words(line: &str) -> Result<Vec<&str>, HexError<'_>> {
+  let mut words = Vec::new();
+  let mut rest = line.trim_start();
+
+  while !rest.is_empty() && !rest.starts_with(';') {
+    let end = match rest.starts_with('"') {
+      true => find_closing_quote(rest).ok_or(HexError::UnterminatedQuote(rest))? + 1,
+      false => rest
+        .find(|char: char| char.is_whitespace() || char == ';')
+        .unwrap_or(rest.len()),
+    };
+
+    words.push(&rest[..end]);
+    rest = rest[end..].trim_start();
   }
+
+  Ok(words)
 }
 
 #[inline]
 #[allow(unused)]
-fn find_words(string: &str) -> impl Iterator<Item = &str> {
-  string.lines().map(remove_comment).flat_map(str::split_whitespace)
+fn find_words(string: &str) -> Result<Vec<&str>, HexError<'_>> {
+  let lines = string.lines().map(split_words);
+  lines.collect::<Result<Vec<_>, _>>().map(|lines| lines.concat())
+}
+
+/// Parses a repeat count or an offset, decimal or hexadecimal (`0x` prefix).
+fn parse_number(string: &str) -> Option<usize> {
+  match string.strip_prefix("0x").or_else(|| string.strip_prefix("0X")) {
+    Some(digits) => usize::from_str_radix(digits, 16).ok(),
+    None => string.parse().ok(),
+  }
+}
+
+/// Removes the quotes and the escaping backslashes (`\"` and `\\`).
+fn unquote(word: &str) -> String {
+  let mut string = String::with_capacity(word.len());
+  let mut chars = word[1..word.len() - 1].chars();
+  while let Some(char) = chars.next() {
+    match char {
+      '\\' => string.extend(chars.next()),
+      char => string.push(char),
+    }
+  }
+  string
 }
 
 ///
@@ -167,29 +246,42 @@ fn find_words(string: &str) -> impl Iterator<Item = &str> {
 ///   first whitespace (there is no closing quote). The first simple quotes are
 ///   removed from the output (`''` to escape them);
 /// - dots (`.`) work like single quotes but are preserved in the output;
+/// - double quotes (`"`) enclose a literal string, whitespaces and semicolons
+///   included, up to the closing quote on the same line (`\"` and `\\` to
+///   escape them). The quotes are removed from the output;
 /// - semicolons (`;`) are reserved characters that mark the beginning of a
 ///   comment (therefore completely removed from the output);
 /// - all other sequences must be even to be interpreted as valid hexadecimal
-///   integers (`12`, `BEEF`...).
+///   integers (`12`, `BEEF`...), an optional `0x` prefix is ignored (`0x7F`);
+/// - a star (`*`) repeats the integer before it, e.g. `00*64` for 64 zeros;
+/// - an at sign (`@`) pads the output with zeros up to the given absolute
+///   offset, e.g. `@0x40`.
+///
+/// Counts and offsets are decimal, or hexadecimal with the `0x` prefix.
 ///
 /// Example:
 /// ```rust
-/// let bytes = parse_hex("1A2B FF 'Ab 20 .cd ; Comment");
-/// let expected = vec![0x1A, 0x2B, 0xFF, 0x41, 0x62, 0x20, 0x2E, 0x63, 0x64];
+/// let bytes = parse_hex("1A2B 0xFF 'Ab 20 .cd \"e f\" ; Comment");
+/// let expected = vec![0x1A, 0x2B, 0xFF, 0x41, 0x62, 0x20, 0x2E, 0x63, 0x64, 0x65, 0x20, 0x66];
 /// assert_eq!(bytes.unwrap(), expected);
 /// ```
 ///
 #[allow(unused)]
-pub fn hex(string: &str) -> Result<Vec<u8>, HexError> {
+pub fn hex(string: &str) -> Result<Vec<u8>, HexError<'_>> {
   // I find this function still amateurish in its use of Rust,
   // how to improve it? Is it necessary? (readability first)
   let mut buffer: Vec<u8> = Vec::new();
 
-  for word in find_words(string) {
+  for word in find_words(string)? {
     if word.is_empty() {
       continue;
     }
 
+    if word.starts_with('"') {
+      buffer.extend_from_slice(unquote(word).as_bytes());
+      continue;
+    }
+
     // TODO: % 2
     if word.len() == 1 {
       return Err(HexError::InvalidWordLength(word));
@@ -205,7 +297,36 @@ pub fn hex(string: &str) -> Result<Vec<u8>, HexError> {
       continue;
     }
 
-    match word.into_chunks(2) {
+    // Zero padding up to an absolute offset.
+    if let Some(offset) = word.strip_prefix('@') {
+      let offset = parse_number(offset).ok_or(HexError::InvalidOffset(word))?;
+      if offset < buffer.len() {
+        return Err(HexError::BackwardOffset(word, buffer.len()));
+      }
+      buffer.resize(offset, 0);
+      continue;
+    }
+
+    // Repetition of the whole word.
+    let (digits, count) = match word.split_once('*') {
+      Some((digits, count)) => (
+        digits,
+        parse_number(count).ok_or(HexError::InvalidRepeatCount(word))?,
+      ),
+      None => (word, 1),
+    };
+
+    // The prefix alone (or the count alone) is not a number.
+    let digits = digits
+      .strip_prefix("0x")
+      .or_else(|| digits.strip_prefix("0X"))
+      .unwrap_or(digits);
+    if digits.is_empty() {
+      return Err(HexError::InvalidWordLength(word));
+    }
+
+    let start = buffer.len();
+    match digits.into_chunks(2) {
       Ok(chunks) => {
         for digits in chunks {
           match u8::from_str_radix(digits, 16) {
@@ -218,6 +339,9 @@ pub fn hex(string: &str) -> Result<Vec<u8>, HexError> {
       }
       Err(error) => return Err(HexError::ChunkError(error)),
     }
+
+    let bytes = buffer.split_off(start);
+    buffer.extend(bytes.repeat(count));
   }
 
   Ok(buffer)
@@ -300,6 +424,82 @@ mod tests {
     0x27, // '
   );
 
+  test_equality!(
+    double_quotes,
+    r#"
+    "a b;c" 01 ; Comment "ignored"
+    "\"\\" "" 'x
+    "#,
+    0x61, // a
+    0x20, // space
+    0x62, // b
+    0x3B, // ;
+    0x63, // c
+    0x01,
+    0x22, // "
+    0x5C, // \
+    0x78, // x
+  );
+
+  test_equality!(
+    prefixed_words,
+    "0x7F 0XEF 0x0102 12",
+    0x7F,
+    0xEF,
+    0x01,
+    0x02,
+    0x12
+  );
+
+  test_error!(
+    unterminated_quote,
+    "01 \"abc\\\" 02\n03",
+    "Double quote must be closed (\"abc\\\" 02)."
+  );
+
+  test_equality!(
+    repetition,
+    "0102*3 0x03*0x2 FF*0",
+    0x01,
+    0x02,
+    0x01,
+    0x02,
+    0x01,
+    0x02,
+    0x03,
+    0x03
+  );
+
+  test_equality!(
+    padding,
+    "01 @4 'a @0x6 @6 02",
+    0x01,
+    0x00,
+    0x00,
+    0x00,
+    0x61,
+    0x00,
+    0x02
+  );
+
+  test_error!(
+    invalid_repeat_count,
+    "00*64 00*x",
+    "Repeat count must be a number (\"00*x\")."
+  );
+
+  test_error!(repeat_nothing, "*4", "Word length must be even (\"*4\").");
+
+  test_error!(invalid_offset, "@0xZ", "Offset must be a number (\"@0xZ\").");
+
+  test_error!(
+    backward_offset,
+    "00*0x10 @0x08",
+    "Offset must not be before the end of the buffer 0x10 (\"@0x08\")."
+  );
+
+  test_error!(prefix_only, "01 0x", "Word length must be even (\"0x\").");
+
   test_error!(
     invalid_word_length,
     "1234 ABCD 'Hello 'World! 5 6 7 8",
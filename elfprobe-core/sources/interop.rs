@@ -0,0 +1,294 @@
+//!
+//! Conversions to the types of the [`object`] crate (`object-interop`
+//! feature): the [`Reader`] types implement [`ReadRef`], so that an
+//! [`object::read::elf::ElfFile`] is built over the same mapping without a
+//! copy, and an [`ElfFile`] tells its [`FileKind`] and [`Endianness`].
+//!
+
+use core::ops::Range;
+
+use object::read::ReadRef;
+use object::{Endianness, FileKind};
+
+use crate::elf::ElfFile;
+use crate::reader::{Reader, SubRange};
+
+// ╦═╗┌─┐┌─┐┌┬┐╦═╗┌─┐┌─┐
+// ╠╦╝├┤ ├─┤ ││╠╦╝├┤ ├┤
+// ╩╚═└─┘┴ ┴─┴┘╩╚═└─┘└
+
+/// Same as `ReadRef::read_bytes_at()` for `&[u8]`, out of range is `Err(())`.
+fn read_bytes_at<'data>(reader: impl Reader<'data>, offset: u64, size: u64) -> Result<&'data [u8], ()> {
+  let offset = usize::try_from(offset).map_err(|_| ())?;
+  if offset > reader.length() {
+    return Err(());
+  }
+  if size == 0 {
+    return Ok(&[]);
+  }
+
+  let size = usize::try_from(size).map_err(|_| ())?;
+  reader.read_bytes(size, offset).ok_or(())
+}
+
+///
+/// Same as `ReadRef::read_bytes_at_until()` for `&[u8]`. The range is read
+/// in growing chunks rather than at once: a [`FileReader`](crate::file::FileReader)
+/// reads on demand and a string is usually short compared to the rest of
+/// its table.
+///
+fn read_bytes_at_until<'data>(
+  reader: impl Reader<'data>,
+  range: Range<u64>,
+  delimiter: u8,
+) -> Result<&'data [u8], ()> {
+  const CHUNK: usize = 64;
+
+  let start = usize::try_from(range.start).map_err(|_| ())?;
+  let end = usize::try_from(range.end).map_err(|_| ())?;
+  if start > end || end > reader.length() {
+    return Err(());
+  }
+
+  let mut size = CHUNK.min(end - start);
+  loop {
+    let bytes = reader.read_bytes(size, start).ok_or(())?;
+    if let Some(length) = bytes.iter().position(|&byte| byte == delimiter) {
+      return Ok(&bytes[..length]);
+    }
+    if size == end - start {
+      return Err(());
+    }
+    size = size.saturating_mul(2).min(end - start);
+  }
+}
+
+#[cfg(all(feature = "std", unix))]
+macro_rules! impl_read_ref {
+  ($($type: ty),+) => {
+    $(
+      impl<'data> ReadRef<'data> for &'data $type {
+        fn len(self) -> Result<u64, ()> {
+          Ok(Reader::length(self) as u64)
+        }
+
+        fn read_bytes_at(self, offset: u64, size: u64) -> Result<&'data [u8], ()> {
+          read_bytes_at(self, offset, size)
+        }
+
+        fn read_bytes_at_until(self, range: Range<u64>, delimiter: u8) -> Result<&'data [u8], ()> {
+          read_bytes_at_until(self, range, delimiter)
+        }
+      }
+    )+
+  };
+}
+
+#[cfg(all(feature = "std", unix))]
+impl_read_ref!(
+  crate::file::MappedFile,
+  crate::file::InputData,
+  crate::file::FileReader
+);
+
+/// Offsets are relative to the start of the range, as [`Reader`].
+impl<'data, Reader: self::Reader<'data>> ReadRef<'data> for SubRange<Reader> {
+  fn len(self) -> Result<u64, ()> {
+    Ok(self.length() as u64)
+  }
+
+  fn read_bytes_at(self, offset: u64, size: u64) -> Result<&'data [u8], ()> {
+    read_bytes_at(self, offset, size)
+  }
+
+  fn read_bytes_at_until(self, range: Range<u64>, delimiter: u8) -> Result<&'data [u8], ()> {
+    read_bytes_at_until(self, range, delimiter)
+  }
+}
+
+// ╔═╗┌─┐┌┐┌┬  ┬┌─┐┬─┐┌─┐┬┌─┐┌┐┌┌─┐
+// ║  │ ││││└┐┌┘├┤ ├┬┘└─┐││ ││││└─┐
+// ╚═╝└─┘┘└┘ └┘ └─┘┴└─└─┘┴└─┘┘└┘└─┘
+
+/// `FileKind::Elf32` or `FileKind::Elf64`, as `FileKind::parse()` tells.
+impl<'data, Reader: self::Reader<'data>> From<&ElfFile<'data, Reader>> for FileKind {
+  fn from(file: &ElfFile<'data, Reader>) -> Self {
+    match file.is_64() {
+      true => FileKind::Elf64,
+      false => FileKind::Elf32,
+    }
+  }
+}
+
+/// The data encoding (`ei_data`), for `object::read::elf::ElfFile<Endianness>`.
+impl<'data, Reader: self::Reader<'data>> From<&ElfFile<'data, Reader>> for Endianness {
+  fn from(file: &ElfFile<'data, Reader>) -> Self {
+    match file {
+      ElfFile::Elf32Be(_) | ElfFile::Elf64Be(_) => Endianness::Big,
+      ElfFile::Elf32Le(_) | ElfFile::Elf64Le(_) => Endianness::Little,
+    }
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::elf::{parse_elf, parse_elf_view};
+  use crate::hex::hex;
+  use object::read::elf::{ElfFile32, ElfFile64};
+  use object::{Object, ObjectSection};
+  use std::{env, fs, process};
+
+  /// ELF64 little-endian relocatable file: null, `.text`, `.bss` and `.shstrtab`.
+  fn elf64() -> Vec<u8> {
+    hex(
+      r"
+        7F 'ELF 02 01 01 00 00 00000000 000000 ; e_ident
+        0100 3E00 01000000 ; e_type, e_machine, e_version
+        0000000000000000 ; e_entry
+        0000000000000000 ; e_phoff
+        6000000000000000 ; e_shoff
+        00000000 4000 ; e_flags, e_ehsize
+        0000 0000 ; e_phentsize, e_phnum
+        4000 0400 0300 ; e_shentsize, e_shnum, e_shstrndx
+
+        C3909090 ; 0x40, .text
+        00 '.text 00 '.bss 00 '.shstrtab 00 ; 0x44, .shstrtab
+
+        @0x60 00*64 ; null section
+
+        01000000 01000000 0600000000000000 0000000000000000 ; .text
+        4000000000000000 0400000000000000 00000000 00000000
+        1000000000000000 0000000000000000
+
+        07000000 08000000 0300000000000000 0000000000000000 ; .bss
+        4400000000000000 1000000000000000 00000000 00000000
+        0800000000000000 0000000000000000
+
+        0C000000 03000000 0000000000000000 0000000000000000 ; .shstrtab
+        4400000000000000 1600000000000000 00000000 00000000
+        0100000000000000 0000000000000000
+      ",
+    )
+    .unwrap()
+  }
+
+  /// ELF32 big-endian (MIPS) relocatable file: null, `.text` and `.shstrtab`.
+  fn elf32() -> Vec<u8> {
+    hex(
+      r"
+        7F 'ELF 01 02 01 00 00 00000000 000000 ; e_ident
+        0001 0008 00000001 ; e_type, e_machine, e_version
+        00000000 00000000 00000050 ; e_entry, e_phoff, e_shoff
+        00000000 0034 ; e_flags, e_ehsize
+        0000 0000 ; e_phentsize, e_phnum
+        0028 0003 0002 ; e_shentsize, e_shnum, e_shstrndx
+
+        03E00008 ; 0x34, .text
+        00 '.text 00 '.shstrtab 00 ; 0x38, .shstrtab
+
+        @0x50 00*40 ; null section
+
+        00000001 00000001 00000006 00000000 00000034 00000004 ; .text
+        00000000 00000000 00000004 00000000
+
+        00000007 00000003 00000000 00000000 00000038 00000011 ; .shstrtab
+        00000000 00000000 00000001 00000000
+      ",
+    )
+    .unwrap()
+  }
+
+  /// Section names read by elfprobe and by `object`, over the same data.
+  fn section_names<'data, Data>(data: Data) -> (Vec<String>, Vec<String>)
+  where
+    Data: Reader<'data> + ReadRef<'data> + 'data,
+  {
+    let view = parse_elf_view(data).unwrap();
+    let ours = view
+      .sections()
+      .skip(1)
+      .map(|section| section.name.unwrap_or_default().into_owned())
+      .collect();
+
+    let file = parse_elf(data).unwrap();
+    assert_eq!(FileKind::parse(data), Ok(FileKind::from(&file)));
+    let names = |sections: &mut dyn Iterator<Item = Result<&str, _>>| {
+      sections.map(|name| name.unwrap().to_string()).collect::<Vec<_>>()
+    };
+    let theirs = match FileKind::from(&file) {
+      FileKind::Elf64 => {
+        let object = ElfFile64::<Endianness, Data>::parse(data).unwrap();
+        assert_eq!(object.endian(), Endianness::from(&file));
+        names(&mut object.sections().map(|section| section.name()))
+      }
+      _ => {
+        let object = ElfFile32::<Endianness, Data>::parse(data).unwrap();
+        assert_eq!(object.endian(), Endianness::from(&file));
+        names(&mut object.sections().map(|section| section.name()))
+      }
+    };
+
+    (ours, theirs)
+  }
+
+  #[test]
+  fn same_section_names() {
+    let (ours, theirs) = section_names(elf64().as_slice());
+    assert_eq!(ours, [".text", ".bss", ".shstrtab"]);
+    assert_eq!(ours, theirs);
+
+    let (ours, theirs) = section_names(elf32().as_slice());
+    assert_eq!(ours, [".text", ".shstrtab"]);
+    assert_eq!(ours, theirs);
+
+    // Embedded at an offset, e.g. in a firmware image.
+    let image = [vec![0xFF; 0x30], elf64()].concat();
+    let (ours, theirs) = section_names(SubRange::new(image.as_slice(), 0x30, None).unwrap());
+    assert_eq!(ours, [".text", ".bss", ".shstrtab"]);
+    assert_eq!(ours, theirs);
+  }
+
+  #[test]
+  #[cfg(all(feature = "std", unix))]
+  fn same_section_names_mapped() {
+    use crate::file::{FileReader, InputData, MappedFile};
+
+    let path = env::temp_dir().join(format!("elfprobe-interop-{}", process::id()));
+    fs::write(&path, elf64()).unwrap();
+    let expected = vec![
+      String::from(".text"),
+      String::from(".bss"),
+      String::from(".shstrtab"),
+    ];
+
+    let mapped = MappedFile::try_from(path.as_path()).unwrap();
+    assert_eq!(section_names(&mapped), (expected.clone(), expected.clone()));
+    let input = InputData::try_from(path.as_path()).unwrap();
+    assert_eq!(section_names(&input), (expected.clone(), expected.clone()));
+    let reader = FileReader::try_from(path.as_path()).unwrap();
+    assert_eq!(section_names(&reader), (expected.clone(), expected));
+    fs::remove_file(&path).unwrap();
+  }
+
+  #[test]
+  fn read_bytes_until() {
+    let mut bytes = [b'a'; 200];
+    bytes[150] = 0;
+    let range = SubRange::new(bytes.as_slice(), 10, None).unwrap();
+
+    assert_eq!(range.read_bytes_at_until(0..190, 0).map(<[u8]>::len), Ok(140));
+    assert_eq!(range.read_bytes_at_until(0..100, 0), Err(()));
+    assert_eq!(range.read_bytes_at_until(0..191, 0), Err(()), "Past the end");
+    assert_eq!(range.read_bytes_at(189, 1), Ok(&b"a"[..]));
+    assert_eq!(range.read_bytes_at(190, 0), Ok(&[][..]));
+    assert_eq!(range.read_bytes_at(190, 1), Err(()));
+    assert_eq!(range.read_bytes_at(191, 0), Err(()), "Past the end");
+    assert_eq!(range.read_bytes_at(u64::MAX, 0), Err(()));
+    assert_eq!(ReadRef::len(range), Ok(190));
+  }
+}
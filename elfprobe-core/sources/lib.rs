@@ -0,0 +1,215 @@
+//!
+//! Zero-copy ELF parsing: the file is borrowed (e.g. memory-mapped with
+//! [`MappedFile`](file::MappedFile)) and headers, tables and strings are
+//! returned as references into it.
+//!
+//! ```no_run
+//! use elfprobe_core::prelude::*;
+//!
+//! let file = ElfFile::open("/bin/ls")?;
+//! print!("{}", file.elf()?.header_display());
+//! # Ok::<(), elfprobe_core::error::ElfError>(())
+//! ```
+//!
+//! Without the `std` feature (default), the parsing builds with `no_std` and
+//! `alloc`: the files, archives and the settings of the output are left out.
+//! The files and archives are also left out of the targets other than Unix
+//! (`wasm32-unknown-unknown`), see the `summary` example.
+//!
+//! The output is deterministic: what is shown to the user is emitted in the
+//! order of the file or sorted (ordered containers, explicit sorts with the
+//! ties broken), never in the order of a hash container, so that two runs
+//! over the same files print the same bytes (golden tests, diffs between
+//! runs). `clippy.toml` forbids `HashMap` and `HashSet`.
+//!
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(all(feature = "std", unix))]
+pub mod archive;
+pub mod elf;
+pub mod endian;
+pub mod error;
+#[cfg(all(feature = "std", unix))]
+pub mod file;
+#[cfg(feature = "object-interop")]
+pub mod interop;
+pub mod pod;
+pub mod primitive;
+pub mod reader;
+pub mod utils;
+pub mod writer;
+
+#[cfg(any(test, doc, clippy))]
+mod hex;
+
+/// The `alloc` items of the std prelude, glob imported where they are used.
+mod alloc_prelude {
+  pub use alloc::borrow::ToOwned;
+  pub use alloc::boxed::Box;
+  pub use alloc::string::{String, ToString};
+  pub use alloc::vec::Vec;
+  pub use alloc::{format, vec};
+}
+
+///
+/// The cargo features the library was built with, in the order of the
+/// manifest, and `mmap` when the files are memory-mapped (`std` on Unix).
+/// Demangling has no feature, it is always built in.
+///
+pub const FEATURES: &[&str] = &[
+  #[cfg(feature = "unaligned")]
+  "unaligned",
+  #[cfg(feature = "std")]
+  "std",
+  #[cfg(feature = "zlib")]
+  "zlib",
+  #[cfg(feature = "zstd")]
+  "zstd",
+  #[cfg(feature = "sha")]
+  "sha",
+  #[cfg(feature = "xz")]
+  "xz",
+  #[cfg(feature = "serde")]
+  "serde",
+  #[cfg(feature = "object-interop")]
+  "object-interop",
+  #[cfg(all(feature = "std", unix))]
+  "mmap",
+];
+
+///
+/// Returns the text of `--version`: the crate version, `git describe` of
+/// the checkout it was built from (when known), the target triple and the
+/// [`FEATURES`].
+///
+pub fn version() -> alloc::string::String {
+  use alloc::format;
+
+  let describe = match option_env!("ELFPROBE_GIT_DESCRIBE") {
+    Some(describe) => format!(" ({})", describe),
+    None => alloc::string::String::new(),
+  };
+  format!(
+    "elfprobe {}{}\ntarget: {}\nfeatures: {}\n",
+    env!("CARGO_PKG_VERSION"),
+    describe,
+    env!("ELFPROBE_TARGET"),
+    FEATURES.join(", ")
+  )
+}
+
+///
+/// The items needed to parse a file and walk through it, to be glob
+/// imported (`use elfprobe_core::prelude::*`).
+///
+pub mod prelude {
+  #[cfg(all(feature = "std", unix))]
+  pub use crate::elf::OwnedElfFile;
+  pub use crate::elf::{
+    parse_elf, parse_elf_view, parse_elf_with, ElfFile, ElfHeader, ElfObject, ElfType, ElfType32, ElfType64,
+    ElfView, ParseOptions,
+  };
+  pub use crate::endian::{BigEndian, Endianness, LittleEndian, NativeEndian, RuntimeEndian};
+  pub use crate::error::{BytesError, ElfError};
+  #[cfg(all(feature = "std", unix))]
+  pub use crate::file::{FileReader, MappedFile};
+  pub use crate::reader::Reader;
+  pub use crate::writer::Writer;
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use std::env;
+  use std::process::Command;
+
+  /// Runs `cargo check` on the library, in a directory of its own next to `debug`.
+  fn check(directory: &str, arguments: &[&str]) {
+    let executable = env::current_exe().unwrap(); // target/debug/deps/elfprobe_core-<hash>
+    let target = executable.ancestors().nth(3).unwrap().join(directory);
+
+    let output = Command::new(env!("CARGO"))
+      .args(["check", "--lib"])
+      .args(arguments)
+      .arg("--manifest-path")
+      .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"))
+      .arg("--target-dir")
+      .arg(target)
+      .output()
+      .unwrap();
+    assert!(
+      output.status.success(),
+      "{}",
+      String::from_utf8_lossy(&output.stderr)
+    );
+  }
+
+  /// `--version` lists the features this build enabled, and only those.
+  #[test]
+  fn version_features() {
+    let version = version();
+    assert!(version.starts_with(concat!("elfprobe ", env!("CARGO_PKG_VERSION"))));
+    assert!(version.contains(concat!("target: ", env!("ELFPROBE_TARGET"), "\n")));
+
+    let features = version
+      .lines()
+      .find_map(|line| line.strip_prefix("features: "))
+      .unwrap();
+    let features: Vec<&str> = features
+      .split(", ")
+      .filter(|feature| !feature.is_empty())
+      .collect();
+    for (feature, enabled) in [
+      ("unaligned", cfg!(feature = "unaligned")),
+      ("std", cfg!(feature = "std")),
+      ("zlib", cfg!(feature = "zlib")),
+      ("zstd", cfg!(feature = "zstd")),
+      ("sha", cfg!(feature = "sha")),
+      ("xz", cfg!(feature = "xz")),
+      ("serde", cfg!(feature = "serde")),
+      ("object-interop", cfg!(feature = "object-interop")),
+      ("mmap", cfg!(all(feature = "std", unix))),
+    ] {
+      assert_eq!(features.contains(&feature), enabled, "{}", feature);
+    }
+    assert_eq!(features, FEATURES);
+  }
+
+  /// The parsing builds without `std`.
+  #[test]
+  fn no_std_build() {
+    check(
+      "no-std",
+      &["--no-default-features", "--features", "unaligned,xz,sha"],
+    );
+  }
+
+  ///
+  /// The library builds for WebAssembly, with and without `std`. Needs the
+  /// target (`rustup target add wasm32-unknown-unknown`), the unit tests run
+  /// with `cargo test --lib --target wasm32-wasip1` and a WASI runtime.
+  ///
+  #[test]
+  #[ignore]
+  fn wasm_build() {
+    check("wasm", &["--target", "wasm32-unknown-unknown"]);
+    check(
+      "wasm",
+      &[
+        "--target",
+        "wasm32-unknown-unknown",
+        "--no-default-features",
+        "--features",
+        "unaligned",
+      ],
+    );
+  }
+}
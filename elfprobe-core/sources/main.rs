@@ -68,22 +68,110 @@ https://maskray.me/blog/2021-01-31-metadata-sections-comdat-and-shf-link-order
 
 // od -An -t x1 -j 4 -N 1 $(which ls) | tr -d [[:space:]]
 
+use std::cell::RefCell;
 use std::env;
+use std::fmt::Write;
 use std::fs::File;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
-// mod MappedFile;
-mod elf;
-mod endian;
-mod error;
-mod file;
-mod pod;
-mod primitive;
-mod reader;
+use elfprobe_core::file::ElfFiles;
+use elfprobe_core::utils::{
+  for_each_ordered, terminal_width, DigestAlgorithm, Style, TableBuilder, TableFormat,
+};
+use elfprobe_core::{elf, reader};
 
-#[cfg(any(test, doc, clippy))]
-mod hex;
+// ╔═╗┬ ┬┌┬┐┌─┐┬ ┬┌┬┐
+// ║ ║│ │ │ ├─┘│ │ │
+// ╚═╝└─┘ ┴ ┴  └─┘ ┴
 
-use std::io;
+// The macros of std are shadowed, so that the files shown by the workers of
+// --jobs are printed whole and in order (see `Captured`).
+macro_rules! print {
+  ($($arg:tt)*) => { emit(false, format_args!($($arg)*)) };
+}
+
+macro_rules! println {
+  () => { print!("\n") };
+  ($($arg:tt)*) => { print!("{}\n", format_args!($($arg)*)) };
+}
+
+macro_rules! eprint {
+  ($($arg:tt)*) => { emit(true, format_args!($($arg)*)) };
+}
+
+macro_rules! eprintln {
+  () => { eprint!("\n") };
+  ($($arg:tt)*) => { eprint!("{}\n", format_args!($($arg)*)) };
+}
+
+thread_local! {
+  static CAPTURED: RefCell<Option<Captured>> = const { RefCell::new(None) };
+}
+
+/// Output of a file shown by a worker, printed once its turn comes.
+#[derive(Debug, Default)]
+struct Captured(Vec<(bool, String)>);
+
+impl Captured {
+  /// Runs `show` with the output of the current thread captured.
+  fn run<Output>(show: impl FnOnce() -> Output) -> (Self, Output) {
+    CAPTURED.with(|captured| captured.replace(Some(Self::default())));
+    let output = show();
+    (CAPTURED.with(RefCell::take).unwrap_or_default(), output)
+  }
+
+  /// Prints the output, the standard error interleaved as it was written.
+  fn print(self) {
+    for (error, text) in self.0 {
+      write_output(error, format_args!("{}", text));
+    }
+  }
+}
+
+/// Prints to the standard output (or error), captured when shown by a worker.
+fn emit(error: bool, arguments: std::fmt::Arguments) {
+  let captured = CAPTURED.with(|captured| match captured.borrow_mut().as_mut() {
+    Some(Captured(output)) => {
+      output.push((error, arguments.to_string()));
+      true
+    }
+    None => false,
+  });
+
+  if !captured {
+    write_output(error, arguments);
+  }
+}
+
+///
+/// Writes to the standard output (or error) without panicking as `print!()`.
+/// A closed pipe (`elfprobe /bin/ls | head`) quietly stops with success, any
+/// other error of the standard output stops with `Status::Io`.
+///
+fn write_output(error: bool, arguments: std::fmt::Arguments) {
+  use std::io::Write;
+
+  if error {
+    // Nowhere left to report a failure of the standard error.
+    let _ = io::stderr().lock().write_fmt(arguments);
+    return;
+  }
+
+  match io::stdout().lock().write_fmt(arguments) {
+    Ok(()) => {}
+    Err(error) if error.kind() == io::ErrorKind::BrokenPipe => std::process::exit(Status::Success as i32),
+    Err(error) => {
+      let _ = writeln!(
+        io::stderr(),
+        "{}",
+        Style::Red.paint(format!("standard output: {}", error))
+      );
+      std::process::exit(Status::Io as i32);
+    }
+  }
+}
 
 #[allow(unused)]
 fn test_file() -> io::Result<()> {
@@ -108,33 +196,2119 @@ fn test_file() -> io::Result<()> {
   Ok(())
 }
 
-fn main() {
-  use crate::file::MappedFile;
-  use crate::*;
-  use std::path::Path;
-
+fn main() -> ExitCode {
   // test_file();
   // return;
 
-  let path: String = env::args().nth(1).expect("File missing");
-  let path: &Path = path.as_ref();
+  let arguments = match Arguments::parse(env::args().skip(1)) {
+    Ok(arguments) => arguments,
+    Err(message) => return usage_error(&message),
+  };
+  TableFormat::set_current(arguments.format);
+  Style::set_enabled(arguments.color);
+  TableBuilder::set_line_width(arguments.width);
+  TableBuilder::set_row_limit(arguments.rows);
+
+  if arguments.diff {
+    return match &arguments.paths[..] {
+      [old, new] => show_diff(Path::new(old), Path::new(new), &arguments).into(),
+      _ => usage_error("--diff expects two files"),
+    };
+  } else if arguments.ignore.is_some() {
+    return usage_error("--ignore requires --diff");
+  }
 
-  // let mmap = <MappedFile as TryFrom<&Path>>::try_from(path.as_ref());
-  let mmap = MappedFile::try_from(path).expect("expect MappedFile");
-  let slice = mmap.as_ref();
+  if let Some(patch) = &arguments.patch {
+    return match (&arguments.paths[..], &arguments.output) {
+      ([path], Some(output)) => patch_file(Path::new(path), patch, Path::new(output)).into(),
+      (_, None) => usage_error(&format!("{} requires --output", patch.flag())),
+      _ => usage_error(&format!("{} expects one file", patch.flag())),
+    };
+  } else if arguments.extract {
+    return match (&arguments.paths[..], &arguments.output) {
+      ([path], Some(output)) => extract_image(Path::new(path), &arguments, Path::new(output)).into(),
+      (_, None) => usage_error("--extract requires --output"),
+      _ => usage_error("--extract expects one file"),
+    };
+  } else if arguments.output.is_some() {
+    return usage_error(
+      "--output requires --set-interp, --{set,clear}-execstack, --remove-section or --extract",
+    );
+  }
 
-  // println!("{:#04X?}", &slice[0..4]);
+  // Several files are shown one after the other, each with its name, as
+  // readelf. The next file is shown after a failure, the worst status wins.
+  let several = arguments.paths.len() > 1 || arguments.recursive;
+  let mut banners = 0;
+  let mut banner = |path: &Path| {
+    if several {
+      if banners != 0 {
+        println!();
+      }
+      println!("File: {}", path.display());
+      banners += 1;
+    }
+  };
 
-  use crate::elf::parse_elf;
+  // Directories are walked for ELF files with --recursive.
+  let files = arguments.paths.iter().map(PathBuf::from).flat_map(|path| {
+    let files: Box<dyn Iterator<Item = Walked> + Send> = match arguments.recursive && path.is_dir() {
+      true => Box::new(ElfFiles::new(path, arguments.max_depth)),
+      false => Box::new(std::iter::once(Ok(path))),
+    };
+    files
+  });
 
-  println!("{:#x?}", parse_elf(slice));
+  let mut status = Status::Success;
+  if arguments.jobs == 1 {
+    for file in files {
+      if let Ok(path) = &file {
+        banner(path);
+      }
+      status = status.max(show_walked(&file, &arguments));
+    }
+    return status.into();
+  }
 
-  // mmap.close().expect("MappedFile close");
+  // The workers capture their output, printed here in the order of the files.
+  let work = |file: &Walked| {
+    TableFormat::set_current(arguments.format);
+    Style::set_enabled(arguments.color);
+    TableBuilder::set_line_width(arguments.width);
+    TableBuilder::set_row_limit(arguments.rows);
+    Captured::run(|| show_walked(file, &arguments))
+  };
+  for_each_ordered(files, arguments.jobs, work, |file, shown| {
+    let path = match &file {
+      Ok(path) => path,
+      Err((path, _)) => path,
+    };
+    match shown {
+      Ok((captured, shown)) => {
+        if file.is_ok() {
+          banner(path);
+        }
+        captured.print();
+        status = status.max(shown);
+      }
+      // The output is lost with the panic, the other files are still shown.
+      Err(message) => {
+        let message = format!("{}: internal error ({}), file skipped", path.display(), message);
+        eprintln!("{}", Style::Red.paint(message));
+        status = status.max(Status::Invalid);
+      }
+    }
+  });
 
   // println!("{:x?}", data); // lower case
   // println!("{:X?}", data); // upper case
   // println!("{:02X?}", data); // print the leading zero
   // println!("{:#04X?}", data); // pretty modifier
+
+  status.into()
+}
+
+// ╔═╗┬─┐┌─┐┬ ┬┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ╠═╣├┬┘│ ┬│ ││││├┤ │││ │ └─┐
+// ╩ ╩┴└─└─┘└─┘┴ ┴└─┘┘└┘ ┴ └─┘
+
+const USAGE: &str = "\
+Usage: elfprobe [options] <file|directory>...
+
+Displays information about ELF files and archives (standard input with `-`).
+Without selector, a summary of the whole file is shown. The readelf selectors
+(-h to -a) combine, the other ones are shown alone.
+
+Selectors:
+  -h, --file-header       Display the ELF file header
+  -S, --section-headers   Display the section headers (alias --sections)
+  -l, --program-headers   Display the program headers (alias --segments)
+  -s, --symbols           Display the symbol tables (alias --syms)
+  -d, --dynamic           Display the dynamic section
+  -n, --notes             Display the notes
+  -a, --all               Same as -h -S -l -d -s -n
+      --checksec          Display the security features
+      --debug-sections    Display the DWARF sections and their sizes
+      --reloc-stats       Display the relocation counts per type and kind
+      --validate          Display the validation issues (header, sections, segments)
+      --layout            Display the gaps of the file layout (bytes outside the headers)
+      --go-buildinfo      Display the Go version, modules and build settings
+      --source-files      Display the source files (STT_FILE symbols) and their symbol counts
+      --coverage          Display how much of the executable sections the function symbols cover
+      --cross-check       Display the allocated sections which the program headers load elsewhere
+      --modinfo           Display the .modinfo entries of a kernel module
+      --vermagic=<string> Same as --modinfo, failing unless the vermagic is the given one
+      --verify-debuglink <path>
+                          Compare the CRC of the debug file with the one of .gnu_debuglink
+      --hash-sections[=<algorithm>]
+                          Display the digests of the sections and of the file: crc32,
+                          sha1 or sha256 (default, requires the `sha` feature)
+      --hash-decompressed Hash the decompressed contents of the compressed sections
+      --hex-dump=<section>
+                          Display the bytes of a section (name or number), or of
+                          the data appended to the file with `overlay`
+      --annotate-header   Display the fields of the ELF header with their offsets and bytes
+      --annotate-section=<number>
+                          Same as --annotate-header for a section header
+      --annotate-segment=<number>
+                          Same as --annotate-header for a program header
+      --extract-section=<section>
+                          Write the bytes of a section (name or number) to the output
+      --decompress        Decompress the extracted section: SHF_COMPRESSED sections, and
+                          the ELF file of .gnu_debugdata (requires the `xz` feature)
+      --diff <old> <new>  Display the structural differences between two files
+      --ignore=<parts>    With --diff, compare the section contents too but the parts
+                          differing between rebuilds: build-id, debug, comment
+      --set-interp <path> Replace the interpreter in a copy of the file written to --output,
+                          the new path must fit in the PT_INTERP segment
+      --set-execstack     Set the PF_X flag of PT_GNU_STACK in a copy written to --output
+      --clear-execstack   Clear the PF_X flag of PT_GNU_STACK in a copy written to --output
+      --remove-section=<name>
+                          Remove the non-allocated sections of this name in a copy written
+                          to --output, can be repeated
+      --extract           Write the ELF image at --offset to --output, up to the end of
+                          its last section or segment or --length bytes
+      --output <file>     File written by --set-interp, --{set,clear}-execstack,
+                          --remove-section and --extract
+
+Options:
+      --offset <offset>   Read the file from this offset (decimal or 0x-prefixed), an ELF
+                          file embedded in a firmware image or a memory dump
+      --length <size>     Read this many bytes at most from the offset
+      --demangle          Demangle the symbol names (C++, Rust)
+      --sort=<key>        Sort the symbols (-s) by name, value or size
+      --only=<kinds>      List only the symbols (-s) of these comma-separated kinds: defined,
+                          undefined, global, local, weak, func or object
+      --entropy           Add the entropy of the sections to the section headers (-S),
+                          flagged above 7.2 bits per byte (compressed or encrypted)
+      --color[=<when>]    Color the output: auto (default), always or never
+  -W, --wide              Do not truncate the long names to fit the terminal
+      --no-limit          Write every row of the tables (10,000 as text on a terminal)
+      --format=<format>   Write the tables as text (default), csv or tsv
+      --strict            Fail on malformed tables instead of warning
+      --follow-scripts    Follow the linker scripts standing in for libraries
+  -r, --recursive         Walk the directories for ELF files (symbolic links followed)
+      --max-depth=<depth> Walk the directories down to the given depth
+      --jobs <count>      Show the files with this many threads (0 for one per processor),
+                          each file printed whole and in order
+      --no-mmap           Copy the files instead of mapping them
+      --max-input-size=<bytes>
+                          Size limit of the inputs read rather than mapped
+                          (standard input, devices, --no-mmap...), 256 MiB
+      --help              Display this help and exit
+      --version           Display the version, target and features and exit
+
+Exit status:
+  0  Every file was shown
+  1  Warnings (malformed tables, validation issues)
+  2  Usage error
+  3  Not an ELF file, or parsing failed
+  4  The file could not be read
+When several files are given, the worst status is returned. With --diff,
+1 means that the files differ.
+";
+
+/// Change written to a copy of the file, see [`patch_file()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Patch {
+  /// New path of the interpreter (`--set-interp`).
+  Interpreter(String),
+  /// Whether the stack is executable (`--set-execstack`, `--clear-execstack`).
+  ExecStack(bool),
+  /// Names of the sections to remove (`--remove-section`, repeated).
+  RemoveSections(Vec<String>),
+}
+
+impl Patch {
+  /// Adds the patch of a flag, only the removed sections add up.
+  fn add(current: &mut Option<Self>, patch: Self) -> Result<(), String> {
+    match (current.as_mut(), patch) {
+      (None, patch) => *current = Some(patch),
+      (Some(Self::RemoveSections(names)), Self::RemoveSections(more)) => names.extend(more),
+      (Some(current), patch) => {
+        return Err(format!(
+          "{} cannot be combined with {}",
+          patch.flag(),
+          current.flag()
+        ));
+      }
+    }
+    Ok(())
+  }
+
+  /// Returns the flag of the patch, for the usage errors.
+  fn flag(&self) -> &'static str {
+    match self {
+      Self::Interpreter(_) => "--set-interp",
+      Self::ExecStack(true) => "--set-execstack",
+      Self::ExecStack(false) => "--clear-execstack",
+      Self::RemoveSections(_) => "--remove-section",
+    }
+  }
+}
+
+/// Parts of the file selected with the readelf flags (`-h`, `-S`...).
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+struct Selection {
+  file_header: bool,
+  section_headers: bool,
+  program_headers: bool,
+  symbols: bool,
+  dynamic: bool,
+  notes: bool,
+}
+
+impl Selection {
+  /// Selects the part of the short flag, returns `false` when unknown.
+  fn select(&mut self, flag: char) -> bool {
+    match flag {
+      'h' => self.file_header = true,
+      'S' => self.section_headers = true,
+      'l' => self.program_headers = true,
+      's' => self.symbols = true,
+      'd' => self.dynamic = true,
+      'n' => self.notes = true,
+      'a' => "hSlsdn".chars().for_each(|flag| _ = self.select(flag)),
+      _ => return false,
+    }
+    true
+  }
+
+  fn is_empty(&self) -> bool {
+    *self == Self::default()
+  }
+}
+
+/// Short flag of the long selectors, readelf aliases included.
+fn selector(name: &str) -> Option<char> {
+  Some(match name {
+    "file-header" => 'h',
+    "section-headers" | "sections" => 'S',
+    "program-headers" | "segments" => 'l',
+    "symbols" | "syms" => 's',
+    "dynamic" => 'd',
+    "notes" => 'n',
+    "all" => 'a',
+    _ => return None,
+  })
+}
+
+/// Command line, see [`USAGE`].
+#[derive(Debug)]
+struct Arguments {
+  view: View,
+  paths: Vec<String>,
+  /// Size limit of the inputs read rather than mapped (`--max-input-size=<bytes>`).
+  limit: usize,
+  /// Linker scripts standing in for libraries are followed to their first
+  /// input, a few levels deep at most (scripts may reference each other).
+  follow: bool,
+  /// Files are copied rather than mapped with --no-mmap, so that files
+  /// truncated while being read (live build directories) cannot raise SIGBUS.
+  mmap: bool,
+  /// Malformed tables are shown empty or partial with a warning, unless
+  /// --strict is given (validation pipelines).
+  options: elf::ParseOptions,
+  /// Tables are written as CSV or TSV with --format=csv|tsv (spreadsheets).
+  format: TableFormat,
+  /// Colors are enabled on terminals unless NO_COLOR is set, or forced with
+  /// --color=always|never (e.g. `| less -R`).
+  color: bool,
+  /// Lines are cut to the width of the terminal (100 columns when not a
+  /// terminal), unlimited with -W/--wide.
+  width: Option<usize>,
+  /// Text tables stop after 10,000 rows on terminals, with a count of the rest,
+  /// unless --no-limit is given.
+  rows: Option<usize>,
+  /// Directories are walked for the ELF files with --recursive, down to
+  /// --max-depth=<depth> (the entries of the directory are at depth 1).
+  recursive: bool,
+  max_depth: Option<usize>,
+  /// Files are shown by --jobs=<count> threads (one by default, 0 for one
+  /// per processor), each file printed whole and in order.
+  jobs: usize,
+  /// Two files are compared with --diff (sections, segments, symbols), and
+  /// their contents but the parts listed with --ignore (reproducible builds).
+  diff: bool,
+  ignore: Option<elf::Ignored>,
+  /// The file is patched with --set-interp or --{set,clear}-execstack, the
+  /// copy is written to --output.
+  patch: Option<Patch>,
+  output: Option<String>,
+  /// The file is read from --offset=<offset>, --length=<size> bytes at most
+  /// (embedded ELF files), and the image is carved to --output with --extract.
+  offset: usize,
+  length: Option<usize>,
+  extract: bool,
+}
+
+impl Arguments {
+  ///
+  /// Parses the arguments (program name excluded). Short selectors can be
+  /// grouped (`-hS`) and `--` ends the options. `--help` and `--version` exit
+  /// right away, other mistakes are returned as the message of a usage error.
+  ///
+  fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+    let mut selection = Selection::default();
+    let (mut checksec, mut debug_sections, mut demangle, mut wide) = (false, false, false, false);
+    let mut no_limit = false;
+    let (mut reloc_stats, mut validate, mut layout, mut go_buildinfo) = (false, false, false, false);
+    let (mut hex_dump, mut entropy) = (None, false);
+    let (mut hash_sections, mut decompressed) = (None, false);
+    let (mut modinfo, mut vermagic) = (false, None);
+    let (mut debug_file, mut source_files, mut coverage, mut cross_check) = (None, false, false, false);
+    let (mut extract, mut decompress) = (None, false);
+    let mut annotate = None;
+    let mut listing = elf::SymbolSelection::default();
+    let mut arguments = Arguments {
+      view: View::Summary,
+      paths: Vec::new(),
+      limit: DEFAULT_INPUT_SIZE,
+      follow: false,
+      mmap: true,
+      options: elf::ParseOptions::LENIENT,
+      format: TableFormat::Text,
+      color: false,
+      width: None,
+      rows: None,
+      recursive: false,
+      max_depth: None,
+      jobs: 1,
+      diff: false,
+      ignore: None,
+      patch: None,
+      output: None,
+      offset: 0,
+      length: None,
+      extract: false,
+    };
+    let mut color = None;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+      let Some(flag) = arg.strip_prefix('-').filter(|flag| !flag.is_empty()) else {
+        arguments.paths.push(arg);
+        continue;
+      };
+
+      let Some(name) = flag.strip_prefix('-') else {
+        for flag in flag.chars() {
+          match flag {
+            'W' => wide = true,
+            'r' => arguments.recursive = true,
+            _ if selection.select(flag) => {}
+            _ => return Err(format!("unknown option `-{}`", flag)),
+          }
+        }
+        continue;
+      };
+
+      let (name, value) = match name.split_once('=') {
+        Some((name, value)) => (name, Some(value)),
+        None => (name, None),
+      };
+
+      match (name, value) {
+        ("", None) => {
+          arguments.paths.extend(args.by_ref());
+          break;
+        }
+        ("help", None) => {
+          print!("{}", USAGE);
+          std::process::exit(0);
+        }
+        ("version", None) => {
+          print!("{}", elfprobe_core::version());
+          std::process::exit(0);
+        }
+        ("checksec", None) => checksec = true,
+        ("debug-sections", None) => debug_sections = true,
+        ("diff", None) => arguments.diff = true,
+        ("set-interp", Some(path)) => {
+          Patch::add(&mut arguments.patch, Patch::Interpreter(String::from(path)))?
+        }
+        ("set-interp", None) => {
+          let path = args.next().ok_or("--set-interp expects the interpreter path")?;
+          Patch::add(&mut arguments.patch, Patch::Interpreter(path))?;
+        }
+        ("set-execstack", None) => Patch::add(&mut arguments.patch, Patch::ExecStack(true))?,
+        ("clear-execstack", None) => Patch::add(&mut arguments.patch, Patch::ExecStack(false))?,
+        ("remove-section", Some(name)) => Patch::add(
+          &mut arguments.patch,
+          Patch::RemoveSections(vec![String::from(name)]),
+        )?,
+        ("remove-section", None) => {
+          let name = args.next().ok_or("--remove-section expects a section name")?;
+          Patch::add(&mut arguments.patch, Patch::RemoveSections(vec![name]))?;
+        }
+        ("offset" | "length", value) => {
+          let number = match value {
+            Some(number) => String::from(number),
+            None => args
+              .next()
+              .ok_or_else(|| format!("--{} expects a number", name))?,
+          };
+          let number = parse_number(&number).ok_or_else(|| format!("--{} expects a number", name))?;
+          match name {
+            "offset" => arguments.offset = number,
+            _ => arguments.length = Some(number),
+          }
+        }
+        ("extract", None) => arguments.extract = true,
+        ("output", Some(path)) => arguments.output = Some(String::from(path)),
+        ("output", None) => {
+          let path = args.next().ok_or("--output expects a file")?;
+          arguments.output = Some(path);
+        }
+        ("ignore", Some(parts)) => {
+          let ignore = arguments.ignore.get_or_insert_with(Default::default);
+          for part in parts.split(',').filter(|part| !part.is_empty()) {
+            match part {
+              "build-id" => ignore.build_id = true,
+              "debug" => ignore.debug = true,
+              "comment" => ignore.comment = true,
+              _ => {
+                return Err(format!(
+                  "--ignore expects build-id, debug or comment, not `{}`",
+                  part
+                ))
+              }
+            }
+          }
+        }
+        ("sort", Some(order)) => {
+          listing.order = elf::SymbolOrder::from_name(order)
+            .ok_or_else(|| format!("--sort expects name, value or size, not `{}`", order))?;
+        }
+        ("only", Some(filters)) => {
+          for name in filters.split(',').filter(|name| !name.is_empty()) {
+            let filter = elf::SymbolFilter::from_name(name).ok_or_else(|| {
+              format!(
+                "--only expects {}, not `{}`",
+                elf::SymbolFilter::NAMES.join(", "),
+                name
+              )
+            })?;
+            listing.filters.push(filter);
+          }
+        }
+        ("reloc-stats", None) => reloc_stats = true,
+        ("validate", None) => validate = true,
+        ("layout", None) => layout = true,
+        ("go-buildinfo", None) => go_buildinfo = true,
+        ("source-files", None) => source_files = true,
+        ("coverage", None) => coverage = true,
+        ("cross-check", None) => cross_check = true,
+        ("modinfo", None) => modinfo = true,
+        ("vermagic", Some(expected)) => vermagic = Some(String::from(expected)),
+        ("verify-debuglink", Some(path)) => debug_file = Some(String::from(path)),
+        ("verify-debuglink", None) => {
+          let path = args.next().ok_or("--verify-debuglink expects the debug file")?;
+          debug_file = Some(path);
+        }
+        ("hex-dump", Some(section)) => hex_dump = Some(String::from(section)),
+        ("extract-section", Some(section)) => extract = Some(String::from(section)),
+        ("annotate-header", None) => annotate = Some(Annotation::Header),
+        ("annotate-section" | "annotate-segment", Some(number)) => {
+          let index = number
+            .parse()
+            .map_err(|_| format!("--{} expects a number", name))?;
+          annotate = Some(match name {
+            "annotate-section" => Annotation::Section(index),
+            _ => Annotation::Segment(index),
+          });
+        }
+        ("decompress", None) => decompress = true,
+        ("hash-sections", None) => hash_sections = Some(DigestAlgorithm::DEFAULT),
+        ("hash-sections", Some(name)) => {
+          let algorithm = DigestAlgorithm::from_name(name).ok_or_else(|| match name {
+            "sha1" | "sha256" => format!("--hash-sections={} requires the `sha` feature", name),
+            _ => format!("--hash-sections expects crc32, sha1 or sha256, not `{}`", name),
+          })?;
+          hash_sections = Some(algorithm);
+        }
+        ("hash-decompressed", None) => decompressed = true,
+        ("demangle", None) => demangle = true,
+        ("entropy", None) => entropy = true,
+        ("wide", None) => wide = true,
+        ("no-limit", None) => no_limit = true,
+        ("color", None | Some("always")) => color = Some(true),
+        ("color", Some("never")) => color = Some(false),
+        ("color", Some("auto")) => color = None,
+        ("color", Some(when)) => {
+          return Err(format!("--color expects auto, always or never, not `{}`", when))
+        }
+        ("strict", None) => arguments.options.strict = true,
+        ("follow-scripts", None) => arguments.follow = true,
+        ("recursive", None) => arguments.recursive = true,
+        ("max-depth", Some(depth)) => {
+          let depth = depth
+            .parse()
+            .map_err(|_| String::from("--max-depth expects a number"))?;
+          arguments.max_depth = Some(depth);
+        }
+        ("jobs", value) => {
+          let count = match value {
+            Some(count) => String::from(count),
+            None => args.next().ok_or("--jobs expects a number")?,
+          };
+          arguments.jobs = count
+            .parse()
+            .map_err(|_| String::from("--jobs expects a number"))?;
+        }
+        ("no-mmap", None) => arguments.mmap = false,
+        ("max-input-size", Some(size)) => {
+          arguments.limit = size
+            .parse()
+            .map_err(|_| String::from("--max-input-size expects a number of bytes"))?;
+        }
+        ("format", Some(format)) => {
+          arguments.format = match format {
+            "csv" => TableFormat::Csv,
+            "tsv" => TableFormat::Tsv,
+            "text" => TableFormat::Text,
+            _ => return Err(format!("--format expects csv, tsv or text, not `{}`", format)),
+          }
+        }
+        (name, None) if selector(name).is_some_and(|flag| selection.select(flag)) => {}
+        _ => return Err(format!("unknown option `{}`", arg)),
+      }
+    }
+
+    // Standard input is read when the path is missing from a pipe.
+    if arguments.paths.is_empty() {
+      match io::stdin().is_terminal() {
+        true => return Err(String::from("missing file")),
+        false => arguments.paths.push(String::from(STDIN)),
+      }
+    }
+
+    arguments.color = color.unwrap_or_else(|| {
+      let disabled = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+      io::stdout().is_terminal() && !disabled
+    });
+
+    // A single view is shown, the readelf selectors (and their options) add up.
+    let views = [
+      (checksec, "--checksec"),
+      (debug_sections, "--debug-sections"),
+      (reloc_stats, "--reloc-stats"),
+      (validate, "--validate"),
+      (layout, "--layout"),
+      (go_buildinfo, "--go-buildinfo"),
+      (source_files, "--source-files"),
+      (coverage, "--coverage"),
+      (cross_check, "--cross-check"),
+      (
+        modinfo || vermagic.is_some(),
+        if modinfo { "--modinfo" } else { "--vermagic" },
+      ),
+      (debug_file.is_some(), "--verify-debuglink"),
+      (hex_dump.is_some(), "--hex-dump"),
+      (annotate.is_some(), annotate.as_ref().map_or("", Annotation::flag)),
+      (extract.is_some(), "--extract-section"),
+      (hash_sections.is_some(), "--hash-sections"),
+      (
+        !selection.is_empty() || entropy || !listing.is_all(),
+        match () {
+          _ if !selection.is_empty() => "the selectors (-h, -S...)",
+          _ if entropy => "--entropy",
+          _ => "--sort/--only",
+        },
+      ),
+    ];
+    let mut requested = views
+      .iter()
+      .filter(|(requested, _)| *requested)
+      .map(|(_, flag)| flag);
+    if let (Some(first), Some(second)) = (requested.next(), requested.next()) {
+      return Err(format!("{} cannot be combined with {}", second, first));
+    }
+
+    arguments.view = match () {
+      _ if checksec => View::Checksec,
+      _ if debug_sections => View::DebugSections,
+      _ if reloc_stats => View::RelocStats,
+      _ if validate => View::Validate,
+      _ if layout => View::Layout,
+      _ if go_buildinfo => View::GoBuildInfo,
+      _ if source_files => View::SourceFiles,
+      _ if coverage => View::Coverage,
+      _ if cross_check => View::CrossCheck,
+      _ if modinfo || vermagic.is_some() => View::ModInfo(vermagic),
+      _ if debug_file.is_some() => View::VerifyDebuglink(debug_file.unwrap_or_default()),
+      _ if hex_dump.is_some() => View::HexDump(hex_dump.unwrap_or_default()),
+      _ if annotate.is_some() => View::Annotate(annotate.unwrap_or(Annotation::Header)),
+      _ if extract.is_some() => View::ExtractSection {
+        section: extract.unwrap_or_default(),
+        decompress,
+      },
+      _ if hash_sections.is_some() => View::HashSections {
+        algorithm: hash_sections.unwrap_or(DigestAlgorithm::DEFAULT),
+        decompressed,
+      },
+      _ if !selection.is_empty() || entropy || !listing.is_all() => {
+        // The entropy is a column of the section headers, --sort and --only
+        // apply to the symbol tables.
+        selection.section_headers |= entropy;
+        selection.symbols |= !listing.is_all();
+        View::Selected {
+          selection,
+          demangle,
+          entropy,
+          listing,
+        }
+      }
+      _ => View::Summary,
+    };
+
+    if arguments.jobs == 0 {
+      arguments.jobs = std::thread::available_parallelism().map_or(1, usize::from);
+    }
+    if arguments.jobs > 1 && matches!(arguments.view, View::ExtractSection { .. }) {
+      return Err(String::from("--extract-section cannot be used with --jobs"));
+    }
+
+    // Long names (C++ symbols) are truncated to keep the lines readable.
+    arguments.width = (!wide).then(|| terminal_width().unwrap_or(DEFAULT_WIDTH));
+    arguments.rows = row_limit(no_limit, io::stdout().is_terminal());
+
+    Ok(arguments)
+  }
+}
+
+///
+/// Returns the rows of a table: listing millions of symbols by accident would
+/// hold the terminal for minutes, the other outputs are complete.
+///
+fn row_limit(no_limit: bool, terminal: bool) -> Option<usize> {
+  (!no_limit && terminal).then_some(DEFAULT_ROWS)
+}
+
+/// Parses an offset or a size, decimal or hexadecimal (`0x` prefix).
+fn parse_number(string: &str) -> Option<usize> {
+  match string.strip_prefix("0x").or_else(|| string.strip_prefix("0X")) {
+    Some(digits) => usize::from_str_radix(digits, 16).ok(),
+    None => string.parse().ok(),
+  }
+}
+
+/// File of the command line or of a walk, with the error of the walk.
+type Walked = Result<PathBuf, (PathBuf, io::Error)>;
+
+/// Shows a file, or reports the error which kept the walk from reaching it.
+fn show_walked(file: &Walked, arguments: &Arguments) -> Status {
+  match file {
+    Ok(path) => show_path(path.clone(), arguments),
+    Err((path, error)) => {
+      report(path.display(), error, Style::Red);
+      Status::Io
+    }
+  }
+}
+
+/// Shows a file, or the first input of a linker script (following them, see [`analyze()`]).
+fn show_path(mut path: PathBuf, arguments: &Arguments) -> Status {
+  const LEVELS: usize = 8;
+
+  for _ in 0..LEVELS {
+    match analyze(&path, arguments) {
+      Ok(Shown::Script(input)) => {
+        println!("{}: linker script, following {}", path.display(), input.display());
+        path = input;
+      }
+      Ok(Shown::File(status)) => return status,
+      Err(error) => {
+        report(path.display(), &*error, Style::Red);
+        return Status::of(&*error);
+      }
+    }
+  }
+
+  let message = format!("{}: too many linker script levels ({})", path.display(), LEVELS);
+  eprintln!("{}", Style::Red.paint(message));
+  Status::Invalid
+}
+
+/// Prints the usage error, see [`Status::Usage`].
+fn usage_error(message: &str) -> ExitCode {
+  eprintln!("elfprobe: {}", message);
+  eprintln!("Try `elfprobe --help` for more information.");
+  Status::Usage.into()
+}
+
+///
+/// Exit status of the program, the worst of the files when several are
+/// given (the variants are sorted from the best to the worst).
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Status {
+  /// Every file was shown.
+  Success = 0,
+  /// Malformed tables (lenient parsing), header validation issues or a
+  /// `--vermagic` mismatch.
+  Warnings = 1,
+  /// Unknown option, missing file...
+  Usage = 2,
+  /// Not an ELF file (or archive), or parsing failed (`--strict`).
+  Invalid = 3,
+  /// The file could not be read (missing, permissions...).
+  Io = 4,
+}
+
+impl Status {
+  /// Returns the status of the error which stopped showing a file.
+  fn of(error: &(dyn std::error::Error + 'static)) -> Self {
+    match error.is::<io::Error>() {
+      true => Self::Io,
+      false => Self::Invalid,
+    }
+  }
+}
+
+impl From<Status> for ExitCode {
+  fn from(status: Status) -> Self {
+    ExitCode::from(status as u8)
+  }
+}
+
+/// What happened to a file once shown.
+#[derive(Debug)]
+enum Shown {
+  /// Shown, with warnings or not.
+  File(Status),
+  /// Linker script whose input is to be shown instead.
+  Script(PathBuf),
+}
+
+// ╔═╗┬┬  ┌─┐┌─┐
+// ╠╣ ││  ├┤ └─┐
+// ╚  ┴┴─┘└─┘└─┘
+
+///
+/// Shows an ELF file or the ELF members of an archive. When following the
+/// scripts, the first input of a linker script is returned instead of failing.
+///
+fn analyze(path: &Path, arguments: &Arguments) -> Result<Shown, Failure> {
+  use elfprobe_core::file::{read_to_end_limited, FileReader};
+
+  if path == Path::new(STDIN) {
+    let bytes = read_to_end_limited(io::stdin().lock(), arguments.limit)?;
+    return analyze_data(path, bytes.as_slice(), arguments);
+  }
+
+  // Reads on demand when the file can neither be mapped nor read at once.
+  match open(path, arguments) {
+    Ok(input) => {
+      let next = analyze_data(path, &input, arguments)?;
+      input.close()?;
+      Ok(next)
+    }
+    // Directories and such are rejected whatever the way of reading, and so
+    // are the streams larger than `--max-input-size`.
+    Err(error)
+      if matches!(
+        error.kind(),
+        io::ErrorKind::InvalidInput | io::ErrorKind::InvalidData
+      ) =>
+    {
+      Err(error.into())
+    }
+    Err(_) => {
+      let file = FileReader::try_from(path)?;
+      analyze_data(path, &file, arguments)
+    }
+  }
+}
+
+///
+/// Writes a patched copy of the file, see [`elf::ElfObject::set_interpreter()`],
+/// [`elf::ElfObject::set_stack_executable()`] and
+/// [`elf::ElfObject::remove_sections()`]. The copy keeps the permissions of
+/// the file (executable bit).
+///
+fn patch_file(path: &Path, patch: &Patch, output: &Path) -> Status {
+  use std::fs;
+
+  fn patched<'data, Reader, ElfType>(
+    object: &elf::ElfObject<'data, Reader, ElfType>,
+    patch: &Patch,
+  ) -> Result<Vec<u8>, elfprobe_core::error::ElfError>
+  where
+    Reader: reader::Reader<'data>,
+    ElfType: elf::ElfType,
+  {
+    match patch {
+      Patch::Interpreter(interpreter) => object.set_interpreter(interpreter.as_bytes()),
+      Patch::ExecStack(executable) => object.set_stack_executable(*executable),
+      Patch::RemoveSections(names) => {
+        object.remove_sections(&names.iter().map(String::as_str).collect::<Vec<_>>())
+      }
+    }
+  }
+
+  let patch = || -> Result<(), (&Path, Failure)> {
+    let bytes = fs::read(path).map_err(|error| (path, error.into()))?;
+    let elf = elf::parse_elf(bytes.as_slice()).map_err(|error| (path, error.into()))?;
+    let patched = match elf {
+      elf::ElfFile::Elf32Be(object) => patched(&object, patch),
+      elf::ElfFile::Elf64Be(object) => patched(&object, patch),
+      elf::ElfFile::Elf32Le(object) => patched(&object, patch),
+      elf::ElfFile::Elf64Le(object) => patched(&object, patch),
+    };
+    let patched = patched.map_err(|error| (path, error.into()))?;
+
+    let permissions = fs::metadata(path)
+      .map_err(|error| (path, error.into()))?
+      .permissions();
+    fs::write(output, patched).map_err(|error| (output, error.into()))?;
+    fs::set_permissions(output, permissions).map_err(|error| (output, error.into()))
+  };
+
+  match patch() {
+    Ok(()) => Status::Success,
+    Err((path, error)) => {
+      report(path.display(), &*error, Style::Red);
+      Status::of(&*error)
+    }
+  }
+}
+
+///
+/// Writes the ELF image found at `--offset` to a file of its own, up to the
+/// end of its last region (see [`elf::ElfObject::image_size()`]) unless
+/// `--length` is given.
+///
+fn extract_image(path: &Path, arguments: &Arguments, output: &Path) -> Status {
+  use elfprobe_core::reader::{Reader, SubRange};
+  use std::fs;
+
+  let extract = || -> Result<(), (&Path, Failure)> {
+    let input = open(path, arguments).map_err(|error| (path, error.into()))?;
+    let data = SubRange::new(input.as_ref(), arguments.offset, arguments.length)
+      .ok_or_else(|| (path, past_the_end(arguments.offset).into()))?;
+    let size = match arguments.length {
+      Some(_) => data.length() as u64,
+      None => match elf::parse_elf_with(data, arguments.options).map_err(|error| (path, error.into()))? {
+        elf::ElfFile::Elf32Be(object) => object.image_size(),
+        elf::ElfFile::Elf64Be(object) => object.image_size(),
+        elf::ElfFile::Elf32Le(object) => object.image_size(),
+        elf::ElfFile::Elf64Le(object) => object.image_size(),
+      },
+    };
+
+    // A truncated image is an error rather than a shorter output.
+    let bytes = usize::try_from(size)
+      .ok()
+      .and_then(|size| data.read_bytes(size, 0))
+      .ok_or_else(|| (path, truncated_image(size, data.length()).into()))?;
+    fs::write(output, bytes).map_err(|error| (output, error.into()))
+  };
+
+  match extract() {
+    Ok(()) => Status::Success,
+    Err((path, error)) => {
+      report(path.display(), &*error, Style::Red);
+      Status::of(&*error)
+    }
+  }
+}
+
+/// Error of an `--offset` past the end of the file.
+fn past_the_end(offset: usize) -> String {
+  format!("--offset {:#x} is past the end of the file", offset)
+}
+
+/// Error of an image larger than the data left after `--offset`.
+fn truncated_image(declared: u64, available: usize) -> String {
+  format!(
+    "truncated image: declared {} bytes, {} available",
+    declared, available
+  )
+}
+
+/// Prints the differences between two files, see [`elf::ElfDiff`].
+fn show_diff(old: &Path, new: &Path, arguments: &Arguments) -> Status {
+  // Failures are reported with the path of the file at fault.
+  fn failed<Error: Into<Failure>>(path: &Path) -> impl FnOnce(Error) -> (PathBuf, Failure) + '_ {
+    move |error| (path.to_path_buf(), error.into())
+  }
+
+  let compare = || -> Result<Status, (PathBuf, Failure)> {
+    let old_file = open(old, arguments).map_err(failed(old))?;
+    let new_file = open(new, arguments).map_err(failed(new))?;
+    let old_view = elf::parse_elf_view(old_file.as_ref()).map_err(failed(old))?;
+    let new_view = elf::parse_elf_view(new_file.as_ref()).map_err(failed(new))?;
+
+    let diff = match arguments.ignore {
+      Some(ignored) => elf::ElfDiff::with_ignored(old_view.as_ref(), new_view.as_ref(), ignored),
+      None => elf::ElfDiff::new(old_view.as_ref(), new_view.as_ref()),
+    };
+    if diff.is_empty() {
+      match arguments.ignore {
+        Some(_) => println!("Identical modulo ignored regions"),
+        None => println!("No structural differences"),
+      }
+      return Ok(Status::Success);
+    }
+
+    println!("--- {}", old.display());
+    println!("+++ {}", new.display());
+    print!("{}", diff);
+    Ok(Status::Warnings)
+  };
+
+  compare().unwrap_or_else(|(path, error)| {
+    report(path.display(), &*error, Style::Red);
+    Status::of(&*error)
+  })
+}
+
+///
+/// Maps the file, or copies it with `--no-mmap`. The files which are read
+/// (devices, FIFOs, virtual files...) are bounded by `--max-input-size`.
+///
+fn open(path: &Path, arguments: &Arguments) -> io::Result<elfprobe_core::file::InputData> {
+  use elfprobe_core::file::InputData;
+
+  match arguments.mmap {
+    true => InputData::open(path, arguments.limit),
+    false => InputData::read(File::open(path)?, arguments.limit),
+  }
+}
+
+/// Same as [`analyze()`] once the file is opened, from `--offset` on.
+fn analyze_data<'data, Reader>(path: &Path, data: Reader, arguments: &Arguments) -> Result<Shown, Failure>
+where
+  Reader: reader::Reader<'data> + std::fmt::Debug,
+{
+  use elfprobe_core::reader::SubRange;
+
+  match (arguments.offset, arguments.length) {
+    (0, None) => analyze_range(path, data, arguments),
+    (offset, length) => match SubRange::new(data, offset, length) {
+      Some(range) => analyze_range(path, range, arguments),
+      None => Err(past_the_end(offset).into()),
+    },
+  }
+}
+
+/// Same as [`analyze_data()`] once the range is read.
+fn analyze_range<'data, Reader>(path: &Path, data: Reader, arguments: &Arguments) -> Result<Shown, Failure>
+where
+  Reader: reader::Reader<'data> + std::fmt::Debug,
+{
+  use elfprobe_core::archive::{Archive, ARMAG};
+  use elfprobe_core::elf::parse_elf_with;
+  use elfprobe_core::error::ElfError;
+
+  // println!("{:#04X?}", data.read_bytes(4, 0));
+
+  let (view, options) = (&arguments.view, arguments.options);
+  if !data.read_bytes(ARMAG.len(), 0).is_some_and(Archive::is_archive) {
+    return match parse_elf_with(data, options) {
+      Ok(elf) => Ok(Shown::File(show_file(&elf, view).max(warn(path, &elf)))),
+      Err(ElfError::LinkerScript { input: Some(input) }) if arguments.follow => {
+        // Relative inputs are resolved against the directory of the script.
+        Ok(Shown::Script(path.parent().unwrap_or(Path::new("")).join(input)))
+      }
+      Err(error) => Err(error.into()),
+    };
+  }
+
+  // Archives are read as a whole.
+  options.limits.check_bytes(data.length() as u64)?;
+  let bytes = data.read_bytes(data.length(), 0).unwrap_or_default();
+  let archive = Archive::parse(bytes)?;
+  let options = options.nested()?;
+  let directory = path.parent().unwrap_or(Path::new(""));
+  let mut status = Status::Success;
+  for member in archive.members() {
+    let member = member?;
+
+    // Members of thin archives are files next to the archive.
+    let file;
+    let data = match archive.is_thin() {
+      false => member.data,
+      true => match open(&member.path(directory), arguments) {
+        Ok(input) => {
+          file = input;
+          file.as_ref()
+        }
+        Err(error) => {
+          println!("Skipping missing member {}: {}", member.name(), error);
+          status = status.max(Status::Warnings);
+          continue;
+        }
+      },
+    };
+
+    match parse_elf_with(data, options) {
+      Ok(elf) => {
+        println!("File: {}({})", path.display(), member.name());
+        status = status.max(show_file(&elf, view)).max(warn(path, &elf));
+        println!();
+      }
+      // Symbol index (__.SYMDEF), text files...
+      Err(error) => println!("Skipping non-ELF member {}: {}", member.name(), error),
+    }
+  }
+
+  Ok(Shown::File(status))
+}
+
+/// Prints the error with its causes, one per line, the first one styled.
+fn report(prefix: impl std::fmt::Display, error: &(dyn std::error::Error + 'static), style: Style) {
+  let mut chain = elfprobe_core::error::chain(error);
+  let message = format!("{}: {}", prefix, chain.next().unwrap_or(error));
+  eprintln!("{}", style.paint(message));
+  for cause in chain {
+    eprintln!("  caused by: {}", cause);
+  }
+}
+
+/// Error which stops showing a file, reported once the file is given up.
+type Failure = Box<dyn std::error::Error>;
+
+/// Prints the warnings recorded while showing the file (lenient mode).
+fn warn<'data, Reader: reader::Reader<'data>>(path: &Path, elf: &elf::ElfFile<'data, Reader>) -> Status {
+  let warnings = elf.diagnostics().take();
+  for warning in &warnings {
+    report(
+      format_args!("{}: warning", path.display()),
+      warning,
+      Style::Yellow,
+    );
+  }
+
+  match warnings.is_empty() {
+    true => Status::Success,
+    false => Status::Warnings,
+  }
+}
+
+/// Shows the object, with warnings when its header has validation issues.
+fn show_file<'data, Reader>(elf: &elf::ElfFile<'data, Reader>, view: &View) -> Status
+where
+  Reader: reader::Reader<'data>,
+{
+  use elf::ElfFile;
+
+  match elf {
+    ElfFile::Elf32Be(object) => show(object, view),
+    ElfFile::Elf64Be(object) => show(object, view),
+    ElfFile::Elf32Le(object) => show(object, view),
+    ElfFile::Elf64Le(object) => show(object, view),
+  }
+}
+
+/// Path standing for the standard input.
+const STDIN: &str = "-";
+
+/// Size limit of the inputs read rather than mapped (`--max-input-size=<bytes>`).
+const DEFAULT_INPUT_SIZE: usize = elfprobe_core::file::READ_LIMIT;
+
+/// Width of the lines when the output is not a terminal, unless `--wide`.
+const DEFAULT_WIDTH: usize = 100;
+
+/// Rows of a table written to a terminal, unless `--no-limit`.
+const DEFAULT_ROWS: usize = 10_000;
+
+/// What to display, selected by the command line flags.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum View {
+  Summary,
+  Checksec,
+  DebugSections,
+  RelocStats,
+  Validate,
+  Layout,
+  GoBuildInfo,
+  SourceFiles,
+  Coverage,
+  CrossCheck,
+  /// The `vermagic` expected with `--vermagic`.
+  ModInfo(Option<String>),
+  /// Separate debug file to check against `.gnu_debuglink`.
+  VerifyDebuglink(String),
+  /// Section name or number, or `overlay`.
+  HexDump(String),
+  Annotate(Annotation),
+  /// Section name or number, written to the standard output.
+  ExtractSection {
+    section: String,
+    decompress: bool,
+  },
+  HashSections {
+    algorithm: DigestAlgorithm,
+    decompressed: bool,
+  },
+  Selected {
+    selection: Selection,
+    demangle: bool,
+    entropy: bool,
+    /// Symbols listed and their order (`--only`, `--sort`).
+    listing: elf::SymbolSelection,
+  },
+}
+
+/// Header annotated with `--annotate-header`, `--annotate-section` or `--annotate-segment`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Annotation {
+  Header,
+  Section(usize),
+  Segment(usize),
+}
+
+impl Annotation {
+  /// Returns the flag of the annotation, for the usage errors.
+  fn flag(&self) -> &'static str {
+    match self {
+      Self::Header => "--annotate-header",
+      Self::Section(_) => "--annotate-section",
+      Self::Segment(_) => "--annotate-segment",
+    }
+  }
+}
+
+fn show<'data, Reader, ElfType>(object: &elf::ElfObject<'data, Reader, ElfType>, view: &View) -> Status
+where
+  Reader: reader::Reader<'data>,
+  ElfType: elf::ElfType,
+{
+  // A failed check fails as the validation issues (packaging pipelines).
+  let checked = show_view(object, view);
+  match object.validate().is_empty() && checked {
+    true => Status::Success,
+    false => Status::Warnings,
+  }
+}
+
+///
+/// Shows the view, `false` when its check fails (`--vermagic`,
+/// `--verify-debuglink`, `--cross-check`) or the section cannot be extracted.
+///
+fn show_view<'data, Reader, ElfType>(object: &elf::ElfObject<'data, Reader, ElfType>, view: &View) -> bool
+where
+  Reader: reader::Reader<'data>,
+  ElfType: elf::ElfType,
+{
+  match *view {
+    View::ModInfo(ref vermagic) => return modinfo(object, vermagic.as_deref()),
+    View::CrossCheck => return cross_check(object),
+    View::VerifyDebuglink(ref path) => return verify_debuglink(object, Path::new(path)),
+    View::ExtractSection {
+      ref section,
+      decompress,
+    } => return extract_section(object, section, decompress),
+    View::Summary => summary(object),
+    View::Checksec => print!("{}", object.security_report()),
+    View::DebugSections => debug_sections(object),
+    View::RelocStats => print!("{}", object.relocation_stats()),
+    View::Validate => validate(object),
+    View::Layout => print!("{}", object.layout()),
+    View::GoBuildInfo => match object.go_buildinfo() {
+      Some(info) => print!("{}", info),
+      None => println!("No Go build information in this file."),
+    },
+    View::SourceFiles => match object.source_files() {
+      Some(files) => print!("{}", files),
+      None => println!("No symbol table (.symtab) in this file, it is stripped."),
+    },
+    View::Coverage => match object.symbol_coverage() {
+      Some(coverage) => print!("{}", coverage),
+      None => println!("No executable section in this file."),
+    },
+    View::HexDump(ref section) => print!("{}", hex_dump(object, section)),
+    View::Annotate(Annotation::Header) => print!("{}", object.annotate_header()),
+    View::Annotate(Annotation::Section(index)) => match object.annotate_section(index) {
+      Some(annotated) => print!("{}", annotated),
+      None => println!("There is no section {} in this file.", index),
+    },
+    View::Annotate(Annotation::Segment(index)) => match object.annotate_segment(index) {
+      Some(annotated) => print!("{}", annotated),
+      None => println!("There is no program header {} in this file.", index),
+    },
+    View::HashSections {
+      algorithm,
+      decompressed,
+    } => print!("{}", object.digests(algorithm, decompressed)),
+    View::Selected {
+      selection,
+      demangle,
+      entropy,
+      ref listing,
+    } => {
+      // Same order as readelf, each part followed by a blank line.
+      let missing = |what| Some(format!("There are no {} in this file.\n", what));
+      let parts = [
+        selection.file_header.then(|| Some(file_header(object))),
+        selection
+          .section_headers
+          .then(|| section_headers(object, entropy).or_else(|| missing("sections"))),
+        selection
+          .program_headers
+          .then(|| program_headers(object).or_else(|| missing("program headers"))),
+        selection
+          .dynamic
+          .then(|| dynamic(object).or_else(|| missing("dynamic entries"))),
+        selection
+          .symbols
+          .then(|| symbols(object, listing, demangle).or_else(|| missing("symbol tables"))),
+        selection
+          .notes
+          .then(|| notes(object).or_else(|| missing("notes"))),
+      ];
+
+      parts
+        .into_iter()
+        .flatten()
+        .flatten()
+        .for_each(|part| println!("{}", part));
+    }
+  }
+
+  true
+}
+
+///
+/// Bytes of a section (`--hex-dump=<name|number>`), or of the overlay with
+/// `--hex-dump=overlay` (unless a section has this name), as readelf's `-x`.
+///
+fn hex_dump<'data, Reader, ElfType>(
+  object: &elf::ElfObject<'data, Reader, ElfType>,
+  name_or_number: &str,
+) -> String
+where
+  Reader: reader::Reader<'data>,
+  ElfType: elf::ElfType,
+{
+  use elfprobe_core::utils::HexDump;
+
+  let section = match name_or_number.parse::<usize>() {
+    Ok(index) => object.sections().nth(index),
+    Err(_) => object.section_by_name(name_or_number),
+  };
+
+  match (section, object.overlay()) {
+    (Some(section), _) => match object.section_bytes(section.header) {
+      Ok([]) | Err(_) => format!("Section '{}' has no data to dump.\n", name_or_number),
+      Ok(bytes) => {
+        let (offset, name) = (section.header.sh_offset(), section.display_name());
+        let name = name.as_deref().unwrap_or(name_or_number);
+        format!("Hex dump of section '{}':\n{:#}", name, HexDump(bytes, offset))
+      }
+    },
+    (None, Some((offset, bytes))) if name_or_number == "overlay" => {
+      format!("Hex dump of the overlay:\n{:#}", HexDump(bytes, offset))
+    }
+    (None, None) if name_or_number == "overlay" => String::from("There is no overlay in this file.\n"),
+    (None, _) => format!(
+      "Section '{}' was not dumped because it does not exist.\n",
+      name_or_number
+    ),
+  }
+}
+
+///
+/// Writes the bytes of a section to the standard output (not a terminal),
+/// decompressed with `--decompress`: `SHF_COMPRESSED` sections, and the ELF
+/// file of `.gnu_debugdata` with the `xz` feature.
+///
+fn extract_section<'data, Reader, ElfType>(
+  object: &elf::ElfObject<'data, Reader, ElfType>,
+  name_or_number: &str,
+  decompress: bool,
+) -> bool
+where
+  Reader: reader::Reader<'data>,
+  ElfType: elf::ElfType,
+{
+  use std::borrow::Cow;
+  use std::io::Write;
+
+  let section = match name_or_number.parse::<usize>() {
+    Ok(index) => object.sections().nth(index),
+    Err(_) => object.section_by_name(name_or_number),
+  };
+  let Some(section) = section else {
+    eprintln!(
+      "Section '{}' was not extracted because it does not exist.",
+      name_or_number
+    );
+    return false;
+  };
+
+  let data = match section.name_bytes() {
+    #[cfg(feature = "xz")]
+    Some(b".gnu_debugdata") if decompress => object.debugdata_elf().map(Cow::Owned),
+    #[cfg(not(feature = "xz"))]
+    Some(b".gnu_debugdata") if decompress => {
+      eprintln!("Decompressing .gnu_debugdata requires the `xz` feature.");
+      return false;
+    }
+    _ if decompress => object.section_data(section.header),
+    _ => object.section_bytes(section.header).map(Cow::Borrowed),
+  };
+
+  let mut stdout = io::stdout();
+  match data {
+    Ok(_) if stdout.is_terminal() => {
+      eprintln!("Not writing the section to a terminal, redirect the output to a file.");
+      false
+    }
+    Ok(data) => stdout.write_all(&data).is_ok(),
+    Err(error) => {
+      report(name_or_number, &error, Style::Red);
+      false
+    }
+  }
+}
+
+/// Validation issues of the header, the sections and the segments.
+fn validate<'data, Reader, ElfType>(object: &elf::ElfObject<'data, Reader, ElfType>)
+where
+  Reader: reader::Reader<'data>,
+  ElfType: elf::ElfType,
+{
+  let issues = object.validate();
+  if issues.is_empty() {
+    println!("No validation issues");
+  }
+  for issue in issues {
+    let style = match issue.severity {
+      elf::Severity::Error => Style::Red,
+      elf::Severity::Warning => Style::Yellow,
+    };
+    println!("{}", style.paint(issue));
+  }
+}
+
+/// Sections which the program headers contradict (`--cross-check`), `false` when there is one.
+fn cross_check<'data, Reader, ElfType>(object: &elf::ElfObject<'data, Reader, ElfType>) -> bool
+where
+  Reader: reader::Reader<'data>,
+  ElfType: elf::ElfType,
+{
+  match object.cross_check() {
+    Some(check) => {
+      print!("{}", check);
+      check.divergences.is_empty()
+    }
+    None => {
+      println!("No section headers or no PT_LOAD segment in this file, nothing to compare.");
+      true
+    }
+  }
+}
+
+/// Entries of `.modinfo` (`--modinfo`), checked against `--vermagic`.
+fn modinfo<'data, Reader, ElfType>(
+  object: &elf::ElfObject<'data, Reader, ElfType>,
+  vermagic: Option<&str>,
+) -> bool
+where
+  Reader: reader::Reader<'data>,
+  ElfType: elf::ElfType,
+{
+  let Some(modinfo) = object.modinfo() else {
+    println!("There is no .modinfo section in this file.");
+    return vermagic.is_none();
+  };
+
+  if !object.is_kernel_module() {
+    println!(
+      "{}",
+      Style::Yellow.paint("Not a kernel module (no .gnu.linkonce.this_module section)")
+    );
+    println!();
+  }
+  print!("{}", modinfo);
+
+  if let Some(expected) = vermagic {
+    println!();
+    match (modinfo.first("vermagic"), modinfo.vermagic_matches(expected)) {
+      (_, true) => println!("vermagic matches `{}`", expected.trim()),
+      (Some(actual), false) => println!(
+        "{}",
+        Style::Red.paint(format!(
+          "vermagic `{}`, expected `{}`",
+          actual.trim(),
+          expected.trim()
+        ))
+      ),
+      (None, false) => println!("{}", Style::Red.paint("No vermagic entry")),
+    }
+  }
+
+  vermagic.is_none_or(|expected| modinfo.vermagic_matches(expected))
+}
+
+///
+/// Name and CRC of the separate debug file (`.gnu_debuglink`), compared with
+/// the CRC of the given file (`--verify-debuglink=<path>`).
+///
+fn verify_debuglink<'data, Reader, ElfType>(
+  object: &elf::ElfObject<'data, Reader, ElfType>,
+  path: &Path,
+) -> bool
+where
+  Reader: reader::Reader<'data>,
+  ElfType: elf::ElfType,
+{
+  use elfprobe_core::utils::crc32;
+
+  let Some((name, expected)) = object.debuglink() else {
+    println!("There is no .gnu_debuglink section in this file.");
+    return false;
+  };
+  println!("Debug link: {} (CRC {:#010x})", name, expected);
+
+  // Same file name expected, the directories are the debugger's business.
+  if path
+    .file_name()
+    .is_some_and(|file_name| file_name != name.as_ref())
+  {
+    let warning = format!("warning: {} is not named {}", path.display(), name);
+    println!("{}", Style::Yellow.paint(warning));
+  }
+
+  match std::fs::read(path) {
+    Ok(bytes) if crc32(&bytes) == expected => {
+      println!("{}: CRC matches", path.display());
+      true
+    }
+    Ok(bytes) => {
+      let mismatch = format!(
+        "{}: CRC {:#010x}, expected {:#010x}",
+        path.display(),
+        crc32(&bytes),
+        expected
+      );
+      println!("{}", Style::Red.paint(mismatch));
+      false
+    }
+    Err(error) => {
+      println!("{}", Style::Red.paint(format!("{}: {}", path.display(), error)));
+      false
+    }
+  }
+}
+
+/// Inventory of the DWARF sections, followed by the totals per category.
+fn debug_sections<'data, Reader, ElfType>(object: &elf::ElfObject<'data, Reader, ElfType>)
+where
+  Reader: reader::Reader<'data>,
+  ElfType: elf::ElfType,
+{
+  use elfprobe_core::utils::display_table;
+
+  let sections = object.debug_sections();
+  let mut output = String::new();
+  let _ = display_table!(
+    &mut output,
+    ["Name", "Size", "Compression", "Uncompressed"],
+    section in &sections => [
+      section.name,
+      section.size,
+      section.compression,
+      section.uncompressed_size,
+    ]
+  );
+
+  println!("Debug sections:");
+  print!("{}", output);
+
+  let totals = elf::debug_totals(&sections);
+  let size: u64 = totals.iter().map(|total| total.size).sum();
+  let uncompressed_size: u64 = totals.iter().map(|total| total.uncompressed_size).sum();
+
+  let mut output = String::new();
+  let _ = display_table!(
+    &mut output,
+    ["Category", "Size", "Uncompressed"],
+    total in totals.iter().map(|total| (total.category, total.size, total.uncompressed_size))
+      .chain([("(total)", size, uncompressed_size)]) => [total.0, total.1, total.2]
+  );
+
+  println!();
+  println!("Totals:");
+  print!("{}", output);
+}
+
+fn summary<'data, Reader, ElfType>(object: &elf::ElfObject<'data, Reader, ElfType>)
+where
+  Reader: reader::Reader<'data>,
+  ElfType: elf::ElfType,
+{
+  use elfprobe_core::utils::{display_row, display_table, Size};
+
+  // One-line summary, as file(1) (no linkage for relocatable files).
+  let mut summary = Vec::new();
+  if !object.program_headers().unwrap_or_default().is_empty() {
+    summary.push(match (object.linkage(), object.interpreter()) {
+      (elf::Linkage::Dynamic, Some(interpreter)) => {
+        format!(
+          "dynamically linked, interpreter {}",
+          elf::escape_controls(interpreter)
+        )
+      }
+      (linkage, _) => linkage.to_string(),
+    });
+  }
+
+  summary.push(object.is_stripped().to_string());
+
+  // Outside every section or not executable, typical of packed binaries.
+  if let Some(entry) = object.entry_point() {
+    summary.push(match entry.is_suspicious() {
+      true => Style::Yellow
+        .paint(format!("entry point {} [suspicious]", entry))
+        .to_string(),
+      false => format!("entry point {}", entry),
+    });
+  }
+
+  // Appended after the last section or segment (installers, archives).
+  if let Some((offset, bytes)) = object.overlay() {
+    summary.push(format!("overlay: {} at {:#x}", Size(bytes.len() as u64), offset));
+  }
+
+  // The version from `.comment`, else the sections or symbols of rustc.
+  match object.rust_version() {
+    Some(version) => summary.push(format!("Rust (rustc {})", version)),
+    None if object.is_rust() => summary.push(String::from("Rust")),
+    None => {}
+  }
+
+  // The kernel release of the vermagic (`6.1.0-18-amd64 SMP preempt ...`).
+  if object.is_kernel_module() {
+    let modinfo = object.modinfo().unwrap_or_default();
+    let release = modinfo
+      .first("vermagic")
+      .and_then(|vermagic| vermagic.split_whitespace().next());
+    summary.push(match release {
+      Some(release) => format!("Linux kernel module for {}", release),
+      None => String::from("Linux kernel module"),
+    });
+  }
+
+  // Symbols for the backtraces of stripped binaries (Fedora, RHEL).
+  if let Some(bytes) = object.debugdata() {
+    summary.push(format!("MiniDebugInfo: {} xz", Size(bytes.len() as u64)));
+  }
+
+  // Only looked for in `.go.buildinfo`.
+  if let Some(info) = object.go_buildinfo() {
+    let module = info
+      .main
+      .map(|main| format!(", module {} {}", main.path, main.version));
+    summary.push(format!("Go {}{}", info.version, module.unwrap_or_default()));
+  }
+
+  println!("Summary: {}", summary.join(", "));
+  println!();
+
+  println!("{}", file_header(object));
+
+  if let Some(abi) = object.mips_abi() {
+    println!("MIPS ABI: {}", abi);
+    println!();
+  }
+
+  if let Some(isa) = object.riscv_isa() {
+    println!("RISC-V ISA: {}", isa.as_str());
+    println!();
+  }
+
+  if let Some(sections) = section_headers(object, false) {
+    println!("{}", sections);
+  }
+
+  let segments = object.program_headers().unwrap_or_default();
+  if let Some(segments) = program_headers(object) {
+    println!("{}", segments);
+  }
+
+  if let Some(interpreter) = object.interpreter_bytes() {
+    println!(
+      "[Requesting program interpreter: {}]",
+      elf::printable(interpreter)
+    );
+  }
+
+  let needed = object.needed_libraries();
+  let (soname, rpath, runpath) = (object.soname(), object.rpath(), object.runpath());
+
+  if !needed.is_empty() || soname.is_some() || rpath.is_some() || runpath.is_some() {
+    println!();
+    println!("Dynamic dependencies:");
+    needed
+      .iter()
+      .for_each(|name| println!("  NEEDED   {}", elf::escape_controls(name)));
+    soname
+      .iter()
+      .for_each(|name| println!("  SONAME   {}", elf::escape_controls(name)));
+    // RPATH and RUNPATH differ: RPATH is searched before LD_LIBRARY_PATH.
+    rpath
+      .iter()
+      .for_each(|path| println!("  RPATH    {}", elf::escape_controls(path)));
+    runpath
+      .iter()
+      .for_each(|path| println!("  RUNPATH  {}", elf::escape_controls(path)));
+  }
+
+  if let Some(dynamic) = dynamic(object) {
+    println!();
+    print!("{}", dynamic);
+  }
+
+  // Relocatable objects have no program headers, hence no security summary.
+  if !segments.is_empty() {
+    println!();
+    println!("Security:");
+    let report = object.security_report().to_string();
+    report.lines().for_each(|line| println!("  {}", line));
+  }
+
+  let relocations = object.plt_relocations();
+  if !relocations.is_empty() {
+    let machine: u64 = object.header().e_machine.into();
+    let mut output = String::new();
+    let _ = display_table!(
+      &mut output,
+      ["  GOT slot", "Type", "Symbol"],
+      relocation in &relocations => [
+        format!("  {:#018x}", relocation.got),
+        elf::relocation_name(machine as u16, relocation.r_type).to_string(),
+        match (&relocation.symbol, relocation.addend) {
+          (Some(symbol), _) => symbol.to_string(),
+          (None, Some(addend)) => format!("{:#x}", addend),
+          (None, None) => String::new(),
+        },
+      ]
+    );
+
+    println!();
+    println!("PLT relocations:");
+    print!("{}", output);
+  }
+
+  let arrays = object.init_arrays();
+  let entries = object.init_entries();
+  if !arrays.is_empty() || !entries.is_empty() {
+    let symbol = |address| match object.nearest_symbol(address) {
+      Some((name, 0)) => name.into_owned(),
+      Some((name, offset)) => format!("{}+{:#x}", name, offset),
+      None => String::new(),
+    };
+
+    println!();
+    println!("Constructors and destructors:");
+    for array in &arrays {
+      println!("  {} ({} entries):", array.name, array.entries.len());
+      for &address in &array.entries {
+        println!(
+          "{}",
+          format!("    {:#018x}  {}", address, symbol(address)).trim_end()
+        );
+      }
+
+      if array.trailing != 0 {
+        println!("    warning: {} trailing bytes ignored", array.trailing);
+      }
+    }
+
+    for entry in &entries {
+      let line = match entry.is_address() {
+        true => format!(
+          "  {:<18}  {:#018x}  {}",
+          entry.name,
+          entry.value,
+          symbol(entry.value)
+        ),
+        false => format!("  {:<18}  {} (bytes)", entry.name, entry.value),
+      };
+
+      println!("{}", line.trim_end());
+    }
+  }
+
+  if let Some(tls) = object.tls_layout() {
+    println!();
+    println!("Thread-local storage:");
+    if let Some(segment) = tls.segment {
+      println!(
+        "  PT_TLS  vaddr {:#x}  filesz {:#x}  memsz {:#x} (zero-filled {:#x})  align {:#x}",
+        segment.vaddr,
+        segment.filesz,
+        segment.memsz,
+        segment.bss_size(),
+        segment.align
+      );
+    }
+
+    let mut output = String::new();
+    let _ = display_table!(
+      &mut output,
+      ["  Section", "Address", "Offset", "Size", "Contents"],
+      section in &tls.sections => [
+        format!("  {}", section.name),
+        format!("{:#018x}", section.address),
+        format!("{:#x}", section.offset),
+        format!("{:#x}", section.size),
+        match section.nobits {
+          true => "zero-filled (memory only)",
+          false => "initialization image",
+        },
+      ]
+    );
+    print!("{}", output);
+
+    if !tls.symbols.is_empty() {
+      let mut table = TableBuilder::new();
+      table.shrink(&[0]);
+      display_row!(table, ["  Symbol", "Offset", "Size", "Section"]);
+      for symbol in &tls.symbols {
+        display_row!(
+          table,
+          [
+            format!("  {}", symbol.name),
+            format!("{:#x}", symbol.offset),
+            format!("{:#x}", symbol.size),
+            symbol.section.as_deref().unwrap_or_default(),
+          ]
+        );
+      }
+
+      let mut output = String::new();
+      let _ = table.finish(&mut output);
+      print!("{}", output);
+    }
+  }
+
+  if let Some(notes) = notes(object) {
+    println!();
+    print!("{}", notes);
+  }
+
+  let properties = object.gnu_properties();
+  if !properties.is_empty() {
+    println!();
+    println!("Program properties:");
+    properties.iter().for_each(|property| println!("  {}", property));
+  }
+
+  if let Some(header) = object.eh_frame_hdr() {
+    println!();
+    println!("Unwind table (.eh_frame_hdr at {:#x}):", header.address);
+    if let Some(eh_frame) = header.eh_frame_ptr {
+      println!("  .eh_frame at {:#x}", eh_frame);
+    }
+    match header.fde_count() {
+      Some(count) => println!("  {} FDEs in the search table", count),
+      None => println!("  No search table"),
+    }
+  }
+
+  let comments = object.comments();
+  if !comments.is_empty() {
+    println!();
+    println!("Compiler comments:");
+    comments.iter().for_each(|comment| println!("  {}", comment));
+  }
+
+  let attributes = object.attributes();
+  if !attributes.is_empty() {
+    println!();
+    println!("Attributes:");
+    attributes.iter().for_each(|subsection| print!("{}", subsection));
+  }
+
+  let e_type: u64 = object.header().e_type.into();
+  if e_type == elf::ET_CORE as u64 {
+    let notes = object.core_notes().unwrap_or_default();
+    if !notes.is_empty() {
+      println!();
+      println!("Core notes:");
+      notes.iter().for_each(|note| print!("{}", note));
+    }
+  }
+}
+
+// ╔═╗┌─┐┬─┐┌┬┐┌─┐
+// ╠═╝├─┤├┬┘ │ └─┐
+// ╩  ┴ ┴┴└─ ┴ └─┘
+
+// Parts selected by the readelf flags, `None` when missing from the file.
+
+/// ELF header with the validation issues (`-h`).
+fn file_header<'data, Reader, ElfType>(object: &elf::ElfObject<'data, Reader, ElfType>) -> String
+where
+  Reader: reader::Reader<'data>,
+  ElfType: elf::ElfType,
+{
+  let mut output = format!("ELF Header:\n{}", object.display_header());
+  for issue in object.validate_header() {
+    let _ = writeln!(output, "  {}", issue);
+  }
+  output
+}
+
+/// Section headers (`-S`), with the entropy of their contents (`--entropy`).
+fn section_headers<'data, Reader, ElfType>(
+  object: &elf::ElfObject<'data, Reader, ElfType>,
+  entropy: bool,
+) -> Option<String>
+where
+  Reader: reader::Reader<'data>,
+  ElfType: elf::ElfType,
+{
+  use elfprobe_core::utils::{display_row, Alignment};
+  use Alignment::{Left, Right};
+
+  let sections = object.section_headers().unwrap_or_default();
+  if sections.is_empty() {
+    return None;
+  }
+
+  let mut table = TableBuilder::new();
+  table.shrink(&[1]);
+  table.align(&[Left, Left, Left, Right, Right, Right, Right, Left, Right, Left]);
+  // Trailing empty cells are trimmed, without --entropy.
+  let column = if entropy { "Entropy" } else { "" };
+  display_row!(
+    table,
+    ["[Nr]", "Name", "Type", "Address", "Offset", "Size", "EntSize", "Flags", "Align", column]
+  );
+  for (index, section) in sections.iter().enumerate() {
+    let entropy = match object.section_entropy(section).filter(|_| entropy) {
+      Some(entropy) if entropy.is_high() => Style::Yellow.paint(format!("{} [high]", entropy)).to_string(),
+      Some(entropy) => entropy.to_string(),
+      None => String::new(),
+    };
+    display_row!(
+      table,
+      [
+        format!("[{:2}]", index),
+        Style::Cyan.paint(object.section_name(section).unwrap_or_default()),
+        section.type_name(object.osabi()),
+        ElfType::address(section.sh_addr()),
+        format!("{:#x}", section.sh_offset()),
+        format!("{:#x}", section.sh_size()),
+        format!("{:#x}", section.sh_entsize()),
+        elf::sh_flags::from(section.sh_flags()).short(),
+        format!("{:#x}", section.sh_addralign()),
+        entropy,
+      ]
+    );
+  }
+
+  let mut output = String::from("Section Headers:\n");
+  let _ = table.finish(&mut output);
+  for issue in object
+    .validate_null_section()
+    .into_iter()
+    .chain(object.validate_sections())
+  {
+    let _ = writeln!(output, "  {}", issue);
+  }
+  Some(output)
+}
+
+/// Program headers and the section to segment mapping (`-l`).
+fn program_headers<'data, Reader, ElfType>(object: &elf::ElfObject<'data, Reader, ElfType>) -> Option<String>
+where
+  Reader: reader::Reader<'data>,
+  ElfType: elf::ElfType,
+{
+  use elfprobe_core::elf::ProgramHeader;
+  use elfprobe_core::utils::display_table;
+
+  let segments = object.program_headers().unwrap_or_default();
+  if segments.is_empty() {
+    return None;
+  }
+
+  let mut output = String::from("Program Headers:\n");
+  let _ = display_table!(
+    &mut output,
+    ["Type", "Offset", "VirtAddr", "PhysAddr", "FileSiz", "MemSiz", "Flags", "Align"],
+    segment in &segments => [
+      segment.type_name(object.osabi()),
+      ElfType::address(segment.p_offset()),
+      ElfType::address(segment.p_vaddr()),
+      ElfType::address(segment.p_paddr()),
+      ElfType::address(segment.p_filesz()),
+      ElfType::address(segment.p_memsz()),
+      segment.p_flags_decoded().rwe(),
+      format!("{:#x}", segment.p_align()),
+    ]
+  );
+
+  output.push_str("\nSection to Segment mapping:\n");
+  let mapping = object.section_to_segment_mapping().unwrap_or_default();
+  let _ = display_table!(
+    &mut output,
+    ["Segment", "Sections..."],
+    (index, names) in mapping.iter().enumerate() => [format!("{:02}", index), names.join(" ")]
+  );
+
+  // Memory of the loadable segments not backed by the file (.bss).
+  let tails = object.zero_fill();
+  if !tails.is_empty() {
+    let total = object.zero_fill_bytes();
+    let _ = writeln!(output, "\nZero-filled memory ({:#x} bytes):", total);
+    let _ = display_table!(
+      &mut output,
+      ["Segment", "Address", "Size", "Sections..."],
+      tail in &tails => [
+        format!("{:02}", tail.segment),
+        ElfType::address(tail.range.start),
+        format!("{:#x}", tail.size()),
+        tail.sections.join(" "),
+      ]
+    );
+  }
+  for issue in object.validate_segments() {
+    let _ = writeln!(output, "  {}", issue);
+  }
+  Some(output)
+}
+
+/// Dynamic section (`-d`).
+fn dynamic<'data, Reader, ElfType>(object: &elf::ElfObject<'data, Reader, ElfType>) -> Option<String>
+where
+  Reader: reader::Reader<'data>,
+  ElfType: elf::ElfType,
+{
+  use elfprobe_core::utils::display_table;
+
+  let entries = object.dynamic().unwrap_or_default();
+  if entries.is_empty() {
+    return None;
+  }
+
+  let e_machine: u64 = object.header().e_machine.into();
+  let names = entries.iter().map(|entry| {
+    let name = elf::dynamic_tag(e_machine as u16, object.osabi(), entry.d_tag()).to_string();
+    (entry, name.strip_prefix("DT_").map(String::from).unwrap_or(name))
+  });
+
+  let mut output = format!("Dynamic section ({} entries):\n", entries.len());
+  let _ = display_table!(
+    &mut output,
+    ["  Tag", "Type", "Value"],
+    (entry, name) in names => [
+      format!("  {:#018x}", entry.d_tag()),
+      format!("({})", name),
+      match entry.d_tag() {
+        elf::d_tag::DT_FLAGS => elf::flag_names(elf::df::from(entry.d_val()).iter(), "DF_"),
+        elf::d_tag::DT_FLAGS_1 => {
+          format!("Flags: {}", elf::flag_names(elf::df_1::from(entry.d_val()).iter(), "DF_1_"))
+        }
+        _ => format!("{:#x}", entry.d_val()),
+      },
+    ]
+  );
+  Some(output)
+}
+
+/// Symbol tables (`.dynsym` then `.symtab`), as readelf, names demangled with `--demangle`.
+fn symbols<'data, Reader, ElfType>(
+  object: &elf::ElfObject<'data, Reader, ElfType>,
+  listing: &elf::SymbolSelection,
+  demangle: bool,
+) -> Option<String>
+where
+  Reader: reader::Reader<'data>,
+  ElfType: elf::ElfType,
+{
+  let mut tables = Vec::new();
+  for (name, sh_type) in [(".dynsym", elf::SHT_DYNSYM), (".symtab", elf::SHT_SYMTAB)] {
+    if let Ok(Some(table)) = object.symbol_table(sh_type) {
+      tables.push(symbol_table(name, &table, listing, demangle));
+    }
+  }
+
+  // The symbols stripped from `.symtab` but kept for the backtraces.
+  #[cfg(feature = "xz")]
+  if let Some(table) = debugdata_symbols(object, listing, demangle) {
+    tables.push(table);
+  }
+
+  (!tables.is_empty()).then(|| tables.join("\n"))
+}
+
+/// Symbol table with its title, the symbols of `--only` in the order of `--sort`.
+fn symbol_table<ElfType: elf::ElfType>(
+  name: &str,
+  table: &elf::SymbolTable<'_, ElfType>,
+  listing: &elf::SymbolSelection,
+  demangle: bool,
+) -> String {
+  let mut output = format!("Symbol table '{}' contains {} entries", name, table.symbols.len());
+  let _ = match listing.filters.is_empty() {
+    true => writeln!(output, ":"),
+    false => writeln!(output, ", {} shown:", table.select(listing).count()),
+  };
+  let _ = match demangle {
+    true => write!(output, "{:#}", table.listing(listing)),
+    false => write!(output, "{}", table.listing(listing)),
+  };
+  output
+}
+
+/// `.symtab` of the ELF file embedded in `.gnu_debugdata` (MiniDebugInfo).
+#[cfg(feature = "xz")]
+fn debugdata_symbols<'data, Reader, ElfType>(
+  object: &elf::ElfObject<'data, Reader, ElfType>,
+  listing: &elf::SymbolSelection,
+  demangle: bool,
+) -> Option<String>
+where
+  Reader: reader::Reader<'data>,
+  ElfType: elf::ElfType,
+{
+  fn symtab<'data, Reader, ElfType>(
+    object: &elf::ElfObject<'data, Reader, ElfType>,
+    listing: &elf::SymbolSelection,
+    demangle: bool,
+  ) -> Option<String>
+  where
+    Reader: reader::Reader<'data>,
+    ElfType: elf::ElfType,
+  {
+    let table = object.symbol_table(elf::SHT_SYMTAB).ok()??;
+    Some(symbol_table(".gnu_debugdata:.symtab", &table, listing, demangle))
+  }
+
+  let bytes = object.debugdata_elf().ok()?;
+  match elf::parse_elf_with(bytes.as_slice(), object.options().nested().ok()?).ok()? {
+    elf::ElfFile::Elf32Be(inner) => symtab(&inner, listing, demangle),
+    elf::ElfFile::Elf64Be(inner) => symtab(&inner, listing, demangle),
+    elf::ElfFile::Elf32Le(inner) => symtab(&inner, listing, demangle),
+    elf::ElfFile::Elf64Le(inner) => symtab(&inner, listing, demangle),
+  }
+}
+
+/// Notes (`-n`).
+fn notes<'data, Reader, ElfType>(object: &elf::ElfObject<'data, Reader, ElfType>) -> Option<String>
+where
+  Reader: reader::Reader<'data>,
+  ElfType: elf::ElfType,
+{
+  let notes = object.notes().unwrap_or_default();
+  if notes.is_empty() {
+    return None;
+  }
+
+  let mut output = String::from("Notes:\n");
+  let _ = writeln!(output, "  {:<20} {:<10}  Description", "Owner", "Data size");
+  for note in &notes {
+    let _ = writeln!(output, "  {}", note);
+  }
+  Some(output)
 }
 
 // Read a usize value from a byte buffer:
@@ -147,3 +2321,111 @@ fn read_usize(x: &[u8]) -> usize {
   unsafe { ptr.read_unaligned() }
 }
  */
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// Parses the arguments, a path is given so that the standard input is not read.
+  fn parse(arguments: &[&str]) -> Result<Arguments, String> {
+    Arguments::parse(arguments.iter().copied().map(String::from))
+  }
+
+  #[test]
+  fn unknown_options() {
+    assert_eq!(
+      parse(&["--bogus", "a.out"]).unwrap_err(),
+      "unknown option `--bogus`"
+    );
+    assert_eq!(parse(&["-hX", "a.out"]).unwrap_err(), "unknown option `-X`");
+    assert_eq!(
+      parse(&["--checksec=yes", "a.out"]).unwrap_err(),
+      "unknown option `--checksec=yes`",
+      "No value expected"
+    );
+  }
+
+  #[test]
+  fn grouped_flags() {
+    let arguments = parse(&["-hSW", "a.out"]).unwrap();
+    let View::Selected { selection, .. } = arguments.view else {
+      panic!("Expected a selection, not {:?}", arguments.view);
+    };
+    assert_eq!(
+      selection,
+      Selection {
+        file_header: true,
+        section_headers: true,
+        ..Selection::default()
+      }
+    );
+    assert_eq!(arguments.width, None, "-W");
+    assert_eq!(arguments.paths, ["a.out"]);
+  }
+
+  #[test]
+  fn end_of_options() {
+    let arguments = parse(&["--strict", "--", "-h", "--wide", "-"]).unwrap();
+    assert_eq!(arguments.paths, ["-h", "--wide", "-"]);
+    assert_eq!(arguments.view, View::Summary);
+    assert!(arguments.options.strict);
+    assert!(arguments.width.is_some(), "--wide is a path");
+  }
+
+  #[test]
+  fn invalid_values() {
+    assert_eq!(
+      parse(&["--format=xml", "a.out"]).unwrap_err(),
+      "--format expects csv, tsv or text, not `xml`"
+    );
+    assert_eq!(
+      parse(&["--max-input-size=1M", "a.out"]).unwrap_err(),
+      "--max-input-size expects a number of bytes"
+    );
+    assert_eq!(
+      parse(&["--jobs=many", "a.out"]).unwrap_err(),
+      "--jobs expects a number"
+    );
+    assert_eq!(parse(&["--jobs"]).unwrap_err(), "--jobs expects a number");
+
+    let arguments = parse(&["--format=tsv", "--max-input-size=4096", "--jobs", "3", "a.out"]).unwrap();
+    assert_eq!(arguments.format, TableFormat::Tsv);
+    assert_eq!(arguments.limit, 4096);
+    assert_eq!(arguments.jobs, 3);
+    assert_eq!(arguments.paths, ["a.out"]);
+  }
+
+  #[test]
+  fn conflicting_views() {
+    assert_eq!(
+      parse(&["--checksec", "-h", "a.out"]).unwrap_err(),
+      "the selectors (-h, -S...) cannot be combined with --checksec"
+    );
+    assert_eq!(
+      parse(&["--hex-dump=.text", "--annotate-section=1", "a.out"]).unwrap_err(),
+      "--annotate-section cannot be combined with --hex-dump"
+    );
+    assert_eq!(
+      parse(&["--vermagic=6.1", "--modinfo", "--layout", "a.out"]).unwrap_err(),
+      "--modinfo cannot be combined with --layout"
+    );
+
+    // The flags of the same view add up.
+    let arguments = parse(&["--modinfo", "--vermagic=6.1", "a.out"]).unwrap();
+    assert_eq!(arguments.view, View::ModInfo(Some(String::from("6.1"))));
+    let arguments = parse(&["-h", "--entropy", "--sort=size", "-S", "a.out"]).unwrap();
+    assert!(matches!(arguments.view, View::Selected { entropy: true, .. }));
+  }
+
+  #[test]
+  fn no_limit() {
+    assert_eq!(parse(&["--no-limit", "a.out"]).unwrap().rows, None);
+    assert_eq!(row_limit(false, true), Some(DEFAULT_ROWS));
+    assert_eq!(row_limit(true, true), None);
+    assert_eq!(row_limit(false, false), None, "Not a terminal");
+  }
+}
@@ -1,6 +1,7 @@
+use core::marker::PhantomData;
 #[allow(unused)]
-use std::mem::align_of;
-use std::mem::size_of;
+use core::mem::align_of;
+use core::mem::size_of;
 
 use crate::error::BytesError;
 
@@ -33,14 +34,37 @@ use crate::error::BytesError;
 ///   are not implemented if the type contains some kind of magic (interior
 ///   mutability, references without a lifetime...).
 ///
+/// # Safety
+///
+/// Values are read from arbitrary bytes, any bit pattern must then be a valid
+/// value of the type: no `bool`, `char`, references, enumerations... and all
+/// the fields of an aggregate must be POD types, which `#[derive(Pod)]` checks
+/// at compile time.
+///
+/// Values are also viewed as bytes ([`Pod::as_bytes()`]), the type must then
+/// have no padding bytes (uninitialized memory): the fields of a `#[repr(C)]`
+/// aggregate are ordered for alignment, or the padding is an explicit field.
+/// `#[derive(Pod)]` checks it too, once the methods are instantiated (see
+/// [`Pod::NO_PADDING`]).
+///
 /// [rust_pod]: https://stackoverflow.com/questions/45634083/is-there-a-concept-of-pod-types-in-rust
 /// [static]: https://doc.rust-lang.org/rust-by-example/scope/lifetime/static_lifetime.html#trait-bound
 ///
 #[allow(unused)]
 // TODO: Add Send + Sync
-pub trait Pod: 'static + Copy + Sized {
+pub unsafe trait Pod: 'static + Copy + Sized {
+  ///
+  /// Evaluated by the methods below, `#[derive(Pod)]` asserts in it that the
+  /// size of the type is the sum of the sizes of its fields: a type with
+  /// padding bytes fails to compile (at once, or once instantiated when the
+  /// type is generic).
+  ///
+  #[doc(hidden)]
+  const NO_PADDING: () = ();
+
   #[allow(clippy::needless_lifetimes)] // For readability.
   fn from_bytes<'data>(bytes: &'data [u8]) -> Result<&'data Self, BytesError> {
+    let () = Self::NO_PADDING;
     if bytes.len() != size_of::<Self>() {
       return Err(BytesError::SizeOfMismatch {
         length: bytes.len(),
@@ -49,15 +73,9 @@ pub trait Pod: 'static + Copy + Sized {
     }
 
     let pointer = bytes.as_ptr();
-    #[cfg(any(clippy, not(feature = "unaligned")))]
-    if (pointer as usize) % align_of::<Self>() != 0 {
-      return Err(BytesError::AlignOfMismatch {
-        pointer: pointer as usize,
-        align_of: align_of::<Self>(),
-      });
-    }
+    check_alignment::<Self>(pointer)?;
 
-    // What about std::ptr::read*() methods?
+    // What about core::ptr::read*() methods?
     // What kind of security do they provide?
     // https://doc.rust-lang.org/std/ptr/fn.read.html
     // https://doc.rust-lang.org/std/ptr/fn.read_volatile.html
@@ -93,18 +111,152 @@ pub trait Pod: 'static + Copy + Sized {
 
     Ok(unsafe { &*pointer.cast::<Self>() })
   }
+
+  ///
+  /// Same as [`Pod::from_bytes()`] with mutable access, to patch a value in
+  /// place (header flags, interpreter...), see [`Writer`](crate::writer::Writer).
+  ///
+  #[allow(clippy::needless_lifetimes)] // For readability.
+  fn from_bytes_mut<'data>(bytes: &'data mut [u8]) -> Result<&'data mut Self, BytesError> {
+    let () = Self::NO_PADDING;
+    if bytes.len() != size_of::<Self>() {
+      return Err(BytesError::SizeOfMismatch {
+        length: bytes.len(),
+        size_of: size_of::<Self>(),
+      });
+    }
+
+    let pointer = bytes.as_mut_ptr();
+    check_alignment::<Self>(pointer)?;
+
+    // Same as from_bytes(), the value exclusively borrows the bytes.
+    Ok(unsafe { &mut *pointer.cast::<Self>() })
+  }
+
+  ///
+  /// Returns the bytes of the value, the inverse of [`Pod::from_bytes()`]
+  /// (checksums, patched copies of headers...).
+  ///
+  /// The type has no padding bytes (see the safety section of [`Pod`]), all
+  /// the bytes of the value are initialized.
+  ///
+  fn as_bytes(&self) -> &[u8] {
+    let () = Self::NO_PADDING;
+    unsafe { core::slice::from_raw_parts((self as *const Self).cast::<u8>(), size_of::<Self>()) }
+  }
+
+  ///
+  /// Same as [`Pod::from_bytes()`] but the value is copied out of the bytes
+  /// with [`core::ptr::read_unaligned()`], whatever their alignment: section
+  /// contents are aligned in memory (`sh_addralign`), not necessarily in the
+  /// file.
+  ///
+  fn from_bytes_copied(bytes: &[u8]) -> Result<Self, BytesError> {
+    let () = Self::NO_PADDING;
+    if bytes.len() != size_of::<Self>() {
+      return Err(BytesError::SizeOfMismatch {
+        length: bytes.len(),
+        size_of: size_of::<Self>(),
+      });
+    }
+
+    // A POD is valid for any bit pattern and the length is checked.
+    Ok(unsafe { bytes.as_ptr().cast::<Self>().read_unaligned() })
+  }
+
+  ///
+  /// Same as [`Pod::from_bytes()`] for consecutive values (tables), the
+  /// length of the bytes must be a multiple of the size of the type.
+  ///
+  #[allow(clippy::needless_lifetimes)] // For readability.
+  fn slice_from_bytes<'data>(bytes: &'data [u8]) -> Result<&'data [Self], BytesError> {
+    let () = Self::NO_PADDING;
+    // The length of a slice of zero-sized values cannot be told from bytes,
+    // instantiating this method with one fails to compile.
+    const {
+      assert!(
+        size_of::<Self>() != 0,
+        "Pod::slice_from_bytes() on a zero-sized type"
+      )
+    };
+
+    if bytes.is_empty() {
+      return Ok(&[]);
+    }
+
+    if !bytes.len().is_multiple_of(size_of::<Self>()) {
+      return Err(BytesError::SizeOfMismatch {
+        length: bytes.len(),
+        size_of: size_of::<Self>(),
+      });
+    }
+
+    let pointer = bytes.as_ptr();
+    check_alignment::<Self>(pointer)?;
+
+    // Same as from_bytes(), the slice borrows the bytes.
+    let length = bytes.len() / size_of::<Self>();
+    Ok(unsafe { core::slice::from_raw_parts(pointer.cast::<Self>(), length) })
+  }
+}
+
+/// Checks the alignment of the pointer, unless unaligned reads are enabled.
+#[inline]
+#[allow(unused_variables)]
+fn check_alignment<Type>(pointer: *const u8) -> Result<(), BytesError> {
+  #[cfg(any(clippy, not(feature = "unaligned")))]
+  if !(pointer as usize).is_multiple_of(align_of::<Type>()) {
+    return Err(BytesError::AlignOfMismatch {
+      pointer: pointer as usize,
+      align_of: align_of::<Type>(),
+    });
+  }
+
+  Ok(())
 }
 
 #[allow(unused_macros)]
 macro_rules! impl_pod {
-  ($($bytes: literal),+, $($type: ident),+) => {
-    $(impl Pod for [u8; $bytes] {})+
-    $(impl Pod for $type {})+
+  ($($type: ident),+) => {
+    $(unsafe impl Pod for $type {})+
   };
 }
 
 // Implement POD trait for primitive types in order to be used by POD aggregates.
-impl_pod!(2, 4, 8, i8, u8, i16, u16, i32, u32, i64, u64);
+impl_pod!(i8, u8, i16, u16, i32, u32, i64, u64, i128, u128);
+
+// SAFETY: Arrays of POD types are POD types (no padding between elements).
+unsafe impl<Type: Pod, const LENGTH: usize> Pod for [Type; LENGTH] {}
+
+// SAFETY: Zero-sized, e.g., the endianness of the primitive types.
+unsafe impl<Type: ?Sized + 'static> Pod for PhantomData<Type> {}
+
+// ╔═╗┬┌─┐┬  ┌┬┐┌─┐
+// ╠╣ │├┤ │   ││└─┐
+// ╚  ┴└─┘┴─┘─┴┘└─┘
+
+/// A field of a structure, its offset and size in bytes, see [`FieldLayout`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Field {
+  pub name: &'static str,
+  pub offset: usize,
+  pub size: usize,
+}
+
+///
+/// The fields of a structure in declaration order, derived with
+/// `#[derive(FieldLayout)]` from `offset_of!` and `size_of` so that they
+/// match the layout the structure is read with (e.g. to tell which bytes of
+/// a header a field is made of).
+///
+pub trait FieldLayout {
+  const FIELDS: &'static [Field];
+
+  /// Returns the field with the given name.
+  fn field(name: &str) -> Option<&'static Field> {
+    Self::FIELDS.iter().find(|field| field.name == name)
+  }
+}
 
 // ╔╦╗┌─┐┌─┐┌┬┐┌─┐
 //  ║ ├┤ └─┐ │ └─┐
@@ -113,7 +265,7 @@ impl_pod!(2, 4, 8, i8, u8, i16, u16, i32, u32, i64, u64);
 #[cfg(test)]
 mod tests {
   use super::*;
-  use std::mem::offset_of;
+  use core::mem::offset_of;
 
   #[repr(C)]
   #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -122,9 +274,11 @@ mod tests {
     b: u32,
     c: u16,
     d: u8,
+    /// Explicit padding, a POD has no uninitialized bytes.
+    _pad: u8,
   }
 
-  impl Pod for Dada {}
+  unsafe impl Pod for Dada {}
 
   impl Default for Dada {
     fn default() -> Self {
@@ -134,6 +288,7 @@ mod tests {
         b: 0x03_03_03_03_u32,
         c: 0x02_02_u16,
         d: 0x01_u8,
+        _pad: 0,
       }
     }
   }
@@ -146,6 +301,7 @@ mod tests {
     assert_eq!(offset_of!(Dada, b), 8);
     assert_eq!(offset_of!(Dada, c), 12);
     assert_eq!(offset_of!(Dada, d), 14);
+    assert_eq!(offset_of!(Dada, _pad), 15);
 
     let bytes: [u8; 16] = [
       // Byte order has no consequence in this way.
@@ -153,7 +309,7 @@ mod tests {
       0x03, 0x03, 0x03, 0x03, // b
       0x02, 0x02, // c
       0x01, // d
-      0x00, //
+      0x00, // _pad
     ];
 
     let dada = Dada::from_bytes(&bytes);
@@ -195,4 +351,75 @@ mod tests {
       }),
     )
   }
+
+  #[test]
+  fn from_bytes_mut() {
+    let mut bytes = Aligned([0; 1 + 32]);
+    let dada = Dada::from_bytes_mut(&mut bytes.0[..16]).unwrap();
+    *dada = Dada::default();
+    dada.d = 0x7F;
+    assert_eq!(bytes.0[..16], [4, 4, 4, 4, 4, 4, 4, 4, 3, 3, 3, 3, 2, 2, 0x7F, 0]);
+
+    assert_eq!(
+      Dada::from_bytes_mut(&mut bytes.0[..3]),
+      Err(BytesError::SizeOfMismatch {
+        length: 3,
+        size_of: 16,
+      }),
+    );
+  }
+
+  #[test]
+  fn from_bytes_copied() {
+    // Same input as from_bytes_align_of_error(), misaligned on purpose.
+    let mut bytes = Aligned([0; 1 + 32]);
+    bytes.0[1..17].copy_from_slice(&[4, 4, 4, 4, 4, 4, 4, 4, 3, 3, 3, 3, 2, 2, 1, 0]);
+
+    assert_eq!(Dada::from_bytes_copied(&bytes.0[1..17]), Ok(Dada::default()));
+    assert_eq!(
+      Dada::from_bytes_copied(&bytes.0[1..16]),
+      Err(BytesError::SizeOfMismatch {
+        length: 15,
+        size_of: 16,
+      }),
+    );
+  }
+
+  /// Buffer aligned for `Dada` so that misalignment is deterministic.
+  #[repr(C, align(8))]
+  struct Aligned([u8; 1 + 32]);
+
+  #[test]
+  fn slice_from_bytes() {
+    let mut bytes = Aligned([0; 1 + 32]);
+    bytes.0[16..32].copy_from_slice(&[4, 4, 4, 4, 4, 4, 4, 4, 3, 3, 3, 3, 2, 2, 1, 0]);
+
+    let dadas = Dada::slice_from_bytes(&bytes.0[..32]).unwrap();
+    assert_eq!(dadas.len(), 2);
+    assert_eq!(dadas[1], Dada::default());
+    assert_eq!(Dada::slice_from_bytes(&[]), Ok(&[][..]));
+
+    assert_eq!(
+      Dada::slice_from_bytes(&bytes.0[..24]),
+      Err(BytesError::SizeOfMismatch {
+        length: 24,
+        size_of: 16,
+      }),
+    );
+  }
+
+  #[test]
+  #[cfg(any(clippy, not(feature = "unaligned")))]
+  fn slice_from_bytes_align_of_error() {
+    let bytes = Aligned([0; 1 + 32]);
+    let slice = &bytes.0[1..];
+
+    assert_eq!(
+      Dada::slice_from_bytes(slice),
+      Err(BytesError::AlignOfMismatch {
+        pointer: slice.as_ptr() as usize,
+        align_of: 8,
+      }),
+    );
+  }
 }
@@ -1,5 +1,6 @@
-use std::fmt;
-use std::marker::PhantomData;
+use core::fmt;
+use core::marker::PhantomData;
+use core::ops;
 
 use crate::endian::Endianness;
 use elfprobe_macro::Pod;
@@ -12,7 +13,7 @@ use elfprobe_macro::Pod;
 // ╚═╝ ┴ ┴└─└─┘└─┘ ┴
 
 macro_rules! create_primitive {
-  ($struct: ident, $alias: ident, $type: ident, $inner: ty, $operation: ty) => {
+  ($struct: ident, $alias: ident, $type: ident, $inner: ty, $operation: ty, [$($wider: ident),*]) => {
     #[doc = concat!("An `", stringify!($inner), "` wrapper with runtime endianness.")]
     ///
     /// It's important that this structure is a zero-cost abstraction of its
@@ -50,6 +51,8 @@ macro_rules! create_primitive {
 
     impl_primitive_method!($struct, $type, $operation);
     impl_primitive_format!($struct);
+    impl_primitive_serialize!($struct);
+    impl_primitive_into!($struct, $type, $($wider),*);
   };
 }
 
@@ -78,10 +81,107 @@ macro_rules! impl_primitive_method {
       pub fn set(&mut self, value: $type) {
         self.0 = <Endianness as $operation>::write(value);
       }
+
+      /// Checked addition, see the native type method.
+      #[inline]
+      #[allow(unused)]
+      pub fn checked_add(self, value: $type) -> Option<Self> {
+        self.get().checked_add(value).map(Self::from)
+      }
+
+      /// Checked multiplication, see the native type method.
+      #[inline]
+      #[allow(unused)]
+      pub fn checked_mul(self, value: $type) -> Option<Self> {
+        self.get().checked_mul(value).map(Self::from)
+      }
+    }
+
+    // Comparisons with native values go through get(), not the stored bytes.
+    impl<Endianness: self::Endianness> PartialEq<$type> for $struct<Endianness> {
+      #[inline]
+      fn eq(&self, value: &$type) -> bool {
+        self.get() == *value
+      }
+    }
+
+    impl<Endianness: self::Endianness> PartialEq<$struct<Endianness>> for $type {
+      #[inline]
+      fn eq(&self, value: &$struct<Endianness>) -> bool {
+        *self == value.get()
+      }
+    }
+
+    impl<Endianness: self::Endianness> PartialOrd<$type> for $struct<Endianness> {
+      #[inline]
+      fn partial_cmp(&self, value: &$type) -> Option<core::cmp::Ordering> {
+        self.get().partial_cmp(value)
+      }
+    }
+
+    impl<Endianness: self::Endianness> PartialOrd<$struct<Endianness>> for $type {
+      #[inline]
+      fn partial_cmp(&self, value: &$struct<Endianness>) -> Option<core::cmp::Ordering> {
+        self.partial_cmp(&value.get())
+      }
+    }
+
+    // Operators work on native values (read, compute, write back), the result
+    // keeps the endianness of the wrapper.
+    impl_primitive_operator!($struct, $type, Add, add);
+    impl_primitive_operator!($struct, $type, Sub, sub);
+    impl_primitive_operator!($struct, $type, BitAnd, bitand);
+    impl_primitive_operator!($struct, $type, BitOr, bitor);
+    impl_primitive_operator!($struct, $type, u32, Shl, shl);
+    impl_primitive_operator!($struct, $type, u32, Shr, shr);
+  };
+}
+
+macro_rules! impl_primitive_operator {
+  ($struct: ident, $type: ident, $trait: ident, $method: ident) => {
+    impl_primitive_operator!($struct, $type, $type, $trait, $method);
+
+    impl<Endianness: self::Endianness> ops::$trait for $struct<Endianness> {
+      type Output = Self;
+
+      #[inline]
+      fn $method(self, value: Self) -> Self {
+        Self::from(ops::$trait::$method(self.get(), value.get()))
+      }
+    }
+  };
+
+  ($struct: ident, $type: ident, $rhs: ident, $trait: ident, $method: ident) => {
+    impl<Endianness: self::Endianness> ops::$trait<$rhs> for $struct<Endianness> {
+      type Output = Self;
+
+      #[inline]
+      fn $method(self, value: $rhs) -> Self {
+        Self::from(ops::$trait::$method(self.get(), value))
+      }
     }
   };
 }
 
+// ╦┌┐┌┌┬┐┌─┐
+// ║│││ │ │ │
+// ╩┘└┘ ┴ └─┘
+
+macro_rules! impl_primitive_into {
+  // Only lossless conversions (native type and wider types) are implemented,
+  // narrowing conversions must be explicit (and checked) at the call site.
+  ($struct: ident, $($type: ident),+ $(,)?) => {
+    $(
+      impl<Endianness: self::Endianness> From<$struct<Endianness>> for $type {
+        #[inline]
+        fn from(value: $struct<Endianness>) -> Self {
+          value.get().into()
+        }
+      }
+    )+
+  };
+}
+
 // ╔═╗┌─┐┬─┐┌┬┐┌─┐┌┬┐
 // ╠╣ │ │├┬┘│││├─┤ │
 // ╚  └─┘┴└─┴ ┴┴ ┴ ┴
@@ -116,22 +216,42 @@ macro_rules! impl_primitive_format {
   };
 }
 
+macro_rules! impl_primitive_serialize {
+  // The native value is serialized, not the stored bytes.
+  ($struct: ident) => {
+    #[cfg(feature = "serde")]
+    impl<Endianness: self::Endianness> serde::Serialize for $struct<Endianness> {
+      fn serialize<Serializer: serde::Serializer>(
+        &self,
+        serializer: Serializer,
+      ) -> Result<Serializer::Ok, Serializer::Error> {
+        self.get().serialize(serializer)
+      }
+    }
+  };
+}
+
 // ╔═╗┬─┐┌─┐┌─┐┌┬┐┌─┐
 // ║  ├┬┘├┤ ├─┤ │ ├┤
 // ╚═╝┴└─└─┘┴ ┴ ┴ └─┘
 
 // #[doc(cfg(not(feature = "unaligned")))]
 #[cfg(any(clippy, doc, not(feature = "unaligned")))]
+#[rustfmt::skip] // One primitive per line.
 mod aligned {
   use super::*;
   use crate::endian::AlignedEndianOperation;
 
-  create_primitive!(AlignedI16, I16, i16, i16, AlignedEndianOperation<i16>);
-  create_primitive!(AlignedU16, U16, u16, u16, AlignedEndianOperation<u16>);
-  create_primitive!(AlignedI32, I32, i32, i32, AlignedEndianOperation<i32>);
-  create_primitive!(AlignedU32, U32, u32, u32, AlignedEndianOperation<u32>);
-  create_primitive!(AlignedI64, I64, i64, i64, AlignedEndianOperation<i64>);
-  create_primitive!(AlignedU64, U64, u64, u64, AlignedEndianOperation<u64>);
+  create_primitive!(AlignedI8, I8, i8, i8, AlignedEndianOperation<i8>, [i16, i32, i64, i128]);
+  create_primitive!(AlignedU8, U8, u8, u8, AlignedEndianOperation<u8>, [u16, u32, u64, u128]);
+  create_primitive!(AlignedI16, I16, i16, i16, AlignedEndianOperation<i16>, [i32, i64, i128]);
+  create_primitive!(AlignedU16, U16, u16, u16, AlignedEndianOperation<u16>, [u32, u64, u128]);
+  create_primitive!(AlignedI32, I32, i32, i32, AlignedEndianOperation<i32>, [i64, i128]);
+  create_primitive!(AlignedU32, U32, u32, u32, AlignedEndianOperation<u32>, [u64, u128]);
+  create_primitive!(AlignedI64, I64, i64, i64, AlignedEndianOperation<i64>, [i128]);
+  create_primitive!(AlignedU64, U64, u64, u64, AlignedEndianOperation<u64>, [u128]);
+  create_primitive!(AlignedI128, I128, i128, i128, AlignedEndianOperation<i128>, []);
+  create_primitive!(AlignedU128, U128, u128, u128, AlignedEndianOperation<u128>, []);
 }
 
 // #[doc(cfg(feature = "unaligned")]
@@ -140,12 +260,16 @@ mod unaligned {
   use super::*;
   use crate::endian::UnalignedEndianOperation;
 
-  create_primitive!(UnalignedI16, I16, i16, [u8; 2], UnalignedEndianOperation<i16, 2>);
-  create_primitive!(UnalignedU16, U16, u16, [u8; 2], UnalignedEndianOperation<u16, 2>);
-  create_primitive!(UnalignedI32, I32, i32, [u8; 4], UnalignedEndianOperation<i32, 4>);
-  create_primitive!(UnalignedU32, U32, u32, [u8; 4], UnalignedEndianOperation<u32, 4>);
-  create_primitive!(UnalignedI64, I64, i64, [u8; 8], UnalignedEndianOperation<i64, 8>);
-  create_primitive!(UnalignedU64, U64, u64, [u8; 8], UnalignedEndianOperation<u64, 8>);
+  create_primitive!(UnalignedI8, I8, i8, [u8; 1], UnalignedEndianOperation<i8, 1>, [i16, i32, i64, i128]);
+  create_primitive!(UnalignedU8, U8, u8, [u8; 1], UnalignedEndianOperation<u8, 1>, [u16, u32, u64, u128]);
+  create_primitive!(UnalignedI16, I16, i16, [u8; 2], UnalignedEndianOperation<i16, 2>, [i32, i64, i128]);
+  create_primitive!(UnalignedU16, U16, u16, [u8; 2], UnalignedEndianOperation<u16, 2>, [u32, u64, u128]);
+  create_primitive!(UnalignedI32, I32, i32, [u8; 4], UnalignedEndianOperation<i32, 4>, [i64, i128]);
+  create_primitive!(UnalignedU32, U32, u32, [u8; 4], UnalignedEndianOperation<u32, 4>, [u64, u128]);
+  create_primitive!(UnalignedI64, I64, i64, [u8; 8], UnalignedEndianOperation<i64, 8>, [i128]);
+  create_primitive!(UnalignedU64, U64, u64, [u8; 8], UnalignedEndianOperation<u64, 8>, [u128]);
+  create_primitive!(UnalignedI128, I128, i128, [u8; 16], UnalignedEndianOperation<i128, 16>, []);
+  create_primitive!(UnalignedU128, U128, u128, [u8; 16], UnalignedEndianOperation<u128, 16>, []);
 }
 
 // ╦ ╦┌─┐┌─┐
@@ -153,11 +277,11 @@ mod unaligned {
 // ╚═╝└─┘└─┘
 
 #[cfg(not(feature = "unaligned"))]
-pub use aligned::{I16, I32, I64, U16, U32, U64};
+pub use aligned::{I128, I16, I32, I64, I8, U128, U16, U32, U64, U8};
 
 #[cfg(feature = "unaligned")]
 /// `unaligned` feature is enabled by default.
-pub use unaligned::{I16, I32, I64, U16, U32, U64};
+pub use unaligned::{I128, I16, I32, I64, I8, U128, U16, U32, U64, U8};
 
 // ╔╦╗┌─┐┌─┐┌┬┐┌─┐
 //  ║ ├┤ └─┐ │ └─┐
@@ -172,18 +296,33 @@ mod tests {
     () => {
       test_primitive!(BigEndian, big_endian);
       test_primitive!(LittleEndian, little_endian);
+      test_primitive!(NativeEndian, native_endian);
     };
 
     ($endian: ident, $module: ident) => {
       mod $module {
         use super::*;
 
+        test_primitive!($endian, I8, i8, 0x11);
+        test_primitive!($endian, U8, u8, 0x11);
         test_primitive!($endian, I16, i16, 0x1122);
         test_primitive!($endian, U16, u16, 0x1122);
         test_primitive!($endian, I32, i32, 0x1122_3344);
         test_primitive!($endian, U32, u32, 0x1122_3344);
         test_primitive!($endian, I64, i64, 0x1122_3344_5566_7788);
         test_primitive!($endian, U64, u64, 0x1122_3344_5566_7788);
+        test_primitive!(
+          $endian,
+          I128,
+          i128,
+          0x1122_3344_5566_7788_99AA_BBCC_DDEE_FF00
+        );
+        test_primitive!(
+          $endian,
+          U128,
+          u128,
+          0x1122_3344_5566_7788_99AA_BBCC_DDEE_FF00
+        );
       }
     };
 
@@ -211,10 +350,77 @@ mod tests {
           let value2 = $struct::<$endian>::from($initial);
           assert_eq!(value1, value2);
         }
+
+        #[test]
+        fn compare() {
+          let value = $struct::<$endian>::from($initial);
+          assert!(value == $initial && $initial == value);
+          assert!(value != $initial - 1 && $initial - 1 != value);
+          assert!(value > $initial - 1 && $initial + 1 > value);
+          assert!(value <= $initial && $initial >= value);
+        }
+
+        #[test]
+        fn operators() {
+          let value = $struct::<$endian>::from($initial);
+          assert_eq!((value + 1).get(), $initial + 1);
+          assert_eq!((value - 1).get(), $initial - 1);
+          assert_eq!(value + value - value, value);
+          assert_eq!(value & 0x0F, $struct::from($initial & 0x0F));
+          assert_eq!(value | value, value);
+          assert_eq!((value << 1) >> 1, value);
+          assert_eq!(value.checked_add(1), Some($struct::from($initial + 1)));
+          assert_eq!(value.checked_mul(2), Some($struct::from($initial * 2)));
+          assert_eq!((value << 1).checked_mul(64), None);
+        }
       }
     };
   }
 
+  #[test]
+  fn operators_representation() {
+    let value = U32::<BigEndian>::from(1) + 2;
+    assert_eq!(value, U32::from(3));
+    // Stored big-endian whatever the host.
+    assert_eq!(
+      unsafe { core::mem::transmute::<U32<BigEndian>, [u8; 4]>(value) },
+      [0, 0, 0, 3]
+    );
+
+    let value = U32::<LittleEndian>::from(0x0100) | U32::from(0x02);
+    assert_eq!(
+      unsafe { core::mem::transmute::<U32<LittleEndian>, [u8; 4]>(value) },
+      [2, 1, 0, 0]
+    );
+  }
+
+  #[test]
+  fn native_endian() {
+    // Stored as is, the bytes are those of the native integer.
+    let value = U32::<NativeEndian>::from(0x1122_3344);
+    let bytes = unsafe { core::mem::transmute::<U32<NativeEndian>, [u8; 4]>(value) };
+    assert_eq!(bytes, 0x1122_3344u32.to_ne_bytes());
+  }
+
+  #[test]
+  fn compare_native() {
+    // Stored bytes of 0x0100 read natively are 0x0001 on one of the two.
+    let big = U16::<BigEndian>::from(0x0100);
+    let little = U16::<LittleEndian>::from(0x0100);
+    assert!(big > 0x00FF && little > 0x00FF);
+    assert!(0x00FF < big && 0x00FF < little);
+    assert!(big == 0x0100 && little == 0x0100);
+  }
+
+  #[test]
+  #[cfg(feature = "serde")]
+  fn serialize() {
+    let value = serde_json::to_string(&U32::<BigEndian>::from(0x0100)).unwrap();
+    assert_eq!(value, "256");
+    let value = serde_json::to_string(&I16::<LittleEndian>::from(-2)).unwrap();
+    assert_eq!(value, "-2");
+  }
+
   #[cfg(not(feature = "unaligned"))]
   mod aligned {
     use super::*;
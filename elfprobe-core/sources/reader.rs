@@ -1,4 +1,4 @@
-use std::mem::size_of;
+use core::mem::size_of;
 
 use crate::error::BytesError;
 use crate::pod::Pod;
@@ -23,7 +23,7 @@ use crate::pod::Pod;
 // ## `Copy` trait
 //
 // The `Copy` trait allows for simple bitwise copies of data and is a
-// `std::marker` trait with no methods. The `Copy` trait can only be
+// `core::marker` trait with no methods. The `Copy` trait can only be
 // implemented by types whose fields also implement `Copy`. Certain types like
 // `String` and `Vec<T>` are not `Copy` because they contain heap-allocated
 // data, which would duplicate the pointer causing a double-free, or like
@@ -72,6 +72,45 @@ pub trait Reader<'data>: Copy + Clone {
       Some(bytes) => Type::from_bytes(bytes),
     }
   }
+
+  ///
+  /// Same as [`Reader::read_pod()`] but returns a copy, which does not
+  /// require the bytes to be aligned, see [`Pod::from_bytes_copied()`].
+  ///
+  #[allow(unused)]
+  fn read_pod_copied<Type: Pod>(self, offset: usize) -> Result<Type, BytesError> {
+    match self.read_bytes(size_of::<Type>(), offset) {
+      // TODO: Same as read_pod(), it is not empty.
+      None => Err(BytesError::Empty),
+      Some(bytes) => Type::from_bytes_copied(bytes),
+    }
+  }
+
+  ///
+  /// Reads `count` consecutive values (section headers, symbols...) without
+  /// copying them, see [`Pod::slice_from_bytes()`].
+  ///
+  #[allow(unused)]
+  fn read_pod_slice<Type: Pod>(self, offset: usize, count: usize) -> Result<&'data [Type], BytesError> {
+    let size = count.checked_mul(size_of::<Type>()).ok_or(BytesError::Overflow {
+      count,
+      size_of: size_of::<Type>(),
+    })?;
+
+    match self.read_bytes(size, offset) {
+      // TODO: Same as read_pod(), it is not empty.
+      None => Err(BytesError::Empty),
+      Some(bytes) => Type::slice_from_bytes(bytes),
+    }
+  }
+
+  ///
+  /// Hints that `size` bytes from `offset` are about to be read in order
+  /// (hashing, entropy), so that they can be read ahead. Nothing is done by
+  /// default, see [`MappedFile::advise_sequential()`](crate::file::MappedFile::advise_sequential()).
+  ///
+  #[allow(unused)]
+  fn advise_sequential(self, offset: usize, size: usize) {}
 }
 
 ///
@@ -86,10 +125,65 @@ impl<'data> Reader<'data> for &'data [u8] {
   }
 
   fn read_bytes(self, size: usize, offset: usize) -> Option<&'data [u8]> {
-    self.get(offset..offset + size)
+    // Offsets come from the file itself and must not overflow.
+    self.get(offset..offset.checked_add(size)?)
+  }
+}
+
+///
+/// A part of the data read as a whole (an ELF file embedded in a firmware
+/// image at a known offset), offsets are relative to its start.
+///
+#[derive(Debug, Copy, Clone)]
+pub struct SubRange<Reader> {
+  reader: Reader,
+  offset: usize,
+  length: usize,
+}
+
+impl<'data, Reader: self::Reader<'data>> SubRange<Reader> {
+  ///
+  /// Returns the bytes from `offset` to the end of the data, or `length`
+  /// bytes at most. `None` when the offset is past the end of the data.
+  ///
+  pub fn new(reader: Reader, offset: usize, length: Option<usize>) -> Option<Self> {
+    let available = reader.length().checked_sub(offset)?;
+    Some(Self {
+      reader,
+      offset,
+      length: length.map_or(available, |length| length.min(available)),
+    })
+  }
+}
+
+impl<'data, Reader: self::Reader<'data>> self::Reader<'data> for SubRange<Reader> {
+  #[inline]
+  fn length(self) -> usize {
+    self.length
+  }
+
+  fn read_bytes(self, size: usize, offset: usize) -> Option<&'data [u8]> {
+    if offset.checked_add(size)? > self.length {
+      return None;
+    }
+    self.reader.read_bytes(size, self.offset + offset)
+  }
+
+  fn advise_sequential(self, offset: usize, size: usize) {
+    self.reader.advise_sequential(self.offset + offset, size);
   }
 }
 
+///
+/// Converts a file offset or size (64-bit fields of elf64 files) to `usize`,
+/// values past the address space of the host (32-bit) are an error rather
+/// than a truncation or a panic.
+///
+#[inline]
+pub fn to_usize(value: u64) -> Result<usize, BytesError> {
+  usize::try_from(value).map_err(|_| BytesError::OffsetOverflow(value))
+}
+
 // ╔╦╗┌─┐┌─┐┌┬┐┌─┐
 //  ║ ├┤ └─┐ │ └─┐
 //  ╩ └─┘└─┘ ┴ └─┘
@@ -97,14 +191,30 @@ impl<'data> Reader<'data> for &'data [u8] {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use core::mem::{align_of, offset_of};
   use elfprobe_macro::Pod;
-  use std::mem::{align_of, offset_of};
 
   #[test]
   fn read_bytes() {
     let slice: &[u8] = &[1, 2, 3, 4, 5, 6];
     let bytes = <&[u8] as Reader<'_>>::read_bytes(slice, 3, 2);
     assert_eq!(bytes, Some(&[3u8, 4u8, 5u8] as &[u8]));
+
+    let bytes = <&[u8] as Reader<'_>>::read_bytes(slice, 2, usize::MAX);
+    assert_eq!(bytes, None);
+  }
+
+  #[test]
+  fn sub_range() {
+    let slice: &[u8] = &[1, 2, 3, 4, 5, 6];
+    let range = SubRange::new(slice, 2, Some(3)).unwrap();
+    assert_eq!(range.length(), 3);
+    assert_eq!(range.read_bytes(2, 1), Some(&[4u8, 5u8] as &[u8]));
+    assert_eq!(range.read_bytes(2, 2), None, "Past the length");
+
+    assert_eq!(SubRange::new(slice, 4, Some(8)).unwrap().length(), 2);
+    assert_eq!(SubRange::new(slice, 6, None).unwrap().length(), 0);
+    assert!(SubRange::new(slice, 7, None).is_none());
   }
 
   #[test]
@@ -113,6 +223,7 @@ mod tests {
     #[derive(Debug, Copy, Clone, PartialEq, Eq, Pod)]
     struct Dada {
       a: u8,
+      _pad: [u8; 7],
       b: u64,
     }
 
@@ -120,6 +231,7 @@ mod tests {
       fn default() -> Self {
         Self {
           a: 0xA0_u8,
+          _pad: [0; 7],
           // The byte order has no effect this way.
           b: 0xB0_B0_B0_B0_B0_B0_B0_B0_u64,
         }
@@ -139,5 +251,30 @@ mod tests {
 
     let dada = bytes.read_pod::<Dada>(8);
     assert_eq!(Ok(&Dada::default()), dada);
+
+    // Copied whatever the alignment of the offset.
+    let mut bytes = [0u8; 1 + 16];
+    bytes[1] = 0xA0;
+    bytes[9..].fill(0xB0);
+    assert_eq!(bytes.as_slice().read_pod_copied::<Dada>(1), Ok(Dada::default()));
+    assert_eq!(
+      bytes.as_slice().read_pod_copied::<Dada>(2),
+      Err(BytesError::Empty)
+    );
+  }
+
+  #[test]
+  fn read_pod_slice() {
+    let slice: &[u8] = &[1, 0, 2, 0, 3, 0, 4];
+    assert_eq!(slice.read_pod_slice::<[u8; 2]>(2, 2), Ok(&[[2, 0], [3, 0]][..]));
+    assert_eq!(slice.read_pod_slice::<[u8; 2]>(7, 0), Ok(&[][..]));
+    assert_eq!(slice.read_pod_slice::<[u8; 2]>(2, 3), Err(BytesError::Empty));
+    assert_eq!(
+      slice.read_pod_slice::<[u8; 4]>(0, usize::MAX / 2),
+      Err(BytesError::Overflow {
+        count: usize::MAX / 2,
+        size_of: 4
+      })
+    );
   }
 }
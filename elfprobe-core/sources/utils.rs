@@ -0,0 +1,40 @@
+mod adapter;
+mod constant;
+mod demangle;
+mod digest;
+mod entropy;
+mod flags;
+#[cfg(feature = "std")]
+mod jobs;
+mod leb128;
+mod setting;
+mod style;
+mod table;
+#[cfg(all(feature = "std", unix))]
+mod terminal;
+#[cfg(feature = "xz")]
+mod xz;
+
+pub use adapter::{Addr, FileOffset, Hex, HexDump, Size};
+pub(crate) use constant::define_constants;
+pub use constant::Constant;
+pub use demangle::{demangle, Demangled};
+pub use digest::{crc32, DigestAlgorithm};
+#[cfg(feature = "sha")]
+pub use digest::{sha1, sha256};
+pub use entropy::{entropy, Entropy, ENTROPY_WINDOW, HIGH_ENTROPY};
+pub(crate) use flags::define_flags;
+pub use flags::UnknownFlag;
+#[cfg(feature = "std")]
+pub use jobs::for_each_ordered;
+pub use leb128::{read_sleb128, read_uleb128};
+#[cfg(not(feature = "std"))]
+pub use setting::Setting;
+pub(crate) use setting::{settings, Restore};
+pub use style::{Style, Styled};
+pub use table::{display_row, display_table};
+pub use table::{Alignment, Overflow, TableBuilder, TableFormat};
+#[cfg(all(feature = "std", unix))]
+pub use terminal::terminal_width;
+#[cfg(feature = "xz")]
+pub use xz::decompress_xz;
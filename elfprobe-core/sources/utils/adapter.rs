@@ -0,0 +1,225 @@
+use core::fmt::{self, Display, LowerHex, Write};
+
+use crate::alloc_prelude::*;
+
+// ╔═╗┌┬┐┌─┐┌─┐┌┬┐┌─┐┬─┐┌─┐
+// ╠═╣ ││├─┤├─┘ │ ├┤ ├┬┘└─┐
+// ╩ ╩─┴┘┴ ┴┴   ┴ └─┘┴└─└─┘
+
+///
+/// Displays a value in hexadecimal with the `0x` prefix (addresses, flags),
+/// padding and alignment of the formatter apply to the whole string.
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Hex<Type>(pub Type);
+
+impl<Type: LowerHex> Display for Hex<Type> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    formatter.pad(&format!("{:#x}", self.0))
+  }
+}
+
+///
+/// Displays an address with a fixed number of hexadecimal digits (8 for
+/// ELFCLASS32, 16 for ELFCLASS64) so that columns align as readelf's
+/// (`0x0000000000401000`), the alternate flag (`{:#}`) drops the leading
+/// zeros. See [`ElfType::address()`](crate::elf::ElfType::address()).
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Addr(pub u64, pub usize);
+
+impl Display for Addr {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match formatter.alternate() {
+      true => formatter.pad(&format!("{:#x}", self.0)),
+      false => formatter.pad(&format!("{:#0width$x}", self.0, width = self.1 + 2)),
+    }
+  }
+}
+
+/// Displays a file offset as readelf (`64 (bytes into file)`).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct FileOffset<Type>(pub Type);
+
+impl<Type: Display> Display for FileOffset<Type> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    formatter.pad(&format!("{} (bytes into file)", self.0))
+  }
+}
+
+///
+/// Displays a size in bytes scaled to the largest IEC unit with one decimal,
+/// followed by the exact count (`1.2 MiB (1259824)`). Sizes below 1 KiB are
+/// displayed as is, as with the alternate flag (`{:#}`, machine-friendly).
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Size<Type>(pub Type);
+
+impl<Type: Copy + Into<u64>> Display for Size<Type> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    const UNITS: [&str; 6] = ["KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+    let size: u64 = self.0.into();
+    if formatter.alternate() || size < 1024 {
+      return formatter.pad(&size.to_string());
+    }
+
+    // Tenths of the unit, rounded before the unit is chosen: 1048575 is
+    // 1.0 MiB, not 1024.0 KiB.
+    let tenths = |exponent: usize| {
+      let unit = 1u128 << (exponent * 10);
+      (size as u128 * 10 + unit / 2) / unit
+    };
+    let mut exponent = (size.ilog2() / 10) as usize;
+    if tenths(exponent) >= 1024 * 10 && exponent < UNITS.len() {
+      exponent += 1;
+    }
+
+    let tenths = tenths(exponent);
+    let unit = UNITS[exponent - 1];
+    formatter.pad(&format!("{}.{} {} ({})", tenths / 10, tenths % 10, unit, size))
+  }
+}
+
+///
+/// Displays bytes as `xxd` does, 16 bytes per line grouped by two with the
+/// offset and an ASCII gutter (non-printable bytes are dots):
+///
+/// ```txt
+/// 00000000: 7f45 4c46 0201 0100 0000 0000 0000 0000  .ELF............
+/// 00000010: 0300 3e00                                ..>.
+/// ```
+///
+/// Offsets start at zero, or at the base (e.g. the section file offset) with
+/// the alternate flag (`{:#}`).
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct HexDump<'data>(pub &'data [u8], pub u64);
+
+impl<'data> HexDump<'data> {
+  const BYTES_PER_LINE: usize = 16;
+
+  /// Hexadecimal column width, the trailing partial line is padded to it.
+  const HEX_WIDTH: usize = Self::BYTES_PER_LINE * 2 + Self::BYTES_PER_LINE / 2 - 1;
+
+  /// Returns the adapter with a zero base.
+  pub fn new(bytes: &'data [u8]) -> Self {
+    Self(bytes, 0)
+  }
+}
+
+impl Display for HexDump<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let base = if formatter.alternate() { self.1 } else { 0 };
+    let mut hex = String::with_capacity(Self::HEX_WIDTH);
+
+    for (index, line) in self.0.chunks(Self::BYTES_PER_LINE).enumerate() {
+      hex.clear();
+      for (index, byte) in line.iter().enumerate() {
+        if index != 0 && index % 2 == 0 {
+          hex.push(' ');
+        }
+        write!(hex, "{:02x}", byte)?;
+      }
+
+      let ascii = line
+        .iter()
+        .map(|&byte| match byte {
+          b' ' | 0x21..=0x7E => byte as char,
+          _ => '.',
+        })
+        .collect::<String>();
+
+      let offset = base.wrapping_add((index * Self::BYTES_PER_LINE) as u64);
+      writeln!(
+        formatter,
+        "{:08x}: {:<width$}  {}",
+        offset,
+        hex,
+        ascii,
+        width = Self::HEX_WIDTH
+      )?;
+    }
+
+    Ok(())
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn adapters() {
+    assert_eq!(Hex(0x401000u64).to_string(), "0x401000");
+    assert_eq!(format!("{:>6}", Hex(0u8)), "   0x0");
+    assert_eq!(FileOffset(64).to_string(), "64 (bytes into file)");
+  }
+
+  #[test]
+  fn addr() {
+    assert_eq!(Addr(0x401000, 16).to_string(), "0x0000000000401000");
+    assert_eq!(Addr(0x401000, 8).to_string(), "0x00401000");
+    assert_eq!(
+      Addr(0x1_0000_0000, 8).to_string(),
+      "0x100000000",
+      "Wider than the class"
+    );
+    assert_eq!(format!("{:#}", Addr(0x401000, 16)), "0x401000");
+    assert_eq!(format!("{:>12}|", Addr(0x10, 8)), "  0x00000010|");
+  }
+
+  #[test]
+  fn size() {
+    assert_eq!(Size(0u64).to_string(), "0");
+    assert_eq!(Size(1023u64).to_string(), "1023");
+    assert_eq!(Size(1024u64).to_string(), "1.0 KiB (1024)");
+    assert_eq!(Size(1536u32).to_string(), "1.5 KiB (1536)");
+    assert_eq!(Size(1259824u64).to_string(), "1.2 MiB (1259824)");
+    assert_eq!(Size(1u64 << 20).to_string(), "1.0 MiB (1048576)");
+    assert_eq!(Size((1u64 << 20) - 1).to_string(), "1.0 MiB (1048575)");
+    assert_eq!(Size((1u64 << 20) - 52).to_string(), "1023.9 KiB (1048524)");
+    assert_eq!(Size(3u64 << 30).to_string(), "3.0 GiB (3221225472)");
+    assert_eq!(Size(u64::MAX).to_string(), "16.0 EiB (18446744073709551615)");
+    assert_eq!(format!("{:#}", Size(1536u64)), "1536");
+    assert_eq!(format!("{:>6}|", Size(12u8)), "    12|");
+  }
+
+  #[test]
+  fn hex_dump() {
+    let bytes = b"\x7fELF\x02\x01\x01\0\0\0\0\0\0\0\0\0\x03\0>\0";
+    assert_eq!(
+      HexDump::new(bytes).to_string(),
+      concat!(
+        "00000000: 7f45 4c46 0201 0100 0000 0000 0000 0000  .ELF............\n",
+        "00000010: 0300 3e00                                ..>.\n",
+      )
+    );
+
+    // Odd length, the last group is a single byte.
+    assert_eq!(
+      HexDump::new(b"Hi there!").to_string(),
+      "00000000: 4869 2074 6865 7265 21                   Hi there!\n"
+    );
+
+    assert_eq!(HexDump::new(b"").to_string(), "");
+  }
+
+  #[test]
+  fn hex_dump_base() {
+    let bytes = [0xFFu8; 17];
+    let dump = HexDump(&bytes, 0x1_0000_0000);
+    assert_eq!(
+      format!("{:#}", dump),
+      concat!(
+        "100000000: ffff ffff ffff ffff ffff ffff ffff ffff  ................\n",
+        "100000010: ff                                       .\n",
+      )
+    );
+    assert!(dump.to_string().starts_with("00000000: "));
+  }
+}
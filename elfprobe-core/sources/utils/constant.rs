@@ -0,0 +1,320 @@
+use core::fmt::{self, Display, LowerHex};
+use core::ops::Sub;
+
+use crate::alloc_prelude::*;
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐
+// ║  │ ││││└─┐ │ ├─┤│││ │
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴
+
+///
+/// A value looked up in a constants table (see [`define_constants!`]), with
+/// its name and meaning when the value is known.
+///
+/// Values within a range (e.g. `SHT_LOOS .. SHT_HIOS`) are named after the
+/// low bound of the range and displayed as an offset from it (`SHT_LOOS+0x3`).
+/// Unknown values are displayed in hexadecimal.
+///
+/// The alternate form (`{:#}`) is meant for machine-readable output and
+/// appends the raw value to the name (`ET_DYN(0x3)`, `SHT_LOOS+0x3(0x60000003)`).
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Constant<Type: 'static> {
+  value: Type,
+  base: Type,
+  name: Option<&'static str>,
+  meaning: Option<&'static str>,
+}
+
+impl<Type: Copy> Constant<Type> {
+  /// A known value, `base` is the value itself or the low bound of its range.
+  pub const fn new(value: Type, base: Type, name: &'static str, meaning: &'static str) -> Self {
+    Self {
+      value,
+      base,
+      name: Some(name),
+      meaning: Some(meaning),
+    }
+  }
+
+  /// A value missing from the table.
+  pub const fn unknown(value: Type) -> Self {
+    Self {
+      value,
+      base: value,
+      name: None,
+      meaning: None,
+    }
+  }
+
+  /// Returns the raw value.
+  pub fn value(&self) -> Type {
+    self.value
+  }
+
+  /// Returns the constant name (or the name of the range low bound).
+  #[allow(unused)]
+  pub fn name(&self) -> Option<&'static str> {
+    self.name
+  }
+
+  /// Returns the human-readable description of the constant.
+  pub fn meaning(&self) -> Option<&'static str> {
+    self.meaning
+  }
+}
+
+/// Serialized as its value, name and meaning (names and meanings are null
+/// when unknown).
+#[cfg(feature = "serde")]
+impl<Type: serde::Serialize> serde::Serialize for Constant<Type> {
+  fn serialize<Serializer: serde::Serializer>(
+    &self,
+    serializer: Serializer,
+  ) -> Result<Serializer::Ok, Serializer::Error> {
+    use serde::ser::SerializeStruct;
+
+    let mut state = serializer.serialize_struct("Constant", 3)?;
+    state.serialize_field("value", &self.value)?;
+    state.serialize_field("name", &self.name)?;
+    state.serialize_field("meaning", &self.meaning)?;
+    state.end()
+  }
+}
+
+impl<Type> Display for Constant<Type>
+where
+  Type: Copy + PartialEq + LowerHex + Sub<Output = Type>,
+{
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let name = match self.name {
+      Some(name) if self.value == self.base => String::from(name),
+      Some(name) => format!("{}+{:#x}", name, self.value - self.base),
+      None => return formatter.pad(&format!("{:#x}", self.value)),
+    };
+
+    match formatter.alternate() {
+      true => formatter.pad(&format!("{}({:#x})", name, self.value)),
+      false => formatter.pad(&name),
+    }
+  }
+}
+
+///
+/// Defines a table of constants as a module holding one `const` per entry,
+/// a `from()` function returning the matching [`Constant`], `all()` listing
+/// the constants and `by_name()` for the reverse lookup. An entry is either a
+/// single value or a range (both bounds are defined as `const` and listed):
+///
+/// ```ignore
+/// define_constants! {
+///   /// Section types.
+///   pub sh_type: u32 {
+///     SHT_NULL = 0 => "Section header table entry unused",
+///     [SHT_LOOS .. SHT_HIOS] = 0x60000000 .. 0x6fffffff => "OS-specific",
+///   }
+/// }
+///
+/// assert_eq!(sh_type::from(0).to_string(), "SHT_NULL");
+/// assert_eq!(sh_type::from(0x60000003).to_string(), "SHT_LOOS+0x3");
+/// assert_eq!(sh_type::by_name("SHT_HIOS"), Some(0x6fffffff));
+/// assert_eq!(sh_type::all().len(), 3);
+/// ```
+///
+/// Entries are matched in declaration order, so single values falling into
+/// a range must be declared before it.
+///
+/// The type may be signed (`d_tag` is an `Sxword`) and the values negative,
+/// unknown negative values are displayed as their two's complement.
+///
+macro_rules! define_constants {
+  (
+    $(#[$meta: meta])*
+    $visibility: vis $table: ident: $type: ty { $($body: tt)* }
+  ) => {
+    $(#[$meta])*
+    #[allow(non_upper_case_globals, unused)]
+    $visibility mod $table {
+      use super::*;
+      use $crate::utils::Constant;
+      $crate::utils::define_constants!(@entries $type, [], $($body)*);
+    }
+  };
+
+  // Single value.
+  (
+    @entries $type: ty, [$($entries: tt)*],
+    $(#[$meta: meta])* $name: ident = $value: expr => $meaning: literal
+    $(, $($rest: tt)*)?
+  ) => {
+    $(#[$meta])*
+    #[doc = $meaning]
+    pub const $name: $type = $value;
+    $crate::utils::define_constants!(
+      @entries $type, [$($entries)* ($name, $name, $meaning, [$name])], $($($rest)*)?
+    );
+  };
+
+  // Range of values.
+  (
+    @entries $type: ty, [$($entries: tt)*],
+    $(#[$meta: meta])* [$low: ident .. $high: ident] = $from: literal .. $to: literal => $meaning: literal
+    $(, $($rest: tt)*)?
+  ) => {
+    $(#[$meta])*
+    #[doc = concat!($meaning, " (low bound).")]
+    pub const $low: $type = $from;
+    #[doc = concat!($meaning, " (high bound).")]
+    pub const $high: $type = $to;
+    $crate::utils::define_constants!(
+      @entries $type, [$($entries)* ($low, $high, $meaning, [$low, $high])], $($($rest)*)?
+    );
+  };
+
+  (
+    @entries $type: ty,
+    [$(($low: ident, $high: ident, $meaning: literal, [$($bound: ident),+]))*],
+  ) => {
+    /// Returns the constant matching the given value.
+    pub fn from(value: $type) -> Constant<$type> {
+      $(
+        if ($low..=$high).contains(&value) {
+          return Constant::new(value, $low, stringify!($low), $meaning);
+        }
+      )*
+      Constant::unknown(value)
+    }
+
+    /// Returns every constant in declaration order, ranges as their two bounds.
+    pub fn all() -> &'static [Constant<$type>] {
+      const ALL: &[Constant<$type>] = &[
+        $($(Constant::new($bound, $bound, stringify!($bound), $meaning),)+)*
+      ];
+      ALL
+    }
+
+    /// Returns the value of the constant with the given name (e.g. `"EM_RISCV"`).
+    pub fn by_name(name: &str) -> Option<$type> {
+      $($(
+        if name == stringify!($bound) {
+          return Some($bound);
+        }
+      )+)*
+      None
+    }
+  };
+}
+
+pub(crate) use define_constants;
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  define_constants! {
+    /// Test table.
+    test_type: u32 {
+      TEST_NULL = 0 => "Null",
+      /// Documented constant.
+      TEST_ONE = 1 => "One",
+      TEST_SPECIAL = 0x60000010 => "Special value in the OS range",
+      [TEST_LOOS .. TEST_HIOS] = 0x60000000 .. 0x6fffffff => "OS-specific",
+    }
+  }
+
+  #[test]
+  fn constants() {
+    assert_eq!(test_type::TEST_ONE, 1);
+    assert_eq!(test_type::TEST_HIOS, 0x6fffffff);
+
+    let constant = test_type::from(1);
+    assert_eq!(constant.name(), Some("TEST_ONE"));
+    assert_eq!(constant.meaning(), Some("One"));
+    assert_eq!(constant.to_string(), "TEST_ONE");
+    assert_eq!(format!("{:<10}|", constant), "TEST_ONE  |");
+  }
+
+  #[test]
+  #[cfg(feature = "serde")]
+  fn serialize() {
+    let json = serde_json::to_string(&test_type::from(1)).unwrap();
+    assert_eq!(json, r#"{"value":1,"name":"TEST_ONE","meaning":"One"}"#);
+    let json = serde_json::to_string(&test_type::from(2)).unwrap();
+    assert_eq!(json, r#"{"value":2,"name":null,"meaning":null}"#);
+  }
+
+  #[test]
+  fn ranges() {
+    assert_eq!(test_type::from(0x60000000).to_string(), "TEST_LOOS");
+    assert_eq!(test_type::from(0x60000003).to_string(), "TEST_LOOS+0x3");
+    assert_eq!(test_type::from(0x60000010).to_string(), "TEST_SPECIAL");
+    assert_eq!(test_type::from(0x6fffffff).meaning(), Some("OS-specific"));
+  }
+
+  #[test]
+  fn alternate() {
+    assert_eq!(test_type::from(1).value(), 1);
+    assert_eq!(format!("{:#}", test_type::from(1)), "TEST_ONE(0x1)");
+    assert_eq!(
+      format!("{:#}", test_type::from(0x60000003)),
+      "TEST_LOOS+0x3(0x60000003)"
+    );
+    assert_eq!(format!("{:#}", test_type::from(42)), "0x2a");
+  }
+
+  #[test]
+  fn iteration() {
+    let names = test_type::all()
+      .iter()
+      .map(|constant| constant.name().unwrap())
+      .collect::<Vec<_>>();
+    assert_eq!(
+      names,
+      ["TEST_NULL", "TEST_ONE", "TEST_SPECIAL", "TEST_LOOS", "TEST_HIOS"]
+    );
+    assert_eq!(test_type::all()[4].to_string(), "TEST_HIOS");
+    assert_eq!(test_type::all()[4].meaning(), Some("OS-specific"));
+  }
+
+  #[test]
+  fn by_name() {
+    assert_eq!(test_type::by_name("TEST_ONE"), Some(1));
+    assert_eq!(test_type::by_name("TEST_HIOS"), Some(0x6fffffff));
+    assert_eq!(test_type::by_name("TEST_TWO"), None);
+    assert_eq!(test_signed::by_name("TEST_LOPROC"), Some(0x70000000));
+  }
+
+  define_constants! {
+    /// Signed test table (as `d_tag`).
+    test_signed: i64 {
+      TEST_ZERO = 0 => "Zero",
+      TEST_NEGATIVE = -2 => "Negative",
+      [TEST_LONEG .. TEST_HINEG] = -0x20 .. -0x10 => "Negative range",
+      [TEST_LOPROC .. TEST_HIPROC] = 0x70000000 .. 0x7fffffff => "Processor-specific",
+    }
+  }
+
+  #[test]
+  fn signed() {
+    assert_eq!(test_signed::from(0).to_string(), "TEST_ZERO");
+    assert_eq!(test_signed::from(0x70000016).to_string(), "TEST_LOPROC+0x16");
+    assert_eq!(test_signed::from(-1).to_string(), "0xffffffffffffffff");
+    assert_eq!(test_signed::from(-2).to_string(), "TEST_NEGATIVE");
+    assert_eq!(test_signed::from(-2).value(), -2);
+    assert_eq!(test_signed::from(-0x1e).to_string(), "TEST_LONEG+0x2");
+    assert_eq!(test_signed::from(-0x10).meaning(), Some("Negative range"));
+    assert_eq!(test_signed::by_name("TEST_HINEG"), Some(-0x10));
+  }
+
+  #[test]
+  fn unknown() {
+    let constant = test_type::from(42);
+    assert_eq!(constant, Constant::unknown(42));
+    assert_eq!(constant.name(), None);
+    assert_eq!(constant.to_string(), "0x2a");
+  }
+}
@@ -0,0 +1,599 @@
+use core::fmt::{self, Display};
+
+use crate::alloc_prelude::*;
+
+// ╔╦╗┌─┐┌┬┐┌─┐┌┐┌┌─┐┬  ┌─┐
+//  ║║├┤ │││├─┤││││ ┬│  ├┤
+// ═╩╝└─┘┴ ┴┴ ┴┘└┘└─┘┴─┘└─┘
+
+///
+/// Displays a symbol name demangled, or as is when it is not mangled or the
+/// mangling is not supported (or invalid). Decoding never panics.
+///
+/// The decoders are built in (no dependency) and minimal, see [`demangle()`].
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Demangled<'name>(pub &'name str);
+
+impl Display for Demangled<'_> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match demangle(self.0) {
+      Some(name) => formatter.pad(&name),
+      None => formatter.pad(self.0),
+    }
+  }
+}
+
+///
+/// Returns the demangled name, `None` when the name is not mangled or uses
+/// unsupported constructs. Supported manglings are:
+///
+/// - Rust legacy (`_ZN...17h<hash>E`), the hash is omitted.
+/// - Rust v0 (`_R`), paths without generic arguments, the crate
+///   disambiguators are omitted.
+/// - Itanium C++ (`_Z`), nested names, constructors and destructors,
+///   builtin, qualified and substituted parameter types, but no templates.
+///
+pub fn demangle(name: &str) -> Option<String> {
+  if let Some(mangled) = name.strip_prefix("_R") {
+    return rust_v0(mangled);
+  }
+
+  let mangled = name.strip_prefix("_Z")?;
+  rust_legacy(mangled).or_else(|| itanium(mangled))
+}
+
+/// Reads a mangled name, every read fails (`None`) past the end.
+struct Parser<'name> {
+  bytes: &'name [u8],
+  position: usize,
+  /// Nesting of recursive rules, bounded against stack overflows.
+  depth: usize,
+}
+
+impl<'name> Parser<'name> {
+  const MAX_DEPTH: usize = 64;
+
+  fn new(mangled: &'name str) -> Self {
+    Self {
+      bytes: mangled.as_bytes(),
+      position: 0,
+      depth: 0,
+    }
+  }
+
+  fn is_empty(&self) -> bool {
+    self.position == self.bytes.len()
+  }
+
+  fn peek(&self) -> Option<u8> {
+    self.bytes.get(self.position).copied()
+  }
+
+  fn next(&mut self) -> Option<u8> {
+    let byte = self.peek()?;
+    self.position += 1;
+    Some(byte)
+  }
+
+  /// Skips the byte when it is the next one.
+  fn eat(&mut self, byte: u8) -> bool {
+    let found = self.peek() == Some(byte);
+    self.position += found as usize;
+    found
+  }
+
+  fn decimal(&mut self) -> Option<usize> {
+    let start = self.position;
+    let mut value: usize = 0;
+    while let Some(digit @ b'0'..=b'9') = self.peek() {
+      value = value.checked_mul(10)?.checked_add((digit - b'0') as usize)?;
+      self.position += 1;
+    }
+
+    (self.position != start).then_some(value)
+  }
+
+  /// Reads `length` bytes, which must be valid UTF-8.
+  fn take(&mut self, length: usize) -> Option<&'name str> {
+    let end = self.position.checked_add(length)?;
+    let bytes = self.bytes.get(self.position..end)?;
+    self.position = end;
+    core::str::from_utf8(bytes).ok()
+  }
+
+  /// Enters a recursive rule, fails when nested too deep.
+  fn enter(&mut self) -> Option<()> {
+    self.depth += 1;
+    (self.depth <= Self::MAX_DEPTH).then_some(())
+  }
+
+  fn leave(&mut self) {
+    self.depth -= 1;
+  }
+}
+
+// ╦═╗┬ ┬┌─┐┌┬┐
+// ╠╦╝│ │└─┐ │
+// ╩╚═└─┘└─┘ ┴
+
+/// Rust legacy mangling: Itanium nested names ending with the hash.
+fn rust_legacy(mangled: &str) -> Option<String> {
+  let mut parser = Parser::new(mangled);
+  if !parser.eat(b'N') {
+    return None;
+  }
+
+  let mut elements = Vec::new();
+  while !parser.eat(b'E') {
+    let length = parser.decimal()?;
+    elements.push(parser.take(length)?);
+  }
+
+  let is_hash = |hash: &&str| {
+    hash.len() == 17 && hash.starts_with('h') && hash[1..].bytes().all(|byte| byte.is_ascii_hexdigit())
+  };
+  elements.pop().filter(is_hash)?;
+  if !parser.is_empty() || elements.is_empty() {
+    return None;
+  }
+
+  let elements = elements.into_iter().map(unescape).collect::<Option<Vec<_>>>()?;
+  Some(elements.join("::"))
+}
+
+/// Decodes the `$LT$`-like escapes and the `..` separators of a legacy element.
+fn unescape(element: &str) -> Option<String> {
+  let mut rest = element.strip_prefix("_$").map_or(element, |_| &element[1..]);
+  let mut output = String::new();
+
+  while let Some(character) = rest.chars().next() {
+    if let Some(escaped) = rest.strip_prefix('$') {
+      let (escape, tail) = escaped.split_once('$')?;
+      output.push(match escape {
+        "SP" => '@',
+        "BP" => '*',
+        "RF" => '&',
+        "LT" => '<',
+        "GT" => '>',
+        "LP" => '(',
+        "RP" => ')',
+        "C" => ',',
+        _ => char::from_u32(u32::from_str_radix(escape.strip_prefix('u')?, 16).ok()?)?,
+      });
+      rest = tail;
+    } else if let Some(tail) = rest.strip_prefix("..") {
+      output.push_str("::");
+      rest = tail;
+    } else {
+      output.push(character);
+      rest = &rest[character.len_utf8()..];
+    }
+  }
+
+  Some(output)
+}
+
+/// Rust v0 mangling, paths made of crate roots and nested names only.
+fn rust_v0(mangled: &str) -> Option<String> {
+  let mut parser = Parser::new(mangled);
+  let path = v0_path(&mut parser)?;
+
+  // The instantiating crate may follow, it is not displayed.
+  if parser.peek() == Some(b'C') {
+    v0_path(&mut parser)?;
+  }
+
+  parser.is_empty().then_some(path)
+}
+
+fn v0_path(parser: &mut Parser) -> Option<String> {
+  parser.enter()?;
+  let path = match parser.next()? {
+    b'C' => v0_identifier(parser).map(|(_, name)| String::from(name)),
+    b'N' => {
+      let namespace = parser.next()?;
+      let parent = v0_path(parser)?;
+      let (disambiguator, name) = v0_identifier(parser)?;
+      let kind = match namespace {
+        b'a'..=b'z' if !name.is_empty() => return Some(format!("{}::{}", parent, name)),
+        b'C' => "closure",
+        b'S' => "shim",
+        _ => return None,
+      };
+
+      Some(match name.is_empty() {
+        true => format!("{}::{{{}#{}}}", parent, kind, disambiguator),
+        false => format!("{}::{{{}:{}#{}}}", parent, kind, name, disambiguator),
+      })
+    }
+    _ => None,
+  };
+
+  parser.leave();
+  path
+}
+
+/// Returns the disambiguator and the name (Punycode names are unsupported).
+fn v0_identifier<'name>(parser: &mut Parser<'name>) -> Option<(u64, &'name str)> {
+  let disambiguator = match parser.eat(b's') {
+    true => v0_base62(parser)?.checked_add(1)?,
+    false => 0,
+  };
+
+  if parser.peek() == Some(b'u') {
+    return None;
+  }
+
+  let length = parser.decimal()?;
+  parser.eat(b'_'); // Separates the length from a name starting with a digit or `_`.
+  Some((disambiguator, parser.take(length)?))
+}
+
+/// Base-62 number terminated by `_`, `_` alone being 0 and `0_` 1.
+fn v0_base62(parser: &mut Parser) -> Option<u64> {
+  if parser.eat(b'_') {
+    return Some(0);
+  }
+
+  let mut value: u64 = 0;
+  loop {
+    let digit = match parser.next()? {
+      b'_' => return value.checked_add(1),
+      digit @ b'0'..=b'9' => digit - b'0',
+      digit @ b'a'..=b'z' => digit - b'a' + 10,
+      digit @ b'A'..=b'Z' => digit - b'A' + 36,
+      _ => return None,
+    };
+    value = value.checked_mul(62)?.checked_add(digit as u64)?;
+  }
+}
+
+// ╦┌┬┐┌─┐┌┐┌┬┬ ┬┌┬┐  ╔═╗╔╗ ╦
+// ║ │ ├─┤││││ ││││  ╠═╣╠╩╗║
+// ╩ ┴ ┴ ┴┘└┘┴└─┘┴ ┴  ╩ ╩╚═╝╩
+
+/// Itanium C++ mangling, a name optionally followed by the parameter types.
+fn itanium(mangled: &str) -> Option<String> {
+  let mut demangler = Itanium {
+    parser: Parser::new(mangled),
+    substitutions: Vec::new(),
+  };
+
+  let (name, qualifiers) = demangler.name(false)?;
+  if demangler.parser.is_empty() {
+    return Some(name); // Variables.
+  }
+
+  let mut parameters = Vec::new();
+  while !demangler.parser.is_empty() {
+    parameters.push(demangler.parameter()?);
+  }
+
+  let parameters = match parameters.as_slice() {
+    [void] if void == "void" => String::new(),
+    _ => parameters.join(", "),
+  };
+
+  Some(format!("{}({}){}", name, parameters, qualifiers))
+}
+
+struct Itanium<'name> {
+  parser: Parser<'name>,
+  /// Components referenced by `S_` then `S<base 36>_`, in order of appearance.
+  substitutions: Vec<String>,
+}
+
+impl Itanium<'_> {
+  ///
+  /// Returns a name and its qualifiers (` const` for const member functions).
+  /// Type names are substitutable themselves, entity names only by prefix.
+  ///
+  fn name(&mut self, is_type: bool) -> Option<(String, &'static str)> {
+    if !self.parser.eat(b'N') {
+      let name = match self.parser.peek()? {
+        b'S' => self.substitution()?.0,
+        _ => self.source_name()?,
+      };
+
+      if is_type {
+        self.substitutions.push(name.clone());
+      }
+      return Some((name, ""));
+    }
+
+    let qualifiers = match self.parser.eat(b'K') {
+      true => " const",
+      false => "",
+    };
+
+    let mut prefix = String::new();
+    let mut last = String::new();
+    let mut substituted = false;
+    while !self.parser.eat(b'E') {
+      // The prefix is complete as another component follows.
+      if !prefix.is_empty() && !substituted {
+        self.substitutions.push(prefix.clone());
+      }
+
+      // Constructors and destructors are named after the class (last component).
+      substituted = false;
+      let (component, name) = match self.parser.peek()? {
+        b'S' if prefix.is_empty() => {
+          substituted = self.parser.bytes.get(self.parser.position + 1) != Some(&b't');
+          self.substitution()?
+        }
+        b'C' => {
+          self.parser.next();
+          matches!(self.parser.next()?, b'1'..=b'3').then_some(())?;
+          (last.clone(), last.clone())
+        }
+        b'D' => {
+          self.parser.next();
+          matches!(self.parser.next()?, b'0'..=b'2').then_some(())?;
+          (format!("~{}", last), last.clone())
+        }
+        _ => {
+          let name = self.source_name()?;
+          (name.clone(), name)
+        }
+      };
+
+      if component.is_empty() {
+        return None;
+      }
+
+      prefix = match prefix.is_empty() {
+        true => component,
+        false => format!("{}::{}", prefix, component),
+      };
+      last = name;
+    }
+
+    if prefix.is_empty() {
+      return None;
+    }
+
+    if is_type && !substituted {
+      self.substitutions.push(prefix.clone());
+    }
+    Some((prefix, qualifiers))
+  }
+
+  /// Length-prefixed identifier.
+  fn source_name(&mut self) -> Option<String> {
+    let length = self.parser.decimal()?;
+    self.parser.take(length).map(String::from)
+  }
+
+  ///
+  /// `St` (`std::`), abbreviations (`Ss`...) and references (`S_`, `S0_`...).
+  /// Returns the name and its last component (constructors are named after it).
+  ///
+  fn substitution(&mut self) -> Option<(String, String)> {
+    if !self.parser.eat(b'S') {
+      return None;
+    }
+
+    let (abbreviation, last) = match self.parser.peek()? {
+      b't' => {
+        self.parser.next();
+        let name = self.source_name()?;
+        return Some((format!("std::{}", name), name));
+      }
+      b'a' => ("std::allocator", "allocator"),
+      b'b' => ("std::basic_string", "basic_string"),
+      b's' => (
+        "std::basic_string<char, std::char_traits<char>, std::allocator<char> >",
+        "basic_string",
+      ),
+      b'i' => (
+        "std::basic_istream<char, std::char_traits<char> >",
+        "basic_istream",
+      ),
+      b'o' => (
+        "std::basic_ostream<char, std::char_traits<char> >",
+        "basic_ostream",
+      ),
+      b'd' => (
+        "std::basic_iostream<char, std::char_traits<char> >",
+        "basic_iostream",
+      ),
+      _ => ("", ""),
+    };
+
+    if !abbreviation.is_empty() {
+      self.parser.next();
+      return Some((String::from(abbreviation), String::from(last)));
+    }
+
+    let mut index: usize = 0;
+    if !self.parser.eat(b'_') {
+      loop {
+        let digit = match self.parser.next()? {
+          b'_' => break,
+          digit @ b'0'..=b'9' => digit - b'0',
+          digit @ b'A'..=b'Z' => digit - b'A' + 10,
+          _ => return None,
+        };
+        index = index.checked_mul(36)?.checked_add(digit as usize)?;
+      }
+      index = index.checked_add(1)?;
+    }
+
+    let name = self.substitutions.get(index)?;
+    let last = name.rsplit("::").next().unwrap_or_default();
+    Some((name.clone(), String::from(last)))
+  }
+
+  /// Parameter type, composite types are substitutable.
+  fn parameter(&mut self) -> Option<String> {
+    self.parser.enter()?;
+    let parameter = self.parameter_type();
+    self.parser.leave();
+    parameter
+  }
+
+  fn parameter_type(&mut self) -> Option<String> {
+    let builtin = match self.parser.peek()? {
+      b'v' => "void",
+      b'w' => "wchar_t",
+      b'b' => "bool",
+      b'c' => "char",
+      b'a' => "signed char",
+      b'h' => "unsigned char",
+      b's' => "short",
+      b't' => "unsigned short",
+      b'i' => "int",
+      b'j' => "unsigned int",
+      b'l' => "long",
+      b'm' => "unsigned long",
+      b'x' => "long long",
+      b'y' => "unsigned long long",
+      b'n' => "__int128",
+      b'o' => "unsigned __int128",
+      b'f' => "float",
+      b'd' => "double",
+      b'e' => "long double",
+      b'g' => "__float128",
+      b'z' => "...",
+      _ => "",
+    };
+
+    if !builtin.is_empty() {
+      self.parser.next();
+      return Some(String::from(builtin));
+    }
+
+    let composite = match self.parser.peek()? {
+      b'P' => "*",
+      b'R' => "&",
+      b'O' => "&&",
+      b'K' => " const",
+      b'N' | b'0'..=b'9' => return self.name(true).map(|(name, _)| name),
+      b'S' => {
+        let is_name = matches!(self.parser.bytes.get(self.parser.position + 1), Some(b't'));
+        let (name, _) = self.substitution()?;
+        if is_name {
+          self.substitutions.push(name.clone());
+        }
+        return Some(name);
+      }
+      _ => return None,
+    };
+
+    self.parser.next();
+    let parameter = format!("{}{}", self.parameter()?, composite);
+    self.substitutions.push(parameter.clone());
+    Some(parameter)
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn rust_legacy() {
+    assert_eq!(
+      Demangled("_ZN4core3fmt5write17h0123456789abcdefE").to_string(),
+      "core::fmt::write"
+    );
+    assert_eq!(
+      Demangled(
+        "_ZN66_$LT$alloc..vec..Vec$LT$T$GT$$u20$as$u20$core..ops..drop..Drop$GT$4drop17h1dd2c5f4ef24ae4aE"
+      )
+      .to_string(),
+      "<alloc::vec::Vec<T> as core::ops::drop::Drop>::drop"
+    );
+    assert_eq!(
+      demangle("_ZN4core3fmt5write17hnothexnothexnothE").as_deref(),
+      Some("core::fmt::write::hnothexnothexnoth"),
+      "Not a hash, demangled as C++"
+    );
+  }
+
+  #[test]
+  fn rust_v0() {
+    assert_eq!(
+      Demangled("_RNvCs15kBYyAo9fc_7mycrate7example").to_string(),
+      "mycrate::example"
+    );
+    assert_eq!(
+      Demangled("_RNvNtCs1234_7mycrate3foo3bar").to_string(),
+      "mycrate::foo::bar"
+    );
+    assert_eq!(
+      Demangled("_RNCNvCs1234_7mycrate4main0").to_string(),
+      "mycrate::main::{closure#0}"
+    );
+    assert_eq!(
+      Demangled("_RNCNvCs1234_7mycrate4mains_0").to_string(),
+      "mycrate::main::{closure#1}"
+    );
+    assert_eq!(
+      demangle("_RNvCs1234_7mycrate3fooINtB2_3BarE"),
+      None,
+      "Unsupported"
+    );
+  }
+
+  #[test]
+  fn itanium() {
+    let demangled = |name| Demangled(name).to_string();
+    assert_eq!(demangled("_Z3foov"), "foo()");
+    assert_eq!(
+      demangled("_ZN9wikipedia7article6formatEv"),
+      "wikipedia::article::format()"
+    );
+    assert_eq!(demangled("_ZN3foo3barERKS_"), "foo::bar(foo const&)");
+    assert_eq!(demangled("_ZNK3Foo3getEi"), "Foo::get(int) const");
+    assert_eq!(demangled("_Z1fPKcRSt6string"), "f(char const*, std::string&)");
+    assert_eq!(demangled("_ZN3FooC1ERKS_"), "Foo::Foo(Foo const&)");
+    assert_eq!(demangled("_ZN3FooD2Ev"), "Foo::~Foo()");
+    assert_eq!(demangled("_ZN2ns5countE"), "ns::count");
+    assert_eq!(demangled("_Z1gPiS_"), "g(int*, int*)");
+    assert_eq!(
+      demangled("_ZNKSs4findEPKcm"),
+      "std::basic_string<char, std::char_traits<char>, std::allocator<char> >::find(char const*, unsigned long) const"
+    );
+    assert_eq!(
+      demangled("_ZNSiD0Ev"),
+      "std::basic_istream<char, std::char_traits<char> >::~basic_istream()"
+    );
+    assert_eq!(demangled("_ZNSt8ios_baseD2Ev"), "std::ios_base::~ios_base()");
+  }
+
+  #[test]
+  fn fallback() {
+    // Not mangled, truncated or unsupported names are displayed as is.
+    for name in [
+      "main",
+      "_Z",
+      "_ZN3foo",
+      "_Z1fIiEvT_",
+      "_ZN99fooE",
+      "_R",
+      "_RNvC",
+      "_Z1fS9_",
+    ] {
+      assert_eq!(Demangled(name).to_string(), name);
+    }
+
+    assert_eq!(
+      demangle(&format!("_R{}", "N".repeat(10_000))),
+      None,
+      "Deep nesting"
+    );
+    assert_eq!(
+      demangle(&format!("_Z1f{}i", "P".repeat(10_000))),
+      None,
+      "Deep nesting"
+    );
+    assert_eq!(demangle("_ZN1\u{e9}E"), None, "Split code point");
+  }
+}
@@ -0,0 +1,282 @@
+use core::fmt::{self, Write};
+
+use crate::alloc_prelude::*;
+
+// ╔╦╗┬┌─┐┌─┐┌─┐┌┬┐┌─┐
+//  ║║││ ┬├┤ └─┐ │ └─┐
+// ═╩╝┴└─┘└─┘└─┘ ┴ └─┘
+
+///
+/// Digest algorithms of `--hash-sections`. CRC-32 is always available (also
+/// the checksum of `.gnu_debuglink`), SHA-1 and SHA-256 require the `sha`
+/// feature. The bytes are hashed in place, without copy.
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+  Crc32,
+  #[cfg(feature = "sha")]
+  Sha1,
+  #[cfg(feature = "sha")]
+  Sha256,
+}
+
+impl DigestAlgorithm {
+  /// SHA-256 when available, CRC-32 otherwise.
+  #[cfg(feature = "sha")]
+  pub const DEFAULT: Self = Self::Sha256;
+  #[cfg(not(feature = "sha"))]
+  pub const DEFAULT: Self = Self::Crc32;
+
+  /// Returns the algorithm with the given name, `None` when unknown or disabled.
+  pub fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "crc32" => Some(Self::Crc32),
+      #[cfg(feature = "sha")]
+      "sha1" => Some(Self::Sha1),
+      #[cfg(feature = "sha")]
+      "sha256" => Some(Self::Sha256),
+      _ => None,
+    }
+  }
+
+  pub fn name(self) -> &'static str {
+    match self {
+      Self::Crc32 => "crc32",
+      #[cfg(feature = "sha")]
+      Self::Sha1 => "sha1",
+      #[cfg(feature = "sha")]
+      Self::Sha256 => "sha256",
+    }
+  }
+
+  /// Returns the digest of the bytes in lowercase hexadecimal.
+  pub fn hex(self, bytes: &[u8]) -> String {
+    let digest = match self {
+      Self::Crc32 => crc32(bytes).to_be_bytes().to_vec(),
+      #[cfg(feature = "sha")]
+      Self::Sha1 => sha1(bytes).to_vec(),
+      #[cfg(feature = "sha")]
+      Self::Sha256 => sha256(bytes).to_vec(),
+    };
+    digest.iter().fold(String::new(), |mut output, byte| {
+      let _ = write!(output, "{:02x}", byte);
+      output
+    })
+  }
+}
+
+impl fmt::Display for DigestAlgorithm {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    formatter.pad(self.name())
+  }
+}
+
+// ╔═╗┬─┐┌─┐
+// ║  ├┬┘│
+// ╚═╝┴└─└─┘
+
+/// Lookup table of the reflected IEEE polynomial (`0xedb88320`).
+const CRC32_TABLE: [u32; 256] = {
+  let mut table = [0; 256];
+  let mut index = 0;
+  while index < 256 {
+    let mut crc = index as u32;
+    let mut bit = 0;
+    while bit < 8 {
+      crc = match crc & 1 {
+        0 => crc >> 1,
+        _ => 0xedb8_8320 ^ (crc >> 1),
+      };
+      bit += 1;
+    }
+    table[index] = crc;
+    index += 1;
+  }
+  table
+};
+
+///
+/// Returns the CRC-32 (IEEE 802.3, as zlib's `crc32()`) of the bytes, the
+/// checksum stored after the file name in `.gnu_debuglink`.
+///
+pub fn crc32(bytes: &[u8]) -> u32 {
+  !bytes.iter().fold(!0, |crc, &byte| {
+    CRC32_TABLE[((crc ^ byte as u32) & 0xff) as usize] ^ (crc >> 8)
+  })
+}
+
+// ╔═╗┬ ┬┌─┐
+// ╚═╗├─┤├─┤
+// ╚═╝┴ ┴┴ ┴
+
+///
+/// Calls `compress` with the 64-byte blocks of the message followed by the
+/// padding and the length in bits (big-endian), as SHA-1 and SHA-256 do.
+/// Only the last partial block is copied.
+///
+#[cfg(feature = "sha")]
+fn for_each_block(bytes: &[u8], mut compress: impl FnMut(&[u8; 64])) {
+  let mut blocks = bytes.chunks_exact(64);
+  for block in &mut blocks {
+    compress(block.try_into().unwrap());
+  }
+
+  let rest = blocks.remainder();
+  let mut tail = [0u8; 128];
+  tail[..rest.len()].copy_from_slice(rest);
+  tail[rest.len()] = 0x80;
+  let length = if rest.len() < 56 { 64 } else { 128 };
+  tail[length - 8..length].copy_from_slice(&(bytes.len() as u64).wrapping_mul(8).to_be_bytes());
+  for block in tail[..length].chunks_exact(64) {
+    compress(block.try_into().unwrap());
+  }
+}
+
+/// Returns the SHA-1 of the bytes (FIPS 180-4), for the tools still using it.
+#[cfg(feature = "sha")]
+pub fn sha1(bytes: &[u8]) -> [u8; 20] {
+  let mut state: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+  for_each_block(bytes, |block| {
+    let mut words = [0u32; 80];
+    for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+      *word = u32::from_be_bytes(bytes.try_into().unwrap());
+    }
+    for index in 16..80 {
+      words[index] =
+        (words[index - 3] ^ words[index - 8] ^ words[index - 14] ^ words[index - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = state;
+    for (index, &word) in words.iter().enumerate() {
+      let (f, k) = match index {
+        0..=19 => ((b & c) | (!b & d), 0x5a827999),
+        20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+        40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+        _ => (b ^ c ^ d, 0xca62c1d6),
+      };
+      let temp = a
+        .rotate_left(5)
+        .wrapping_add(f)
+        .wrapping_add(e)
+        .wrapping_add(k)
+        .wrapping_add(word);
+      (e, d, c, b, a) = (d, c, b.rotate_left(30), a, temp);
+    }
+
+    for (value, delta) in state.iter_mut().zip([a, b, c, d, e]) {
+      *value = value.wrapping_add(delta);
+    }
+  });
+
+  let mut digest = [0; 20];
+  for (bytes, value) in digest.chunks_exact_mut(4).zip(state) {
+    bytes.copy_from_slice(&value.to_be_bytes());
+  }
+  digest
+}
+
+/// Round constants of SHA-256.
+#[cfg(feature = "sha")]
+const SHA256_K: [u32; 64] = [
+  0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5, 0xd807aa98,
+  0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786,
+  0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8,
+  0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13,
+  0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819,
+  0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a,
+  0x5b9cca4f, 0x682e6ff3, 0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
+  0xc67178f2,
+];
+
+/// Returns the SHA-256 of the bytes (FIPS 180-4).
+#[cfg(feature = "sha")]
+pub fn sha256(bytes: &[u8]) -> [u8; 32] {
+  let mut state: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+  ];
+
+  for_each_block(bytes, |block| {
+    let mut words = [0u32; 64];
+    for (word, bytes) in words.iter_mut().zip(block.chunks_exact(4)) {
+      *word = u32::from_be_bytes(bytes.try_into().unwrap());
+    }
+    for index in 16..64 {
+      let (w15, w2) = (words[index - 15], words[index - 2]);
+      let s0 = w15.rotate_right(7) ^ w15.rotate_right(18) ^ (w15 >> 3);
+      let s1 = w2.rotate_right(17) ^ w2.rotate_right(19) ^ (w2 >> 10);
+      words[index] = words[index - 16]
+        .wrapping_add(s0)
+        .wrapping_add(words[index - 7])
+        .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+    for (&k, &word) in SHA256_K.iter().zip(&words) {
+      let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+      let ch = (e & f) ^ (!e & g);
+      let t1 = h
+        .wrapping_add(s1)
+        .wrapping_add(ch)
+        .wrapping_add(k)
+        .wrapping_add(word);
+      let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+      let maj = (a & b) ^ (a & c) ^ (b & c);
+      let t2 = s0.wrapping_add(maj);
+      (h, g, f, e, d, c, b, a) = (g, f, e, d.wrapping_add(t1), c, b, a, t1.wrapping_add(t2));
+    }
+
+    for (value, delta) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+      *value = value.wrapping_add(delta);
+    }
+  });
+
+  let mut digest = [0; 32];
+  for (bytes, value) in digest.chunks_exact_mut(4).zip(state) {
+    bytes.copy_from_slice(&value.to_be_bytes());
+  }
+  digest
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn crc32_checksum() {
+    assert_eq!(crc32(b""), 0);
+    assert_eq!(crc32(b"123456789"), 0xcbf43926);
+    assert_eq!(DigestAlgorithm::Crc32.hex(b"123456789"), "cbf43926");
+  }
+
+  #[test]
+  #[cfg(feature = "sha")]
+  fn sha_digests() {
+    // Two blocks once padded (56 bytes).
+    let long = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+
+    let sha1 = |bytes| DigestAlgorithm::Sha1.hex(bytes);
+    assert_eq!(sha1(b""), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+    assert_eq!(sha1(b"abc"), "a9993e364706816aba3e25717850c26c9cd0d89d");
+    assert_eq!(sha1(long), "84983e441c3bd26ebaae4aa1f95129e5e54670f1");
+
+    let sha256 = |bytes| DigestAlgorithm::Sha256.hex(bytes);
+    assert_eq!(
+      sha256(b""),
+      "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_eq!(
+      sha256(b"abc"),
+      "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+      sha256(long),
+      "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+    );
+    assert_eq!(sha256(&[b'a'; 1000]).len(), 64);
+  }
+}
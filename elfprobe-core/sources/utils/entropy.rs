@@ -0,0 +1,141 @@
+use core::fmt;
+
+use crate::alloc_prelude::*;
+
+// ╔═╗┌┐┌┌┬┐┬─┐┌─┐┌─┐┬ ┬
+// ║╣ │││ │ ├┬┘│ │├─┘└┬┘
+// ╚═╝┘└┘ ┴ ┴└─└─┘┴   ┴
+
+///
+/// Entropy (bits per byte) above which the contents are most likely
+/// compressed or encrypted, machine code and tables stay well below.
+///
+pub const HIGH_ENTROPY: f64 = 7.2;
+
+/// Size of the windows of [`Entropy::peak`].
+pub const ENTROPY_WINDOW: usize = 4096;
+
+///
+/// Entropy of some bytes and, when larger than [`ENTROPY_WINDOW`], the
+/// highest entropy of the windows (a compressed blob in a large section).
+///
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Entropy {
+  pub value: f64,
+  pub peak: Option<f64>,
+}
+
+impl Entropy {
+  pub fn new(bytes: &[u8]) -> Self {
+    let peak = bytes.chunks_exact(ENTROPY_WINDOW).map(entropy).reduce(f64::max);
+    Self {
+      value: entropy(bytes),
+      peak: peak.filter(|_| bytes.len() > ENTROPY_WINDOW),
+    }
+  }
+
+  /// Returns `true` when the entropy or the peak is above [`HIGH_ENTROPY`].
+  pub fn is_high(&self) -> bool {
+    self.value.max(self.peak.unwrap_or(0.0)) > HIGH_ENTROPY
+  }
+}
+
+///
+/// Returns the Shannon entropy of the bytes in bits per byte, from 0.0 (a
+/// single byte value repeated) to 8.0 (all the values equally frequent).
+/// Zero for empty slices.
+///
+pub fn entropy(bytes: &[u8]) -> f64 {
+  let mut counts = [0u64; 256];
+  for &byte in bytes {
+    counts[byte as usize] += 1;
+  }
+
+  let length = bytes.len() as f64;
+  let entropy: f64 = counts
+    .iter()
+    .filter(|&&count| count != 0)
+    .map(|&count| {
+      let probability = count as f64 / length;
+      -probability * log2(probability)
+    })
+    .sum();
+  entropy + 0.0 // Not -0.0 for a single value.
+}
+
+#[cfg(feature = "std")]
+fn log2(value: f64) -> f64 {
+  value.log2()
+}
+
+///
+/// `f64::log2()` is not in `core`: the exponent plus the logarithm of the
+/// mantissa m, in [1, 2), as 2 atanh((m - 1) / (m + 1)) / ln 2. Normal and
+/// positive values only (probabilities).
+///
+#[cfg(any(test, not(feature = "std")))]
+fn log2_series(value: f64) -> f64 {
+  let bits = value.to_bits();
+  let exponent = ((bits >> 52) & 0x7ff) as i64 - 1023;
+  let mantissa = f64::from_bits(bits & ((1 << 52) - 1) | (1023 << 52));
+
+  // z <= 1/3, the terms past z^39 are below the precision.
+  let z = (mantissa - 1.0) / (mantissa + 1.0);
+  let (mut term, mut sum) = (z, 0.0);
+  for n in (1..40).step_by(2) {
+    sum += term / n as f64;
+    term *= z * z;
+  }
+  exponent as f64 + 2.0 * sum / core::f64::consts::LN_2
+}
+
+#[cfg(not(feature = "std"))]
+use log2_series as log2;
+
+/// As `5.42`, or `5.42 (peak 7.96)`.
+impl fmt::Display for Entropy {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.peak {
+      Some(peak) => formatter.pad(&format!("{:.2} (peak {:.2})", self.value, peak)),
+      None => formatter.pad(&format!("{:.2}", self.value)),
+    }
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn log2_without_std() {
+    for value in [1.0, 0.5, 0.75, 1.0 / 3.0, 1e-9, 0.999_999, 1.5, 1024.0] {
+      assert!((log2_series(value) - value.log2()).abs() < 1e-12, "{}", value);
+    }
+  }
+
+  #[test]
+  fn shannon_entropy() {
+    assert_eq!(entropy(&[]), 0.0);
+    assert_eq!(entropy(&[0; 4096]), 0.0);
+    assert_eq!(entropy(&[0x00, 0xff].repeat(64)), 1.0);
+    assert_eq!(entropy(b"abcd"), 2.0);
+
+    let all = (0..=255).collect::<Vec<u8>>().repeat(16);
+    assert_eq!(entropy(&all), 8.0);
+  }
+
+  #[test]
+  fn window_peak() {
+    let mut bytes = vec![0; 3 * ENTROPY_WINDOW];
+    bytes[ENTROPY_WINDOW..2 * ENTROPY_WINDOW].copy_from_slice(&(0..=255).collect::<Vec<u8>>().repeat(16));
+
+    let entropy = Entropy::new(&bytes);
+    assert_eq!(entropy.peak, Some(8.0));
+    assert!(entropy.value < HIGH_ENTROPY && entropy.is_high());
+    assert_eq!(Entropy::new(&bytes[..ENTROPY_WINDOW]).to_string(), "0.00");
+  }
+}
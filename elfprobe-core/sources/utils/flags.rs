@@ -0,0 +1,330 @@
+use core::error::Error;
+use core::fmt::{self, Display};
+
+use crate::alloc_prelude::*;
+
+///
+/// Error of the flags parsing (`str::parse()`), the name (or number) is not
+/// a flag of the set.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownFlag(pub String);
+
+impl Display for UnknownFlag {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(formatter, "unknown flag `{}`", self.0)
+  }
+}
+
+impl Error for UnknownFlag {}
+
+///
+/// Defines a set of bit flags as a module holding one `const` per flag and a
+/// `Flags` wrapper (built with `from()`) displaying the set flags by name,
+/// remaining unknown bits are displayed in hexadecimal:
+///
+/// ```ignore
+/// define_flags! {
+///   /// Section flags.
+///   pub sh_flags: u64 {
+///     SHF_WRITE / W = 0x1 => "Writable",
+///     SHF_ALLOC / A = 0x2 => "Occupies memory during execution",
+///   }
+/// }
+///
+/// assert_eq!(sh_flags::from(0x3).to_string(), "SHF_WRITE | SHF_ALLOC");
+/// assert_eq!(sh_flags::from(0x12).to_string(), "SHF_ALLOC | 0x10");
+/// ```
+///
+/// The wrapper tests and updates the set (`contains()`, `insert()`,
+/// `remove()`) and iterates over the set flags as [`Constant`](super::Constant)s:
+///
+/// ```ignore
+/// let mut flags = sh_flags::from(0x12);
+/// flags.remove(SHF_ALLOC);
+/// assert_eq!(flags.bits(), 0x10);
+/// assert_eq!(sh_flags::from(0x3).iter().count(), 2);
+/// ```
+///
+/// Sets are parsed back from their names (case-insensitive) separated by
+/// `|`, `+` or spaces, unknown bits being given as numbers:
+///
+/// ```ignore
+/// assert_eq!("SHF_ALLOC|shf_write".parse(), Ok(sh_flags::from(0x3)));
+/// assert_eq!("SHF_ALLOC + 0x10".parse(), Ok(sh_flags::from(0x12)));
+/// ```
+///
+/// A flag may be given a short alias (`/ W`), e.g. for compact columns, see
+/// `Flags::short()` and the alternate form (`{:#}`) displaying them:
+///
+/// ```ignore
+/// assert_eq!(sh_flags::from(0x3).short(), "WA");
+/// assert_eq!(format!("{:#4}|", sh_flags::from(0x3)), "WA  |");
+/// ```
+///
+macro_rules! define_flags {
+  (
+    $(#[$meta: meta])*
+    $visibility: vis $table: ident: $type: ty { $($body: tt)* }
+  ) => {
+    $(#[$meta])*
+    #[allow(non_upper_case_globals, unused)]
+    $visibility mod $table {
+      use super::*;
+      $crate::utils::define_flags!(@entries $type, [], $($body)*);
+    }
+  };
+
+  (
+    @entries $type: ty, [$($entries: tt)*],
+    $(#[$meta: meta])* $name: ident $(/ $short: ident)? = $value: expr => $meaning: literal
+    $(, $($rest: tt)*)?
+  ) => {
+    $(#[$meta])*
+    #[doc = $meaning]
+    pub const $name: $type = $value;
+    $crate::utils::define_flags!(
+      @entries $type, [$($entries)* ($name, $meaning, [$($short)?])], $($($rest)*)?
+    );
+  };
+
+  (@entries $type: ty, [$(($name: ident, $meaning: literal, [$($short: ident)?]))*], ) => {
+    /// Name, value and meaning of each flag (in declaration order).
+    pub const FLAGS: &[(&str, $type, &str)] = &[$((stringify!($name), $name, $meaning)),*];
+
+    /// Short alias and value of the flags having one (in declaration order).
+    pub const SHORT: &[(&str, $type)] = &[$($((stringify!($short), $name),)?)*];
+
+    /// Set of flags.
+    #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+    pub struct Flags(pub $type);
+
+    /// Returns the set of flags matching the given value.
+    pub fn from(value: $type) -> Flags {
+      Flags(value)
+    }
+
+    impl Flags {
+      /// Returns the raw value.
+      pub fn bits(&self) -> $type {
+        self.0
+      }
+
+      /// Returns whether all the given flags are set.
+      pub fn contains(&self, flags: $type) -> bool {
+        self.0 & flags == flags
+      }
+
+      /// Sets the given flags.
+      pub fn insert(&mut self, flags: $type) {
+        self.0 |= flags;
+      }
+
+      /// Clears the given flags.
+      pub fn remove(&mut self, flags: $type) {
+        self.0 &= !flags;
+      }
+
+      ///
+      /// Returns the set flags in declaration order, followed by the
+      /// remaining unknown bits (as a single unnamed constant), as displayed.
+      ///
+      pub fn iter(&self) -> impl Iterator<Item = $crate::utils::Constant<$type>> {
+        let value = self.0;
+        let known = FLAGS
+          .iter()
+          .filter(move |&&(_, flag, _)| flag != 0 && value & flag == flag)
+          .map(|&(name, flag, meaning)| $crate::utils::Constant::new(flag, flag, name, meaning));
+        let unknown = FLAGS
+          .iter()
+          .filter(|&&(_, flag, _)| value & flag == flag)
+          .fold(value, |unknown, &(_, flag, _)| unknown & !flag);
+        known.chain((unknown != 0).then(|| $crate::utils::Constant::unknown(unknown)))
+      }
+
+      ///
+      /// Returns the concatenated short aliases (as the readelf `Flg` column).
+      /// A multi-bit flag (e.g. a mask) is shown when any of its bits is set
+      /// and not already covered by a previous flag, remaining bits are shown
+      /// as `x`.
+      ///
+      pub fn short(&self) -> ::alloc::string::String {
+        let mut short = ::alloc::string::String::new();
+        let mut remaining = self.0;
+        for &(alias, flag) in SHORT {
+          if remaining & flag != 0 {
+            short.push_str(alias);
+            remaining &= !flag;
+          }
+        }
+
+        if remaining != 0 {
+          short.push('x');
+        }
+
+        short
+      }
+    }
+
+    impl ::core::str::FromStr for Flags {
+      type Err = $crate::utils::UnknownFlag;
+
+      fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut flags = Flags::default();
+        for token in string.split(['|', '+']).flat_map(str::split_whitespace) {
+          let flag = FLAGS
+            .iter()
+            .find(|(name, _, _)| name.eq_ignore_ascii_case(token))
+            .map(|&(_, flag, _)| flag);
+          let number = match token.strip_prefix("0x").or_else(|| token.strip_prefix("0X")) {
+            Some(digits) => <$type>::from_str_radix(digits, 16).ok(),
+            None => token.parse::<$type>().ok(),
+          };
+
+          match flag.or(number) {
+            Some(flag) => flags.insert(flag),
+            None => return Err($crate::utils::UnknownFlag(::alloc::string::String::from(token))),
+          }
+        }
+
+        Ok(flags)
+      }
+    }
+
+    impl ::core::fmt::Display for Flags {
+      fn fmt(&self, formatter: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        if formatter.alternate() {
+          return formatter.pad(&self.short());
+        }
+
+        use ::alloc::string::ToString;
+
+        let mut names = self.iter().map(|flag| flag.to_string()).collect::<::alloc::vec::Vec<_>>();
+        if names.is_empty() {
+          names.push(::alloc::format!("{:#x}", self.0));
+        }
+
+        formatter.pad(&names.join(" | "))
+      }
+    }
+  };
+}
+
+pub(crate) use define_flags;
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::UnknownFlag;
+  use crate::utils::Constant;
+
+  define_flags! {
+    /// Test flags.
+    test_flags: u32 {
+      TEST_READ / R = 0x4 => "Readable",
+      TEST_WRITE / W = 0x2 => "Writable",
+      /// Documented flag.
+      TEST_EXEC = 0x1 => "Executable",
+      TEST_MASK / M = 0xf0 => "Mask",
+    }
+  }
+
+  #[test]
+  fn flags() {
+    assert_eq!(test_flags::TEST_WRITE, 2);
+    assert_eq!(test_flags::FLAGS[2], ("TEST_EXEC", 1, "Executable"));
+    assert_eq!(test_flags::from(0x5).to_string(), "TEST_READ | TEST_EXEC");
+    assert_eq!(test_flags::from(0x7).0, 7);
+    assert_eq!(format!("{:<12}|", test_flags::from(0x2)), "TEST_WRITE  |");
+  }
+
+  #[test]
+  fn short() {
+    assert_eq!(test_flags::SHORT, &[("R", 0x4), ("W", 0x2), ("M", 0xf0)]);
+    assert_eq!(test_flags::from(0x6).short(), "RW");
+    assert_eq!(test_flags::from(0x1).short(), "x", "No alias");
+    assert_eq!(test_flags::from(0x30).short(), "M", "Mask");
+    assert_eq!(test_flags::from(0).short(), "");
+    assert_eq!(format!("{:#}", test_flags::from(0x7)), "RWx");
+    assert_eq!(format!("{:#4}|", test_flags::from(0x6)), "RW  |");
+  }
+
+  #[test]
+  fn set() {
+    let mut flags = test_flags::from(0x5);
+    assert_eq!(flags.bits(), 5);
+    assert!(flags.contains(test_flags::TEST_READ | test_flags::TEST_EXEC));
+    assert!(!flags.contains(test_flags::TEST_WRITE));
+
+    flags.insert(test_flags::TEST_WRITE);
+    flags.remove(test_flags::TEST_EXEC);
+    assert_eq!(flags, test_flags::from(0x6));
+  }
+
+  #[test]
+  fn iter() {
+    let flags = test_flags::from(0x1f5).iter().collect::<Vec<_>>();
+    assert_eq!(
+      flags,
+      [
+        Constant::new(0x4, 0x4, "TEST_READ", "Readable"),
+        Constant::new(0x1, 0x1, "TEST_EXEC", "Executable"),
+        Constant::new(0xf0, 0xf0, "TEST_MASK", "Mask"),
+        Constant::unknown(0x100),
+      ]
+    );
+    assert_eq!(
+      test_flags::from(0x30).iter().collect::<Vec<_>>(),
+      [Constant::unknown(0x30)]
+    );
+    assert_eq!(test_flags::from(0).iter().count(), 0);
+  }
+
+  #[test]
+  fn from_str() {
+    assert_eq!("TEST_READ|TEST_EXEC".parse(), Ok(test_flags::from(0x5)));
+    assert_eq!("test_read + Test_Write".parse(), Ok(test_flags::from(0x6)));
+    assert_eq!("TEST_EXEC | 0x100 | 16".parse(), Ok(test_flags::from(0x111)));
+    assert_eq!("0x0".parse(), Ok(test_flags::from(0)));
+    assert_eq!(
+      "TEST_READ | TEST_SEEK".parse::<test_flags::Flags>(),
+      Err(UnknownFlag(String::from("TEST_SEEK")))
+    );
+    assert_eq!(
+      UnknownFlag(String::from("0xZZ")).to_string(),
+      "unknown flag `0xZZ`"
+    );
+  }
+
+  #[test]
+  fn roundtrip() {
+    let values = test_flags::FLAGS
+      .iter()
+      .map(|&(_, flag, _)| flag)
+      .chain([0, 0x7, 0x1f5]);
+    for value in values {
+      let flags = test_flags::from(value);
+      assert_eq!(flags.to_string().parse(), Ok(flags));
+    }
+
+    for &(_, flag, _) in crate::elf::sh_flags::FLAGS {
+      let flags = crate::elf::sh_flags::from(flag | 0x1000_0000_0000);
+      assert_eq!(flags.to_string().parse(), Ok(flags));
+    }
+
+    for &(_, flag, _) in crate::elf::p_flags::FLAGS {
+      let flags = crate::elf::p_flags::from(flag);
+      assert_eq!(flags.to_string().parse(), Ok(flags));
+    }
+  }
+
+  #[test]
+  fn unknown() {
+    assert_eq!(test_flags::from(0).to_string(), "0x0");
+    assert_eq!(test_flags::from(0x12).to_string(), "TEST_WRITE | 0x10");
+    assert_eq!(test_flags::from(0x80000000).to_string(), "0x80000000");
+  }
+}
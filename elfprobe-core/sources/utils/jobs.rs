@@ -0,0 +1,121 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Mutex, PoisonError};
+use std::thread;
+
+// ╦┌─┐┌┐ ┌─┐
+// ║│ │├┴┐└─┐
+// ╚╝└─┘└─┘└─┘
+
+///
+/// Runs `work` on the items with `jobs` threads, and hands the results to
+/// `done` on the calling thread in the order of the items, as they come in.
+///
+/// A panic of `work` is caught and handed to `done` as its message, the
+/// thread goes on with the next item. The items are pulled one at a time, so
+/// that a directory walk proceeds along with the work.
+///
+pub fn for_each_ordered<Item, Output, Items, Work, Done>(
+  items: Items,
+  jobs: usize,
+  work: Work,
+  mut done: Done,
+) where
+  Item: Send,
+  Output: Send,
+  Items: Iterator<Item = Item> + Send,
+  Work: Fn(&Item) -> Output + Sync,
+  Done: FnMut(Item, Result<Output, String>),
+{
+  let items = Mutex::new(items.enumerate());
+  let (sender, receiver) = mpsc::channel();
+
+  thread::scope(|scope| {
+    for _ in 0..jobs.max(1) {
+      let (items, work, sender) = (&items, &work, sender.clone());
+      scope.spawn(move || loop {
+        // A panic of the iterator poisons the lock, not the other items.
+        let next = items.lock().unwrap_or_else(PoisonError::into_inner).next();
+        let Some((index, item)) = next else {
+          break;
+        };
+        let output = panic::catch_unwind(AssertUnwindSafe(|| work(&item))).map_err(message);
+        if sender.send((index, item, output)).is_err() {
+          break;
+        }
+      });
+    }
+    drop(sender);
+
+    // Results ahead of their turn wait for the previous ones.
+    let mut pending = BTreeMap::new();
+    let mut next = 0;
+    for (index, item, output) in receiver {
+      pending.insert(index, (item, output));
+      while let Some((item, output)) = pending.remove(&next) {
+        done(item, output);
+        next += 1;
+      }
+    }
+
+    // Gaps are left by the items lost with a thread, the others are still due.
+    pending
+      .into_values()
+      .for_each(|(item, output)| done(item, output));
+  });
+}
+
+/// Returns the message of a panic, `&str` or `String` payloads.
+fn message(payload: Box<dyn Any + Send>) -> String {
+  match payload.downcast::<String>() {
+    Ok(message) => *message,
+    Err(payload) => match payload.downcast::<&str>() {
+      Ok(message) => String::from(*message),
+      Err(_) => String::from("panic"),
+    },
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use std::time::Duration;
+
+  #[test]
+  fn ordered_results() {
+    let mut results = Vec::new();
+    let work = |&item: &u64| {
+      // The first items finish last.
+      thread::sleep(Duration::from_millis(20 - item));
+      item * 2
+    };
+    for_each_ordered(0..20, 4, work, |item, output| {
+      results.push((item, output.unwrap()))
+    });
+
+    let expected: Vec<_> = (0..20).map(|item| (item, item * 2)).collect();
+    assert_eq!(results, expected);
+  }
+
+  #[test]
+  fn caught_panics() {
+    let mut results = Vec::new();
+    let work = |&item: &u32| match item {
+      3 => panic!("item {}", item),
+      5 => std::panic::panic_any(5),
+      _ => item,
+    };
+    for_each_ordered(0..8, 3, work, |item, output| results.push((item, output)));
+
+    assert_eq!(results.len(), 8, "No item lost");
+    assert_eq!(results[2], (2, Ok(2)));
+    assert_eq!(results[3], (3, Err(String::from("item 3"))));
+    assert_eq!(results[5], (5, Err(String::from("panic"))));
+    assert_eq!(results[7], (7, Ok(7)));
+  }
+}
@@ -0,0 +1,112 @@
+// ╦  ╔═╗╔╗ ┌─┐
+// ║  ║╣ ╠╩╗ ├┤
+// ╩═╝╚═╝╚═╝└─┘ 128
+
+///
+/// Reads an unsigned LEB128 (Little Endian Base 128) integer at the given
+/// offset and moves the offset past it. `None` is returned when the encoding
+/// is truncated or overflows 64 bits (the offset is then left unchanged).
+///
+/// Used by build attributes (ARM, RISC-V) and DWARF.
+///
+pub fn read_uleb128(bytes: &[u8], offset: &mut usize) -> Option<u64> {
+  let mut value: u64 = 0;
+
+  for (index, &byte) in bytes.get(*offset..)?.iter().enumerate() {
+    let shift = 7 * index as u32;
+    let bits = (byte & 0x7f) as u64;
+
+    if shift >= 64 || (shift > 0 && bits >> (64 - shift) != 0) {
+      return None; // Overflow.
+    }
+
+    value |= bits << shift;
+    if byte & 0x80 == 0 {
+      *offset += index + 1;
+      return Some(value);
+    }
+  }
+
+  None // Truncated.
+}
+
+///
+/// Reads a signed LEB128 integer at the given offset and moves the offset
+/// past it, see [`read_uleb128()`].
+///
+pub fn read_sleb128(bytes: &[u8], offset: &mut usize) -> Option<i64> {
+  let mut value: i64 = 0;
+
+  for (index, &byte) in bytes.get(*offset..)?.iter().enumerate() {
+    let shift = 7 * index as u32;
+    if shift >= 64 {
+      return None; // Overflow.
+    }
+
+    value |= ((byte & 0x7f) as i64) << shift;
+    if byte & 0x80 == 0 {
+      if shift + 7 < 64 && byte & 0x40 != 0 {
+        value |= -1 << (shift + 7); // Sign extension.
+      }
+
+      *offset += index + 1;
+      return Some(value);
+    }
+  }
+
+  None // Truncated.
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn read(bytes: &[u8]) -> Option<(u64, usize)> {
+    let mut offset = 0;
+    read_uleb128(bytes, &mut offset).map(|value| (value, offset))
+  }
+
+  #[test]
+  fn uleb128() {
+    assert_eq!(read(&[0x00]), Some((0, 1)));
+    assert_eq!(read(&[0x7f, 0xff]), Some((127, 1)));
+    assert_eq!(read(&[0x80, 0x01]), Some((128, 2)));
+    assert_eq!(read(&[0xe5, 0x8e, 0x26]), Some((624485, 3)));
+    assert_eq!(read(&[0x80, 0x80, 0x00]), Some((0, 3)), "Redundant padding");
+
+    let max = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+    assert_eq!(read(&max), Some((u64::MAX, 10)));
+  }
+
+  #[test]
+  fn sleb128() {
+    let read = |bytes: &[u8]| {
+      let mut offset = 0;
+      read_sleb128(bytes, &mut offset).map(|value| (value, offset))
+    };
+
+    assert_eq!(read(&[0x02]), Some((2, 1)));
+    assert_eq!(read(&[0x7e]), Some((-2, 1)));
+    assert_eq!(read(&[0xff, 0x00]), Some((127, 2)));
+    assert_eq!(read(&[0x80, 0x7f]), Some((-128, 2)));
+    assert_eq!(read(&[0xc0, 0xbb, 0x78]), Some((-123456, 3)));
+    assert_eq!(read(&[0x80]), None, "Truncated");
+  }
+
+  #[test]
+  fn uleb128_errors() {
+    assert_eq!(read(&[]), None);
+    assert_eq!(read(&[0x80, 0x80]), None, "Truncated");
+
+    let overflow = [0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x02];
+    assert_eq!(read(&overflow), None, "Overflow");
+
+    let mut offset = 42;
+    assert_eq!(read_uleb128(&[0x00], &mut offset), None, "Out of bounds");
+    assert_eq!(offset, 42);
+  }
+}
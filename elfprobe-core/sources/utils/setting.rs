@@ -0,0 +1,100 @@
+#[cfg(not(feature = "std"))]
+use core::cell::UnsafeCell;
+#[cfg(not(feature = "std"))]
+use core::sync::atomic::{AtomicBool, Ordering};
+
+// ╔═╗┌─┐┌┬┐┌┬┐┬┌┐┌┌─┐┌─┐
+// ╚═╗├┤  │  │ │││││ ┬└─┐
+// ╚═╝└─┘ ┴  ┴ ┴┘└┘└─┘└─┘
+
+///
+/// Declares the settings of the output (colors, table format...), read and
+/// written with `NAME.with(|setting| setting.get())`, `set()` or `replace()`.
+///
+/// They are thread-local `Cell`s with the `std` feature. Without it there is
+/// no thread-local storage, they are [`Setting`]s shared by the threads.
+///
+#[cfg(feature = "std")]
+macro_rules! settings {
+  ($(static $name:ident: $type:ty = $value:expr;)*) => {
+    std::thread_local! {
+      $(static $name: core::cell::Cell<$type> = const { core::cell::Cell::new($value) };)*
+    }
+  };
+}
+
+#[cfg(not(feature = "std"))]
+macro_rules! settings {
+  ($(static $name:ident: $type:ty = $value:expr;)*) => {
+    $(static $name: $crate::utils::Setting<$type> = $crate::utils::Setting::new($value);)*
+  };
+}
+
+pub(crate) use settings;
+
+///
+/// Calls the function when dropped, to restore a setting changed for a scope
+/// even when the scope panics (e.g. caught by a test or a worker thread):
+/// `let _restore = Restore(|| Style::set_enabled(previous));`.
+///
+pub(crate) struct Restore<Function: FnMut()>(pub Function);
+
+impl<Function: FnMut()> Drop for Restore<Function> {
+  fn drop(&mut self) {
+    (self.0)()
+  }
+}
+
+///
+/// Setting shared by the threads without `std`, see [`settings!`]. The value
+/// is behind a spin lock, held for a copy.
+///
+#[cfg(not(feature = "std"))]
+pub struct Setting<Type> {
+  locked: AtomicBool,
+  value: UnsafeCell<Type>,
+}
+
+// SAFETY: The value is only reached with the lock held.
+#[cfg(not(feature = "std"))]
+unsafe impl<Type: Send> Sync for Setting<Type> {}
+
+#[cfg(not(feature = "std"))]
+impl<Type: Copy> Setting<Type> {
+  pub const fn new(value: Type) -> Self {
+    Self {
+      locked: AtomicBool::new(false),
+      value: UnsafeCell::new(value),
+    }
+  }
+
+  /// Same as `LocalKey::with()`, the setting is global.
+  pub fn with<Output>(&'static self, function: impl FnOnce(&Self) -> Output) -> Output {
+    function(self)
+  }
+
+  pub fn get(&self) -> Type {
+    self.locked(|value| *value)
+  }
+
+  pub fn set(&self, value: Type) {
+    self.replace(value);
+  }
+
+  pub fn replace(&self, value: Type) -> Type {
+    self.locked(|current| core::mem::replace(current, value))
+  }
+
+  fn locked<Output>(&self, function: impl FnOnce(&mut Type) -> Output) -> Output {
+    while self
+      .locked
+      .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+      .is_err()
+    {
+      core::hint::spin_loop();
+    }
+    let output = function(unsafe { &mut *self.value.get() });
+    self.locked.store(false, Ordering::Release);
+    output
+  }
+}
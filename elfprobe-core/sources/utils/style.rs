@@ -0,0 +1,145 @@
+use core::fmt::{self, Display};
+
+use super::{settings, Restore};
+use crate::alloc_prelude::*;
+
+// ╔═╗┌┬┐┬ ┬┬  ┌─┐
+// ╚═╗ │ └┬┘│  ├┤
+// ╚═╝ ┴  ┴ ┴─┘└─┘
+
+settings! {
+  static ENABLED: bool = false;
+}
+
+///
+/// ANSI colors of the terminal output, see [`Styled`].
+///
+/// Colors are a thread-local setting, disabled by default: `Display` impls
+/// may style their output and stay color-free unless the binary enables the
+/// colors (terminal detection, `--color=always`).
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Style {
+  Bold,
+  Dim,
+  Red,
+  Green,
+  Yellow,
+  Blue,
+  Magenta,
+  Cyan,
+}
+
+impl Style {
+  /// Returns `true` when the colors are enabled on the current thread.
+  pub fn enabled() -> bool {
+    ENABLED.with(|current| current.get())
+  }
+
+  /// Enables or disables the colors on the current thread.
+  pub fn set_enabled(enabled: bool) {
+    ENABLED.with(|current| current.set(enabled));
+  }
+
+  /// Runs `function` with the colors enabled or not then restores the setting.
+  pub fn with<Output>(enabled: bool, function: impl FnOnce() -> Output) -> Output {
+    let previous = ENABLED.with(|current| current.replace(enabled));
+    let _restore = Restore(|| Self::set_enabled(previous));
+    function()
+  }
+
+  /// Returns the value displayed with the style.
+  pub fn paint<Type>(self, value: Type) -> Styled<Type> {
+    Styled(self, value)
+  }
+
+  /// Select Graphic Rendition parameter of the style.
+  fn code(self) -> u8 {
+    match self {
+      Self::Bold => 1,
+      Self::Dim => 2,
+      Self::Red => 31,
+      Self::Green => 32,
+      Self::Yellow => 33,
+      Self::Blue => 34,
+      Self::Magenta => 35,
+      Self::Cyan => 36,
+    }
+  }
+}
+
+///
+/// Displays a value with a style when the colors are enabled, as is
+/// otherwise. Padding applies to the value, not to the escape sequences.
+///
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Styled<Type>(pub Style, pub Type);
+
+impl<Type: Display> Display for Styled<Type> {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if !Style::enabled() {
+      return self.1.fmt(formatter);
+    }
+
+    write!(formatter, "\x1b[{}m", self.0.code())?;
+    self.1.fmt(formatter)?;
+    write!(formatter, "\x1b[0m")
+  }
+}
+
+/// Returns the text without the escape sequences (`ESC [ ... m`).
+pub fn unstyled(text: &str) -> String {
+  let mut output = String::with_capacity(text.len());
+  let mut chars = text.chars();
+  while let Some(char) = chars.next() {
+    match char {
+      // Parameters up to the final byte (a letter).
+      '\x1b' => _ = chars.by_ref().find(char::is_ascii_alphabetic),
+      _ => output.push(char),
+    }
+  }
+  output
+}
+
+/// Returns the number of characters once displayed (escape sequences excluded).
+pub fn visible_width(text: &str) -> usize {
+  match text.contains('\x1b') {
+    true => unstyled(text).chars().count(),
+    false => text.chars().count(),
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn styled() {
+    let styled = Style::Green.paint("GLOBAL");
+    assert_eq!(format!("[{:<8}]", styled), "[GLOBAL  ]", "Disabled by default");
+
+    Style::with(true, || {
+      assert_eq!(format!("{}", styled), "\x1b[32mGLOBAL\x1b[0m");
+      assert_eq!(format!("[{:>8}]", styled), "[\x1b[32m  GLOBAL\x1b[0m]");
+    });
+    assert!(!Style::enabled(), "Restored");
+
+    let result = std::panic::catch_unwind(|| Style::with(true, || panic!("Unwound")));
+    assert!(result.is_err());
+    assert!(!Style::enabled(), "Restored when the function panics");
+  }
+
+  #[test]
+  fn width() {
+    let text = Style::with(true, || {
+      format!("{} {}", Style::Red.paint("é"), Style::Bold.paint(42))
+    });
+    assert_eq!(unstyled(&text), "é 42");
+    assert_eq!(visible_width(&text), 4);
+    assert_eq!(visible_width("plain"), 5);
+  }
+}
@@ -0,0 +1,784 @@
+use core::fmt::{self, Display, Write};
+
+use super::style::{unstyled, visible_width};
+use super::{settings, Restore};
+use crate::alloc_prelude::*;
+
+// ╔╦╗┌─┐┌┐ ┬  ┌─┐
+//  ║ ├─┤├┴┐│  ├┤
+//  ╩ ┴ ┴└─┘┴─┘└─┘
+
+///
+/// Collects rows of cells (anything [`Display`]) and writes them as a text
+/// table where each column is padded to its widest cell. The last column is
+/// never padded so that lines have no trailing whitespace (but when it is
+/// right-aligned).
+///
+/// The rows may instead be written as CSV or TSV, see [`TableFormat`].
+///
+/// See [`display_table!`] for a more concise syntax.
+///
+#[derive(Debug, Clone)]
+pub struct TableBuilder {
+  rows: Vec<Vec<String>>,
+  alignments: Vec<Alignment>,
+  limits: Vec<Option<(usize, Overflow)>>,
+  format: TableFormat,
+  width: Option<usize>,
+  shrink: Vec<usize>,
+  row_limit: Option<usize>,
+  /// Rows past the limit, counted but not kept.
+  omitted: usize,
+}
+
+impl Default for TableBuilder {
+  fn default() -> Self {
+    Self {
+      rows: Vec::new(),
+      alignments: Vec::new(),
+      limits: Vec::new(),
+      format: TableFormat::current(),
+      width: TableBuilder::line_width(),
+      shrink: Vec::new(),
+      row_limit: TableBuilder::row_limit(),
+      omitted: 0,
+    }
+  }
+}
+
+settings! {
+  static FORMAT: TableFormat = TableFormat::Text;
+  static LINE_WIDTH: Option<usize> = None;
+  static ROW_LIMIT: Option<usize> = None;
+}
+
+///
+/// How tables are written, per thread (padded text by default).
+///
+/// The format is a thread-local setting picked up by the new builders, so
+/// that the tables built by [`display_table!`] (e.g. in `Display` impls) are
+/// emitted as CSV or TSV without changing their call sites.
+///
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum TableFormat {
+  /// Columns padded to their widest cell.
+  #[default]
+  Text,
+  /// Comma-separated values, quoted as RFC 4180 (and when surrounded by spaces).
+  Csv,
+  /// Tab-separated values, trimmed, tabs and line breaks in cells become spaces.
+  Tsv,
+}
+
+impl TableFormat {
+  /// Returns the format of the current thread.
+  pub fn current() -> Self {
+    FORMAT.with(|current| current.get())
+  }
+
+  /// Selects the format of the current thread.
+  pub fn set_current(format: Self) {
+    FORMAT.with(|current| current.set(format));
+  }
+
+  /// Runs `function` with the given format then restores the previous one.
+  pub fn with<Output>(format: Self, function: impl FnOnce() -> Output) -> Output {
+    let previous = FORMAT.with(|current| current.replace(format));
+    let _restore = Restore(|| Self::set_current(previous));
+    function()
+  }
+
+  /// Returns the cell as a field (without colors), see the variants.
+  fn field(self, cell: &str) -> String {
+    let cell = unstyled(cell);
+    let quoted = cell.contains([',', '"', '\n', '\r']) || cell.trim() != cell;
+    match self {
+      Self::Csv if quoted => format!("\"{}\"", cell.replace('"', "\"\"")),
+      Self::Tsv => cell.trim().replace(['\t', '\n', '\r'], " "),
+      _ => cell,
+    }
+  }
+}
+
+/// Alignment of the cells of a column, see [`TableBuilder::align()`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Alignment {
+  #[default]
+  Left,
+  /// Typically numeric columns (sizes, offsets, indices).
+  Right,
+}
+
+/// What happens to the cells wider than their column, see [`TableBuilder::max_width()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Overflow {
+  /// The cell is cut and ends with `…`.
+  Truncate,
+  /// The cell continues on the next lines, at the start of the column.
+  Wrap,
+}
+
+impl TableBuilder {
+  /// Separator between two columns.
+  const SEPARATOR: &'static str = "  ";
+
+  /// Width under which [`shrink()`](Self::shrink()) does not cut a column.
+  const MIN_WIDTH: usize = 8;
+
+  /// Returns the line width of the current thread (unlimited by default).
+  pub fn line_width() -> Option<usize> {
+    LINE_WIDTH.with(|current| current.get())
+  }
+
+  ///
+  /// Sets the line width of the current thread, picked up by the new builders
+  /// as [`TableFormat`] (typically the width of the terminal).
+  ///
+  pub fn set_line_width(width: Option<usize>) {
+    LINE_WIDTH.with(|current| current.set(width));
+  }
+
+  /// Returns the row limit of the current thread (unlimited by default).
+  pub fn row_limit() -> Option<usize> {
+    ROW_LIMIT.with(|current| current.get())
+  }
+
+  ///
+  /// Sets the row limit of the current thread, picked up by the new builders
+  /// as [`TableFormat`] (interactive output, so that a table of millions of
+  /// symbols does not hold the terminal for minutes).
+  ///
+  pub fn set_row_limit(limit: Option<usize>) {
+    ROW_LIMIT.with(|current| current.set(limit));
+  }
+
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  ///
+  /// Appends a row, rows may have different lengths. Past the row limit, the
+  /// row is only counted: the cells are not even formatted. CSV and TSV are
+  /// read by tools and never limited, a notice would be a corrupted row.
+  ///
+  pub fn row(&mut self, cells: &[&dyn Display]) -> &mut Self {
+    // The first row is the header, not counted.
+    let limit = self.row_limit.filter(|_| self.format == TableFormat::Text);
+    if limit.is_some_and(|limit| self.rows.len() > limit) {
+      self.omitted += 1;
+      return self;
+    }
+
+    self
+      .rows
+      .push(cells.iter().map(|cell| cell.to_string()).collect());
+    self
+  }
+
+  /// Sets the alignment of the columns, the missing ones are left-aligned.
+  pub fn align(&mut self, alignments: &[Alignment]) -> &mut Self {
+    self.alignments = alignments.to_vec();
+    self
+  }
+
+  ///
+  /// Limits the width of a column (in characters), e.g. so that a long
+  /// symbol name does not widen the whole table. The widest cells are either
+  /// truncated or wrapped.
+  ///
+  pub fn max_width(&mut self, column: usize, width: usize, overflow: Overflow) -> &mut Self {
+    if self.limits.len() <= column {
+      self.limits.resize(column + 1, None);
+    }
+
+    self.limits[column] = Some((width.max(1), overflow));
+    self
+  }
+
+  ///
+  /// Returns the lines of a cell once limited to the width of its column.
+  /// Colored cells lose their colors when cut.
+  ///
+  fn cell_lines(limits: &[Option<(usize, Overflow)>], column: usize, cell: &str) -> Vec<String> {
+    let length = visible_width(cell);
+    match limits.get(column).copied().flatten() {
+      Some((width, Overflow::Truncate)) if length > width => {
+        vec![unstyled(cell).chars().take(width - 1).chain(['…']).collect()]
+      }
+      Some((width, Overflow::Wrap)) if length > width => {
+        let chars = unstyled(cell).chars().collect::<Vec<_>>();
+        chars.chunks(width).map(|chunk| chunk.iter().collect()).collect()
+      }
+      _ => vec![String::from(cell)],
+    }
+  }
+
+  /// Sets the line width, the line width of the thread by default.
+  pub fn fit(&mut self, width: Option<usize>) -> &mut Self {
+    self.width = width;
+    self
+  }
+
+  ///
+  /// Lists the columns which may be truncated when the lines are wider than
+  /// the line width, the first ones first (names, never numbers). A column is
+  /// not cut below 8 characters, the lines may therefore remain too wide.
+  ///
+  pub fn shrink(&mut self, columns: &[usize]) -> &mut Self {
+    self.shrink = columns.to_vec();
+    self
+  }
+
+  /// Returns the limits of the columns once shrunk to fit the line width.
+  fn fitted_limits(&self, widths: &[usize]) -> Vec<Option<(usize, Overflow)>> {
+    let mut limits = self.limits.clone();
+    let Some(width) = self.width else {
+      return limits;
+    };
+
+    let total = widths.iter().sum::<usize>() + Self::SEPARATOR.len() * widths.len().saturating_sub(1);
+    let mut excess = total.saturating_sub(width);
+    for &column in &self.shrink {
+      let Some(&current) = widths.get(column).filter(|_| excess != 0) else {
+        continue;
+      };
+
+      let target = current.saturating_sub(excess).max(Self::MIN_WIDTH.min(current));
+      if target < current {
+        if limits.len() <= column {
+          limits.resize(column + 1, None);
+        }
+        limits[column] = Some((target, Overflow::Truncate));
+        excess -= current - target;
+      }
+    }
+
+    limits
+  }
+
+  ///
+  /// Sets the number of rows written after the first one (the header), the
+  /// row limit of the thread by default. The rows past the limit are replaced
+  /// by a notice with their count. The limit applies to the rows as they are
+  /// appended, that is once sorted and filtered by the caller, and only to
+  /// [`TableFormat::Text`].
+  ///
+  pub fn limit_rows(&mut self, limit: Option<usize>) -> &mut Self {
+    self.row_limit = limit;
+    self
+  }
+
+  /// Sets the format of the table, the format of the thread by default.
+  pub fn format(&mut self, format: TableFormat) -> &mut Self {
+    self.format = format;
+    self
+  }
+
+  ///
+  /// Writes the table (one line per row, more when cells are wrapped). CSV
+  /// and TSV fields are neither padded, limited nor aligned, see [`TableFormat`].
+  /// The rows past the row limit are counted on a last line of the text.
+  ///
+  pub fn finish(&self, output: &mut dyn Write) -> fmt::Result {
+    let separator = match self.format {
+      TableFormat::Text => None,
+      TableFormat::Csv => Some(","),
+      TableFormat::Tsv => Some("\t"),
+    };
+
+    match separator {
+      None => self.finish_text(output)?,
+      Some(separator) => {
+        for row in &self.rows {
+          let fields = row.iter().map(|cell| self.format.field(cell)).collect::<Vec<_>>();
+          writeln!(output, "{}", fields.join(separator))?;
+        }
+      }
+    }
+
+    match self.omitted {
+      0 => Ok(()),
+      1 => writeln!(output, "… 1 more row (use --no-limit)"),
+      omitted => writeln!(output, "… {} more rows (use --no-limit)", Grouped(omitted)),
+    }
+  }
+
+  ///
+  /// Returns the rows once the cells are limited, wrapped rows being split
+  /// into several rows of the same length, and the widths of the columns.
+  ///
+  fn layout(&self, limits: &[Option<(usize, Overflow)>]) -> (Vec<Vec<String>>, Vec<usize>) {
+    let mut rows = Vec::new();
+    for row in &self.rows {
+      let cells = row
+        .iter()
+        .enumerate()
+        .map(|(index, cell)| Self::cell_lines(limits, index, cell))
+        .collect::<Vec<_>>();
+      let count = cells.iter().map(Vec::len).max().unwrap_or(1);
+      for line in 0..count {
+        rows.push(
+          cells
+            .iter()
+            .map(|lines| lines.get(line).map_or("", String::as_str).to_owned())
+            .collect::<Vec<_>>(),
+        );
+      }
+    }
+
+    let mut widths = Vec::<usize>::new();
+    for row in &rows {
+      for (index, cell) in row.iter().enumerate() {
+        let width = visible_width(cell);
+        match widths.get_mut(index) {
+          Some(max) => *max = (*max).max(width),
+          None => widths.push(width),
+        }
+      }
+    }
+
+    (rows, widths)
+  }
+
+  /// Writes the table as padded text.
+  fn finish_text(&self, output: &mut dyn Write) -> fmt::Result {
+    let (rows, widths) = self.layout(&self.limits);
+    let (rows, widths) = match self.width.is_some() && !self.shrink.is_empty() {
+      true => self.layout(&self.fitted_limits(&widths)),
+      false => (rows, widths),
+    };
+
+    for row in &rows {
+      let mut line = String::new();
+      for (index, cell) in row.iter().enumerate() {
+        // Padded by hand, escape sequences (colors) take no room.
+        let padding = " ".repeat(widths[index] - visible_width(cell));
+        let separator = if index + 1 == row.len() {
+          ""
+        } else {
+          Self::SEPARATOR
+        };
+        match self.alignments.get(index).copied().unwrap_or_default() {
+          Alignment::Right => write!(line, "{}{}{}", padding, cell, separator)?,
+          Alignment::Left if separator.is_empty() => line.push_str(cell),
+          Alignment::Left => write!(line, "{}{}{}", cell, padding, separator)?,
+        }
+      }
+
+      writeln!(output, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Number with its thousands separated by commas (`29,990,000`).
+struct Grouped(usize);
+
+impl Display for Grouped {
+  fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let digits = self.0.to_string();
+    for (index, digit) in digits.chars().enumerate() {
+      if index != 0 && (digits.len() - index).is_multiple_of(3) {
+        formatter.write_char(',')?;
+      }
+      formatter.write_char(digit)?;
+    }
+    Ok(())
+  }
+}
+
+///
+/// Writes a table to the given output ([`fmt::Formatter`], [`String`]...).
+/// Either with a fixed list of rows:
+///
+/// ```ignore
+/// display_table!(formatter, [
+///   ["Magic:", magic],
+///   ["Class:", class],
+/// ])
+/// ```
+///
+/// Or with a header row followed by one row per item, optionally preceded
+/// by the alignment of the columns:
+///
+/// ```ignore
+/// display_table!(formatter, ["Name", "Size"], section in sections => [
+///   section.name, section.size,
+/// ])
+///
+/// display_table!(formatter, align [Left, Right], ["Name", "Size"], section in sections => [
+///   section.name, section.size,
+/// ])
+/// ```
+///
+#[macro_export]
+macro_rules! display_table {
+  ($output: expr, [ $([ $($cell: expr),* $(,)? ]),* $(,)? ]) => {{
+    let mut table = $crate::utils::TableBuilder::new();
+    $($crate::utils::display_row!(table, [$($cell),*]);)*
+    table.finish($output)
+  }};
+
+  ($output: expr, [ $($head: expr),* $(,)? ], $item: pat in $items: expr => [ $($cell: expr),* $(,)? ]) => {
+    $crate::utils::display_table!($output, align [], [$($head),*], $item in $items => [$($cell),*])
+  };
+
+  (
+    $output: expr, align [ $($alignment: ident),* $(,)? ],
+    [ $($head: expr),* $(,)? ], $item: pat in $items: expr => [ $($cell: expr),* $(,)? ]
+  ) => {{
+    let mut table = $crate::utils::TableBuilder::new();
+    table.align(&[ $($crate::utils::Alignment::$alignment),* ]);
+    $crate::utils::display_row!(table, [$($head),*]);
+    for $item in $items {
+      $crate::utils::display_row!(table, [$($cell),*]);
+    }
+    table.finish($output)
+  }};
+}
+
+pub use display_table;
+
+///
+/// Appends a row to a [`TableBuilder`], for tables built incrementally
+/// (rows depending on conditions, several loops...):
+///
+/// ```ignore
+/// let mut table = TableBuilder::new();
+/// display_row!(table, ["Name", "Size"]);
+/// for section in sections {
+///   display_row!(table, [section.name, section.size]);
+/// }
+/// ```
+///
+#[macro_export]
+macro_rules! display_row {
+  ($table: expr, [ $($cell: expr),* $(,)? ]) => {
+    $table.row(&[ $(&$cell as &dyn ::core::fmt::Display),* ])
+  };
+}
+
+pub use display_row;
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn table_builder() {
+    let mut output = String::new();
+    TableBuilder::new()
+      .row(&[&"Name", &"Size", &"Flags"])
+      .row(&[&".text", &1234, &"AX"])
+      .row(&[&".données", &5])
+      .row(&[&".bss", &"", &""])
+      .finish(&mut output)
+      .unwrap();
+
+    assert_eq!(
+      output,
+      concat!(
+        "Name      Size  Flags\n",
+        ".text     1234  AX\n",
+        ".données  5\n",
+        ".bss\n",
+      )
+    );
+  }
+
+  #[test]
+  fn alignment() {
+    let mut output = String::new();
+    TableBuilder::new()
+      .align(&[
+        Alignment::Left,
+        Alignment::Right,
+        Alignment::Left,
+        Alignment::Right,
+      ])
+      .row(&[&"Name", &"Size", &"Flags", &"Align"])
+      .row(&[&".text", &1234, &"AX", &16])
+      .row(&[&".données", &5])
+      .finish(&mut output)
+      .unwrap();
+
+    assert_eq!(
+      output,
+      concat!(
+        "Name      Size  Flags  Align
+",
+        ".text     1234  AX        16
+",
+        ".données     5
+",
+      )
+    );
+  }
+
+  #[test]
+  fn truncate() {
+    let mut output = String::new();
+    TableBuilder::new()
+      .max_width(0, 5, Overflow::Truncate)
+      .row(&[&"12345", &"at the limit"])
+      .row(&[&"123456", &"over the limit"])
+      .row(&[&"éàçüö", &"multi-byte"])
+      .row(&[&"éàçüöï", &"multi-byte, over"])
+      .finish(&mut output)
+      .unwrap();
+
+    assert_eq!(
+      output,
+      concat!(
+        "12345  at the limit\n",
+        "1234…  over the limit\n",
+        "éàçüö  multi-byte\n",
+        "éàçü…  multi-byte, over\n",
+      )
+    );
+  }
+
+  #[test]
+  fn wrap() {
+    let mut output = String::new();
+    TableBuilder::new()
+      .max_width(1, 4, Overflow::Wrap)
+      .row(&[&"0x10", &"abcd", &"at the limit"])
+      .row(&[&"0x20", &"abcdefghé", &"over the limit"])
+      .finish(&mut output)
+      .unwrap();
+
+    assert_eq!(
+      output,
+      concat!(
+        "0x10  abcd  at the limit\n",
+        "0x20  abcd  over the limit\n",
+        "      efgh\n",
+        "      é\n",
+      )
+    );
+  }
+
+  #[test]
+  fn shrink() {
+    let table = |width| {
+      let mut output = String::new();
+      TableBuilder::new()
+        .fit(width)
+        .shrink(&[2, 1])
+        .row(&[
+          &"0x1000",
+          &"read_symbol_table",
+          &"elfprobe_core::elf::symbols",
+          &"0x20",
+        ])
+        .row(&[&"0x2000", &"main", &"main", &"0x8"])
+        .finish(&mut output)
+        .unwrap();
+      output
+    };
+
+    assert_eq!(
+      table(None),
+      concat!(
+        "0x1000  read_symbol_table  elfprobe_core::elf::symbols  0x20\n",
+        "0x2000  main               main                         0x8\n",
+      ),
+      "Unlimited"
+    );
+
+    assert_eq!(
+      table(Some(50)),
+      concat!(
+        "0x1000  read_symbol_table  elfprobe_core::e…  0x20\n",
+        "0x2000  main               main               0x8\n",
+      ),
+      "The first column listed is cut first"
+    );
+
+    assert_eq!(
+      table(Some(30)),
+      concat!(
+        "0x1000  read_sy…  elfprob…  0x20\n",
+        "0x2000  main      main      0x8\n",
+      ),
+      "Both cut down to the minimum width, the numbers are kept"
+    );
+
+    let mut output = String::new();
+    TableBuilder::new()
+      .fit(Some(10))
+      .shrink(&[1, 5])
+      .row(&[&"0x1000", &"name"])
+      .finish(&mut output)
+      .unwrap();
+    assert_eq!(output, "0x1000  name\n", "Narrow or missing columns are kept");
+
+    assert_eq!(TableBuilder::line_width(), None);
+    TableBuilder::set_line_width(Some(30));
+    let mut output = String::new();
+    display_table!(&mut output, [["0x1000", "elfprobe_core::elf::symbols"]]).unwrap();
+    assert_eq!(
+      output, "0x1000  elfprobe_core::elf::symbols\n",
+      "Nothing to shrink"
+    );
+    TableBuilder::set_line_width(None);
+  }
+
+  #[test]
+  fn styled() {
+    use crate::utils::Style;
+
+    let output = Style::with(true, || {
+      let mut output = String::new();
+      TableBuilder::new()
+        .align(&[Alignment::Right, Alignment::Left, Alignment::Left])
+        .max_width(2, 4, Overflow::Truncate)
+        .row(&[&"Size", &"Bind", &"Name"])
+        .row(&[
+          &Style::Red.paint(8),
+          &Style::Green.paint("GLOBAL"),
+          &Style::Cyan.paint("main"),
+        ])
+        .row(&[&16, &"LOCAL", &Style::Cyan.paint("_start")])
+        .finish(&mut output)
+        .unwrap();
+      output
+    });
+
+    assert_eq!(
+      output,
+      concat!(
+        "Size  Bind    Name\n",
+        "   \x1b[31m8\x1b[0m  \x1b[32mGLOBAL\x1b[0m  \x1b[36mmain\x1b[0m\n",
+        "  16  LOCAL   _st…\n",
+      )
+    );
+
+    let mut output = String::new();
+    Style::with(true, || {
+      TableBuilder::new()
+        .format(TableFormat::Csv)
+        .row(&[&Style::Green.paint("GLOBAL"), &"main"])
+        .finish(&mut output)
+    })
+    .unwrap();
+    assert_eq!(output, "GLOBAL,main\n", "No colors in CSV");
+  }
+
+  #[test]
+  fn display_row() {
+    let mut output = String::new();
+    let mut table = TableBuilder::new();
+    display_row!(table, ["Name", "Size"]);
+    for (name, size) in [(".text", 1234), (".bss", 8)] {
+      display_row!(table, [name, size]);
+    }
+    display_row!(table, ["Total:", 1234 + 8,]);
+    table.finish(&mut output).unwrap();
+    assert_eq!(output, "Name    Size\n.text   1234\n.bss    8\nTotal:  1242\n");
+
+    let mut output = String::new();
+    display_table!(&mut output, [["Class:", "ELF64"], ["Version:", 1]]).unwrap();
+    assert_eq!(
+      output, "Class:    ELF64\nVersion:  1\n",
+      "Built with display_row!"
+    );
+  }
+
+  #[test]
+  fn row_limit() {
+    let table = |format| {
+      let mut output = String::new();
+      let mut table = TableBuilder::new();
+      table.format(format).limit_rows(Some(2));
+      display_row!(table, ["Name", "Size"]);
+      for (name, size) in [(".text", 1234), (".data", 8), (".bss", 16), (".tbss", 4)] {
+        display_row!(table, [name, size]);
+      }
+      table.finish(&mut output).unwrap();
+      output
+    };
+
+    assert_eq!(
+      table(TableFormat::Text),
+      "Name   Size\n.text  1234\n.data  8\n… 2 more rows (use --no-limit)\n",
+      "The header is not counted"
+    );
+    assert_eq!(
+      table(TableFormat::Csv),
+      "Name,Size\n.text,1234\n.data,8\n.bss,16\n.tbss,4\n",
+      "Not limited"
+    );
+
+    assert_eq!(TableBuilder::row_limit(), None);
+    TableBuilder::set_row_limit(Some(1));
+    let mut output = String::new();
+    display_table!(&mut output, ["N"], number in 0..1000 => [number]).unwrap();
+    assert_eq!(output, "N\n0\n… 999 more rows (use --no-limit)\n");
+    TableBuilder::set_row_limit(None);
+
+    assert_eq!(Grouped(29_990_000).to_string(), "29,990,000");
+    assert_eq!(Grouped(999).to_string(), "999");
+    assert_eq!(Grouped(1000).to_string(), "1,000");
+  }
+
+  #[test]
+  fn csv() {
+    let mut output = String::new();
+    TableBuilder::new()
+      .format(TableFormat::Csv)
+      .max_width(0, 2, Overflow::Truncate)
+      .row(&[&"  Name", &"Size", &"Flags"])
+      .row(&[&".text", &1234, &"A, X"])
+      .row(&[&"say \"hi\"", &"line\nbreak"])
+      .finish(&mut output)
+      .unwrap();
+
+    assert_eq!(
+      output,
+      concat!(
+        "\"  Name\",Size,Flags\n",
+        ".text,1234,\"A, X\"\n",
+        "\"say \"\"hi\"\"\",\"line\nbreak\"\n",
+      )
+    );
+  }
+
+  #[test]
+  fn tsv() {
+    let mut output = String::new();
+    TableFormat::with(TableFormat::Tsv, || {
+      display_table!(&mut output, [["Class:", "ELF64"], ["Tab\there", "a,b"]]).unwrap();
+    });
+    assert_eq!(output, "Class:\tELF64\nTab here\ta,b\n");
+
+    // Restored when the function panics.
+    let result = std::panic::catch_unwind(|| TableFormat::with(TableFormat::Csv, || panic!("Unwound")));
+    assert!(result.is_err());
+    assert_eq!(TableFormat::current(), TableFormat::Text);
+    assert_eq!(TableFormat::current(), TableFormat::Text, "Restored");
+  }
+
+  #[test]
+  fn display_table() {
+    let mut output = String::new();
+    display_table!(&mut output, [["Class:", "ELF64"], ["Version:", 1]]).unwrap();
+    assert_eq!(output, "Class:    ELF64\nVersion:  1\n");
+
+    let mut output = String::new();
+    let items = [("a", 1), ("bcd", 22)];
+    display_table!(&mut output, ["Name", "Value"], (name, value) in items => [name, value]).unwrap();
+    assert_eq!(output, "Name  Value\na     1\nbcd   22\n");
+
+    let mut output = String::new();
+    display_table!(&mut output, align [Left, Right], ["Name", "Value"], (name, value) in items => [name, value])
+      .unwrap();
+    assert_eq!(output, "Name  Value\na         1\nbcd      22\n");
+  }
+}
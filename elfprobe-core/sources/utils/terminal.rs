@@ -0,0 +1,57 @@
+use std::env;
+use std::io::{self, IsTerminal};
+
+// ╔╦╗┌─┐┬─┐┌┬┐┬┌┐┌┌─┐┬
+//  ║ ├┤ ├┬┘│││││││├─┤│
+//  ╩ └─┘┴└─┴ ┴┴┘└┘┴ ┴┴─┘
+
+///
+/// Returns the number of columns of the terminal of the standard output,
+/// `None` when the output is not a terminal (pipe, file). `COLUMNS` takes
+/// precedence over the size of the window, as ls(1).
+///
+pub fn terminal_width() -> Option<usize> {
+  if !io::stdout().is_terminal() {
+    return None;
+  }
+
+  columns(env::var("COLUMNS").ok().as_deref()).or_else(window_columns)
+}
+
+/// Returns the number of columns of the window, unknown (0) for some terminals.
+fn window_columns() -> Option<usize> {
+  let mut size = libc::winsize {
+    ws_row: 0,
+    ws_col: 0,
+    ws_xpixel: 0,
+    ws_ypixel: 0,
+  };
+
+  let result = unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) };
+  (result == 0 && size.ws_col != 0).then_some(usize::from(size.ws_col))
+}
+
+/// Parses `COLUMNS`, ignored when empty, zero or not a number.
+fn columns(value: Option<&str>) -> Option<usize> {
+  value?.trim().parse().ok().filter(|&columns| columns != 0)
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_columns() {
+    assert_eq!(columns(Some("120")), Some(120));
+    assert_eq!(columns(Some(" 80\n")), Some(80));
+    assert_eq!(columns(Some("")), None);
+    assert_eq!(columns(Some("0")), None);
+    assert_eq!(columns(Some("-1")), None);
+    assert_eq!(columns(Some("wide")), None);
+    assert_eq!(columns(None), None);
+  }
+}
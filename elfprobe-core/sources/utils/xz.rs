@@ -0,0 +1,591 @@
+use super::crc32;
+use crate::alloc_prelude::*;
+
+// ╔═╗┌─┐┌┐┌┌─┐┌┬┐┌─┐┌┐┌┌┬┐┌─┐
+// ║  │ ││││└─┐ │ ├─┤│││ │ └─┐
+// ╚═╝└─┘┘└┘└─┘ ┴ ┴ ┴┘└┘ ┴ └─┘
+
+/// Magic number of the xz streams (`.gnu_debugdata`).
+const XZ_MAGIC: [u8; 6] = [0xfd, b'7', b'z', b'X', b'Z', 0x00];
+
+/// Filter identifier of LZMA2, the only filter supported.
+const FILTER_LZMA2: u64 = 0x21;
+
+/// Check types of the stream flags.
+const CHECK_NONE: u8 = 0x00;
+const CHECK_CRC32: u8 = 0x01;
+const CHECK_CRC64: u8 = 0x04;
+
+/// Number of states of the LZMA state machine (literal, match, rep...).
+const STATES: usize = 12;
+/// States below this one follow a literal.
+const LITERAL_STATES: usize = 7;
+const POS_STATES_MAX: usize = 1 << 4;
+
+/// Distances of the slots below this one are encoded with probabilities.
+const END_POS_MODEL_INDEX: u32 = 14;
+const FULL_DISTANCES: usize = 1 << (END_POS_MODEL_INDEX >> 1);
+const ALIGN_BITS: u32 = 4;
+const MATCH_LEN_MIN: usize = 2;
+
+// ╔╦╗┌─┐┌─┐┌─┐┌┬┐┌─┐┬─┐
+//  ║║├┤ │  │ │ ││├┤ ├┬┘
+// ═╩╝└─┘└─┘└─┘─┴┘└─┘┴└─
+
+///
+/// Decompresses an xz stream (the `.gnu_debugdata` of MiniDebugInfo) with
+/// the LZMA2 filter alone, as `xz` writes by default. The headers and the
+/// CRC-32 or CRC-64 checks are verified, the output is limited to `limit`
+/// bytes. Concatenated streams and the index are ignored.
+///
+pub fn decompress_xz(bytes: &[u8], limit: usize) -> Result<Vec<u8>, &'static str> {
+  let header = bytes.get(..12).ok_or("truncated stream header")?;
+  if header[..6] != XZ_MAGIC {
+    return Err("not an xz stream");
+  }
+  if crc32(&header[6..8]) != u32::from_le_bytes(header[8..12].try_into().unwrap()) {
+    return Err("stream header CRC mismatch");
+  }
+  let check = header[7] & 0x0f;
+  let check_size = match check {
+    0 => 0,
+    _ => 4 << ((check - 1) / 3),
+  };
+
+  let mut output = Vec::new();
+  let mut offset = 12;
+  loop {
+    // A null header size starts the index, which ends the blocks.
+    let size = *bytes.get(offset).ok_or("truncated block")?;
+    if size == 0 {
+      return Ok(output);
+    }
+
+    let size = (size as usize + 1) * 4;
+    let header = bytes.get(offset..offset + size).ok_or("truncated block header")?;
+    let (header, crc) = header.split_at(size - 4);
+    if crc32(header) != u32::from_le_bytes(crc.try_into().unwrap()) {
+      return Err("block header CRC mismatch");
+    }
+    parse_block_header(header)?;
+
+    let start = output.len();
+    let input = bytes.get(offset + size..).unwrap_or_default();
+    let consumed = Lzma2Decoder::default().decode(input, &mut output, limit)?;
+
+    // The compressed data is padded to a multiple of 4 bytes, then checked.
+    offset = (offset + size + consumed).next_multiple_of(4);
+    let stored = bytes.get(offset..offset + check_size).ok_or("truncated check")?;
+    let data = &output[start..];
+    let valid = match check {
+      CHECK_NONE => true,
+      CHECK_CRC32 => crc32(data).to_le_bytes() == stored,
+      CHECK_CRC64 => crc64(data).to_le_bytes() == stored,
+      _ => true, // SHA-256 and reserved checks are skipped.
+    };
+    if !valid {
+      return Err("block check mismatch");
+    }
+    offset += check_size;
+  }
+}
+
+/// Checks that a block header (without its CRC) has the LZMA2 filter alone.
+fn parse_block_header(header: &[u8]) -> Result<(), &'static str> {
+  let flags = *header.get(1).ok_or("truncated block header")?;
+  if flags & 0x3c != 0 {
+    return Err("unsupported block flags");
+  }
+  if flags & 0x03 != 0 {
+    return Err("unsupported filter chain (only LZMA2 is)");
+  }
+
+  let mut offset = 2;
+  let mut varint = || -> Result<u64, &'static str> {
+    let mut value = 0;
+    for shift in (0..63).step_by(7) {
+      let byte = *header.get(offset).ok_or("truncated block header")?;
+      offset += 1;
+      value |= u64::from(byte & 0x7f) << shift;
+      if byte & 0x80 == 0 {
+        return Ok(value);
+      }
+    }
+    Err("invalid block header integer")
+  };
+
+  // Compressed and uncompressed sizes, not needed to decode.
+  for present in [flags & 0x40 != 0, flags & 0x80 != 0] {
+    if present {
+      varint()?;
+    }
+  }
+
+  match (varint()?, varint()?) {
+    (FILTER_LZMA2, 1) => Ok(()),
+    (FILTER_LZMA2, _) => Err("invalid LZMA2 properties"),
+    _ => Err("unsupported filter (only LZMA2 is)"),
+  }
+}
+
+/// Lookup table of the reflected ECMA-182 polynomial, the CRC-64 of xz.
+const CRC64_TABLE: [u64; 256] = {
+  let mut table = [0; 256];
+  let mut index = 0;
+  while index < 256 {
+    let mut crc = index as u64;
+    let mut bit = 0;
+    while bit < 8 {
+      crc = match crc & 1 {
+        0 => crc >> 1,
+        _ => 0xc96c_5795_d787_0f42 ^ (crc >> 1),
+      };
+      bit += 1;
+    }
+    table[index] = crc;
+    index += 1;
+  }
+  table
+};
+
+/// Returns the CRC-64 (ECMA-182, as xz) of the bytes.
+fn crc64(bytes: &[u8]) -> u64 {
+  !bytes.iter().fold(!0, |crc, &byte| {
+    CRC64_TABLE[((crc ^ byte as u64) & 0xff) as usize] ^ (crc >> 8)
+  })
+}
+
+// ╦═╗┌─┐┌┐┌┌─┐┌─┐  ┌┬┐┌─┐┌─┐┌─┐┌┬┐┌─┐┬─┐
+// ╠╦╝├─┤││││ ┬├┤    ││├┤ │  │ │ ││├┤ ├┬┘
+// ╩╚═┴ ┴┘└┘└─┘└─┘  ─┴┘└─┘└─┘└─┘─┴┘└─┘┴└─
+
+/// Probability of a bit being 0, out of 2048.
+type Probability = u16;
+const PROBABILITY_INIT: Probability = 1024;
+
+/// Binary range decoder of the LZMA chunks, reading their packed bytes.
+struct RangeDecoder<'data> {
+  input: &'data [u8],
+  range: u32,
+  code: u32,
+}
+
+impl<'data> RangeDecoder<'data> {
+  fn new(input: &'data [u8]) -> Result<Self, &'static str> {
+    match input {
+      [0, code @ ..] if code.len() >= 4 => Ok(Self {
+        input: &code[4..],
+        range: u32::MAX,
+        code: u32::from_be_bytes(code[..4].try_into().unwrap()),
+      }),
+      _ => Err("invalid range coder initialization"),
+    }
+  }
+
+  fn normalize(&mut self) -> Result<(), &'static str> {
+    if self.range < 1 << 24 {
+      let (&byte, rest) = self.input.split_first().ok_or("truncated LZMA chunk")?;
+      self.input = rest;
+      self.range <<= 8;
+      self.code = (self.code << 8) | byte as u32;
+    }
+    Ok(())
+  }
+
+  fn bit(&mut self, probability: &mut Probability) -> Result<u32, &'static str> {
+    self.normalize()?;
+    let bound = (self.range >> 11) * *probability as u32;
+    if self.code < bound {
+      self.range = bound;
+      *probability += (2048 - *probability) >> 5;
+      Ok(0)
+    } else {
+      self.range -= bound;
+      self.code -= bound;
+      *probability -= *probability >> 5;
+      Ok(1)
+    }
+  }
+
+  /// Decodes `bits` bits, most significant first.
+  fn bittree(&mut self, probabilities: &mut [Probability], bits: u32) -> Result<u32, &'static str> {
+    let mut symbol = 1;
+    for _ in 0..bits {
+      symbol = (symbol << 1) | self.bit(&mut probabilities[symbol as usize])?;
+    }
+    Ok(symbol - (1 << bits))
+  }
+
+  /// Decodes `bits` bits, least significant first (the tree is not 1-based).
+  fn reverse_bittree(&mut self, probabilities: &mut [Probability], bits: u32) -> Result<u32, &'static str> {
+    let (mut symbol, mut value) = (1, 0);
+    for bit in 0..bits {
+      let decoded = self.bit(&mut probabilities[symbol as usize - 1])?;
+      symbol = (symbol << 1) | decoded;
+      value |= decoded << bit;
+    }
+    Ok(value)
+  }
+
+  /// Decodes `bits` bits of equal probabilities.
+  fn direct(&mut self, bits: u32) -> Result<u32, &'static str> {
+    let mut value = 0;
+    for _ in 0..bits {
+      self.normalize()?;
+      self.range >>= 1;
+      let bit = (self.code >= self.range) as u32;
+      self.code -= self.range * bit;
+      value = (value << 1) | bit;
+    }
+    Ok(value)
+  }
+}
+
+// ╦  ╔═╗╔╦╗╔═╗
+// ║  ╔═╝║║║╠═╣
+// ╩═╝╚═╝╩ ╩╩ ╩
+
+/// Probabilities of a match length (2 to 273).
+#[derive(Clone)]
+struct LengthDecoder {
+  choice: Probability,
+  choice2: Probability,
+  low: [[Probability; 8]; POS_STATES_MAX],
+  mid: [[Probability; 8]; POS_STATES_MAX],
+  high: [Probability; 256],
+}
+
+impl Default for LengthDecoder {
+  fn default() -> Self {
+    Self {
+      choice: PROBABILITY_INIT,
+      choice2: PROBABILITY_INIT,
+      low: [[PROBABILITY_INIT; 8]; POS_STATES_MAX],
+      mid: [[PROBABILITY_INIT; 8]; POS_STATES_MAX],
+      high: [PROBABILITY_INIT; 256],
+    }
+  }
+}
+
+impl LengthDecoder {
+  fn decode(&mut self, decoder: &mut RangeDecoder, pos_state: usize) -> Result<usize, &'static str> {
+    let length = match () {
+      _ if decoder.bit(&mut self.choice)? == 0 => decoder.bittree(&mut self.low[pos_state], 3)?,
+      _ if decoder.bit(&mut self.choice2)? == 0 => 8 + decoder.bittree(&mut self.mid[pos_state], 3)?,
+      _ => 16 + decoder.bittree(&mut self.high, 8)?,
+    };
+    Ok(MATCH_LEN_MIN + length as usize)
+  }
+}
+
+/// State of the LZMA decoder, kept between the chunks unless reset.
+struct LzmaState {
+  lc: u32,
+  lp: u32,
+  pb: u32,
+  state: usize,
+  /// Distances minus one of the last four matches.
+  reps: [u32; 4],
+  is_match: [[Probability; POS_STATES_MAX]; STATES],
+  is_rep: [Probability; STATES],
+  is_rep0: [Probability; STATES],
+  is_rep1: [Probability; STATES],
+  is_rep2: [Probability; STATES],
+  is_rep0_long: [[Probability; POS_STATES_MAX]; STATES],
+  dist_slot: [[Probability; 64]; 4],
+  dist_special: [Probability; FULL_DISTANCES - END_POS_MODEL_INDEX as usize],
+  dist_align: [Probability; 1 << ALIGN_BITS],
+  match_length: LengthDecoder,
+  rep_length: LengthDecoder,
+  literal: Vec<Probability>,
+}
+
+impl LzmaState {
+  /// Returns a reset state with the given properties (`(pb * 5 + lp) * 9 + lc`).
+  fn new(properties: u8) -> Result<Self, &'static str> {
+    let (lc, rest) = (properties % 9, properties / 9);
+    let (lp, pb) = (rest % 5, rest / 5);
+    if pb > 4 || lc + lp > 4 {
+      return Err("invalid LZMA properties");
+    }
+
+    Ok(Self {
+      lc: lc as u32,
+      lp: lp as u32,
+      pb: pb as u32,
+      state: 0,
+      reps: [0; 4],
+      is_match: [[PROBABILITY_INIT; POS_STATES_MAX]; STATES],
+      is_rep: [PROBABILITY_INIT; STATES],
+      is_rep0: [PROBABILITY_INIT; STATES],
+      is_rep1: [PROBABILITY_INIT; STATES],
+      is_rep2: [PROBABILITY_INIT; STATES],
+      is_rep0_long: [[PROBABILITY_INIT; POS_STATES_MAX]; STATES],
+      dist_slot: [[PROBABILITY_INIT; 64]; 4],
+      dist_special: [PROBABILITY_INIT; FULL_DISTANCES - END_POS_MODEL_INDEX as usize],
+      dist_align: [PROBABILITY_INIT; 1 << ALIGN_BITS],
+      match_length: LengthDecoder::default(),
+      rep_length: LengthDecoder::default(),
+      literal: vec![PROBABILITY_INIT; 0x300 << (lc + lp)],
+    })
+  }
+
+  ///
+  /// Decodes `size` bytes from the packed bytes of a chunk, appended to the
+  /// output whose bytes since `dictionary` may be referenced by the matches.
+  ///
+  fn decode(
+    &mut self,
+    packed: &[u8],
+    size: usize,
+    output: &mut Vec<u8>,
+    dictionary: usize,
+  ) -> Result<(), &'static str> {
+    let mut decoder = RangeDecoder::new(packed)?;
+    let end = output.len() + size;
+
+    while output.len() < end {
+      let position = output.len() - dictionary;
+      let pos_state = position & ((1 << self.pb) - 1);
+      let state = self.state;
+
+      if decoder.bit(&mut self.is_match[state][pos_state])? == 0 {
+        let previous = output[dictionary..].last().copied().unwrap_or(0) as usize;
+        let index = ((position & ((1 << self.lp) - 1)) << self.lc) + (previous >> (8 - self.lc));
+        let probabilities = &mut self.literal[0x300 * index..0x300 * (index + 1)];
+
+        let byte = match state < LITERAL_STATES {
+          true => decoder.bittree(probabilities, 8)?,
+          false => {
+            // Guided by the byte at the last match distance.
+            let mut matched = (Self::byte_at(output, dictionary, self.reps[0])? as u32) << 1;
+            let (mut symbol, mut offset) = (1_u32, 0x100_u32);
+            while symbol < 0x100 {
+              let match_bit = matched & offset;
+              matched <<= 1;
+              let bit = decoder.bit(&mut probabilities[(offset + match_bit + symbol) as usize])?;
+              symbol = (symbol << 1) | bit;
+              offset &= match (bit, match_bit) {
+                (1, _) => match_bit,
+                _ => !match_bit,
+              };
+            }
+            symbol - 0x100
+          }
+        };
+
+        output.push(byte as u8);
+        self.state = match state {
+          0..=3 => 0,
+          4..=9 => state - 3,
+          _ => state - 6,
+        };
+        continue;
+      }
+
+      let length = if decoder.bit(&mut self.is_rep[state])? == 0 {
+        // New distance.
+        self.state = if state < LITERAL_STATES { 7 } else { 10 };
+        let length = self.match_length.decode(&mut decoder, pos_state)?;
+        let distance = self.distance(&mut decoder, length)?;
+        self.reps = [distance, self.reps[0], self.reps[1], self.reps[2]];
+        length
+      } else if decoder.bit(&mut self.is_rep0[state])? == 0 {
+        if decoder.bit(&mut self.is_rep0_long[state][pos_state])? == 0 {
+          // Single byte at the last distance.
+          self.state = if state < LITERAL_STATES { 9 } else { 11 };
+          output.push(Self::byte_at(output, dictionary, self.reps[0])?);
+          continue;
+        }
+        self.state = if state < LITERAL_STATES { 8 } else { 11 };
+        self.rep_length.decode(&mut decoder, pos_state)?
+      } else {
+        // One of the older distances, moved to the front.
+        let index = match () {
+          _ if decoder.bit(&mut self.is_rep1[state])? == 0 => 1,
+          _ if decoder.bit(&mut self.is_rep2[state])? == 0 => 2,
+          _ => 3,
+        };
+        self.reps[..=index].rotate_right(1);
+        self.state = if state < LITERAL_STATES { 8 } else { 11 };
+        self.rep_length.decode(&mut decoder, pos_state)?
+      };
+
+      let distance = self.reps[0] as usize + 1;
+      if distance > output.len() - dictionary {
+        return Err("match distance beyond the dictionary");
+      }
+      if length > end - output.len() {
+        return Err("match beyond the chunk");
+      }
+      // Byte per byte, the source may overlap the copy.
+      let start = output.len() - distance;
+      for index in start..start + length {
+        output.push(output[index]);
+      }
+    }
+
+    Ok(())
+  }
+
+  /// Returns the byte at the given distance minus one from the end of the output.
+  fn byte_at(output: &[u8], dictionary: usize, distance: u32) -> Result<u8, &'static str> {
+    let distance = distance as usize + 1;
+    match distance <= output.len() - dictionary {
+      true => Ok(output[output.len() - distance]),
+      false => Err("match distance beyond the dictionary"),
+    }
+  }
+
+  /// Decodes the distance minus one of a match of the given length.
+  fn distance(&mut self, decoder: &mut RangeDecoder, length: usize) -> Result<u32, &'static str> {
+    let length_state = (length - MATCH_LEN_MIN).min(3);
+    let slot = decoder.bittree(&mut self.dist_slot[length_state], 6)?;
+    if slot < 4 {
+      return Ok(slot);
+    }
+
+    let bits = (slot >> 1) - 1;
+    let base = (2 | (slot & 1)) << bits;
+    if slot < END_POS_MODEL_INDEX {
+      // The trees of the slots follow each other, from slot 4 (1 bit) to 13.
+      let probabilities = &mut self.dist_special[(base - slot) as usize..];
+      return Ok(base + decoder.reverse_bittree(probabilities, bits)?);
+    }
+
+    let direct = decoder.direct(bits - ALIGN_BITS)? << ALIGN_BITS;
+    let align = decoder.reverse_bittree(&mut self.dist_align, ALIGN_BITS)?;
+    match base.checked_add(direct + align) {
+      Some(u32::MAX) | None => Err("unexpected end marker"),
+      Some(distance) => Ok(distance),
+    }
+  }
+}
+
+// ╦  ╔═╗╔╦╗╔═╗┌─┐
+// ║  ╔═╝║║║╠═╣┌─┘
+// ╩═╝╚═╝╩ ╩╩ ╩└─┘
+
+/// Decoder of the LZMA2 chunks of a block.
+#[derive(Default)]
+struct Lzma2Decoder {
+  state: Option<LzmaState>,
+  /// Start of the dictionary in the output (reset by some chunks).
+  dictionary: Option<usize>,
+}
+
+impl Lzma2Decoder {
+  /// Decodes the chunks up to the end marker, returns the bytes consumed.
+  fn decode(&mut self, input: &[u8], output: &mut Vec<u8>, limit: usize) -> Result<usize, &'static str> {
+    let mut offset = 0;
+    loop {
+      let control = *input.get(offset).ok_or("truncated LZMA2 chunk")?;
+      let field = |start: usize| -> Result<usize, &'static str> {
+        let bytes = input
+          .get(offset + start..offset + start + 2)
+          .ok_or("truncated LZMA2 chunk")?;
+        Ok(u16::from_be_bytes(bytes.try_into().unwrap()) as usize + 1)
+      };
+
+      // Dictionary resets: uncompressed chunk 1 and LZMA chunks 0xe0 and above.
+      if control == 0x01 || control >= 0xe0 {
+        self.dictionary = Some(output.len());
+      }
+      let dictionary = self.dictionary.ok_or("missing dictionary reset")?;
+
+      match control {
+        0x00 => return Ok(offset + 1),
+        0x01 | 0x02 => {
+          let size = field(1)?;
+          let data = input
+            .get(offset + 3..offset + 3 + size)
+            .ok_or("truncated LZMA2 chunk")?;
+          if output.len() + size > limit {
+            return Err("decompressed size above the limit");
+          }
+          output.extend_from_slice(data);
+          offset += 3 + size;
+        }
+        0x80.. => {
+          let size = ((control as usize & 0x1f) << 16) + field(1)?;
+          let packed_size = field(3)?;
+          let mut start = offset + 5;
+          if control >= 0xc0 {
+            let properties = *input.get(start).ok_or("truncated LZMA2 chunk")?;
+            self.state = Some(LzmaState::new(properties)?);
+            start += 1;
+          }
+
+          let state = self.state.as_mut().ok_or("missing LZMA properties")?;
+          if (0xa0..0xc0).contains(&control) {
+            let properties = (state.pb * 5 + state.lp) * 9 + state.lc;
+            *state = LzmaState::new(properties as u8)?;
+          }
+
+          if output.len() + size > limit {
+            return Err("decompressed size above the limit");
+          }
+          let packed = input
+            .get(start..start + packed_size)
+            .ok_or("truncated LZMA2 chunk")?;
+          state.decode(packed, size, output, dictionary)?;
+          offset = start + packed_size;
+        }
+        _ => return Err("invalid LZMA2 control byte"),
+      }
+    }
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use crate::hex::hex;
+
+  /// `xz` (CRC-64) of the names below, a literal then matches and repetitions.
+  const STREAM: &str = "
+    fd 37 7a 58 5a 00 00 04 e6 d6 b4 46 02 00 21 01
+    16 00 00 00 74 2f e5 a3 e0 00 7a 00 2b 5d 00 26
+    9a 49 c6 af 46 01 1b f1 49 df 55 5a 55 ce 4e 3f
+    0e b9 f6 59 f0 79 3b 45 67 77 ca 74 ad 43 93 56
+    52 fc e0 57 b6 1c 91 38 a8 00 00 00 ee 12 e3 0e
+    44 5b f0 d2 00 01 47 7b 5d c4 69 58 1f b6 f3 7d
+    01 00 00 00 00 04 59 5a
+  ";
+
+  #[test]
+  fn crc64_checksum() {
+    assert_eq!(crc64(b""), 0);
+    assert_eq!(crc64(b"123456789"), 0x995d_c9bb_df19_39fa);
+  }
+
+  #[test]
+  fn xz_stream() {
+    let stream = hex(STREAM).unwrap();
+    let names = "_start main _start main _init _fini ".repeat(3);
+    let expected = format!("MiniDebugInfo: {}", names);
+    assert_eq!(decompress_xz(&stream, 1 << 20).unwrap(), expected.as_bytes());
+
+    assert_eq!(
+      decompress_xz(&stream, 100),
+      Err("decompressed size above the limit")
+    );
+    assert_eq!(
+      decompress_xz(&stream[..60], 1 << 20),
+      Err("truncated LZMA2 chunk")
+    );
+    let mut corrupted = stream.clone();
+    corrupted[0x4c] ^= 1; // CRC-64 of the data.
+    assert_eq!(decompress_xz(&corrupted, 1 << 20), Err("block check mismatch"));
+    corrupted[7] = 0x0a; // Check type.
+    assert_eq!(
+      decompress_xz(&corrupted, 1 << 20),
+      Err("stream header CRC mismatch")
+    );
+  }
+}
@@ -0,0 +1,68 @@
+use core::mem::size_of;
+
+use crate::error::BytesError;
+use crate::pod::Pod;
+
+///
+/// Mutable counterpart of [`Reader`](crate::reader::Reader) to patch data in
+/// place (set the interpreter, flip flags...), typically a heap copy of the
+/// file which is then written back.
+///
+/// Unlike readers, writers are not `Copy` (there is a single `&mut`) and the
+/// methods borrow the writer mutably: only one value can be patched at a time.
+///
+pub trait Writer {
+  /// Returns the bytes in range for writing.
+  fn write_bytes(&mut self, size: usize, offset: usize) -> Option<&mut [u8]>;
+
+  /// Returns the value at the given offset for writing, see [`Pod::from_bytes_mut()`].
+  fn write_pod<Type: Pod>(&mut self, offset: usize) -> Result<&mut Type, BytesError> {
+    let size = size_of::<Type>();
+    match self.write_bytes(size, offset) {
+      None => Err(BytesError::OutOfBounds { offset, size }),
+      Some(bytes) => Type::from_bytes_mut(bytes),
+    }
+  }
+}
+
+/// Covers `&mut [u8]`, `Vec<u8>` and arrays through deref.
+impl Writer for [u8] {
+  fn write_bytes(&mut self, size: usize, offset: usize) -> Option<&mut [u8]> {
+    // Offsets come from the file itself and must not overflow.
+    self.get_mut(offset..offset.checked_add(size)?)
+  }
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn write_bytes() {
+    let mut bytes = vec![1u8, 2, 3, 4, 5, 6];
+    bytes.write_bytes(2, 1).unwrap().copy_from_slice(&[0xA, 0xB]);
+    assert_eq!(bytes, [1, 0xA, 0xB, 4, 5, 6]);
+    assert_eq!(bytes.write_bytes(2, usize::MAX), None);
+    assert_eq!(bytes.write_bytes(1, 6), None);
+  }
+
+  #[test]
+  fn write_pod() {
+    let mut bytes = [0u8; 6];
+    let slice: &mut [u8] = &mut bytes;
+    *slice.write_pod::<[u8; 2]>(4).unwrap() = [7, 8];
+    assert_eq!(
+      slice.write_pod::<[u8; 4]>(4),
+      Err(BytesError::OutOfBounds { offset: 4, size: 4 })
+    );
+    assert_eq!(bytes, [0, 0, 0, 0, 7, 8]);
+    assert_eq!(
+      BytesError::OutOfBounds { offset: 4, size: 4 }.to_string(),
+      "4 bytes at offset 0x4 out of bounds"
+    );
+  }
+}
@@ -0,0 +1,148 @@
+//!
+//! The memory allocated while parsing a forged file stays proportional to
+//! the size of the file, whatever the counts of its tables claim. A counting
+//! allocator is installed for this test binary only.
+//!
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, PoisonError};
+
+use elfprobe_core::elf::{ElfFile, ParseOptions};
+use elfprobe_core::error::ElfError;
+use elfprobe_core::prelude::parse_elf_with;
+
+struct Counting;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    let current = CURRENT.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+    PEAK.fetch_max(current, Ordering::SeqCst);
+    unsafe { System.alloc(layout) }
+  }
+
+  unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+    CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+    unsafe { System.dealloc(pointer, layout) }
+  }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+///
+/// 1 KiB ELF64 little-endian shared object whose header claims 0xFF00
+/// section headers and 0xFFFF program headers, the first one a `PT_DYNAMIC`
+/// segment of 4 GiB.
+///
+fn forged() -> Vec<u8> {
+  let mut bytes = vec![0u8; 1024];
+  bytes[..8].copy_from_slice(&[0x7F, b'E', b'L', b'F', 2, 1, 1, 0]);
+  let mut write = |offset: usize, value: &[u8]| bytes[offset..offset + value.len()].copy_from_slice(value);
+  write(0x10, &3u16.to_le_bytes()); // ET_DYN
+  write(0x12, &0x3Eu16.to_le_bytes()); // EM_X86_64
+  write(0x14, &1u32.to_le_bytes()); // e_version
+  write(0x20, &0x40u64.to_le_bytes()); // e_phoff
+  write(0x28, &0x40u64.to_le_bytes()); // e_shoff
+  write(0x34, &64u16.to_le_bytes()); // e_ehsize
+  write(0x36, &56u16.to_le_bytes()); // e_phentsize
+  write(0x38, &0xFFFFu16.to_le_bytes()); // e_phnum
+  write(0x3A, &64u16.to_le_bytes()); // e_shentsize
+  write(0x3C, &0xFF00u16.to_le_bytes()); // e_shnum
+
+  write(0x40, &2u32.to_le_bytes()); // PT_DYNAMIC
+  write(0x40 + 0x20, &0xFFFF_FFFFu64.to_le_bytes()); // p_filesz
+  bytes
+}
+
+///
+/// ELF64 little-endian relocatable file of 4000 valid `SHT_PROGBITS`
+/// sections, all of them the same 256 bytes after the header: a pairwise
+/// overlap check would report millions of issues.
+///
+fn overlapping() -> Vec<u8> {
+  const COUNT: usize = 4000;
+  const SHOFF: usize = 0x140;
+
+  let mut bytes = vec![0u8; SHOFF + COUNT * 64];
+  bytes[..8].copy_from_slice(&[0x7F, b'E', b'L', b'F', 2, 1, 1, 0]);
+  let mut write = |offset: usize, value: &[u8]| bytes[offset..offset + value.len()].copy_from_slice(value);
+  write(0x10, &1u16.to_le_bytes()); // ET_REL
+  write(0x12, &0x3Eu16.to_le_bytes()); // EM_X86_64
+  write(0x14, &1u32.to_le_bytes()); // e_version
+  write(0x28, &(SHOFF as u64).to_le_bytes()); // e_shoff
+  write(0x34, &64u16.to_le_bytes()); // e_ehsize
+  write(0x3A, &64u16.to_le_bytes()); // e_shentsize
+  write(0x3C, &(COUNT as u16).to_le_bytes()); // e_shnum
+
+  // The null section stays zeros.
+  for index in 1..COUNT {
+    let header = SHOFF + index * 64;
+    write(header + 0x04, &1u32.to_le_bytes()); // SHT_PROGBITS
+    write(header + 0x18, &0x40u64.to_le_bytes()); // sh_offset
+    write(header + 0x20, &0x100u64.to_le_bytes()); // sh_size
+    write(header + 0x30, &1u64.to_le_bytes()); // sh_addralign
+  }
+  bytes
+}
+
+/// The tests share the counters of the allocator.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+#[test]
+fn forged_counts() {
+  let _serial = SERIAL.lock().unwrap_or_else(PoisonError::into_inner);
+  let bytes = forged();
+  let baseline = CURRENT.load(Ordering::SeqCst);
+  PEAK.store(baseline, Ordering::SeqCst);
+
+  let Ok(ElfFile::Elf64Le(object)) = parse_elf_with(bytes.as_slice(), ParseOptions::LENIENT) else {
+    panic!("Not an elf64 little-endian");
+  };
+  let sections = object.section_headers().unwrap().len();
+  let segments = object.program_headers().unwrap().len();
+  let dynamic = object.dynamic().unwrap().len();
+  let _ = (object.sections().count(), object.plt_relocations().len());
+  let _ = (object.validate(), object.layout().to_string());
+
+  let peak = PEAK.load(Ordering::SeqCst) - baseline;
+  assert!(peak <= 32 * bytes.len(), "{} bytes allocated", peak);
+  assert!(sections <= 16 && segments <= 18 && dynamic <= 64);
+
+  let warnings = object.diagnostics().take();
+  for (table, count, max) in [
+    ("section header table", 0xFF00, 16),
+    ("program header table", 0xFFFF, 18),
+    ("dynamic table", 0xFFFF_FFFF / 16, 64),
+  ] {
+    let clamped = ElfError::CountClamped { table, count, max };
+    assert!(warnings.contains(&clamped), "{}: {:?}", clamped, warnings);
+  }
+}
+
+#[test]
+fn overlapping_sections() {
+  let _serial = SERIAL.lock().unwrap_or_else(PoisonError::into_inner);
+  let bytes = overlapping();
+  let baseline = CURRENT.load(Ordering::SeqCst);
+  PEAK.store(baseline, Ordering::SeqCst);
+
+  let Ok(ElfFile::Elf64Le(object)) = parse_elf_with(bytes.as_slice(), ParseOptions::LENIENT) else {
+    panic!("Not an elf64 little-endian");
+  };
+  let sections = object.section_headers().unwrap().len();
+  let issues = object.validate_sections();
+  let _ = (
+    object.sections().count(),
+    object.validate(),
+    object.layout().to_string(),
+  );
+
+  let peak = PEAK.load(Ordering::SeqCst) - baseline;
+  assert!(peak <= 32 * bytes.len(), "{} bytes allocated", peak);
+  assert_eq!(sections, 4000);
+  assert!(issues.len() < sections, "{} issues", issues.len());
+}
@@ -0,0 +1,488 @@
+//!
+//! Compares `elfprobe -h -S -l` with `readelf -W -h -S -l` on the fixtures
+//! of `tests/fixtures`, generated by [`ElfBuilder`]. The two outputs are not
+//! laid out the same (padding, `0x` prefixes, columns), both are normalized
+//! into records (header fields, sections, segments and the section to
+//! segment mapping) which are then compared.
+//!
+//! The comparison is opt-in, it needs binutils: `READELF=readelf cargo test
+//! --test readelf`. The fixtures are rewritten with `ELFPROBE_BLESS=1` after
+//! a change of [`fixtures()`].
+//!
+
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use elfprobe_core::elf::{p_flags, p_type, sh_flags, sh_type, BuilderSection, ElfBuilder};
+use elfprobe_core::prelude::*;
+
+// ╔═╗┬─┐ ┬┌┬┐┬ ┬┬─┐┌─┐┌─┐
+// ╠╣ │┌┴┬┘ │ │ │├┬┘├┤ └─┐
+// ╚  ┴┴ └─ ┴ └─┘┴└─└─┘└─┘
+
+/// Directory of the checked-in fixtures.
+fn fixtures_directory() -> PathBuf {
+  Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+/// A section allocated at the given address (0 to follow the previous one in its segment).
+fn allocated(name: &str, sh_type: u32, sh_flags: u64, sh_addr: u64, data: &[u8]) -> BuilderSection {
+  BuilderSection {
+    name: String::from(name),
+    sh_type,
+    sh_flags: sh_flags::SHF_ALLOC | sh_flags,
+    sh_addr,
+    sh_addralign: 1,
+    data: data.to_vec(),
+    ..Default::default()
+  }
+}
+
+///
+/// The fixtures and their names: a relocatable object, a statically linked
+/// executable (big-endian, 32-bit) and a shared object with a `SONAME`.
+///
+fn fixtures() -> Vec<(&'static str, Vec<u8>)> {
+  use p_flags::{PF_R, PF_W, PF_X};
+  use sh_flags::{SHF_EXECINSTR, SHF_WRITE};
+  use sh_type::{SHT_DYNAMIC, SHT_NOBITS, SHT_PROGBITS, SHT_STRTAB, SHT_SYMTAB};
+
+  // Null symbol then `main`, a global function of `.text`.
+  let mut symtab = vec![0; 24];
+  symtab.extend(1u32.to_le_bytes());
+  symtab.extend([0x12, 0]);
+  symtab.extend(1u16.to_le_bytes());
+  symtab.extend([0; 8]);
+  symtab.extend(4u64.to_le_bytes());
+
+  let relocatable = ElfBuilder::<ElfType64<LittleEndian>>::new()
+    .add_section(BuilderSection {
+      sh_addralign: 16,
+      ..allocated(".text", SHT_PROGBITS, SHF_EXECINSTR, 0, &[0x31, 0xC0, 0xC3, 0x90])
+    })
+    .add_section(allocated(".data", SHT_PROGBITS, SHF_WRITE, 0, &[1, 2, 3, 4]))
+    .add_section(BuilderSection {
+      size: Some(0x40),
+      ..allocated(".bss", SHT_NOBITS, SHF_WRITE, 0, &[])
+    })
+    .add_section(BuilderSection {
+      name: String::from(".symtab"),
+      sh_type: SHT_SYMTAB,
+      sh_link: 5,
+      sh_info: 1,
+      sh_addralign: 8,
+      sh_entsize: 24,
+      data: symtab,
+      ..Default::default()
+    })
+    .section(".strtab", SHT_STRTAB, b"\0main\0")
+    .section(".note.GNU-stack", SHT_PROGBITS, &[])
+    .build();
+
+  let executable = ElfBuilder::<ElfType32<BigEndian>>::new()
+    .object_type(2) // ET_EXEC
+    .machine(8) // EM_MIPS
+    .entry(0x400100)
+    .add_section(allocated(
+      ".text",
+      SHT_PROGBITS,
+      SHF_EXECINSTR,
+      0x400100,
+      &[0; 16],
+    ))
+    .add_section(allocated(".rodata", SHT_PROGBITS, 0, 0, b"hello\0"))
+    .add_section(allocated(".data", SHT_PROGBITS, SHF_WRITE, 0x410000, &[0xFF; 8]))
+    .add_section(BuilderSection {
+      size: Some(0x100),
+      ..allocated(".bss", SHT_NOBITS, SHF_WRITE, 0, &[])
+    })
+    .segment(p_type::PT_LOAD, PF_R | PF_X, &[1, 2])
+    .segment(p_type::PT_LOAD, PF_R | PF_W, &[3, 4])
+    .build();
+
+  // `DT_SONAME`, `DT_STRTAB`, `DT_STRSZ` and `DT_NULL`.
+  let mut dynamic = Vec::new();
+  for (d_tag, d_val) in [(14u64, 1u64), (5, 0x1000), (10, 14), (0, 0)] {
+    dynamic.extend(d_tag.to_le_bytes());
+    dynamic.extend(d_val.to_le_bytes());
+  }
+  let shared = ElfBuilder::<ElfType64<LittleEndian>>::new()
+    .object_type(3) // ET_DYN
+    .add_section(allocated(".dynstr", SHT_STRTAB, 0, 0x1000, b"\0libstub.so.1\0"))
+    .add_section(BuilderSection {
+      sh_link: 1,
+      sh_addralign: 8,
+      sh_entsize: 16,
+      ..allocated(".dynamic", SHT_DYNAMIC, SHF_WRITE, 0, &dynamic)
+    })
+    .segment(p_type::PT_LOAD, PF_R | PF_W, &[1, 2])
+    .segment(p_type::PT_DYNAMIC, PF_R | PF_W, &[2])
+    .build();
+
+  vec![
+    ("relocatable.o", relocatable),
+    ("executable-mips", executable),
+    ("libstub.so.1", shared),
+  ]
+}
+
+// ╔╗╔┌─┐┬─┐┌┬┐┌─┐┬  ┬┌─┐┌─┐┌┬┐┬┌─┐┌┐┌
+// ║║║│ │├┬┘│││├─┤│  │┌─┘├─┤ │ ││ ││││
+// ╝╚╝└─┘┴└─┴ ┴┴ ┴┴─┘┴└─┘┴ ┴ ┴ ┴└─┘┘└┘
+
+///
+/// Names which readelf and elfprobe spell differently, readelf's first. The
+/// comparison is on the meaning, not the wording.
+///
+const ALIASES: &[(&str, &str)] = &[
+  ("Advanced Micro Devices X86-64", "AMD x86-64 architecture"),
+  ("MIPS R3000", "MIPS R3000 big-endian"),
+  ("VERSYM", "GNU_versym"),
+  ("VERNEED", "GNU_verneed"),
+  ("VERDEF", "GNU_verdef"),
+];
+
+/// Part of the output a record belongs to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum Part {
+  Header,
+  Sections,
+  Segments,
+  Mapping,
+}
+
+///
+/// A line of either output once normalized: its part and its fields, the
+/// numbers in hexadecimal without padding and the names through [`ALIASES`].
+///
+type Record = (Part, Vec<String>);
+
+/// Returns the number of a field (`0x` hexadecimal or decimal), `None` when it is not one.
+fn number(field: &str, hexadecimal: bool) -> Option<u64> {
+  match field.strip_prefix("0x") {
+    Some(digits) => u64::from_str_radix(digits, 16).ok(),
+    None if hexadecimal => u64::from_str_radix(field, 16).ok(),
+    None => field.parse().ok(),
+  }
+}
+
+/// Returns the field as a hexadecimal number, unchanged when it is not one.
+fn canonical(field: &str, hexadecimal: bool) -> String {
+  match number(field, hexadecimal) {
+    Some(value) => format!("{:#x}", value),
+    None => String::from(field),
+  }
+}
+
+/// Returns the elfprobe spelling of a name.
+fn alias(name: &str) -> String {
+  let alias = ALIASES.iter().find(|(readelf, _)| *readelf == name);
+  String::from(alias.map_or(name, |(_, elfprobe)| *elfprobe))
+}
+
+///
+/// Returns the header field of a `Key: value` line: the value loses its
+/// readelf annotations (`1 (current)`), the numbers are made canonical and
+/// the magic bytes are separated by single spaces.
+///
+fn header_field(line: &str) -> Option<Vec<String>> {
+  let (key, value) = line.split_once(':')?;
+  let value = value.split_whitespace().collect::<Vec<_>>().join(" ");
+  let value = value.strip_suffix(" (current)").unwrap_or(&value);
+  // readelf leaves zero flags undecoded, elfprobe still names the MIPS ISA.
+  let value = match key.trim() {
+    "Flags" => value.split(',').next().unwrap_or_default(),
+    _ => value,
+  };
+  let value = match value.split_once(' ') {
+    Some((first, rest)) if rest.starts_with('(') && number(first, false).is_some() => {
+      format!("{} {}", canonical(first, false), rest)
+    }
+    _ => alias(&canonical(value, false)),
+  };
+  Some(vec![String::from(key.trim()), value])
+}
+
+///
+/// Returns the fields of a section header row, `[Nr] Name Type Address Off
+/// Size ES Flg` then readelf's `Lk Inf Al` or elfprobe's `Align`. The name
+/// and the flags may be empty, the row is therefore read from both ends:
+/// the flags are the token before the numeric columns which is not a
+/// two-digit (or more) hexadecimal number. readelf's numbers have no `0x`,
+/// except for the alignment which it prints in decimal.
+///
+fn section_row(line: &str, readelf: bool) -> Option<Vec<String>> {
+  let line = line.replace("[ ", "[");
+  let (index, rest) = line.trim().strip_prefix('[')?.split_once(']')?;
+  let mut tokens = rest.split_whitespace().collect::<Vec<_>>();
+
+  // readelf's link and info are not shown by elfprobe -S.
+  let align = tokens.pop()?;
+  if readelf {
+    tokens.truncate(tokens.len().checked_sub(2)?);
+  }
+  let is_flags = |token: &str| token.len() < 2 || !token.chars().all(|char| char.is_ascii_hexdigit());
+  let flags = match tokens.last() {
+    Some(token) if !token.starts_with("0x") && is_flags(token) => tokens.pop().unwrap_or_default(),
+    _ => "",
+  };
+
+  let numbers = tokens.split_off(tokens.len().checked_sub(4)?);
+  let section_type = tokens.pop()?;
+  let name = tokens.join(" ");
+
+  let mut fields = vec![canonical(index.trim(), false), name, alias(section_type)];
+  fields.extend(numbers.iter().map(|field| canonical(field, readelf)));
+  fields.push(String::from(flags));
+  fields.push(canonical(align, false));
+  Some(fields)
+}
+
+///
+/// Returns the fields of a program header row: the type, the five numbers,
+/// the flags (`R E`, spaces removed) and the alignment.
+///
+fn segment_row(line: &str) -> Option<Vec<String>> {
+  let tokens = line.split_whitespace().collect::<Vec<_>>();
+  if tokens.len() < 8 || number(tokens[1], false).is_none() {
+    return None;
+  }
+  let align = tokens.last()?;
+  let mut fields = vec![alias(tokens[0])];
+  fields.extend(tokens[1..6].iter().map(|field| canonical(field, false)));
+  fields.push(tokens[6..tokens.len() - 1].concat());
+  fields.push(canonical(align, false));
+  Some(fields)
+}
+
+///
+/// Normalizes the output of `readelf -W -h -S -l` or of `elfprobe -W -h -S
+/// -l` into records, the lines which are not records (titles, column
+/// headers, readelf's key to the flags and interpreter) are skipped.
+///
+fn normalize(output: &str, readelf: bool) -> Vec<Record> {
+  let mut part = None;
+  let mut records = Vec::new();
+  for line in output.lines() {
+    let trimmed = line.trim();
+    let next = match trimmed {
+      "ELF Header:" => Some(Part::Header),
+      "Section Headers:" => Some(Part::Sections),
+      "Program Headers:" => Some(Part::Segments),
+      "Section to Segment mapping:" => Some(Part::Mapping),
+      // Other titles (readelf's key to the flags, elfprobe's zero-filled memory).
+      _ if trimmed.ends_with(':') => None,
+      _ => {
+        let fields = match part {
+          _ if trimmed.is_empty() => None,
+          Some(Part::Header) => header_field(trimmed),
+          Some(Part::Sections) if trimmed.starts_with("[Nr]") => None,
+          Some(Part::Sections) => section_row(trimmed, readelf),
+          Some(Part::Segments) => segment_row(trimmed),
+          Some(Part::Mapping) if trimmed.starts_with("Segment") => None,
+          Some(Part::Mapping) => Some(trimmed.split_whitespace().map(String::from).collect()),
+          None => None,
+        };
+        if let (Some(part), Some(fields)) = (part, fields) {
+          records.push((part, fields));
+        }
+        continue;
+      }
+    };
+    part = next;
+  }
+  records
+}
+
+///
+/// Returns the records which differ, as a diff: `-` for readelf, `+` for
+/// elfprobe, empty when the outputs agree.
+///
+fn diff(readelf: &[Record], elfprobe: &[Record]) -> String {
+  let mut output = String::new();
+  for part in [Part::Header, Part::Sections, Part::Segments, Part::Mapping] {
+    let lines = |records: &[Record]| {
+      records
+        .iter()
+        .filter(|(record_part, _)| *record_part == part)
+        .map(|(_, fields)| fields.join(" | "))
+        .collect::<Vec<_>>()
+    };
+    let (expected, actual) = (lines(readelf), lines(elfprobe));
+    for index in 0..expected.len().max(actual.len()) {
+      match (expected.get(index), actual.get(index)) {
+        (Some(expected), Some(actual)) if expected == actual => (),
+        (expected, actual) => {
+          let _ = writeln!(output, "{:?} #{}", part, index);
+          if let Some(expected) = expected {
+            let _ = writeln!(output, "- {}", expected);
+          }
+          if let Some(actual) = actual {
+            let _ = writeln!(output, "+ {}", actual);
+          }
+        }
+      }
+    }
+  }
+  output
+}
+
+/// Runs the command and returns its standard output.
+fn run(program: impl AsRef<std::ffi::OsStr>, arguments: &[&str], path: &Path) -> String {
+  let output = Command::new(program)
+    .args(arguments)
+    .arg(path)
+    .env("NO_COLOR", "1")
+    .output()
+    .unwrap();
+  assert!(
+    output.status.success(),
+    "{}",
+    String::from_utf8_lossy(&output.stderr)
+  );
+  String::from_utf8(output.stdout).unwrap()
+}
+
+// ╔╦╗┌─┐┌─┐┌┬┐┌─┐
+//  ║ ├┤ └─┐ │ └─┐
+//  ╩ └─┘└─┘ ┴ └─┘
+
+/// The checked-in fixtures are the ones generated, rewritten with `ELFPROBE_BLESS=1`.
+#[test]
+fn fixtures_up_to_date() {
+  let bless = env::var_os("ELFPROBE_BLESS").is_some();
+  for (name, bytes) in fixtures() {
+    let path = fixtures_directory().join(name);
+    if bless {
+      fs::create_dir_all(fixtures_directory()).unwrap();
+      fs::write(&path, &bytes).unwrap();
+    }
+    let checked_in = fs::read(&path).unwrap_or_default();
+    assert!(
+      checked_in == bytes,
+      "{} is out of date, run with ELFPROBE_BLESS=1",
+      name
+    );
+  }
+}
+
+#[test]
+fn normalization() {
+  let readelf = concat!(
+    "ELF Header:\n",
+    "  Magic:   7f 45 4c 46 02 01 01 00 00 00 00 00 00 00 00 00 \n",
+    "  Version:                           1 (current)\n",
+    "  Machine:                           Advanced Micro Devices X86-64\n",
+    "  Entry point address:               0x23d0\n",
+    "  Start of section headers:          33680 (bytes into file)\n",
+    "\n",
+    "Section Headers:\n",
+    "  [Nr] Name              Type            Address          Off    Size   ES Flg Lk Inf Al\n",
+    "  [ 0]                   NULL            0000000000000000 000000 000000 00      0   0  0\n",
+    "  [ 8] .gnu.version      VERSYM          0000000000000b76 000b76 00006a 02   A  6   0  2\n",
+    "  [30] .shstrtab         STRTAB          0000000000000000 008260 00012f 00      0   0  1\n",
+    "Key to Flags:\n",
+    "  W (write), A (alloc), X (execute), M (merge), S (strings), I (info),\n",
+    "\n",
+    "Program Headers:\n",
+    "  Type           Offset   VirtAddr           PhysAddr           FileSiz  MemSiz   Flg Align\n",
+    "  INTERP         0x000318 0x0000000000000318 0x0000000000000318 0x00001c 0x00001c R   0x1\n",
+    "      [Requesting program interpreter: /lib64/ld-linux-x86-64.so.2]\n",
+    "  LOAD           0x002000 0x0000000000002000 0x0000000000002000 0x003d59 0x003d59 R E 0x1000\n",
+    "\n",
+    " Section to Segment mapping:\n",
+    "  Segment Sections...\n",
+    "   00     \n",
+    "   01     .interp \n",
+  );
+  let elfprobe = concat!(
+    "ELF Header:\n",
+    "Magic:                              7f 45 4c 46 02 01 01 00 00 00 00 00 00 00 00 00\n",
+    "Version:                            1\n",
+    "Machine:                            AMD x86-64 architecture\n",
+    "Entry point address:                0x00000000000023d0\n",
+    "Start of section headers:           33680 (bytes into file)\n",
+    "\n",
+    "Section Headers:\n",
+    "[Nr]  Name                Type                    Address  Offset    Size  EntSize  Flags  Align\n",
+    "[ 0]                      NULL         0x0000000000000000     0x0     0x0      0x0           0x0\n",
+    "[ 8]  .gnu.version        GNU_versym   0x0000000000000b76   0xb76    0x6a      0x2  A        0x2\n",
+    "[30]  .shstrtab           STRTAB       0x0000000000000000  0x8260   0x12f      0x0           0x1\n",
+    "\n",
+    "Program Headers:\n",
+    "Type          Offset              VirtAddr            PhysAddr            FileSiz             MemSiz              Flags  Align\n",
+    "INTERP        0x0000000000000318  0x0000000000000318  0x0000000000000318  0x000000000000001c  0x000000000000001c  R      0x1\n",
+    "LOAD          0x0000000000002000  0x0000000000002000  0x0000000000002000  0x0000000000003d59  0x0000000000003d59  R E    0x1000\n",
+    "\n",
+    "Section to Segment mapping:\n",
+    "Segment  Sections...\n",
+    "00\n",
+    "01       .interp\n",
+  );
+
+  let (expected, actual) = (normalize(readelf, true), normalize(elfprobe, false));
+  assert_eq!(expected.len(), 12);
+  assert_eq!(diff(&expected, &actual), "");
+  assert_eq!(
+    actual[6].1,
+    [
+      "0x8",
+      ".gnu.version",
+      "GNU_versym",
+      "0xb76",
+      "0xb76",
+      "0x6a",
+      "0x2",
+      "A",
+      "0x2"
+    ]
+  );
+  assert_eq!(actual[9].1[6], "RE");
+
+  let changed = elfprobe.replace("0x6a      0x2  A ", "0x6b      0x2  A ");
+  assert_eq!(
+    diff(&expected, &normalize(&changed, false)),
+    concat!(
+      "Sections #1\n",
+      "- 0x8 | .gnu.version | GNU_versym | 0xb76 | 0xb76 | 0x6a | 0x2 | A | 0x2\n",
+      "+ 0x8 | .gnu.version | GNU_versym | 0xb76 | 0xb76 | 0x6b | 0x2 | A | 0x2\n",
+    )
+  );
+}
+
+/// elfprobe agrees with readelf on the fixtures, run when `READELF` names the readelf to compare with.
+#[test]
+fn readelf_golden() {
+  let Some(readelf) = env::var_os("READELF") else {
+    eprintln!("READELF is not set, the comparison with readelf is skipped");
+    return;
+  };
+
+  let mut failures = String::new();
+  for (name, _) in fixtures() {
+    let path = fixtures_directory().join(name);
+    let expected = normalize(&run(&readelf, &["-W", "-h", "-S", "-l"], &path), true);
+    let actual = normalize(
+      &run(
+        env!("CARGO_BIN_EXE_elfprobe-core"),
+        &["-W", "-h", "-S", "-l"],
+        &path,
+      ),
+      false,
+    );
+    assert!(!expected.is_empty(), "{}: nothing read from readelf", name);
+
+    let diff = diff(&expected, &actual);
+    if !diff.is_empty() {
+      let _ = write!(failures, "{}:\n{}\n", name, diff);
+    }
+  }
+  assert!(
+    failures.is_empty(),
+    "elfprobe differs from readelf\n\n{}",
+    failures
+  );
+}
@@ -24,7 +24,7 @@ impl From<TokenStream> for Buffer {
 
 impl Buffer {
   #[inline(always)]
-  pub(crate) fn cursor(&self) -> Cursor {
+  pub(crate) fn cursor(&self) -> Cursor<'_> {
     Cursor::from(self)
   }
 
@@ -14,7 +14,9 @@ use crate::rules::StructType;
 ///
 /// ```txt
 /// pub struct #GENERICS Dada {...} where #WHERE;
-/// impl #IMPL_GENERICS #TRAIT for Dada #TYPE_GENERICS where #WHERE {}
+/// impl #IMPL_GENERICS #TRAIT for Dada #TYPE_GENERICS where #WHERE {
+///   const NO_PADDING: () = assert!(size_of::<Self>() == 0 + size_of::<A>() + ...);
+/// }
 /// ```
 ///
 pub(crate) fn derive(stream: TokenStream, r#trait: &str) -> TokenStream {
@@ -46,8 +48,11 @@ pub(crate) fn derive(stream: TokenStream, r#trait: &str) -> TokenStream {
   // A. Build the TokenStream.
   let mut derive = TokenStream::new();
 
-  // B. "impl" keyworkd
-  derive.extend([TokenTree::from(Identifier::new("impl", Span::call_site()))]);
+  // B. "unsafe impl" keywords
+  derive.extend([
+    TokenTree::from(Identifier::new("unsafe", Span::call_site())),
+    TokenTree::from(Identifier::new("impl", Span::call_site())),
+  ]);
 
   // C. Structure generics
   if let Some(r#struct) = &r#struct {
@@ -75,8 +80,114 @@ pub(crate) fn derive(stream: TokenStream, r#trait: &str) -> TokenStream {
     derive.extend(r#struct.collect_where_clause());
   }
 
-  // I. Brace group
-  derive.extend([TokenTree::from(Group::new(Delimiter::Brace, TokenStream::new()))]);
+  // I. Brace group, the size of the structure must be the sum of the sizes
+  // of its fields (no padding bytes). The assertion is evaluated when the
+  // methods of the trait are instantiated, at once below without generics.
+  let mut body = TokenStream::new();
+  if let Some(r#struct) = &r#struct {
+    let mut sum = TokenStream::from_str("::core::mem::size_of::<Self>() == 0").unwrap();
+    for field in r#struct.collect_fields() {
+      sum.extend(TokenStream::from_str("+ ::core::mem::size_of::<"));
+      sum.extend(field);
+      sum.extend(TokenStream::from_str(">()"));
+    }
+    sum.extend(TokenStream::from_str(
+      ", \"#[derive(Pod)] on a type with padding bytes\"",
+    ));
+
+    body.extend(TokenStream::from_str("const NO_PADDING: () = assert!"));
+    body.extend([TokenTree::from(Group::new(Delimiter::Parenthesis, sum))]);
+    body.extend(TokenStream::from_str(";"));
+  }
+  derive.extend([TokenTree::from(Group::new(Delimiter::Brace, body))]);
+
+  if let Some(r#struct) = r#struct
+    .as_ref()
+    .filter(|r#struct| r#struct.collect_impl().is_empty())
+  {
+    derive.extend(TokenStream::from_str("const _: () = <"));
+    derive.extend([TokenTree::from(r#struct.name().clone())]);
+    derive.extend(TokenStream::from_str(&format!(" as {}>::NO_PADDING;", r#trait)));
+  }
+
+  // J. Field checks, the field types keep their spans for the error messages.
+  if let Some(r#struct) = &r#struct {
+    let mut checks = TokenStream::from_str(&format!("const fn assert<Field: {}>() {{}}", r#trait)).unwrap();
+    for field in r#struct.collect_fields() {
+      checks.extend(TokenStream::from_str("assert::<"));
+      checks.extend(field);
+      checks.extend(TokenStream::from_str(">();"));
+    }
+
+    let mut function =
+      TokenStream::from_str("#[doc(hidden)] #[allow(dead_code)] const fn __assert_fields()").unwrap();
+    function.extend([TokenTree::from(Group::new(Delimiter::Brace, checks))]);
+
+    derive.extend([TokenTree::from(Identifier::new("impl", Span::call_site()))]);
+    derive.extend(r#struct.collect_impl());
+    derive.extend([TokenTree::from(r#struct.name().clone())]);
+    derive.extend(r#struct.collect_types());
+    derive.extend(r#struct.collect_where_clause());
+    derive.extend([TokenTree::from(Group::new(Delimiter::Brace, function))]);
+  }
+
+  derive
+}
+
+///
+/// ```txt
+/// impl #IMPL_GENERICS #MODULE::FieldLayout for Dada #TYPE_GENERICS where #WHERE {
+///   const FIELDS: &'static [#MODULE::Field] = &[
+///     #MODULE::Field { name: "a", offset: offset_of!(Self, a), size: size_of::<A>() },
+///   ];
+/// }
+/// ```
+///
+pub(crate) fn field_layout(stream: TokenStream, module: &str) -> TokenStream {
+  let buffer = Buffer::from(stream);
+  let cursor = buffer.cursor();
+
+  let Some(r#struct) = StructType::parse(&cursor) else {
+    panic!("Could not parse the given structure (enumerations are not supported yet).");
+  };
+  if !cursor.is_end() {
+    panic!("Expected the end of the token stream.")
+  }
+
+  // One `Field` per field, the types keep their spans for the error messages.
+  let mut fields = TokenStream::new();
+  for (name, r#type) in r#struct
+    .collect_field_names()
+    .into_iter()
+    .zip(r#struct.collect_fields())
+  {
+    let mut field = TokenStream::from_str(&format!(
+      "name: {:?}, offset: ::core::mem::offset_of!(Self, {}), size: ::core::mem::size_of::<",
+      name.trim_start_matches("r#"),
+      name
+    ))
+    .unwrap();
+    field.extend(r#type);
+    field.extend(TokenStream::from_str(">()"));
+
+    fields.extend(TokenStream::from_str(&format!("{}::Field", module)));
+    fields.extend([TokenTree::from(Group::new(Delimiter::Brace, field))]);
+    fields.extend(TokenStream::from_str(","));
+  }
+
+  let mut constant =
+    TokenStream::from_str(&format!("const FIELDS: &'static [{}::Field] = &", module)).unwrap();
+  constant.extend([TokenTree::from(Group::new(Delimiter::Bracket, fields))]);
+  constant.extend(TokenStream::from_str(";"));
 
+  let mut derive = TokenStream::new();
+  derive.extend([TokenTree::from(Identifier::new("impl", Span::call_site()))]);
+  derive.extend(r#struct.collect_impl());
+  derive.extend(TokenStream::from_str(&format!("{}::FieldLayout", module)));
+  derive.extend([TokenTree::from(Identifier::new("for", Span::call_site()))]);
+  derive.extend([TokenTree::from(r#struct.name().clone())]);
+  derive.extend(r#struct.collect_types());
+  derive.extend(r#struct.collect_where_clause());
+  derive.extend([TokenTree::from(Group::new(Delimiter::Brace, constant))]);
   derive
 }
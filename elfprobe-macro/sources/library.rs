@@ -11,7 +11,52 @@ mod parser;
 mod rules;
 mod token;
 
+///
+/// Implements `crate::pod::Pod` once the fields are checked to be POD types
+/// and to leave no padding bytes, the padding must be explicit:
+///
+/// ```
+/// # mod pod {
+/// #   pub unsafe trait Pod { const NO_PADDING: () = (); }
+/// #   unsafe impl Pod for u8 {}
+/// #   unsafe impl Pod for u64 {}
+/// #   unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+/// # }
+/// use elfprobe_macro::Pod;
+///
+/// #[repr(C)]
+/// #[derive(Copy, Clone, Pod)]
+/// struct Padded {
+///   a: u8,
+///   _pad: [u8; 7],
+///   b: u64,
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ```compile_fail,E0080
+/// # mod pod {
+/// #   pub unsafe trait Pod { const NO_PADDING: () = (); }
+/// #   unsafe impl Pod for u8 {}
+/// #   unsafe impl Pod for u64 {}
+/// # }
+/// use elfprobe_macro::Pod;
+///
+/// #[repr(C)]
+/// #[derive(Copy, Clone, Pod)]
+/// struct Padded {
+///   a: u8,
+///   b: u64,
+/// }
+/// # fn main() {}
+/// ```
+///
 #[proc_macro_derive(Pod)]
 pub fn pod_derive(input: TokenStream) -> TokenStream {
   crate::derive::derive(input, "crate::pod::Pod")
 }
+
+#[proc_macro_derive(FieldLayout)]
+pub fn field_layout_derive(input: TokenStream) -> TokenStream {
+  crate::derive::field_layout(input, "crate::pod")
+}
@@ -1,5 +1,7 @@
 use proc_macro::TokenTree;
 
+use crate::entry::Delimiter;
+use crate::entry::Group;
 use crate::entry::Identifier;
 use crate::parser::parser;
 use crate::parser::Collect;
@@ -99,6 +101,126 @@ impl StructType {
     }
     tree
   }
+
+  /// Returns the type of each field,
+  /// e.g., `{ a: u8, pub(crate) b: Foo<A, B> }` gives `[u8], [Foo<A, B>]`.
+  pub(crate) fn collect_fields(&self) -> Vec<Vec<TokenTree>> {
+    self
+      .split_fields()
+      .map(|(fields, named)| fields.into_iter().map(|field| field_type(field, named)).collect())
+      .unwrap_or_default()
+  }
+
+  /// Returns the name of each field, the index for tuple structures,
+  /// e.g., `{ a: u8, pub(crate) b: Foo<A, B> }` gives `a, b`.
+  pub(crate) fn collect_field_names(&self) -> Vec<String> {
+    let Some((fields, named)) = self.split_fields() else {
+      return Vec::new();
+    };
+    match named {
+      true => fields
+        .iter()
+        .filter_map(|field| field.get(field_start(field)).map(ToString::to_string))
+        .collect(),
+      false => (0..fields.len()).map(|index| index.to_string()).collect(),
+    }
+  }
+
+  /// Returns the tokens of each field and whether they are named, `None` for unit structures.
+  fn split_fields(&self) -> Option<(Vec<Vec<TokenTree>>, bool)> {
+    match &self.tree.2 {
+      Union::A(struct_struct) => match &struct_struct.tree.4 {
+        Union::A(brace) => Some((split_fields(&brace.token), true)),
+        _ => None, // Unit structure.
+      },
+      Union::B(tuple_struct) => Some((split_fields(&tuple_struct.tree.3.token), false)),
+      _ => unreachable!(),
+    }
+  }
+}
+
+///
+/// Fields are not parsed by the rules (the group is opaque), they are split at
+/// the top-level commas.
+///
+/// Angle brackets are punctuations and not groups, e.g., `Foo<A, B>`, hence
+/// the depth tracking (`->` in function pointers does not close anything).
+///
+fn split_fields(group: &Group) -> Vec<Vec<TokenTree>> {
+  let mut fields = Vec::new();
+  let mut field = Vec::new();
+  let mut depth = 0usize;
+  let mut previous = None;
+
+  for token in group.stream() {
+    let punctuation = match &token {
+      TokenTree::Punct(punctuation) => Some(punctuation.as_char()),
+      _ => None,
+    };
+    match punctuation {
+      Some(',') if depth == 0 => {
+        fields.push(std::mem::take(&mut field));
+        previous = None;
+        continue;
+      }
+      Some('<') => depth += 1,
+      Some('>') if previous != Some('-') => depth = depth.saturating_sub(1),
+      _ => (),
+    }
+    previous = punctuation;
+    field.push(token);
+  }
+
+  // Trailing comma or not.
+  if !field.is_empty() {
+    fields.push(field);
+  }
+
+  fields
+}
+
+/// Returns the type of a field, e.g., `#[doc = ""] pub(crate) a: u8` gives `u8`.
+fn field_type(field: Vec<TokenTree>, named: bool) -> Vec<TokenTree> {
+  let mut start = field_start(&field);
+
+  // Name and colon.
+  if named {
+    start += 2;
+  }
+
+  field.into_iter().skip(start).collect()
+}
+
+/// Returns the index of the name (the type of tuple fields), past the attributes and visibility.
+fn field_start(field: &[TokenTree]) -> usize {
+  let mut start = 0;
+
+  // Outer attributes.
+  while let (Some(TokenTree::Punct(hash)), Some(TokenTree::Group(group))) =
+    (field.get(start), field.get(start + 1))
+  {
+    if hash.as_char() != '#' || group.delimiter() != Delimiter::Bracket {
+      break;
+    }
+    start += 2;
+  }
+
+  // Visibility, `pub (u8, u16)` is a public tuple though.
+  if let Some(TokenTree::Ident(r#pub)) = field.get(start) {
+    if r#pub.to_string() == "pub" {
+      start += 1;
+      if let Some(TokenTree::Group(group)) = field.get(start) {
+        let restriction = group.stream().into_iter().next().map(|token| token.to_string());
+        if group.delimiter() == Delimiter::Parenthesis
+          && matches!(restriction.as_deref(), Some("crate" | "self" | "super" | "in"))
+        {
+          start += 1;
+        }
+      }
+    }
+  }
+
+  start
 }
 
 impl StructStruct {
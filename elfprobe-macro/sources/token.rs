@@ -16,6 +16,7 @@ macro_rules! create_token {
     struct $name: ident($token: ident) when
       token.$method: ident() is $expr1: expr $(, but $expr2: expr)?
   ) => {
+    #[allow(unused)] // Not all tokens are used by the rules (yet).
     pub(crate) struct $name {
       // Store a Span instead?
       // Ident::span(), Ident::set_span(), Ident::new()
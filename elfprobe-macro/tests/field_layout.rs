@@ -0,0 +1,94 @@
+mod pod {
+  /// Mirrors `elfprobe_core::pod::Field`.
+  #[derive(Debug, PartialEq, Eq)]
+  pub struct Field {
+    pub name: &'static str,
+    pub offset: usize,
+    pub size: usize,
+  }
+
+  /// Mirrors `elfprobe_core::pod::FieldLayout`.
+  pub trait FieldLayout {
+    const FIELDS: &'static [Field];
+  }
+
+  /// Returns the names, offsets and sizes of the fields.
+  pub fn fields<T: FieldLayout>() -> Vec<(&'static str, usize, usize)> {
+    T::FIELDS
+      .iter()
+      .map(|field| (field.name, field.offset, field.size))
+      .collect()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::pod::{self, Field, FieldLayout};
+  use elfprobe_macro::FieldLayout;
+  use std::marker::PhantomData;
+
+  #[test]
+  fn unit_struct() {
+    #[derive(FieldLayout)]
+    struct Dada;
+    assert_eq!(Dada::FIELDS, []);
+  }
+
+  #[test]
+  fn struct_fields() {
+    #[allow(unused)]
+    #[derive(FieldLayout)]
+    #[repr(C)]
+    struct Dada {
+      #[doc = "a, b"]
+      pub(crate) a: u8,
+      pub b: [u8; 3],
+      r#type: u32,
+      d: PhantomData<(u8, u16)>,
+    }
+
+    assert_eq!(
+      pod::fields::<Dada>(),
+      [("a", 0, 1), ("b", 1, 3), ("type", 4, 4), ("d", 8, 0)]
+    );
+    assert_eq!(
+      Dada::FIELDS[0],
+      Field {
+        name: "a",
+        offset: 0,
+        size: 1
+      }
+    );
+  }
+
+  #[test]
+  fn tuple_struct_fields() {
+    #[allow(unused)]
+    #[derive(FieldLayout)]
+    #[repr(C)]
+    pub(super) struct Dada(u16, pub u16, u32);
+
+    assert_eq!(pod::fields::<Dada>(), [("0", 0, 2), ("1", 2, 2), ("2", 4, 4)]);
+  }
+
+  #[test]
+  fn generic_fields() {
+    trait Types {
+      type Word;
+    }
+    struct Wide;
+    impl Types for Wide {
+      type Word = u64;
+    }
+
+    #[allow(unused)]
+    #[derive(FieldLayout)]
+    #[repr(C)]
+    struct Dada<T: Types> {
+      name: [u8; 8],
+      word: T::Word,
+    }
+
+    assert_eq!(pod::fields::<Dada<Wide>>(), [("name", 0, 8), ("word", 8, 8)]);
+  }
+}
@@ -1,12 +1,38 @@
 // #![allow(unused)]
 
+// The following structures deliberately exercise unusual syntax.
+#![allow(clippy::needless_pub_self)]
+#![allow(clippy::default_constructed_unit_structs)]
+#![allow(clippy::repr_packed_without_abi)]
+
 trait Fafa {}
 trait Gaga {}
 trait Haha {}
 
 mod pod {
-  pub trait Pod {}
-  pub fn test(_: impl Pod) {}
+  use std::marker::PhantomData;
+
+  /// # Safety
+  ///
+  /// Mirrors `elfprobe_core::pod::Pod`, implemented by POD types only.
+  pub unsafe trait Pod {
+    const NO_PADDING: () = ();
+  }
+
+  /// Instantiates the padding check, as the methods of the actual trait.
+  pub fn test<T: Pod>(_: T) {
+    let () = T::NO_PADDING;
+  }
+
+  macro_rules! impl_pod {
+    ($($type: ident),*) => {
+      $(unsafe impl Pod for $type {})*
+    };
+  }
+
+  impl_pod!(u8, u16, u32, u64, u128, usize);
+  impl_pod!(i8, i16, i32, i64, i128, isize);
+  unsafe impl<T: ?Sized> Pod for PhantomData<T> {}
 }
 
 pub mod a {
@@ -85,6 +111,7 @@ mod tests {
         c: u32,
         d: u16,
         e: u8,
+        f: u8,
       }
       pod::test(Dada::default());
     }
@@ -93,7 +120,31 @@ mod tests {
     fn tuple_struct_parameters() {
       #[allow(unused)]
       #[derive(Pod, Default)]
-      pub(super) struct Dada(u128, u64, u32, u16, u8);
+      pub(super) struct Dada(u128, u64, u32, u16, u8, u8);
+      pod::test(Dada::default());
+    }
+
+    #[test]
+    fn field_prefixes() {
+      #[allow(unused)]
+      #[derive(Pod, Default)]
+      struct Dada {
+        #[doc = "a, b"]
+        pub(crate) a: PhantomData<(u8, u16)>,
+        pub b: PhantomData<fn(u8, u16) -> u32>,
+        r#c: u8,
+      }
+      pod::test(Dada::default());
+    }
+
+    #[test]
+    fn tuple_field_prefixes() {
+      #[allow(unused)]
+      #[derive(Pod, Default)]
+      struct Dada(
+        #[doc = "a, b"] pub(super) PhantomData<(u8, u16)>,
+        pub PhantomData<(u32,)>,
+      );
       pod::test(Dada::default());
     }
 
@@ -101,7 +152,7 @@ mod tests {
     fn empty_generics() {
       #[allow(unused)]
       #[derive(Pod, Default)]
-      pub(super) struct Dada(Option<()>);
+      pub(super) struct Dada(PhantomData<()>);
       pod::test(Dada::default());
     }
   }
@@ -249,14 +300,14 @@ mod tests {
     #[test]
     fn one_trait() {
       #[derive(Pod)]
-      pub(self) struct Dada<A>(A);
-      pod::test(Dada(1u8));
+      pub(self) struct Dada<A>(PhantomData<A>);
+      pod::test(Dada(PhantomData::<u8>));
     }
 
     #[test]
     fn one_trait_bound() {
       #[derive(Pod, Default)]
-      pub(super) struct Dada<A: Fafa>(A);
+      pub(super) struct Dada<A: Fafa + pod::Pod>(A);
       pod::test(Dada(1u16));
     }
 
@@ -264,7 +315,7 @@ mod tests {
     fn two_traits() {
       #[allow(unused)]
       #[derive(Pod, Default)]
-      pub(crate) struct Dada<A, B> {
+      pub(crate) struct Dada<A: pod::Pod, B: pod::Pod> {
         a: A,
         b: B,
       }
@@ -275,7 +326,7 @@ mod tests {
     fn two_traits_bound() {
       #[allow(unused)]
       #[derive(Pod, Default)]
-      pub(crate) struct Dada<A, B: Fafa> {
+      pub(crate) struct Dada<A: pod::Pod, B: Fafa + pod::Pod> {
         a: A,
         b: B,
       }
@@ -287,7 +338,7 @@ mod tests {
       #[rustfmt::skip]
       #[allow(unused)]
       #[derive(Pod, Default)]
-      pub struct Dada<A, B: Haha + Fafa +, C: Fafa + Gaga,> {
+      pub struct Dada<A: pod::Pod, B: Haha + Fafa + pod::Pod +, C: Fafa + Gaga + pod::Pod,> {
         a: A,
         b: B,
         c: C,
@@ -299,16 +350,16 @@ mod tests {
     fn trailing_colon() {
       #[rustfmt::skip]
       #[derive(Pod, Default)]
-      pub struct Dada<A:>(A);
-      pod::test(Dada(1i8));
+      pub struct Dada<A:>(PhantomData<A>);
+      pod::test(Dada(PhantomData::<i8>));
     }
 
     #[test]
     fn trailing_comma() {
       #[rustfmt::skip]
       #[derive(Pod, Default)]
-      pub struct Dada<A,>(A);
-      pod::test(Dada(1i32));
+      pub struct Dada<A,>(PhantomData<A>);
+      pod::test(Dada(PhantomData::<i32>));
     }
 
     #[test]
@@ -316,11 +367,14 @@ mod tests {
       #[rustfmt::skip]
       #[allow(unused)]
       #[derive(Pod, Default)]
-      pub struct Dada<A:, B: Fafa +>{
-        a: A,
+      pub struct Dada<A:, B: Fafa + pod::Pod +>{
+        a: PhantomData<A>,
         b: B,
       }
-      pod::test(Dada { a: 0x1u32, b: 0b1i32 });
+      pod::test(Dada {
+        a: PhantomData::<u32>,
+        b: 0b1i32,
+      });
     }
   }
 
@@ -331,30 +385,30 @@ mod tests {
     fn one_segment() {
       #[derive(Pod)]
       #[rustfmt::skip]
-      pub(in self) struct Dada<A: a::A +,>(A);
+      pub(in self) struct Dada<A: a::A + pod::Pod +,>(A);
       pod::test(Dada(1u8));
     }
 
     #[test]
     fn two_segments() {
       #[derive(Pod)]
-      pub(in super::path) struct Dada<A: a::b::B>(A);
+      pub(in super::path) struct Dada<A: a::b::B + pod::Pod>(A);
       pod::test(Dada(1u8));
     }
 
     #[test]
     fn multiple_segments() {
       #[derive(Pod)]
-      pub(self) struct Dada<A: a::b::B + a::A + a::Foo, B: a::b::c::d::D>(A, B);
-      pod::test(Dada(1u8, 2i16));
+      pub(self) struct Dada<A: a::b::B + a::A + a::Foo + pod::Pod, B: a::b::c::d::D + pod::Pod>(A, B);
+      pod::test(Dada(1u16, 2i16));
     }
 
     #[test]
     fn trailing_punctuations() {
       #[derive(Pod)]
       #[rustfmt::skip]
-      pub struct Dada<A:, B: a::b::B + , C: a::A + a::b::c::C +, >(A, B, C);
-      pod::test(Dada(1u8, 2i16, 1u8));
+      pub struct Dada<A:, B: a::b::B + , C: a::A + a::b::c::C +, >(PhantomData<A>, PhantomData<(B, C)>);
+      pod::test(Dada(PhantomData::<u8>, PhantomData::<(i16, u8)>));
     }
   }
 
@@ -367,7 +421,7 @@ mod tests {
       #[allow(dead_code)]
       pub(super) struct Albator<A>
       where
-        A: a::A + Default,
+        A: a::A + Default + pod::Pod,
       {
         a: A,
       }
@@ -380,9 +434,9 @@ mod tests {
       #[allow(dead_code)]
       pub(super) struct Goldorak<A, B>(A, B)
       where
-        A: a::A,
-        B: a::b::B + self::Fafa + a::Foo;
-      pod::test(Goldorak(1u8, 2i16));
+        A: a::A + pod::Pod,
+        B: a::b::B + self::Fafa + a::Foo + pod::Pod;
+      pod::test(Goldorak(1u16, 2i16));
     }
   }
 
@@ -402,14 +456,14 @@ mod tests {
         'b: 'static + 'a + 'static,
         A: 'static + self::Fafa + 'a + 'b + Fafa + a::b::c::d::D + crate::Fafa,
         B: self::Gaga + crate::a::b::B,
-        C,
-        D: Here + 'b + Haha,
+        C: pod::Pod,
+        D: Here + 'b + Haha + pod::Pod,
         E,
         F,
       >
       where
         E: 'a + a::b::B + 'static,
-        F: Default,
+        F: Default + pod::Pod,
       {
         a: PhantomData<&'a B>,
         b: PhantomData<&'b A>,